@@ -0,0 +1,81 @@
+use crate::{
+    complex::NumStr,
+    error::Error,
+    expr::Expr,
+    load_vars::{get_vars, is_var_name, set_var},
+    options::set_commands,
+    parse::input_expr,
+    print::answer_to_string,
+    units::{Colors, Data, Options, Variable},
+};
+#[derive(Clone, Debug)]
+pub struct Calculator {
+    pub vars: Vec<Variable>,
+    pub options: Options,
+    pub colors: Colors,
+}
+impl Default for Calculator {
+    fn default() -> Self {
+        Self::new(Options::default())
+    }
+}
+impl From<Data> for Calculator {
+    fn from(value: Data) -> Self {
+        Self {
+            vars: value.vars,
+            options: value.options,
+            colors: value.colors,
+        }
+    }
+}
+impl Calculator {
+    pub fn new(options: Options) -> Self {
+        Self {
+            vars: if options.allow_vars {
+                get_vars(options)
+            } else {
+                Vec::new()
+            },
+            options,
+            colors: Colors::default(),
+        }
+    }
+    #[allow(clippy::type_complexity)]
+    pub fn parse(&self, input: &str) -> Result<(Expr, Vec<(String, Vec<NumStr>)>), Error> {
+        let (expr, func_vars, define) = input_expr(input, &self.vars, self.options)?;
+        if define {
+            return Err(Error::Parse("use define for assignments", None));
+        }
+        Ok((expr, func_vars))
+    }
+    pub fn eval(&self, input: &str) -> Result<NumStr, Error> {
+        let (expr, func_vars) = self.parse(input)?;
+        expr.eval(self.options, &func_vars)
+    }
+    pub fn define(&mut self, input: &str) -> Result<(), Error> {
+        let Some((l, r)) = input.split_once('=') else {
+            return Err(Error::Parse("no '=' in definition", None));
+        };
+        let l = l.replace(' ', "");
+        if !is_var_name(&l) {
+            return Err(Error::Parse("invalid variable name", None));
+        }
+        set_var(l.chars().collect(), r, &mut self.vars, self.options)
+    }
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        match set_commands(
+            &mut self.options,
+            &mut self.colors,
+            &mut self.vars,
+            name,
+            value,
+        ) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(Error::Setting("unknown option", None)),
+            Err(s) => Err(s),
+        }
+    }
+    pub fn format(&self, value: &NumStr) -> String {
+        answer_to_string(value.clone(), self.options, &self.colors)
+    }
+}
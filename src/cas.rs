@@ -1,15 +1,20 @@
 use crate::complex::NumStr::Vector;
-use crate::complex::{NumStr, cubic, quadratic, quartic, unity};
+use crate::complex::{NumStr, cubic, polyroots, pow_nth, quadratic, quartic, unity};
+use crate::types::float::{Constant, Special::Nan};
+use crate::types::{Complex, Exact, Float, Integer, Precision, Rational, fits, ops::Pow};
 use crate::{
     complex::NumStr::{
-        Division, Exponent, Func, InternalMultiplication, LeftBracket, LeftCurlyBracket, Minus,
-        Multiplication, Num, Plus, RightBracket, RightCurlyBracket,
+        Comma, Division, Exponent, Func, InternalMultiplication, LeftBracket, LeftCurlyBracket,
+        Minus, Multiplication, Num, Plus, PlusMinus, RightBracket, RightCurlyBracket,
     },
+    error::Error,
+    expr::{Expr, inline, is_lazy, level},
     math::do_math,
-    units::{Number, Options},
+    units::{
+        AngleType::{Degrees, Gradians, Radians},
+        Number, Options,
+    },
 };
-use rug::float::Constant;
-use rug::{Complex, Float};
 use std::cmp::Ordering;
 use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
 #[derive(Clone, Default)]
@@ -22,8 +27,11 @@ fn mul(mut lhs: Vec<Complex>, rhs: Complex) -> Vec<Complex> {
     lhs
 }
 fn mul_assign(lhs: &mut Vec<Complex>, rhs: &[Complex]) {
-    let v =
-        vec![Complex::new(rhs.first().map(|a| a.prec().0).unwrap_or(1)); rhs.len() + lhs.len() - 1];
+    let v = vec![
+        rhs.first()
+            .map_or_else(Complex::default, |a| Complex::new(a.prec()));
+        rhs.len() + lhs.len() - 1
+    ];
     let q = std::mem::replace(lhs, v);
     for (j, b) in rhs.iter().enumerate() {
         if b.is_zero() {
@@ -140,13 +148,6 @@ impl From<(Vec<Complex>, Vec<Complex>)> for Polynomial {
     }
 }
 impl Polynomial {
-    fn new(prec: u32) -> Self {
-        let divisor = vec![Complex::with_val(prec, 1)];
-        Self {
-            quotient: Vec::new(),
-            divisor,
-        }
-    }
     fn recip(self) -> Self {
         Polynomial {
             quotient: self.divisor,
@@ -168,9 +169,9 @@ impl Polynomial {
         }
         (last_non_zero(&self.quotient), last_non_zero(&self.divisor))
     }
-    fn div_checked(mut self) -> Result<(Vec<Complex>, Option<Vec<Complex>>), &'static str> {
+    fn div_checked(mut self) -> Result<(Vec<Complex>, Option<Vec<Complex>>), Error> {
         let (d_rem, Some(d_div)) = self.degree() else {
-            return Err("zero divisor");
+            return Err(Error::Domain("zero divisor", None));
         };
         if d_div == 0 {
             return Ok((self.quotient, None));
@@ -200,7 +201,7 @@ impl Polynomial {
         }
         Ok((self.divisor, Some(self.quotient)))
     }
-    fn gcd(mut self) -> Result<Vec<Complex>, &'static str> {
+    fn gcd(mut self) -> Result<Vec<Complex>, Error> {
         let mut d = self.divisor.clone();
         while let Ok((_, Some(r))) = std::mem::take(&mut self).div_checked() {
             self.quotient = d;
@@ -209,497 +210,3076 @@ impl Polynomial {
         }
         Ok(d)
     }
-    fn compute(mut self) -> Result<Vec<Complex>, &'static str> {
+    fn compute(mut self) -> Result<Vec<Complex>, Error> {
         self.divisor = self.clone().gcd()?;
         Ok(self.div_checked()?.0)
     }
-    fn get_polynomial(
-        func: &[NumStr],
-        options: &Options,
-        var: &[NumStr],
-    ) -> Result<Self, &'static str> {
-        if is_interior(func) {
-            return Self::get_polynomial(&func[1..func.len() - 1], options, var);
-        }
-        let mut arr = Polynomial::new(options.prec);
-        if is_constant(func, var) {
-            arr.quotient
-                .push(do_math(func.to_vec(), *options, Vec::new())?.num()?.number);
-            return Ok(arr);
-        }
-        if func == var {
-            arr.quotient.push(Complex::new(options.prec));
-            arr.quotient.push(Complex::with_val(options.prec, 1));
-            return Ok(arr);
-        }
-        let list = place(func, &Plus, false);
-        let is_empty = list.is_empty();
-        for p in list {
-            poly_add(options, var, &mut arr, p)?;
-        }
-        if !is_empty {
-            return Ok(arr);
-        }
-        let list = place(func, &Minus, false);
-        let is_empty = list.is_empty();
-        for (k, p) in list.into_iter().enumerate() {
-            if k == 0 {
-                arr = Self::get_polynomial(p, options, var)?;
-                continue;
-            }
-            if is_constant(p, var) {
-                arr -= do_math(p.to_vec(), *options, Vec::new())?.num()?.number
-            } else {
-                let q = Self::get_polynomial(p, options, var)?;
-                arr -= q;
-            }
+}
+fn branches(f: &str, r: Complex, n: &Complex, options: &Options) -> Option<Vec<Complex>> {
+    let prec = options.precision();
+    let k = value(&radians(*options))?.clone();
+    let half = Complex::with_val(prec, Constant::Pi) / &k;
+    let turn = || half.clone() * 2 * n;
+    let tau = Complex::with_val(prec, (0, Constant::Pi)) * 2 * n;
+    let within = |v: Complex, back: Complex| {
+        if near(&back, &r) { vec![v] } else { Vec::new() }
+    };
+    Some(match f {
+        "sin" => {
+            let a = r.asin() / &k;
+            vec![a.clone() + turn(), half.clone() - a + turn()]
         }
-        if !is_empty {
-            return Ok(arr);
+        "cos" => {
+            let a = r.acos() / &k;
+            vec![a.clone() + turn(), -a + turn()]
         }
-        let list = place(func, &Multiplication, false);
-        let is_empty = list.is_empty();
-        if !is_empty {
-            arr.quotient.push(Complex::with_val(options.prec, 1));
+        "tan" => vec![r.atan() / &k + half * n],
+        "cot" if r.is_zero() => vec![half.clone() / 2 + half * n],
+        "cot" => vec![r.recip().atan() / &k + half * n],
+        "sec" | "csc" if r.is_zero() => Vec::new(),
+        "sec" => branches("cos", r.recip(), n, options)?,
+        "csc" => branches("sin", r.recip(), n, options)?,
+        "sinh" => {
+            let a = r.asinh();
+            let pi = Complex::with_val(prec, (0, Constant::Pi));
+            vec![a.clone() + &tau, pi - a + &tau]
         }
-        for p in list {
-            poly_mul(options, var, &mut arr, p)?;
+        "cosh" => {
+            let a = r.acosh();
+            vec![a.clone() + &tau, -a + &tau]
         }
-        if !is_empty {
-            return Ok(arr);
+        "tanh" => vec![r.atanh() + tau / 2],
+        "exp" | "aln" if r.is_zero() => Vec::new(),
+        "exp" | "aln" => vec![r.ln() + tau],
+        "ln" | "aexp" | "log" => {
+            let v = r.clone().exp();
+            within(v.clone(), v.ln())
         }
-        let list = place(func, &Division, false);
-        let is_empty = list.is_empty();
-        for (k, p) in list.into_iter().enumerate() {
-            if k == 0 {
-                arr = Self::get_polynomial(p, options, var)?;
-                continue;
+        "sqrt" | "asquare" => {
+            let v = r.clone().square();
+            within(v.clone(), v.sqrt())
+        }
+        "cbrt" | "acube" => {
+            let v: Complex = r.clone().pow(3);
+            let back = if v.imag().is_zero() {
+                Complex::with_val(prec, v.real().clone().cbrt())
+            } else {
+                pow_nth(v.clone(), Complex::with_val(prec, 3).recip())
+            };
+            within(v, back)
+        }
+        "abs" if r.imag().is_zero() && !r.real().is_sign_negative() => vec![r.clone(), -r],
+        "abs" => Vec::new(),
+        "square" | "asqrt" => unity(r, Complex::with_val(prec, 2))
+            .into_iter()
+            .map(|a| a.number)
+            .collect(),
+        "cube" | "acbrt" => unity(r, Complex::with_val(prec, 3))
+            .into_iter()
+            .map(|a| a.number)
+            .collect(),
+        "recip" if r.is_zero() => Vec::new(),
+        "recip" => vec![r.recip()],
+        "asin" | "arcsin" => {
+            let v = (r.clone() * &k).sin();
+            within(v.clone(), v.asin() / &k)
+        }
+        "acos" | "arccos" => {
+            let v = (r.clone() * &k).cos();
+            within(v.clone(), v.acos() / &k)
+        }
+        "atan" | "arctan" => {
+            let v = (r.clone() * &k).tan();
+            within(v.clone(), v.atan() / &k)
+        }
+        _ => return None,
+    })
+}
+fn solve_for(
+    expr: &Expr,
+    var: &str,
+    rhs: Vec<Complex>,
+    n: &Complex,
+    options: &Options,
+    func_vars: &[(String, Vec<NumStr>)],
+) -> Option<Vec<Complex>> {
+    let prec = options.precision();
+    let eval = |e: &Expr| Some(e.eval(*options, func_vars).ok()?.num().ok()?.number);
+    let s = |e: &Expr, rhs: Vec<Complex>| solve_for(e, var, rhs, n, options, func_vars);
+    match expr {
+        Expr::Var(v) if v == var => Some(rhs),
+        Expr::Group(e) => s(e, rhs),
+        Expr::Binary(op, a, b) => {
+            let left = depends(a, var);
+            if left == depends(b, var) {
+                return solve_poly(expr, var, rhs, n, options, func_vars);
             }
-            if is_constant(p, var) {
-                let d = do_math(p.to_vec(), *options, Vec::new())?.num()?.number;
-                if d.is_zero() {
-                    return Err("zero divisor");
+            let (dep, c) = if left { (a, eval(b)?) } else { (b, eval(a)?) };
+            let rhs = rhs.into_iter();
+            match op {
+                Plus => s(dep, rhs.map(|r| r - &c).collect()),
+                Minus if left => s(dep, rhs.map(|r| r + &c).collect()),
+                Minus => s(dep, rhs.map(|r| c.clone() - r).collect()),
+                Multiplication | InternalMultiplication if !c.is_zero() => {
+                    s(dep, rhs.map(|r| r / &c).collect())
                 }
-                arr /= d
-            } else {
-                let p = Self::get_polynomial(p, options, var)?;
-                arr /= p;
-            }
-        }
-        if !is_empty {
-            return Ok(arr);
-        }
-        let mut list = place(func, &Exponent, true);
-        let is_empty = list.is_empty();
-        if !is_empty {
-            let p = list.remove(0);
-            let p = Self::get_polynomial(p, options, var)?;
-            let k = do_math(list.remove(0).to_vec(), *options, Vec::new())?
-                .num()?
-                .number
-                .into_real_imag();
-            if !k.1.is_zero() || !k.0.clone().fract().is_zero() {
-                return Err("non integer exponent");
-            }
-            let k = k.0.to_integer().unwrap_or_default();
-            match k.cmp0() {
-                Ordering::Less => {
-                    let mut i = rug::Integer::from(1);
-                    let k = -k;
-                    arr = p.clone();
-                    while i < k {
-                        arr *= &p;
-                        i += 1;
-                    }
-                    arr = arr.recip();
+                Division if left && !c.is_zero() => s(dep, rhs.map(|r| r * &c).collect()),
+                Division if !left => s(
+                    dep,
+                    rhs.filter(|r| !r.is_zero())
+                        .map(|r| c.clone() / r)
+                        .collect(),
+                ),
+                Exponent
+                    if left && is_integer(&c) && !c.is_zero() && c.real().clone().abs() <= 64 =>
+                {
+                    let m = Complex::with_val(prec, c.real().clone().abs());
+                    s(
+                        dep,
+                        rhs.flat_map(|r| {
+                            unity(if is_negative(&c) { r.recip() } else { r }, m.clone())
+                        })
+                        .map(|a| a.number)
+                        .collect(),
+                    )
                 }
-                Ordering::Equal => {
-                    arr.quotient.push(Complex::with_val(options.prec, 1));
+                Exponent if left && !c.is_zero() => s(
+                    dep,
+                    rhs.filter_map(|r| {
+                        let v = r.clone().pow(c.clone().recip());
+                        near(&v.clone().pow(&c), &r).then_some(v)
+                    })
+                    .collect(),
+                ),
+                Exponent if !left && !c.clone().ln().is_zero() => {
+                    let tau = Complex::with_val(prec, (0, Constant::Pi)) * 2 * n;
+                    let l = c.ln();
+                    s(
+                        dep,
+                        rhs.filter(|r| !r.is_zero())
+                            .map(|r| (r.ln() + &tau) / &l)
+                            .collect(),
+                    )
                 }
-                Ordering::Greater => {
-                    arr = p.clone();
-                    let mut i = rug::Integer::from(1);
-                    while i < k {
-                        arr *= &p;
-                        i += 1;
+                _ => None,
+            }
+        }
+        Expr::Call(f, args) if f == "log" && args.len() == 2 => {
+            if depends(&args[0], var) {
+                let c = eval(&args[1])?;
+                s(
+                    &args[0],
+                    rhs.into_iter()
+                        .filter(|r| !r.is_zero())
+                        .map(|r| c.clone().pow(r.recip()))
+                        .filter(is_base)
+                        .collect(),
+                )
+            } else {
+                let c = eval(&args[0])?;
+                s(
+                    &args[1],
+                    rhs.into_iter().map(|r| c.clone().pow(r)).collect(),
+                )
+            }
+        }
+        Expr::Call(f, args) if args.len() == 1 => {
+            let mut values = Vec::new();
+            for r in rhs {
+                values.extend(branches(f, r, n, options)?)
+            }
+            s(&args[0], values)
+        }
+        Expr::Apply(f, e) => {
+            let mut values = Vec::new();
+            for r in rhs {
+                values.extend(branches(f, r, n, options)?)
+            }
+            s(e, values)
+        }
+        _ => None,
+    }
+}
+///the roots of a polynomial with its coefficients from the constant term up
+fn roots(mut p: Vec<Complex>, options: &Options) -> Result<Vec<Number>, Error> {
+    let mut mult = 1;
+    let mut r = Vec::with_capacity(p.len());
+    while p.len() > 1 && p[0].is_zero() && p[1].is_zero() {
+        p.remove(0);
+        r.push(Number::new(options))
+    }
+    if p.len() > 5 {
+        let powers = p
+            .iter()
+            .enumerate()
+            .filter_map(|(i, n)| if n.is_zero() && i != 0 { None } else { Some(i) })
+            .collect::<Vec<usize>>();
+        if powers.len() >= 2 {
+            mult = powers[1] - powers[0];
+            for (i, p) in powers[1..].iter().enumerate() {
+                if let Some(q) = powers.get(i + 2) {
+                    if q - p != mult {
+                        mult = 1;
+                        break;
                     }
                 }
             }
         }
-        if !is_empty {
-            return Ok(arr);
+    }
+    let l = p.len().div_ceil(mult);
+    let mut p = p
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, a)| if i % mult == 0 { Some(a) } else { None });
+    let n = |c: Complex| Number::from(c, None);
+    r.extend(match l {
+        0 | 1 => vec![Number::from(
+            Complex::with_val(options.precision(), Nan),
+            None,
+        )],
+        2 => {
+            let a = p.next().unwrap();
+            let b = p.next().unwrap();
+            vec![Number::from(-a / b, None)]
+        }
+        3 => {
+            let c = p.next().unwrap();
+            let b = p.next().unwrap();
+            let a = p.next().unwrap();
+            quadratic(n(a), n(b), n(c), false)
+        }
+        4 => {
+            let d = p.next().unwrap();
+            let c = p.next().unwrap();
+            let b = p.next().unwrap();
+            let a = p.next().unwrap();
+            cubic(n(a), n(b), n(c), n(d), false)
         }
-        Err("not poly")
+        5 => {
+            let e = p.next().unwrap();
+            let d = p.next().unwrap();
+            let c = p.next().unwrap();
+            let b = p.next().unwrap();
+            let a = p.next().unwrap();
+            quartic(n(a), n(b), n(c), n(d), n(e), false)
+        }
+        _ => polyroots(p.collect(), options.precision())?
+            .into_iter()
+            .map(|a| Number::from(a, None))
+            .collect(),
+    });
+    if mult == 1 {
+        return Ok(r);
     }
+    let m = Complex::with_val(options.precision(), mult);
+    Ok(r.into_iter()
+        .flat_map(|n| unity(n.number, m.clone()))
+        .collect())
 }
-fn is_poly(func: &[NumStr], var: &[NumStr]) -> bool {
-    let a = func.len();
-    let b = var.len();
-    if a >= b {
-        let mut i = 0;
-        while i <= a - b {
-            if &func[i..i + b] == var {
-                i += b
-            } else {
-                if !matches!(
-                    func[i],
-                    Num(_)
-                        | Plus
-                        | Multiplication
-                        | InternalMultiplication
-                        | Minus
-                        | Division
-                        | Exponent
-                        | LeftBracket
-                        | RightBracket
-                ) {
-                    return false;
-                }
-                i += 1
+///evaluates the parts of expr that do not depend on var
+fn constants(
+    expr: Expr,
+    var: &str,
+    options: &Options,
+    func_vars: &[(String, Vec<NumStr>)],
+) -> Option<Expr> {
+    if !depends(&expr, var) {
+        return Some(num(expr.eval(*options, func_vars).ok()?.num().ok()?.number));
+    }
+    let c = |e: Expr| constants(e, var, options, func_vars);
+    Some(match expr {
+        Expr::Group(e) => Expr::Group(Box::new(c(*e)?)),
+        Expr::Binary(op, a, b) => Expr::Binary(op, Box::new(c(*a)?), Box::new(c(*b)?)),
+        Expr::Call(s, args) => {
+            Expr::Call(s, args.into_iter().map(c).collect::<Option<Vec<Expr>>>()?)
+        }
+        Expr::Apply(s, e) => Expr::Apply(s, Box::new(c(*e)?)),
+        Expr::Spanned(_, e) => c(*e)?,
+        e => e,
+    })
+}
+///the single argument calls of var in expr, the candidates to solve a polynomial in
+fn atoms(expr: &Expr, var: &str, list: &mut Vec<Expr>) {
+    match expr {
+        Expr::Call(_, args) if args.len() == 1 && depends(&args[0], var) => {
+            if !list.contains(expr) {
+                list.push(expr.clone())
             }
+            atoms(&args[0], var, list)
         }
-        true
-    } else {
-        func.iter().all(|f| {
-            matches!(
-                f,
-                Num(_)
-                    | Plus
-                    | Multiplication
-                    | InternalMultiplication
-                    | Minus
-                    | Division
-                    | Exponent
-                    | LeftBracket
-                    | RightBracket
-            )
-        })
+        Expr::Apply(_, e) if depends(e, var) => {
+            if !list.contains(expr) {
+                list.push(expr.clone())
+            }
+            atoms(e, var, list)
+        }
+        Expr::Call(_, args) | Expr::Vector(args) => args.iter().for_each(|a| atoms(a, var, list)),
+        Expr::Apply(_, e) | Expr::Group(e) | Expr::Not(e) | Expr::Spanned(_, e) => {
+            atoms(e, var, list)
+        }
+        Expr::Binary(_, a, b) => {
+            atoms(a, var, list);
+            atoms(b, var, list)
+        }
+        _ => {}
+    }
+}
+fn replace(expr: &Expr, atom: &Expr, var: &str) -> Expr {
+    if expr == atom {
+        return Expr::Var(var.to_string());
+    }
+    let r = |e: &Expr| replace(e, atom, var);
+    match expr {
+        Expr::Group(e) => Expr::Group(Box::new(r(e))),
+        Expr::Binary(op, a, b) => Expr::Binary(op.clone(), Box::new(r(a)), Box::new(r(b))),
+        Expr::Call(s, args) => Expr::Call(s.clone(), args.iter().map(r).collect()),
+        Expr::Apply(s, e) => Expr::Apply(s.clone(), Box::new(r(e))),
+        Expr::Vector(args) => Expr::Vector(args.iter().map(r).collect()),
+        Expr::Not(e) => Expr::Not(Box::new(r(e))),
+        Expr::Spanned(span, e) => Expr::Spanned(*span, Box::new(r(e))),
+        e => e.clone(),
     }
 }
-fn poly_mul(
+///solves expr = rhs as a polynomial in var, or in a call of var every occurrence sits in
+fn solve_poly(
+    expr: &Expr,
+    var: &str,
+    rhs: Vec<Complex>,
+    n: &Complex,
     options: &Options,
-    var: &[NumStr],
-    arr: &mut Polynomial,
-    p: &[NumStr],
-) -> Result<(), &'static str> {
-    if is_constant(p, var) {
-        *arr *= do_math(p.to_vec(), *options, Vec::new())?.num()?.number
-    } else {
-        let p = Polynomial::get_polynomial(p, options, var)?;
-        *arr *= &p
+    func_vars: &[(String, Vec<NumStr>)],
+) -> Option<Vec<Complex>> {
+    let prec = options.precision();
+    let expr = constants(expr.clone(), var, options, func_vars)?;
+    let mut list = vec![Expr::Var(var.to_string())];
+    atoms(&expr, var, &mut list);
+    list[1..].sort_by_key(size);
+    for atom in list {
+        let sub = replace(&expr, &atom, "");
+        if depends(&sub, var) {
+            continue;
+        }
+        let Some(p) = rational(&sub, "", prec) else {
+            continue;
+        };
+        let mut values = Vec::new();
+        for r in &rhs {
+            let mut top = p.quotient.clone();
+            top.resize(top.len().max(p.divisor.len()), Complex::new(prec));
+            for (t, d) in top.iter_mut().zip(&p.divisor) {
+                *t -= d.clone() * r
+            }
+            let mut top = Polynomial::from((top, p.divisor.clone())).compute().ok()?;
+            trim(&mut top);
+            values.extend(roots(top, options).ok()?.into_iter().map(|a| a.number))
+        }
+        return if matches!(atom, Expr::Var(_)) {
+            Some(values)
+        } else {
+            solve_for(&atom, var, values, n, options, func_vars)
+        };
     }
-    Ok(())
+    None
+}
+///whether c can be the base of a logarithm, so neither zero, one nor a negative real
+fn is_base(c: &Complex) -> bool {
+    !(c.imag().is_zero() && (*c == 1 || c.real().is_sign_negative() || c.real().is_zero()))
 }
-fn poly_add(
+///errors on a log whose base is a constant that cannot be one
+fn check_bases(
+    expr: &Expr,
+    var: &str,
     options: &Options,
-    var: &[NumStr],
-    arr: &mut Polynomial,
-    p: &[NumStr],
-) -> Result<(), &'static str> {
-    if is_constant(p, var) {
-        *arr += do_math(p.to_vec(), *options, Vec::new())?.num()?.number
-    } else {
-        let q = Polynomial::get_polynomial(p, options, var)?;
-        *arr += q
-    }
-    Ok(())
-}
-fn place<'a>(func: &'a [NumStr], target: &'a NumStr, once: bool) -> Vec<&'a [NumStr]> {
-    let mut b = 0;
-    let mut l = 0;
-    let mut vec = Vec::new();
-    for (i, n) in func.iter().enumerate() {
-        match n {
-            LeftBracket | LeftCurlyBracket => b += 1,
-            RightBracket | RightCurlyBracket => b -= 1,
-            _ if b == 0
-                && (n == target
-                    || if target == &Multiplication {
-                        n == &InternalMultiplication
-                    } else {
-                        false
-                    }) =>
-            {
-                vec.push(&func[l..i]);
-                l = i + 1;
-                if once {
-                    vec.push(&func[l..]);
-                    return vec;
+    func_vars: &[(String, Vec<NumStr>)],
+) -> Result<(), Error> {
+    match expr {
+        Expr::Call(f, args) if f == "log" && args.len() == 2 && !depends(&args[0], var) => {
+            if let Ok(Num(c)) = args[0].eval(*options, func_vars) {
+                if !is_base(&c.number) {
+                    return Err(Error::Domain("log base must not be 0, 1 or negative", None));
                 }
             }
-            _ => {}
+            check_bases(&args[1], var, options, func_vars)
+        }
+        Expr::Call(_, args) | Expr::Vector(args) => args
+            .iter()
+            .try_for_each(|a| check_bases(a, var, options, func_vars)),
+        Expr::Apply(_, e) | Expr::Group(e) | Expr::Not(e) | Expr::Spanned(_, e) => {
+            check_bases(e, var, options, func_vars)
         }
+        Expr::Binary(_, a, b) => {
+            check_bases(a, var, options, func_vars)?;
+            check_bases(b, var, options, func_vars)
+        }
+        Expr::Literal(_) | Expr::Var(_) | Expr::Raw(_) => Ok(()),
+    }
+}
+///solves func=0 for var, n is the integer picked for every periodic inverse so the solutions
+///listed are those of that branch only, the full family is their union over all integer n
+pub fn isolate(
+    func: &[NumStr],
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    var: String,
+    n: Complex,
+) -> Result<NumStr, Error> {
+    let expr = Expr::from_tokens(func)?;
+    if !depends(&expr, &var) {
+        return Err(Error::Unsupported("nothing to isolate", None));
     }
-    if l != 0 {
-        vec.push(&func[l..]);
+    check_bases(&expr, &var, &options, &func_vars)?;
+    let mut r = solve_for(
+        &expr,
+        &var,
+        vec![Complex::new(options.precision())],
+        &n,
+        &options,
+        &func_vars,
+    )
+    .ok_or(Error::Unsupported("could not isolate", None))?;
+    r.sort_by(|a, b| a.total_cmp(b));
+    r.dedup_by(|a, b| near(a, b));
+    Ok(Vector(
+        r.into_iter().map(|a| Number::from(a, None)).collect(),
+    ))
+}
+fn num(n: Complex) -> Expr {
+    Expr::Literal(NumStr::new(Number::from(n, None)))
+}
+fn int(n: i32, prec: Precision) -> Expr {
+    num(Complex::with_val(prec, n))
+}
+fn value(expr: &Expr) -> Option<&Complex> {
+    match expr {
+        Expr::Literal(Num(n)) if n.units.is_none() => Some(&n.number),
+        Expr::Group(e) => value(e),
+        _ => None,
     }
-    vec
 }
-fn is_interior(func: &[NumStr]) -> bool {
-    let mut b = 0;
-    if func[0] == LeftBracket && func[func.len() - 1] == RightBracket {
-        for n in func {
-            match n {
-                LeftBracket => b += 1,
-                RightBracket => b -= 1,
-                _ if b == 0 => return false,
-                _ => {}
-            }
+fn is_value(expr: &Expr, n: i32) -> bool {
+    value(expr).is_some_and(|v| *v == n)
+}
+fn is_negative(n: &Complex) -> bool {
+    if n.imag().is_zero() {
+        n.real().is_sign_negative() && !n.real().is_zero()
+    } else {
+        !n.real().is_zero() || n.imag().is_sign_negative()
+    }
+}
+fn negated(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Binary(InternalMultiplication | Multiplication, a, b) if is_value(a, -1) => Some(b),
+        _ => None,
+    }
+}
+fn wrap(expr: Expr, op: &NumStr, right: bool) -> Expr {
+    let outer = level(op).unwrap_or(0);
+    let group = match &expr {
+        Expr::Binary(o, _, _) => {
+            let inner = level(o).unwrap_or(0);
+            inner < outer || (inner == outer && right != (outer == 6))
         }
-        true
+        Expr::Not(_) => true,
+        Expr::Literal(Num(n)) => {
+            ((outer >= 6 || (right && outer >= 3)) && is_negative(&n.number))
+                || (!n.number.imag().is_zero() && (outer >= 4 || (right && matches!(op, Minus))))
+        }
+        _ => false,
+    };
+    if group {
+        Expr::Group(Box::new(expr))
     } else {
-        false
+        expr
     }
 }
-fn is_constant(func: &[NumStr], var: &[NumStr]) -> bool {
-    let a = func.len();
-    let b = var.len();
-    if a >= b {
-        let mut i = 0;
-        while i <= a - b {
-            if &func[i..i + b] == var {
-                return false;
-            }
-            i += 1
+fn binary(op: NumStr, a: Expr, b: Expr) -> Expr {
+    let a = wrap(a, &op, false);
+    let b = wrap(b, &op, true);
+    Expr::Binary(op, Box::new(a), Box::new(b))
+}
+fn plus(a: Expr, b: Expr, p: Precision) -> Expr {
+    if is_value(&a, 0) {
+        return b;
+    }
+    if is_value(&b, 0) {
+        return a;
+    }
+    match (value(&a), value(&b)) {
+        (Some(x), Some(y)) => num(x.clone() + y),
+        _ if is_minus(&b) => binary(Minus, a, negate(b, p)),
+        _ => binary(Plus, a, b),
+    }
+}
+fn minus(a: Expr, b: Expr, p: Precision) -> Expr {
+    if is_value(&b, 0) {
+        return a;
+    }
+    match (value(&a), value(&b)) {
+        (Some(x), Some(y)) => num(x.clone() - y),
+        (Some(x), _) if x.is_zero() => negate(b, p),
+        _ if is_minus(&b) => binary(Plus, a, negate(b, p)),
+        _ => binary(Minus, a, b),
+    }
+}
+fn is_minus(expr: &Expr) -> bool {
+    match expr {
+        Expr::Literal(Num(n)) => {
+            n.units.is_none() && n.number.imag().is_zero() && n.number.real().is_sign_negative()
         }
+        Expr::Binary(InternalMultiplication | Multiplication | Division, c, _) => is_minus(c),
+        _ => false,
     }
-    true
 }
-fn get_var<'a>(func: &'a [NumStr], var: &'a [NumStr]) -> &'a [NumStr] {
-    let a = func.len();
-    let b = var.len();
-    let mut values = Vec::new();
-    if a >= b {
-        let mut i = 0;
-        while i <= a - b {
-            if &func[i..i + b] == var {
-                values.push(i);
-                i += b;
+fn negate(a: Expr, p: Precision) -> Expr {
+    if let Some(x) = value(&a) {
+        return num(-x.clone());
+    }
+    if let Some(a) = negated(&a) {
+        return a.clone();
+    }
+    match &a {
+        Expr::Binary(Multiplication | InternalMultiplication, c, d) if value(c).is_some() => {
+            times(num(-value(c).unwrap().clone()), *d.clone(), p)
+        }
+        Expr::Binary(op @ (Multiplication | InternalMultiplication), c, d) => {
+            binary(op.clone(), negate(*c.clone(), p), *d.clone())
+        }
+        Expr::Binary(Division, c, d) => over(negate(*c.clone(), p), *d.clone(), p),
+        _ => binary(InternalMultiplication, int(-1, p), a),
+    }
+}
+fn times(a: Expr, b: Expr, p: Precision) -> Expr {
+    if is_value(&a, 0) || is_value(&b, 1) {
+        return a;
+    }
+    if is_value(&b, 0) || is_value(&a, 1) {
+        return b;
+    }
+    match (value(&a), value(&b)) {
+        (Some(x), Some(y)) => num(x.clone() * y),
+        (None, Some(_)) => times(b, a, p),
+        (Some(x), None) => match &b {
+            Expr::Binary(Multiplication | InternalMultiplication, c, d) if value(c).is_some() => {
+                times(num(x.clone() * value(c).unwrap()), *d.clone(), p)
+            }
+            Expr::Binary(Division, c, d) if value(c).is_some() => {
+                over(num(x.clone() * value(c).unwrap()), *d.clone(), p)
+            }
+            _ if *x == -1 => negate(b, p),
+            _ => binary(Multiplication, a, b),
+        },
+        (None, None) => match (negated(&a), negated(&b)) {
+            (Some(a), Some(b)) => times(a.clone(), b.clone(), p),
+            (Some(a), None) => negate(times(a.clone(), b, p), p),
+            (None, Some(b)) => negate(times(a, b.clone(), p), p),
+            (None, None) => match (&a, &b) {
+                (Expr::Binary(Division, n, d), _) if is_value(n, 1) => over(b, *d.clone(), p),
+                (_, Expr::Binary(Division, n, d)) if is_value(n, 1) => over(a, *d.clone(), p),
+                _ if a == b => power(a, int(2, p), p),
+                _ => binary(Multiplication, a, b),
+            },
+        },
+    }
+}
+fn over(a: Expr, b: Expr, p: Precision) -> Expr {
+    if is_value(&b, 0) {
+        return binary(Division, a, b);
+    }
+    if is_value(&b, 1) || is_value(&a, 0) {
+        return a;
+    }
+    if a == b && value(&a).is_none() {
+        return int(1, p);
+    }
+    match (value(&a), value(&b)) {
+        (Some(x), Some(y)) if !y.is_zero() => num(x.clone() / y),
+        (None, Some(y)) if !y.is_zero() => times(num(y.clone().recip()), a, p),
+        _ => binary(Division, a, b),
+    }
+}
+fn power(a: Expr, b: Expr, p: Precision) -> Expr {
+    if is_value(&b, 0) {
+        return int(1, p);
+    }
+    if is_value(&b, 1) {
+        return a;
+    }
+    match (value(&a), value(&b)) {
+        (Some(x), Some(y)) => num(x.clone().pow(y)),
+        _ => binary(Exponent, a, b),
+    }
+}
+fn call(s: &str, a: Expr) -> Expr {
+    Expr::Call(s.to_string(), vec![a])
+}
+fn ln(a: Expr) -> Expr {
+    match value(&a) {
+        Some(x) if !x.is_zero() => {
+            let l = x.clone().ln();
+            let r = l.real().clone().round();
+            if l.imag().is_zero()
+                && (l.real().clone() - &r).abs()
+                    < Float::with_val(l.prec(), 1) >> (l.prec().0.bits - 16)
+            {
+                num(Complex::with_val(l.prec(), r))
             } else {
-                i += 1
+                num(l)
             }
         }
+        _ => call("ln", a),
     }
-    let mut i = 0;
-    let mut j = b;
-    while values.iter().all(|k| {
-        values[0] > i
-            && func[k - i - 1] == func[values[0] - i - 1]
-            && matches!(func[k - i - 1], LeftBracket)
-    }) && values.iter().all(|k| {
-        func.len() > k + j
-            && func[k + j] == func[values[0] + j]
-            && matches!(func[k + j], RightBracket)
-    }) {
-        i += 1;
-        if values.iter().all(|k| {
-            values[0] > i
-                && func[k - i - 1] == func[values[0] - i - 1]
-                && matches!(func[k - i - 1], Func(_))
-        }) {
-            i += 1;
+}
+fn fold(expr: Expr, p: Precision) -> Expr {
+    match expr {
+        Expr::Group(e) => fold(*e, p),
+        Expr::Binary(op, a, b) => {
+            let (a, b) = (fold(*a, p), fold(*b, p));
+            match op {
+                Plus => plus(a, b, p),
+                Minus => minus(a, b, p),
+                Multiplication | InternalMultiplication => times(a, b, p),
+                Division => over(a, b, p),
+                Exponent => power(a, b, p),
+                _ => binary(op, a, b),
+            }
         }
-        j += 1;
+        Expr::Call(s, args) => Expr::Call(s, args.into_iter().map(|a| fold(a, p)).collect()),
+        Expr::Vector(args) => Expr::Vector(args.into_iter().map(|a| fold(a, p)).collect()),
+        Expr::Apply(s, e) => Expr::Apply(s, Box::new(fold(*e, p))),
+        Expr::Not(e) => Expr::Not(Box::new(fold(*e, p))),
+        e => e,
     }
-    &func[values[0] - i..values[0] + j]
 }
-fn to_vec(a: NumStr) -> Vec<Number> {
-    match a {
-        Num(a) => vec![*a],
-        Vector(a) => a,
-        _ => unreachable!(),
+fn depends(expr: &Expr, var: &str) -> bool {
+    match expr {
+        Expr::Literal(_) => false,
+        Expr::Var(s) => s == var,
+        Expr::Raw(tokens) => tokens.iter().any(|t| t.str_is(var)),
+        Expr::Call(_, args) | Expr::Vector(args) => args.iter().any(|a| depends(a, var)),
+        Expr::Apply(_, e) | Expr::Group(e) | Expr::Not(e) | Expr::Spanned(_, e) => depends(e, var),
+        Expr::Binary(_, a, b) => depends(a, var) || depends(b, var),
     }
 }
-fn inverse(
-    func: &[NumStr],
-    val: Vec<Number>,
-    options: &Options,
-) -> Result<Vec<Number>, &'static str> {
-    if func.len() > 1 {
-        let Func(f) = &func[0] else { unreachable!() };
-        let v = match f.as_str() {
-            "sin" => {
-                let pi = Float::with_val(val[0].number.prec().0, Constant::Pi);
-                val.into_iter()
-                    .flat_map(|a| {
-                        let a = a.number.asin();
-                        vec![a.clone(), pi.clone() - a]
-                    })
-                    .map(|a| Number::from(a, None))
-                    .collect()
-            }
-            "cos" => val
-                .into_iter()
-                .flat_map(|a| {
-                    let a = a.number.acos();
-                    vec![a.clone(), -a]
-                })
-                .map(|a| Number::from(a, None))
-                .collect(),
-            _ => {
-                let inv = "a".to_owned() + f;
-                let v = vec![Func(inv), LeftBracket, Vector(val), RightBracket];
-                to_vec(do_math(v, *options, Vec::new())?)
+fn numeric(expr: &Expr, var: &str) -> Expr {
+    Expr::Call(
+        "slope".to_string(),
+        vec![
+            Expr::Var(var.to_string()),
+            expr.clone(),
+            Expr::Var(var.to_string()),
+        ],
+    )
+}
+pub fn differentiate(expr: &Expr, var: &str, options: Options) -> Expr {
+    let d = |e: &Expr| differentiate(e, var, options);
+    let p = options.precision();
+    if !depends(expr, var) {
+        return int(0, p);
+    }
+    match expr {
+        Expr::Var(_) => int(1, p),
+        Expr::Group(e) => d(e),
+        Expr::Vector(args) => Expr::Vector(args.iter().map(d).collect()),
+        Expr::Binary(op, a, b) => {
+            let (a, b) = (a.as_ref(), b.as_ref());
+            match op {
+                Plus => plus(d(a), d(b), p),
+                Minus => minus(d(a), d(b), p),
+                PlusMinus => binary(PlusMinus, d(a), d(b)),
+                Multiplication | InternalMultiplication => {
+                    plus(times(d(a), b.clone(), p), times(a.clone(), d(b), p), p)
+                }
+                Division if !depends(b, var) => over(d(a), b.clone(), p),
+                Division => over(
+                    minus(times(d(a), b.clone(), p), times(a.clone(), d(b), p), p),
+                    power(b.clone(), int(2, p), p),
+                    p,
+                ),
+                Exponent if !depends(b, var) => times(
+                    times(
+                        b.clone(),
+                        power(a.clone(), minus(b.clone(), int(1, p), p), p),
+                        p,
+                    ),
+                    d(a),
+                    p,
+                ),
+                Exponent if !depends(a, var) => {
+                    times(times(expr.clone(), ln(a.clone()), p), d(b), p)
+                }
+                Exponent => times(
+                    expr.clone(),
+                    plus(
+                        times(d(b), ln(a.clone()), p),
+                        over(times(b.clone(), d(a), p), a.clone(), p),
+                        p,
+                    ),
+                    p,
+                ),
+                _ => numeric(expr, var),
+            }
+        }
+        Expr::Call(s, args) if s == "log" && args.len() == 2 => {
+            d(&over(ln(args[1].clone()), ln(args[0].clone()), p))
+        }
+        Expr::Call(s, args) if s == "root" && args.len() == 2 && !depends(&args[1], var) => times(
+            over(expr.clone(), times(args[1].clone(), args[0].clone(), p), p),
+            d(&args[0]),
+            p,
+        ),
+        Expr::Call(s, args) if s == "root" && args.len() == 2 => d(&power(
+            args[0].clone(),
+            over(int(1, p), args[1].clone(), p),
+            p,
+        )),
+        //the index is local to the sum, so only the term and not the bounds may depend on var
+        Expr::Call(s, args)
+            if matches!(
+                s.as_str(),
+                "sum" | "summation" | "Σ" | "prod" | "product" | "Π"
+            ) && args.len() == 4
+                && matches!(&args[0], Expr::Var(_))
+                && !depends(&args[2], var)
+                && !depends(&args[3], var) =>
+        {
+            if args[0] == Expr::Var(var.to_string()) {
+                return int(0, p);
+            }
+            let sum = |f: Expr| {
+                Expr::Call(
+                    "sum".to_string(),
+                    vec![args[0].clone(), f, args[2].clone(), args[3].clone()],
+                )
+            };
+            if matches!(s.as_str(), "sum" | "summation" | "Σ") {
+                sum(d(&args[1]))
+            } else {
+                times(expr.clone(), sum(over(d(&args[1]), args[1].clone(), p)), p)
             }
-        };
-        if func.len() == 2 {
-            Ok(v)
-        } else {
-            inverse(&func[2..func.len() - 1], v, options)
         }
+        Expr::Call(s, args) if args.len() == 1 => match derivative(s, &args[0], options) {
+            Some(f) => times(f, d(&args[0]), p),
+            None => numeric(expr, var),
+        },
+        Expr::Apply(s, e) => match derivative(s, e, options) {
+            Some(f) => times(f, d(e), p),
+            None => numeric(expr, var),
+        },
+        _ => numeric(expr, var),
+    }
+}
+fn radians(options: Options) -> Expr {
+    let p = options.precision();
+    match options.angle {
+        Radians => int(1, p),
+        Degrees => num(Complex::with_val(p, Constant::Pi) / 180),
+        Gradians => num(Complex::with_val(p, Constant::Pi) / 200),
+    }
+}
+fn derivative(s: &str, u: &Expr, options: Options) -> Option<Expr> {
+    let p = options.precision();
+    let u = || u.clone();
+    let sq = |e: Expr| power(e, int(2, p), p);
+    let to_rad = radians(options);
+    let from_rad = over(int(1, p), to_rad.clone(), p);
+    Some(match s {
+        "sin" => times(to_rad, call("cos", u()), p),
+        "cos" => negate(times(to_rad, call("sin", u()), p), p),
+        "tan" => times(to_rad, sq(call("sec", u())), p),
+        "sec" => times(to_rad, times(call("sec", u()), call("tan", u()), p), p),
+        "csc" => negate(
+            times(to_rad, times(call("csc", u()), call("cot", u()), p), p),
+            p,
+        ),
+        "cot" => negate(times(to_rad, sq(call("csc", u())), p), p),
+        "asin" | "arcsin" => over(from_rad, call("sqrt", minus(int(1, p), sq(u()), p)), p),
+        "acos" | "arccos" => negate(
+            over(from_rad, call("sqrt", minus(int(1, p), sq(u()), p)), p),
+            p,
+        ),
+        "atan" | "arctan" => over(from_rad, plus(int(1, p), sq(u()), p), p),
+        "acot" | "arccot" => negate(over(from_rad, plus(int(1, p), sq(u()), p), p), p),
+        "asec" | "arcsec" => over(
+            from_rad,
+            times(
+                call("abs", u()),
+                call("sqrt", minus(sq(u()), int(1, p), p)),
+                p,
+            ),
+            p,
+        ),
+        "acsc" | "arccsc" => negate(
+            over(
+                from_rad,
+                times(
+                    call("abs", u()),
+                    call("sqrt", minus(sq(u()), int(1, p), p)),
+                    p,
+                ),
+                p,
+            ),
+            p,
+        ),
+        "sinh" => call("cosh", u()),
+        "cosh" => call("sinh", u()),
+        "tanh" => sq(call("sech", u())),
+        "sech" => negate(times(call("sech", u()), call("tanh", u()), p), p),
+        "csch" => negate(times(call("csch", u()), call("coth", u()), p), p),
+        "coth" => negate(sq(call("csch", u())), p),
+        "asinh" | "arcsinh" => over(int(1, p), call("sqrt", plus(sq(u()), int(1, p), p)), p),
+        "acosh" | "arccosh" => over(int(1, p), call("sqrt", minus(sq(u()), int(1, p), p)), p),
+        "atanh" | "arctanh" => over(int(1, p), minus(int(1, p), sq(u()), p), p),
+        "acoth" | "arccoth" => over(int(1, p), minus(int(1, p), sq(u()), p), p),
+        "exp" | "aln" => call("exp", u()),
+        "ln" | "aexp" | "log" => over(int(1, p), u(), p),
+        "sqrt" | "asquare" => over(int(1, p), times(int(2, p), call("sqrt", u()), p), p),
+        "cbrt" | "acube" => over(int(1, p), times(int(3, p), sq(call("cbrt", u())), p), p),
+        "abs" | "norm" => call("sgn", u()),
+        "sgn" | "sign" | "floor" | "ceil" | "round" | "int" | "trunc" => int(0, p),
+        "frac" | "fract" => int(1, p),
+        "recip" => negate(over(int(1, p), sq(u()), p), p),
+        "square" | "asqrt" => times(int(2, p), u(), p),
+        "cube" | "acbrt" => times(int(3, p), sq(u()), p),
+        "erf" => times(
+            num(2 / Complex::with_val(p, Constant::Pi).sqrt()),
+            call("exp", negate(sq(u()), p)),
+            p,
+        ),
+        "erfc" => times(
+            num(-2 / Complex::with_val(p, Constant::Pi).sqrt()),
+            call("exp", negate(sq(u()), p)),
+            p,
+        ),
+        "erfi" => times(
+            num(2 / Complex::with_val(p, Constant::Pi).sqrt()),
+            call("exp", sq(u())),
+            p,
+        ),
+        "gamma" | "Γ" => times(call("gamma", u()), call("digamma", u()), p),
+        "fact" | "factorial" => times(
+            call("fact", u()),
+            call("digamma", plus(u(), int(1, p), p)),
+            p,
+        ),
+        "digamma" | "ψ" => call("trigamma", u()),
+        "W" | "productlog" | "lambertw" => over(
+            call("W", u()),
+            times(u(), plus(int(1, p), call("W", u()), p), p),
+            p,
+        ),
+        "sinc" => over(minus(call("cos", u()), call("sinc", u()), p), u(), p),
+        _ => return None,
+    })
+}
+#[derive(Clone, Debug, PartialEq)]
+struct Sum(Vec<(Complex, Product)>);
+#[derive(Clone, Debug, PartialEq)]
+struct Product(Vec<(Expr, Sum)>);
+fn near(a: &Complex, b: &Complex) -> bool {
+    let p = a.prec().0.bits.min(b.prec().0.bits);
+    let d = (a.clone() - b).abs().real().clone();
+    d.is_zero() || d <= a.clone().abs().real().clone().max(b.clone().abs().real()) >> (p * 3 / 4)
+}
+fn accumulate(t: &mut Complex, c: Complex) {
+    if near(t, &-c.clone()) {
+        *t = Complex::new(t.prec())
     } else {
-        Ok(val)
+        *t += c
     }
 }
-fn isolate_inner(
-    func: &[NumStr],
-    options: &Options,
-    var: &[NumStr],
-) -> Result<Vec<NumStr>, &'static str> {
-    if is_interior(func) {
-        return isolate_inner(&func[1..func.len() - 1], options, var);
-    }
-    if matches!(func[0], Func(_)) && func.len() > 1 && is_interior(&func[1..]) {
-        let v = to_vec(do_math(
-            isolate_inner(&func[2..func.len() - 1], options, var)?,
-            *options,
-            Vec::new(),
-        )?);
-        let m = inverse(&func[0..2], v, options)?;
-        return Ok(vec![Vector(m)]);
-    }
-    let var = get_var(func, var);
-    if is_poly(func, var) {
-        let mut p: Vec<Complex> = Polynomial::get_polynomial(func, options, var)?.compute()?;
-        let mut mult = 1;
-        let mut r = Vec::with_capacity(p.len());
-        while p.len() > 1 && p[0].is_zero() && p[1].is_zero() {
-            p.remove(0);
-            r.push(Number::new(options))
-        }
-        if p.len() > 5 {
-            let powers = p
-                .iter()
-                .enumerate()
-                .filter_map(|(i, n)| if n.is_zero() && i != 0 { None } else { Some(i) })
-                .collect::<Vec<usize>>();
-            if powers.len() >= 2 {
-                mult = powers[1] - powers[0];
-                for (i, p) in powers[1..].iter().enumerate() {
-                    if let Some(q) = powers.get(i + 2) {
-                        if q - p != mult {
-                            mult = 1;
-                            break;
+fn principal(e: &Complex) -> bool {
+    e.imag().is_zero() && *e.real() > -1 && *e.real() <= 1
+}
+fn is_integer(c: &Complex) -> bool {
+    c.imag().is_zero() && c.real().is_integer()
+}
+fn rank(expr: &Expr) -> (usize, String) {
+    (
+        match expr {
+            Expr::Var(_) => 0,
+            Expr::Group(_) | Expr::Binary(..) => 1,
+            Expr::Call(..) | Expr::Apply(..) => 2,
+            Expr::Literal(_) => 3,
+            _ => 4,
+        },
+        format!("{expr:?}"),
+    )
+}
+impl Sum {
+    fn constant(c: Complex) -> Self {
+        if c.is_zero() {
+            Sum(Vec::new())
+        } else {
+            Sum(vec![(c, Product(Vec::new()))])
+        }
+    }
+    fn atom(expr: Expr, prec: Precision) -> Self {
+        Sum(vec![(
+            Complex::with_val(prec, 1),
+            Product(vec![(expr, Sum::constant(Complex::with_val(prec, 1)))]),
+        )])
+    }
+    fn value(&self, prec: Precision) -> Option<Complex> {
+        match self.0.as_slice() {
+            [] => Some(Complex::new(prec)),
+            [(c, p)] if p.0.is_empty() => Some(c.clone()),
+            _ => None,
+        }
+    }
+    fn scale(mut self, c: &Complex) -> Self {
+        if c.is_zero() {
+            return Sum(Vec::new());
+        }
+        self.0.iter_mut().for_each(|t| t.0 *= c);
+        self
+    }
+    fn add(mut self, rhs: Self) -> Self {
+        for (c, p) in rhs.0 {
+            match self.0.iter_mut().find(|t| t.1 == p) {
+                Some(t) => accumulate(&mut t.0, c),
+                None => self.0.push((c, p)),
+            }
+        }
+        self.0.retain(|t| !t.0.is_zero());
+        self.pythagorean();
+        self.0.sort_by(|a, b| order(&a.1, &b.1));
+        self
+    }
+    fn pythagorean(&mut self) {
+        for (f, g, sign) in [("sin", "cos", 1), ("cosh", "sinh", -1)] {
+            let mut i = 0;
+            while i < self.0.len() {
+                let (c, p) = self.0[i].clone();
+                let found = p.0.iter().enumerate().find_map(|(k, (b, e))| match b {
+                    Expr::Call(s, u) if s == f && e.value(c.prec().0).is_some_and(|e| e == 2) => {
+                        let mut q = p.clone();
+                        q.0[k].0 = Expr::Call(g.to_string(), u.clone());
+                        q.0.sort_by_key(|a| rank(&a.0));
+                        let j = self
+                            .0
+                            .iter()
+                            .position(|t| t.1 == q && near(&t.0, &(c.clone() * sign)))?;
+                        let mut r = p.clone();
+                        r.0.remove(k);
+                        Some((j, r))
+                    }
+                    _ => None,
+                });
+                match found {
+                    Some((j, r)) => {
+                        self.0[i].1 = r;
+                        self.0.remove(j);
+                        let merged = std::mem::replace(self, Sum(Vec::new()));
+                        *self = Sum(Vec::new()).add_raw(merged);
+                        i = 0
+                    }
+                    None => i += 1,
+                }
+            }
+        }
+    }
+    fn add_raw(mut self, rhs: Self) -> Self {
+        for (c, p) in rhs.0 {
+            match self.0.iter_mut().find(|t| t.1 == p) {
+                Some(t) => accumulate(&mut t.0, c),
+                None => self.0.push((c, p)),
+            }
+        }
+        self.0.retain(|t| !t.0.is_zero());
+        self
+    }
+    fn mul(self, rhs: Self, expand: bool, prec: Precision) -> Self {
+        if let Some(c) = self.value(prec) {
+            return rhs.scale(&c);
+        }
+        if let Some(c) = rhs.value(prec) {
+            return self.scale(&c);
+        }
+        match (self.0.len(), rhs.0.len()) {
+            (1, 1) => {
+                let (a, p) = self.0.into_iter().next().unwrap();
+                let (b, q) = rhs.0.into_iter().next().unwrap();
+                p.mul(q, prec).scale(&(a * b))
+            }
+            _ if expand => {
+                let mut sum = Sum(Vec::new());
+                for (a, p) in &self.0 {
+                    for (b, q) in &rhs.0 {
+                        sum = sum.add(p.clone().mul(q.clone(), prec).scale(&(a.clone() * b)))
+                    }
+                }
+                sum
+            }
+            (1, _) => self.mul(Sum::atom(rhs.to_expr(prec), prec), expand, prec),
+            _ => Sum::atom(self.to_expr(prec), prec).mul(rhs, expand, prec),
+        }
+    }
+    fn pow(self, exp: Self, expand: bool, prec: Precision) -> Self {
+        let one = || Complex::with_val(prec, 1);
+        let Some(n) = exp.value(prec) else {
+            if let Some(b) = self.value(prec) {
+                if near(&b, &one().exp()) {
+                    if let [(c, Product(f))] = exp.0.as_slice() {
+                        if let [(Expr::Call(s, u), e)] = f.as_slice() {
+                            if s == "ln" && e.value(prec).is_some_and(|e| e == 1) {
+                                return normal(&u[0], expand, prec).pow(
+                                    Sum::constant(c.clone()),
+                                    expand,
+                                    prec,
+                                );
+                            }
                         }
                     }
                 }
+                return Sum(vec![(one(), Product(vec![(num(b), exp)]))]);
             }
+            return match self.0.as_slice() {
+                [(c, Product(f))]
+                    if *c == 1
+                        && f.len() == 1
+                        && f[0].1.value(prec).is_some_and(|e| principal(&e)) =>
+                {
+                    let e = f[0].1.clone().mul(exp, expand, prec);
+                    Sum(vec![(one(), Product(vec![(f[0].0.clone(), e)]))])
+                }
+                _ => Sum(vec![(one(), Product(vec![(self.to_expr(prec), exp)]))]),
+            };
+        };
+        if n.is_zero() {
+            return Sum::constant(one());
         }
-        let l = p.len().div_ceil(mult);
-        let mut p = p
-            .into_iter()
-            .enumerate()
-            .filter_map(|(i, a)| if i % mult == 0 { Some(a) } else { None });
-        let n = |c: Complex| Number::from(c, None);
-        r.extend(match l {
-            0 | 1 => vec![Number::from(
-                Complex::with_val(options.prec, rug::float::Special::Nan),
-                None,
-            )],
-            2 => {
-                let a = p.next().unwrap();
-                let b = p.next().unwrap();
-                vec![Number::from(-a / b, None)]
-            }
-            3 => {
-                let c = p.next().unwrap();
-                let b = p.next().unwrap();
-                let a = p.next().unwrap();
-                quadratic(n(a), n(b), n(c), false)
-            }
-            4 => {
-                let d = p.next().unwrap();
-                let c = p.next().unwrap();
-                let b = p.next().unwrap();
-                let a = p.next().unwrap();
-                cubic(n(a), n(b), n(c), n(d), false)
-            }
-            5 => {
-                let e = p.next().unwrap();
-                let d = p.next().unwrap();
-                let c = p.next().unwrap();
-                let b = p.next().unwrap();
-                let a = p.next().unwrap();
-                quartic(n(a), n(b), n(c), n(d), n(e), false)
+        if let Some(b) = self.value(prec) {
+            return Sum::constant(b.pow(&n));
+        }
+        if is_integer(&n) {
+            if self.0.len() == 1 {
+                let (c, p) = self.0.into_iter().next().unwrap();
+                let p = Product(p.0.into_iter().map(|(b, e)| (b, e.scale(&n))).collect());
+                return Sum(vec![(c.pow(&n), p)]);
             }
-            _ => {
-                //TODO newtons method
-                return Err("poly greater then quartic");
+            if expand && n.real() > &1 && n.real() <= &32 {
+                let mut sum = self.clone();
+                for _ in 1..n.real().to_u32_saturating().unwrap_or(1) {
+                    sum = sum.mul(self.clone(), expand, prec)
+                }
+                return sum;
+            }
+        }
+        match self.0.as_slice() {
+            [(c, Product(f))]
+                if *c == 1 && f.len() == 1 && f[0].1.value(prec).is_some_and(|e| e == 1) =>
+            {
+                Sum(vec![(
+                    one(),
+                    Product(vec![(f[0].0.clone(), Sum::constant(n))]),
+                )])
+            }
+            _ => Sum(vec![(
+                one(),
+                Product(vec![(self.to_expr(prec), Sum::constant(n))]),
+            )]),
+        }
+    }
+    fn to_expr(&self, prec: Precision) -> Expr {
+        let mut terms = self.0.iter().collect::<Vec<&(Complex, Product)>>();
+        if let Some(i) = terms
+            .iter()
+            .position(|t| t.0.imag().is_zero() && t.0.real().is_sign_positive())
+        {
+            let t = terms.remove(i);
+            terms.insert(0, t)
+        }
+        let mut expr: Option<Expr> = None;
+        for (c, p) in terms {
+            let term = p.to_expr(c);
+            expr = Some(match expr {
+                Some(e) => plus(e, term, prec),
+                None => term,
+            })
+        }
+        expr.unwrap_or_else(|| int(0, prec))
+    }
+}
+fn exponent(s: &Sum) -> Option<f64> {
+    match s.0.as_slice() {
+        [] => Some(0.0),
+        [(c, p)] if p.0.is_empty() => Some(c.real().to_f64()),
+        _ => None,
+    }
+}
+fn order(a: &Product, b: &Product) -> Ordering {
+    let degree = |p: &Product| {
+        p.0.iter()
+            .filter(|f| matches!(f.0, Expr::Var(_)))
+            .filter_map(|f| exponent(&f.1))
+            .sum::<f64>()
+    };
+    if a.0.is_empty() || b.0.is_empty() {
+        return b.0.len().min(1).cmp(&a.0.len().min(1));
+    }
+    degree(b)
+        .partial_cmp(&degree(a))
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| {
+            for (x, y) in a.0.iter().zip(&b.0) {
+                let o = rank(&x.0).cmp(&rank(&y.0)).then_with(|| {
+                    let e = |s: &Sum| exponent(s).unwrap_or(0.0);
+                    e(&y.1).partial_cmp(&e(&x.1)).unwrap_or(Ordering::Equal)
+                });
+                if o != Ordering::Equal {
+                    return o;
+                }
+            }
+            a.0.len().cmp(&b.0.len())
+        })
+}
+impl Product {
+    fn mul(mut self, rhs: Self, prec: Precision) -> Sum {
+        let mut coeff = Complex::with_val(prec, 1);
+        for (b, e) in rhs.0 {
+            match self.0.iter_mut().find(|f| f.0 == b) {
+                Some(f) => f.1 = std::mem::replace(&mut f.1, Sum(Vec::new())).add(e),
+                None => self.0.push((b, e)),
+            }
+        }
+        self.0.retain(|f| !f.1.0.is_empty());
+        let mut i = 0;
+        while i < self.0.len() {
+            if let (Some(b), Some(e)) = (value(&self.0[i].0), self.0[i].1.value(prec)) {
+                coeff *= b.clone().pow(&e);
+                self.0.remove(i);
+            } else {
+                i += 1
+            }
+        }
+        for (f, g, h) in [("sin", "cos", "tan"), ("sinh", "cosh", "tanh")] {
+            while let Some((i, j)) = self.0.iter().enumerate().find_map(|(i, (b, e))| match b {
+                Expr::Call(s, u) if s == f => self
+                    .0
+                    .iter()
+                    .position(|(c, d)| {
+                        matches!(c, Expr::Call(s, v) if s == g && v == u)
+                            && d.clone().add(e.clone()).0.is_empty()
+                    })
+                    .map(|j| (i, j)),
+                _ => None,
+            }) {
+                if let Expr::Call(_, u) = &self.0[i].0 {
+                    self.0[i].0 = Expr::Call(h.to_string(), u.clone());
+                }
+                self.0.remove(j);
             }
+        }
+        self.0.sort_by_key(|a| rank(&a.0));
+        Sum(vec![(coeff, self)]).add_raw(Sum(Vec::new()))
+    }
+    fn to_expr(&self, c: &Complex) -> Expr {
+        let prec = c.prec().0;
+        let mut top = num(c.clone());
+        let mut bottom = num(Complex::with_val(prec, 1));
+        let unit = self.0.iter().position(|(b, e)| {
+            matches!(b, Expr::Literal(Num(n)) if n.units.is_some())
+                && e.value(c.prec().0).is_some_and(|e| e == 1)
         });
-        r.sort_unstable_by(|a, b| a.number.total_cmp(&b.number));
-        let mut a = Vec::new();
-        if mult != 1 {
-            let m = Complex::with_val(options.prec, mult);
-            for n in r.into_iter() {
-                a.extend(unity(n.number, m.clone()))
+        if let Some(Expr::Literal(Num(n))) = unit.map(|i| &self.0[i].0) {
+            top = Expr::Literal(NumStr::new(Number::from(c.clone() * &n.number, n.units)))
+        } else if !is_integer(c) {
+            if let Some(m) = integral_scale(std::slice::from_ref(c)) {
+                top = num(c.clone() * &m);
+                bottom = num(m);
             }
-        } else {
-            a = r
         }
-        return Ok(vec![Vector(if var.len() == 1 {
-            a
-        } else {
-            inverse(var, a, options)?
-        })]);
-    }
-    let mut v = Vec::new();
-    let list = place(func, &Plus, false);
-    let mut some = false;
-    let empty = list.is_empty();
-    for p in list {
-        if is_constant(p, var) {
-            v.push(LeftBracket);
-            v.push(Num(Box::new(Number::new(options))));
-            v.push(Minus);
-            v.push(LeftBracket);
-            v.extend_from_slice(p);
-            v.push(RightBracket);
-            v.push(RightBracket);
-            v.push(Plus);
-            some = true;
-        } else {
-            let list = isolate_inner(p, options, var)?;
-            if !list.is_empty() && list != var {
-                v.extend(list);
-                v.push(Plus);
-                some = true;
+        for (i, (b, e)) in self.0.iter().enumerate() {
+            if Some(i) == unit {
+                continue;
+            }
+            let (e, down) = match e.value(c.prec().0) {
+                Some(n) if is_negative(&n) && n.imag().is_zero() => (Sum::constant(-n), true),
+                _ => (e.clone(), false),
+            };
+            let f = factor(b, &e, c.prec().0);
+            if down {
+                bottom = times(bottom, f, prec)
+            } else {
+                top = times(top, f, prec)
             }
         }
+        ratio(top, bottom, prec)
     }
-    if some {
-        v.pop();
+}
+fn ratio(top: Expr, bottom: Expr, prec: Precision) -> Expr {
+    match value(&bottom) {
+        Some(b) if *b != 1 && value(&top).is_none() => binary(Division, top, bottom),
+        _ => over(top, bottom, prec),
     }
-    if !empty {
-        return Ok(v);
+}
+fn factor(base: &Expr, exp: &Sum, prec: Precision) -> Expr {
+    let half = Complex::with_val(prec, 0.5);
+    match exp.value(prec) {
+        Some(n) if n == 1 => base.clone(),
+        Some(n) if near(&n, &half) => call("sqrt", base.clone()),
+        _ if value(base).is_some_and(|b| near(b, &Complex::with_val(prec, 1).exp())) => {
+            call("exp", exp.to_expr(prec))
+        }
+        _ => power(base.clone(), exp.to_expr(prec), prec),
     }
-    Ok(v)
 }
-pub fn isolate(
-    func: &[NumStr],
-    func_vars: Vec<(String, Vec<NumStr>)>,
-    options: Options,
-    var: String,
-) -> Result<NumStr, &'static str> {
-    if func.iter().all(|f| match f {
-        Func(v) => v != &var,
-        _ => true,
-    }) {
-        return Err("nothing to isolate");
+fn normal(expr: &Expr, expand: bool, prec: Precision) -> Sum {
+    let n = |e: &Expr| normal(e, expand, prec);
+    match expr {
+        Expr::Literal(Num(n)) if n.units.is_none() => Sum::constant(n.number.clone()),
+        Expr::Group(e) => n(e),
+        Expr::Binary(Plus, a, b) => n(a).add(n(b)),
+        Expr::Binary(Minus, a, b) => n(a).add(n(b).scale(&Complex::with_val(prec, -1))),
+        Expr::Binary(Multiplication | InternalMultiplication, a, b) => n(a).mul(n(b), expand, prec),
+        Expr::Binary(Division, a, b) => {
+            let d = n(b);
+            if d.value(prec).is_some_and(|d| d.is_zero()) {
+                //a division by zero is left alone, 0/0 included
+                return Sum::atom(
+                    binary(Division, transform(a, expand, prec), d.to_expr(prec)),
+                    prec,
+                );
+            }
+            n(a).mul(
+                d.pow(Sum::constant(Complex::with_val(prec, -1)), expand, prec),
+                expand,
+                prec,
+            )
+        }
+        Expr::Binary(Exponent, a, b) => n(a).pow(n(b), expand, prec),
+        Expr::Binary(op, a, b) => Sum::atom(
+            binary(
+                op.clone(),
+                transform(a, expand, prec),
+                transform(b, expand, prec),
+            ),
+            prec,
+        ),
+        Expr::Call(s, args) if args.len() == 1 && !is_lazy(s) => {
+            let u = transform(&args[0], expand, prec);
+            let constant = |c: f64| Sum::constant(Complex::with_val(prec, c));
+            match s.as_str() {
+                "exp" | "aln" => {
+                    Sum::constant(Complex::with_val(prec, 1).exp()).pow(n(&u), expand, prec)
+                }
+                "sqrt" | "asquare" => n(&u).pow(constant(0.5), expand, prec),
+                "recip" => n(&u).pow(constant(-1.0), expand, prec),
+                "square" | "asqrt" => n(&u).pow(constant(2.0), expand, prec),
+                "cube" | "acbrt" => n(&u).pow(constant(3.0), expand, prec),
+                "ln" | "aexp" => match n(&u).value(prec) {
+                    Some(c) if !c.is_zero() => Sum::constant(c.ln()),
+                    _ => Sum::atom(call(s, u), prec),
+                },
+                _ => Sum::atom(call(s, u), prec),
+            }
+        }
+        Expr::Call(s, args) if !is_lazy(s) => Sum::atom(
+            Expr::Call(
+                s.clone(),
+                args.iter().map(|a| transform(a, expand, prec)).collect(),
+            ),
+            prec,
+        ),
+        Expr::Vector(args) => Sum::atom(
+            Expr::Vector(args.iter().map(|a| transform(a, expand, prec)).collect()),
+            prec,
+        ),
+        Expr::Apply(s, e) => Sum::atom(
+            Expr::Apply(s.clone(), Box::new(transform(e, expand, prec))),
+            prec,
+        ),
+        e => Sum::atom(e.clone(), prec),
     }
-    do_math(
-        isolate_inner(func, &options, &[Func(var)])?,
-        options,
-        func_vars,
+}
+fn transform(expr: &Expr, expand: bool, prec: Precision) -> Expr {
+    if expand {
+        normal(expr, true, prec).to_expr(prec)
+    } else {
+        simplify(expr, prec)
+    }
+}
+fn size(expr: &Expr) -> usize {
+    expr.to_tokens().len()
+}
+fn vars(expr: &Expr, list: &mut Vec<String>) {
+    match expr {
+        Expr::Var(s) => {
+            if !list.contains(s) {
+                list.push(s.clone())
+            }
+        }
+        Expr::Call(_, args) | Expr::Vector(args) => args.iter().for_each(|a| vars(a, list)),
+        Expr::Apply(_, e) | Expr::Group(e) | Expr::Not(e) | Expr::Spanned(_, e) => vars(e, list),
+        Expr::Binary(_, a, b) => {
+            vars(a, list);
+            vars(b, list)
+        }
+        Expr::Raw(_) => list.push(String::new()),
+        Expr::Literal(_) => {}
+    }
+}
+fn rational(expr: &Expr, var: &str, prec: Precision) -> Option<Polynomial> {
+    let r = |e: &Expr| rational(e, var, prec);
+    Some(match expr {
+        Expr::Literal(Num(n)) if n.units.is_none() => Polynomial::from(vec![n.number.clone()]),
+        Expr::Var(s) if s == var => {
+            Polynomial::from(vec![Complex::new(prec), Complex::with_val(prec, 1)])
+        }
+        Expr::Group(e) => r(e)?,
+        Expr::Binary(Plus, a, b) => {
+            let mut a = r(a)?;
+            a += r(b)?;
+            a
+        }
+        Expr::Binary(Minus, a, b) => {
+            let mut a = r(a)?;
+            a -= r(b)?;
+            a
+        }
+        Expr::Binary(Multiplication | InternalMultiplication, a, b) => {
+            let mut a = r(a)?;
+            a *= &r(b)?;
+            a
+        }
+        Expr::Binary(Division, a, b) => {
+            let b = r(b)?;
+            if b.quotient.iter().all(|c| c.is_zero()) {
+                return None;
+            }
+            let mut a = r(a)?;
+            a /= b;
+            a
+        }
+        Expr::Binary(Exponent, a, b) => {
+            let n = value(b)
+                .filter(|n| is_integer(n))?
+                .real()
+                .to_i32_saturating()?;
+            if n.unsigned_abs() > 64 {
+                return None;
+            }
+            let a = r(a)?;
+            let mut p = Polynomial::from(vec![Complex::with_val(prec, 1)]);
+            for _ in 0..n.unsigned_abs() {
+                p *= &a
+            }
+            if n < 0 { p.recip() } else { p }
+        }
+        _ => return None,
+    })
+}
+fn trim(p: &mut Vec<Complex>) {
+    let Some(first) = p.first() else {
+        return;
+    };
+    let max = p
+        .iter()
+        .map(|c| c.clone().abs().real().clone())
+        .fold(Float::new(first.prec().0), |a, b| a.max(&b));
+    while p.last().is_some_and(|c| {
+        let p = c.prec().0;
+        c.is_zero()
+            || c.clone().abs().real().clone()
+                <= max.clone() * (Float::with_val(p, 1) >> (p.bits * 3 / 4))
+    }) {
+        p.pop();
+    }
+}
+fn poly_divrem(a: &[Complex], b: &[Complex]) -> (Vec<Complex>, Vec<Complex>) {
+    let mut r = a.to_vec();
+    trim(&mut r);
+    let mut b = b.to_vec();
+    trim(&mut b);
+    let prec = b[0].prec().0;
+    if r.len() < b.len() {
+        return (vec![Complex::new(prec)], r);
+    }
+    let mut q = vec![Complex::new(prec); r.len() + 1 - b.len()];
+    while r.len() >= b.len() && !r.is_empty() {
+        let shift = r.len() - b.len();
+        let c = r.last().unwrap().clone() / b.last().unwrap();
+        for (k, d) in b.iter().enumerate() {
+            r[k + shift] -= c.clone() * d
+        }
+        r.pop();
+        q[shift] = c;
+        trim(&mut r);
+    }
+    (q, r)
+}
+fn poly_gcd(a: &[Complex], b: &[Complex]) -> Vec<Complex> {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    trim(&mut a);
+    trim(&mut b);
+    while !b.is_empty() {
+        let (_, r) = poly_divrem(&a, &b);
+        a = std::mem::replace(&mut b, r);
+    }
+    if let Some(l) = a.last().cloned() {
+        a.iter_mut().for_each(|c| *c /= &l);
+    }
+    a
+}
+fn poly_expr(p: &[Complex], var: &str) -> Sum {
+    let mut sum = Sum(Vec::new());
+    for (k, c) in p.iter().enumerate() {
+        let prec = c.prec().0;
+        let term = if k == 0 {
+            Sum::constant(c.clone())
+        } else {
+            Sum(vec![(
+                c.clone(),
+                Product(vec![(
+                    Expr::Var(var.to_string()),
+                    Sum::constant(Complex::with_val(prec, k)),
+                )]),
+            )])
+        };
+        sum = sum.add(term)
+    }
+    sum
+}
+fn integral_scale(p: &[Complex]) -> Option<Complex> {
+    let prec = p.first()?.prec().0;
+    (1..=1000).map(|m| Complex::with_val(prec, m)).find(|m| {
+        p.iter().all(|c| {
+            let c = c.clone() * m;
+            near(
+                &c,
+                &Complex::with_val(prec, (c.real().clone().round(), c.imag().clone().round())),
+            )
+        })
+    })
+}
+fn rational_form(expr: &Expr, var: &str, prec: Precision) -> Option<Expr> {
+    let p = rational(expr, var, prec)?;
+    let g = poly_gcd(&p.quotient, &p.divisor);
+    let (mut top, _) = poly_divrem(&p.quotient, &g);
+    let (mut bottom, _) = poly_divrem(&p.divisor, &g);
+    trim(&mut top);
+    trim(&mut bottom);
+    let lead = bottom.last()?.clone();
+    top.iter_mut().for_each(|c| *c /= &lead);
+    bottom.iter_mut().for_each(|c| *c /= &lead);
+    if let Some(m) = integral_scale(&top.iter().chain(&bottom).cloned().collect::<Vec<Complex>>()) {
+        top.iter_mut().for_each(|c| *c *= &m);
+        bottom.iter_mut().for_each(|c| *c *= &m);
+    }
+    let top = poly_expr(&top, var).to_expr(prec);
+    if bottom.len() == 1 {
+        Some(ratio(top, num(bottom[0].clone()), prec))
+    } else {
+        Some(over(top, poly_expr(&bottom, var).to_expr(prec), prec))
+    }
+}
+pub fn simplify(expr: &Expr, prec: Precision) -> Expr {
+    let mut best = fold(expr.clone(), prec);
+    let mut list = Vec::new();
+    vars(expr, &mut list);
+    let mut candidates = vec![
+        normal(expr, false, prec).to_expr(prec),
+        normal(expr, true, prec).to_expr(prec),
+    ];
+    if let [v] = list.as_slice() {
+        candidates.extend(rational_form(expr, v, prec))
+    }
+    for c in candidates.into_iter().rev() {
+        if size(&c) <= size(&best) {
+            best = c
+        }
+    }
+    best
+}
+pub fn expand(expr: &Expr, prec: Precision) -> Expr {
+    normal(expr, true, prec).to_expr(prec)
+}
+pub fn collect(expr: &Expr, var: &str, prec: Precision) -> Expr {
+    let mut groups: Vec<(Complex, Sum)> = Vec::new();
+    for (c, mut p) in normal(expr, true, prec).0 {
+        let k = match p.0.iter().position(|f| f.0 == Expr::Var(var.to_string())) {
+            Some(i) => match p.0[i].1.value(prec) {
+                Some(k) => {
+                    p.0.remove(i);
+                    k
+                }
+                None => Complex::new(prec),
+            },
+            None => Complex::new(prec),
+        };
+        let term = Sum(vec![(c, p)]);
+        match groups.iter_mut().find(|g| g.0 == k) {
+            Some(g) => g.1 = std::mem::replace(&mut g.1, Sum(Vec::new())).add(term),
+            None => groups.push((k, term)),
+        }
+    }
+    groups.sort_by(|a, b| {
+        b.0.real()
+            .partial_cmp(a.0.real())
+            .unwrap_or(Ordering::Equal)
+    });
+    let mut expr: Option<Expr> = None;
+    for (k, c) in groups {
+        if c.0.is_empty() {
+            continue;
+        }
+        let term = times(
+            c.to_expr(prec),
+            factor(&Expr::Var(var.to_string()), &Sum::constant(k), prec),
+            prec,
+        );
+        expr = Some(match expr {
+            Some(e) => plus(e, term, prec),
+            None => term,
+        })
+    }
+    expr.unwrap_or_else(|| int(0, prec))
+}
+fn to_rational(c: &Complex) -> Option<Rational> {
+    if !near(c, &Complex::with_val(c.prec(), c.real())) {
+        return None;
+    }
+    let x = c.real().clone();
+    let p = x.prec();
+    let tol = Float::with_val(p, x.clone().abs().max(&Float::with_val(p, 1))) >> (p.bits * 3 / 4);
+    let (mut h0, mut h1) = (Integer::from(0), Integer::from(1));
+    let (mut k0, mut k1) = (Integer::from(1), Integer::from(0));
+    let mut y = x.clone();
+    for _ in 0..128 {
+        let a = y.clone().floor().to_integer()?;
+        (h0, h1) = (h1.clone(), a.clone() * &h1 + h0);
+        (k0, k1) = (k1.clone(), a.clone() * &k1 + k0);
+        if k1.significant_bits() > p.bits / 4 {
+            return None;
+        }
+        let r = Rational::from((h1.clone(), k1.clone()));
+        if (Float::with_val(p, &r) - &x).abs() <= tol {
+            return Some(r);
+        }
+        let f = y.clone() - a;
+        if f.is_zero() {
+            return None;
+        }
+        y = f.recip();
+    }
+    None
+}
+fn from_rational(r: &Rational, prec: Precision) -> Complex {
+    Complex::with_val(prec, Float::with_val(prec, r))
+}
+///a result of the exact helpers below, the i128 numbers without rug overflow long before
+///gmp's would
+fn fit<T: Exact>(q: T) -> Result<T, Error> {
+    if fits(&q) {
+        Ok(q)
+    } else {
+        Err(Error::UnsupportedBackend("requires rug", None))
+    }
+}
+fn exact<T: Exact>(p: Vec<T>) -> Result<Vec<T>, Error> {
+    if p.iter().all(fits) {
+        Ok(p)
+    } else {
+        Err(Error::UnsupportedBackend("requires rug", None))
+    }
+}
+fn q_trim(mut p: Vec<Rational>) -> Vec<Rational> {
+    while p.last().is_some_and(|c| *c == 0) {
+        p.pop();
+    }
+    p
+}
+fn q_add(a: &[Rational], b: &[Rational]) -> Result<Vec<Rational>, Error> {
+    let mut c = vec![Rational::new(); a.len().max(b.len())];
+    for (i, a) in a.iter().enumerate() {
+        c[i] += a
+    }
+    for (i, b) in b.iter().enumerate() {
+        c[i] += b
+    }
+    exact(q_trim(c))
+}
+fn q_scale(a: &[Rational], s: &Rational) -> Result<Vec<Rational>, Error> {
+    exact(q_trim(a.iter().map(|a| a.clone() * s).collect()))
+}
+fn q_sub(a: &[Rational], b: &[Rational]) -> Result<Vec<Rational>, Error> {
+    q_add(a, &q_scale(b, &Rational::from(-1))?)
+}
+fn q_mul(a: &[Rational], b: &[Rational]) -> Result<Vec<Rational>, Error> {
+    if a.is_empty() || b.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut c = vec![Rational::new(); a.len() + b.len() - 1];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            c[i + j] += a.clone() * b
+        }
+    }
+    exact(q_trim(c))
+}
+#[allow(clippy::type_complexity)]
+fn q_divrem(a: &[Rational], b: &[Rational]) -> Result<(Vec<Rational>, Vec<Rational>), Error> {
+    let mut r = exact(q_trim(a.to_vec()))?;
+    if r.len() < b.len() {
+        return Ok((Vec::new(), r));
+    }
+    let mut q = vec![Rational::new(); r.len() + 1 - b.len()];
+    let lead = b.last().unwrap();
+    while r.len() >= b.len() {
+        let shift = r.len() - b.len();
+        let c = r.last().unwrap().clone() / lead;
+        for (k, d) in b.iter().enumerate() {
+            r[k + shift] -= c.clone() * d
+        }
+        q[shift] = c;
+        r.pop();
+        r = q_trim(r);
+    }
+    Ok((exact(q_trim(q))?, exact(r)?))
+}
+fn q_monic(a: Vec<Rational>) -> Result<Vec<Rational>, Error> {
+    match a.last().cloned() {
+        Some(l) => exact(a.into_iter().map(|c| c / &l).collect()),
+        None => Ok(a),
+    }
+}
+fn q_gcd(a: &[Rational], b: &[Rational]) -> Result<Vec<Rational>, Error> {
+    let (mut a, mut b) = (q_trim(a.to_vec()), q_trim(b.to_vec()));
+    if a.len() > 1 && b.len() > 1 {
+        //coprime modulo a prime dividing neither leading coefficient means coprime, which
+        //settles the usual case before the remainders' coefficients blow up
+        let (za, zb) = (primitive(&a)?.1, primitive(&b)?.1);
+        let (la, lb) = (za.last().unwrap(), zb.last().unwrap());
+        if primes()
+            .filter(|p| {
+                !la.is_divisible(&Integer::from(*p)) && !lb.is_divisible(&Integer::from(*p))
+            })
+            .take(4)
+            .any(|p| fp_gcd(&fp_of(&za, p), &fp_of(&zb, p), p).len() == 1)
+        {
+            return Ok(vec![Rational::from(1)]);
+        }
+    }
+    while !b.is_empty() {
+        let r = q_divrem(&a, &b)?.1;
+        a = std::mem::replace(&mut b, r);
+    }
+    q_monic(a)
+}
+#[allow(clippy::type_complexity)]
+fn q_gcdex(
+    a: &[Rational],
+    b: &[Rational],
+) -> Result<(Vec<Rational>, Vec<Rational>, Vec<Rational>), Error> {
+    let (mut r0, mut r1) = (q_trim(a.to_vec()), q_trim(b.to_vec()));
+    let (mut s0, mut s1) = (vec![Rational::from(1)], Vec::new());
+    let (mut t0, mut t1) = (Vec::new(), vec![Rational::from(1)]);
+    while !r1.is_empty() {
+        let (q, r) = q_divrem(&r0, &r1)?;
+        r0 = std::mem::replace(&mut r1, r);
+        let s = q_sub(&s0, &q_mul(&q, &s1)?)?;
+        s0 = std::mem::replace(&mut s1, s);
+        let t = q_sub(&t0, &q_mul(&q, &t1)?)?;
+        t0 = std::mem::replace(&mut t1, t);
+    }
+    let l = r0.last().cloned().unwrap_or(Rational::from(1)).recip();
+    Ok((q_scale(&r0, &l)?, q_scale(&s0, &l)?, q_scale(&t0, &l)?))
+}
+fn q_deriv(a: &[Rational]) -> Result<Vec<Rational>, Error> {
+    exact(q_trim(
+        a.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(k, c)| c.clone() * Rational::from(k))
+            .collect(),
+    ))
+}
+fn q_pow(a: &[Rational], n: usize) -> Result<Vec<Rational>, Error> {
+    (0..n).try_fold(vec![Rational::from(1)], |p, _| q_mul(&p, a))
+}
+#[allow(clippy::type_complexity)]
+fn square_free(f: &[Rational]) -> Result<Vec<(Vec<Rational>, usize)>, Error> {
+    let mut list = Vec::new();
+    let d = q_deriv(f)?;
+    let a = q_gcd(f, &d)?;
+    let mut b = q_divrem(f, &a)?.0;
+    let mut c = q_divrem(&d, &a)?.0;
+    let mut i = 1;
+    while b.len() > 1 {
+        let d = q_sub(&c, &q_deriv(&b)?)?;
+        let a = q_gcd(&b, &d)?;
+        if a.len() > 1 {
+            list.push((a.clone(), i))
+        }
+        b = q_divrem(&b, &a)?.0;
+        c = q_divrem(&d, &a)?.0;
+        i += 1;
+    }
+    Ok(list)
+}
+fn primitive(f: &[Rational]) -> Result<(Rational, Vec<Integer>), Error> {
+    let l = fit(f.iter().fold(Integer::from(1), |l, c| l.lcm(&c.denom())))?;
+    let p = exact(
+        f.iter()
+            .map(|c| (c.clone() * &l).numer().clone())
+            .collect::<Vec<Integer>>(),
+    )?;
+    let mut g = p.iter().fold(Integer::new(), |g, c| g.gcd(c));
+    if p.last().is_some_and(|c| c.cmp0() == Ordering::Less) {
+        g = -g
+    }
+    if g == 0 {
+        return Ok((Rational::new(), p));
+    }
+    Ok((
+        fit(Rational::from((g.clone(), l)))?,
+        p.into_iter().map(|c| c / &g).collect(),
+    ))
+}
+///the odd primes in order, the moduli tried for the modular steps
+fn primes() -> impl Iterator<Item = u64> {
+    (3u64..).filter(|p| (2..).take_while(|d| d * d <= *p).all(|d| p % d != 0))
+}
+fn fp_of(a: &[Integer], p: u64) -> Vec<u64> {
+    a.iter().map(|c| c.mod_u(p as u32) as u64).collect()
+}
+fn fp_trim(mut a: Vec<u64>) -> Vec<u64> {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+    a
+}
+fn fp_inv(a: u64, p: u64) -> u64 {
+    let mut r = 1;
+    let (mut b, mut e) = (a % p, p - 2);
+    while e > 0 {
+        if e & 1 == 1 {
+            r = r * b % p
+        }
+        b = b * b % p;
+        e >>= 1
+    }
+    r
+}
+fn fp_sub(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    let mut c = vec![0; a.len().max(b.len())];
+    for (i, c) in c.iter_mut().enumerate() {
+        *c = (a.get(i).unwrap_or(&0) + p - b.get(i).unwrap_or(&0)) % p
+    }
+    fp_trim(c)
+}
+fn fp_mul(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut c = vec![0; a.len() + b.len() - 1];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            c[i + j] = (c[i + j] + a * b) % p
+        }
+    }
+    fp_trim(c)
+}
+fn fp_divrem(a: &[u64], b: &[u64], p: u64) -> (Vec<u64>, Vec<u64>) {
+    let mut r = fp_trim(a.to_vec());
+    if r.len() < b.len() {
+        return (Vec::new(), r);
+    }
+    let inv = fp_inv(*b.last().unwrap(), p);
+    let mut q = vec![0; r.len() + 1 - b.len()];
+    while r.len() >= b.len() {
+        let shift = r.len() - b.len();
+        let c = r.last().unwrap() * inv % p;
+        for (k, d) in b.iter().enumerate() {
+            r[k + shift] = (r[k + shift] + p - c * d % p) % p
+        }
+        q[shift] = c;
+        r.pop();
+        r = fp_trim(r);
+    }
+    (fp_trim(q), r)
+}
+fn fp_monic(a: Vec<u64>, p: u64) -> Vec<u64> {
+    match a.last() {
+        Some(l) => {
+            let inv = fp_inv(*l, p);
+            a.iter().map(|c| c * inv % p).collect()
+        }
+        None => a,
+    }
+}
+fn fp_gcd(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    let (mut a, mut b) = (fp_trim(a.to_vec()), fp_trim(b.to_vec()));
+    while !b.is_empty() {
+        let r = fp_divrem(&a, &b, p).1;
+        a = std::mem::replace(&mut b, r);
+    }
+    fp_monic(a, p)
+}
+fn fp_gcdex(a: &[u64], b: &[u64], p: u64) -> (Vec<u64>, Vec<u64>) {
+    let (mut r0, mut r1) = (fp_trim(a.to_vec()), fp_trim(b.to_vec()));
+    let (mut s0, mut s1) = (vec![1], Vec::new());
+    let (mut t0, mut t1) = (Vec::new(), vec![1]);
+    while !r1.is_empty() {
+        let (q, r) = fp_divrem(&r0, &r1, p);
+        r0 = std::mem::replace(&mut r1, r);
+        let s = fp_sub(&s0, &fp_mul(&q, &s1, p), p);
+        s0 = std::mem::replace(&mut s1, s);
+        let t = fp_sub(&t0, &fp_mul(&q, &t1, p), p);
+        t0 = std::mem::replace(&mut t1, t);
+    }
+    let l = fp_inv(*r0.last().unwrap_or(&1), p);
+    (
+        s0.iter().map(|c| c * l % p).collect(),
+        t0.iter().map(|c| c * l % p).collect(),
+    )
+}
+fn fp_powmod(a: &[u64], e: &Integer, f: &[u64], p: u64) -> Vec<u64> {
+    let mut r = vec![1];
+    let mut b = fp_divrem(a, f, p).1;
+    for i in 0..e.significant_bits() {
+        if e.get_bit(i) {
+            r = fp_divrem(&fp_mul(&r, &b, p), f, p).1
+        }
+        b = fp_divrem(&fp_mul(&b, &b, p), f, p).1
+    }
+    r
+}
+fn fp_split(g: Vec<u64>, d: usize, p: u64, seed: &mut u64) -> Vec<Vec<u64>> {
+    if g.len() - 1 <= d {
+        return vec![g];
+    }
+    let e = (Integer::from(p).pow(d as u32) - 1u32) / 2u32;
+    loop {
+        let a = fp_trim(
+            (0..g.len() - 1)
+                .map(|_| {
+                    *seed = seed
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    (*seed >> 33) % p
+                })
+                .collect(),
+        );
+        if a.len() < 2 {
+            continue;
+        }
+        let b = fp_sub(&fp_powmod(&a, &e, &g, p), &[1], p);
+        let u = fp_gcd(&g, &b, p);
+        if u.len() > 1 && u.len() < g.len() {
+            let v = fp_monic(fp_divrem(&g, &u, p).0, p);
+            let mut list = fp_split(u, d, p, seed);
+            list.extend(fp_split(v, d, p, seed));
+            return list;
+        }
+    }
+}
+fn fp_factor(f: Vec<u64>, p: u64) -> Vec<Vec<u64>> {
+    let mut f = fp_monic(f, p);
+    let mut h = vec![0, 1];
+    let mut list = Vec::new();
+    let mut seed = p;
+    let mut d = 0;
+    while f.len() > 2 * (d + 1) {
+        d += 1;
+        h = fp_powmod(&h, &Integer::from(p), &f, p);
+        let g = fp_gcd(&f, &fp_sub(&h, &[0, 1], p), p);
+        if g.len() > 1 {
+            f = fp_monic(fp_divrem(&f, &g, p).0, p);
+            h = fp_divrem(&h, &f, p).1;
+            list.extend(fp_split(g, d, p, &mut seed));
+        }
+    }
+    if f.len() > 1 {
+        list.push(f)
+    }
+    list
+}
+fn z_mod(a: &[Integer], m: &Integer) -> Result<Vec<Integer>, Error> {
+    let mut a = a
+        .iter()
+        .map(|c| c.clone().modulo(m))
+        .collect::<Vec<Integer>>();
+    while a.last().is_some_and(|c| *c == 0) {
+        a.pop();
+    }
+    exact(a)
+}
+fn z_add(a: &[Integer], b: &[Integer]) -> Result<Vec<Integer>, Error> {
+    let mut c = vec![Integer::new(); a.len().max(b.len())];
+    for (i, a) in a.iter().enumerate() {
+        c[i] += a
+    }
+    for (i, b) in b.iter().enumerate() {
+        c[i] += b
+    }
+    exact(c)
+}
+fn z_sub(a: &[Integer], b: &[Integer]) -> Result<Vec<Integer>, Error> {
+    z_add(a, &b.iter().map(|b| -b.clone()).collect::<Vec<Integer>>())
+}
+fn z_mul(a: &[Integer], b: &[Integer]) -> Result<Vec<Integer>, Error> {
+    if a.is_empty() || b.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut c = vec![Integer::new(); a.len() + b.len() - 1];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            c[i + j] += a.clone() * b
+        }
+    }
+    exact(c)
+}
+#[allow(clippy::type_complexity)]
+fn z_divrem_monic(
+    a: &[Integer],
+    b: &[Integer],
+    m: &Integer,
+) -> Result<(Vec<Integer>, Vec<Integer>), Error> {
+    let mut r = z_mod(a, m)?;
+    if r.len() < b.len() {
+        return Ok((Vec::new(), r));
+    }
+    let mut q = vec![Integer::new(); r.len() + 1 - b.len()];
+    while r.len() >= b.len() {
+        let shift = r.len() - b.len();
+        let c = r.last().unwrap().clone();
+        for (k, d) in b.iter().enumerate() {
+            r[k + shift] -= c.clone() * d
+        }
+        q[shift] = c;
+        r.pop();
+        r = z_mod(&r, m)?;
+    }
+    Ok((z_mod(&q, m)?, r))
+}
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn hensel(
+    f: &[Integer],
+    mut g: Vec<Integer>,
+    mut h: Vec<Integer>,
+    mut s: Vec<Integer>,
+    mut t: Vec<Integer>,
+    p: u64,
+    bound: &Integer,
+) -> Result<(Vec<Integer>, Vec<Integer>, Integer), Error> {
+    let p = Integer::from(p);
+    let mut m = p.clone();
+    while &m <= bound {
+        //any power of p up to m^2 lifts, so stop a factor of p past bound instead of squaring
+        //far past it and keep the products in reach of an i128
+        let mut next = fit(m.clone() * &m)?;
+        while &(next.clone() / &p) > bound {
+            next /= &p
+        }
+        m = next;
+        let e = z_mod(&z_sub(f, &z_mul(&g, &h)?)?, &m)?;
+        let (q, r) = z_divrem_monic(&z_mul(&s, &e)?, &h, &m)?;
+        g = z_mod(&z_add(&z_add(&g, &z_mul(&t, &e)?)?, &z_mul(&q, &g)?)?, &m)?;
+        h = z_mod(&z_add(&h, &r)?, &m)?;
+        let b = z_mod(
+            &z_sub(
+                &z_add(&z_mul(&s, &g)?, &z_mul(&t, &h)?)?,
+                &[Integer::from(1)],
+            )?,
+            &m,
+        )?;
+        let (c, d) = z_divrem_monic(&z_mul(&s, &b)?, &h, &m)?;
+        s = z_mod(&z_sub(&s, &d)?, &m)?;
+        t = z_mod(&z_sub(&z_sub(&t, &z_mul(&t, &b)?)?, &z_mul(&c, &g)?)?, &m)?;
+    }
+    Ok((g, h, m))
+}
+fn symmetric(a: &[Integer], m: &Integer) -> Result<Vec<Integer>, Error> {
+    let half = m.clone() / 2u32;
+    exact(
+        a.iter()
+            .map(|c| {
+                let c = c.clone().modulo(m);
+                if c > half { c - m } else { c }
+            })
+            .collect(),
     )
 }
+fn z_primitive(a: Vec<Integer>) -> Result<Vec<Integer>, Error> {
+    let mut g = a.iter().fold(Integer::new(), |g, c| g.gcd(c));
+    if a.last().is_some_and(|c| c.cmp0() == Ordering::Less) {
+        g = -g
+    }
+    if g == 0 {
+        return exact(a);
+    }
+    exact(a.into_iter().map(|c| c / &g).collect())
+}
+///a/b when b divides a, giving up once a quotient coefficient passes bound which no factor
+///of a can, so the quotients of wrong candidates stop before they outgrow an i128
+fn z_divides(a: &[Integer], b: &[Integer], bound: &Integer) -> Result<Option<Vec<Integer>>, Error> {
+    let mut r = a.to_vec();
+    let Some(lead) = b.last() else {
+        return Ok(None);
+    };
+    if r.len() < b.len() {
+        return Ok(None);
+    }
+    let mut q = vec![Integer::new(); r.len() + 1 - b.len()];
+    while r.len() >= b.len() {
+        let shift = r.len() - b.len();
+        let last = r.last().unwrap();
+        if !last.is_divisible(lead) {
+            return Ok(None);
+        }
+        let c = last.clone() / lead;
+        if &c.clone().abs() > bound {
+            return Ok(None);
+        }
+        for (k, d) in b.iter().enumerate() {
+            r[k + shift] -= c.clone() * d
+        }
+        q[shift] = c;
+        r.pop();
+        while r.last().is_some_and(|c| *c == 0) {
+            r.pop();
+        }
+    }
+    Ok(if exact(r)?.is_empty() {
+        Some(exact(q)?)
+    } else {
+        None
+    })
+}
+fn zassenhaus(f: Vec<Integer>) -> Result<Vec<Vec<Integer>>, Error> {
+    let n = f.len() - 1;
+    if n <= 1 {
+        return Ok(vec![f]);
+    }
+    let lead = f[n].clone();
+    let Some((p, u)) = primes().take(256).find_map(|p| {
+        if lead.is_divisible(&Integer::from(p)) {
+            return None;
+        }
+        let fp = fp_of(&f, p);
+        let d = fp
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(k, c)| c * (k as u64 % p) % p)
+            .collect::<Vec<u64>>();
+        if fp_gcd(&fp, &d, p).len() != 1 {
+            return None;
+        }
+        Some((p, fp_factor(fp, p)))
+    }) else {
+        return Ok(vec![f]);
+    };
+    if u.len() == 1 {
+        return Ok(vec![f]);
+    }
+    let max = f.iter().map(|c| c.clone().abs()).max().unwrap_or_default();
+    let bound = fit((Integer::from(n + 1).sqrt() + 1u32)
+        * Integer::from(2).pow(n as u32)
+        * max
+        * lead.clone().abs()
+        * 2u32)?;
+    let mut lifted = Vec::new();
+    let mut rest = f.clone();
+    let mut m = Integer::from(p);
+    for (k, v) in u.iter().enumerate() {
+        if k + 1 == u.len() {
+            lifted.push(z_mod(&rest, &m)?);
+            break;
+        }
+        let w = u[k + 1..].iter().fold(vec![1], |a, b| fp_mul(&a, b, p));
+        let l = rest.last().unwrap().mod_u(p as u32) as u64;
+        let g = v.iter().map(|c| c * l % p).collect::<Vec<u64>>();
+        let (s, t) = fp_gcdex(&g, &w, p);
+        let z = |a: &[u64]| {
+            a.iter()
+                .map(|c| Integer::from(*c))
+                .collect::<Vec<Integer>>()
+        };
+        let (g, h, modulus) = hensel(&rest, z(&g), z(&w), z(&s), z(&t), p, &bound)?;
+        let inv = rest
+            .last()
+            .unwrap()
+            .clone()
+            .invert(&modulus)
+            .unwrap_or_default();
+        lifted.push(z_mod(
+            &g.iter().map(|c| c.clone() * &inv).collect::<Vec<Integer>>(),
+            &modulus,
+        )?);
+        m = modulus;
+        rest = h;
+    }
+    let mut factors = Vec::new();
+    let mut f = f;
+    let mut size = 1;
+    while 2 * size <= lifted.len() {
+        let mut found = None;
+        let mut set = (0..size).collect::<Vec<usize>>();
+        'outer: loop {
+            let lead = f.last().unwrap().clone();
+            let g = set
+                .iter()
+                .try_fold(vec![lead], |a, i| z_mod(&z_mul(&a, &lifted[*i])?, &m))?;
+            let g = z_primitive(symmetric(&g, &m)?)?;
+            if let Some(q) = z_divides(&f, &g, &bound)? {
+                found = Some((set.clone(), g, q));
+                break;
+            }
+            let mut i = size;
+            while i > 0 {
+                i -= 1;
+                if set[i] < lifted.len() - size + i {
+                    set[i] += 1;
+                    for j in i + 1..size {
+                        set[j] = set[j - 1] + 1
+                    }
+                    continue 'outer;
+                }
+            }
+            break;
+        }
+        match found {
+            Some((set, g, q)) => {
+                factors.push(g);
+                f = q;
+                for i in set.into_iter().rev() {
+                    lifted.remove(i);
+                }
+            }
+            None => size += 1,
+        }
+    }
+    factors.push(f);
+    Ok(factors)
+}
+#[allow(clippy::type_complexity)]
+fn factor_poly(f: &[Rational]) -> Result<(Rational, Vec<(Vec<Integer>, usize)>), Error> {
+    let mut list = Vec::new();
+    for (g, k) in square_free(f)? {
+        for h in zassenhaus(primitive(&g)?.1)? {
+            list.push((z_primitive(h)?, k))
+        }
+    }
+    list.sort_by(|a, b| a.0.len().cmp(&b.0.len()).then_with(|| a.0.cmp(&b.0)));
+    let content = list
+        .iter()
+        .fold(f.last().cloned().unwrap_or_default(), |c, (h, k)| {
+            c / Rational::from(h.last().unwrap().clone().pow(*k as u32))
+        });
+    Ok((fit(content)?, list))
+}
+fn q_of(expr: &Expr, var: &str, prec: Precision) -> Result<(Vec<Rational>, Vec<Rational>), Error> {
+    let p = rational(expr, var, prec).ok_or(Error::Unsupported("not a rational function", None))?;
+    let q = |p: &[Complex]| -> Result<Vec<Rational>, Error> {
+        Ok(q_trim(
+            p.iter()
+                .map(to_rational)
+                .collect::<Option<Vec<Rational>>>()
+                .ok_or(Error::Domain(
+                    "polynomial coefficients must be rational",
+                    None,
+                ))?,
+        ))
+    };
+    let (top, bottom) = (q(&p.quotient)?, q(&p.divisor)?);
+    if bottom.is_empty() {
+        return Err(Error::Domain("division by zero polynomial", None));
+    }
+    Ok((top, bottom))
+}
+fn q_poly(expr: &Expr, var: &str, prec: Precision) -> Result<Vec<Rational>, Error> {
+    match q_of(expr, var, prec)? {
+        (top, bottom) if bottom.len() == 1 => q_scale(&top, &bottom[0].clone().recip()),
+        _ => Err(Error::Unsupported("not a polynomial", None)),
+    }
+}
+fn q_expr(p: &[Rational], var: &str, prec: Precision) -> Expr {
+    poly_expr(
+        &p.iter()
+            .map(|c| from_rational(c, prec))
+            .collect::<Vec<Complex>>(),
+        var,
+    )
+    .to_expr(prec)
+}
+fn fraction(r: &Rational, prec: Precision) -> Expr {
+    let top = num(Complex::with_val(prec, r.numer()));
+    if r.denom() == 1 {
+        top
+    } else {
+        binary(Division, top, num(Complex::with_val(prec, r.denom())))
+    }
+}
+fn degree(p: &[Rational]) -> usize {
+    p.len().saturating_sub(1)
+}
+fn q_resultant(a: &[Rational], b: &[Rational]) -> Result<Rational, Error> {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    let mut res = Rational::from(1);
+    loop {
+        if a.is_empty() || b.is_empty() {
+            return Ok(Rational::new());
+        }
+        let (m, n) = (degree(&a), degree(&b));
+        let lead = b.last().unwrap().clone();
+        if n == 0 {
+            return fit(res * lead.pow(m as u32));
+        }
+        let r = q_divrem(&a, &b)?.1;
+        if r.is_empty() {
+            return Ok(Rational::new());
+        }
+        res = fit(res * lead.pow((m - degree(&r)) as u32))?;
+        if m * n % 2 == 1 {
+            res = -res
+        }
+        (a, b) = (b, r)
+    }
+}
+pub fn factorize(expr: &Expr, var: &str, prec: Precision) -> Result<Expr, Error> {
+    let f = q_poly(expr, var, prec)?;
+    if f.len() <= 1 {
+        return Ok(fraction(&f.first().cloned().unwrap_or_default(), prec));
+    }
+    let (content, list) = factor_poly(&f)?;
+    let mut expr = num(Complex::with_val(prec, content.numer()));
+    for (h, k) in list {
+        let h = q_expr(
+            &h.into_iter().map(Rational::from).collect::<Vec<Rational>>(),
+            var,
+            prec,
+        );
+        expr = times(expr, power(h, int(k as i32, prec), prec), prec)
+    }
+    Ok(ratio(
+        expr,
+        num(Complex::with_val(prec, content.denom())),
+        prec,
+    ))
+}
+pub fn pgcd(a: &Expr, b: &Expr, var: &str, prec: Precision) -> Result<Expr, Error> {
+    let g = q_gcd(&q_poly(a, var, prec)?, &q_poly(b, var, prec)?)?;
+    Ok(q_expr(&g, var, prec))
+}
+pub fn pdiv(a: &Expr, b: &Expr, var: &str, prec: Precision) -> Result<Expr, Error> {
+    let b = q_poly(b, var, prec)?;
+    if b.is_empty() {
+        return Err(Error::Domain("division by zero polynomial", None));
+    }
+    let (q, r) = q_divrem(&q_poly(a, var, prec)?, &b)?;
+    Ok(Expr::Vector(vec![
+        q_expr(&q, var, prec),
+        q_expr(&r, var, prec),
+    ]))
+}
+pub fn prem(a: &Expr, b: &Expr, var: &str, prec: Precision) -> Result<Expr, Error> {
+    let (a, b) = (q_poly(a, var, prec)?, q_poly(b, var, prec)?);
+    if b.is_empty() {
+        return Err(Error::Domain("division by zero polynomial", None));
+    }
+    let n = (degree(&a) + 1).saturating_sub(degree(&b)) as u32;
+    let r = q_divrem(&a, &b)?.1;
+    Ok(q_expr(
+        &q_scale(&r, &b.last().unwrap().clone().pow(n))?,
+        var,
+        prec,
+    ))
+}
+pub fn resultant(a: &Expr, b: &Expr, var: &str, prec: Precision) -> Result<Expr, Error> {
+    let r = q_resultant(&q_poly(a, var, prec)?, &q_poly(b, var, prec)?)?;
+    Ok(fraction(&r, prec))
+}
+pub fn discriminant(a: &Expr, var: &str, prec: Precision) -> Result<Expr, Error> {
+    let a = q_poly(a, var, prec)?;
+    let n = degree(&a);
+    if n == 0 {
+        return Err(Error::Domain(
+            "discriminant needs a nonconstant polynomial",
+            None,
+        ));
+    }
+    let mut r = fit(q_resultant(&a, &q_deriv(&a)?)? / a.last().unwrap())?;
+    if n * (n - 1) / 2 % 2 == 1 {
+        r = -r
+    }
+    Ok(fraction(&r, prec))
+}
+#[allow(clippy::type_complexity)]
+fn partial_fractions(
+    top: &[Rational],
+    bottom: &[Rational],
+) -> Result<(Vec<Rational>, Vec<(Vec<Rational>, Vec<Rational>, usize)>), Error> {
+    let lead = bottom.last().unwrap().clone().recip();
+    let (top, bottom) = (q_scale(top, &lead)?, q_scale(bottom, &lead)?);
+    let (q, r) = q_divrem(&top, &bottom)?;
+    let g = q_gcd(&r, &bottom)?;
+    let (r, bottom) = (q_divrem(&r, &g)?.0, q_divrem(&bottom, &g)?.0);
+    if r.is_empty() || bottom.len() == 1 {
+        return Ok((
+            q_add(&q, &q_scale(&r, &bottom[0].clone().recip())?)?,
+            Vec::new(),
+        ));
+    }
+    let mut list = Vec::new();
+    for (h, k) in factor_poly(&bottom)?.1 {
+        let h = h.into_iter().map(Rational::from).collect::<Vec<Rational>>();
+        let l = h.last().unwrap().clone();
+        let f = q_monic(h.clone())?;
+        let part = q_pow(&f, k)?;
+        let (_, s, _) = q_gcdex(&q_divrem(&bottom, &part)?.0, &part)?;
+        let mut a = q_divrem(&q_mul(&r, &s)?, &part)?.1;
+        for j in (1..=k).rev() {
+            let (quo, rem) = q_divrem(&a, &f)?;
+            a = quo;
+            if !rem.is_empty() {
+                list.push((q_scale(&rem, &l.clone().pow(j as u32))?, h.clone(), j))
+            }
+        }
+    }
+    Ok((q, list))
+}
+pub fn apart(expr: &Expr, var: &str, prec: Precision) -> Result<Expr, Error> {
+    let (top, bottom) = q_of(expr, var, prec)?;
+    let (q, list) = partial_fractions(&top, &bottom)?;
+    let mut expr = q_expr(&q, var, prec);
+    for (c, h, j) in list {
+        let (c, rem) = primitive(&c)?;
+        let rem = rem
+            .into_iter()
+            .map(Rational::from)
+            .collect::<Vec<Rational>>();
+        let top = times(
+            num(Complex::with_val(prec, c.numer().clone().abs())),
+            q_expr(&rem, var, prec),
+            prec,
+        );
+        let bottom = times(
+            num(Complex::with_val(prec, c.denom())),
+            power(q_expr(&h, var, prec), int(j as i32, prec), prec),
+            prec,
+        );
+        expr = if c < 0 {
+            minus(expr, ratio(top, bottom, prec), prec)
+        } else {
+            plus(expr, ratio(top, bottom, prec), prec)
+        }
+    }
+    Ok(expr)
+}
+fn log_abs(u: Expr) -> Expr {
+    ln(call("abs", u))
+}
+fn surd(r: &Rational) -> (Rational, Option<Integer>) {
+    let mut m = r.numer().clone() * r.denom();
+    let mut k = Integer::from(1);
+    let mut d = Integer::from(2);
+    while d.clone() * &d <= m && d < 100000 {
+        let square = d.clone() * &d;
+        while m.is_divisible(&square) {
+            m /= &square;
+            k *= &d
+        }
+        d += 1
+    }
+    let k = Rational::from((k, r.denom().clone()));
+    if m.is_perfect_square() {
+        (k * m.sqrt(), None)
+    } else {
+        (k, Some(m))
+    }
+}
+fn split(expr: &Expr, prec: Precision) -> (Rational, Expr) {
+    let rational = |e: &Expr| value(e).and_then(to_rational);
+    match expr {
+        Expr::Group(e) => split(e, prec),
+        _ if rational(expr).is_some() => (rational(expr).unwrap(), int(1, prec)),
+        Expr::Binary(Division, a, b) if rational(b).is_some_and(|r| r != 0) => {
+            let (r, core) = split(a, prec);
+            (r / rational(b).unwrap(), core)
+        }
+        Expr::Binary(Multiplication | InternalMultiplication, a, b) if rational(a).is_some() => {
+            let (r, core) = split(b, prec);
+            (r * rational(a).unwrap(), core)
+        }
+        Expr::Binary(Division, a, b) => {
+            let (r, core) = split(a, prec);
+            (r, over(core, *b.clone(), prec))
+        }
+        _ => (Rational::from(1), expr.clone()),
+    }
+}
+fn scaled(r: &Rational, expr: Expr, prec: Precision) -> Expr {
+    let (s, core) = split(&expr, prec);
+    let r = s * r;
+    let top = times(num(Complex::with_val(prec, r.numer())), core, prec);
+    ratio(top, num(Complex::with_val(prec, r.denom())), prec)
+}
+fn coefficient(c: Expr, expr: Expr, prec: Precision) -> Expr {
+    match value(&c) {
+        Some(v) => match to_rational(v) {
+            Some(r) => scaled(&r, expr, prec),
+            None => {
+                let (r, core) = split(&expr, prec);
+                times(num(v.clone() * Float::with_val(prec, &r)), core, prec)
+            }
+        },
+        None => times(c, expr, prec),
+    }
+}
+fn product_of(a: Expr, b: Expr, prec: Precision) -> Expr {
+    let (r, a) = split(&a, prec);
+    let (s, b) = split(&b, prec);
+    scaled(&(r * s), times(a, b, prec), prec)
+}
+fn add_scaled(expr: Expr, r: &Rational, term: Expr, prec: Precision) -> Expr {
+    match r.cmp0() {
+        Ordering::Less => minus(expr, scaled(&-r.clone(), term, prec), prec),
+        Ordering::Greater => plus(expr, scaled(r, term, prec), prec),
+        Ordering::Equal => expr,
+    }
+}
+fn q_integral(p: &[Rational]) -> Result<Vec<Rational>, Error> {
+    let mut q = vec![Rational::new()];
+    q.extend(
+        p.iter()
+            .enumerate()
+            .map(|(k, c)| c.clone() / Rational::from(k + 1)),
+    );
+    exact(q_trim(q))
+}
+fn rational_integral(expr: &Expr, var: &str, options: Options) -> Result<Option<Expr>, Error> {
+    let prec = options.precision();
+    let Ok((top, bottom)) = q_of(expr, var, prec) else {
+        return Ok(None);
+    };
+    let (q, list) = partial_fractions(&top, &bottom)?;
+    let mut expr = q_expr(&q_integral(&q)?, var, prec);
+    let linear = |p: &[Rational]| -> Result<Expr, Error> {
+        let (c, p) = primitive(p)?;
+        let p = p.into_iter().map(Rational::from).collect::<Vec<Rational>>();
+        Ok(scaled(&c, q_expr(&p, var, prec), prec))
+    };
+    for (c, h, j) in list {
+        let hx = || q_expr(&h, var, prec);
+        match degree(&h) {
+            1 if j == 1 => {
+                expr = add_scaled(expr, &fit(c[0].clone() / &h[1])?, log_abs(hx()), prec)
+            }
+            1 => {
+                let n = j as i32 - 1;
+                let term = over(
+                    int(1, prec),
+                    power(hx(), int(n, prec), options.precision()),
+                    options.precision(),
+                );
+                expr = add_scaled(expr, &fit(-c[0].clone() / &h[1] / n)?, term, prec)
+            }
+            2 => {
+                let (c0, b, a) = (&h[0], &h[1], &h[2]);
+                let zero = Rational::new();
+                let (p, q) = (c.get(1).unwrap_or(&zero), &c[0]);
+                let alpha = fit(p.clone() / (a.clone() * 2u32))?;
+                let beta = fit(q.clone() - alpha.clone() * b)?;
+                let delta = fit(a.clone() * c0 * 4u32 - b.clone() * b)?;
+                let derivative = [b.clone(), a.clone() * 2u32];
+                let mut integral = match surd(&delta.clone().abs()) {
+                    (k, None) if delta > 0 => {
+                        let arg = linear(&q_scale(&derivative, &k.clone().recip())?)?;
+                        let atan = times(radians(options), call("atan", arg), prec);
+                        scaled(&(Rational::from(2) / k), atan, prec)
+                    }
+                    (k, Some(m)) if delta > 0 => {
+                        let root = || call("sqrt", num(Complex::with_val(prec, &m)));
+                        let arg = over(
+                            linear(&q_scale(&derivative, &k.clone().recip())?)?,
+                            root(),
+                            prec,
+                        );
+                        let atan = times(radians(options), call("atan", arg), prec);
+                        scaled(
+                            &(Rational::from(2) / k / &m),
+                            times(root(), atan, prec),
+                            prec,
+                        )
+                    }
+                    (k, m) => {
+                        let m = m.unwrap_or(Integer::from(1));
+                        let half = (a.clone() * 2u32).recip();
+                        let root = || {
+                            let sqrt = call("sqrt", num(Complex::with_val(prec, &m)));
+                            scaled(&(k.clone() * &half), sqrt, prec)
+                        };
+                        let monic = q_expr(&q_scale(&derivative, &half)?, var, prec);
+                        let log = log_abs(over(
+                            minus(monic.clone(), root(), prec),
+                            plus(monic, root(), prec),
+                            prec,
+                        ));
+                        let root = call("sqrt", num(Complex::with_val(prec, &m)));
+                        scaled(&(k * &m).recip(), times(root, log, prec), prec)
+                    }
+                };
+                for k in 2..=j {
+                    let k1 = Rational::from(k - 1);
+                    let term = over(
+                        linear(&derivative)?,
+                        power(hx(), int(k as i32 - 1, prec), prec),
+                        prec,
+                    );
+                    integral = add_scaled(
+                        scaled(&(k1.clone() * &delta).recip(), term, prec),
+                        &(Rational::from(2 * (2 * k - 3)) * a / (k1 * &delta)),
+                        integral,
+                        prec,
+                    )
+                }
+                let log = if j == 1 {
+                    log_abs(hx())
+                } else {
+                    over(
+                        int(1, prec),
+                        power(hx(), int(j as i32 - 1, prec), prec),
+                        prec,
+                    )
+                };
+                let alpha = if j == 1 {
+                    alpha
+                } else {
+                    -alpha / (j as u32 - 1)
+                };
+                expr = add_scaled(expr, &alpha, log, prec);
+                expr = add_scaled(expr, &beta, integral, prec)
+            }
+            _ => return Ok(None),
+        }
+    }
+    Ok(Some(expr))
+}
+///c/sqrt(ax^2+bx+c0), the derivatives of asin, acos and asinh after completing the square
+fn arc_integral(expr: &Expr, var: &str, options: Options) -> Result<Option<Expr>, Error> {
+    let p = options.precision();
+    let mut list = Vec::new();
+    factors(expr, &mut list, p);
+    let mut c = Rational::from(1);
+    let mut root = None;
+    for f in list {
+        match f {
+            Expr::Binary(Exponent, q, n)
+                if root.is_none()
+                    && value(&n).is_some_and(|n| *n == Complex::with_val(p, -0.5)) =>
+            {
+                root = Some(*q)
+            }
+            f => match value(&f).and_then(to_rational) {
+                Some(r) => c *= r,
+                None => return Ok(None),
+            },
+        }
+    }
+    let Some(q) = root else {
+        return Ok(None);
+    };
+    let Ok(q) = q_poly(&q, var, p) else {
+        return Ok(None);
+    };
+    if degree(&q) != 2 {
+        return Ok(None);
+    }
+    let (a, b) = (&q[2], &q[1]);
+    let shift = fit(b.clone() / (a.clone() * 2u32))?;
+    let m = fit(q[0].clone() - shift.clone() * b / 2u32)?;
+    if m == 0 {
+        return Ok(None);
+    }
+    let sqrt = |r: &Rational| match surd(r) {
+        (k, Some(m)) => scaled(&k, call("sqrt", num(Complex::with_val(p, &m))), p),
+        (k, None) => scaled(&k, int(1, p), p),
+    };
+    let u = q_expr(&[shift, Rational::from(1)], var, p);
+    let arg = product_of(sqrt(&fit(a.clone() / &m)?.abs()), u.clone(), p);
+    let from_rad = || over(int(1, p), radians(options), p);
+    let f = match (a.cmp0(), m.cmp0()) {
+        (Ordering::Less, _) if m < 0 => return Ok(None),
+        (Ordering::Less, _) if c < 0 => {
+            c = -c;
+            times(from_rad(), call("acos", arg), p)
+        }
+        (Ordering::Less, _) => times(from_rad(), call("asin", arg), p),
+        (_, Ordering::Greater) => call("asinh", arg),
+        _ => {
+            let rest = q_expr(&q_scale(&q, &a.clone().recip())?, var, p);
+            log_abs(plus(u, call("sqrt", rest), p))
+        }
+    };
+    let coef = sqrt(&a.clone().abs().recip());
+    Ok(Some(scaled(&c, product_of(coef, f, p), p)))
+}
+fn table(s: &str, u: &Expr, options: Options) -> Option<Expr> {
+    let p = options.precision();
+    let u = || u.clone();
+    let to_rad = radians(options);
+    let from_rad = || over(int(1, p), to_rad.clone(), p);
+    let sq = |e: Expr| power(e, int(2, p), p);
+    Some(match s {
+        "sin" => negate(times(from_rad(), call("cos", u()), p), p),
+        "cos" => times(from_rad(), call("sin", u()), p),
+        "tan" => negate(times(from_rad(), log_abs(call("cos", u())), p), p),
+        "cot" => times(from_rad(), log_abs(call("sin", u())), p),
+        "sec" => times(
+            from_rad(),
+            log_abs(plus(call("sec", u()), call("tan", u()), p)),
+            p,
+        ),
+        "csc" => negate(
+            times(
+                from_rad(),
+                log_abs(plus(call("csc", u()), call("cot", u()), p)),
+                p,
+            ),
+            p,
+        ),
+        "asin" | "arcsin" => plus(
+            times(u(), call("asin", u()), p),
+            times(from_rad(), call("sqrt", minus(int(1, p), sq(u()), p)), p),
+            p,
+        ),
+        "acos" | "arccos" => minus(
+            times(u(), call("acos", u()), p),
+            times(from_rad(), call("sqrt", minus(int(1, p), sq(u()), p)), p),
+            p,
+        ),
+        "atan" | "arctan" => minus(
+            times(u(), call("atan", u()), p),
+            times(
+                from_rad(),
+                ratio(ln(plus(sq(u()), int(1, p), p)), int(2, p), p),
+                p,
+            ),
+            p,
+        ),
+        "sinh" => call("cosh", u()),
+        "cosh" => call("sinh", u()),
+        "tanh" => ln(call("cosh", u())),
+        "coth" => log_abs(call("sinh", u())),
+        "asinh" | "arcsinh" => minus(
+            times(u(), call("asinh", u()), p),
+            call("sqrt", plus(sq(u()), int(1, p), p)),
+            p,
+        ),
+        "acosh" | "arccosh" => minus(
+            times(u(), call("acosh", u()), p),
+            call("sqrt", minus(sq(u()), int(1, p), p)),
+            p,
+        ),
+        "atanh" | "arctanh" => plus(
+            times(u(), call("atanh", u()), p),
+            ratio(ln(minus(int(1, p), sq(u()), p)), int(2, p), p),
+            p,
+        ),
+        "exp" | "aln" => call("exp", u()),
+        "ln" | "aexp" | "log" => minus(times(u(), call("ln", u()), p), u(), p),
+        "sqrt" | "asquare" => ratio(
+            times(int(2, p), times(u(), call("sqrt", u()), p), p),
+            int(3, p),
+            p,
+        ),
+        "cbrt" | "acube" => ratio(
+            times(int(3, p), times(u(), call("cbrt", u()), p), p),
+            int(4, p),
+            p,
+        ),
+        "abs" => ratio(times(u(), call("abs", u()), p), int(2, p), p),
+        "recip" => log_abs(u()),
+        "square" | "asqrt" => ratio(power(u(), int(3, p), p), int(3, p), p),
+        "cube" | "acbrt" => ratio(power(u(), int(4, p), p), int(4, p), p),
+        _ => return None,
+    })
+}
+fn primitive_of(expr: &Expr, var: &str, options: Options) -> Option<(Expr, Expr)> {
+    let p = options.precision();
+    let inner = |e: &Expr| match e {
+        Expr::Group(e) => *e.clone(),
+        e => e.clone(),
+    };
+    let from_rad = || over(int(1, p), radians(options), p);
+    match expr {
+        Expr::Group(e) => primitive_of(e, var, options),
+        Expr::Var(s) if s == var => Some((
+            expr.clone(),
+            ratio(power(expr.clone(), int(2, p), p), int(2, p), p),
+        )),
+        Expr::Binary(Exponent, a, b) if !depends(b, var) => {
+            if is_value(b, -1) {
+                return Some((*a.clone(), log_abs(*a.clone())));
+            }
+            if let Expr::Call(s, args) = a.as_ref() {
+                if args.len() == 1 && is_value(b, 2) {
+                    let u = args[0].clone();
+                    let f = match s.as_str() {
+                        "sec" => Some(times(from_rad(), call("tan", u.clone()), p)),
+                        "csc" => Some(negate(times(from_rad(), call("cot", u.clone()), p), p)),
+                        "sech" => Some(call("tanh", u.clone())),
+                        "csch" => Some(negate(call("coth", u.clone()), p)),
+                        _ => None,
+                    };
+                    if let Some(f) = f {
+                        return Some((u, f));
+                    }
+                }
+            }
+            let n = plus(*b.clone(), int(1, p), p);
+            match value(&n).and_then(to_rational) {
+                Some(r) => Some((
+                    *a.clone(),
+                    scaled(
+                        &r.clone().recip(),
+                        factor(a, &Sum::constant(value(&n)?.clone()), p),
+                        p,
+                    ),
+                )),
+                None => Some((*a.clone(), over(power(*a.clone(), n.clone(), p), n, p))),
+            }
+        }
+        Expr::Binary(Exponent, a, b) if !depends(a, var) => {
+            if value(a).is_some_and(|a| near(a, &Complex::with_val(p, 1).exp())) {
+                Some((inner(b), call("exp", inner(b))))
+            } else {
+                Some((inner(b), over(expr.clone(), call("ln", inner(a)), p)))
+            }
+        }
+        Expr::Binary(Division, a, b) if !depends(a, var) => {
+            Some((*b.clone(), times(*a.clone(), log_abs(*b.clone()), p)))
+        }
+        Expr::Call(s, args) if args.len() == 1 => {
+            Some((args[0].clone(), table(s, &args[0], options)?))
+        }
+        _ => None,
+    }
+}
+fn factors(expr: &Expr, list: &mut Vec<Expr>, prec: Precision) {
+    match expr {
+        Expr::Group(e) => factors(e, list, prec),
+        Expr::Binary(Multiplication | InternalMultiplication, a, b) => {
+            factors(a, list, prec);
+            factors(b, list, prec)
+        }
+        Expr::Binary(Division, a, b) => {
+            factors(a, list, prec);
+            let mut b = b.as_ref();
+            while let Expr::Group(e) = b {
+                b = e
+            }
+            let mut rest = Vec::new();
+            factors(b, &mut rest, prec);
+            for f in rest {
+                list.push(match &f {
+                    Expr::Binary(Exponent, c, n) if value(n).is_some() => {
+                        power(*c.clone(), num(-value(n).unwrap().clone()), prec)
+                    }
+                    _ => power(f.clone(), int(-1, prec), prec),
+                })
+            }
+        }
+        Expr::Call(s, args) if s == "sqrt" && args.len() == 1 => list.push(power(
+            args[0].clone(),
+            num(Complex::with_val(prec, 0.5)),
+            prec,
+        )),
+        _ => list.push(expr.clone()),
+    }
+}
+fn product(list: Vec<Expr>, prec: Precision) -> Expr {
+    list.into_iter()
+        .fold(int(1, prec), |a, b| times(a, b, prec))
+}
+fn is_polynomial(expr: &Expr, var: &str, prec: Precision) -> bool {
+    rational(expr, var, prec).is_some_and(|r| {
+        let mut d = r.divisor;
+        trim(&mut d);
+        d.len() == 1
+    })
+}
+///unwraps a found integral, or gives up on this way of integrating
+macro_rules! found {
+    ($e:expr) => {
+        match $e? {
+            Some(e) => e,
+            None => return Ok(None),
+        }
+    };
+}
+fn integral(expr: &Expr, var: &str, options: Options, depth: usize) -> Result<Option<Expr>, Error> {
+    let p = options.precision();
+    if !depends(expr, var) {
+        return Ok(Some(times(expr.clone(), Expr::Var(var.to_string()), p)));
+    }
+    if depth > 6 {
+        return Ok(None);
+    }
+    let i = |e: &Expr| integral(e, var, options, depth + 1);
+    match expr {
+        Expr::Group(e) => return i(e),
+        Expr::Binary(Plus, a, b) => return Ok(Some(plus(found!(i(a)), found!(i(b)), p))),
+        Expr::Binary(Minus, a, b) => return Ok(Some(minus(found!(i(a)), found!(i(b)), p))),
+        _ => {}
+    }
+    if let Some(r) = arc_integral(expr, var, options)? {
+        return Ok(Some(r));
+    }
+    let mut list = Vec::new();
+    factors(expr, &mut list, p);
+    let (consts, list): (Vec<Expr>, Vec<Expr>) = list.into_iter().partition(|f| !depends(f, var));
+    if !consts.is_empty() {
+        let f = found!(i(&product(list, p)));
+        return Ok(Some(coefficient(product(consts, p), f, p)));
+    }
+    if let Some(r) = rational_integral(expr, var, options)? {
+        return Ok(Some(r));
+    }
+    if let Some(r) = rational(expr, var, p) {
+        let mut d = r.divisor;
+        trim(&mut d);
+        if let [d] = d.as_slice() {
+            let mut q = vec![Complex::new(p)];
+            q.extend(
+                r.quotient
+                    .iter()
+                    .enumerate()
+                    .map(|(k, c)| c.clone() / d / (k + 1)),
+            );
+            return Ok(Some(poly_expr(&q, var).to_expr(p)));
+        }
+    }
+    let single = match list.as_slice() {
+        [f] => f,
+        _ => expr,
+    };
+    if let Some((u, f)) = primitive_of(single, var, options) {
+        let du = simplify(&differentiate(&u, var, options), p);
+        if !depends(&du, var) {
+            return Ok(Some(coefficient(over(int(1, p), du, p), f, p)));
+        }
+    }
+    let others = |k: usize| {
+        let mut rest = list.clone();
+        rest.remove(k);
+        product(rest, p)
+    };
+    if list.len() > 1 {
+        for (k, f) in list.iter().enumerate() {
+            let mut candidates = vec![(
+                f.clone(),
+                ratio(power(f.clone(), int(2, p), p), int(2, p), p),
+            )];
+            candidates.extend(primitive_of(f, var, options));
+            for (u, g) in candidates {
+                let c = simplify(&over(others(k), differentiate(&u, var, options), p), p);
+                if !depends(&c, var) {
+                    return Ok(Some(coefficient(c, g, p)));
+                }
+            }
+        }
+    }
+    for (k, f) in list.iter().enumerate() {
+        let rest = others(k);
+        if matches!(f, Expr::Call(s, _) if matches!(s.as_str(), "ln" | "log" | "atan" | "arctan" | "asin" | "arcsin" | "acos" | "arccos" | "asinh" | "arcsinh" | "acosh" | "arccosh" | "atanh" | "arctanh"))
+            && is_polynomial(&rest, var, p)
+        {
+            let q = found!(i(&rest));
+            let w = found!(i(&product_of(q.clone(), differentiate(f, var, options), p)));
+            return Ok(Some(minus(product_of(q, f.clone(), p), w, p)));
+        }
+    }
+    for (k, f) in list.iter().enumerate() {
+        if is_polynomial(f, var, p) {
+            if let Some(v) = i(&others(k))? {
+                if let Some(w) = i(&product_of(differentiate(f, var, options), v.clone(), p))? {
+                    return Ok(Some(minus(product_of(f.clone(), v, p), w, p)));
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+pub fn antiderivative(expr: &Expr, var: &str, options: Options) -> Result<Expr, Error> {
+    integral(expr, var, options, 0)?.ok_or(Error::Unsupported(
+        "no elementary antiderivative found",
+        None,
+    ))
+}
+fn rename(expr: Expr, from: &str, to: &str) -> Expr {
+    let list = |args: Vec<Expr>| {
+        args.into_iter()
+            .map(|a| rename(a, from, to))
+            .collect::<Vec<Expr>>()
+    };
+    match expr {
+        Expr::Var(s) if s == from => Expr::Var(to.to_string()),
+        Expr::Call(s, args)
+            if is_lazy(&s)
+                && args
+                    .iter()
+                    .take_while(|a| matches!(a, Expr::Var(_)))
+                    .any(|a| a == &Expr::Var(from.to_string())) =>
+        {
+            let k = args
+                .iter()
+                .position(|a| !matches!(a, Expr::Var(_)))
+                .map(|k| k + 1)
+                .unwrap_or(args.len());
+            let mut args = args;
+            let rest = list(args.split_off(k));
+            args.extend(rest);
+            Expr::Call(s, args)
+        }
+        Expr::Call(s, args) => Expr::Call(s, list(args)),
+        Expr::Vector(args) => Expr::Vector(list(args)),
+        Expr::Apply(s, e) => Expr::Apply(s, Box::new(rename(*e, from, to))),
+        Expr::Group(e) => Expr::Group(Box::new(rename(*e, from, to))),
+        Expr::Not(e) => Expr::Not(Box::new(rename(*e, from, to))),
+        Expr::Binary(op, a, b) => Expr::Binary(
+            op,
+            Box::new(rename(*a, from, to)),
+            Box::new(rename(*b, from, to)),
+        ),
+        e => e,
+    }
+}
+fn bound(tokens: &[NumStr], var: &str) -> bool {
+    let mut count = 0;
+    for (i, t) in tokens.iter().enumerate().rev() {
+        match t {
+            RightBracket | RightCurlyBracket => count += 1,
+            LeftBracket | LeftCurlyBracket if count > 0 => count -= 1,
+            LeftBracket
+                if i != 0
+                    && matches!(&tokens[i - 1], Func(s) if is_lazy(s))
+                    && tokens[i + 1..]
+                        .chunks(2)
+                        .take_while(|c| c.len() == 2 && matches!(c[0], Func(_)) && c[1] == Comma)
+                        .any(|c| c[0].str_is(var)) =>
+            {
+                return true;
+            }
+            _ => {}
+        }
+    }
+    false
+}
+pub fn symbolic(
+    output: &mut Vec<NumStr>,
+    func_vars: &[(String, Vec<NumStr>)],
+    options: Options,
+) -> Result<Vec<String>, Error> {
+    let mut free = Vec::new();
+    let mut end_search = output.len();
+    while let Some(i) = (0..end_search).rev().find(|i| {
+        matches!(&output[*i], Func(s) if matches!(s.as_str(), "diff" | "simplify" | "expand" | "collect" | "factor" | "pgcd" | "pdiv" | "prem" | "resultant" | "discriminant" | "apart" | "antiderivative"))
+            && output.get(i + 1) == Some(&LeftBracket)
+    }) {
+        end_search = i;
+        let mut args = Vec::new();
+        let mut start = i + 2;
+        let mut count = 0;
+        let mut end = None;
+        for (j, t) in output.iter().enumerate().skip(i + 2) {
+            match t {
+                LeftBracket | LeftCurlyBracket => count += 1,
+                RightBracket | RightCurlyBracket if count == 0 => {
+                    args.push(start..j);
+                    end = Some(j);
+                    break;
+                }
+                RightBracket | RightCurlyBracket => count -= 1,
+                Comma if count == 0 => {
+                    args.push(start..j);
+                    start = j + 1
+                }
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            return Err(Error::Parse("round bracket err", None));
+        };
+        let get = |k: usize| -> Result<Expr, Error> {
+            Ok(fold(inline(
+                Expr::from_tokens(&output[args[k].clone()])?,
+                func_vars,
+            )?, options.precision()))
+        };
+        let Func(name) = output[i].clone() else {
+            continue;
+        };
+        let var = match (&output[args[0].clone()], name.as_str(), args.len()) {
+            (_, "simplify" | "expand", 1) => None,
+            ([Func(v)], "diff", 2 | 3)
+            | ([Func(v)], "collect" | "factor" | "discriminant" | "apart" | "antiderivative", 2)
+            | ([Func(v)], "pgcd" | "pdiv" | "prem" | "resultant", 3) => Some(v.clone()),
+            (_, "factor", _) => continue,
+            (_, "pgcd" | "pdiv" | "prem" | "resultant", _) => {
+                return Err(Error::Arity(
+                    "expected two expressions and a variable",
+                    None,
+                ));
+            }
+            _ => {
+                return Err(Error::Arity(
+                    "expected an expression and a variable",
+                    None,
+                ));
+            }
+        };
+        let v = var.as_deref().unwrap_or_default();
+        let prec = options.precision();
+        let mut expr = get(var.is_some() as usize)?;
+        match name.as_str() {
+            "diff" => {
+                let n = match args.get(2) {
+                    Some(n) => {
+                        let n = do_math(output[n.clone()].to_vec(), options, func_vars.to_vec())?
+                            .num()?
+                            .number;
+                        if !n.imag().is_zero()
+                            || !n.real().is_integer()
+                            || n.real().is_sign_negative()
+                        {
+                            return Err(Error::Domain("diff order must be a natural number", None));
+                        }
+                        n.real().to_u32_saturating().unwrap_or_default()
+                    }
+                    None => 1,
+                };
+                for _ in 0..n {
+                    expr = fold(differentiate(&expr, v, options), options.precision())
+                }
+            }
+            "collect" => expr = collect(&expr, v, prec),
+            "factor" => expr = factorize(&expr, v, prec)?,
+            "discriminant" => expr = discriminant(&expr, v, prec)?,
+            "apart" => expr = apart(&expr, v, prec)?,
+            "antiderivative" => expr = antiderivative(&expr, v, options)?,
+            "pgcd" => expr = pgcd(&expr, &get(2)?, v, prec)?,
+            "pdiv" => expr = pdiv(&expr, &get(2)?, v, prec)?,
+            "prem" => expr = prem(&expr, &get(2)?, v, prec)?,
+            "resultant" => expr = resultant(&expr, &get(2)?, v, prec)?,
+            "expand" => expr = expand(&expr, prec),
+            _ => expr = simplify(&expr, prec),
+        }
+        if let Some(var) = var {
+            if let Some(v) = var.strip_prefix('@').filter(|v| !v.ends_with('@')) {
+                expr = rename(expr, &var, v)
+            }
+            if !bound(&output[..i], &var) && !free.contains(&var) {
+                free.push(var)
+            }
+        }
+        let mut tokens = vec![LeftBracket];
+        tokens.extend(expr.to_tokens());
+        tokens.push(RightBracket);
+        output.splice(i..=end, tokens);
+    }
+    Ok(free)
+}
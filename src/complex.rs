@@ -1,8 +1,18 @@
+use crate::types::{
+    Complex, Float, Integer, Precision, Rational, fits,
+    float::{
+        Constant::Pi,
+        Special::{Infinity, Nan},
+    },
+    integer::IsPrime,
+    ops::Pow,
+};
 use crate::{
     complex::NumStr::{
-        Comma, Division, Exponent, Func, LeftBracket, LeftCurlyBracket, Matrix, Minus,
-        Multiplication, Num, Plus, RightBracket, RightCurlyBracket, Vector,
+        Comma, Division, Func, LeftBracket, LeftCurlyBracket, Matrix, Minus, Multiplication, Num,
+        RightBracket, RightCurlyBracket, Vector,
     },
+    error::Error,
     math::do_math,
     misc::{do_math_with_var, place_funcvar, place_var},
     parse::simplify,
@@ -10,18 +20,9 @@ use crate::{
 };
 #[cfg(feature = "rayon")]
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use rug::{
-    Complex, Float, Integer,
-    float::{
-        Constant::Pi,
-        Special::{Infinity, Nan},
-    },
-    integer::IsPrime,
-    ops::Pow,
-};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
+use std::{cmp::Ordering, sync::OnceLock};
 #[derive(Clone, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NumStr {
@@ -71,20 +72,27 @@ impl NumStr {
 }
 impl Number {
     pub fn from(number: Complex, units: Option<Units>) -> Number {
-        Self { number, units }
+        Self {
+            number,
+            units,
+            exact: None,
+        }
+    }
+    pub fn from_rational(exact: Rational, units: Option<Units>, prec: Precision) -> Number {
+        Self {
+            number: Complex::with_val(prec, &exact),
+            units,
+            exact: fits(&exact).then_some(exact),
+        }
     }
     pub fn set_prec(&mut self, prec: u32) {
         self.number.set_prec(prec)
     }
     pub fn from_f64(f: f64, opt: &Options) -> Number {
-        let number = Complex::with_val(opt.prec, f);
-        let units = None;
-        Self { number, units }
+        Number::from(Complex::with_val(opt.precision(), f), None)
     }
     pub fn new(opt: &Options) -> Number {
-        let number = Complex::new(opt.prec);
-        let units = None;
-        Self { number, units }
+        Number::from(Complex::new(opt.precision()), None)
     }
     pub fn real(&self) -> &Float {
         self.number.real()
@@ -94,16 +102,24 @@ impl Number {
     }
 }
 pub fn add(a: &Number, b: &Number) -> Number {
-    Number::from(
-        a.number.clone() + b.number.clone(),
-        if a.units == b.units { a.units } else { None },
-    )
+    let units = if a.units == b.units { a.units } else { None };
+    if let (Some(x), Some(y)) = (&a.exact, &b.exact) {
+        let q = x.clone() + y;
+        if fits(&q) {
+            return Number::from_rational(q, units, a.number.prec().0);
+        }
+    }
+    Number::from(a.number.clone() + b.number.clone(), units)
 }
 pub fn sub(a: &Number, b: &Number) -> Number {
-    Number::from(
-        a.number.clone() - b.number.clone(),
-        if a.units == b.units { a.units } else { None },
-    )
+    let units = if a.units == b.units { a.units } else { None };
+    if let (Some(x), Some(y)) = (&a.exact, &b.exact) {
+        let q = x.clone() - y;
+        if fits(&q) {
+            return Number::from_rational(q, units, a.number.prec().0);
+        }
+    }
+    Number::from(a.number.clone() - b.number.clone(), units)
 }
 pub fn set_prec(function: &mut [NumStr], func_vars: &mut [(String, Vec<NumStr>)], prec: u32) {
     function.iter_mut().for_each(|n| n.set_prec(prec));
@@ -122,8 +138,18 @@ impl NumStr {
             _ => {}
         }
     }
-    pub fn mul(&self, b: &Self) -> Result<Self, &'static str> {
+    pub fn mul(&self, b: &Self) -> Result<Self, Error> {
         fn m(a: &Number, b: &Number) -> Number {
+            if let (Some(x), Some(y)) = (&a.exact, &b.exact) {
+                let q = x.clone() * y;
+                if fits(&q) {
+                    return Number::from_rational(
+                        q,
+                        mul_units(a.units, b.units),
+                        a.number.prec().0,
+                    );
+                }
+            }
             Number::from(
                 {
                     let a = a.number.clone();
@@ -209,10 +235,10 @@ impl NumStr {
                     })
                     .collect(),
             ),
-            _ => return Err("mul err"),
+            _ => return Err(Error::Dimension("mul err", None)),
         })
     }
-    pub fn pm(&self, b: &Self) -> Result<Self, &'static str> {
+    pub fn pm(&self, b: &Self) -> Result<Self, Error> {
         Ok(match (self, b) {
             (Num(a), Num(b)) => Vector(vec![add(a, b), sub(a, b)]),
             (Num(a), Vector(b)) => Vector(
@@ -254,11 +280,28 @@ impl NumStr {
                     })
                     .collect::<Vec<Number>>(),
             ),
-            _ => return Err("plus-minus unsupported"),
+            _ => return Err(Error::Unsupported("plus-minus unsupported", None)),
         })
     }
-    pub fn pow(&self, b: &Self) -> Result<Self, &'static str> {
+    pub fn pow(&self, b: &Self) -> Result<Self, Error> {
         fn p(a: &Number, b: &Number) -> Number {
+            if let (Some(x), Some(y)) = (&a.exact, &b.exact) {
+                if let Some(n) = y.is_integer().then(|| y.numer().to_i32()).flatten() {
+                    if (n >= 0 || *x != 0) && n.unsigned_abs() <= 65536 {
+                        let q = x.clone().pow(n);
+                        if fits(&q) {
+                            return Number::from_rational(
+                                q,
+                                match (a.units, b.units) {
+                                    (Some(a), None) => Some(a.pow(n as f64)),
+                                    _ => None,
+                                },
+                                a.number.prec().0,
+                            );
+                        }
+                    }
+                }
+            }
             Number::from(
                 {
                     let a = a.number.clone();
@@ -341,7 +384,10 @@ impl NumStr {
                         }
                     }
                 } else {
-                    return Err("no imag/fractional support for matrix powers");
+                    return Err(Error::Unsupported(
+                        "no imag/fractional support for matrix powers",
+                        None,
+                    ));
                 }
             }
             (Vector(b), Matrix(a)) if b.len() == a.len() => Matrix(
@@ -372,10 +418,10 @@ impl NumStr {
                     })
                     .collect(),
             ),
-            _ => return Err("pow err"),
+            _ => return Err(Error::Dimension("pow err", None)),
         })
     }
-    pub fn func<F>(&self, b: &Self, func: F) -> Result<Self, &'static str>
+    pub fn func<F>(&self, b: &Self, func: F) -> Result<Self, Error>
     where
         F: Fn(&Number, &Number) -> Number,
     {
@@ -424,7 +470,7 @@ impl NumStr {
                     })
                     .collect(),
             ),
-            _ => return Err("operation err"),
+            _ => return Err(Error::Dimension("operation err", None)),
         })
     }
     pub fn str_is(&self, s: &str) -> bool {
@@ -433,22 +479,22 @@ impl NumStr {
             _ => false,
         }
     }
-    pub fn num(&self) -> Result<Number, &'static str> {
+    pub fn num(&self) -> Result<Number, Error> {
         match self {
             Num(n) => Ok(*n.clone()),
-            _ => Err("failed to get number"),
+            _ => Err(Error::Type("failed to get number", None)),
         }
     }
-    pub fn vec(&self) -> Result<Vec<Number>, &'static str> {
+    pub fn vec(&self) -> Result<Vec<Number>, Error> {
         match self {
             Vector(v) => Ok(v.clone()),
-            _ => Err("failed to get vector"),
+            _ => Err(Error::Type("failed to get vector", None)),
         }
     }
-    pub fn mat(&self) -> Result<Vec<Vec<Number>>, &'static str> {
+    pub fn mat(&self) -> Result<Vec<Vec<Number>>, Error> {
         match self {
             Matrix(m) => Ok(m.clone()),
-            _ => Err("failed to get matrix"),
+            _ => Err(Error::Type("failed to get matrix", None)),
         }
     }
 }
@@ -519,7 +565,7 @@ pub fn implies(a: &Number, b: &Number) -> Number {
         None,
     )
 }
-pub fn not(a: &NumStr) -> Result<NumStr, &'static str> {
+pub fn not(a: &NumStr) -> Result<NumStr, Error> {
     match a {
         Num(a) => {
             let a = &a.number;
@@ -555,10 +601,18 @@ pub fn not(a: &NumStr) -> Result<NumStr, &'static str> {
             }
             Ok(Matrix(k))
         }
-        _ => Err("bad not input"),
+        _ => Err(Error::Parse("bad not input", None)),
     }
 }
 pub fn div(a: &Number, b: &Number) -> Number {
+    if let (Some(x), Some(y)) = (&a.exact, &b.exact) {
+        if *y != 0 {
+            let q = x.clone() / y;
+            if fits(&q) {
+                return Number::from_rational(q, div_units(a.units, b.units), a.number.prec().0);
+            }
+        }
+    }
     Number::from(
         {
             let a = a.number.clone();
@@ -701,7 +755,7 @@ pub fn ne(a: &Number, b: &Number) -> Number {
     let a = a.number.clone();
     let b = b.number.clone();
     let c: Complex = a.clone() - b.clone();
-    let int = Integer::from(10).pow(a.prec().0 / 4);
+    let int = Integer::from(10).pow(a.prec().0.bits / 4);
     let re: Float = c.real().clone() * int.clone();
     let re: Float = re.round() / int.clone();
     let im: Float = c.imag().clone() * int.clone();
@@ -732,7 +786,7 @@ pub fn eq(a: &Number, b: &Number) -> Number {
     let a = a.number.clone();
     let b = b.number.clone();
     let c: Complex = a.clone() - b.clone();
-    let int = Integer::from(10).pow(a.prec().0 / 4);
+    let int = Integer::from(10).pow(a.prec().0.bits / 4);
     let re: Float = c.real().clone() * int.clone();
     let re: Float = re.round() / int.clone();
     let im: Float = c.imag().clone() * int.clone();
@@ -805,9 +859,9 @@ pub fn rem(a: &Number, b: &Number) -> Number {
 pub fn digamma(mut z: Complex, mut n: u32) -> Complex {
     n += 1;
     let oop = z.prec();
-    let op = oop.0 / 2;
-    let prec = n * op;
-    z.set_prec(prec);
+    let op = oop.0.bits / 2;
+    let prec = oop.0.with_bits(n * op);
+    z.set_prec(prec.bits);
     let h: Float = Float::with_val(prec, 0.5).pow(op / 2);
     let num = Integer::from(n);
     let mut sum = Complex::new(prec);
@@ -819,7 +873,7 @@ pub fn digamma(mut z: Complex, mut n: u32) -> Complex {
         }
     }
     let mut n = sum * Float::with_val(prec, 2).pow(op / 2 * n);
-    n.set_prec(oop);
+    n.set_prec(oop.0.bits);
     n
 }
 pub fn gamma(a: Complex) -> Complex {
@@ -828,7 +882,20 @@ pub fn gamma(a: Complex) -> Complex {
     } else if a.real().is_sign_negative() && a.real().clone().fract().is_zero() {
         Complex::with_val(a.prec(), Infinity)
     } else {
-        a.real().clone().gamma().into()
+        match a.real().clone().gamma() {
+            Ok(g) => g.into(),
+            Err(_) if *a.real() > 10000 => stirling(a),
+            Err(_) => {
+                //the continued fraction only converges for small arguments, so step down
+                let mut z = a;
+                let mut k = Complex::with_val(z.prec(), 1);
+                while *z.real() > 8 {
+                    z -= 1;
+                    k *= z.clone();
+                }
+                gamma0(z) * k
+            }
+        }
     }
 }
 pub fn tetration(a: &Number, b: &Number) -> Number {
@@ -1134,7 +1201,7 @@ pub fn to_cyl(mut a: Vec<Number>, to_deg: Complex) -> Vec<Number> {
         ]
     }
 }
-pub fn to(a: &NumStr, b: &NumStr) -> Result<NumStr, &'static str> {
+pub fn to(a: &NumStr, b: &NumStr) -> Result<NumStr, Error> {
     Ok(match (a, b) {
         (Num(a), Num(b)) => {
             let prec = a.number.prec();
@@ -1163,7 +1230,7 @@ pub fn to(a: &NumStr, b: &NumStr) -> Result<NumStr, &'static str> {
                     .collect()
             };
             if vec.is_empty() {
-                return Err("start range greater then end range");
+                return Err(Error::Domain("start range greater then end range", None));
             }
             Vector(vec)
         }
@@ -1199,7 +1266,7 @@ pub fn to(a: &NumStr, b: &NumStr) -> Result<NumStr, &'static str> {
                 })
                 .collect();
             if mat.is_empty() || mat.iter().any(|vec| vec.is_empty()) {
-                return Err("start range greater then end range");
+                return Err(Error::Domain("start range greater then end range", None));
             }
             Matrix(mat)
         }
@@ -1235,11 +1302,11 @@ pub fn to(a: &NumStr, b: &NumStr) -> Result<NumStr, &'static str> {
                 })
                 .collect();
             if mat.is_empty() || mat.iter().any(|vec| vec.is_empty()) {
-                return Err("start range greater then end range");
+                return Err(Error::Domain("start range greater then end range", None));
             }
             Matrix(mat)
         }
-        _ => return Err(".. err"),
+        _ => return Err(Error::Parse(".. err", None)),
     })
 }
 pub fn mvec(
@@ -1250,17 +1317,20 @@ pub fn mvec(
     end: isize,
     mvec: bool,
     options: Options,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     let mut vec = Vec::new();
     let mut mat = Vec::new();
     let mut test = true;
-    let mut body = |z: isize| -> Result<(), &'static str> {
+    let mut body = |z: isize| -> Result<(), Error> {
         match do_math_with_var(
             function.clone(),
             options,
             func_vars.clone(),
             var,
-            NumStr::new(Number::from(Complex::with_val(options.prec, z), None)),
+            NumStr::new(Number::from(
+                Complex::with_val(options.precision(), z),
+                None,
+            )),
         )? {
             Num(n) => {
                 if test && vec.is_empty() {
@@ -1290,7 +1360,7 @@ pub fn mvec(
                 }
                 mat.extend(m)
             }
-            _ => return Err("cant create 3d matrix"),
+            _ => return Err(Error::Dimension("cant create 3d matrix", None)),
         }
         Ok(())
     };
@@ -1305,7 +1375,7 @@ pub fn mvec(
     }
     if mat.is_empty() {
         if vec.is_empty() {
-            Err("start>end")
+            Err(Error::Domain("start>end", None))
         } else {
             Ok(Vector(vec))
         }
@@ -1321,7 +1391,7 @@ pub fn sum(
     mut end: Float,
     product: bool,
     options: Options,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     if start > end {
         (start, end) = (end, start)
     }
@@ -1333,18 +1403,24 @@ pub fn sum(
             .to_isize()
             .unwrap_or_default();
         let k = if end.is_sign_positive() { 1 } else { -1 };
-        let mut last = NumStr::new(Number::from(Complex::with_val(options.prec, Nan), None));
+        let mut last = NumStr::new(Number::from(
+            Complex::with_val(options.precision(), Nan),
+            None,
+        ));
         let mut value = do_math_with_var(
             function.clone(),
             options,
             func_vars.clone(),
             var,
-            NumStr::new(Number::from(Complex::with_val(options.prec, n), None)),
+            NumStr::new(Number::from(
+                Complex::with_val(options.precision(), n),
+                None,
+            )),
         )?;
         while last != value {
             if j > 10000 {
                 return Ok(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, Nan),
+                    Complex::with_val(options.precision(), Nan),
                     None,
                 )));
             }
@@ -1356,7 +1432,10 @@ pub fn sum(
                 options,
                 func_vars.clone(),
                 var,
-                NumStr::new(Number::from(Complex::with_val(options.prec, n), None)),
+                NumStr::new(Number::from(
+                    Complex::with_val(options.precision(), n),
+                    None,
+                )),
             )?;
             if product {
                 value = last.mul(&math)?;
@@ -1366,7 +1445,10 @@ pub fn sum(
         }
         Ok(value)
     } else if start.is_infinite() {
-        Err("unsupported due to lack of example for convergence")
+        Err(Error::Unsupported(
+            "unsupported due to lack of example for convergence",
+            None,
+        ))
     } else {
         let start = start
             .to_integer()
@@ -1383,7 +1465,10 @@ pub fn sum(
             options,
             func_vars.clone(),
             var,
-            NumStr::new(Number::from(Complex::with_val(options.prec, start), None)),
+            NumStr::new(Number::from(
+                Complex::with_val(options.precision(), start),
+                None,
+            )),
         )?;
         for z in start + 1..=end {
             let math = do_math_with_var(
@@ -1391,7 +1476,10 @@ pub fn sum(
                 options,
                 func_vars.clone(),
                 var,
-                NumStr::new(Number::from(Complex::with_val(options.prec, z), None)),
+                NumStr::new(Number::from(
+                    Complex::with_val(options.precision(), z),
+                    None,
+                )),
             )?;
             if product {
                 value = value.mul(&math)?;
@@ -1425,7 +1513,7 @@ pub fn trace(a: &[Vec<Number>]) -> Number {
     }
     Number::from(n, a[0][0].units)
 }
-pub fn identity(a: usize, prec: u32) -> Vec<Vec<Number>> {
+pub fn identity(a: usize, prec: Precision) -> Vec<Vec<Number>> {
     let mut mat = Vec::with_capacity(a.max(1));
     for i in 0..a {
         let mut vec = Vec::with_capacity(a.max(1));
@@ -1440,8 +1528,15 @@ pub fn identity(a: usize, prec: u32) -> Vec<Vec<Number>> {
     }
     mat
 }
-pub fn determinant(a: &[Vec<Number>]) -> Result<Number, &'static str> {
+pub fn determinant(a: &[Vec<Number>]) -> Result<Number, Error> {
     if !a.is_empty() && (0..a.len()).all(|j| a.len() == a[j].len()) {
+        if let Some(det) = exact_matrix(a).and_then(|mut m| exact_rref(&mut m)) {
+            return Ok(Number::from_rational(
+                det,
+                a[0][0].units.map(|b| b.pow(a.len() as f64)),
+                a[0][0].number.prec().0,
+            ));
+        }
         Ok(Number::from(
             match a.len() {
                 1 => a[0][0].number.clone(),
@@ -1461,23 +1556,37 @@ pub fn determinant(a: &[Vec<Number>]) -> Result<Number, &'static str> {
                                 - a[1][1].number.clone() * a[2][0].number.clone())
                 }
                 _ => {
-                    let mut det = Complex::new(a[0][0].number.prec());
-                    for (i, x) in a[0].iter().enumerate() {
-                        let mut sub_matrix = a[1..].to_vec();
-                        for row in &mut sub_matrix {
-                            row.remove(i);
+                    let lu = Lu::new(
+                        complex_mat(a, a[0][0].number.prec().0),
+                        &Float::new(a[0][0].number.prec().0),
+                    );
+                    let mut det = Complex::with_val(a[0][0].number.prec(), 1);
+                    if lu.pivots.len() != a.len() {
+                        det = Complex::new(a[0][0].number.prec())
+                    } else {
+                        for (i, r) in lu.u.iter().enumerate() {
+                            det *= &r[i]
                         }
-                        det += x.number.clone()
-                            * determinant(&sub_matrix)?.number
-                            * if i % 2 == 0 { 1.0 } else { -1.0 };
                     }
-                    det
+                    //an integer matrix has an integer determinant, drop what lu rounded
+                    if det.real().is_finite()
+                        && det.imag().is_finite()
+                        && a.iter()
+                            .flatten()
+                            .all(|n| n.number.real().is_integer() && n.number.imag().is_integer())
+                    {
+                        det = Complex::with_val(
+                            det.prec(),
+                            (det.real().clone().round(), det.imag().clone().round()),
+                        )
+                    }
+                    if lu.odd { -det } else { det }
                 }
             },
             a[0][0].units.map(|b| b.pow(a.len() as f64)),
         ))
     } else {
-        Err("not square")
+        Err(Error::Dimension("not square", None))
     }
 }
 pub fn transpose(a: &[Vec<Number>]) -> Vec<Vec<Number>> {
@@ -1487,7 +1596,7 @@ pub fn transpose(a: &[Vec<Number>]) -> Vec<Vec<Number>> {
             max = i.len()
         }
     }
-    let mut b = vec![vec![Number::from(Complex::new(1), None); a.len()]; max];
+    let mut b = vec![vec![Number::from(Complex::default(), None); a.len()]; max];
     for (i, l) in a.iter().enumerate() {
         for (j, n) in l.iter().enumerate() {
             b[j][i].clone_from(n);
@@ -1495,9 +1604,9 @@ pub fn transpose(a: &[Vec<Number>]) -> Vec<Vec<Number>> {
     }
     b
 }
-pub fn minors(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, &'static str> {
+pub fn minors(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, Error> {
     if a.iter().all(|j| a.len() == j.len()) {
-        let mut result = vec![vec![Number::from(Complex::new(1), None); a[0].len()]; a.len()];
+        let mut result = vec![vec![Number::from(Complex::default(), None); a[0].len()]; a.len()];
         for (i, k) in result.iter_mut().enumerate() {
             for (j, l) in k.iter_mut().enumerate() {
                 *l = determinant(&submatrix(a, i, j))?
@@ -1505,12 +1614,12 @@ pub fn minors(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, &'static str> {
         }
         Ok(result)
     } else {
-        Err("not square")
+        Err(Error::Dimension("not square", None))
     }
 }
-pub fn cofactor(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, &'static str> {
+pub fn cofactor(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, Error> {
     if a.iter().all(|j| a.len() == j.len()) && !a.is_empty() {
-        let mut result = vec![vec![Number::from(Complex::new(1), None); a[0].len()]; a.len()];
+        let mut result = vec![vec![Number::from(Complex::default(), None); a[0].len()]; a.len()];
         for (i, k) in result.iter_mut().enumerate() {
             for (j, l) in k.iter_mut().enumerate() {
                 *l = if (i + j) % 2 == 1 {
@@ -1523,16 +1632,57 @@ pub fn cofactor(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, &'static str> {
         }
         Ok(result)
     } else {
-        Err("not square")
+        Err(Error::Dimension("not square", None))
     }
 }
-pub fn inverse(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, &'static str> {
+pub fn inverse(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, Error> {
     if (0..a.len()).all(|j| a.len() == a[j].len()) {
-        Matrix(transpose(&cofactor(a)?))
-            .func(&NumStr::new(determinant(a)?), div)?
-            .mat()
+        if let Some(m) = exact_matrix(a) {
+            let n = a.len();
+            let mut aug = m
+                .into_iter()
+                .enumerate()
+                .map(|(i, mut r)| {
+                    r.extend((0..n).map(|j| Rational::from((i == j) as u32)));
+                    r
+                })
+                .collect::<Vec<Vec<Rational>>>();
+            if exact_rref(&mut aug).is_some_and(|d| d != 0) {
+                let prec = a[0][0].number.prec().0;
+                let units = a[0][0].units.map(|u| u.pow(-1.0));
+                return Ok(aug
+                    .into_iter()
+                    .map(|r| {
+                        r.into_iter()
+                            .skip(n)
+                            .map(|q| Number::from_rational(q, units, prec))
+                            .collect()
+                    })
+                    .collect());
+            }
+        }
+        if a.is_empty() {
+            return Ok(Vec::new());
+        }
+        let prec = a[0][0].number.prec().0;
+        let lu = Lu::new(complex_mat(a, prec), &Float::new(prec));
+        if lu.pivots.len() != a.len() {
+            return Err(Error::Domain("singular matrix", None));
+        }
+        let units = a[0][0].units.map(|u| u.pow(-1.0));
+        let cols = identity(a.len(), prec)
+            .iter()
+            .map(|e| lu.solve(e.iter().map(|n| n.number.clone()).collect()))
+            .collect::<Vec<Vec<Complex>>>();
+        Ok((0..a.len())
+            .map(|i| {
+                cols.iter()
+                    .map(|c| Number::from(c[i].clone(), units))
+                    .collect()
+            })
+            .collect())
     } else {
-        Err("not square")
+        Err(Error::Dimension("not square", None))
     }
 }
 pub fn nth_prime(n: Integer) -> Integer {
@@ -1588,7 +1738,7 @@ pub fn sort(mut a: Vec<Number>) -> Vec<Number> {
     });
     a
 }
-pub fn sort_mat(mut a: Vec<Vec<Number>>, prec: u32) -> Vec<Vec<Number>> {
+pub fn sort_mat(mut a: Vec<Vec<Number>>, prec: Precision) -> Vec<Vec<Number>> {
     a.sort_by(|x, y| {
         if x.is_empty() || y.is_empty() {
             Ordering::Equal
@@ -1605,14 +1755,14 @@ pub fn sort_mat(mut a: Vec<Vec<Number>>, prec: u32) -> Vec<Vec<Number>> {
     a.sort_by(|x, y| x.len().partial_cmp(&y.len()).unwrap_or(Ordering::Equal));
     a
 }
-pub fn eigenvalues(mat: &[Vec<Number>], real: bool) -> Result<NumStr, &'static str> {
+pub fn eigenvalues(mat: &[Vec<Number>], real: bool) -> Result<NumStr, Error> {
     if !mat.is_empty() && (0..mat.len()).all(|j| mat.len() == mat[j].len()) {
         match mat.len() {
             1 => Ok(NumStr::new(mat[0][0].clone())),
             2 => {
                 let pr = mat[0][0].number.prec().0;
                 let mut mat = Matrix(mat.into());
-                mat.set_prec(pr * 2);
+                mat.set_prec(pr.bits * 2);
                 let mat = mat.mat()?;
                 let mut v = Vector(quadratic(
                     Number::from(Complex::with_val(mat[0][0].number.prec(), 1), None),
@@ -1627,13 +1777,13 @@ pub fn eigenvalues(mat: &[Vec<Number>], real: bool) -> Result<NumStr, &'static s
                     ),
                     real,
                 ));
-                v.set_prec(pr);
+                v.set_prec(pr.bits);
                 Ok(v)
             }
             3 => {
                 let pr = mat[0][0].number.prec().0;
                 let mut mat = Matrix(mat.into());
-                mat.set_prec(pr * 2);
+                mat.set_prec(pr.bits * 2);
                 let mat = mat.mat()?;
                 let mut v = Vector(cubic(
                     Number::from(Complex::with_val(mat[0][0].number.prec(), -1), None),
@@ -1675,13 +1825,13 @@ pub fn eigenvalues(mat: &[Vec<Number>], real: bool) -> Result<NumStr, &'static s
                     ),
                     real,
                 ));
-                v.set_prec(pr);
+                v.set_prec(pr.bits);
                 Ok(v)
             }
             4 => {
                 let pr = mat[0][0].number.prec().0;
                 let mut mat = Matrix(mat.into());
-                mat.set_prec(pr * 2);
+                mat.set_prec(pr.bits * 2);
                 let mat = mat.mat()?;
                 let a = mat[0][0].number.clone();
                 let b = mat[0][1].number.clone();
@@ -1773,597 +1923,2435 @@ pub fn eigenvalues(mat: &[Vec<Number>], real: bool) -> Result<NumStr, &'static s
                     ),
                     real,
                 ));
-                v.set_prec(pr);
+                v.set_prec(pr.bits);
                 Ok(v)
             }
-            _ => Err("unsupported"),
-        }
-    } else {
-        Err("not square")
-    }
-}
-pub fn eigenvectors(mat: &[Vec<Number>], real: bool) -> Result<NumStr, &'static str> {
-    if !mat.is_empty() && (0..mat.len()).all(|j| mat.len() == mat[j].len()) {
-        let one = Number::from(Complex::with_val(mat[0][0].number.prec(), 1), None);
-        match mat.len() {
-            1 => Ok(NumStr::new(one)),
-            2..5 => {
-                let p = mat[0][0].number.prec().0;
-                let mut l = eigenvalues(mat, real)?.vec()?;
-                let mut i = 0;
-                while i + 1 < l.len() {
-                    let mut has = false;
-                    for v in l[i + 1..].iter().cloned() {
-                        if (v.number - l[i].number.clone()).abs().real().clone().log2()
-                            < -(p as i32 / 8)
-                        {
-                            l.remove(i);
-                            has = true;
-                            break;
-                        }
+            _ => {
+                let pr = mat[0][0].number.prec().0;
+                let mut vec = Vec::new();
+                for mut z in qr_eigenvalues(mat)? {
+                    if -z.imag().clone().abs().log10() > pr.bits / 4 {
+                        z = z.real().clone().into();
                     }
-                    if !has {
-                        i += 1;
+                    if !real || z.imag().is_zero() {
+                        vec.push(Number::from(z, mat[0][0].units))
                     }
                 }
-                let v = l
-                    .iter()
-                    .filter_map(|l| {
-                        Matrix(identity(mat.len(), l.number.prec().0))
-                            .mul(&NumStr::new(l.clone()))
-                            .map(|n| {
-                                Matrix(mat.to_vec())
-                                    .func(&n, sub)
-                                    .map(|m| Some(kernel(m.mat().unwrap()).unwrap()))
-                                    .unwrap_or(None)
-                            })
-                            .unwrap_or(None)
-                    })
-                    .flatten()
-                    .collect::<Vec<Vec<Number>>>();
-                Ok(Matrix(v))
+                if vec.is_empty() {
+                    vec.push(Number::from(Complex::with_val(pr, Nan), None))
+                }
+                Ok(Vector(vec))
             }
-            _ => Err("unsupported"),
         }
     } else {
-        Err("not square")
+        Err(Error::Dimension("not square", None))
     }
 }
-pub fn rcf(mat: Vec<Vec<Number>>) -> Result<NumStr, &'static str> {
-    if mat.is_empty() || (0..mat.len()).any(|j| mat.len() != mat[j].len()) {
-        return Err("matrix not square");
-    }
+fn qr_eigenvalues(mat: &[Vec<Number>]) -> Result<Vec<Complex>, Error> {
     let pr = mat[0][0].number.prec().0;
-    let l = mat.len();
-    let mut ev = gen_ev(&mat, false)?;
-    let mut beta = Vec::new();
-    let m = Matrix(mat.clone());
-    while !ev.is_empty() {
-        let v = ev
-            .iter_mut()
-            .filter_map(|e| e.pop().map(Vector))
-            .collect::<Vec<NumStr>>();
-        let l = v.len();
-        if l == 0 {
-            break;
+    let (_, t) = schur_form(complex_mat(mat, pr * 2))?;
+    Ok(t.into_iter()
+        .enumerate()
+        .map(|(i, r)| Complex::with_val(pr, &r[i]))
+        .collect())
+}
+fn complex_mat(a: &[Vec<Number>], prec: Precision) -> Vec<Vec<Complex>> {
+    a.iter()
+        .map(|r| {
+            r.iter()
+                .map(|n| Complex::with_val(prec, &n.number))
+                .collect()
+        })
+        .collect()
+}
+fn number_mat(a: Vec<Vec<Complex>>, units: Option<Units>, prec: Precision) -> Vec<Vec<Number>> {
+    a.into_iter()
+        .map(|r| {
+            r.into_iter()
+                .map(|n| Number::from(Complex::with_val(prec, n), units))
+                .collect()
+        })
+        .collect()
+}
+fn conj_transpose(a: &[Vec<Complex>]) -> Vec<Vec<Complex>> {
+    (0..a[0].len())
+        .map(|j| a.iter().map(|r| r[j].clone().conj()).collect())
+        .collect()
+}
+fn abs2(c: &Complex) -> Float {
+    c.clone().norm().real().clone()
+}
+fn householder(x: &[Complex]) -> Option<(Vec<Complex>, Float)> {
+    let prec = x[0].prec().0;
+    let norm = x.iter().fold(Float::new(prec), |s, x| s + abs2(x)).sqrt();
+    if norm.is_zero() {
+        return None;
+    }
+    let mut v = x.to_vec();
+    if x[0].is_zero() {
+        v[0] += &norm
+    } else {
+        v[0] += x[0].clone() / x[0].clone().abs() * &norm
+    }
+    let vv = v.iter().fold(Float::new(prec), |s, x| s + abs2(x));
+    if vv.is_zero() { None } else { Some((v, vv)) }
+}
+fn reflect_rows(a: &mut [Vec<Complex>], v: &[Complex], vv: &Float, cols: usize) {
+    for j in cols..a[0].len() {
+        let mut s = Complex::new(v[0].prec());
+        for (r, v) in a.iter().zip(v) {
+            s += v.clone().conj() * &r[j]
         }
-        let v = if l == 1 {
-            v[0].clone()
-        } else {
-            v[1..].iter().fold(v[0].clone(), |sum, val| {
-                sum.func(val, add).unwrap_or(Func(String::new()))
-            })
-        };
-        let mut c = v.vec()?;
-        beta.push(c.clone());
-        for _ in 1..l {
-            c = m.mul(&Vector(c))?.vec()?;
-            beta.push(c.clone())
+        s = s * 2 / vv;
+        for (r, v) in a.iter_mut().zip(v) {
+            r[j] -= s.clone() * v
         }
     }
-    change_basis(mat, &identity(l, pr), &transpose(&beta))
 }
-pub fn jcf(mat: Vec<Vec<Number>>) -> Result<NumStr, &'static str> {
-    if mat.is_empty() || (0..mat.len()).any(|j| mat.len() != mat[j].len()) {
-        return Err("matrix not square");
+fn reflect_cols(a: &mut [Vec<Complex>], v: &[Complex], vv: &Float, start: usize) {
+    for r in a.iter_mut() {
+        let mut s = Complex::new(v[0].prec());
+        for (x, v) in r[start..].iter().zip(v) {
+            s += x.clone() * v
+        }
+        s = s * 2 / vv;
+        for (x, v) in r[start..].iter_mut().zip(v) {
+            *x -= s.clone() * v.clone().conj()
+        }
     }
-    let pr = mat[0][0].number.prec().0;
-    let l = mat.len();
-    let beta = transpose(&generalized_eigenvectors(&mat, false)?.mat()?);
-    let i = identity(l, pr);
-    let mut o = i.clone();
-    let mut d = change_basis(mat, &i, &beta)?.mat()?;
-    for (i, r) in o.iter_mut().enumerate() {
-        r[i].number = d[i..d.len() - 1]
+}
+#[allow(clippy::type_complexity)]
+fn householder_qr(mut r: Vec<Vec<Complex>>) -> (Vec<Vec<Complex>>, Vec<Vec<Complex>>) {
+    let (m, n) = (r.len(), r[0].len());
+    let prec = r[0][0].prec().0;
+    let mut q = (0..m)
+        .map(|i| {
+            (0..m)
+                .map(|j| Complex::with_val(prec, (i == j) as u8))
+                .collect()
+        })
+        .collect::<Vec<Vec<Complex>>>();
+    for k in 0..n.min(m.saturating_sub(1)) {
+        let x = r[k..]
             .iter()
-            .enumerate()
-            .map_while(|(j, r)| {
-                if -r[i + j + 1].number.clone().abs().real().clone().ln() < pr / 16 {
-                    Some(r[i + j + 1].number.clone())
-                } else {
-                    None
-                }
-            })
-            .fold(Complex::with_val(pr, 1), |sum, val| sum * val.clone());
-    }
-    let l = o.len();
-    for k in 1..l {
-        d = change_basis(d, &i, &o)?.mat()?;
-        o = i.clone();
-        let mut sum = Complex::new(pr);
-        for (i, r) in o[1..l - k].iter_mut().enumerate() {
-            let i = i + 1;
-            sum += d[i - 1][i + k].number.clone();
-            r[i + k].number = -sum.clone();
+            .map(|r| r[k].clone())
+            .collect::<Vec<Complex>>();
+        if let Some((v, vv)) = householder(&x) {
+            reflect_rows(&mut r[k..], &v, &vv, k);
+            reflect_cols(&mut q, &v, &vv, k);
+            for r in r[k + 1..].iter_mut() {
+                r[k] = Complex::new(prec)
+            }
         }
     }
-    let d = change_basis(d, &i, &o)?;
-    Ok(d)
+    (q, r)
 }
-fn gen_ev(mat: &[Vec<Number>], real: bool) -> Result<Vec<Vec<Vec<Number>>>, &'static str> {
-    if mat.is_empty() || (0..mat.len()).any(|j| mat.len() != mat[j].len()) {
-        return Err("matrix not square");
+#[allow(clippy::type_complexity)]
+fn schur_form(mut a: Vec<Vec<Complex>>) -> Result<(Vec<Vec<Complex>>, Vec<Vec<Complex>>), Error> {
+    let n = a.len();
+    let wp = a[0][0].prec().0;
+    let mut q = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| Complex::with_val(wp, (i == j) as u8))
+                .collect()
+        })
+        .collect::<Vec<Vec<Complex>>>();
+    for k in 0..n.saturating_sub(2) {
+        let x = a[k + 1..]
+            .iter()
+            .map(|r| r[k].clone())
+            .collect::<Vec<Complex>>();
+        if let Some((v, vv)) = householder(&x) {
+            reflect_rows(&mut a[k + 1..], &v, &vv, k);
+            reflect_cols(&mut a, &v, &vv, k + 1);
+            reflect_cols(&mut q, &v, &vv, k + 1);
+            for r in a[k + 2..].iter_mut() {
+                r[k] = Complex::new(wp)
+            }
+        }
     }
-    let p = mat[0][0].number.prec().0;
-    let mut l = eigenvalues(mat, real)?.vec()?;
-    let mut i = 0;
-    while i + 1 < l.len() {
-        let mut has = false;
-        for v in l[i + 1..].iter().cloned() {
-            if (v.number - l[i].number.clone()).abs().real().clone().log2() < -(p as i32 / 8) {
-                l.remove(i);
-                has = true;
+    let abs = |c: &Complex| -> Float { c.clone().abs().real().clone() };
+    let eps = Float::with_val(wp, 1) >> (wp.bits - 8);
+    let mut hi = n - 1;
+    let mut iter = 0;
+    let mut total = 0;
+    while hi > 0 {
+        let mut lo = hi;
+        while lo > 0 {
+            let s = abs(&a[lo][lo]) + abs(&a[lo - 1][lo - 1]);
+            if abs(&a[lo][lo - 1]) <= eps.clone() * s {
+                a[lo][lo - 1] = Complex::new(wp);
                 break;
             }
+            lo -= 1;
+        }
+        if lo == hi {
+            hi -= 1;
+            iter = 0;
+            continue;
+        }
+        //defective eigenvalues converge linearly, about a bit a sweep
+        if total > (64 + wp.bits as usize) * n {
+            return Err(Error::NotConverged("eigenvalues did not converge", None));
+        }
+        iter += 1;
+        total += 1;
+        let mu = if iter % 11 == 0 {
+            a[hi][hi].clone() + abs(&a[hi][hi - 1])
+        } else {
+            let (w, x, y, z) = (
+                &a[hi - 1][hi - 1],
+                &a[hi - 1][hi],
+                &a[hi][hi - 1],
+                &a[hi][hi],
+            );
+            let h: Complex = (w.clone() - z) / 2;
+            let d = (h.clone() * &h + x.clone() * y).sqrt();
+            let m1 = z.clone() + &h + &d;
+            let m2 = z.clone() + &h - &d;
+            if abs(&(m1.clone() - z)) < abs(&(m2.clone() - z)) {
+                m1
+            } else {
+                m2
+            }
+        };
+        for (k, r) in a.iter_mut().enumerate().take(hi + 1).skip(lo) {
+            r[k] -= &mu
+        }
+        let mut rot = Vec::with_capacity(hi - lo);
+        for k in lo..hi {
+            let r = (abs2(&a[k][k]) + abs2(&a[k + 1][k])).sqrt();
+            let (c, s) = if r.is_zero() {
+                (Complex::with_val(wp, 1), Complex::new(wp))
+            } else {
+                (a[k][k].clone() / &r, a[k + 1][k].clone() / &r)
+            };
+            let (cc, sc) = (c.clone().conj(), s.clone().conj());
+            let (top, bottom) = a.split_at_mut(k + 1);
+            for (x, y) in top[k][k..].iter_mut().zip(bottom[0][k..].iter_mut()) {
+                let t = cc.clone() * &*x + sc.clone() * &*y;
+                *y = c.clone() * &*y - s.clone() * &*x;
+                *x = t
+            }
+            rot.push((c, s));
+        }
+        for (k, (c, s)) in (lo..hi).zip(rot) {
+            let (cc, sc) = (c.clone().conj(), s.clone().conj());
+            for r in a[..=(k + 2).min(hi)].iter_mut().chain(q.iter_mut()) {
+                let t = c.clone() * &r[k] + s.clone() * &r[k + 1];
+                r[k + 1] = cc.clone() * &r[k + 1] - sc.clone() * &r[k];
+                r[k] = t
+            }
         }
-        if !has {
-            i += 1;
+        for (k, r) in a.iter_mut().enumerate().take(hi + 1).skip(lo) {
+            r[k] += &mu
         }
     }
-    Ok(l.iter()
-        .filter_map(|l| {
-            Matrix(identity(mat.len(), l.number.prec().0))
-                .mul(&NumStr::new(l.clone()))
-                .map(|n| {
-                    Matrix(mat.to_vec())
-                        .func(&n, sub)
-                        .map(|m| {
-                            m.pow(&NumStr::new(Number::from(
-                                Complex::with_val(p, mat.len()),
-                                None,
-                            )))
-                            .map(|m| {
-                                let ker = kernel(m.mat().unwrap()).unwrap();
-                                Some(ker)
-                            })
-                            .unwrap_or(None)
-                        })
-                        .unwrap_or(None)
-                })
-                .unwrap_or(None)
-        })
-        .collect::<Vec<Vec<Vec<Number>>>>())
+    Ok((q, a))
 }
-pub fn generalized_eigenvectors(mat: &[Vec<Number>], real: bool) -> Result<NumStr, &'static str> {
-    if !mat.is_empty() && (0..mat.len()).all(|j| mat.len() == mat[j].len()) {
-        let one = Number::from(Complex::with_val(mat[0][0].number.prec(), 1), None);
-        match mat.len() {
-            1 => Ok(NumStr::new(one)),
-            2..5 => Ok(Matrix(
-                gen_ev(mat, real)?
+#[allow(clippy::type_complexity)]
+fn jacobi_svd(
+    a: &[Vec<Complex>],
+) -> Result<(Vec<Vec<Complex>>, Vec<Float>, Vec<Vec<Complex>>), Error> {
+    let (m, n) = (a.len(), a[0].len());
+    if m < n {
+        let (u, s, v) = jacobi_svd(&conj_transpose(a))?;
+        return Ok((v, s, u));
+    }
+    let prec = a[0][0].prec().0;
+    let mut u = conj_transpose(a)
+        .into_iter()
+        .map(|c| c.into_iter().map(|x| x.conj()).collect())
+        .collect::<Vec<Vec<Complex>>>();
+    let mut v = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| Complex::with_val(prec, (i == j) as u8))
+                .collect()
+        })
+        .collect::<Vec<Vec<Complex>>>();
+    let eps = Float::with_val(prec, m) >> (prec.bits - 1);
+    let floor = u
+        .iter()
+        .flatten()
+        .fold(Float::new(prec), |s, x| s + abs2(x))
+        * &eps;
+    let mut converged = false;
+    for _ in 0..64 {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let alpha = u[p].iter().fold(Float::new(prec), |s, x| s + abs2(x));
+                let beta = u[q].iter().fold(Float::new(prec), |s, x| s + abs2(x));
+                let gamma = u[p]
                     .iter()
-                    .flatten()
-                    .cloned()
-                    .collect::<Vec<Vec<Number>>>(),
-            )),
-            _ => Err("unsupported"),
+                    .zip(&u[q])
+                    .fold(Complex::new(prec), |s, (x, y)| s + x.clone().conj() * y);
+                let g = gamma.clone().abs().real().clone();
+                if g <= floor || g <= eps.clone() * (alpha.clone() * &beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let e = (gamma / &g).conj();
+                let zeta: Float = (beta - alpha) / (2 * g);
+                let t = if zeta.is_zero() {
+                    Float::with_val(prec, 1)
+                } else {
+                    let t: Float = zeta.clone().abs() + (zeta.clone().square() + 1u8).sqrt();
+                    if zeta.is_sign_negative() {
+                        -t.recip()
+                    } else {
+                        t.recip()
+                    }
+                };
+                let c = (t.clone().square() + 1u8).sqrt().recip();
+                let s = c.clone() * t;
+                for w in [&mut u, &mut v] {
+                    let (left, right) = w.split_at_mut(q);
+                    for (x, y) in left[p].iter_mut().zip(right[0].iter_mut()) {
+                        let yp = e.clone() * &*y;
+                        *y = x.clone() * &s + yp.clone() * &c;
+                        *x = x.clone() * &c - yp * &s
+                    }
+                }
+            }
+        }
+        if !rotated {
+            converged = true;
+            break;
         }
-    } else {
-        Err("not square")
-    }
-}
-pub fn change_basis(
-    a: Vec<Vec<Number>>,
-    beta: &[Vec<Number>],
-    gamma: &[Vec<Number>],
-) -> Result<NumStr, &'static str> {
-    let m = Matrix(a);
-    let tn = Matrix(inverse(&transpose(gamma))?);
-    let mut c = Vec::new();
-    for b in beta {
-        c.push(tn.mul(&Vector(b.to_vec()))?.vec()?)
-    }
-    let tn = Matrix(inverse(&transpose(beta))?);
-    let mut d = Vec::new();
-    for g in gamma {
-        d.push(tn.mul(&Vector(g.to_vec()))?.vec()?)
     }
-    Matrix(c).mul(&m)?.mul(&Matrix(d))
-}
-pub fn coordinate(v: Vec<Number>, beta: Vec<Vec<Number>>) -> Result<NumStr, &'static str> {
-    let tn = Matrix(inverse(&transpose(&beta))?);
-    tn.mul(&Vector(v))
-}
-pub fn rref(mut a: Vec<Vec<Number>>) -> Result<Vec<Vec<Number>>, &'static str> {
-    if a.is_empty() || a[0].is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
-        return Err("invalid matrix");
+    if !converged {
+        return Err(Error::NotConverged("svd did not converge", None));
     }
-    let mut count = 0;
-    for i in 0..a[0].len() {
-        if let Some((n, v)) = a
-            .clone()
-            .iter()
-            .enumerate()
-            .find(|(j, b)| *j >= count && !b[i].number.is_zero())
-        {
-            for (a, r) in a.iter_mut().enumerate() {
-                let c = r[i].number.clone() / v[i].number.clone();
-                for (b, num) in r.iter_mut().enumerate() {
-                    if a != n && !v[i].number.is_zero() {
-                        num.number -= v[b].number.clone() * c.clone();
-                    } else {
-                        num.number /= v[i].number.clone()
+    let mut s = u
+        .iter()
+        .map(|c| c.iter().fold(Float::new(prec), |s, x| s + abs2(x)).sqrt())
+        .enumerate()
+        .collect::<Vec<(usize, Float)>>();
+    s.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    let tol = s[0].1.clone() >> (prec.bits / 2);
+    let mut uc: Vec<Vec<Complex>> = Vec::with_capacity(n);
+    for (i, sigma) in &s {
+        if *sigma > tol {
+            uc.push(u[*i].iter().map(|x| x.clone() / sigma).collect())
+        } else {
+            for e in 0..m {
+                let mut x = (0..m)
+                    .map(|j| Complex::with_val(prec, (j == e) as u8))
+                    .collect::<Vec<Complex>>();
+                for c in &uc {
+                    let d = c
+                        .iter()
+                        .zip(&x)
+                        .fold(Complex::new(prec), |s, (c, x)| s + c.clone().conj() * x);
+                    for (x, c) in x.iter_mut().zip(c) {
+                        *x -= d.clone() * c
                     }
                 }
+                let norm = x.iter().fold(Float::new(prec), |s, x| s + abs2(x)).sqrt();
+                if norm > 0.5 {
+                    uc.push(x.into_iter().map(|x| x / &norm).collect());
+                    break;
+                }
             }
-            if n != count {
-                a.swap(count, n);
-            }
-            count += 1;
         }
     }
-    Ok(a.to_vec())
+    Ok((
+        conj_transpose(&uc)
+            .into_iter()
+            .map(|r| r.into_iter().map(|x| x.conj()).collect())
+            .collect(),
+        s.iter().map(|(_, s)| s.clone()).collect(),
+        (0..n)
+            .map(|j| s.iter().map(|(i, _)| v[*i][j].clone()).collect())
+            .collect(),
+    ))
 }
-pub fn kernel(a: Vec<Vec<Number>>) -> Result<Vec<Vec<Number>>, &'static str> {
+pub fn qr(a: &[Vec<Number>]) -> Result<[Vec<Vec<Number>>; 2], Error> {
     if a.is_empty() || a[0].is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
-        return Err("invalid matrix");
-    }
-    let pr = a[0][0].number.prec().0;
-    let rref = rref(a.clone())?;
-    let mut ker = Vec::new();
-    let mut leading_ones = Vec::new();
-    for r in &rref {
-        if let Some((pos, _)) = r.iter().enumerate().find(|(_, n)| !n.number.is_zero()) {
-            leading_ones.push(pos);
-        }
-    }
-    let t = transpose(&rref);
-    let m = Matrix(a);
-    for (i, t) in t.iter().enumerate() {
-        if !leading_ones.contains(&i) {
-            let mut zero = vec![Number::from(Complex::new(pr), None); rref[0].len()];
-            for j in 0..i.min(leading_ones.len()) {
-                if leading_ones[j] < i {
-                    zero[leading_ones[j]] = Number::from(-1.0 * t[j].number.clone(), None)
-                }
+        return Err(Error::Dimension("invalid matrix", None));
+    }
+    let prec = a[0][0].number.prec().0;
+    let (q, r) = householder_qr(complex_mat(a, prec));
+    Ok([
+        number_mat(q, None, prec),
+        number_mat(r, a[0][0].units, prec),
+    ])
+}
+pub fn schur(a: &[Vec<Number>]) -> Result<[Vec<Vec<Number>>; 2], Error> {
+    if a.is_empty() || a.iter().any(|b| a.len() != b.len()) {
+        return Err(Error::Dimension("not square", None));
+    }
+    let prec = a[0][0].number.prec().0;
+    let (q, t) = schur_form(complex_mat(a, prec * 2))?;
+    Ok([
+        number_mat(q, None, prec),
+        number_mat(t, a[0][0].units, prec),
+    ])
+}
+#[allow(clippy::type_complexity)]
+pub fn svd(a: &[Vec<Number>]) -> Result<(Vec<Vec<Number>>, Vec<Number>, Vec<Vec<Number>>), Error> {
+    if a.is_empty() || a[0].is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
+        return Err(Error::Dimension("invalid matrix", None));
+    }
+    let prec = a[0][0].number.prec().0;
+    let (u, s, v) = jacobi_svd(&complex_mat(a, prec * 2))?;
+    Ok((
+        number_mat(u, None, prec),
+        s.into_iter()
+            .map(|s| Number::from(Complex::with_val(prec, s), a[0][0].units))
+            .collect(),
+        number_mat(v, None, prec),
+    ))
+}
+///singular values at or below this count as zero, max(m,n)·σ₀·2^-prec unless given
+fn rank_tol(a: &[Vec<Number>], s: &[Float], tol: Option<Float>) -> Float {
+    let prec = a[0][0].number.prec().0;
+    tol.unwrap_or_else(|| (Float::with_val(prec, a.len().max(a[0].len())) * &s[0]) >> prec.bits)
+}
+pub fn pinv(a: &[Vec<Number>], tol: Option<Float>) -> Result<Vec<Vec<Number>>, Error> {
+    if a.is_empty() || a[0].is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
+        return Err(Error::Dimension("invalid matrix", None));
+    }
+    let prec = a[0][0].number.prec().0;
+    let (u, s, v) = jacobi_svd(&complex_mat(a, prec * 2))?;
+    let tol = rank_tol(a, &s, tol);
+    let p = v
+        .iter()
+        .map(|v| {
+            (0..u.len())
+                .map(|j| {
+                    v.iter()
+                        .zip(&s)
+                        .zip(&u[j])
+                        .filter(|((_, s), _)| !s.is_zero() && **s > tol)
+                        .fold(Complex::new(prec * 2), |sum, ((v, s), u)| {
+                            sum + v.clone() * u.clone().conj() / s
+                        })
+                })
+                .collect()
+        })
+        .collect();
+    Ok(number_mat(p, a[0][0].units.map(|u| u.pow(-1.0)), prec))
+}
+pub fn norm2(a: &[Vec<Number>]) -> Result<Number, Error> {
+    let s = svd(a)?.1;
+    Ok(s[0].clone())
+}
+pub fn cond(a: &[Vec<Number>]) -> Result<Number, Error> {
+    let s = svd(a)?.1;
+    let min = &s[s.len() - 1].number;
+    Ok(Number::from(
+        if min.is_zero() {
+            Complex::with_val(min.prec(), Infinity)
+        } else {
+            s[0].number.clone() / min
+        },
+        None,
+    ))
+}
+fn matmul(a: &[Vec<Complex>], b: &[Vec<Complex>]) -> Vec<Vec<Complex>> {
+    let prec = a[0][0].prec();
+    a.iter()
+        .map(|r| {
+            (0..b[0].len())
+                .map(|j| {
+                    r.iter()
+                        .zip(b)
+                        .fold(Complex::new(prec), |s, (x, b)| s + x.clone() * &b[j])
+                })
+                .collect()
+        })
+        .collect()
+}
+fn eye(n: usize, prec: Precision) -> Vec<Vec<Complex>> {
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| Complex::with_val(prec, (i == j) as u8))
+                .collect()
+        })
+        .collect()
+}
+fn norm_inf(a: &[Vec<Complex>]) -> Float {
+    let prec = a[0][0].prec().0;
+    a.iter()
+        .map(|r| {
+            r.iter()
+                .fold(Float::new(prec), |s, x| s + x.clone().abs().real())
+        })
+        .fold(Float::new(prec), |m, s| if s > m { s } else { m })
+}
+fn combine(a: &[Vec<Complex>], b: &[Vec<Complex>], x: &Complex, y: &Complex) -> Vec<Vec<Complex>> {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| {
+            a.iter()
+                .zip(b)
+                .map(|(a, b)| a.clone() * x + b.clone() * y)
+                .collect()
+        })
+        .collect()
+}
+fn inverse_c(a: &[Vec<Complex>]) -> Result<Vec<Vec<Complex>>, Error> {
+    let prec = a[0][0].prec().0;
+    let lu = Lu::new(a.to_vec(), &Float::new(prec));
+    if lu.pivots.len() != a.len() {
+        return Err(Error::Domain("singular matrix", None));
+    }
+    let cols = eye(a.len(), prec)
+        .into_iter()
+        .map(|e| lu.solve(e))
+        .collect::<Vec<Vec<Complex>>>();
+    Ok((0..a.len())
+        .map(|i| cols.iter().map(|c| c[i].clone()).collect())
+        .collect())
+}
+fn expm_c(a: &[Vec<Complex>]) -> Result<Vec<Vec<Complex>>, Error> {
+    let prec = a[0][0].prec().0;
+    let norm = norm_inf(a);
+    let s = if norm > 0.5 {
+        (norm.log2().ceil().to_f64() as u32) + 1
+    } else {
+        0
+    };
+    let b = a
+        .iter()
+        .map(|r| r.iter().map(|x| x.clone() >> s).collect())
+        .collect::<Vec<Vec<Complex>>>();
+    let eps = Float::with_val(prec, 1) >> (prec.bits - 8);
+    let mut sum = eye(a.len(), prec);
+    let mut term = sum.clone();
+    let one = Complex::with_val(prec, 1);
+    for k in 1.. {
+        if k > prec.bits {
+            return Err(Error::NotConverged("expm did not converge", None));
+        }
+        term = matmul(&term, &b)
+            .into_iter()
+            .map(|r| r.into_iter().map(|x| x / k).collect())
+            .collect();
+        sum = combine(&sum, &term, &one, &one);
+        if norm_inf(&term) <= eps.clone() * norm_inf(&sum) {
+            break;
+        }
+    }
+    for _ in 0..s {
+        sum = matmul(&sum, &sum)
+    }
+    Ok(sum)
+}
+fn sqrtm_c(a: &[Vec<Complex>]) -> Result<Vec<Vec<Complex>>, Error> {
+    let prec = a[0][0].prec().0;
+    let (q, t) = schur_form(a.to_vec())?;
+    let n = t.len();
+    let mut r = vec![vec![Complex::new(prec); n]; n];
+    for (i, row) in r.iter_mut().enumerate() {
+        row[i] = t[i][i].clone().sqrt()
+    }
+    for d in 1..n {
+        for i in 0..n - d {
+            let j = i + d;
+            let mut s = t[i][j].clone();
+            for (x, r) in r[i][i + 1..j].iter().zip(&r[i + 1..j]) {
+                s -= x.clone() * &r[j]
             }
-            zero[i] = Number::from(Complex::with_val(pr, 1), None);
-            if m.mul(&Vector(zero.clone()))?
-                .vec()?
-                .iter()
-                .all(|n| -n.number.clone().abs().real().clone().log2() > pr / 16)
-            {
-                ker.push(zero);
+            let den = r[i][i].clone() + &r[j][j];
+            if den.is_zero() {
+                return Err(Error::Domain("matrix has no square root", None));
             }
+            r[i][j] = s / den
         }
     }
-    Ok(ker)
+    Ok(matmul(&matmul(&q, &r), &conj_transpose(&q)))
 }
-pub fn range(a: Vec<Vec<Number>>) -> Result<Vec<Vec<Number>>, &'static str> {
-    let rref = rref(a.clone())?;
-    let mut ran = Vec::new();
-    let mut leading_ones = Vec::new();
-    for r in &rref {
-        if let Some((pos, _)) = r.iter().enumerate().find(|(_, n)| !n.number.is_zero()) {
-            leading_ones.push(pos);
+fn logm_c(a: &[Vec<Complex>]) -> Result<Vec<Vec<Complex>>, Error> {
+    let prec = a[0][0].prec().0;
+    //an eigenvalue within rounding of zero, using the bits the floats carry
+    let tol = norm_inf(a) >> (a[0][0].real().prec().bits / 2);
+    let t = schur_form(a.to_vec())?.1;
+    if (0..t.len()).any(|i| t[i][i].clone().abs().real() <= &tol) {
+        return Err(Error::Domain("singular matrix has no logarithm", None));
+    }
+    let n = a.len();
+    let id = eye(n, prec);
+    let one = Complex::with_val(prec, 1);
+    let mut a = a.to_vec();
+    let mut k = 0;
+    while norm_inf(&combine(&a, &id, &one, &-one.clone())) > 0.25 {
+        if k > 64 {
+            return Err(Error::NotConverged("logm did not converge", None));
         }
+        a = sqrtm_c(&a)?;
+        k += 1;
     }
-    let t = transpose(&a);
-    for i in leading_ones {
-        ran.push(t[i].clone());
+    let z = matmul(
+        &combine(&a, &id, &one, &-one.clone()),
+        &inverse_c(&combine(&a, &id, &one, &one))?,
+    );
+    let z2 = matmul(&z, &z);
+    let eps = Float::with_val(prec, 1) >> (prec.bits - 8);
+    let mut term = z;
+    let mut sum = term.clone();
+    for j in (3..).step_by(2) {
+        if j > 4 * prec.bits {
+            return Err(Error::NotConverged("logm did not converge", None));
+        }
+        term = matmul(&term, &z2);
+        let t = Complex::with_val(prec, 1) / j;
+        sum = combine(&sum, &term, &one, &t);
+        if norm_inf(&term) <= eps.clone() * norm_inf(&sum) * j {
+            break;
+        }
     }
-    Ok(ran)
+    let scale = Complex::with_val(prec, 2) << k;
+    let zero = Complex::new(prec);
+    Ok(combine(&sum, &id, &scale, &zero))
 }
-pub fn mul_units(a: Option<Units>, b: Option<Units>) -> Option<Units> {
-    match (a, b) {
-        (Some(a), None) => Some(a),
-        (None, Some(b)) => Some(b),
-        (Some(a), Some(b)) => Some(a.mul(&b)),
-        _ => None,
+#[allow(clippy::type_complexity)]
+fn matrix_function(
+    a: &[Vec<Number>],
+    units: Option<Units>,
+    f: fn(&[Vec<Complex>]) -> Result<Vec<Vec<Complex>>, Error>,
+) -> Result<Vec<Vec<Number>>, Error> {
+    if a.is_empty() || a.iter().any(|b| a.len() != b.len()) {
+        return Err(Error::Dimension("not square", None));
+    }
+    if units.is_none()
+        && a.iter()
+            .flatten()
+            .any(|n| n.units.is_some_and(|u| !u.is_none()))
+    {
+        return Err(Error::Unit(
+            "matrix function needs a dimensionless matrix",
+            None,
+        ));
     }
+    let prec = a[0][0].number.prec().0;
+    Ok(number_mat(f(&complex_mat(a, prec * 2))?, units, prec))
 }
-pub fn div_units(a: Option<Units>, b: Option<Units>) -> Option<Units> {
-    match (a, b) {
-        (Some(a), None) => Some(a),
-        (None, Some(b)) => Some(b.pow(-1.0)),
-        (Some(a), Some(b)) => Some(a.div(&b)),
-        _ => None,
-    }
+pub fn expm(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, Error> {
+    matrix_function(a, None, expm_c)
 }
-pub fn quadratic(a: Number, b: Number, c: Number, real: bool) -> Vec<Number> {
-    if a.number.is_zero() {
-        return if b.number.is_zero() {
-            vec![Number::from(Complex::new(a.number.prec()), None)]
-        } else {
-            let units = div_units(c.units, b.units);
-            let b = b.number;
-            let c = c.number;
-            let mut r = -c / b;
-            if -r.imag().clone().abs().log10() > a.number.prec().0 / 4 {
-                r = r.real().clone().into();
-            }
-            if real && !r.imag().is_zero() {
-                vec![Number::from(Complex::with_val(a.number.prec(), Nan), None)]
-            } else {
-                vec![Number::from(r, units)]
+pub fn logm(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, Error> {
+    matrix_function(a, None, logm_c)
+}
+pub fn sqrtm(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, Error> {
+    matrix_function(a, a[0][0].units.map(|u| u.root(2.0)), sqrtm_c)
+}
+pub fn cosm(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, Error> {
+    matrix_function(a, None, |a| {
+        let prec = a[0][0].prec().0;
+        let i = Complex::with_val(prec, (0, 1));
+        let half = Complex::with_val(prec, 0.5);
+        let zero = Complex::new(prec);
+        let p = expm_c(&combine(a, a, &i, &zero))?;
+        let m = expm_c(&combine(a, a, &-i, &zero))?;
+        Ok(combine(&p, &m, &half, &half))
+    })
+}
+pub fn sinm(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, Error> {
+    matrix_function(a, None, |a| {
+        let prec = a[0][0].prec().0;
+        let i = Complex::with_val(prec, (0, 1));
+        let h = Complex::with_val(prec, (0, -0.5));
+        let zero = Complex::new(prec);
+        let p = expm_c(&combine(a, a, &i, &zero))?;
+        let m = expm_c(&combine(a, a, &-i, &zero))?;
+        Ok(combine(&p, &m, &h, &-h.clone()))
+    })
+}
+fn swap_schur(q: &mut [Vec<Complex>], t: &mut [Vec<Complex>], k: usize) {
+    let x = t[k][k + 1].clone();
+    let y = t[k + 1][k + 1].clone() - &t[k][k];
+    let norm = Complex::with_val(x.prec(), abs2(&x) + abs2(&y)).sqrt();
+    if norm.is_zero() {
+        return;
+    }
+    let (c, s) = (x / &norm, y / norm);
+    let (cc, sc) = (c.clone().conj(), s.clone().conj());
+    for row in t.iter_mut().chain(q.iter_mut()) {
+        let (a, b) = (row[k].clone(), row[k + 1].clone());
+        row[k] = a.clone() * &c + b.clone() * &s;
+        row[k + 1] = b * &cc - a * &sc;
+    }
+    let (top, bottom) = t.split_at_mut(k + 1);
+    for (x, y) in top[k].iter_mut().zip(bottom[0].iter_mut()) {
+        let (a, b) = (x.clone(), y.clone());
+        *x = a.clone() * &cc + b.clone() * &sc;
+        *y = b * &c - a * &s;
+    }
+    t[k + 1][k] = Complex::new(t[k][k].prec());
+}
+pub fn funm(
+    a: Vec<Vec<Number>>,
+    func: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    var: String,
+) -> Result<NumStr, Error> {
+    if a.is_empty() || a.iter().any(|b| a.len() != b.len()) {
+        return Err(Error::Dimension("not square", None));
+    }
+    let prec = options.precision();
+    let (mut q, mut t) = schur_form(complex_mat(&a, prec * 2))?;
+    let n = t.len();
+    let tol = norm_inf(&t) >> (prec.bits / 8);
+    let mut block: Vec<usize> = Vec::with_capacity(n);
+    for i in 0..n {
+        let b = (0..i)
+            .find(|j| (t[*j][*j].clone() - &t[i][i]).abs().real() <= &tol)
+            .map(|j| block[j])
+            .unwrap_or(i);
+        block.push(b)
+    }
+    let mut sorted = false;
+    while !sorted {
+        sorted = true;
+        for k in 0..n.saturating_sub(1) {
+            if block[k] > block[k + 1] {
+                swap_schur(&mut q, &mut t, k);
+                block.swap(k, k + 1);
+                sorted = false
             }
-        };
+        }
     }
-    let units = div_units(c.units, a.units).map(|a| a.root(2.0));
-    let a = a.number;
-    let b = b.number;
-    let c = c.number;
-    let p: Complex = sqr(b.clone());
-    let p: Complex = p - (4 * c * a.clone());
-    let p = p.sqrt();
-    let a: Complex = 2 * a;
-    let mut z1 = (p.clone() - b.clone()) / a.clone();
-    let mut z2 = (-p - b) / a.clone();
-    if -z1.imag().clone().abs().log10() > a.prec().0 / 4 {
-        z1 = z1.real().clone().into();
+    let f = |x: Complex, nth: u32| -> Result<Complex, Error> {
+        let x = Number::from(Complex::with_val(prec, x), None);
+        Ok(Complex::with_val(
+            prec * 2,
+            slope(
+                func.clone(),
+                func_vars.clone(),
+                options,
+                var.clone(),
+                x,
+                true,
+                nth,
+            )?
+            .num()?
+            .number,
+        ))
+    };
+    let mut m = vec![vec![Complex::new(prec * 2); n]; n];
+    let mut s = 0;
+    while s < n {
+        let e = (s..n).find(|i| block[*i] != block[s]).unwrap_or(n);
+        let size = e - s;
+        let sigma = (s..e).fold(Complex::new(prec * 2), |a, i| a + &t[i][i]) / size;
+        let nil: Vec<Vec<Complex>> = (s..e)
+            .map(|i| {
+                (s..e)
+                    .map(|j| {
+                        if i == j {
+                            t[i][j].clone() - &sigma
+                        } else {
+                            t[i][j].clone()
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut power = eye(size, prec * 2);
+        let mut factorial = Complex::with_val(prec * 2, 1);
+        for k in 0..size {
+            if k > 0 {
+                power = matmul(&power, &nil);
+                factorial *= k;
+            }
+            let c = f(sigma.clone(), k as u32)? / &factorial;
+            for (i, row) in power.iter().enumerate() {
+                for (j, p) in row.iter().enumerate() {
+                    m[s + i][s + j] += c.clone() * p
+                }
+            }
+        }
+        s = e
     }
-    if -z2.imag().clone().abs().log10() > a.prec().0 / 4 {
-        z2 = z2.real().clone().into();
+    for d in 1..n {
+        for i in 0..n - d {
+            let j = i + d;
+            if block[i] == block[j] {
+                continue;
+            }
+            let mut sum = t[i][j].clone() * (m[j][j].clone() - &m[i][i]);
+            for k in i + 1..j {
+                sum += t[i][k].clone() * &m[k][j] - m[i][k].clone() * &t[k][j]
+            }
+            m[i][j] = sum / (t[j][j].clone() - &t[i][i])
+        }
     }
-    if real {
-        let mut vec = Vec::new();
-        if z1.imag().is_zero() {
-            vec.push(Number::from(z1, units))
+    let qh = conj_transpose(&q);
+    Ok(Matrix(number_mat(matmul(&matmul(&q, &m), &qh), None, prec)))
+}
+pub fn chol(a: &[Vec<Number>]) -> Result<Vec<Vec<Number>>, Error> {
+    if a.is_empty() || a.iter().any(|b| a.len() != b.len()) {
+        return Err(Error::Dimension("not square", None));
+    }
+    let prec = a[0][0].number.prec().0;
+    let units = a[0][0].units.map(|u| u.root(2.0));
+    let a = complex_mat(a, prec);
+    let mut l = vec![vec![Complex::new(prec); a.len()]; a.len()];
+    for j in 0..a.len() {
+        let mut d = a[j][j].clone();
+        for x in &l[j][..j] {
+            d -= abs2(x)
         }
-        if z2.imag().is_zero() {
-            vec.push(Number::from(z2, units))
+        if d.real().is_sign_negative() || d.real().is_zero() {
+            return Err(Error::Domain("not positive definite", None));
         }
-        if vec.is_empty() {
-            vec![Number::from(Complex::with_val(a.prec(), Nan), None)]
-        } else {
-            vec
+        let d = Complex::with_val(prec, d.real().clone().sqrt());
+        for i in j + 1..a.len() {
+            let mut s = a[i][j].clone();
+            for (x, y) in l[i][..j].iter().zip(&l[j][..j]) {
+                s -= x.clone() * y.clone().conj()
+            }
+            l[i][j] = s / &d
         }
-    } else {
-        vec![Number::from(z1, units), Number::from(z2, units)]
+        l[j][j] = d
     }
+    Ok(number_mat(l, units, prec))
 }
-pub fn cubic(a: Number, b: Number, c: Number, d: Number, real: bool) -> Vec<Number> {
-    let units = div_units(d.units, a.units).map(|a| a.root(3.0));
-    if a.number.is_zero() {
-        return quadratic(b, c, d, real);
+pub fn eigenvectors(mat: &[Vec<Number>], real: bool) -> Result<NumStr, Error> {
+    if !mat.is_empty() && (0..mat.len()).all(|j| mat.len() == mat[j].len()) {
+        let one = Number::from(Complex::with_val(mat[0][0].number.prec(), 1), None);
+        match mat.len() {
+            1 => Ok(NumStr::new(one)),
+            _ => {
+                let p = mat[0][0].number.prec().0;
+                let a = complex_mat(mat, p);
+                let mut v = Vec::new();
+                for (l, _) in clusters(mat, real)? {
+                    let m = number_mat(shift(&a, &l), None, p);
+                    v.extend(null_space(&m, &pivot_tol(&m), 1))
+                }
+                Ok(Matrix(v))
+            }
+        }
+    } else {
+        Err(Error::Dimension("not square", None))
     }
-    let d = d.number;
-    if d.is_zero() {
-        let mut vec = quadratic(a, b, c, real);
-        vec.push(Number::from(Complex::new(d.prec()), vec[0].units));
-        return vec;
+}
+pub fn rcf(mat: Vec<Vec<Number>>) -> Result<NumStr, Error> {
+    if mat.is_empty() || (0..mat.len()).any(|j| mat.len() != mat[j].len()) {
+        return Err(Error::Dimension("matrix not square", None));
     }
-    let a = a.number;
-    let b = b.number;
-    let c = c.number;
-    let prec = a.prec();
-    let threerecip = Float::with_val(prec.0, 3).recip();
-    if b.is_zero() && c.is_zero() {
-        let reuse = pow_nth(d / a.clone(), threerecip.clone().into());
-        let mut z1 = -reuse.clone();
-        let mut z2 = reuse.clone() * Complex::with_val(prec.0, -1).pow(threerecip.clone());
-        let mut z3: Complex = -reuse * Complex::with_val(prec.0, -1).pow(2 * threerecip);
-        if -z1.imag().clone().abs().log10() > a.prec().0 / 4 {
-            z1 = z1.real().clone().into();
+    let pr = mat[0][0].number.prec().0;
+    let l = mat.len();
+    let mut ev = gen_ev(&mat, false)?;
+    let mut beta = Vec::new();
+    let m = Matrix(mat.clone());
+    while !ev.is_empty() {
+        let v = ev
+            .iter_mut()
+            .filter_map(|e| e.pop().map(Vector))
+            .collect::<Vec<NumStr>>();
+        let l = v.len();
+        if l == 0 {
+            break;
         }
-        if -z2.imag().clone().abs().log10() > a.prec().0 / 4 {
-            z2 = z2.real().clone().into();
+        let v = if l == 1 {
+            v[0].clone()
+        } else {
+            v[1..].iter().fold(v[0].clone(), |sum, val| {
+                sum.func(val, add).unwrap_or(Func(String::new()))
+            })
+        };
+        let mut c = v.vec()?;
+        beta.push(c.clone());
+        for _ in 1..l {
+            c = m.mul(&Vector(c))?.vec()?;
+            beta.push(c.clone())
         }
-        if -z3.imag().clone().abs().log10() > a.prec().0 / 4 {
-            z3 = z3.real().clone().into();
+    }
+    change_basis(mat, &identity(l, pr), &transpose(&beta))
+}
+pub fn jcf(mat: Vec<Vec<Number>>) -> Result<NumStr, Error> {
+    if mat.is_empty() || (0..mat.len()).any(|j| mat.len() != mat[j].len()) {
+        return Err(Error::Dimension("matrix not square", None));
+    }
+    let pr = mat[0][0].number.prec().0;
+    let l = mat.len();
+    let beta = transpose(&generalized_eigenvectors(&mat, false)?.mat()?);
+    if beta.len() != l || beta[0].len() != l {
+        return Err(Error::Domain("eigenvectors do not span", None));
+    }
+    change_basis(mat, &identity(l, pr), &beta)
+}
+///the jordan chains of each eigenvalue, every chain starting at its eigenvector
+fn gen_ev(mat: &[Vec<Number>], real: bool) -> Result<Vec<Vec<Vec<Number>>>, Error> {
+    if mat.is_empty() || (0..mat.len()).any(|j| mat.len() != mat[j].len()) {
+        return Err(Error::Dimension("matrix not square", None));
+    }
+    let p = mat[0][0].number.prec().0;
+    let a = complex_mat(mat, p);
+    let mut ev = Vec::new();
+    for (l, k) in clusters(mat, real)? {
+        let n = shift(&a, &l);
+        let mut power = eye(a.len(), p);
+        let mut kernels = vec![Vec::new()];
+        for j in 1..=k {
+            power = matmul(&power, &n);
+            let m = number_mat(power.clone(), None, p);
+            kernels.push(
+                null_space(&m, &pivot_tol(&m), if j == k { k } else { 1 })
+                    .into_iter()
+                    .map(|v| v.into_iter().map(|n| n.number).collect())
+                    .collect::<Vec<Vec<Complex>>>(),
+            )
         }
-        return if real {
-            let mut vec = Vec::new();
-            if z1.imag().is_zero() {
-                vec.push(Number::from(z1, units))
-            }
-            if z2.imag().is_zero() {
-                vec.push(Number::from(z2, units))
-            }
-            if z3.imag().is_zero() {
-                vec.push(Number::from(z3, units))
-            }
-            if vec.is_empty() {
-                vec![Number::from(Complex::with_val(prec, Nan), None)]
-            } else {
-                vec
+        //the images of longer chains already used at each depth
+        let mut taken: Vec<Vec<Vec<Complex>>> = vec![Vec::new(); k + 1];
+        let mut chains = Vec::new();
+        for depth in (1..=k).rev() {
+            let mut span = kernels[depth - 1].clone();
+            span.extend(taken[depth].iter().cloned());
+            for v in &kernels[depth] {
+                span.push(v.clone());
+                let lu = Lu::new(span.clone(), &complex_tol(&span));
+                if lu.pivots.len() != span.len() {
+                    span.pop();
+                    continue;
+                }
+                let mut chain = vec![v.clone()];
+                for d in (1..depth).rev() {
+                    let last = &chain[chain.len() - 1];
+                    let v = n
+                        .iter()
+                        .map(|r| {
+                            r.iter()
+                                .zip(last)
+                                .fold(Complex::new(p), |s, (x, y)| s + x.clone() * y)
+                        })
+                        .collect::<Vec<Complex>>();
+                    taken[d].push(v.clone());
+                    chain.push(v)
+                }
+                chain.reverse();
+                chains.extend(
+                    chain
+                        .into_iter()
+                        .map(|v| v.into_iter().map(|n| Number::from(n, None)).collect()),
+                )
             }
+        }
+        ev.push(chains)
+    }
+    Ok(ev)
+}
+///eigenvalues with their multiplicity, merging those a perturbation of n·eps·‖A‖∞ could have
+///split, m of them by up to (n·eps·‖A‖∞·‖A‖∞^(m-1))^(1/m), each cluster at its mean
+fn clusters(mat: &[Vec<Number>], real: bool) -> Result<Vec<(Complex, usize)>, Error> {
+    let prec = mat[0][0].number.prec().0;
+    let a = complex_mat(mat, prec);
+    let tol = complex_tol(&a);
+    let norm = norm_inf(&a);
+    let radius = |m: usize| {
+        if tol.is_zero() {
+            tol.clone()
         } else {
-            vec![
-                Number::from(z1, units),
-                Number::from(z2, units),
-                Number::from(z3, units),
-            ]
-        };
-    }
-    let b = b / a.clone();
-    let c = c / a.clone();
-    let d = d / a.clone();
-    // https://en.wikipedia.org/wiki/Cubic_equation#General_cubic_formula
-    let d0: Complex = sqr(b.clone()) - 3 * c.clone();
-    let d1: Complex = 2 * cube(b.clone()) - 9 * b.clone() * c.clone() + 27 * d.clone();
-    let c: Complex = sqr(d1.clone()) - 4 * cube(d0.clone());
-    let c: Complex = (d1 + c.sqrt()) / 2;
-    let c = pow_nth(c, threerecip.clone().into());
-    let omega: Complex = Complex::with_val(prec, (-0.5, Float::with_val(prec.0, 3).sqrt() / 2));
-    let mut z1: Complex = if d0.is_zero() {
-        -(b.clone() + c.clone()) / 3
-    } else {
-        -(b.clone() + c.clone() + d0.clone() / c.clone()) / 3
-    };
-    let c0 = c.clone() * omega.clone();
-    let mut z2: Complex = if d0.is_zero() {
-        -(b.clone() + c0.clone()) / 3
-    } else {
-        -(b.clone() + c0.clone() + d0.clone() / c0) / 3
-    };
-    let c1 = c * omega.conj();
-    let mut z3: Complex = if d0.is_zero() {
-        -(b + c1.clone()) / 3
-    } else {
-        -(b + c1.clone() + d0 / c1) / 3
+            ((tol.clone().ln() + norm.clone().ln() * (m - 1)) / m).exp()
+        }
     };
-    if -z1.imag().clone().abs().log10() > a.prec().0 / 4 {
-        z1 = z1.real().clone().into();
-    }
-    if -z2.imag().clone().abs().log10() > a.prec().0 / 4 {
-        z2 = z2.real().clone().into();
+    let mut l = eigenvalues(mat, real)?
+        .vec()?
+        .into_iter()
+        .map(|n| n.number)
+        .collect::<Vec<Complex>>();
+    let mut clusters = Vec::new();
+    while !l.is_empty() {
+        let first = l[0].clone();
+        l.sort_by(|a, b| {
+            let a = (a.clone() - &first).abs().real().clone();
+            let b = (b.clone() - &first).abs().real().clone();
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        });
+        //the largest group around the first one that fits its own radius
+        let (m, mean) = (1..=l.len())
+            .rev()
+            .map(|m| {
+                let mean = l[..m].iter().fold(Complex::new(prec), |s, n| s + n) / m;
+                (m, mean)
+            })
+            .find(|(m, mean)| {
+                let r = radius(*m);
+                l[..*m]
+                    .iter()
+                    .all(|v| (v.clone() - mean).abs().real() <= &r)
+            })
+            .unwrap();
+        clusters.push((mean, m));
+        l.drain(..m);
     }
-    if -z3.imag().clone().abs().log10() > a.prec().0 / 4 {
-        z3 = z3.real().clone().into();
+    Ok(clusters)
+}
+fn shift(a: &[Vec<Complex>], l: &Complex) -> Vec<Vec<Complex>> {
+    let mut a = a.to_vec();
+    for (i, r) in a.iter_mut().enumerate() {
+        r[i] -= l
     }
-    if real {
-        let mut vec = Vec::new();
-        if z1.imag().is_zero() {
-            vec.push(Number::from(z1, units))
-        }
-        if z2.imag().is_zero() {
-            vec.push(Number::from(z2, units))
-        }
-        if z3.imag().is_zero() {
-            vec.push(Number::from(z3, units))
-        }
-        if vec.is_empty() {
-            vec![Number::from(Complex::with_val(prec, Nan), None)]
-        } else {
-            vec
+    a
+}
+///pivots at or below tol count as zero, and the smallest others too until there are min vectors,
+///as a shift by an inexact eigenvalue leaves its null space only nearly singular
+fn null_space(a: &[Vec<Number>], tol: &Float, min: usize) -> Vec<Vec<Number>> {
+    let prec = a[0][0].number.prec().0;
+    let mut lu = Lu::new(complex_mat(a, prec), tol);
+    let n = a[0].len();
+    let missing = min.saturating_sub(n - lu.pivots.len()).min(lu.pivots.len());
+    if missing > 0 {
+        let mut pivots = lu
+            .pivots
+            .iter()
+            .enumerate()
+            .map(|(r, c)| lu.u[r][*c].clone().abs().real().clone())
+            .collect::<Vec<Float>>();
+        pivots.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        lu = Lu::new(complex_mat(a, prec), &pivots[missing - 1])
+    }
+    (0..n)
+        .filter(|c| !lu.pivots.contains(c))
+        .map(|f| {
+            let mut x = vec![Complex::new(prec); n];
+            x[f] = Complex::with_val(prec, 1);
+            lu.back_substitute(&mut x);
+            x.into_iter().map(|n| Number::from(n, None)).collect()
+        })
+        .collect()
+}
+///pivots at or below n·eps·‖A‖∞ count as zero, eps from the working precision
+fn pivot_tol(a: &[Vec<Number>]) -> Float {
+    complex_tol(&complex_mat(a, a[0][0].number.prec().0))
+}
+fn complex_tol(a: &[Vec<Complex>]) -> Float {
+    let prec = a[0][0].prec().0;
+    let n = a.len().max(a[0].len());
+    (Float::with_val(prec, n) * norm_inf(a)) >> (prec.bits - 1)
+}
+pub fn generalized_eigenvectors(mat: &[Vec<Number>], real: bool) -> Result<NumStr, Error> {
+    if !mat.is_empty() && (0..mat.len()).all(|j| mat.len() == mat[j].len()) {
+        let one = Number::from(Complex::with_val(mat[0][0].number.prec(), 1), None);
+        match mat.len() {
+            1 => Ok(NumStr::new(one)),
+            _ => Ok(Matrix(
+                gen_ev(mat, real)?
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .collect::<Vec<Vec<Number>>>(),
+            )),
         }
     } else {
-        vec![
-            Number::from(z1, units),
-            Number::from(z2, units),
-            Number::from(z3, units),
-        ]
+        Err(Error::Dimension("not square", None))
     }
 }
-pub fn quartic(div: Number, b: Number, c: Number, d: Number, e: Number, real: bool) -> Vec<Number> {
-    let units = div_units(e.units, div.units).map(|a| a.root(4.0));
-    if e.number.is_zero() {
-        let mut vec = cubic(div, b, c, d, real);
-        vec.push(Number::from(Complex::new(e.number.prec()), vec[0].units));
-        return vec;
+pub fn change_basis(
+    a: Vec<Vec<Number>>,
+    beta: &[Vec<Number>],
+    gamma: &[Vec<Number>],
+) -> Result<NumStr, Error> {
+    let m = Matrix(a);
+    let tn = Matrix(inverse(&transpose(gamma))?);
+    let mut c = Vec::new();
+    for b in beta {
+        c.push(tn.mul(&Vector(b.to_vec()))?.vec()?)
     }
-    let div = div.number;
-    if div.is_zero() {
-        return cubic(b, c, d, e, real);
+    let tn = Matrix(inverse(&transpose(beta))?);
+    let mut d = Vec::new();
+    for g in gamma {
+        d.push(tn.mul(&Vector(g.to_vec()))?.vec()?)
     }
-    let b = b.number;
-    let c = c.number;
-    let d = d.number;
-    let e = e.number;
-    let prec = div.prec();
-    let threerecip = Float::with_val(prec.0, 3).recip();
-    let a = b / div.clone();
-    let b = c / div.clone();
-    let c = d / div.clone();
-    let d = e / div;
-    if a.is_zero() && b.is_zero() && c.is_zero() {
-        return if d.is_zero() {
-            vec![Number::from(Complex::new(prec), None)]
+    Matrix(c).mul(&m)?.mul(&Matrix(d))
+}
+pub fn coordinate(v: Vec<Number>, beta: Vec<Vec<Number>>) -> Result<NumStr, Error> {
+    let t = transpose(&beta);
+    if exact_matrix(&t).is_some() {
+        return Matrix(inverse(&t)?).mul(&Vector(v));
+    }
+    if t.is_empty() || t.iter().any(|r| r.len() != t.len()) {
+        return Err(Error::Dimension("not square", None));
+    }
+    if v.len() != t.len() {
+        return Err(Error::Dimension("mul err", None));
+    }
+    let prec = t[0][0].number.prec().0;
+    let lu = Lu::new(complex_mat(&t, prec), &Float::new(prec));
+    if lu.pivots.len() != t.len() {
+        return Err(Error::Domain("singular matrix", None));
+    }
+    let units = div_units(v[0].units, t[0][0].units);
+    Ok(Vector(
+        lu.solve(v.into_iter().map(|n| n.number).collect())
+            .into_iter()
+            .map(|n| Number::from(n, units))
+            .collect(),
+    ))
+}
+pub fn linsolve(a: &[Vec<Number>], b: &[Number]) -> Result<NumStr, Error> {
+    if a.is_empty() || a[0].is_empty() || a.iter().any(|r| a[0].len() != r.len()) {
+        return Err(Error::Dimension("invalid matrix", None));
+    }
+    if a.len() != b.len() {
+        return Err(Error::Dimension("mul err", None));
+    }
+    let prec = a[0][0].number.prec().0;
+    let n = a[0].len();
+    let units = div_units(b[0].units, a[0][0].units);
+    let aug = a
+        .iter()
+        .zip(b)
+        .map(|(r, b)| {
+            let mut r = r.clone();
+            r.push(b.clone());
+            r
+        })
+        .collect::<Vec<Vec<Number>>>();
+    let (x, ker) =
+        if let Some(m) = exact_matrix(&aug).and_then(|mut m| exact_rref(&mut m).map(|_| m)) {
+            let mut x = vec![Rational::new(); n];
+            for r in m {
+                match r.iter().position(|q| *q != 0) {
+                    Some(p) if p == n => return Err(Error::Domain("inconsistent system", None)),
+                    Some(p) => x[p] = r[n].clone(),
+                    None => {}
+                }
+            }
+            (
+                x.into_iter()
+                    .map(|q| Number::from_rational(q, units, prec))
+                    .collect::<Vec<Number>>(),
+                kernel(a.to_vec())?,
+            )
         } else {
-            let a = Complex::with_val(prec, 1) / 4;
-            let mut a: Complex = (-d).pow(a);
-            let mut v = vec![a.clone()];
-            for _ in 0..3 {
-                a = a.mul_i(false);
-                v.push(a.clone());
+            let lu = Lu::new(complex_mat(&aug, prec), &pivot_tol(&aug));
+            if lu.pivots.contains(&n) {
+                return Err(Error::Domain("inconsistent system", None));
             }
-            v.into_iter().map(|a| Number::from(a, None)).collect()
+            let mut x = vec![Complex::new(prec); n + 1];
+            x[n] = Complex::with_val(prec, -1);
+            lu.back_substitute(&mut x);
+            x.pop();
+            (
+                x.into_iter().map(|x| Number::from(x, units)).collect(),
+                null_space(a, &pivot_tol(a), 0),
+            )
         };
-    }
-    // https://upload.wikimedia.org/wikipedia/commons/9/99/Quartic_Formula.svg
-    let alpha: Complex = sqr(b.clone()) - 3 * a.clone() * c.clone() + 12 * d.clone();
-    let phi: Complex = 2 * cube(b.clone()) - 9 * a.clone() * b.clone() * c.clone()
-        + 27 * sqr(c.clone())
-        + 27 * sqr(a.clone()) * d.clone()
-        - 72 * b.clone() * d.clone();
-    let omega: Complex = -4 * cube(alpha.clone()) + sqr(phi.clone());
-    let omega: Complex = phi + omega.sqrt();
-    let alpha: Complex = if alpha.is_zero() {
-        Complex::new(prec)
-    } else {
-        Float::with_val(prec.0, 2).pow(threerecip.clone()) * alpha
-            / (3 * pow_nth(omega.clone(), threerecip.clone().into()))
-    };
-    let beta: Complex = omega / 54;
-    let beta: Complex = pow_nth(beta, threerecip.clone().into());
-    let infirst: Complex = sqr(a.clone()) / 4 - 2 * b.clone() / 3 + alpha.clone() + beta.clone();
-    let first: Complex = infirst.clone().sqrt() / 2;
-    let third: Complex = -1 * cube(a.clone()) + 4 * a.clone() * b.clone() - 8 * c.clone();
-    let third: Complex = if third.is_zero() {
-        Complex::new(prec)
+    if ker.is_empty() {
+        Ok(Vector(x))
     } else {
-        third / (first.clone() * 8)
-    };
-    let a4: Complex = -a.clone() / 4;
-    let second: Complex = sqr(a.clone()) / 2 - 4 * b.clone() / 3 - alpha.clone() - beta.clone();
-    let secondn: Complex = second.clone() - third.clone();
-    let secondn: Complex = secondn.sqrt() / 2;
-    let secondp: Complex = second + third.clone();
-    let secondp: Complex = secondp.sqrt() / 2;
-    let mut r1 = a4.clone() - first.clone() - secondn.clone();
-    let mut r2 = a4.clone() - first.clone() + secondn.clone();
-    let mut r3 = a4.clone() + first.clone() - secondp.clone();
-    let mut r4 = a4.clone() + first.clone() + secondp.clone();
-    if -r1.imag().clone().abs().log10() > a.prec().0 / 8 {
-        r1 = r1.real().clone().into();
+        let mut m = vec![x];
+        m.extend(ker);
+        Ok(Matrix(m))
     }
-    if -r2.imag().clone().abs().log10() > a.prec().0 / 8 {
-        r2 = r2.real().clone().into();
+}
+pub fn lstsq(a: &[Vec<Number>], b: &[Number]) -> Result<(Vec<Number>, Number), Error> {
+    if a.len() != b.len() {
+        return Err(Error::Dimension("mul err", None));
+    }
+    let x = Matrix(pinv(a, None)?).mul(&Vector(b.to_vec()))?.vec()?;
+    let r = Matrix(a.to_vec()).mul(&Vector(x.clone()))?.vec()?;
+    let norm = r
+        .iter()
+        .zip(b)
+        .fold(Float::new(a[0][0].number.prec().0), |s, (r, b)| {
+            s + (r.number.clone() - &b.number).abs().real().clone().square()
+        })
+        .sqrt();
+    Ok((
+        x.into_iter()
+            .map(|x| Number::from(x.number, div_units(b[0].units, a[0][0].units)))
+            .collect(),
+        Number::from(norm.into(), b[0].units),
+    ))
+}
+fn fit_data(data: &[Vec<Number>]) -> Result<(Vec<Float>, Vec<Float>), Error> {
+    if data.len() < 2 || data.iter().any(|r| r.len() != 2) {
+        return Err(Error::Dimension(
+            "fit data should be {{x0,y0},{x1,y1}...}",
+            None,
+        ));
     }
-    if -r3.imag().clone().abs().log10() > a.prec().0 / 8 {
-        r3 = r3.real().clone().into();
+    Ok(data
+        .iter()
+        .map(|r| (r[0].number.real().clone(), r[1].number.real().clone()))
+        .unzip())
+}
+///the parameters, r^2 and residuals of a fit
+pub type Fit = (Vec<Number>, Number, Vec<Number>);
+///picks the parameters for mode 0, r^2 for mode 1 and the residuals for mode 2
+pub fn fit_mode((params, r2, residuals): Fit, mode: Option<Complex>) -> Result<NumStr, Error> {
+    match mode {
+        None => Ok(Vector(params)),
+        Some(mode) if mode == 0 => Ok(Vector(params)),
+        Some(mode) if mode == 1 => Ok(NumStr::new(r2)),
+        Some(mode) if mode == 2 => Ok(Vector(residuals)),
+        Some(_) => Err(Error::Domain("fit mode must be 0, 1 or 2", None)),
     }
-    if -r4.imag().clone().abs().log10() > a.prec().0 / 8 {
-        r4 = r4.real().clone().into();
+}
+fn fit_output<F: Fn(&Float) -> Result<Float, Error>>(
+    params: Vec<Number>,
+    model: F,
+    data: &[Vec<Number>],
+) -> Result<Fit, Error> {
+    let (x, y) = fit_data(data)?;
+    let prec = y[0].prec();
+    let units = data[0][1].units;
+    let mean: Float = y.iter().fold(Float::new(prec), |s, y| s + y) / y.len();
+    let mut total = Float::new(prec);
+    let mut ss = Float::new(prec);
+    let mut residuals = Vec::with_capacity(y.len());
+    for (x, y) in x.iter().zip(&y) {
+        let r: Float = y.clone() - model(x)?;
+        total += (y.clone() - &mean).square();
+        ss += r.clone().square();
+        residuals.push(Number::from(r.into(), units));
+    }
+    let r2: Float = if total.is_zero() {
+        Float::with_val(prec, ss.is_zero() as u8)
+    } else {
+        1 - ss / total
+    };
+    Ok((params, Number::from(r2.into(), None), residuals))
+}
+fn levenberg_marquardt<F: Fn(&Float, &[Float]) -> Result<Float, Error>>(
+    f: F,
+    x: &[Float],
+    y: &[Float],
+    mut p: Vec<Float>,
+    prec: Precision,
+) -> Result<Vec<Float>, Error> {
+    let m = p.len();
+    let tol = Float::with_val(prec, 0.5).pow(prec.bits / 6);
+    let residuals = |p: &[Float]| -> Result<(Vec<Float>, Float), Error> {
+        let mut ss = Float::new(prec);
+        let r = x
+            .iter()
+            .zip(y)
+            .map(|(x, y)| {
+                let r: Float = y.clone() - f(x, p)?;
+                ss += r.clone().square();
+                Ok(r)
+            })
+            .collect::<Result<Vec<Float>, Error>>()?;
+        Ok((
+            r,
+            if ss.is_nan() {
+                Float::with_val(prec, Infinity)
+            } else {
+                ss
+            },
+        ))
+    };
+    let (mut r, mut ss) = residuals(&p)?;
+    if !ss.is_finite() {
+        return Err(Error::Domain(
+            "fit function is not finite at the starting values",
+            None,
+        ));
     }
-    if real {
-        let mut vec = Vec::new();
-        if r1.imag().is_zero() {
-            vec.push(Number::from(r1, units))
+    let mut lambda = Float::with_val(prec, 0.001);
+    let limit = Float::with_val(prec, 2).pow(prec.bits / 4);
+    for _ in 0..1000 {
+        if ss.is_zero() {
+            return Ok(p);
         }
-        if r2.imag().is_zero() {
-            vec.push(Number::from(r2, units))
+        let mut jac = vec![vec![Float::new(prec); m]; x.len()];
+        for j in 0..m {
+            let h: Float = Float::with_val(prec, 0.5).pow(prec.bits / 3)
+                * p[j].clone().abs().max(&Float::with_val(prec, 1));
+            let mut pp = p.clone();
+            pp[j] += &h;
+            let mut pm = p.clone();
+            pm[j] -= &h;
+            for (row, x) in jac.iter_mut().zip(x) {
+                row[j] = (f(x, &pp)? - f(x, &pm)?) / (2 * h.clone());
+            }
         }
-        if r3.imag().is_zero() {
-            vec.push(Number::from(r3, units))
+        let a: Vec<Vec<Float>> = (0..m)
+            .map(|i| {
+                (0..m)
+                    .map(|j| {
+                        jac.iter()
+                            .fold(Float::new(prec), |s, row| s + row[i].clone() * &row[j])
+                    })
+                    .collect()
+            })
+            .collect();
+        let g: Vec<Complex> = (0..m)
+            .map(|i| {
+                jac.iter()
+                    .zip(&r)
+                    .fold(Float::new(prec), |s, (row, r)| s + row[i].clone() * r)
+                    .into()
+            })
+            .collect();
+        loop {
+            let aug: Vec<Vec<Complex>> = a
+                .iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    row.iter()
+                        .enumerate()
+                        .map(|(j, a)| {
+                            if i == j {
+                                let d = if a.is_zero() {
+                                    Float::with_val(prec, 1)
+                                } else {
+                                    a.clone()
+                                };
+                                (a.clone() + d * &lambda).into()
+                            } else {
+                                a.clone().into()
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+            let delta = Lu::new(aug, &Float::new(prec)).solve(g.clone());
+            let pn: Vec<Float> = p
+                .iter()
+                .zip(&delta)
+                .map(|(p, d)| p.clone() + d.real())
+                .collect();
+            let (rn, ssn) = residuals(&pn)?;
+            if ssn < ss {
+                let step = delta
+                    .iter()
+                    .fold(Float::new(prec), |s, d| s.max(&d.real().clone().abs()));
+                let scale = p
+                    .iter()
+                    .fold(Float::with_val(prec, 1), |s, p| s.max(&p.clone().abs()));
+                let done =
+                    step <= tol.clone() * scale || ss.clone() - &ssn <= tol.clone().square() * &ss;
+                (p, r, ss) = (pn, rn, ssn);
+                lambda /= 10;
+                if done {
+                    return Ok(p);
+                }
+                break;
+            }
+            lambda *= 10;
+            if lambda > limit {
+                return Ok(p);
+            }
         }
-        if r4.imag().is_zero() {
-            vec.push(Number::from(r4, units))
+    }
+    Err(Error::NotConverged("fit did not converge", None))
+}
+pub fn polyfit(data: &[Vec<Number>], degree: usize) -> Result<Fit, Error> {
+    let (x, _) = fit_data(data)?;
+    if data.len() <= degree {
+        return Err(Error::Dimension("not enough points for the degree", None));
+    }
+    let prec = x[0].prec();
+    let wprec = prec * 2;
+    let mut a: Vec<Vec<Complex>> = x
+        .iter()
+        .map(|x| {
+            (0..=degree)
+                .rev()
+                .map(|k| Complex::with_val(wprec, Float::with_val(wprec, x).pow(k as u32)))
+                .collect()
+        })
+        .collect();
+    //qr of the column scaled vandermonde matrix, the normal equations would square its conditioning
+    let scale: Vec<Float> = (0..=degree)
+        .map(|j| {
+            a.iter().fold(Float::new(wprec), |m, r| {
+                m.max(&Float::with_val(wprec, r[j].abs_ref()))
+            })
+        })
+        .collect();
+    if scale.iter().any(|s| s.is_zero()) {
+        return Err(Error::Domain(
+            "fit data does not determine the polynomial",
+            None,
+        ));
+    }
+    for r in a.iter_mut() {
+        for (a, s) in r.iter_mut().zip(&scale) {
+            *a /= s
         }
-        if vec.is_empty() {
-            vec![Number::from(Complex::with_val(prec, Nan), None)]
-        } else {
-            vec
+    }
+    let tol = Float::with_val(wprec, data.len()) >> (prec.bits - 1);
+    let (q, r) = householder_qr(a);
+    let mut c: Vec<Complex> = (0..=degree)
+        .map(|j| {
+            q.iter().zip(data).fold(Complex::new(wprec), |s, (q, d)| {
+                s + q[j].clone().conj() * d[1].number.real()
+            })
+        })
+        .collect();
+    for j in (0..=degree).rev() {
+        if Float::with_val(wprec, r[j][j].abs_ref()) <= tol {
+            return Err(Error::Domain(
+                "fit data does not determine the polynomial",
+                None,
+            ));
         }
-    } else {
+        for k in j + 1..=degree {
+            let t = r[j][k].clone() * &c[k];
+            c[j] -= t
+        }
+        c[j] /= &r[j][j]
+    }
+    let c: Vec<Float> = c
+        .into_iter()
+        .zip(&scale)
+        .map(|(c, s)| Float::with_val(prec, c.real().clone() / s))
+        .collect();
+    let (xu, yu) = (data[0][0].units, data[0][1].units);
+    let params = c
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            Number::from(
+                c.clone().into(),
+                div_units(yu, xu.map(|u| u.pow((degree - i) as f64))),
+            )
+        })
+        .collect();
+    fit_output(
+        params,
+        |x| Ok(c.iter().fold(Float::new(prec), |s, c| s * x + c)),
+        data,
+    )
+}
+pub fn logfit(data: &[Vec<Number>]) -> Result<Fit, Error> {
+    let (x, y) = fit_data(data)?;
+    if x.iter().any(|x| x.is_sign_negative() || x.is_zero()) {
+        return Err(Error::Domain("logfit needs positive x values", None));
+    }
+    let a: Vec<Vec<Number>> = x
+        .iter()
+        .map(|x| {
+            vec![
+                Number::from(Complex::with_val(x.prec(), 1), None),
+                Number::from(x.clone().ln().into(), None),
+            ]
+        })
+        .collect();
+    let b: Vec<Number> = y
+        .into_iter()
+        .map(|y| Number::from(y.into(), None))
+        .collect();
+    let (c, _) = lstsq(&a, &b)?;
+    let (a, b) = (c[0].number.real().clone(), c[1].number.real().clone());
+    let units = data[0][1].units;
+    fit_output(
         vec![
-            Number::from(r1, units),
-            Number::from(r2, units),
-            Number::from(r3, units),
-            Number::from(r4, units),
-        ]
-    }
+            Number::from(a.clone().into(), units),
+            Number::from(b.clone().into(), units),
+        ],
+        |x| Ok(a.clone() + b.clone() * x.clone().ln()),
+        data,
+    )
 }
-pub fn variance(a: &[Number], mean: Option<Complex>, prec: u32) -> Number {
-    let mean = if let Some(n) = mean {
-        n
+pub fn expfit(data: &[Vec<Number>], power: bool) -> Result<Fit, Error> {
+    let (x, y) = fit_data(data)?;
+    //the starting values come from a linear fit of ln|y|, and of ln x for powfit
+    if y.iter().any(|y| y.is_zero()) {
+        return Err(Error::Domain("fit needs nonzero y values", None));
+    }
+    if power && x.iter().any(|x| x.is_sign_negative() || x.is_zero()) {
+        return Err(Error::Domain("powfit needs positive x values", None));
+    }
+    let prec = y[0].prec();
+    let a: Vec<Vec<Number>> = x
+        .iter()
+        .map(|x| {
+            vec![
+                Number::from(if power { x.clone().ln() } else { x.clone() }.into(), None),
+                Number::from(Complex::with_val(prec, 1), None),
+            ]
+        })
+        .collect();
+    let b: Vec<Number> = y
+        .iter()
+        .map(|y| Number::from(y.clone().abs().ln().into(), None))
+        .collect();
+    let (c, _) = lstsq(&a, &b)?;
+    let sign = if y
+        .iter()
+        .fold(Float::new(prec), |s, y| s + y)
+        .is_sign_negative()
+    {
+        -1
     } else {
-        a.iter()
-            .fold(Complex::new(prec), |sum, val| sum + val.number.clone())
-            / a.len()
+        1
     };
-    let mut variance = Complex::new(prec);
+    let start = vec![
+        c[1].number.real().clone().exp() * sign,
+        c[0].number.real().clone(),
+    ];
+    let model = |x: &Float, p: &[Float]| -> Result<Float, Error> {
+        Ok(if power {
+            p[0].clone() * x.clone().pow(&p[1])
+        } else {
+            p[0].clone() * (p[1].clone() * x).exp()
+        })
+    };
+    let p = levenberg_marquardt(model, &x, &y, start, prec)?;
+    let (xu, yu) = (data[0][0].units, data[0][1].units);
+    let params = if power {
+        vec![
+            Number::from(
+                p[0].clone().into(),
+                div_units(yu, xu.map(|u| u.pow(p[1].to_f64()))),
+            ),
+            Number::from(p[1].clone().into(), None),
+        ]
+    } else {
+        vec![
+            Number::from(p[0].clone().into(), yu),
+            Number::from(p[1].clone().into(), div_units(None, xu)),
+        ]
+    };
+    fit_output(params, |x| model(x, &p), data)
+}
+#[allow(clippy::too_many_arguments)]
+pub fn fit(
+    func: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    var: String,
+    params: Vec<String>,
+    data: &[Vec<Number>],
+    start: Vec<Number>,
+) -> Result<Fit, Error> {
+    if params.len() != start.len() {
+        return Err(Error::Dimension(
+            "fit needs a starting value for each parameter",
+            None,
+        ));
+    }
+    let (x, y) = fit_data(data)?;
+    let xunits = data[0][0].units;
+    let yunits = data[0][1].units;
+    let eval = |x: &Float, p: &[Float], units: &[Option<Units>]| -> Result<Number, Error> {
+        let mut func = func.clone();
+        let mut func_vars = func_vars.clone();
+        for ((name, p), u) in params.iter().zip(p).zip(units) {
+            let num = NumStr::new(Number::from(Complex::with_val(options.precision(), p), *u));
+            func = place_var(func, name, num.clone());
+            func_vars = place_funcvar(func_vars, name, num);
+        }
+        let num = NumStr::new(Number::from(
+            Complex::with_val(options.precision(), x),
+            xunits,
+        ));
+        func = place_var(func, &var, num.clone());
+        func_vars = place_funcvar(func_vars, &var, num);
+        do_math(func, options, func_vars)?.num()
+    };
+    let none = vec![None; params.len()];
+    let model = |x: &Float, p: &[Float]| -> Result<Float, Error> {
+        Ok(eval(x, p, &none)?.number.real().clone())
+    };
+    let p = levenberg_marquardt(
+        model,
+        &x,
+        &y,
+        start.into_iter().map(|p| p.number.real().clone()).collect(),
+        options.precision(),
+    )?;
+    let units = fit_units(
+        |units| eval(&x[0], &p, units).ok().map(|n| n.units),
+        params.len(),
+        xunits,
+        yunits,
+    );
+    fit_output(
+        p.iter()
+            .zip(units)
+            .map(|(p, u)| Number::from(p.clone().into(), u))
+            .collect(),
+        |x| model(x, &p),
+        data,
+    )
+}
+fn fit_units<F: Fn(&[Option<Units>]) -> Option<Option<Units>>>(
+    units_of: F,
+    len: usize,
+    xunits: Option<Units>,
+    yunits: Option<Units>,
+) -> Vec<Option<Units>> {
+    let none = vec![None; len];
+    if xunits.is_none() && yunits.is_none() || len > 4 {
+        return none;
+    }
+    let mut candidates = vec![None];
+    for u in [
+        yunits,
+        div_units(yunits, xunits),
+        div_units(None, xunits),
+        xunits,
+        mul_units(yunits, xunits),
+    ] {
+        if u.is_some_and(|u| !u.is_none()) && !candidates.contains(&u) {
+            candidates.push(u)
+        }
+    }
+    let mut index = vec![0; len];
+    loop {
+        let units: Vec<Option<Units>> = index.iter().map(|i| candidates[*i]).collect();
+        if units_of(&units) == Some(yunits) {
+            return units;
+        }
+        let Some(k) = index.iter().rposition(|i| *i + 1 < candidates.len()) else {
+            return none;
+        };
+        index[k] += 1;
+        index[k + 1..].iter_mut().for_each(|i| *i = 0);
+    }
+}
+pub enum Interpolation {
+    Linear,
+    Pchip,
+    Natural,
+    Clamped(Float, Float),
+}
+fn hermite_slopes(x: &[Float], y: &[Float], kind: &Interpolation) -> Vec<Float> {
+    let n = x.len();
+    let prec = y[0].prec();
+    let h: Vec<Float> = x.windows(2).map(|x| x[1].clone() - &x[0]).collect();
+    let d: Vec<Float> = y
+        .windows(2)
+        .zip(&h)
+        .map(|(y, h)| (y[1].clone() - &y[0]) / h)
+        .collect();
+    match kind {
+        Interpolation::Linear => Vec::new(),
+        _ if n == 2 => match kind {
+            Interpolation::Clamped(a, b) => vec![a.clone(), b.clone()],
+            _ => vec![d[0].clone(), d[0].clone()],
+        },
+        Interpolation::Pchip => {
+            let end = |h0: &Float, h1: &Float, d0: &Float, d1: &Float| -> Float {
+                let m: Float = ((2 * h0.clone() + h1) * d0 - h0.clone() * d1) / (h0.clone() + h1);
+                if m.is_sign_positive() != d0.is_sign_positive() || m.is_zero() {
+                    Float::new(prec)
+                } else if d0.is_sign_positive() != d1.is_sign_positive()
+                    && m.clone().abs() > 3 * d0.clone().abs()
+                {
+                    3 * d0.clone()
+                } else {
+                    m
+                }
+            };
+            let mut m = vec![end(&h[0], &h[1], &d[0], &d[1])];
+            for i in 1..n - 1 {
+                m.push(
+                    if d[i - 1].is_zero()
+                        || d[i].is_zero()
+                        || d[i - 1].is_sign_positive() != d[i].is_sign_positive()
+                    {
+                        Float::new(prec)
+                    } else {
+                        let w1: Float = 2 * h[i].clone() + &h[i - 1];
+                        let w2: Float = h[i].clone() + 2 * h[i - 1].clone();
+                        (w1.clone() + &w2) / (w1 / &d[i - 1] + w2 / &d[i])
+                    },
+                )
+            }
+            m.push(end(&h[n - 2], &h[n - 3], &d[n - 2], &d[n - 3]));
+            m
+        }
+        Interpolation::Natural | Interpolation::Clamped(_, _) => {
+            let mut lower = vec![Float::new(prec); n];
+            let mut diag = vec![Float::new(prec); n];
+            let mut upper = vec![Float::new(prec); n];
+            let mut rhs = vec![Float::new(prec); n];
+            if let Interpolation::Clamped(a, b) = kind {
+                diag[0] = Float::with_val(prec, 1);
+                rhs[0] = a.clone();
+                diag[n - 1] = Float::with_val(prec, 1);
+                rhs[n - 1] = b.clone();
+            } else {
+                diag[0] = Float::with_val(prec, 2);
+                upper[0] = Float::with_val(prec, 1);
+                rhs[0] = 3 * d[0].clone();
+                lower[n - 1] = Float::with_val(prec, 1);
+                diag[n - 1] = Float::with_val(prec, 2);
+                rhs[n - 1] = 3 * d[n - 2].clone();
+            }
+            for i in 1..n - 1 {
+                lower[i] = h[i].clone();
+                diag[i] = 2 * (h[i - 1].clone() + &h[i]);
+                upper[i] = h[i - 1].clone();
+                rhs[i] = 3 * (h[i].clone() * &d[i - 1] + h[i - 1].clone() * &d[i]);
+            }
+            for i in 1..n {
+                let w: Float = lower[i].clone() / &diag[i - 1];
+                diag[i] -= w.clone() * &upper[i - 1];
+                let r: Float = w * &rhs[i - 1];
+                rhs[i] -= r;
+            }
+            let mut m = vec![Float::new(prec); n];
+            m[n - 1] = rhs[n - 1].clone() / &diag[n - 1];
+            for i in (0..n - 1).rev() {
+                m[i] = (rhs[i].clone() - upper[i].clone() * &m[i + 1]) / &diag[i];
+            }
+            m
+        }
+    }
+}
+///the interpolant at x, or with no x sampled at about samples points as a {{x,y}...} matrix
+pub fn piecewise(
+    data: &[Vec<Number>],
+    kind: Interpolation,
+    at: Option<NumStr>,
+    samples: usize,
+) -> Result<NumStr, Error> {
+    let mut data = data.to_vec();
+    data.sort_by(|a, b| a[0].number.real().total_cmp(b[0].number.real()));
+    let (x, y) = fit_data(&data)?;
+    if x.windows(2).any(|x| x[0] == x[1]) {
+        return Err(Error::Domain(
+            "interpolation data has repeated x values",
+            None,
+        ));
+    }
+    if matches!(kind, Interpolation::Pchip) && x.len() == 2 {
+        return piecewise(&data, Interpolation::Linear, at, samples);
+    }
+    let (xunits, yunits) = (data[0][0].units, data[0][1].units);
+    let m = hermite_slopes(&x, &y, &kind);
+    let eval = |t: &Float| -> Number {
+        let i = x[1..x.len() - 1].partition_point(|x| x <= t);
+        let h: Float = x[i + 1].clone() - &x[i];
+        let s: Float = (t.clone() - &x[i]) / &h;
+        let value: Float = if m.is_empty() {
+            y[i].clone() + s * (y[i + 1].clone() - &y[i])
+        } else {
+            let s2: Float = s.clone().square();
+            let s3: Float = s2.clone() * &s;
+            (2 * s3.clone() - 3 * s2.clone() + 1u8) * &y[i]
+                + (s3.clone() - 2 * s2.clone() + &s) * h.clone() * &m[i]
+                + (3 * s2.clone() - 2 * s3.clone()) * &y[i + 1]
+                + (s3 - s2) * h * &m[i + 1]
+        };
+        Number::from(value.into(), yunits)
+    };
+    Ok(match at {
+        Some(Num(t)) => NumStr::new(eval(t.number.real())),
+        Some(Vector(t)) => Vector(t.iter().map(|t| eval(t.number.real())).collect()),
+        Some(_) => {
+            return Err(Error::Type(
+                "expected a number or vector to interpolate at",
+                None,
+            ));
+        }
+        None => {
+            let steps = (samples / (x.len() - 1)).max(1);
+            let mut points = Vec::with_capacity((x.len() - 1) * steps + 1);
+            for w in x.windows(2) {
+                for k in 0..steps {
+                    let t: Float = w[0].clone() + (w[1].clone() - &w[0]) * k / steps;
+                    points.push(vec![Number::from(t.clone().into(), xunits), eval(&t)]);
+                }
+            }
+            let last = x[x.len() - 1].clone();
+            points.push(vec![Number::from(last.clone().into(), xunits), eval(&last)]);
+            Matrix(points)
+        }
+    })
+}
+pub fn lu(a: &[Vec<Number>]) -> Result<[Vec<Vec<Number>>; 3], Error> {
+    if a.is_empty() || a[0].is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
+        return Err(Error::Dimension("invalid matrix", None));
+    }
+    let prec = a[0][0].number.prec().0;
+    let lu = Lu::new(complex_mat(a, prec), &Float::new(prec));
+    let mut p = vec![vec![Number::from(Complex::new(prec), None); a.len()]; a.len()];
+    for (r, i) in p.iter_mut().zip(lu.perm) {
+        r[i] = Number::from(Complex::with_val(prec, 1), None)
+    }
+    Ok([
+        p,
+        lu.l.into_iter()
+            .map(|r| r.into_iter().map(|n| Number::from(n, None)).collect())
+            .collect(),
+        lu.u.into_iter()
+            .map(|r| {
+                r.into_iter()
+                    .map(|n| Number::from(n, a[0][0].units))
+                    .collect()
+            })
+            .collect(),
+    ])
+}
+pub fn rank(a: &[Vec<Number>], tol: Option<Float>) -> Result<usize, Error> {
+    if a.is_empty() || a[0].is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
+        return Err(Error::Dimension("invalid matrix", None));
+    }
+    if let Some(m) = exact_matrix(a).and_then(|mut m| exact_rref(&mut m).map(|_| m)) {
+        return Ok(m.iter().filter(|r| r.iter().any(|q| *q != 0)).count());
+    }
+    let prec = a[0][0].number.prec().0;
+    let (_, s, _) = jacobi_svd(&complex_mat(a, prec * 2))?;
+    let tol = rank_tol(a, &s, tol);
+    Ok(s.iter().filter(|s| !s.is_zero() && **s > tol).count())
+}
+struct Lu {
+    l: Vec<Vec<Complex>>,
+    u: Vec<Vec<Complex>>,
+    perm: Vec<usize>,
+    pivots: Vec<usize>,
+    odd: bool,
+}
+impl Lu {
+    fn new(mut u: Vec<Vec<Complex>>, tol: &Float) -> Self {
+        let prec = u[0][0].prec();
+        let (m, n) = (u.len(), u[0].len());
+        let mut l = vec![vec![Complex::new(prec); m]; m];
+        let mut perm = (0..m).collect::<Vec<usize>>();
+        let mut pivots = Vec::new();
+        let mut odd = false;
+        for col in 0..n {
+            let row = pivots.len();
+            if row == m {
+                break;
+            }
+            let (p, max) = (row..m)
+                .map(|i| (i, u[i][col].clone().abs().real().clone()))
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+                .unwrap();
+            if max <= *tol || max.is_nan() {
+                continue;
+            }
+            if p != row {
+                u.swap(p, row);
+                l.swap(p, row);
+                perm.swap(p, row);
+                odd = !odd
+            }
+            let (top, rest) = u.split_at_mut(row + 1);
+            let v = &top[row];
+            for (r, lr) in rest.iter_mut().zip(l[row + 1..].iter_mut()) {
+                let f = r[col].clone() / &v[col];
+                for (x, y) in r[col + 1..].iter_mut().zip(&v[col + 1..]) {
+                    *x -= y.clone() * &f
+                }
+                r[col] = Complex::new(prec);
+                lr[row] = f
+            }
+            pivots.push(col)
+        }
+        for (i, r) in l.iter_mut().enumerate() {
+            r[i] = Complex::with_val(prec, 1)
+        }
+        Self {
+            l,
+            u,
+            perm,
+            pivots,
+            odd,
+        }
+    }
+    fn back_substitute(&self, x: &mut [Complex]) {
+        for (r, c) in self.u.iter().zip(&self.pivots).rev() {
+            let mut sum = Complex::new(x[0].prec());
+            for (u, x) in r[c + 1..].iter().zip(&x[c + 1..]) {
+                sum += u.clone() * x
+            }
+            x[*c] = -sum / &r[*c]
+        }
+    }
+    fn solve(&self, b: Vec<Complex>) -> Vec<Complex> {
+        let n = self.u.len();
+        let mut x = self
+            .perm
+            .iter()
+            .map(|i| b[*i].clone())
+            .collect::<Vec<Complex>>();
+        for i in 0..n {
+            for j in 0..i {
+                let t = self.l[i][j].clone() * &x[j];
+                x[i] -= t
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                let t = self.u[i][j].clone() * &x[j];
+                x[i] -= t
+            }
+            x[i] /= &self.u[i][i]
+        }
+        x
+    }
+}
+pub fn rref(mut a: Vec<Vec<Number>>) -> Result<Vec<Vec<Number>>, Error> {
+    if a.is_empty() || a[0].is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
+        return Err(Error::Dimension("invalid matrix", None));
+    }
+    if let Some(m) = exact_matrix(&a).and_then(|mut m| exact_rref(&mut m).map(|_| m)) {
+        let prec = a[0][0].number.prec().0;
+        return Ok(a
+            .into_iter()
+            .zip(m)
+            .map(|(r, q)| {
+                r.into_iter()
+                    .zip(q)
+                    .map(|(n, q)| Number::from_rational(q, n.units, prec))
+                    .collect()
+            })
+            .collect());
+    }
+    let mut count = 0;
+    for i in 0..a[0].len() {
+        if let Some((n, v)) = a
+            .clone()
+            .into_iter()
+            .enumerate()
+            .skip(count)
+            .filter(|(_, b)| !b[i].number.is_zero())
+            .max_by(|(_, b), (_, c)| {
+                b[i].number
+                    .clone()
+                    .abs()
+                    .real()
+                    .partial_cmp(c[i].number.clone().abs().real())
+                    .unwrap_or(Ordering::Equal)
+            })
+        {
+            for (a, r) in a.iter_mut().enumerate() {
+                let c = r[i].number.clone() / v[i].number.clone();
+                for (b, num) in r.iter_mut().enumerate() {
+                    if a != n && !v[i].number.is_zero() {
+                        num.number -= v[b].number.clone() * c.clone();
+                    } else {
+                        num.number /= v[i].number.clone()
+                    }
+                }
+            }
+            if n != count {
+                a.swap(count, n);
+            }
+            count += 1;
+        }
+    }
+    Ok(a.to_vec())
+}
+pub fn kernel(a: Vec<Vec<Number>>) -> Result<Vec<Vec<Number>>, Error> {
+    if a.is_empty() || a[0].is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
+        return Err(Error::Dimension("invalid matrix", None));
+    }
+    let pr = a[0][0].number.prec().0;
+    let rref = rref(a.clone())?;
+    let mut ker = Vec::new();
+    let mut leading_ones = Vec::new();
+    for r in &rref {
+        if let Some((pos, _)) = r.iter().enumerate().find(|(_, n)| !n.number.is_zero()) {
+            leading_ones.push(pos);
+        }
+    }
+    let t = transpose(&rref);
+    let exact = rref.iter().flatten().all(|n| n.exact.is_some());
+    let int = |n: i32| {
+        if exact {
+            Number::from_rational(Rational::from(n), None, pr)
+        } else {
+            Number::from(Complex::with_val(pr, n), None)
+        }
+    };
+    let m = Matrix(a);
+    for (i, t) in t.iter().enumerate() {
+        if !leading_ones.contains(&i) {
+            let mut zero = vec![int(0); rref[0].len()];
+            for j in 0..i.min(leading_ones.len()) {
+                if leading_ones[j] < i {
+                    zero[leading_ones[j]] = match &t[j].exact {
+                        Some(q) => Number::from_rational(-q.clone(), None, pr),
+                        None => Number::from(-1.0 * t[j].number.clone(), None),
+                    }
+                }
+            }
+            zero[i] = int(1);
+            if m.mul(&Vector(zero.clone()))?
+                .vec()?
+                .iter()
+                .all(|n| -n.number.clone().abs().real().clone().log2() > pr.bits / 16)
+            {
+                ker.push(zero);
+            }
+        }
+    }
+    Ok(ker)
+}
+fn exact_matrix(a: &[Vec<Number>]) -> Option<Vec<Vec<Rational>>> {
+    a.iter()
+        .map(|r| r.iter().map(|n| n.exact.clone()).collect())
+        .collect()
+}
+///reduces in place and returns the determinant, None once an entry stopped being exact
+fn exact_rref(a: &mut [Vec<Rational>]) -> Option<Rational> {
+    let mut det = Rational::from(1);
+    let mut row = 0;
+    for col in 0..a[0].len() {
+        if row == a.len() {
+            break;
+        }
+        if let Some(p) = (row..a.len()).find(|j| a[*j][col] != 0) {
+            if p != row {
+                a.swap(p, row);
+                det = -det;
+            }
+            let pivot = a[row][col].clone();
+            det *= &pivot;
+            a[row].iter_mut().for_each(|x| *x /= &pivot);
+            let r = a[row].clone();
+            for (j, v) in a.iter_mut().enumerate() {
+                if j != row && v[col] != 0 {
+                    let f = v[col].clone();
+                    for (x, y) in v.iter_mut().zip(r.iter()) {
+                        *x -= f.clone() * y
+                    }
+                }
+            }
+            row += 1;
+        } else {
+            det = Rational::new()
+        }
+    }
+    (fits(&det) && a.iter().flatten().all(fits)).then_some(det)
+}
+pub fn range(a: Vec<Vec<Number>>) -> Result<Vec<Vec<Number>>, Error> {
+    let rref = rref(a.clone())?;
+    let mut ran = Vec::new();
+    let mut leading_ones = Vec::new();
+    for r in &rref {
+        if let Some((pos, _)) = r.iter().enumerate().find(|(_, n)| !n.number.is_zero()) {
+            leading_ones.push(pos);
+        }
+    }
+    let t = transpose(&a);
+    for i in leading_ones {
+        ran.push(t[i].clone());
+    }
+    Ok(ran)
+}
+pub fn mul_units(a: Option<Units>, b: Option<Units>) -> Option<Units> {
+    match (a, b) {
+        (Some(a), None) => Some(a),
+        (None, Some(b)) => Some(b),
+        (Some(a), Some(b)) => Some(a.mul(&b)),
+        _ => None,
+    }
+}
+pub fn div_units(a: Option<Units>, b: Option<Units>) -> Option<Units> {
+    match (a, b) {
+        (Some(a), None) => Some(a),
+        (None, Some(b)) => Some(b.pow(-1.0)),
+        (Some(a), Some(b)) => Some(a.div(&b)),
+        _ => None,
+    }
+}
+pub fn quadratic(a: Number, b: Number, c: Number, real: bool) -> Vec<Number> {
+    if a.number.is_zero() {
+        return if b.number.is_zero() {
+            vec![Number::from(Complex::new(a.number.prec()), None)]
+        } else {
+            let units = div_units(c.units, b.units);
+            let b = b.number;
+            let c = c.number;
+            let mut r = -c / b;
+            if -r.imag().clone().abs().log10() > a.number.prec().0.bits / 4 {
+                r = r.real().clone().into();
+            }
+            if real && !r.imag().is_zero() {
+                vec![Number::from(Complex::with_val(a.number.prec(), Nan), None)]
+            } else {
+                vec![Number::from(r, units)]
+            }
+        };
+    }
+    let units = div_units(c.units, a.units).map(|a| a.root(2.0));
+    let a = a.number;
+    let b = b.number;
+    let c = c.number;
+    let p: Complex = sqr(b.clone());
+    let p: Complex = p - (4 * c * a.clone());
+    let p = p.sqrt();
+    let a: Complex = 2 * a;
+    let mut z1 = (p.clone() - b.clone()) / a.clone();
+    let mut z2 = (-p - b) / a.clone();
+    if -z1.imag().clone().abs().log10() > a.prec().0.bits / 4 {
+        z1 = z1.real().clone().into();
+    }
+    if -z2.imag().clone().abs().log10() > a.prec().0.bits / 4 {
+        z2 = z2.real().clone().into();
+    }
+    if real {
+        let mut vec = Vec::new();
+        if z1.imag().is_zero() {
+            vec.push(Number::from(z1, units))
+        }
+        if z2.imag().is_zero() {
+            vec.push(Number::from(z2, units))
+        }
+        if vec.is_empty() {
+            vec![Number::from(Complex::with_val(a.prec(), Nan), None)]
+        } else {
+            vec
+        }
+    } else {
+        vec![Number::from(z1, units), Number::from(z2, units)]
+    }
+}
+pub fn cubic(a: Number, b: Number, c: Number, d: Number, real: bool) -> Vec<Number> {
+    let units = div_units(d.units, a.units).map(|a| a.root(3.0));
+    if a.number.is_zero() {
+        return quadratic(b, c, d, real);
+    }
+    let d = d.number;
+    if d.is_zero() {
+        let mut vec = quadratic(a, b, c, real);
+        vec.push(Number::from(Complex::new(d.prec()), vec[0].units));
+        return vec;
+    }
+    let a = a.number;
+    let b = b.number;
+    let c = c.number;
+    let prec = a.prec();
+    let threerecip = Float::with_val(prec.0, 3).recip();
+    if b.is_zero() && c.is_zero() {
+        let reuse = pow_nth(d / a.clone(), threerecip.clone().into());
+        let mut z1 = -reuse.clone();
+        let mut z2 = reuse.clone() * Complex::with_val(prec.0, -1).pow(threerecip.clone());
+        let mut z3: Complex = -reuse * Complex::with_val(prec.0, -1).pow(2 * threerecip);
+        if -z1.imag().clone().abs().log10() > a.prec().0.bits / 4 {
+            z1 = z1.real().clone().into();
+        }
+        if -z2.imag().clone().abs().log10() > a.prec().0.bits / 4 {
+            z2 = z2.real().clone().into();
+        }
+        if -z3.imag().clone().abs().log10() > a.prec().0.bits / 4 {
+            z3 = z3.real().clone().into();
+        }
+        return if real {
+            let mut vec = Vec::new();
+            if z1.imag().is_zero() {
+                vec.push(Number::from(z1, units))
+            }
+            if z2.imag().is_zero() {
+                vec.push(Number::from(z2, units))
+            }
+            if z3.imag().is_zero() {
+                vec.push(Number::from(z3, units))
+            }
+            if vec.is_empty() {
+                vec![Number::from(Complex::with_val(prec, Nan), None)]
+            } else {
+                vec
+            }
+        } else {
+            vec![
+                Number::from(z1, units),
+                Number::from(z2, units),
+                Number::from(z3, units),
+            ]
+        };
+    }
+    let b = b / a.clone();
+    let c = c / a.clone();
+    let d = d / a.clone();
+    // https://en.wikipedia.org/wiki/Cubic_equation#General_cubic_formula
+    let d0: Complex = sqr(b.clone()) - 3 * c.clone();
+    let d1: Complex = 2 * cube(b.clone()) - 9 * b.clone() * c.clone() + 27 * d.clone();
+    let c: Complex = sqr(d1.clone()) - 4 * cube(d0.clone());
+    let c = c.sqrt();
+    let c: Complex = if (d1.clone() - &c).abs().real() > (d1.clone() + &c).abs().real() {
+        (d1 - c) / 2
+    } else {
+        (d1 + c) / 2
+    };
+    let c = pow_nth(c, threerecip.clone().into());
+    let omega: Complex = Complex::with_val(prec, (-0.5, Float::with_val(prec.0, 3).sqrt() / 2));
+    let mut z1: Complex = if d0.is_zero() {
+        -(b.clone() + c.clone()) / 3
+    } else {
+        -(b.clone() + c.clone() + d0.clone() / c.clone()) / 3
+    };
+    let c0 = c.clone() * omega.clone();
+    let mut z2: Complex = if d0.is_zero() {
+        -(b.clone() + c0.clone()) / 3
+    } else {
+        -(b.clone() + c0.clone() + d0.clone() / c0) / 3
+    };
+    let c1 = c * omega.conj();
+    let mut z3: Complex = if d0.is_zero() {
+        -(b + c1.clone()) / 3
+    } else {
+        -(b + c1.clone() + d0 / c1) / 3
+    };
+    if -z1.imag().clone().abs().log10() > a.prec().0.bits / 4 {
+        z1 = z1.real().clone().into();
+    }
+    if -z2.imag().clone().abs().log10() > a.prec().0.bits / 4 {
+        z2 = z2.real().clone().into();
+    }
+    if -z3.imag().clone().abs().log10() > a.prec().0.bits / 4 {
+        z3 = z3.real().clone().into();
+    }
+    if real {
+        let mut vec = Vec::new();
+        if z1.imag().is_zero() {
+            vec.push(Number::from(z1, units))
+        }
+        if z2.imag().is_zero() {
+            vec.push(Number::from(z2, units))
+        }
+        if z3.imag().is_zero() {
+            vec.push(Number::from(z3, units))
+        }
+        if vec.is_empty() {
+            vec![Number::from(Complex::with_val(prec, Nan), None)]
+        } else {
+            vec
+        }
+    } else {
+        vec![
+            Number::from(z1, units),
+            Number::from(z2, units),
+            Number::from(z3, units),
+        ]
+    }
+}
+pub fn quartic(div: Number, b: Number, c: Number, d: Number, e: Number, real: bool) -> Vec<Number> {
+    let units = div_units(e.units, div.units).map(|a| a.root(4.0));
+    if e.number.is_zero() {
+        let mut vec = cubic(div, b, c, d, real);
+        vec.push(Number::from(Complex::new(e.number.prec()), vec[0].units));
+        return vec;
+    }
+    let div = div.number;
+    if div.is_zero() {
+        return cubic(b, c, d, e, real);
+    }
+    let b = b.number;
+    let c = c.number;
+    let d = d.number;
+    let e = e.number;
+    let prec = div.prec();
+    let threerecip = Float::with_val(prec.0, 3).recip();
+    let a = b / div.clone();
+    let b = c / div.clone();
+    let c = d / div.clone();
+    let d = e / div;
+    if a.is_zero() && b.is_zero() && c.is_zero() {
+        return if d.is_zero() {
+            vec![Number::from(Complex::new(prec), None)]
+        } else {
+            let a = Complex::with_val(prec, 1) / 4;
+            let mut a: Complex = (-d).pow(a);
+            let mut v = vec![a.clone()];
+            for _ in 0..3 {
+                a = a.mul_i(false);
+                v.push(a.clone());
+            }
+            v.into_iter().map(|a| Number::from(a, None)).collect()
+        };
+    }
+    // https://upload.wikimedia.org/wikipedia/commons/9/99/Quartic_Formula.svg
+    let alpha: Complex = sqr(b.clone()) - 3 * a.clone() * c.clone() + 12 * d.clone();
+    let phi: Complex = 2 * cube(b.clone()) - 9 * a.clone() * b.clone() * c.clone()
+        + 27 * sqr(c.clone())
+        + 27 * sqr(a.clone()) * d.clone()
+        - 72 * b.clone() * d.clone();
+    let omega: Complex = -4 * cube(alpha.clone()) + sqr(phi.clone());
+    let omega: Complex = phi + omega.sqrt();
+    let alpha: Complex = if alpha.is_zero() {
+        Complex::new(prec)
+    } else {
+        Float::with_val(prec.0, 2).pow(threerecip.clone()) * alpha
+            / (3 * pow_nth(omega.clone(), threerecip.clone().into()))
+    };
+    let beta: Complex = omega / 54;
+    let beta: Complex = pow_nth(beta, threerecip.clone().into());
+    let infirst: Complex = sqr(a.clone()) / 4 - 2 * b.clone() / 3 + alpha.clone() + beta.clone();
+    let first: Complex = infirst.clone().sqrt() / 2;
+    let third: Complex = -1 * cube(a.clone()) + 4 * a.clone() * b.clone() - 8 * c.clone();
+    let third: Complex = if third.is_zero() {
+        Complex::new(prec)
+    } else {
+        third / (first.clone() * 8)
+    };
+    let a4: Complex = -a.clone() / 4;
+    let second: Complex = sqr(a.clone()) / 2 - 4 * b.clone() / 3 - alpha.clone() - beta.clone();
+    let secondn: Complex = second.clone() - third.clone();
+    let secondn: Complex = secondn.sqrt() / 2;
+    let secondp: Complex = second + third.clone();
+    let secondp: Complex = secondp.sqrt() / 2;
+    let mut r1 = a4.clone() - first.clone() - secondn.clone();
+    let mut r2 = a4.clone() - first.clone() + secondn.clone();
+    let mut r3 = a4.clone() + first.clone() - secondp.clone();
+    let mut r4 = a4.clone() + first.clone() + secondp.clone();
+    if -r1.imag().clone().abs().log10() > a.prec().0.bits / 8 {
+        r1 = r1.real().clone().into();
+    }
+    if -r2.imag().clone().abs().log10() > a.prec().0.bits / 8 {
+        r2 = r2.real().clone().into();
+    }
+    if -r3.imag().clone().abs().log10() > a.prec().0.bits / 8 {
+        r3 = r3.real().clone().into();
+    }
+    if -r4.imag().clone().abs().log10() > a.prec().0.bits / 8 {
+        r4 = r4.real().clone().into();
+    }
+    if real {
+        let mut vec = Vec::new();
+        if r1.imag().is_zero() {
+            vec.push(Number::from(r1, units))
+        }
+        if r2.imag().is_zero() {
+            vec.push(Number::from(r2, units))
+        }
+        if r3.imag().is_zero() {
+            vec.push(Number::from(r3, units))
+        }
+        if r4.imag().is_zero() {
+            vec.push(Number::from(r4, units))
+        }
+        if vec.is_empty() {
+            vec![Number::from(Complex::with_val(prec, Nan), None)]
+        } else {
+            vec
+        }
+    } else {
+        vec![
+            Number::from(r1, units),
+            Number::from(r2, units),
+            Number::from(r3, units),
+            Number::from(r4, units),
+        ]
+    }
+}
+fn horner(p: &[Complex], z: &Complex) -> (Complex, Complex, Float) {
+    let mut y = p[p.len() - 1].clone();
+    let mut dy = Complex::new(z.prec());
+    let mut bound: Float = y.clone().abs().real().clone();
+    let az: Float = z.clone().abs().real().clone();
+    for a in p[..p.len() - 1].iter().rev() {
+        dy = dy * z + &y;
+        y = y * z + a;
+        bound = bound * &az + a.clone().abs().real();
+    }
+    (y, dy, bound)
+}
+pub fn polyroots(mut p: Vec<Complex>, prec: Precision) -> Result<Vec<Complex>, Error> {
+    //aberth-ehrlich, p is in ascending order
+    while p.last().is_some_and(|a| a.is_zero()) {
+        p.pop();
+    }
+    if p.is_empty() {
+        return Err(Error::Domain("the zero polynomial has every root", None));
+    }
+    let mut roots = Vec::new();
+    while p.len() > 1 && p[0].is_zero() {
+        p.remove(0);
+        roots.push(Complex::new(prec));
+    }
+    if p.len() > 1 {
+        let wprec = prec + 32;
+        let real = p.iter().all(|a| a.imag().is_zero());
+        let p = p
+            .into_iter()
+            .map(|a| Complex::with_val(wprec, a))
+            .collect::<Vec<Complex>>();
+        let n = p.len() - 1;
+        //the precision the floats actually carry, without rug they stay at 53 bits
+        let eps = Float::with_val(wprec, 0.5).pow(p[n].real().prec().bits - 8);
+        let r: Float = (p[0].clone() / &p[n])
+            .abs()
+            .real()
+            .clone()
+            .pow(Float::with_val(wprec, n).recip());
+        let mut z = (0..n)
+            .map(|k| {
+                let t: Float = Float::with_val(wprec, Pi) * 2 * k / n + 0.25;
+                let (s, c) = t.sin_cos(Float::new(wprec));
+                Complex::with_val(wprec, (c * &r, s * &r))
+            })
+            .collect::<Vec<Complex>>();
+        let mut done = vec![false; n];
+        let mut converged = false;
+        for _ in 0..4 * prec.bits {
+            for k in 0..n {
+                if done[k] {
+                    continue;
+                }
+                let (y, dy, bound) = horner(&p, &z[k]);
+                if y.clone().abs().real().clone() <= bound * &eps {
+                    done[k] = true;
+                    continue;
+                }
+                let ratio = y / dy;
+                let mut sum = Complex::new(wprec);
+                for (j, zj) in z.iter().enumerate() {
+                    if j != k {
+                        sum += (z[k].clone() - zj).recip();
+                    }
+                }
+                let w: Complex = ratio.clone() / (1 - ratio * sum);
+                if w.real().is_finite() && w.imag().is_finite() {
+                    z[k] -= w;
+                } else {
+                    z[k] *= Complex::with_val(wprec, (1, 0.5));
+                }
+            }
+            if done.iter().all(|d| *d) {
+                converged = true;
+                break;
+            }
+        }
+        if !converged {
+            return Err(Error::NotConverged("polyroots did not converge", None));
+        }
+        for z in z.iter_mut() {
+            let (mut y, mut dy, _) = horner(&p, z);
+            for _ in 0..3 {
+                let next: Complex = z.clone() - y.clone() / &dy;
+                let (ny, ndy, _) = horner(&p, &next);
+                if ny.clone().abs().real() < y.clone().abs().real() {
+                    *z = next;
+                    (y, dy) = (ny, ndy);
+                } else {
+                    break;
+                }
+            }
+        }
+        let tol = Float::with_val(wprec, 0.5).pow(prec.bits / 4);
+        roots.extend(z.into_iter().map(|mut z| {
+            if real
+                && z.imag().clone().abs()
+                    <= tol.clone()
+                        * z.clone()
+                            .abs()
+                            .real()
+                            .clone()
+                            .max(&Float::with_val(wprec, 1))
+            {
+                z = z.real().clone().into();
+            }
+            z.set_prec(prec.bits);
+            z
+        }));
+    }
+    roots.sort_unstable_by(|a, b| a.total_cmp(b));
+    Ok(roots)
+}
+pub fn variance(a: &[Number], mean: Option<Complex>, prec: Precision) -> Number {
+    let mean = if let Some(n) = mean {
+        n
+    } else {
+        a.iter()
+            .fold(Complex::new(prec), |sum, val| sum + val.number.clone())
+            / a.len()
+    };
+    let mut variance = Complex::new(prec);
     for a in a {
         variance += sqr(a.number.clone() - mean.clone())
     }
@@ -2376,7 +4364,7 @@ pub fn recursion(
     mut func_vars: Vec<(String, Vec<NumStr>)>,
     mut func: Vec<NumStr>,
     options: Options,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     for fv in func_vars.clone() {
         if fv.0.ends_with(')') {
             let mut cont = false;
@@ -2574,7 +4562,8 @@ pub fn erf(z: Complex) -> Complex {
 }
 pub fn erfc(z: Complex) -> Complex {
     let p2: Complex = sqr(z.clone());
-    erfc_recursion(z.clone(), 0, z.prec().0 as usize / 4) / Complex::with_val(z.prec(), Pi).sqrt()
+    erfc_recursion(z.clone(), 0, z.prec().0.bits as usize / 4)
+        / Complex::with_val(z.prec(), Pi).sqrt()
         * z
         / p2.exp()
 }
@@ -2589,8 +4578,19 @@ fn erfc_recursion(z: Complex, iter: usize, max: usize) -> Complex {
         1 + iter / (2 * erfc_recursion(z, iter + 1, max))
     }
 }
+///stirling's series, past 10000 its first terms are as exact as the working precision
+fn stirling(z: Complex) -> Complex {
+    let inv = z.clone().recip();
+    let inv2 = inv.clone().square();
+    //1/12z-1/360z^3+1/1260z^5-1/1680z^7
+    let inner: Complex = 1 - inv2.clone() * (4 - 3 * inv2.clone()) / 14;
+    let series = inv * (1 - inv2 * inner / 30) / 12;
+    let two_pi: Complex = 2 * Complex::with_val(z.prec(), Pi);
+    let ln: Complex = z.clone().ln() - 1;
+    (z.clone() * ln + series).exp() * (two_pi / z).sqrt()
+}
 fn gamma0(z: Complex) -> Complex {
-    let p = z.prec().0 as usize / 4;
+    let p = z.prec().0.bits as usize / 4;
     gamma0_recursion_first(z.clone(), 0, p) + gamma0_recursion_second(z, 0, p)
 }
 fn gamma0_recursion_first(z: Complex, iter: usize, max: usize) -> Complex {
@@ -2620,16 +4620,16 @@ pub fn incomplete_gamma(s: Complex, z: Complex) -> Complex {
         && !s.real().clone().fract().is_zero()
         && *z.real() <= 0.25
     {
-        let p = z.prec().0 as usize / 4;
+        let p = z.prec().0.bits as usize / 4;
         gamma(s.clone()) - lower_incomplete_gamma_recursion(s, z, 0, p)
     } else {
-        let p = z.prec().0 as usize / 4;
+        let p = z.prec().0.bits as usize / 4;
         incomplete_gamma_recursion(s, z, 0, p)
     }
 }
 pub fn lower_incomplete_gamma(s: Complex, z: Complex) -> Complex {
     if s.real().is_sign_positive() && !s.real().clone().fract().is_zero() && *z.real() <= 1 {
-        let p = z.prec().0 as usize / 4;
+        let p = z.prec().0.bits as usize / 4;
         lower_incomplete_gamma_recursion(s, z, 0, p)
     } else {
         gamma(s.clone()) - incomplete_gamma(s, z)
@@ -2639,7 +4639,7 @@ pub fn eta(s: Complex) -> Complex {
     let prec = s.prec().0;
     let mut sum = Complex::new(prec);
     let two = Float::with_val(prec, 2);
-    for n in 0..=(prec / 16).max(16) {
+    for n in 0..=(prec.bits / 16).max(16) {
         let mut innersum = Complex::new(prec);
         let nb = Integer::from(n);
         for k in 0..=n {
@@ -2701,23 +4701,30 @@ pub fn binomial(a: Complex, b: Complex) -> Complex {
         && a.real() >= &0
         && b.real() >= &0
     {
-        Complex::with_val(
+        let k = b
+            .real()
+            .to_integer()
+            .unwrap_or_default()
+            .to_u32()
+            .unwrap_or_default();
+        let n = Complex::with_val(
             a.prec(),
-            a.real().to_integer().unwrap_or_default().binomial(
-                b.real()
-                    .to_integer()
-                    .unwrap_or_default()
-                    .to_u32()
-                    .unwrap_or_default(),
-            ),
-        )
+            a.real().to_integer().unwrap_or_default().binomial(k),
+        );
+        if n.real().is_nan() {
+            (0..k).fold(Complex::with_val(a.prec(), 1), |n, i| {
+                n * (a.clone() - i) / (i + 1)
+            })
+        } else {
+            n
+        }
     } else if a.real().is_sign_negative()
         && a.real().clone().fract().is_zero()
         && a.imag().is_zero()
         && b.imag().is_zero()
     {
         let prec = a.prec().0;
-        let a = a + Complex::with_val(prec, (0, 1)) * Float::with_val(prec, 0.5).pow(prec / 2);
+        let a = a + Complex::with_val(prec, (0, 1)) * Float::with_val(prec, 0.5).pow(prec.bits / 2);
         (gamma(a.clone() + 1) / (gamma(b.clone() + 1) * gamma(a.clone() - b.clone() + 1)))
             .real()
             .clone()
@@ -2755,7 +4762,7 @@ fn lower_incomplete_gamma_recursion(s: Complex, z: Complex, iter: usize, max: us
     }
 }
 pub fn subfactorial(z: Complex) -> Complex {
-    subfactorial_recursion(z.clone(), 0, (z.prec().0 as usize / 4).max(32))
+    subfactorial_recursion(z.clone(), 0, (z.prec().0.bits as usize / 4).max(32))
         + gamma(z.clone() + 1) / Float::with_val(z.prec().0, 1).exp()
 }
 fn subfactorial_recursion(z: Complex, iter: usize, max: usize) -> Complex {
@@ -2778,15 +4785,15 @@ pub fn surface_area(
     vary: String,
     starty_func: Vec<NumStr>,
     endy_func: Vec<NumStr>,
-) -> Result<Number, &'static str> {
+) -> Result<Number, Error> {
     if starty_func.is_empty() || endy_func.is_empty() {
-        return Err("bad start/end");
+        return Err(Error::Domain("bad start/end", None));
     }
     let points = options.prec as usize / 16;
     let unitsx = endx.units;
     let endx = endx.number;
     let deltax: Complex = (endx.clone() - startx.clone()) / (points - 1);
-    let mut area: Complex = Complex::new(options.prec);
+    let mut area: Complex = Complex::new(options.precision());
     if let (Ok(Num(start)), Ok(Num(end))) = (
         do_math(starty_func.clone(), options, func_vars.clone()),
         do_math(endy_func.clone(), options, func_vars.clone()),
@@ -2947,7 +4954,7 @@ pub fn surface_area(
                 }
                 Ok(Number::from(area, units))
             }
-            _ => Err("bad input"),
+            _ => Err(Error::Parse("bad input", None)),
         }
     } else {
         let n = NumStr::new(Number::from(startx.clone(), unitsx));
@@ -3171,7 +5178,7 @@ pub fn surface_area(
                 }
                 Ok(Number::from(area, units))
             }
-            _ => Err("bad input"),
+            _ => Err(Error::Parse("bad input", None)),
         }
     }
 }
@@ -3182,7 +5189,7 @@ pub fn length(
     var: String,
     mut start: Complex,
     end: Number,
-) -> Result<Number, &'static str> {
+) -> Result<Number, Error> {
     let points = options.prec as usize / 4;
     let units = end.units;
     let end = end.number;
@@ -3204,9 +5211,9 @@ pub fn length(
                 None
             }
         }
-        _ => return Err("not supported arc length data"),
+        _ => return Err(Error::Unsupported("not supported arc length data", None)),
     };
-    let mut length = Complex::new(options.prec);
+    let mut length = Complex::new(options.precision());
     for i in 0..points {
         if i + 1 == points {
             start.clone_from(&end)
@@ -3235,784 +5242,869 @@ pub fn length(
                 let nl: Complex = xi
                     .iter()
                     .zip(xf.clone())
-                    .fold(Complex::new(options.prec), |sum, x| {
+                    .fold(Complex::new(options.precision()), |sum, x| {
                         sum + sqr(x.1.number - x.0.number.clone())
                     });
                 length += nl.sqrt();
                 x0 = Vector(xf);
-            }
-            (_, _) => return Err("not supported arc length data"),
-        };
-    }
-    Ok(Number::from(length, length_units))
-}
-#[allow(clippy::too_many_arguments)]
-pub fn area(
-    mut func: Vec<NumStr>,
-    mut func_vars: Vec<(String, Vec<NumStr>)>,
-    options: Options,
-    var: String,
-    mut start: Complex,
-    mut end: Number,
-    nth: Complex,
-    combine: bool,
-) -> Result<NumStr, &'static str> {
-    let mut negate = false;
-    if start.real() > end.number.real() && nth == 1 {
-        negate = true;
-        (end.number, start) = (start, end.number)
-    }
-    if start.real().is_infinite() {
-        let neg = start.real().is_sign_negative();
-        start = Complex::with_val(options.prec, 1) << (options.prec / 16);
-        if neg {
-            start *= -1
-        }
-    }
-    if end.number.real().is_infinite() {
-        let neg = end.number.real().is_sign_negative();
-        end.number = Complex::with_val(options.prec, 1) << (options.prec / 16);
-        if neg {
-            end.number *= -1
-        }
-    }
-    let points = options.prec as usize / 4;
-    let units = end.units;
-    let mut end = end.number;
-    let mut funcs = Vec::new();
-    if combine
-        && !func.is_empty()
-        && func[0] == LeftCurlyBracket
-        && func[func.len() - 1] == RightCurlyBracket
-    {
-        let mut brackets = 0;
-        let mut last = 1;
-        for (i, f) in func.iter().enumerate() {
-            match f {
-                LeftBracket | LeftCurlyBracket => brackets += 1,
-                RightBracket | RightCurlyBracket => brackets -= 1,
-                Comma if brackets == 1 => {
-                    funcs.push(func[last..i].to_vec());
-                    last = i + 1;
-                }
-                _ => {}
-            }
-        }
-        if last != 1 {
-            func = func[last..func.len().saturating_sub(1)].to_vec();
-        }
-    }
-    let mut areavec: Vec<Number> = Vec::new();
-    let div = Float::with_val(options.prec, 0.5).pow(options.prec / 2);
-    let mut delta: Complex = (end.clone() - start.clone()) / points;
-    let mut area: Complex = Complex::new(options.prec);
-    let mut x0 = do_math_with_var(
-        func.clone(),
-        options,
-        func_vars.clone(),
-        &var,
-        NumStr::new(Number::from(start.clone(), units)),
-    )?;
-    if start == end {
-        return match x0 {
-            Num(_) => Ok(NumStr::new(Number::from(Complex::new(options.prec), None))),
-            Vector(_) => Ok(Vector(Vec::new())),
-            _ => Err(
-                "not supported area data, if parametric have the 2nd arg start and end with the { } brackets",
-            ),
-        };
-    }
-    {
-        fn check_bounds(
-            func: Vec<NumStr>,
-            func_vars: Vec<(String, Vec<NumStr>)>,
-            options: Options,
-            var: String,
-            delta: &mut Complex,
-            units: Option<Units>,
-            x0: &mut NumStr,
-            start: &mut Complex,
-            right: bool,
-            compute: bool,
-        ) -> Result<(), &'static str> {
-            let mut has = false;
-            if let Ok(a) = x0.num() {
-                if !a.number.real().is_finite() {
-                    let points = options.prec as usize / 4;
-                    let end = if right {
-                        points * delta.clone() + start.clone()
-                    } else {
-                        start.clone() - points * delta.clone()
-                    };
-                    if right {
-                        *start += delta.clone() >> 4;
-                    } else {
-                        *start -= delta.clone() >> 4;
-                    }
-                    *delta = if right {
-                        (end.clone() - start.clone()) / points
-                    } else {
-                        (start.clone() - end.clone()) / points
-                    };
-                    *x0 = do_math_with_var(
-                        func.clone(),
-                        options,
-                        func_vars.clone(),
-                        &var,
-                        NumStr::new(Number::from(start.clone(), units)),
-                    )?;
-                    has = true
-                }
-            }
-            if !has && compute {
-                *x0 = do_math_with_var(
-                    func.clone(),
-                    options,
-                    func_vars.clone(),
-                    &var,
-                    NumStr::new(Number::from(start.clone(), units)),
-                )?;
-                check_bounds(
-                    func, func_vars, options, var, delta, units, x0, start, right, false,
-                )?
-            }
-            Ok(())
-        }
-        check_bounds(
-            func.clone(),
-            func_vars.clone(),
-            options,
-            var.clone(),
-            &mut delta,
-            units,
-            &mut x0,
-            &mut start,
-            true,
-            false,
-        )?;
-        let mut endv = do_math_with_var(
-            func.clone(),
-            options,
-            func_vars.clone(),
-            &var,
-            NumStr::new(Number::from(end.clone(), units)),
-        )?;
-        check_bounds(
-            func.clone(),
-            func_vars.clone(),
-            options,
-            var.clone(),
-            &mut delta,
-            units,
-            &mut endv,
-            &mut end,
-            false,
-            false,
-        )?;
-        if nth == 1 && end.real().clone() - start.real().clone() > 2.0 {
-            let mut small_start = false;
-            if start.real().is_sign_negative() {
-                if let Ok(a) = x0.num() {
-                    if a.number.real().clone().abs() < Float::with_val(options.prec, 1) >> 16 {
-                        let a = do_math_with_var(
-                            func.clone(),
-                            options,
-                            func_vars.clone(),
-                            &var,
-                            NumStr::new(Number::from(start.clone() * 2 - 1.141, units)),
-                        )?
-                        .num()?;
-                        if a.number.real().clone().abs() < Float::with_val(options.prec, 1) >> 17 {
-                            let a = do_math_with_var(
-                                func.clone(),
-                                options,
-                                func_vars.clone(),
-                                &var,
-                                NumStr::new(Number::from(
-                                    -(Complex::with_val(options.prec, 4) << (options.prec / 16)),
-                                    units,
-                                )),
-                            )?
-                            .num()?;
-                            if a.number.real().clone().abs()
-                                < Float::with_val(options.prec, 1) >> 18
-                            {
-                                small_start = true;
-                            }
-                        }
-                    }
-                }
-            }
-            let mut small_end = false;
-            if end.real().is_sign_positive() {
-                if let Ok(a) = endv.num() {
-                    if a.number.real().clone().abs() < Float::with_val(options.prec, 1) >> 16 {
-                        let a = do_math_with_var(
-                            func.clone(),
-                            options,
-                            func_vars.clone(),
-                            &var,
-                            NumStr::new(Number::from(end.clone() * 2 + 1.141, units)),
-                        )?
-                        .num()?;
-                        if a.number.real().clone().abs() < Float::with_val(options.prec, 1) >> 17 {
-                            let a = do_math_with_var(
-                                func.clone(),
-                                options,
-                                func_vars.clone(),
-                                &var,
-                                NumStr::new(Number::from(
-                                    Complex::with_val(options.prec, 4) << (options.prec / 16),
-                                    units,
-                                )),
-                            )?
-                            .num()?;
-                            if a.number.real().clone().abs()
-                                < Float::with_val(options.prec, 1) >> 18
-                            {
-                                small_end = true
-                            }
-                        }
-                    }
-                }
-            }
-            let two = NumStr::new(Number::from(Complex::with_val(options.prec, 2), None));
-            let one = NumStr::new(Number::from(Complex::with_val(options.prec, 1), None));
-            fn change_var(
-                func: &mut [NumStr],
-                func_vars: &mut Vec<(String, Vec<NumStr>)>,
-                from: String,
-                fto: Vec<NumStr>,
-            ) {
-                let to = format!("@!@{from}@");
-                for v in func.iter_mut() {
-                    if *v == Func(from.clone()) {
-                        *v = Func(to.clone())
-                    }
-                }
-                for func in func_vars.iter_mut() {
-                    for v in func.1.iter_mut() {
-                        if *v == Func(from.clone()) {
-                            *v = Func(to.clone())
-                        }
-                    }
-                }
-                func_vars.push((to, fto));
-            }
-            match (small_start, small_end) {
-                (true, true) => {
-                    change_var(
-                        &mut func,
-                        &mut func_vars,
-                        var.clone(),
-                        vec![
-                            Func(var.clone()),
-                            Division,
-                            LeftBracket,
-                            one.clone(),
-                            Minus,
-                            Func(var.clone()),
-                            Exponent,
-                            two.clone(),
-                            RightBracket,
-                        ],
-                    );
-                    func.insert(0, LeftBracket);
-                    func.extend(vec![
-                        RightBracket,
-                        Multiplication,
-                        LeftBracket,
-                        one.clone(),
-                        Plus,
-                        Func(var.clone()),
-                        Exponent,
-                        two.clone(),
-                        RightBracket,
-                        Division,
-                        LeftBracket,
-                        one,
-                        Minus,
-                        Func(var.clone()),
-                        Exponent,
-                        two.clone(),
-                        RightBracket,
-                        Exponent,
-                        two,
-                    ]);
-                    start = Complex::with_val(options.prec, -1);
-                    end = Complex::with_val(options.prec, 1);
-                    delta = 2 * end.clone() / points;
-                    check_bounds(
-                        func.clone(),
-                        func_vars.clone(),
-                        options,
-                        var.clone(),
-                        &mut delta,
-                        units,
-                        &mut x0,
-                        &mut start,
-                        true,
-                        true,
-                    )?;
-                    check_bounds(
-                        func.clone(),
-                        func_vars.clone(),
-                        options,
-                        var.clone(),
-                        &mut delta,
-                        units,
-                        &mut endv,
-                        &mut end,
-                        false,
-                        true,
-                    )?;
-                }
-                (false, true) => {
-                    change_var(
-                        &mut func,
-                        &mut func_vars,
-                        var.clone(),
-                        vec![
-                            NumStr::new(Number::from(start, None)),
-                            Plus,
-                            Func(var.clone()),
-                            Division,
-                            LeftBracket,
-                            one.clone(),
-                            Minus,
-                            Func(var.clone()),
-                            RightBracket,
-                        ],
-                    );
-                    func.insert(0, LeftBracket);
-                    func.extend(vec![
-                        RightBracket,
-                        Division,
-                        LeftBracket,
-                        one,
-                        Minus,
-                        Func(var.clone()),
-                        RightBracket,
-                        Exponent,
-                        two,
-                    ]);
-                    start = Complex::new(options.prec);
-                    end = Complex::with_val(options.prec, 1);
-                    delta = end.clone() / points;
-                    check_bounds(
-                        func.clone(),
-                        func_vars.clone(),
-                        options,
-                        var.clone(),
-                        &mut delta,
-                        units,
-                        &mut x0,
-                        &mut start,
-                        true,
-                        true,
-                    )?;
-                    check_bounds(
-                        func.clone(),
-                        func_vars.clone(),
-                        options,
-                        var.clone(),
-                        &mut delta,
-                        units,
-                        &mut endv,
-                        &mut end,
-                        false,
-                        true,
-                    )?;
-                }
-                (true, false) => {
-                    change_var(
-                        &mut func,
-                        &mut func_vars,
-                        var.clone(),
-                        vec![
-                            NumStr::new(Number::from(end, None)),
-                            Minus,
-                            LeftBracket,
-                            one.clone(),
-                            Minus,
-                            Func(var.clone()),
-                            RightBracket,
-                            Division,
-                            Func(var.clone()),
-                        ],
-                    );
-                    func.insert(0, LeftBracket);
-                    func.extend(vec![
-                        RightBracket,
-                        Division,
-                        Func(var.clone()),
-                        Exponent,
-                        two,
-                    ]);
-                    start = Complex::new(options.prec);
-                    end = Complex::with_val(options.prec, 1);
-                    delta = end.clone() / points;
-                    check_bounds(
-                        func.clone(),
-                        func_vars.clone(),
-                        options,
-                        var.clone(),
-                        &mut delta,
-                        units,
-                        &mut x0,
-                        &mut start,
-                        true,
-                        true,
-                    )?;
-                    check_bounds(
-                        func.clone(),
-                        func_vars.clone(),
-                        options,
-                        var.clone(),
-                        &mut delta,
-                        units,
-                        &mut endv,
-                        &mut end,
-                        false,
-                        true,
-                    )?;
+            }
+            (_, _) => return Err(Error::Unsupported("not supported arc length data", None)),
+        };
+    }
+    Ok(Number::from(length, length_units))
+}
+#[allow(clippy::too_many_arguments)]
+pub fn area(
+    mut func: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    var: String,
+    start: Complex,
+    end: Number,
+    nth: Complex,
+    combine: bool,
+    tol: Option<Float>,
+) -> Result<NumStr, Error> {
+    let units = end.units;
+    let end = end.number;
+    let mut funcs = Vec::new();
+    if combine
+        && !func.is_empty()
+        && func[0] == LeftCurlyBracket
+        && func[func.len() - 1] == RightCurlyBracket
+    {
+        let mut brackets = 0;
+        let mut last = 1;
+        for (i, f) in func.iter().enumerate() {
+            match f {
+                LeftBracket | LeftCurlyBracket => brackets += 1,
+                RightBracket | RightCurlyBracket => brackets -= 1,
+                Comma if brackets == 1 => {
+                    funcs.push(func[last..i].to_vec());
+                    last = i + 1;
                 }
                 _ => {}
             }
         }
-    }
-    let yunits = if let Num(ref a) = x0 { a.units } else { None };
-    if !funcs.is_empty() {
-        let mut nx0t = Complex::new(options.prec);
-        for i in &funcs {
-            nx0t += sqr((do_math_with_var(
-                i.clone(),
-                options,
-                func_vars.clone(),
-                &var,
-                NumStr::new(Number::from(start.clone() + div.clone(), units)),
-            )?
-            .num()?
-            .number
-                - do_math_with_var(
-                    i.clone(),
-                    options,
-                    func_vars.clone(),
-                    &var,
-                    NumStr::new(Number::from(start.clone(), units)),
-                )?
-                .num()?
-                .number)
-                / div.clone())
+        if last != 1 {
+            func = func[last..func.len().saturating_sub(1)].to_vec();
         }
     }
-    let h: Complex = delta.clone() / 4;
-    let body = |i: usize| -> Result<(Option<Complex>, Option<Vec<Number>>), &str> {
-        let point = if i + 1 == points {
-            end.clone()
-        } else {
-            start.clone() + (i + 1) * delta.clone()
-        };
-        let x0 = do_math_with_var(
-            func.clone(),
-            options,
-            func_vars.clone(),
-            &var,
-            NumStr::new(Number::from(point.clone() - 4 * h.clone(), units)),
-        )?;
-        let x1 = do_math_with_var(
-            func.clone(),
-            options,
-            func_vars.clone(),
-            &var,
-            NumStr::new(Number::from(point.clone() - 3 * h.clone(), units)),
-        )?;
-        let x2 = do_math_with_var(
-            func.clone(),
+    let eval = |func: &[NumStr], x: Complex| {
+        do_math_with_var(
+            func.to_vec(),
             options,
             func_vars.clone(),
             &var,
-            NumStr::new(Number::from(point.clone() - 2 * h.clone(), units)),
-        )?;
-        let x3 = do_math_with_var(
-            func.clone(),
+            NumStr::new(Number::from(x, units)),
+        )
+    };
+    if start == end {
+        return match eval(&func, start)? {
+            Num(_) => Ok(NumStr::new(Number::from(
+                Complex::new(options.precision()),
+                None,
+            ))),
+            Vector(_) => Ok(Vector(Vec::new())),
+            _ => Err(Error::Unsupported(
+                "not supported area data, if parametric have the 2nd arg start and end with the { } brackets",
+                None,
+            )),
+        };
+    }
+    let prec = options.precision();
+    let tail =
+        start.real().is_infinite() && start.real().is_sign_negative() && end.real().is_finite();
+    let (start, end) = if nth == 1 {
+        (start, end)
+    } else {
+        let clamp = |z: Complex| {
+            if z.real().is_infinite() {
+                let big = Complex::with_val(prec, 1) << (prec.bits / 16);
+                if z.real().is_sign_negative() {
+                    -big
+                } else {
+                    big
+                }
+            } else {
+                z
+            }
+        };
+        (if tail { start } else { clamp(start) }, clamp(end))
+    };
+    let width: Complex = end.clone() - &start;
+    let map = interval_map(start, end, prec);
+    let half = Float::with_val(prec, 0.5);
+    let (yunits, size) = match eval(&func, map(&half, &half).0)? {
+        Num(a) => (vec![a.units], None),
+        Vector(a) if funcs.is_empty() => (a.iter().map(|a| a.units).collect(), Some(a.len())),
+        _ => {
+            return Err(Error::Unsupported(
+                "not supported area data, if parametric have the 2nd arg start and end with the { } brackets",
+                None,
+            ));
+        }
+    };
+    let div = Float::with_val(prec, 0.5).pow(prec.bits / 2);
+    let integrand = |s: &Float, c: &Float| -> Result<Vec<Complex>, Error> {
+        let (x, mut weight) = map(s, c);
+        if !funcs.is_empty() {
+            let mut speed = Complex::new(prec);
+            for f in &funcs {
+                speed += sqr((eval(f, x.clone() + div.clone())?.num()?.number
+                    - eval(f, x.clone())?.num()?.number)
+                    / div.clone())
+            }
+            weight *= speed.sqrt()
+        }
+        if nth != 1 {
+            let kernel = if tail {
+                Complex::with_val(prec, c / s)
+            } else {
+                width.clone() * c
+            };
+            weight *= pow_nth(kernel, nth.clone() - 1)
+        }
+        match eval(&func, x)? {
+            Num(a) if size.is_none() => Ok(vec![a.number * weight]),
+            Vector(a) if Some(a.len()) == size => {
+                Ok(a.into_iter().map(|a| a.number * &weight).collect())
+            }
+            _ => Err(Error::Unsupported("not supported area data", None)),
+        }
+    };
+    let (area, err) = quadrature(integrand, options, tol.as_ref(), 1)?;
+    let g = gamma(nth);
+    let err = Complex::with_val(prec, err) / g.clone().abs();
+    let (area, errors): (Vec<Number>, Vec<Number>) = area
+        .into_iter()
+        .zip(yunits)
+        .map(|(a, y)| {
+            let units = mul_units(units, y);
+            (
+                Number::from(a / g.clone(), units),
+                Number::from(err.clone(), units),
+            )
+        })
+        .unzip();
+    Ok(match (size, tol) {
+        (None, None) => NumStr::new(area[0].clone()),
+        (None, Some(_)) => Vector(vec![area[0].clone(), errors[0].clone()]),
+        (Some(_), None) => Vector(area),
+        (Some(_), Some(_)) => Matrix(vec![area, errors]),
+    })
+}
+fn interval_map(
+    mut start: Complex,
+    mut end: Complex,
+    prec: Precision,
+) -> impl Fn(&Float, &Float) -> (Complex, Complex) + Sync {
+    let mut sign = 1;
+    if (start.real().is_infinite() || end.real().is_infinite()) && start.real() > end.real() {
+        sign = -1;
+        (start, end) = (end, start)
+    }
+    let inf_start = start.real().is_infinite();
+    let inf_end = end.real().is_infinite();
+    let width: Complex = end.clone() - &start;
+    move |s: &Float, c: &Float| {
+        let (x, jacobian) = match (inf_start, inf_end) {
+            (false, false) => (
+                if s <= c {
+                    start.clone() + width.clone() * s
+                } else {
+                    end.clone() - width.clone() * c
+                },
+                width.clone(),
+            ),
+            (false, true) => (
+                start.clone() + Float::with_val(prec, s / c),
+                Complex::with_val(prec, 1 / Float::with_val(prec, c.square_ref())),
+            ),
+            (true, false) => (
+                end.clone() - Float::with_val(prec, c / s),
+                Complex::with_val(prec, 1 / Float::with_val(prec, s.square_ref())),
+            ),
+            (true, true) => {
+                let y = Float::with_val(prec, s - c);
+                let sc = Float::with_val(prec, s * c);
+                (
+                    Complex::with_val(prec, y.clone() / (4 * sc.clone())),
+                    Complex::with_val(prec, (1 + y.square()) / (8 * sc.square())),
+                )
+            }
+        };
+        (x, jacobian * sign)
+    }
+}
+pub fn multiple_integral(
+    func: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    bounds: &[(String, Vec<NumStr>, Vec<NumStr>)],
+    tol: Option<&Float>,
+) -> Result<(Number, Float), Error> {
+    nested_integral(func, func_vars, options, bounds, tol, bounds.len() as u32)
+}
+fn nested_integral(
+    func: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    bounds: &[(String, Vec<NumStr>, Vec<NumStr>)],
+    tol: Option<&Float>,
+    dims: u32,
+) -> Result<(Number, Float), Error> {
+    let (var, start, end) = &bounds[0];
+    let start = do_math(start.clone(), options, func_vars.clone())?
+        .num()?
+        .number;
+    let end = do_math(end.clone(), options, func_vars.clone())?.num()?;
+    if start == end.number {
+        return Ok((
+            Number::from(Complex::new(options.precision()), None),
+            Float::new(options.precision()),
+        ));
+    }
+    let map = interval_map(start, end.number, options.precision());
+    let units = OnceLock::new();
+    let integrand = |s: &Float, c: &Float| -> Result<Vec<Complex>, Error> {
+        let (x, jacobian) = map(s, c);
+        let num = NumStr::new(Number::from(x, end.units));
+        let func = place_var(func.clone(), var, num.clone());
+        let func_vars = place_funcvar(func_vars.clone(), var, num.clone());
+        let value = if bounds.len() == 1 {
+            do_math(func, options, func_vars)?.num()?
+        } else {
+            let bounds: Vec<(String, Vec<NumStr>, Vec<NumStr>)> = bounds[1..]
+                .iter()
+                .map(|(v, a, b)| {
+                    (
+                        v.clone(),
+                        place_var(a.clone(), var, num.clone()),
+                        place_var(b.clone(), var, num.clone()),
+                    )
+                })
+                .collect();
+            nested_integral(func, func_vars, options, &bounds, tol, dims)?.0
+        };
+        units.get_or_init(|| value.units);
+        Ok(vec![value.number * jacobian])
+    };
+    let (value, err) = quadrature(integrand, options, tol, dims)?;
+    Ok((
+        Number::from(
+            value[0].clone(),
+            mul_units(end.units, units.get().copied().flatten()),
+        ),
+        err,
+    ))
+}
+#[allow(clippy::too_many_arguments)]
+pub fn contour(
+    func: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    var: String,
+    path: Vec<NumStr>,
+    path_var: String,
+    start: Complex,
+    end: Complex,
+    tol: Option<Float>,
+) -> Result<(Number, Float), Error> {
+    if start == end {
+        return Ok((
+            Number::from(Complex::new(options.precision()), None),
+            Float::new(options.precision()),
+        ));
+    }
+    let prec = options.precision();
+    let map = interval_map(start, end, prec);
+    let h = Float::with_val(prec, 1) >> (prec.bits / 3);
+    let gamma = |t: Complex| {
+        do_math_with_var(
+            path.clone(),
             options,
             func_vars.clone(),
-            &var,
-            NumStr::new(Number::from(point.clone() - h.clone(), units)),
-        )?;
-        let x4 = do_math_with_var(
+            &path_var,
+            NumStr::new(Number::from(t, None)),
+        )?
+        .num()
+    };
+    let units = OnceLock::new();
+    let integrand = |s: &Float, c: &Float| -> Result<Vec<Complex>, Error> {
+        let (t, jacobian) = map(s, c);
+        let z = gamma(t.clone())?;
+        let dz = (gamma(t.clone() + &h)?.number - gamma(t - &h)?.number) / (2 * h.clone());
+        let value = do_math_with_var(
             func.clone(),
             options,
             func_vars.clone(),
             &var,
-            NumStr::new(Number::from(point.clone(), units)),
-        )?;
-        match (x0, x1, x2, x3, x4) {
-            (Num(nx0), Num(nx1), Num(nx2), Num(nx3), Num(nx4)) if funcs.is_empty() => {
-                let n0;
-                let n1;
-                let n2;
-                let n3;
-                let n4;
-                if nth != 1.0 {
-                    let nt = pow_nth(end.clone() - point.clone() + delta.clone(), nth.clone() - 1);
-                    n0 = nx0.number * nt;
-                    let n: Complex = end.clone() - point.clone() + 3 * h.clone();
-                    let nt = pow_nth(n, nth.clone() - 1);
-                    n1 = nx1.number * nt;
-                    let n: Complex = end.clone() - point.clone() + 2 * h.clone();
-                    let nt = pow_nth(n, nth.clone() - 1);
-                    n2 = nx2.number * nt;
-                    let n: Complex = end.clone() - point.clone() + h.clone();
-                    let nt = pow_nth(n, nth.clone() - 1);
-                    n3 = nx3.number * nt;
-                    let n: Complex = end.clone() - point.clone();
-                    let nt = pow_nth(n, nth.clone() - 1);
-                    n4 = nx4.number * nt;
-                } else {
-                    n0 = nx0.number;
-                    n1 = nx1.number;
-                    n2 = nx2.number;
-                    n3 = nx3.number;
-                    n4 = nx4.number;
+            NumStr::new(z.clone()),
+        )?
+        .num()?;
+        units.get_or_init(|| mul_units(value.units, z.units));
+        Ok(vec![value.number * dz * jacobian])
+    };
+    let (value, err) = quadrature(integrand, options, tol.as_ref(), 1)?;
+    Ok((
+        Number::from(value[0].clone(), units.get().copied().flatten()),
+        err,
+    ))
+}
+pub fn quadrature<F>(
+    f: F,
+    options: Options,
+    tol: Option<&Float>,
+    dims: u32,
+) -> Result<(Vec<Complex>, Float), Error>
+where
+    F: Fn(&Float, &Float) -> Result<Vec<Complex>, Error> + Sync,
+{
+    let prec = options.precision();
+    let strict = tol.is_some();
+    //nested integrals share the precision since full precision at every level costs nodes^dims,
+    //each level still aims for 48 bits when the precision has them
+    let bits = ((prec.bits - 10) / dims).max((prec.bits - 10).min(48));
+    let tol = tol
+        .cloned()
+        .unwrap_or_else(|| Float::with_val(prec, 1) >> bits);
+    //once the part limit is hit the default still takes half the precision, at most 64 bits
+    let limit_tol = Float::with_val(prec, 1) >> (prec.bits / 2).min(64);
+    let norm = |v: &[Complex]| {
+        v.iter()
+            .map(|a| Float::with_val(prec, a.abs_ref()))
+            .fold(Float::with_val(prec, 1), |m, a| m.max(&a))
+    };
+    let zero = Float::new(prec);
+    let one = Float::with_val(prec, 1);
+    let (sum, err) = tanh_sinh(&f, &zero, &one, options, &tol, &one)?;
+    let mut parts = vec![(zero, one, sum, err, 0)];
+    //parts within their share of tol are settled, leaving the budget to the ones holding a kink
+    let mut settled: Vec<(Vec<Complex>, Float)> = Vec::new();
+    let mut splits = 0;
+    loop {
+        let mut total: Vec<Complex> = Vec::new();
+        let mut err = Float::new(prec);
+        for (sum, e) in settled
+            .iter()
+            .map(|p| (&p.0, &p.1))
+            .chain(parts.iter().map(|p| (&p.2, &p.3)))
+        {
+            if total.is_empty() {
+                total = sum.clone()
+            } else if sum.len() != total.len() {
+                return Err(Error::Dimension("inconsistent integrand size", None));
+            } else {
+                for (t, s) in total.iter_mut().zip(sum.iter()) {
+                    *t += s
                 }
-                Ok((
-                    Some(2 * h.clone() * (7 * (n0 + n4) + 12 * n2 + 32 * (n1 + n3)) / 45),
-                    None,
-                ))
             }
-            (Num(nx0), Num(nx1), Num(nx2), Num(nx3), Num(nx4)) => {
-                let mut nx1t = Complex::new(options.prec);
-                let mut nx2t = Complex::new(options.prec);
-                let mut nx3t = Complex::new(options.prec);
-                let mut nx4t = Complex::new(options.prec);
-                for i in &funcs {
-                    nx1t += sqr((do_math_with_var(
-                        i.clone(),
-                        options,
-                        func_vars.clone(),
-                        &var,
-                        NumStr::new(Number::from(
-                            point.clone() - 3 * h.clone() + div.clone(),
-                            units,
-                        )),
-                    )?
-                    .num()?
-                    .number
-                        - do_math_with_var(
-                            i.clone(),
-                            options,
-                            func_vars.clone(),
-                            &var,
-                            NumStr::new(Number::from(point.clone() - 3 * h.clone(), units)),
-                        )?
-                        .num()?
-                        .number)
-                        / div.clone());
-                    nx2t += sqr((do_math_with_var(
-                        i.clone(),
-                        options,
-                        func_vars.clone(),
-                        &var,
-                        NumStr::new(Number::from(
-                            point.clone() - 2 * h.clone() + div.clone(),
-                            units,
-                        )),
-                    )?
-                    .num()?
-                    .number
-                        - do_math_with_var(
-                            i.clone(),
-                            options,
-                            func_vars.clone(),
-                            &var,
-                            NumStr::new(Number::from(point.clone() - 2 * h.clone(), units)),
-                        )?
-                        .num()?
-                        .number)
-                        / div.clone());
-                    nx3t += sqr((do_math_with_var(
-                        i.clone(),
-                        options,
-                        func_vars.clone(),
-                        &var,
-                        NumStr::new(Number::from(point.clone() - h.clone() + div.clone(), units)),
-                    )?
-                    .num()?
-                    .number
-                        - do_math_with_var(
-                            i.clone(),
-                            options,
-                            func_vars.clone(),
-                            &var,
-                            NumStr::new(Number::from(point.clone() - h.clone(), units)),
-                        )?
-                        .num()?
-                        .number)
-                        / div.clone());
-                    nx4t += sqr((do_math_with_var(
-                        i.clone(),
-                        options,
-                        func_vars.clone(),
-                        &var,
-                        NumStr::new(Number::from(point.clone() + div.clone(), units)),
-                    )?
-                    .num()?
-                    .number
-                        - do_math_with_var(
-                            i.clone(),
-                            options,
-                            func_vars.clone(),
-                            &var,
-                            NumStr::new(Number::from(point.clone(), units)),
-                        )?
-                        .num()?
-                        .number)
-                        / div.clone())
-                }
-                let nx1 = nx1.number * nx1t.sqrt();
-                let nx2 = nx2.number * nx2t.sqrt();
-                let nx3 = nx3.number * nx3t.sqrt();
-                let nx4 = nx4.number * nx4t.sqrt();
-                let n0;
-                let n1;
-                let n2;
-                let n3;
-                let n4;
-                if nth != 1.0 {
-                    if i == 0 {
-                        let nt =
-                            pow_nth(end.clone() - point.clone() + delta.clone(), nth.clone() - 1);
-                        n0 = nx0.number * nt;
-                    } else {
-                        n0 = nx0.number;
-                    }
-                    let n: Complex = end.clone() - point.clone() + 3 * h.clone();
-                    let nt: Complex = pow_nth(n, nth.clone() - 1);
-                    n1 = nx1 * nt;
-                    let n: Complex = end.clone() - point.clone() + 2 * h.clone();
-                    let nt: Complex = pow_nth(n, nth.clone() - 1);
-                    n2 = nx2 * nt;
-                    let n: Complex = end.clone() - point.clone() + h.clone();
-                    let nt: Complex = pow_nth(n, nth.clone() - 1);
-                    n3 = nx3 * nt;
-                    let n: Complex = end.clone() - point.clone();
-                    let nt: Complex = pow_nth(n, nth.clone() - 1);
-                    n4 = nx4 * nt;
+            err += e
+        }
+        let scale = norm(&total);
+        if err <= tol.clone() * &scale {
+            return Ok((total, err));
+        }
+        let mut i = 0;
+        while i < parts.len() {
+            let width = Float::with_val(prec, &parts[i].1 - &parts[i].0);
+            if parts[i].3 <= tol.clone() * &scale * width {
+                let (_, _, sum, e, _) = parts.swap_remove(i);
+                settled.push((sum, e))
+            } else {
+                i += 1
+            }
+        }
+        let Some(i) = (0..parts.len())
+            .filter(|i| parts[*i].4 < 3 && parts.len() < 32 && splits < 256)
+            .max_by(|a, b| {
+                parts[*a]
+                    .3
+                    .partial_cmp(&parts[*b].3)
+                    .unwrap_or(Ordering::Equal)
+            })
+        else {
+            if !strict && err <= limit_tol * &scale {
+                return Ok((total, err));
+            }
+            return Err(Error::NotConverged("integral did not converge", None));
+        };
+        splits += 1;
+        let (lo, hi, _, e, stalls) = parts.swap_remove(i);
+        let mid = Float::with_val(prec, &lo + &hi) / 2;
+        let floor = Float::with_val(prec, &mid - &lo) * &scale;
+        let (lsum, lerr) = tanh_sinh(&f, &lo, &mid, options, &tol, &floor)?;
+        let (rsum, rerr) = tanh_sinh(&f, &mid, &hi, options, &tol, &floor)?;
+        //halving that does not lower the error has hit the rounding floor
+        //halvings that keep not lowering the error have hit the rounding floor,
+        //a single one can be a kink estimate that jumped around
+        let stalls = if Float::with_val(prec, &lerr + &rerr) >= e {
+            stalls + 1
+        } else {
+            0
+        };
+        parts.push((lo, mid.clone(), lsum, lerr, stalls));
+        parts.push((mid, hi, rsum, rerr, stalls));
+    }
+}
+fn tanh_sinh<F>(
+    f: &F,
+    lo: &Float,
+    hi: &Float,
+    options: Options,
+    tol: &Float,
+    floor: &Float,
+) -> Result<(Vec<Complex>, Float), Error>
+where
+    F: Fn(&Float, &Float) -> Result<Vec<Complex>, Error> + Sync,
+{
+    let prec = options.precision();
+    let width = Float::with_val(prec, hi - lo);
+    let pi = Float::with_val(prec, Pi);
+    let cut = Float::with_val(prec, 1) >> (prec.bits / 2);
+    //nodes reach about tol² from the ends with room to spare, so the tail an x^-1/2 endpoint
+    //leaves past the outermost node stays below tol
+    let bits = tol
+        .get_exp()
+        .map(|e| (16 - 2 * e).clamp(32, 2 * prec.bits as i32) as f64)
+        .unwrap_or(2.0 * prec.bits as f64);
+    let tmax = (bits * std::f64::consts::LN_2 / std::f64::consts::PI).asinh();
+    let mut sum: Vec<Complex> = Vec::new();
+    let mut err = Float::with_val(prec, Infinity);
+    let mut last: Option<Float> = None;
+    let mut before: Option<Float> = None;
+    let mut fast = false;
+    //the tail past the outermost finite node at each end, including nodes dropped for rounding
+    //onto the endpoint, an x^-a endpoint leaves term/(aπ cosh t) there, taken for a down to 1/4
+    let mut tails = [
+        (Float::new(prec), Float::new(prec)),
+        (Float::new(prec), Float::new(prec)),
+    ];
+    for level in 0..=6 {
+        let h = Float::with_val(prec, 1) >> level;
+        let ts: Vec<Float> = if level == 0 {
+            (0..=tmax as usize)
+                .map(|k| Float::with_val(prec, k))
+                .collect()
+        } else {
+            (0..)
+                .map(|j| Float::with_val(prec, 2 * j + 1) * &h)
+                .take_while(|t| *t <= tmax)
+                .collect()
+        };
+        let mut nodes = Vec::new();
+        for t in ts {
+            let u = pi.clone() / 2 * t.clone().sinh();
+            let e = Float::with_val(prec, -2 * u).exp();
+            let sigma: Float = e.clone() / (1 + e);
+            let w: Float = pi.clone() * &sigma * (1 - sigma.clone()) * t.clone().cosh() * &width;
+            let small = sigma < cut;
+            if t.is_zero() {
+                let s: Float = lo.clone() + width.clone() / 2;
+                let c = 1 - s.clone();
+                nodes.push((s, c, w, small, t, None));
+            } else {
+                let d = sigma * &width;
+                nodes.push((
+                    Float::with_val(prec, lo + &d),
+                    1 - lo.clone() - &d,
+                    w.clone(),
+                    small,
+                    t.clone(),
+                    Some(0),
+                ));
+                nodes.push((hi.clone() - &d, 1 - hi.clone() + &d, w, small, t, Some(1)));
+            }
+        }
+        #[allow(clippy::type_complexity)]
+        let body = |(s, c, w, small, t, end): (Float, Float, Float, bool, Float, Option<usize>)| {
+            f(&s, &c).map(|v| {
+                if v.iter()
+                    .all(|a| a.real().is_finite() && a.imag().is_finite())
+                {
+                    Some((
+                        v.into_iter().map(|a| a * &w).collect::<Vec<Complex>>(),
+                        t,
+                        end,
+                    ))
+                } else if small {
+                    Some((Vec::new(), t, end))
                 } else {
-                    n0 = nx0.number;
-                    n1 = nx1;
-                    n2 = nx2;
-                    n3 = nx3;
-                    n4 = nx4;
+                    None
                 }
-                Ok((
-                    Some(2 * h.clone() * (7 * (n0 + n4.clone()) + 12 * n2 + 32 * (n1 + n3)) / 45),
-                    None,
-                ))
+            })
+        };
+        #[allow(clippy::type_complexity)]
+        let data: Vec<Result<Option<(Vec<Complex>, Float, Option<usize>)>, Error>> =
+            if options.graphing {
+                nodes.into_iter().map(body).collect()
+            } else {
+                #[cfg(feature = "rayon")]
+                let r = nodes.into_par_iter().map(body).collect();
+                #[cfg(not(feature = "rayon"))]
+                let r = nodes.into_iter().map(body).collect();
+                r
+            };
+        let mut level_sum: Vec<Complex> = Vec::new();
+        for d in data {
+            let Some((v, t, end)) = d? else {
+                return Ok((sum, Float::with_val(prec, Infinity)));
+            };
+            if v.is_empty() {
+                continue;
             }
-            (Vector(nx0), Vector(nx1), Vector(nx2), Vector(nx3), Vector(nx4)) if !combine => {
-                let mut areavec = Vec::new();
-                for i in 0..nx0.len() {
-                    let n0;
-                    let n1;
-                    let n2;
-                    let n3;
-                    let n4;
-                    if nth != 1.0 {
-                        let nt =
-                            pow_nth(end.clone() - point.clone() + delta.clone(), nth.clone() - 1);
-                        n0 = nx0[i].number.clone() * nt;
-                        let n: Complex = end.clone() - point.clone() + 3 * h.clone();
-                        let nt = pow_nth(n, nth.clone() - 1);
-                        n1 = nx1[i].number.clone() * nt;
-                        let n: Complex = end.clone() - point.clone() + 2 * h.clone();
-                        let nt = pow_nth(n, nth.clone() - 1);
-                        n2 = nx2[i].number.clone() * nt;
-                        let n: Complex = end.clone() - point.clone() + h.clone();
-                        let nt = pow_nth(n, nth.clone() - 1);
-                        n3 = nx3[i].number.clone() * nt;
-                        let n: Complex = end.clone() - point.clone();
-                        let nt = pow_nth(n, nth.clone() - 1);
-                        n4 = nx4[i].number.clone() * nt;
-                    } else {
-                        n0 = nx0[i].number.clone();
-                        n1 = nx1[i].number.clone();
-                        n2 = nx2[i].number.clone();
-                        n3 = nx3[i].number.clone();
-                        n4 = nx4[i].number.clone();
-                    }
-                    areavec.push(Number::from(
-                        2 * h.clone() * (7 * (n0 + n4) + 12 * n2 + 32 * (n1 + n3)) / 45,
-                        match (units, nx1[i].units) {
-                            (Some(a), Some(b)) => Some(a.mul(&b)),
-                            (Some(a), None) | (None, Some(a)) => Some(a),
-                            (None, None) => None,
-                        },
-                    ))
+            if let Some(end) = end {
+                if t > tails[end].0 {
+                    let tail = largest(&v, prec) * 4 / (pi.clone() * t.clone().cosh());
+                    tails[end] = (t, tail)
                 }
-                Ok((None, Some(areavec)))
             }
-            (_, _, _, _, _) => Err(
-                "not supported area data, if parametric have the 2nd arg start and end with the { } brackets",
-            ),
+            if level_sum.is_empty() {
+                level_sum = vec![Complex::new(prec); v.len()];
+            }
+            if v.len() != level_sum.len() {
+                return Err(Error::Dimension("inconsistent integrand size", None));
+            }
+            for (a, b) in level_sum.iter_mut().zip(v) {
+                *a += b
+            }
+        }
+        let next: Vec<Complex> = if level == 0 {
+            level_sum
+        } else if level_sum.is_empty() {
+            sum.iter().map(|a| a.clone() / 2).collect()
+        } else if sum.is_empty() {
+            level_sum.into_iter().map(|a| a * &h).collect()
+        } else {
+            if sum.len() != level_sum.len() {
+                return Err(Error::Dimension("inconsistent integrand size", None));
+            }
+            sum.iter()
+                .zip(level_sum)
+                .map(|(a, b)| a.clone() / 2 + b * &h)
+                .collect()
+        };
+        if level > 0 {
+            let delta = sum
+                .iter()
+                .zip(next.iter())
+                .map(|(a, b)| Float::with_val(prec, (b.clone() - a).abs_ref()))
+                .fold(Float::new(prec), |m, a| m.max(&a));
+            //the difference is taken to square with each level only after two fast drops in a row,
+            //a kink or jump converges algebraically and can drop once by chance
+            fast = match (&before, &last) {
+                (Some(before), Some(last)) => {
+                    Float::with_val(prec, &delta * 16) < *last
+                        && Float::with_val(prec, last * 16) < *before
+                }
+                _ => false,
+            };
+            err = match &last {
+                Some(last) if fast => Float::with_val(prec, delta.square_ref()) / last,
+                Some(last) => delta.clone().max(last),
+                None => delta.clone(),
+            } + &tails[0].1
+                + &tails[1].1;
+            before = last;
+            last = Some(delta);
+        }
+        sum = next;
+        if level >= 2 {
+            let scale = sum
+                .iter()
+                .map(|a| Float::with_val(prec, a.abs_ref()))
+                .fold(floor.clone(), |m, a| m.max(&a));
+            //past level 4 the nodes only pay off while converging fast, a kink is cheaper to bisect
+            if err <= tol.clone() * scale || (level >= 4 && !fast) {
+                break;
+            }
+        }
+    }
+    Ok((sum, err))
+}
+fn largest(v: &[Complex], prec: Precision) -> Float {
+    v.iter()
+        .map(|a| Float::with_val(prec, a.abs_ref()))
+        .fold(Float::new(prec), |m, a| m.max(&a))
+}
+#[allow(clippy::too_many_arguments)]
+pub fn odesolve(
+    func: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    tvar: String,
+    yvar: String,
+    t0: Number,
+    y0: NumStr,
+    t1: Float,
+    samples: usize,
+    stiff: bool,
+    tol: Option<Float>,
+) -> Result<NumStr, Error> {
+    let prec = options.precision();
+    let (mut y, yunits, system) = match y0 {
+        Num(a) => (vec![a.number], vec![a.units], false),
+        Vector(a) if !a.is_empty() => (
+            a.iter().map(|a| a.number.clone()).collect(),
+            a.iter().map(|a| a.units).collect(),
+            true,
+        ),
+        _ => {
+            return Err(Error::Unsupported(
+                "odesolve needs a scalar or vector y0",
+                None,
+            ));
         }
     };
-    #[allow(clippy::type_complexity)]
-    let data: Vec<Result<(Option<Complex>, Option<Vec<Number>>), &str>> = if options.graphing {
-        (0..points).map(body).collect()
-    } else {
-        #[cfg(feature = "rayon")]
-        let r = (0..points).into_par_iter().map(body).collect();
-        #[cfg(not(feature = "rayon"))]
-        let r = (0..points).map(body).collect();
+    let tunits = t0.units;
+    let n = y.len();
+    let state = |y: &[Complex]| {
+        if system {
+            Vector(
+                y.iter()
+                    .zip(&yunits)
+                    .map(|(a, u)| Number::from(a.clone(), *u))
+                    .collect(),
+            )
+        } else {
+            NumStr::new(Number::from(y[0].clone(), yunits[0]))
+        }
+    };
+    let f = |t: &Float, y: &[Complex]| -> Result<Vec<Complex>, Error> {
+        let t = NumStr::new(Number::from(Complex::with_val(prec, t), tunits));
+        let y = state(y);
+        let func = place_var(place_var(func.clone(), &tvar, t.clone()), &yvar, y.clone());
+        let func_vars = place_funcvar(place_funcvar(func_vars.clone(), &tvar, t), &yvar, y);
+        match do_math(func, options, func_vars)? {
+            Num(a) if n == 1 => Ok(vec![a.number]),
+            Vector(a) if a.len() == n => Ok(a.into_iter().map(|a| a.number).collect()),
+            _ => Err(Error::Dimension("odesolve f(t,y) does not match y0", None)),
+        }
+    };
+    //sqrt(eps) as both the relative and absolute tolerance, capped where the step count explodes
+    let tol = tol.unwrap_or_else(|| {
+        Float::with_val(prec, 1) >> (prec.bits / 2).min(if stiff { 32 } else { 48 })
+    });
+    let norm = |e: &[Complex], y: &[Complex], z: &[Complex]| {
+        let mut sum = Float::new(prec);
+        for ((e, y), z) in e.iter().zip(y).zip(z) {
+            let scale = Float::with_val(prec, y.abs_ref()).max(&Float::with_val(prec, z.abs_ref()))
+                * &tol
+                + &tol;
+            sum += (Float::with_val(prec, e.abs_ref()) / scale).square()
+        }
+        (sum / n as u32).sqrt()
+    };
+    let mut t = t0.number.real().clone();
+    let mut rows = Vec::new();
+    let row = |t: &Float, y: &[Complex]| {
+        let mut r = vec![Number::from(Complex::with_val(prec, t), tunits)];
+        r.extend(
+            y.iter()
+                .zip(&yunits)
+                .map(|(a, u)| Number::from(a.clone(), *u)),
+        );
         r
     };
-    for d in data {
-        if let Ok(a) = d {
-            if let Some(a) = a.0 {
-                area += a
-            } else if let Some(a) = a.1 {
-                if areavec.is_empty() {
-                    areavec = a
-                } else {
-                    for (a, b) in areavec.iter_mut().zip(a.iter()) {
-                        a.number += b.number.clone()
-                    }
-                }
+    if samples > 0 {
+        rows.push(row(&t, &y))
+    }
+    let span = Float::with_val(prec, &t1 - &t);
+    let mut h: Float = span.clone() / 100;
+    let tiny = Float::with_val(prec, span.abs_ref()) >> (prec.bits / 2);
+    let mut steps = 0;
+    let mut fsal: Option<Vec<Complex>> = None;
+    for k in 1..=samples.max(1) {
+        let target = if samples == 0 {
+            t1.clone()
+        } else {
+            span.clone() * k as u32 / samples as u32 + t0.number.real()
+        };
+        while t != target {
+            steps += 1;
+            if steps > 100000 || h.clone().abs() < tiny {
+                return Err(Error::NotConverged("odesolve step size too small", None));
+            }
+            let remaining = Float::with_val(prec, &target - &t);
+            let last = h.clone().abs() >= remaining.clone().abs();
+            let step = if last { remaining } else { h.clone() };
+            let k1 = match fsal.take() {
+                Some(k) => k,
+                None => f(&t, &y)?,
+            };
+            let (next, err, k_end, order) = if stiff {
+                let (next, err, k_end) = rosenbrock_step(&f, &t, &y, &k1, &step)?;
+                (next, err, k_end, 3)
+            } else {
+                let (next, err, k_end) = dormand_prince_step(&f, &t, &y, &k1, &step)?;
+                (next, err, k_end, 5)
+            };
+            let err = norm(&err, &y, &next);
+            if err <= 1 {
+                t = if last { target.clone() } else { t + &step };
+                y = next;
+                fsal = Some(k_end);
+            } else {
+                fsal = Some(k1);
+            }
+            let factor = if err.is_zero() {
+                Float::with_val(prec, 5)
+            } else if !err.is_finite() {
+                Float::with_val(prec, 0.2)
+            } else {
+                (err.clone().pow(-1.0 / order as f64) * 0.9f64)
+                    .clamp(&Float::with_val(prec, 0.2), &Float::with_val(prec, 5))
+            };
+            if !last || err > 1 {
+                h = step * factor
             }
-        } else if let Err(s) = d {
-            return Err(s);
+        }
+        if samples > 0 {
+            rows.push(row(&t, &y))
         }
     }
-    let g = gamma(nth.clone());
-    if areavec.is_empty() {
-        Ok(NumStr::new(Number::from(
-            if negate { -area / g } else { area / g },
-            match (units, yunits) {
-                (Some(a), Some(b)) => Some(a.mul(&b)),
-                (Some(a), None) | (None, Some(a)) => Some(a),
-                (None, None) => None,
-            },
-        )))
+    if samples > 0 {
+        Ok(Matrix(rows))
     } else {
-        Ok(Vector(
-            areavec
-                .iter()
-                .map(|a| {
-                    Number::from(
-                        if negate {
-                            -a.number.clone() / g.clone()
-                        } else {
-                            a.number.clone() / g.clone()
-                        },
-                        a.units,
-                    )
-                })
-                .collect::<Vec<Number>>(),
-        ))
+        Ok(state(&y))
+    }
+}
+#[allow(clippy::type_complexity)]
+fn dormand_prince_step<F>(
+    f: &F,
+    t: &Float,
+    y: &[Complex],
+    k1: &[Complex],
+    h: &Float,
+) -> Result<(Vec<Complex>, Vec<Complex>, Vec<Complex>), Error>
+where
+    F: Fn(&Float, &[Complex]) -> Result<Vec<Complex>, Error>,
+{
+    const C: [(i32, i32); 6] = [(1, 5), (3, 10), (4, 5), (8, 9), (1, 1), (1, 1)];
+    const A: [&[(i32, i32)]; 6] = [
+        &[(1, 5)],
+        &[(3, 40), (9, 40)],
+        &[(44, 45), (-56, 15), (32, 9)],
+        &[(19372, 6561), (-25360, 2187), (64448, 6561), (-212, 729)],
+        &[
+            (9017, 3168),
+            (-355, 33),
+            (46732, 5247),
+            (49, 176),
+            (-5103, 18656),
+        ],
+        &[
+            (35, 384),
+            (0, 1),
+            (500, 1113),
+            (125, 192),
+            (-2187, 6784),
+            (11, 84),
+        ],
+    ];
+    const E: [(i32, i32); 7] = [
+        (71, 57600),
+        (0, 1),
+        (-71, 16695),
+        (71, 1920),
+        (-17253, 339200),
+        (22, 525),
+        (-1, 40),
+    ];
+    let prec = h.prec();
+    let frac = |(a, b): (i32, i32)| Float::with_val(prec, a) / b;
+    let mut k = vec![k1.to_vec()];
+    let mut next = y.to_vec();
+    for (c, a) in C.iter().zip(A) {
+        next = y.to_vec();
+        for (kj, aj) in k.iter().zip(a) {
+            let w = frac(*aj) * h;
+            for (x, d) in next.iter_mut().zip(kj) {
+                *x += d.clone() * &w
+            }
+        }
+        k.push(f(&(frac(*c) * h + t), &next)?);
+    }
+    let mut err = vec![Complex::new(prec); y.len()];
+    for (kj, e) in k.iter().zip(E) {
+        let w = frac(e) * h;
+        for (x, d) in err.iter_mut().zip(kj) {
+            *x += d.clone() * &w
+        }
     }
+    Ok((next, err, k.pop().unwrap()))
+}
+#[allow(clippy::type_complexity)]
+fn rosenbrock_step<F>(
+    f: &F,
+    t: &Float,
+    y: &[Complex],
+    f0: &[Complex],
+    h: &Float,
+) -> Result<(Vec<Complex>, Vec<Complex>, Vec<Complex>), Error>
+where
+    F: Fn(&Float, &[Complex]) -> Result<Vec<Complex>, Error>,
+{
+    let prec = h.prec();
+    let n = y.len();
+    let sqrt2 = Float::with_val(prec, 2).sqrt();
+    let d = 1 / (2 + sqrt2.clone());
+    let e32 = 6 + sqrt2;
+    let delta = Float::with_val(prec, 1) >> (prec.bits / 2);
+    let dt = f(&(t.clone() + &delta), y)?
+        .into_iter()
+        .zip(f0)
+        .map(|(a, b)| (a - b) / &delta)
+        .collect::<Vec<Complex>>();
+    let hd = Float::with_val(prec, h * &d);
+    let mut w = vec![vec![Complex::new(prec); n]; n];
+    for j in 0..n {
+        let step = Float::with_val(prec, y[j].abs_ref()).max(&Float::with_val(prec, 1)) * &delta;
+        let mut z = y.to_vec();
+        z[j] += &step;
+        for (i, fj) in f(t, &z)?.into_iter().enumerate() {
+            w[i][j] = -(fj - &f0[i]) / &step * &hd
+        }
+        w[j][j] += 1;
+    }
+    let lu = Lu::new(w, &Float::new(prec));
+    let k1 = lu.solve(
+        f0.iter()
+            .zip(&dt)
+            .map(|(a, b)| a.clone() + b.clone() * &hd)
+            .collect(),
+    );
+    let half: Vec<Complex> = y
+        .iter()
+        .zip(&k1)
+        .map(|(a, b)| a.clone() + b.clone() * h / 2)
+        .collect();
+    let f1 = f(&(t.clone() + h.clone() / 2), &half)?;
+    let k2: Vec<Complex> = lu
+        .solve(f1.iter().zip(&k1).map(|(a, b)| a.clone() - b).collect())
+        .into_iter()
+        .zip(&k1)
+        .map(|(a, b)| a + b)
+        .collect();
+    let next: Vec<Complex> = y
+        .iter()
+        .zip(&k2)
+        .map(|(a, b)| a.clone() + b.clone() * h)
+        .collect();
+    let f2 = f(&(t.clone() + h), &next)?;
+    let k3 = lu.solve(
+        (0..n)
+            .map(|i| {
+                f2[i].clone() - (k2[i].clone() - &f1[i]) * &e32 - (k1[i].clone() - &f0[i]) * 2
+                    + dt[i].clone() * &hd
+            })
+            .collect(),
+    );
+    let err = (0..n)
+        .map(|i| (k1[i].clone() - k2[i].clone() * 2 + &k3[i]) * h / 6)
+        .collect();
+    Ok((next, err, f2))
 }
 pub fn iter(
     func: Vec<NumStr>,
@@ -4022,9 +6114,12 @@ pub fn iter(
     mut x: NumStr,
     n: Float,
     all: bool,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     if n.is_infinite() {
-        let mut last = NumStr::new(Number::from(Complex::with_val(options.prec, Nan), None));
+        let mut last = NumStr::new(Number::from(
+            Complex::with_val(options.precision(), Nan),
+            None,
+        ));
         let mut j = 0;
         if all {
             if let Num(num) = x.clone() {
@@ -4032,7 +6127,7 @@ pub fn iter(
                 loop {
                     if j > 10000 {
                         return Ok(NumStr::new(Number::from(
-                            Complex::with_val(options.prec, Nan),
+                            Complex::with_val(options.precision(), Nan),
                             None,
                         )));
                     }
@@ -4050,7 +6145,7 @@ pub fn iter(
                 loop {
                     if j > 10000 {
                         return Ok(NumStr::new(Number::from(
-                            Complex::with_val(options.prec, Nan),
+                            Complex::with_val(options.precision(), Nan),
                             None,
                         )));
                     }
@@ -4064,13 +6159,13 @@ pub fn iter(
                 }
                 Ok(Matrix(vec))
             } else {
-                Err("unsupported iter")
+                Err(Error::Unsupported("unsupported iter", None))
             }
         } else {
             while last != x {
                 if j > 10000 {
                     return Ok(NumStr::new(Number::from(
-                        Complex::with_val(options.prec, Nan),
+                        Complex::with_val(options.precision(), Nan),
                         None,
                     )));
                 }
@@ -4102,7 +6197,7 @@ pub fn iter(
                 }
                 Ok(Matrix(vec))
             } else {
-                Err("unsupported iter")
+                Err(Error::Unsupported("unsupported iter", None))
             }
         } else {
             for _ in 0..n {
@@ -4118,87 +6213,88 @@ pub fn solve(
     mut options: Options,
     var: String,
     x: Number,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     //newtons method, x-f(x)/f'(x)
     let units = x.units;
     let mut x = x.number;
     if x.real().is_nan() {
         let points = if x.imag().is_zero() {
             vec![
-                Number::from(Complex::new(options.prec), None),
-                Number::from(Complex::with_val(options.prec, -2), None),
-                Number::from(Complex::with_val(options.prec, 2), None),
+                Number::from(Complex::new(options.precision()), None),
+                Number::from(Complex::with_val(options.precision(), -2), None),
+                Number::from(Complex::with_val(options.precision(), 2), None),
             ]
         } else {
             vec![
-                Number::from(Complex::new(options.prec), None),
-                Number::from(Complex::with_val(options.prec, -2), None),
-                Number::from(Complex::with_val(options.prec, 2), None),
-                Number::from(Complex::with_val(options.prec, (0, -2)), None),
-                Number::from(Complex::with_val(options.prec, (0, 2)), None),
-                Number::from(Complex::with_val(options.prec, (-4, 4)), None),
-                Number::from(Complex::with_val(options.prec, (4, 4)), None),
-                Number::from(Complex::with_val(options.prec, (-4, -4)), None),
-                Number::from(Complex::with_val(options.prec, (4, -4)), None),
+                Number::from(Complex::new(options.precision()), None),
+                Number::from(Complex::with_val(options.precision(), -2), None),
+                Number::from(Complex::with_val(options.precision(), 2), None),
+                Number::from(Complex::with_val(options.precision(), (0, -2)), None),
+                Number::from(Complex::with_val(options.precision(), (0, 2)), None),
+                Number::from(Complex::with_val(options.precision(), (-4, 4)), None),
+                Number::from(Complex::with_val(options.precision(), (4, 4)), None),
+                Number::from(Complex::with_val(options.precision(), (-4, -4)), None),
+                Number::from(Complex::with_val(options.precision(), (4, -4)), None),
             ]
         };
         let mut values: Vec<Number> = Vec::new();
         let mut first = true;
         'main: for p in points {
-            let v = solve(
+            let v = match solve(
                 func.clone(),
                 func_vars.clone(),
                 options,
                 var.clone(),
                 p.clone(),
-            )?
-            .num()?;
-            if !v.number.real().is_nan() {
-                if first {
-                    first = false;
-                    func.insert(0, LeftBracket);
-                    func.push(RightBracket);
-                    func.push(Division);
-                    func.push(LeftBracket);
-                    func.push(LeftBracket);
-                    func.push(Func(var.clone()));
-                    func.push(Minus);
-                    func.push(NumStr::new(v.clone()));
-                    func.push(RightBracket);
-                    func.push(RightBracket);
-                } else {
-                    for n1 in &values {
-                        if -(n1.number.real() - v.number.real().clone())
+            ) {
+                Ok(v) => v.num()?,
+                Err(Error::NotConverged(..)) => continue,
+                Err(e) => return Err(e),
+            };
+            if first {
+                first = false;
+                func.insert(0, LeftBracket);
+                func.push(RightBracket);
+                func.push(Division);
+                func.push(LeftBracket);
+                func.push(LeftBracket);
+                func.push(Func(var.clone()));
+                func.push(Minus);
+                func.push(NumStr::new(v.clone()));
+                func.push(RightBracket);
+                func.push(RightBracket);
+            } else {
+                for n1 in &values {
+                    if -(n1.number.real() - v.number.real().clone())
+                        .clone()
+                        .abs()
+                        .log2()
+                        > options.prec / 16
+                        && -(n1.number.imag() - v.number.imag().clone())
                             .clone()
                             .abs()
                             .log2()
                             > options.prec / 16
-                            && -(n1.number.imag() - v.number.imag().clone())
-                                .clone()
-                                .abs()
-                                .log2()
-                                > options.prec / 16
-                        {
-                            continue 'main;
-                        }
+                    {
+                        continue 'main;
                     }
-                    func.insert(func.len() - 1, Multiplication);
-                    func.insert(func.len() - 1, LeftBracket);
-                    func.insert(func.len() - 1, Func(var.clone()));
-                    func.insert(func.len() - 1, Minus);
-                    func.insert(func.len() - 1, NumStr::new(v.clone()));
-                    func.insert(func.len() - 1, RightBracket);
                 }
-                values.push(v);
+                func.insert(func.len() - 1, Multiplication);
+                func.insert(func.len() - 1, LeftBracket);
+                func.insert(func.len() - 1, Func(var.clone()));
+                func.insert(func.len() - 1, Minus);
+                func.insert(func.len() - 1, NumStr::new(v.clone()));
+                func.insert(func.len() - 1, RightBracket);
             }
+            values.push(v);
         }
-        Ok(if values.is_empty() {
-            NumStr::new(Number::from(Complex::with_val(options.prec, Nan), None))
+        if values.is_empty() {
+            Err(Error::NotConverged("solve did not converge", None))
         } else if values.len() == 1 {
-            NumStr::new(values[0].clone())
+            Ok(NumStr::new(values[0].clone()))
         } else {
-            Vector(values)
-        })
+            Ok(Vector(values))
+        }
     } else {
         let op = options.prec;
         let prec;
@@ -4261,12 +6357,207 @@ pub fn solve(
             x.set_prec(op);
             Ok(NumStr::new(Number::from(x, units)))
         } else {
-            Ok(NumStr::new(Number::from(
-                Complex::with_val(options.prec, Nan),
-                None,
-            )))
+            Err(Error::NotConverged("solve did not converge", None))
+        }
+    }
+}
+fn brent<F: Fn(&Float) -> Result<Float, Error>>(
+    f: F,
+    mut a: Float,
+    mut b: Float,
+    mut fa: Float,
+    mut fb: Float,
+    prec: Precision,
+) -> Result<Float, Error> {
+    //the bits the floats actually carry, f64 and f32 ignore prec.bits
+    let bits = b.prec().bits;
+    let eps = Float::with_val(prec, 0.5).pow(bits - 2);
+    let mut c = b.clone();
+    let mut fc = fb.clone();
+    let mut d: Float = b.clone() - &a;
+    let mut e = d.clone();
+    for _ in 0..4 * bits {
+        if (fb.is_sign_positive() && fc.is_sign_positive())
+            || (fb.is_sign_negative() && fc.is_sign_negative())
+        {
+            c = a.clone();
+            fc = fa.clone();
+            d = b.clone() - &a;
+            e = d.clone();
+        }
+        if fc.clone().abs() < fb.clone().abs() {
+            a = b;
+            b = c;
+            c = a.clone();
+            fa = fb;
+            fb = fc;
+            fc = fa.clone();
+        }
+        let tol: Float = 2 * eps.clone() * b.clone().abs() + eps.clone() / 2;
+        let xm: Float = (c.clone() - &b) / 2;
+        if xm.clone().abs() <= tol || fb.is_zero() {
+            return Ok(b);
+        }
+        if e.clone().abs() >= tol && fa.clone().abs() > fb.clone().abs() {
+            let s: Float = fb.clone() / &fa;
+            let (mut p, mut q): (Float, Float) = if a == c {
+                (2 * xm.clone() * &s, 1 - s.clone())
+            } else {
+                let q: Float = fa.clone() / &fc;
+                let r: Float = fb.clone() / &fc;
+                (
+                    s.clone()
+                        * (2 * xm.clone() * &q * (q.clone() - &r)
+                            - (b.clone() - &a) * (r.clone() - 1)),
+                    (q - 1) * (r - 1) * (s - 1),
+                )
+            };
+            if p.is_sign_positive() {
+                q = -q
+            }
+            p = p.abs();
+            let min1: Float = 3 * xm.clone() * &q - (tol.clone() * &q).abs();
+            let min2: Float = (e.clone() * &q).abs();
+            if 2 * p.clone() < min1.min(&min2) {
+                e = d;
+                d = p / q;
+            } else {
+                d = xm.clone();
+                e = d.clone();
+            }
+        } else {
+            d = xm.clone();
+            e = d.clone();
+        }
+        a = b.clone();
+        fa = fb;
+        if d.clone().abs() > tol {
+            b += &d
+        } else if xm.is_sign_positive() {
+            b += tol
+        } else {
+            b -= tol
+        }
+        fb = f(&b)?;
+        if !fb.is_finite() {
+            return Err(Error::NotConverged("solve did not converge", None));
+        }
+    }
+    Err(Error::NotConverged("solve did not converge", None))
+}
+fn opposite_signs(a: &Float, b: &Float) -> bool {
+    a.is_finite()
+        && b.is_finite()
+        && !a.is_zero()
+        && !b.is_zero()
+        && a.is_sign_positive() != b.is_sign_positive()
+}
+pub fn solve_bracket(
+    func: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    var: String,
+    a: Number,
+    b: Number,
+) -> Result<NumStr, Error> {
+    let units = a.units;
+    let f = |x: &Float| -> Result<Float, Error> {
+        Ok(do_math_with_var(
+            func.clone(),
+            options,
+            func_vars.clone(),
+            &var,
+            NumStr::new(Number::from(
+                Complex::with_val(options.precision(), x),
+                units,
+            )),
+        )?
+        .num()?
+        .number
+        .real()
+        .clone())
+    };
+    let a = a.number.real().clone();
+    let b = b.number.real().clone();
+    let fa = f(&a)?;
+    let fb = f(&b)?;
+    let x = if fa.is_zero() {
+        a
+    } else if fb.is_zero() {
+        b
+    } else if opposite_signs(&fa, &fb) {
+        brent(f, a, b, fa, fb, options.precision())?
+    } else {
+        return Err(Error::Domain("solve bracket does not change sign", None));
+    };
+    Ok(NumStr::new(Number::from(
+        Complex::with_val(options.precision(), x),
+        units,
+    )))
+}
+pub fn roots(
+    func: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    var: String,
+    a: Number,
+    b: Number,
+    n: usize,
+) -> Result<NumStr, Error> {
+    let units = a.units;
+    let f = |x: &Float| -> Result<Float, Error> {
+        Ok(do_math_with_var(
+            func.clone(),
+            options,
+            func_vars.clone(),
+            &var,
+            NumStr::new(Number::from(
+                Complex::with_val(options.precision(), x),
+                units,
+            )),
+        )?
+        .num()?
+        .number
+        .real()
+        .clone())
+    };
+    let a = a.number.real().clone();
+    let h: Float = (b.number.real().clone() - &a) / n;
+    let points = (0..=n)
+        .map(|i| {
+            let x: Float = a.clone() + h.clone() * i;
+            let y = f(&x)?;
+            Ok((x, y))
+        })
+        .collect::<Result<Vec<(Float, Float)>, Error>>()?;
+    let mut vec = Vec::new();
+    for (i, (x, y)) in points.iter().enumerate() {
+        if y.is_zero() {
+            vec.push(x.clone());
+        } else if i != n && opposite_signs(y, &points[i + 1].1) {
+            let (xb, yb) = &points[i + 1];
+            let Ok(r) = brent(
+                f,
+                x.clone(),
+                xb.clone(),
+                y.clone(),
+                yb.clone(),
+                options.precision(),
+            ) else {
+                continue;
+            };
+            if f(&r).is_ok_and(|fr| {
+                fr.is_finite() && fr.abs() <= y.clone().abs().max(&yb.clone().abs())
+            }) {
+                vec.push(r)
+            }
         }
     }
+    Ok(Vector(
+        vec.into_iter()
+            .map(|x| Number::from(Complex::with_val(options.precision(), x), units))
+            .collect(),
+    ))
 }
 pub fn extrema(
     mut func: Vec<NumStr>,
@@ -4274,7 +6565,7 @@ pub fn extrema(
     mut options: Options,
     var: String,
     x: Number,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     //newtons method, x-f'(x)/f''(x)
     let units = x.units;
     let mut x = x.number;
@@ -4298,7 +6589,7 @@ pub fn extrema(
             func_vars.clone(),
             &var.clone(),
             NumStr::new(Number::from(
-                x.clone() + Float::with_val(options.prec, 0.5).pow(prec),
+                x.clone() + Float::with_val(options.precision(), 0.5).pow(prec),
                 None,
             )),
         )?;
@@ -4348,7 +6639,7 @@ pub fn extrema(
         func_vars.clone(),
         &var.clone(),
         NumStr::new(Number::from(
-            x.clone() + Float::with_val(options.prec, 0.5).pow(prec),
+            x.clone() + Float::with_val(options.precision(), 0.5).pow(prec),
             None,
         )),
     )?;
@@ -4390,7 +6681,7 @@ pub fn extrema(
             do_math_with_var(func, options, func_vars, &var, NumStr::new(n.clone()))?.num()?,
             Number::from(
                 Complex::with_val(
-                    options.prec,
+                    options.precision(),
                     if k.clone().abs().real().clone().log2() < op as i32 / -16 {
                         0
                     } else if k.real().is_sign_positive() {
@@ -4406,11 +6697,338 @@ pub fn extrema(
         Ok(v)
     } else {
         Ok(Vector(vec![Number::from(
-            Complex::with_val(options.prec, Nan),
+            Complex::with_val(options.precision(), Nan),
             None,
         )]))
     }
 }
+fn dot(a: &[Float], b: &[Float]) -> Float {
+    let mut sum = Float::new(a[0].prec());
+    for (a, b) in a.iter().zip(b) {
+        sum += a.clone() * b
+    }
+    sum
+}
+fn max_abs(a: &[Float]) -> Float {
+    a.iter()
+        .fold(Float::new(a[0].prec()), |m, a| m.max(&a.clone().abs()))
+}
+fn bfgs<F, G>(
+    f: F,
+    grad: G,
+    mut x: Vec<Float>,
+    prec: Precision,
+    max: bool,
+) -> Result<Option<(Vec<Float>, Float)>, Error>
+where
+    F: Fn(&[Float]) -> Result<Float, Error>,
+    G: Fn(&[Float]) -> Result<Vec<Float>, Error>,
+{
+    let n = x.len();
+    let tol = Float::with_val(prec, 0.5).pow(prec.bits / 2);
+    //once f stops resolving the descent the gradient is only good to about eps^(1/4)
+    let stalled = |x: Vec<Float>, fx: Float, g: &[Float]| {
+        (max_abs(g) <= tol.clone().sqrt() * (1 + fx.clone().abs())).then_some((x, fx))
+    };
+    let identity = |scale: Float| -> Vec<Vec<Float>> {
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        if i == j {
+                            scale.clone()
+                        } else {
+                            Float::new(prec)
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+    let mut fx = f(&x)?;
+    let mut g = grad(&x)?;
+    if !fx.is_finite() || g.iter().any(|g| !g.is_finite()) {
+        return Ok(None);
+    }
+    let mut h = identity(Float::with_val(prec, 1));
+    let mut restarted = false;
+    for iter in 0..200 * n {
+        if max_abs(&g) <= tol.clone() * (1 + fx.clone().abs()) {
+            return Ok(Some((x, fx)));
+        }
+        let mut p: Vec<Float> = h.iter().map(|row| -dot(row, &g)).collect();
+        let mut slope = dot(&g, &p);
+        if !slope.is_sign_negative() {
+            h = identity(Float::with_val(prec, 1));
+            p = g.iter().map(|g| -g.clone()).collect();
+            slope = dot(&g, &p);
+        }
+        let mut t = Float::with_val(prec, 1);
+        let step = loop {
+            let xn: Vec<Float> = x
+                .iter()
+                .zip(&p)
+                .map(|(x, p)| x.clone() + t.clone() * p)
+                .collect();
+            let fxn = f(&xn)?;
+            if fxn <= fx.clone() + t.clone() * &slope / 10000 {
+                break Some((xn, fxn));
+            }
+            t /= 2;
+            if t < tol.clone().square() {
+                break None;
+            }
+        };
+        let Some((xn, fxn)) = step else {
+            //no descent along p, retry once from steepest descent before judging the gradient
+            if !restarted {
+                restarted = true;
+                h = identity(Float::with_val(prec, 1));
+                continue;
+            }
+            return Ok(stalled(x, fx, &g));
+        };
+        let gn = grad(&xn)?;
+        if gn.iter().any(|g| !g.is_finite()) {
+            return Ok(None);
+        }
+        let s: Vec<Float> = xn.iter().zip(&x).map(|(a, b)| a.clone() - b).collect();
+        let y: Vec<Float> = gn.iter().zip(&g).map(|(a, b)| a.clone() - b).collect();
+        let sy = dot(&s, &y);
+        if sy.is_sign_positive() && !sy.is_zero() {
+            if iter == 0 {
+                h = identity(sy.clone() / dot(&y, &y));
+            }
+            let rho: Float = sy.recip();
+            let hy: Vec<Float> = h.iter().map(|row| dot(row, &y)).collect();
+            let c: Float = rho.clone() * (1 + rho.clone() * dot(&y, &hy));
+            for (i, row) in h.iter_mut().enumerate() {
+                for (j, a) in row.iter_mut().enumerate() {
+                    *a += c.clone() * &s[i] * &s[j]
+                        - rho.clone() * (hy[i].clone() * &s[j] + s[i].clone() * &hy[j]);
+                }
+            }
+        }
+        let tiny = max_abs(&s) <= tol.clone() * (1 + max_abs(&xn));
+        (x, fx, g) = (xn, fxn, gn);
+        if tiny && restarted {
+            return Ok(stalled(x, fx, &g));
+        } else if tiny {
+            restarted = true;
+            h = identity(Float::with_val(prec, 1));
+        } else {
+            restarted = false;
+        }
+    }
+    Err(not_converged(max))
+}
+fn not_converged(max: bool) -> Error {
+    Error::NotConverged(
+        if max {
+            "maximize did not converge"
+        } else {
+            "minimize did not converge"
+        },
+        None,
+    )
+}
+fn nelder_mead<F>(
+    f: F,
+    x0: Vec<Float>,
+    bounds: Option<&(Vec<Float>, Vec<Float>)>,
+    prec: Precision,
+    max: bool,
+) -> Result<(Vec<Float>, Float), Error>
+where
+    F: Fn(&[Float]) -> Result<Float, Error>,
+{
+    let n = x0.len();
+    let tol = Float::with_val(prec, 0.5).pow(prec.bits / 2);
+    let clamp = |x: Vec<Float>| -> Vec<Float> {
+        if let Some((lower, upper)) = bounds {
+            x.into_iter()
+                .zip(lower.iter().zip(upper))
+                .map(|(x, (l, u))| x.max(l).min(u))
+                .collect()
+        } else {
+            x
+        }
+    };
+    let along = |a: &[Float], b: &[Float], t: f64| -> Vec<Float> {
+        clamp(
+            a.iter()
+                .zip(b)
+                .map(|(a, b)| a.clone() + (b.clone() - a) * t)
+                .collect(),
+        )
+    };
+    let x0 = clamp(x0);
+    let mut simplex = vec![(x0.clone(), f(&x0)?)];
+    for i in 0..n {
+        let mut x = x0.clone();
+        let step = if x[i].is_zero() {
+            Float::with_val(prec, 0.00025)
+        } else {
+            x[i].clone() / 20
+        };
+        x[i] += &step;
+        if bounds.is_some_and(|(_, upper)| x[i] > upper[i]) {
+            x[i] -= step * 2;
+        }
+        let x = clamp(x);
+        let y = f(&x)?;
+        simplex.push((x, y));
+    }
+    for _ in 0..1000 * (n + 1) * (prec.bits as usize / 64).max(1) {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let best = simplex[0].0.clone();
+        let diameter = simplex[1..]
+            .iter()
+            .map(|(x, _)| {
+                max_abs(
+                    &x.iter()
+                        .zip(&best)
+                        .map(|(a, b)| a.clone() - b)
+                        .collect::<Vec<Float>>(),
+                )
+            })
+            .fold(Float::new(prec), |m, d| m.max(&d));
+        if diameter <= tol.clone() * (1 + max_abs(&best)) {
+            let (x, y) = simplex.swap_remove(0);
+            return Ok((x, y));
+        }
+        let mut centroid = vec![Float::new(prec); n];
+        for (x, _) in &simplex[..n] {
+            for (c, x) in centroid.iter_mut().zip(x) {
+                *c += x;
+            }
+        }
+        centroid.iter_mut().for_each(|c| *c /= n);
+        let (worst, fw) = simplex[n].clone();
+        let xr = along(&centroid, &worst, -1.0);
+        let fr = f(&xr)?;
+        if fr < simplex[0].1 {
+            let xe = along(&centroid, &worst, -2.0);
+            let fe = f(&xe)?;
+            simplex[n] = if fe < fr { (xe, fe) } else { (xr, fr) };
+        } else if fr < simplex[n - 1].1 {
+            simplex[n] = (xr, fr);
+        } else {
+            let (xc, fc) = if fr < fw {
+                let xc = along(&centroid, &xr, 0.5);
+                let fc = f(&xc)?;
+                (xc, fc)
+            } else {
+                let xc = along(&centroid, &worst, 0.5);
+                let fc = f(&xc)?;
+                (xc, fc)
+            };
+            if fc < fr.min(&fw) {
+                simplex[n] = (xc, fc);
+            } else {
+                for vertex in simplex[1..].iter_mut() {
+                    let x = along(&best, &vertex.0, 0.5);
+                    let y = f(&x)?;
+                    *vertex = (x, y);
+                }
+            }
+        }
+    }
+    Err(not_converged(max))
+}
+#[allow(clippy::too_many_arguments)]
+pub fn minimize(
+    func: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    vars: Vec<String>,
+    x0: Vec<Number>,
+    bounds: Option<(Vec<Number>, Vec<Number>)>,
+    max: bool,
+) -> Result<NumStr, Error> {
+    if x0.len() != vars.len() {
+        return Err(Error::Dimension(
+            "minimize needs a starting value for each variable",
+            None,
+        ));
+    }
+    let units: Vec<_> = x0.iter().map(|x| x.units).collect();
+    let place = |x: &[Float], skip: Option<usize>| {
+        let mut func = func.clone();
+        let mut func_vars = func_vars.clone();
+        for (i, (var, x)) in vars.iter().zip(x).enumerate() {
+            if Some(i) != skip {
+                let num = NumStr::new(Number::from(
+                    Complex::with_val(options.precision(), x),
+                    units[i],
+                ));
+                func = place_var(func, var, num.clone());
+                func_vars = place_funcvar(func_vars, var, num);
+            }
+        }
+        (func, func_vars)
+    };
+    let f = |x: &[Float]| -> Result<Float, Error> {
+        let (func, func_vars) = place(x, None);
+        let y = do_math(func, options, func_vars)?
+            .num()?
+            .number
+            .real()
+            .clone();
+        Ok(if y.is_nan() {
+            Float::with_val(options.precision(), Infinity)
+        } else if max {
+            -y
+        } else {
+            y
+        })
+    };
+    let grad = |x: &[Float]| -> Result<Vec<Float>, Error> {
+        (0..x.len())
+            .map(|k| {
+                let (func, func_vars) = place(x, Some(k));
+                let d = slope(
+                    func,
+                    func_vars,
+                    options,
+                    vars[k].clone(),
+                    Number::from(Complex::with_val(options.precision(), &x[k]), units[k]),
+                    false,
+                    1,
+                )?
+                .num()?
+                .number
+                .real()
+                .clone();
+                Ok(if max { -d } else { d })
+            })
+            .collect()
+    };
+    let x0: Vec<Float> = x0.into_iter().map(|x| x.number.real().clone()).collect();
+    let (x, y) = if let Some((lower, upper)) = bounds {
+        let bounds = (
+            lower.into_iter().map(|x| x.number.real().clone()).collect(),
+            upper.into_iter().map(|x| x.number.real().clone()).collect(),
+        );
+        nelder_mead(f, x0, Some(&bounds), options.precision(), max)?
+    } else if let Some(r) = bfgs(f, grad, x0.clone(), options.precision(), max)? {
+        r
+    } else {
+        nelder_mead(f, x0, None, options.precision(), max)?
+    };
+    let (func, func_vars) = place(&x, None);
+    let y_units = do_math(func, options, func_vars)?.num()?.units;
+    let mut vec: Vec<Number> = x
+        .into_iter()
+        .zip(units)
+        .map(|(x, units)| Number::from(Complex::with_val(options.precision(), x), units))
+        .collect();
+    vec.push(Number::from(
+        Complex::with_val(options.precision(), if max { -y } else { y }),
+        y_units,
+    ));
+    Ok(Vector(vec))
+}
 #[allow(clippy::too_many_arguments)]
 pub fn taylor(
     mut func: Vec<NumStr>,
@@ -4420,7 +7038,7 @@ pub fn taylor(
     x: Option<Number>,
     a: Number,
     nth: usize,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     fn fact(n: usize) -> Integer {
         let mut fact = Integer::from(1);
         for i in 1..=n {
@@ -4449,7 +7067,7 @@ pub fn taylor(
         func_vars.clone(),
         &var,
         NumStr::new(Number::from(
-            an.clone() + Float::with_val(options.prec, 0.5).pow(prec),
+            an.clone() + Float::with_val(options.precision(), 0.5).pow(prec),
             None,
         )),
     )?;
@@ -4466,7 +7084,7 @@ pub fn taylor(
                     func_vars.clone(),
                     &var,
                     NumStr::new(Number::from(
-                        an.clone() + Float::with_val(options.prec, 0.5).pow(prec),
+                        an.clone() + Float::with_val(options.precision(), 0.5).pow(prec),
                         None,
                     )),
                 )?;
@@ -4500,7 +7118,7 @@ pub fn taylor(
         let mut poly = Vec::with_capacity(nth + 1);
         let is_vector = match val.clone() {
             Vector(a) => {
-                let empty = vec![Number::from(Complex::new(options.prec), None); a.len()];
+                let empty = vec![Number::from(Complex::new(options.precision()), None); a.len()];
                 poly_mat.push(a);
                 for _ in 1..=nth {
                     poly_mat.push(empty.clone())
@@ -4508,14 +7126,14 @@ pub fn taylor(
                 true
             }
             Num(a) => {
-                let empty = Number::from(Complex::new(options.prec), None);
+                let empty = Number::from(Complex::new(options.precision()), None);
                 poly.push(*a);
                 for _ in 1..=nth {
                     poly.push(empty.clone())
                 }
                 false
             }
-            _ => return Err("unsupported type"),
+            _ => return Err(Error::Unsupported("unsupported type", None)),
         };
         for n in 1..=nth {
             if n % 8 == 0 {
@@ -4526,7 +7144,7 @@ pub fn taylor(
                     func_vars.clone(),
                     &var,
                     NumStr::new(Number::from(
-                        an.clone() + Float::with_val(options.prec, 0.5).pow(prec),
+                        an.clone() + Float::with_val(options.precision(), 0.5).pow(prec),
                         None,
                     )),
                 )?;
@@ -4603,7 +7221,7 @@ pub fn slope(
     mut point: Number,
     combine: bool,
     nth: u32,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     let oop = options.prec;
     if nth == 0 {
         do_math_with_var(
@@ -4687,7 +7305,7 @@ pub fn slope(
                     Ok(n)
                 } else {
                     Ok(NumStr::new(Number::from(
-                        Complex::with_val(options.prec, Nan),
+                        Complex::with_val(options.precision(), Nan),
                         None,
                     )))
                 }
@@ -4720,7 +7338,7 @@ pub fn slope(
                         {
                             Number::from((left + right) / 2, units)
                         } else {
-                            Number::from(Complex::with_val(options.prec, Nan), None)
+                            Number::from(Complex::with_val(options.precision(), Nan), None)
                         }
                     })
                 }
@@ -4728,7 +7346,7 @@ pub fn slope(
                 v.set_prec(oop);
                 Ok(v)
             }
-            (_, _) => Err("lim err"),
+            (_, _) => Err(Error::Type("lim err", None)),
         }
     }
 }
@@ -4746,7 +7364,7 @@ pub fn slopesided(
     val: Option<NumStr>,
     val2: Option<NumStr>,
     prec: Option<u32>,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     if nth == 0 {
         return do_math_with_var(
             func.clone(),
@@ -4769,10 +7387,12 @@ pub fn slopesided(
         point.set_prec(options.prec);
         prec
     };
+    //a step past half the mantissa is lost in point+h, so a narrow float like f32 needs a wider one
+    let prec = prec.min(point.real().prec().bits / 2 + 2);
     let h: Float = if right {
-        Float::with_val(options.prec, 0.5).pow(prec)
+        Float::with_val(options.precision(), 0.5).pow(prec)
     } else {
-        -Float::with_val(options.prec, 0.5).pow(prec)
+        -Float::with_val(options.precision(), 0.5).pow(prec)
     };
     let num = Integer::from(nth);
     let n = if let Some(n) = val {
@@ -4827,7 +7447,7 @@ pub fn slopesided(
             }
             if right || nth % 2 == 0 {
                 let mut n = NumStr::new(Number::from(
-                    sum * Float::with_val(options.prec, 2).pow(nth * prec),
+                    sum * Float::with_val(options.precision(), 2).pow(nth * prec),
                     match (yunits, units) {
                         (Some(a), Some(b)) => Some(a.div(&b)),
                         (Some(a), None) => Some(a),
@@ -4841,7 +7461,7 @@ pub fn slopesided(
                 Ok(n)
             } else {
                 let mut n = NumStr::new(Number::from(
-                    -sum * Float::with_val(options.prec, 2).pow(nth * prec),
+                    -sum * Float::with_val(options.precision(), 2).pow(nth * prec),
                     match (yunits, units) {
                         (Some(a), Some(b)) => Some(a.div(&b)),
                         (Some(a), None) => Some(a),
@@ -4887,9 +7507,11 @@ pub fn slopesided(
                     .map(|n| {
                         Number::from(
                             if right || nth % 2 == 0 {
-                                n.number.clone() * Float::with_val(options.prec, 2).pow(nth * prec)
+                                n.number.clone()
+                                    * Float::with_val(options.precision(), 2).pow(nth * prec)
                             } else {
-                                -n.number.clone() * Float::with_val(options.prec, 2).pow(nth * prec)
+                                -n.number.clone()
+                                    * Float::with_val(options.precision(), 2).pow(nth * prec)
                             },
                             match (yunits, units) {
                                 (Some(a), Some(b)) => Some(a.div(&b)),
@@ -4938,7 +7560,7 @@ pub fn slopesided(
             }
             if right || nth % 2 == 0 {
                 let mut n = NumStr::new(Number::from(
-                    sum[0].number.clone() * Float::with_val(options.prec, 2).pow(nth * prec),
+                    sum[0].number.clone() * Float::with_val(options.precision(), 2).pow(nth * prec),
                     match (yunits, units) {
                         (Some(a), Some(b)) => Some(a.div(&b)),
                         (Some(a), None) => Some(a),
@@ -4952,7 +7574,8 @@ pub fn slopesided(
                 Ok(n)
             } else {
                 let mut n = NumStr::new(Number::from(
-                    -sum[0].number.clone() * Float::with_val(options.prec, 2).pow(nth * prec),
+                    -sum[0].number.clone()
+                        * Float::with_val(options.precision(), 2).pow(nth * prec),
                     match (yunits, units) {
                         (Some(a), Some(b)) => Some(a.div(&b)),
                         (Some(a), None) => Some(a),
@@ -5031,7 +7654,7 @@ pub fn slopesided(
                 Ok(v)
             }
         }
-        _ => Err("not supported slope data"),
+        _ => Err(Error::Unsupported("not supported slope data", None)),
     }
 }
 #[derive(Copy, Clone, PartialEq)]
@@ -5047,7 +7670,7 @@ pub fn limit(
     var: String,
     point: Number,
     side: LimSide,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     let xunits = point.units;
     let mut point = point.number;
     let oop = options.prec;
@@ -5060,11 +7683,14 @@ pub fn limit(
         let (h1, h2);
         let positive = point.real().is_sign_positive();
         if positive {
-            h1 = Complex::with_val(options.prec, 2).pow(options.prec / 4);
-            h2 = Complex::with_val(options.prec, 2).pow((options.prec / 3) as f64 + 7.0 / 0.94) - 3;
+            h1 = Complex::with_val(options.precision(), 2).pow(options.prec / 4);
+            h2 = Complex::with_val(options.precision(), 2)
+                .pow((options.prec / 3) as f64 + 7.0 / 0.94)
+                - 3;
         } else {
-            h1 = -Complex::with_val(options.prec, 2).pow(options.prec / 4);
-            h2 = 3 - Complex::with_val(options.prec, 2).pow((options.prec / 3) as f64 + 7.0 / 0.94);
+            h1 = -Complex::with_val(options.precision(), 2).pow(options.prec / 4);
+            h2 = 3 - Complex::with_val(options.precision(), 2)
+                .pow((options.prec / 3) as f64 + 7.0 / 0.94);
         }
         let n1 = do_math_with_var(
             func.clone(),
@@ -5092,32 +7718,36 @@ pub fn limit(
                 } else if n1.real().is_sign_positive() != n2.real().is_sign_positive()
                     || n1.imag().is_sign_positive() != n2.imag().is_sign_positive()
                 {
-                    NumStr::new(Number::from(Complex::with_val(options.prec, Nan), None))
+                    NumStr::new(Number::from(
+                        Complex::with_val(options.precision(), Nan),
+                        None,
+                    ))
                 } else if n2.real().is_infinite() || n2.imag().is_infinite() {
                     NumStr::new(Number::from(
                         match (n2.real().is_infinite(), n2.imag().is_infinite()) {
                             (true, true) => {
                                 match (n1.real().is_sign_positive(), n1.imag().is_sign_positive()) {
                                     (true, true) => {
-                                        Complex::with_val(options.prec, (Infinity, Infinity))
+                                        Complex::with_val(options.precision(), (Infinity, Infinity))
                                     }
                                     (true, false) => Complex::with_val(
-                                        options.prec,
-                                        (Infinity, -Float::with_val(options.prec, Infinity)),
+                                        options.precision(),
+                                        (Infinity, -Float::with_val(options.precision(), Infinity)),
                                     ),
                                     (false, true) => Complex::with_val(
-                                        options.prec,
-                                        (-Float::with_val(options.prec, Infinity), Infinity),
+                                        options.precision(),
+                                        (-Float::with_val(options.precision(), Infinity), Infinity),
+                                    ),
+                                    (false, false) => -Complex::with_val(
+                                        options.precision(),
+                                        (Infinity, Infinity),
                                     ),
-                                    (false, false) => {
-                                        -Complex::with_val(options.prec, (Infinity, Infinity))
-                                    }
                                 }
                             }
                             (true, false) => {
                                 if n1.real().is_sign_positive() {
                                     Complex::with_val(
-                                        options.prec,
+                                        options.precision(),
                                         (
                                             Infinity,
                                             if (n1.imag() - n2.imag().clone()).abs().log2()
@@ -5125,13 +7755,13 @@ pub fn limit(
                                             {
                                                 n2.imag().clone()
                                             } else {
-                                                Float::new(options.prec)
+                                                Float::new(options.precision())
                                             },
                                         ),
                                     )
                                 } else {
                                     -Complex::with_val(
-                                        options.prec,
+                                        options.precision(),
                                         (
                                             Infinity,
                                             if (n1.imag() - n2.imag().clone()).abs().log2()
@@ -5139,7 +7769,7 @@ pub fn limit(
                                             {
                                                 -n2.imag().clone()
                                             } else {
-                                                Float::new(options.prec)
+                                                Float::new(options.precision())
                                             },
                                         ),
                                     )
@@ -5148,35 +7778,35 @@ pub fn limit(
                             (false, true) => {
                                 if n1.imag().is_sign_positive() {
                                     Complex::with_val(
-                                        options.prec,
+                                        options.precision(),
                                         (
                                             if (n1.real() - n2.real().clone()).abs().log2()
                                                 < options.prec as i32 / -16
                                             {
                                                 n2.real().clone()
                                             } else {
-                                                Float::new(options.prec)
+                                                Float::new(options.precision())
                                             },
                                             Infinity,
                                         ),
                                     )
                                 } else {
                                     -Complex::with_val(
-                                        options.prec,
+                                        options.precision(),
                                         (
                                             if (n1.real() - n2.real().clone()).abs().log2()
                                                 < options.prec as i32 / -16
                                             {
                                                 -n2.real().clone()
                                             } else {
-                                                Float::new(options.prec)
+                                                Float::new(options.precision())
                                             },
                                             Infinity,
                                         ),
                                     )
                                 }
                             }
-                            (false, false) => Complex::with_val(options.prec, Nan),
+                            (false, false) => Complex::with_val(options.precision(), Nan),
                         },
                         units,
                     ))
@@ -5188,11 +7818,11 @@ pub fn limit(
                         &var,
                         NumStr::new(Number::from(
                             if positive {
-                                Complex::with_val(options.prec, 2)
+                                Complex::with_val(options.precision(), 2)
                                     .pow((options.prec / 2) as f64 + 13.0 / 0.7)
                                     - 7
                             } else {
-                                7 - Complex::with_val(options.prec, 2)
+                                7 - Complex::with_val(options.precision(), 2)
                                     .pow((options.prec / 2) as f64 + 13.0 / 0.7)
                             },
                             xunits,
@@ -5210,7 +7840,7 @@ pub fn limit(
                     let n3i = n3.imag().clone().abs();
                     NumStr::new(Number::from(
                         if !sign {
-                            Complex::with_val(options.prec, Nan)
+                            Complex::with_val(options.precision(), Nan)
                         } else {
                             match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
                                 (true, true) => {
@@ -5218,26 +7848,34 @@ pub fn limit(
                                         n1.real().is_sign_positive(),
                                         n1.imag().is_sign_positive(),
                                     ) {
-                                        (true, true) => {
-                                            Complex::with_val(options.prec, (Infinity, Infinity))
-                                        }
+                                        (true, true) => Complex::with_val(
+                                            options.precision(),
+                                            (Infinity, Infinity),
+                                        ),
                                         (true, false) => Complex::with_val(
-                                            options.prec,
-                                            (Infinity, -Float::with_val(options.prec, Infinity)),
+                                            options.precision(),
+                                            (
+                                                Infinity,
+                                                -Float::with_val(options.precision(), Infinity),
+                                            ),
                                         ),
                                         (false, true) => Complex::with_val(
-                                            options.prec,
-                                            (-Float::with_val(options.prec, Infinity), Infinity),
+                                            options.precision(),
+                                            (
+                                                -Float::with_val(options.precision(), Infinity),
+                                                Infinity,
+                                            ),
+                                        ),
+                                        (false, false) => -Complex::with_val(
+                                            options.precision(),
+                                            (Infinity, Infinity),
                                         ),
-                                        (false, false) => {
-                                            -Complex::with_val(options.prec, (Infinity, Infinity))
-                                        }
                                     }
                                 }
                                 (true, false) => {
                                     if n1.real().is_sign_positive() {
                                         Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 Infinity,
                                                 if (n2i - n3i.clone()).abs().log2()
@@ -5245,13 +7883,13 @@ pub fn limit(
                                                 {
                                                     n3i
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                             ),
                                         )
                                     } else {
                                         -Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 Infinity,
                                                 if (n2i - n3i.clone()).abs().log2()
@@ -5259,7 +7897,7 @@ pub fn limit(
                                                 {
                                                     -n3i
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                             ),
                                         )
@@ -5268,35 +7906,35 @@ pub fn limit(
                                 (false, true) => {
                                     if n1.imag().is_sign_positive() {
                                         Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 if (n2r - n3r.clone()).abs().log2()
                                                     < options.prec as i32 / -16
                                                 {
                                                     n3r
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                                 Infinity,
                                             ),
                                         )
                                     } else {
                                         -Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 if (n2r - n3r.clone()).abs().log2()
                                                     < options.prec as i32 / -16
                                                 {
                                                     -n3r
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                                 Infinity,
                                             ),
                                         )
                                     }
                                 }
-                                (false, false) => Complex::with_val(options.prec, Nan),
+                                (false, false) => Complex::with_val(options.precision(), Nan),
                             }
                         },
                         units,
@@ -5320,7 +7958,7 @@ pub fn limit(
                         } else if n1.real().is_sign_positive() != n2.real().is_sign_positive()
                             || n1.imag().is_sign_positive() != n2.imag().is_sign_positive()
                         {
-                            Complex::with_val(options.prec, Nan)
+                            Complex::with_val(options.precision(), Nan)
                         } else if n2.real().is_infinite() || n2.imag().is_infinite() {
                             match (n2.real().is_infinite(), n2.imag().is_infinite()) {
                                 (true, true) => {
@@ -5328,26 +7966,34 @@ pub fn limit(
                                         n1.real().is_sign_positive(),
                                         n1.imag().is_sign_positive(),
                                     ) {
-                                        (true, true) => {
-                                            Complex::with_val(options.prec, (Infinity, Infinity))
-                                        }
+                                        (true, true) => Complex::with_val(
+                                            options.precision(),
+                                            (Infinity, Infinity),
+                                        ),
                                         (true, false) => Complex::with_val(
-                                            options.prec,
-                                            (Infinity, -Float::with_val(options.prec, Infinity)),
+                                            options.precision(),
+                                            (
+                                                Infinity,
+                                                -Float::with_val(options.precision(), Infinity),
+                                            ),
                                         ),
                                         (false, true) => Complex::with_val(
-                                            options.prec,
-                                            (-Float::with_val(options.prec, Infinity), Infinity),
+                                            options.precision(),
+                                            (
+                                                -Float::with_val(options.precision(), Infinity),
+                                                Infinity,
+                                            ),
+                                        ),
+                                        (false, false) => -Complex::with_val(
+                                            options.precision(),
+                                            (Infinity, Infinity),
                                         ),
-                                        (false, false) => {
-                                            -Complex::with_val(options.prec, (Infinity, Infinity))
-                                        }
                                     }
                                 }
                                 (true, false) => {
                                     if n1.real().is_sign_positive() {
                                         Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 Infinity,
                                                 if (n1.imag() - n2.imag().clone()).abs().log10()
@@ -5355,13 +8001,13 @@ pub fn limit(
                                                 {
                                                     n2.imag().clone()
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                             ),
                                         )
                                     } else {
                                         -Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 Infinity,
                                                 if (n1.imag() - n2.imag().clone()).abs().log10()
@@ -5369,7 +8015,7 @@ pub fn limit(
                                                 {
                                                     -n2.imag().clone()
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                             ),
                                         )
@@ -5378,35 +8024,35 @@ pub fn limit(
                                 (false, true) => {
                                     if n1.imag().is_sign_positive() {
                                         Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 if (n1.real() - n2.real().clone()).abs().log10()
                                                     <= -10
                                                 {
                                                     n2.real().clone()
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                                 Infinity,
                                             ),
                                         )
                                     } else {
                                         -Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 if (n1.real() - n2.real().clone()).abs().log10()
                                                     <= -10
                                                 {
                                                     -n2.real().clone()
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                                 Infinity,
                                             ),
                                         )
                                     }
                                 }
-                                (false, false) => Complex::with_val(options.prec, Nan),
+                                (false, false) => Complex::with_val(options.precision(), Nan),
                             }
                         } else {
                             if v3.is_empty() {
@@ -5417,11 +8063,11 @@ pub fn limit(
                                     &var,
                                     NumStr::new(Number::from(
                                         if positive {
-                                            Complex::with_val(options.prec, 2)
+                                            Complex::with_val(options.precision(), 2)
                                                 .pow((options.prec / 2) as f64 + 13.0 / 0.7)
                                                 - 7
                                         } else {
-                                            7 - Complex::with_val(options.prec, 2)
+                                            7 - Complex::with_val(options.precision(), 2)
                                                 .pow((options.prec / 2) as f64 + 13.0 / 0.7)
                                         },
                                         xunits,
@@ -5439,7 +8085,7 @@ pub fn limit(
                             let n2i = n2.imag().clone().abs();
                             let n3i = v3.imag().clone().abs();
                             if !sign {
-                                Complex::with_val(options.prec, Nan)
+                                Complex::with_val(options.precision(), Nan)
                             } else {
                                 match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
                                     (true, true) => {
@@ -5448,25 +8094,25 @@ pub fn limit(
                                             n1.imag().is_sign_positive(),
                                         ) {
                                             (true, true) => Complex::with_val(
-                                                options.prec,
+                                                options.precision(),
                                                 (Infinity, Infinity),
                                             ),
                                             (true, false) => Complex::with_val(
-                                                options.prec,
+                                                options.precision(),
                                                 (
                                                     Infinity,
-                                                    -Float::with_val(options.prec, Infinity),
+                                                    -Float::with_val(options.precision(), Infinity),
                                                 ),
                                             ),
                                             (false, true) => Complex::with_val(
-                                                options.prec,
+                                                options.precision(),
                                                 (
-                                                    -Float::with_val(options.prec, Infinity),
+                                                    -Float::with_val(options.precision(), Infinity),
                                                     Infinity,
                                                 ),
                                             ),
                                             (false, false) => -Complex::with_val(
-                                                options.prec,
+                                                options.precision(),
                                                 (Infinity, Infinity),
                                             ),
                                         }
@@ -5474,7 +8120,7 @@ pub fn limit(
                                     (true, false) => {
                                         if n1.real().is_sign_positive() {
                                             Complex::with_val(
-                                                options.prec,
+                                                options.precision(),
                                                 (
                                                     Infinity,
                                                     if (n2i - n3i.clone()).abs().log2()
@@ -5482,13 +8128,13 @@ pub fn limit(
                                                     {
                                                         n3i
                                                     } else {
-                                                        Float::new(options.prec)
+                                                        Float::new(options.precision())
                                                     },
                                                 ),
                                             )
                                         } else {
                                             -Complex::with_val(
-                                                options.prec,
+                                                options.precision(),
                                                 (
                                                     Infinity,
                                                     if (n2i - n3i.clone()).abs().log2()
@@ -5496,7 +8142,7 @@ pub fn limit(
                                                     {
                                                         -n3i
                                                     } else {
-                                                        Float::new(options.prec)
+                                                        Float::new(options.precision())
                                                     },
                                                 ),
                                             )
@@ -5505,35 +8151,35 @@ pub fn limit(
                                     (false, true) => {
                                         if n1.imag().is_sign_positive() {
                                             Complex::with_val(
-                                                options.prec,
+                                                options.precision(),
                                                 (
                                                     if (n2r - n3r.clone()).abs().log2()
                                                         < options.prec as i32 / -16
                                                     {
                                                         n3r
                                                     } else {
-                                                        Float::new(options.prec)
+                                                        Float::new(options.precision())
                                                     },
                                                     Infinity,
                                                 ),
                                             )
                                         } else {
                                             -Complex::with_val(
-                                                options.prec,
+                                                options.precision(),
                                                 (
                                                     if (n2r - n3r.clone()).abs().log2()
                                                         < options.prec as i32 / -16
                                                     {
                                                         -n3r
                                                     } else {
-                                                        Float::new(options.prec)
+                                                        Float::new(options.precision())
                                                     },
                                                     Infinity,
                                                 ),
                                             )
                                         }
                                     }
-                                    (false, false) => Complex::with_val(options.prec, Nan),
+                                    (false, false) => Complex::with_val(options.precision(), Nan),
                                 }
                             }
                         },
@@ -5544,7 +8190,7 @@ pub fn limit(
                 v.set_prec(oop);
                 Ok(v)
             }
-            (_, _) => Err("unsupported lim data"),
+            (_, _) => Err(Error::Unsupported("unsupported lim data", None)),
         }
     } else {
         let point = Number::from(point, xunits);
@@ -5590,7 +8236,7 @@ pub fn limit(
                             Ok(n)
                         } else {
                             Ok(NumStr::new(Number::from(
-                                Complex::with_val(options.prec, Nan),
+                                Complex::with_val(options.precision(), Nan),
                                 None,
                             )))
                         }
@@ -5625,7 +8271,7 @@ pub fn limit(
                                 {
                                     Number::from((left + right.clone()) / 2, units)
                                 } else {
-                                    Number::from(Complex::with_val(options.prec, Nan), None)
+                                    Number::from(Complex::with_val(options.precision(), Nan), None)
                                 },
                             )
                         }
@@ -5633,7 +8279,7 @@ pub fn limit(
                         v.set_prec(options.prec);
                         Ok(v)
                     }
-                    (_, _) => Err("lim err"),
+                    (_, _) => Err(Error::Type("lim err", None)),
                 }
             }
         }
@@ -5646,11 +8292,11 @@ fn limsided(
     var: String,
     point: Number,
     right: bool,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     let xunits = point.units;
     let point = point.number;
-    let h1 = Float::with_val(options.prec, 0.5).pow(options.prec / 4);
-    let h2 = Float::with_val(options.prec, 0.5).pow((options.prec / 3) as f64 + 7.0 / 0.94);
+    let h1 = Float::with_val(options.precision(), 0.5).pow(options.prec / 4);
+    let h2 = Float::with_val(options.precision(), 0.5).pow((options.prec / 3) as f64 + 7.0 / 0.94);
     let n1 = do_math_with_var(
         func.clone(),
         options,
@@ -5681,7 +8327,7 @@ fn limsided(
                 {
                     n1
                 } else {
-                    let h3 = Complex::with_val(options.prec, 0.5)
+                    let h3 = Complex::with_val(options.precision(), 0.5)
                         .pow((options.prec / 2) as f64 + 13.0 / 0.7);
                     let n3 = do_math_with_var(
                         func.clone(),
@@ -5706,31 +8352,32 @@ fn limsided(
                     let n2i = n2.imag().clone().abs();
                     let n3i = n3.imag().clone().abs();
                     if !sign {
-                        Complex::with_val(options.prec, Nan)
+                        Complex::with_val(options.precision(), Nan)
                     } else {
                         match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
                             (true, true) => {
                                 match (n1.real().is_sign_positive(), n1.imag().is_sign_positive()) {
                                     (true, true) => {
-                                        Complex::with_val(options.prec, (Infinity, Infinity))
+                                        Complex::with_val(options.precision(), (Infinity, Infinity))
                                     }
                                     (true, false) => Complex::with_val(
-                                        options.prec,
-                                        (Infinity, -Float::with_val(options.prec, Infinity)),
+                                        options.precision(),
+                                        (Infinity, -Float::with_val(options.precision(), Infinity)),
                                     ),
                                     (false, true) => Complex::with_val(
-                                        options.prec,
-                                        (-Float::with_val(options.prec, Infinity), Infinity),
+                                        options.precision(),
+                                        (-Float::with_val(options.precision(), Infinity), Infinity),
+                                    ),
+                                    (false, false) => -Complex::with_val(
+                                        options.precision(),
+                                        (Infinity, Infinity),
                                     ),
-                                    (false, false) => {
-                                        -Complex::with_val(options.prec, (Infinity, Infinity))
-                                    }
                                 }
                             }
                             (true, false) => {
                                 if n1.real().is_sign_positive() {
                                     Complex::with_val(
-                                        options.prec,
+                                        options.precision(),
                                         (
                                             Infinity,
                                             if (n1.imag() - n2.imag().clone()).abs().log2()
@@ -5738,13 +8385,13 @@ fn limsided(
                                             {
                                                 n2.imag().clone()
                                             } else {
-                                                Float::new(options.prec)
+                                                Float::new(options.precision())
                                             },
                                         ),
                                     )
                                 } else {
                                     -Complex::with_val(
-                                        options.prec,
+                                        options.precision(),
                                         (
                                             Infinity,
                                             if (n1.imag() - n2.imag().clone()).abs().log2()
@@ -5752,7 +8399,7 @@ fn limsided(
                                             {
                                                 -n2.imag().clone()
                                             } else {
-                                                Float::new(options.prec)
+                                                Float::new(options.precision())
                                             },
                                         ),
                                     )
@@ -5761,35 +8408,35 @@ fn limsided(
                             (false, true) => {
                                 if n1.imag().is_sign_positive() {
                                     Complex::with_val(
-                                        options.prec,
+                                        options.precision(),
                                         (
                                             if (n1.real() - n2.real().clone()).abs().log2()
                                                 < options.prec as i32 / -16
                                             {
                                                 n2.real().clone()
                                             } else {
-                                                Float::new(options.prec)
+                                                Float::new(options.precision())
                                             },
                                             Infinity,
                                         ),
                                     )
                                 } else {
                                     -Complex::with_val(
-                                        options.prec,
+                                        options.precision(),
                                         (
                                             if (n1.real() - n2.real().clone()).abs().log2()
                                                 < options.prec as i32 / -16
                                             {
                                                 -n2.real().clone()
                                             } else {
-                                                Float::new(options.prec)
+                                                Float::new(options.precision())
                                             },
                                             Infinity,
                                         ),
                                     )
                                 }
                             }
-                            (false, false) => Complex::with_val(options.prec, Nan),
+                            (false, false) => Complex::with_val(options.precision(), Nan),
                         }
                     }
                 },
@@ -5810,7 +8457,7 @@ fn limsided(
                         n1.clone()
                     } else {
                         if n3.is_empty() {
-                            let h3 = Complex::with_val(options.prec, 0.5)
+                            let h3 = Complex::with_val(options.precision(), 0.5)
                                 .pow((options.prec / 2) as f64 + 13.0 / 0.7);
                             n3 = do_math_with_var(
                                 func.clone(),
@@ -5833,7 +8480,7 @@ fn limsided(
                         let n2i = n2.imag().clone().abs();
                         let n3i = n3.imag().clone().abs();
                         if !sign {
-                            Complex::with_val(options.prec, Nan)
+                            Complex::with_val(options.precision(), Nan)
                         } else {
                             match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
                                 (true, true) => {
@@ -5841,26 +8488,34 @@ fn limsided(
                                         n1.real().is_sign_positive(),
                                         n1.imag().is_sign_positive(),
                                     ) {
-                                        (true, true) => {
-                                            Complex::with_val(options.prec, (Infinity, Infinity))
-                                        }
+                                        (true, true) => Complex::with_val(
+                                            options.precision(),
+                                            (Infinity, Infinity),
+                                        ),
                                         (true, false) => Complex::with_val(
-                                            options.prec,
-                                            (Infinity, -Float::with_val(options.prec, Infinity)),
+                                            options.precision(),
+                                            (
+                                                Infinity,
+                                                -Float::with_val(options.precision(), Infinity),
+                                            ),
                                         ),
                                         (false, true) => Complex::with_val(
-                                            options.prec,
-                                            (-Float::with_val(options.prec, Infinity), Infinity),
+                                            options.precision(),
+                                            (
+                                                -Float::with_val(options.precision(), Infinity),
+                                                Infinity,
+                                            ),
+                                        ),
+                                        (false, false) => -Complex::with_val(
+                                            options.precision(),
+                                            (Infinity, Infinity),
                                         ),
-                                        (false, false) => {
-                                            -Complex::with_val(options.prec, (Infinity, Infinity))
-                                        }
                                     }
                                 }
                                 (true, false) => {
                                     if n1.real().is_sign_positive() {
                                         Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 Infinity,
                                                 if (n2i - n3i.clone()).abs().log2()
@@ -5868,13 +8523,13 @@ fn limsided(
                                                 {
                                                     n3i
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                             ),
                                         )
                                     } else {
                                         -Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 Infinity,
                                                 if (n2i - n3i.clone()).abs().log2()
@@ -5882,7 +8537,7 @@ fn limsided(
                                                 {
                                                     -n3i
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                             ),
                                         )
@@ -5891,35 +8546,35 @@ fn limsided(
                                 (false, true) => {
                                     if n1.imag().is_sign_positive() {
                                         Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 if (n2r - n3r.clone()).abs().log2()
                                                     < options.prec as i32 / -16
                                                 {
                                                     n3r
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                                 Infinity,
                                             ),
                                         )
                                     } else {
                                         -Complex::with_val(
-                                            options.prec,
+                                            options.precision(),
                                             (
                                                 if (n2r - n3r.clone()).abs().log2()
                                                     < options.prec as i32 / -16
                                                 {
                                                     -n3r
                                                 } else {
-                                                    Float::new(options.prec)
+                                                    Float::new(options.precision())
                                                 },
                                                 Infinity,
                                             ),
                                         )
                                     }
                                 }
-                                (false, false) => Complex::with_val(options.prec, Nan),
+                                (false, false) => Complex::with_val(options.precision(), Nan),
                             }
                         }
                     },
@@ -5928,7 +8583,7 @@ fn limsided(
             }
             Ok(Vector(vec))
         }
-        (_, _) => Err("unsupported lim data"),
+        (_, _) => Err(Error::Unsupported("unsupported lim data", None)),
     }
 }
 //https://github.com/IstvanMezo/LambertW-function/blob/master/complex%20Lambert.cpp
@@ -5947,7 +8602,7 @@ pub fn lambertw(z: Complex, k: Integer) -> Complex {
         }
     }
     let mut w = initpoint(z.clone(), k);
-    for _ in 0..(z.prec().0 / 64).max(8) {
+    for _ in 0..(z.prec().0.bits / 64).max(8) {
         let zexp = w.clone().exp();
         let zexpz = &w * zexp.clone();
         let zexpz_d = &zexp + zexpz.clone();
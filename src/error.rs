@@ -0,0 +1,90 @@
+use std::fmt;
+pub type Span = (usize, usize);
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error {
+    Empty,
+    Parse(&'static str, Option<Span>),
+    Arity(&'static str, Option<Span>),
+    Type(&'static str, Option<Span>),
+    Dimension(&'static str, Option<Span>),
+    Unit(&'static str, Option<Span>),
+    Domain(&'static str, Option<Span>),
+    NotConverged(&'static str, Option<Span>),
+    Unsupported(&'static str, Option<Span>),
+    UnsupportedBackend(&'static str, Option<Span>),
+    Setting(&'static str, Option<Span>),
+}
+impl Error {
+    pub fn message(&self) -> &'static str {
+        match self {
+            Error::Empty => "",
+            Error::Parse(s, _)
+            | Error::Arity(s, _)
+            | Error::Type(s, _)
+            | Error::Dimension(s, _)
+            | Error::Unit(s, _)
+            | Error::Domain(s, _)
+            | Error::NotConverged(s, _)
+            | Error::Unsupported(s, _)
+            | Error::UnsupportedBackend(s, _)
+            | Error::Setting(s, _) => s,
+        }
+    }
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Error::Empty => None,
+            Error::Parse(_, span)
+            | Error::Arity(_, span)
+            | Error::Type(_, span)
+            | Error::Dimension(_, span)
+            | Error::Unit(_, span)
+            | Error::Domain(_, span)
+            | Error::NotConverged(_, span)
+            | Error::Unsupported(_, span)
+            | Error::UnsupportedBackend(_, span)
+            | Error::Setting(_, span) => *span,
+        }
+    }
+    pub fn with_span(mut self, new: Span) -> Self {
+        match &mut self {
+            Error::Empty => {}
+            Error::Parse(_, span)
+            | Error::Arity(_, span)
+            | Error::Type(_, span)
+            | Error::Dimension(_, span)
+            | Error::Unit(_, span)
+            | Error::Domain(_, span)
+            | Error::NotConverged(_, span)
+            | Error::Unsupported(_, span)
+            | Error::UnsupportedBackend(_, span)
+            | Error::Setting(_, span) => *span = Some(new),
+        }
+        self
+    }
+    pub fn offset(mut self, by: usize) -> Self {
+        match &mut self {
+            Error::Empty => {}
+            Error::Parse(_, span)
+            | Error::Arity(_, span)
+            | Error::Type(_, span)
+            | Error::Dimension(_, span)
+            | Error::Unit(_, span)
+            | Error::Domain(_, span)
+            | Error::NotConverged(_, span)
+            | Error::Unsupported(_, span)
+            | Error::UnsupportedBackend(_, span)
+            | Error::Setting(_, span) => {
+                if let Some((a, b)) = span {
+                    *span = Some((*a + by, *b + by))
+                }
+            }
+        }
+        self
+    }
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+impl std::error::Error for Error {}
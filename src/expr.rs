@@ -0,0 +1,682 @@
+use crate::types::Complex;
+use crate::{
+    complex::{
+        NumStr,
+        NumStr::{
+            And, Comma, Converse, Conversion, Division, Equal, Exponent, Func, Greater,
+            GreaterEqual, Implies, InternalMultiplication, LeftBracket, LeftCurlyBracket, Lesser,
+            LesserEqual, Matrix, Minus, Modulo, Multiplication, Nand, NearEqual, Nor, Not,
+            NotEqual, Num, Or, Plus, PlusMinus, Range, RightBracket, RightCurlyBracket, Root,
+            ShiftLeft, ShiftRight, Tetration, Vector, Xor,
+        },
+        and, not,
+    },
+    error::{Error, Span},
+    math::{
+        Scalar, binary, call, compare, constant, do_math, is_comparison, map_scalar, scalar, to_deg,
+    },
+    misc::place_var,
+    units::Options,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+///expression tree behind Calculator::parse/eval, diff, simplify, isolate and compiled graphs.
+///the migration stops there: the cli, print and load_vars paths still evaluate the token stream
+///with do_math, lazy calls (sum, area, solve, ...) hand their bodies to do_math since it samples
+///them per point, and arguments the tree parser cannot place are kept as Raw tokens
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Expr {
+    Literal(NumStr),
+    Var(String),
+    Call(String, Vec<Expr>),
+    Apply(String, Box<Expr>),
+    Vector(Vec<Expr>),
+    Group(Box<Expr>),
+    Binary(NumStr, Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Raw(Vec<NumStr>),
+    Spanned(Span, Box<Expr>),
+}
+impl Expr {
+    pub fn from_tokens(tokens: &[NumStr]) -> Result<Self, Error> {
+        Self::from_spans(tokens, &[])
+    }
+    pub fn from_spans(tokens: &[NumStr], spans: &[Option<Span>]) -> Result<Self, Error> {
+        if tokens.is_empty() {
+            return Err(Error::Empty);
+        }
+        let mut i = 0;
+        let expr = parse_level(tokens, spans, &mut i, 0)?;
+        if i != tokens.len() {
+            return Err(Error::Parse("failed to compute", None));
+        }
+        Ok(expr)
+    }
+    pub fn to_tokens(&self) -> Vec<NumStr> {
+        let mut tokens = Vec::new();
+        self.push_tokens(&mut tokens);
+        tokens
+    }
+    fn push_tokens(&self, tokens: &mut Vec<NumStr>) {
+        match self {
+            Expr::Literal(n) => tokens.push(n.clone()),
+            Expr::Var(s) => tokens.push(Func(s.clone())),
+            Expr::Call(s, args) => {
+                tokens.push(Func(s.clone()));
+                tokens.push(LeftBracket);
+                push_list(args, tokens);
+                tokens.push(RightBracket);
+            }
+            Expr::Apply(s, e) => {
+                tokens.push(Func(s.clone()));
+                e.push_tokens(tokens);
+            }
+            Expr::Vector(args) => {
+                tokens.push(LeftCurlyBracket);
+                push_list(args, tokens);
+                tokens.push(RightCurlyBracket);
+            }
+            Expr::Group(e) => {
+                tokens.push(LeftBracket);
+                e.push_tokens(tokens);
+                tokens.push(RightBracket);
+            }
+            Expr::Binary(op, a, b) => {
+                a.push_tokens(tokens);
+                tokens.push(op.clone());
+                b.push_tokens(tokens);
+            }
+            Expr::Not(e) => {
+                tokens.push(Not);
+                e.push_tokens(tokens);
+            }
+            Expr::Raw(raw) => tokens.extend(raw.iter().cloned()),
+            Expr::Spanned(_, e) => e.push_tokens(tokens),
+        }
+    }
+    ///replaces var with num, except in the body of a lazy call that binds var itself
+    pub fn place_var(self, var: &str, num: &NumStr) -> Self {
+        let list = |args: Vec<Expr>| {
+            args.into_iter()
+                .map(|a| a.place_var(var, num))
+                .collect::<Vec<Expr>>()
+        };
+        match self {
+            Expr::Var(s) if s == var => Expr::Literal(num.clone()),
+            Expr::Raw(tokens) => Expr::Raw(place_var(tokens, var, num.clone())),
+            Expr::Call(s, args) => {
+                let body = binder(&s, &args, var).map(|b| b + 1);
+                Expr::Call(
+                    s,
+                    args.into_iter()
+                        .enumerate()
+                        .map(|(i, a)| {
+                            if body.is_some_and(|b| i <= b) {
+                                a
+                            } else {
+                                a.place_var(var, num)
+                            }
+                        })
+                        .collect(),
+                )
+            }
+            Expr::Apply(s, e) => Expr::Apply(s, Box::new(e.place_var(var, num))),
+            Expr::Vector(args) => Expr::Vector(list(args)),
+            Expr::Group(e) => Expr::Group(Box::new(e.place_var(var, num))),
+            Expr::Binary(op, a, b) => Expr::Binary(
+                op,
+                Box::new(a.place_var(var, num)),
+                Box::new(b.place_var(var, num)),
+            ),
+            Expr::Not(e) => Expr::Not(Box::new(e.place_var(var, num))),
+            Expr::Spanned(span, e) => Expr::Spanned(span, Box::new(e.place_var(var, num))),
+            e => e,
+        }
+    }
+    pub fn eval(
+        &self,
+        options: Options,
+        func_vars: &[(String, Vec<NumStr>)],
+    ) -> Result<NumStr, Error> {
+        match self {
+            Expr::Literal(n) => Ok(n.clone()),
+            Expr::Var(s) => match func_vars
+                .iter()
+                .position(|(name, _)| name == s && !name.ends_with(')'))
+            {
+                Some(i) => Expr::from_tokens(&func_vars[i].1)?.eval(options, &func_vars[..i]),
+                None => Ok(constant(s, options)?.unwrap_or(Func(s.clone()))),
+            },
+            Expr::Raw(raw) => do_math(raw.clone(), options, func_vars.to_vec()),
+            Expr::Spanned(span, e) => e.eval(options, func_vars).map_err(|err| {
+                if err.span().is_none() {
+                    err.with_span(*span)
+                } else {
+                    err
+                }
+            }),
+            Expr::Call(s, _) if is_lazy(s) => {
+                do_math(self.to_tokens(), options, func_vars.to_vec())
+            }
+            Expr::Call(s, args) => call(
+                s,
+                args.iter()
+                    .map(|a| a.eval(options, func_vars))
+                    .collect::<Result<Vec<NumStr>, Error>>()?,
+                options,
+            ),
+            Expr::Apply(s, e) => call(s, vec![e.eval(options, func_vars)?], options),
+            Expr::Vector(args) => vector(
+                args.iter()
+                    .map(|a| a.eval(options, func_vars))
+                    .collect::<Result<Vec<NumStr>, Error>>()?,
+            ),
+            Expr::Group(e) => e.eval(options, func_vars),
+            Expr::Not(e) => not(&e.eval(options, func_vars)?),
+            Expr::Binary(op, a, b) => match peel(a) {
+                Expr::Binary(inner, _, mid) if is_comparison(op) && is_comparison(inner) => {
+                    let a = a.eval(options, func_vars)?;
+                    let b = compare(
+                        op,
+                        &mid.eval(options, func_vars)?,
+                        &b.eval(options, func_vars)?,
+                    )?;
+                    a.func(&b, and)
+                }
+                _ => binary(
+                    op,
+                    &a.eval(options, func_vars)?,
+                    &b.eval(options, func_vars)?,
+                    options,
+                ),
+            },
+        }
+    }
+}
+fn vector(args: Vec<NumStr>) -> Result<NumStr, Error> {
+    let mut vec = Vec::new();
+    let mut mat = Vec::new();
+    for a in args {
+        match a {
+            Num(n) => vec.push(*n),
+            Vector(n) => mat.push(n),
+            _ => return Err(Error::Parse("broken matrix", None)),
+        }
+    }
+    if mat.is_empty() {
+        Ok(Vector(vec))
+    } else if vec.is_empty() {
+        Ok(Matrix(mat))
+    } else {
+        Err(Error::Parse("vector err", None))
+    }
+}
+fn push_list(args: &[Expr], tokens: &mut Vec<NumStr>) {
+    for (i, a) in args.iter().enumerate() {
+        if i != 0 {
+            tokens.push(Comma)
+        }
+        a.push_tokens(tokens)
+    }
+}
+fn peel(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Spanned(_, e) => peel(e),
+        e => e,
+    }
+}
+///the argument naming var among the variables a lazy call binds, its body is the argument after
+fn binder(s: &str, args: &[Expr], var: &str) -> Option<usize> {
+    let bound = match s {
+        "integrate3" => 3,
+        "surfacearea" | "sarea" | "integrate2" | "contour" | "odesolve" | "minimize"
+        | "maximize" | "fit" => 2,
+        s if is_lazy(s) => 1,
+        _ => 0,
+    };
+    args.iter()
+        .take(bound)
+        .position(|a| matches!(a, Expr::Var(v) if v == var))
+}
+pub fn is_lazy(s: &str) -> bool {
+    matches!(
+        s,
+        "sum"
+            | "area"
+            | "surfacearea"
+            | "sarea"
+            | "solve"
+            | "∫"
+            | "length"
+            | "slope"
+            | "taylor"
+            | "iter"
+            | "extrema"
+            | "summation"
+            | "prod"
+            | "product"
+            | "Σ"
+            | "Π"
+            | "vec"
+            | "mat"
+            | "piecewise"
+            | "pw"
+            | "D"
+            | "integrate"
+            | "arclength"
+            | "lim"
+            | "limit"
+            | "set"
+            | "isolate"
+            | "fit"
+            | "maximize"
+            | "minimize"
+            | "roots"
+            | "odesolve"
+            | "contour"
+            | "integrate3"
+            | "integrate2"
+            | "funm"
+    )
+}
+pub fn level(op: &NumStr) -> Option<usize> {
+    Some(match op {
+        And | Or | Xor | Implies | Nand | Nor | Converse => 0,
+        Lesser | Greater | Equal | NotEqual | NearEqual | LesserEqual | GreaterEqual
+        | ShiftLeft | ShiftRight => 1,
+        Conversion => 2,
+        Plus | Minus | PlusMinus => 3,
+        Multiplication | Division => 4,
+        InternalMultiplication => 5,
+        Exponent | Tetration | Root => 6,
+        Modulo | Range => 7,
+        _ => return None,
+    })
+}
+fn parse_level(
+    tokens: &[NumStr],
+    spans: &[Option<Span>],
+    i: &mut usize,
+    n: usize,
+) -> Result<Expr, Error> {
+    if n == 8 {
+        return parse_primary(tokens, spans, i);
+    }
+    let start = *i;
+    let mut lhs = parse_operand(tokens, spans, i, n)?;
+    while let Some(op) = tokens.get(*i).filter(|op| level(op) == Some(n)) {
+        *i += 1;
+        let rhs = if n == 6 {
+            parse_level(tokens, spans, i, 6)?
+        } else {
+            parse_operand(tokens, spans, i, n)?
+        };
+        lhs = spanned(
+            Expr::Binary(op.clone(), Box::new(lhs), Box::new(rhs)),
+            spans,
+            start,
+            *i,
+        );
+        if n == 6 {
+            break;
+        }
+    }
+    Ok(lhs)
+}
+fn parse_operand(
+    tokens: &[NumStr],
+    spans: &[Option<Span>],
+    i: &mut usize,
+    n: usize,
+) -> Result<Expr, Error> {
+    if n == 0 && tokens.get(*i) == Some(&Not) {
+        *i += 1;
+        Ok(Expr::Not(Box::new(parse_level(tokens, spans, i, 1)?)))
+    } else {
+        parse_level(tokens, spans, i, n + 1)
+    }
+}
+fn parse_list(
+    tokens: &[NumStr],
+    spans: &[Option<Span>],
+    i: &mut usize,
+    end: &NumStr,
+) -> Result<Vec<Expr>, Error> {
+    let mut args = Vec::new();
+    if tokens.get(*i) == Some(end) {
+        *i += 1;
+        return Ok(args);
+    }
+    loop {
+        let start = *i;
+        match parse_level(tokens, spans, i, 0) {
+            Ok(e) if matches!(tokens.get(*i), Some(t) if t == end || *t == Comma) => args.push(e),
+            _ => {
+                *i = start;
+                let mut count = 0;
+                while let Some(t) = tokens.get(*i) {
+                    match t {
+                        Comma if count == 0 => break,
+                        _ if count == 0 && t == end => break,
+                        LeftBracket | LeftCurlyBracket => count += 1,
+                        RightBracket | RightCurlyBracket => count -= 1,
+                        _ => {}
+                    }
+                    *i += 1;
+                }
+                args.push(spanned(
+                    Expr::Raw(tokens[start..*i].to_vec()),
+                    spans,
+                    start,
+                    *i,
+                ))
+            }
+        }
+        match tokens.get(*i) {
+            Some(Comma) => *i += 1,
+            Some(_) => {
+                *i += 1;
+                return Ok(args);
+            }
+            None => {
+                return Err(Error::Parse(
+                    if *end == RightBracket {
+                        "round bracket err"
+                    } else {
+                        "curly bracket err"
+                    },
+                    None,
+                ));
+            }
+        }
+    }
+}
+fn parse_primary(tokens: &[NumStr], spans: &[Option<Span>], i: &mut usize) -> Result<Expr, Error> {
+    let Some(token) = tokens.get(*i) else {
+        return Err(Error::Arity("not enough input", None));
+    };
+    *i += 1;
+    match token {
+        Num(_) | Vector(_) | Matrix(_) => Ok(Expr::Literal(token.clone())),
+        Func(s) => {
+            let start = *i - 1;
+            if tokens.get(*i) == Some(&LeftBracket) {
+                *i += 1;
+                let args = parse_list(tokens, spans, i, &RightBracket)?;
+                Ok(spanned(Expr::Call(s.clone(), args), spans, start, *i))
+            } else if tokens.get(*i) == Some(&LeftCurlyBracket) {
+                let e = parse_primary(tokens, spans, i)?;
+                Ok(spanned(
+                    Expr::Apply(s.clone(), Box::new(e)),
+                    spans,
+                    start,
+                    *i,
+                ))
+            } else if !matches!(s.as_str(), "epoch" | "rnd" | "rand")
+                && matches!(
+                    tokens.get(*i),
+                    Some(Num(_) | Vector(_) | Matrix(_) | Func(_))
+                )
+            {
+                let e = match &tokens[*i] {
+                    Func(v) => Expr::Var(v.clone()),
+                    n => Expr::Literal(n.clone()),
+                };
+                *i += 1;
+                Ok(spanned(
+                    Expr::Apply(s.clone(), Box::new(e)),
+                    spans,
+                    start,
+                    *i,
+                ))
+            } else {
+                Ok(Expr::Var(s.clone()))
+            }
+        }
+        LeftBracket => {
+            let e = parse_level(tokens, spans, i, 0)?;
+            if tokens.get(*i) != Some(&RightBracket) {
+                return Err(Error::Parse("round bracket err", None));
+            }
+            *i += 1;
+            Ok(Expr::Group(Box::new(e)))
+        }
+        LeftCurlyBracket => Ok(Expr::Vector(parse_list(
+            tokens,
+            spans,
+            i,
+            &RightCurlyBracket,
+        )?)),
+        _ => Err(Error::Parse("failed to compute", None)),
+    }
+}
+fn spanned(expr: Expr, spans: &[Option<Span>], start: usize, end: usize) -> Expr {
+    match (spans.get(start), spans.get(end - 1)) {
+        (Some(Some(a)), Some(Some(b))) => {
+            Expr::Spanned((a.0.min(b.0), a.1.max(b.1)), Box::new(expr))
+        }
+        _ => expr,
+    }
+}
+#[derive(Clone, Debug)]
+enum Op {
+    Push(NumStr),
+    X,
+    Y,
+    Binary(NumStr),
+    Chain(NumStr),
+    Not,
+    Vector(usize),
+    Call(String, usize),
+    Apply(String),
+    Scalar(Scalar),
+    Tokens(Vec<NumStr>, bool),
+}
+#[derive(Clone, Debug)]
+pub struct CompiledExpr {
+    ops: Vec<Op>,
+    stack: Vec<NumStr>,
+    func_vars: Vec<(String, Vec<NumStr>)>,
+    options: Options,
+    to_deg: Complex,
+}
+impl CompiledExpr {
+    pub fn new(
+        tokens: &[NumStr],
+        func_vars: &[(String, Vec<NumStr>)],
+        options: Options,
+    ) -> Result<Self, Error> {
+        let expr = inline(Expr::from_tokens(tokens)?, func_vars)?;
+        let mut ops = Vec::new();
+        compile(&expr, &mut ops, options, func_vars)?;
+        let depth = ops
+            .iter()
+            .fold((0, 0), |(d, m), op| {
+                let d = match op {
+                    Op::Push(_) | Op::X | Op::Y | Op::Tokens(_, _) => d + 1,
+                    Op::Binary(_) => d - 1,
+                    Op::Chain(_) => d - 2,
+                    Op::Vector(n) | Op::Call(_, n) => d + 1 - n,
+                    Op::Not | Op::Apply(_) | Op::Scalar(_) => d,
+                };
+                (d, m.max(d))
+            })
+            .1;
+        Ok(Self {
+            ops,
+            stack: Vec::with_capacity(depth),
+            func_vars: func_vars.to_vec(),
+            options,
+            to_deg: to_deg(options),
+        })
+    }
+    pub fn eval(&mut self, x: &NumStr, y: &NumStr) -> Result<NumStr, Error> {
+        self.stack.clear();
+        for op in &self.ops {
+            let n = match op {
+                Op::Push(n) => n.clone(),
+                Op::X => x.clone(),
+                Op::Y => y.clone(),
+                Op::Binary(op) => {
+                    let b = self.stack.pop().unwrap();
+                    let a = self.stack.pop().unwrap();
+                    binary(op, &a, &b, self.options)?
+                }
+                Op::Chain(op) => {
+                    let b = self.stack.pop().unwrap();
+                    let mid = self.stack.pop().unwrap();
+                    let a = self.stack.pop().unwrap();
+                    a.func(&compare(op, &mid, &b)?, and)?
+                }
+                Op::Not => not(&self.stack.pop().unwrap())?,
+                Op::Vector(n) => vector(self.stack.split_off(self.stack.len() - n))?,
+                Op::Call(s, n) => {
+                    call(s, self.stack.split_off(self.stack.len() - n), self.options)?
+                }
+                Op::Apply(s) => call(s, vec![self.stack.pop().unwrap()], self.options)?,
+                Op::Scalar(f) => map_scalar(self.stack.pop().unwrap(), *f, &self.to_deg)?,
+                Op::Tokens(tokens, false) => {
+                    do_math(tokens.clone(), self.options, self.func_vars.clone())?
+                }
+                Op::Tokens(tokens, true) => do_math(
+                    place_var(place_var(tokens.clone(), "x", x.clone()), "y", y.clone()),
+                    self.options,
+                    self.func_vars.clone(),
+                )?,
+            };
+            self.stack.push(n)
+        }
+        self.stack
+            .pop()
+            .ok_or(Error::Parse("failed to compute", None))
+    }
+}
+pub fn inline(expr: Expr, func_vars: &[(String, Vec<NumStr>)]) -> Result<Expr, Error> {
+    let list = |args: Vec<Expr>| {
+        args.into_iter()
+            .map(|a| inline(a, func_vars))
+            .collect::<Result<Vec<Expr>, Error>>()
+    };
+    Ok(match expr {
+        Expr::Var(s) => match func_vars.iter().position(|v| v.0 == s) {
+            Some(i) => Expr::Group(Box::new(inline(
+                Expr::from_tokens(&func_vars[i].1)?,
+                &func_vars[..i],
+            )?)),
+            None => Expr::Var(s),
+        },
+        Expr::Raw(tokens) => Expr::Raw(inline_tokens(tokens, func_vars)),
+        Expr::Call(s, args) => Expr::Call(s, list(args)?),
+        Expr::Apply(s, e) => Expr::Apply(s, Box::new(inline(*e, func_vars)?)),
+        Expr::Vector(args) => Expr::Vector(list(args)?),
+        Expr::Group(e) => Expr::Group(Box::new(inline(*e, func_vars)?)),
+        Expr::Binary(op, a, b) => Expr::Binary(
+            op,
+            Box::new(inline(*a, func_vars)?),
+            Box::new(inline(*b, func_vars)?),
+        ),
+        Expr::Not(e) => Expr::Not(Box::new(inline(*e, func_vars)?)),
+        Expr::Spanned(span, e) => Expr::Spanned(span, Box::new(inline(*e, func_vars)?)),
+        e => e,
+    })
+}
+fn inline_tokens(tokens: Vec<NumStr>, func_vars: &[(String, Vec<NumStr>)]) -> Vec<NumStr> {
+    let mut inlined = Vec::with_capacity(tokens.len());
+    for t in tokens {
+        match &t {
+            Func(s) => match func_vars.iter().position(|v| v.0 == *s) {
+                Some(i) => {
+                    inlined.push(LeftBracket);
+                    inlined.extend(inline_tokens(func_vars[i].1.clone(), &func_vars[..i]));
+                    inlined.push(RightBracket)
+                }
+                None => inlined.push(t),
+            },
+            _ => inlined.push(t),
+        }
+    }
+    inlined
+}
+fn is_constant(expr: &Expr) -> bool {
+    match expr {
+        Expr::Literal(_) => true,
+        Expr::Var(_) | Expr::Raw(_) => false,
+        Expr::Call(s, _) | Expr::Apply(s, _)
+            if is_lazy(s)
+                || matches!(s.as_str(), "rnd" | "rand" | "epoch" | "roll")
+                || s.starts_with("rand_") =>
+        {
+            false
+        }
+        Expr::Call(_, args) | Expr::Vector(args) => args.iter().all(is_constant),
+        Expr::Apply(_, e) | Expr::Group(e) | Expr::Not(e) | Expr::Spanned(_, e) => is_constant(e),
+        Expr::Binary(_, a, b) => is_constant(a) && is_constant(b),
+    }
+}
+fn compile(
+    expr: &Expr,
+    ops: &mut Vec<Op>,
+    options: Options,
+    func_vars: &[(String, Vec<NumStr>)],
+) -> Result<(), Error> {
+    if !matches!(expr, Expr::Literal(_)) && is_constant(expr) {
+        ops.push(Op::Push(expr.eval(options, func_vars)?));
+        return Ok(());
+    }
+    match expr {
+        Expr::Literal(n) => ops.push(Op::Push(n.clone())),
+        Expr::Var(s) if s == "x" => ops.push(Op::X),
+        Expr::Var(s) if s == "y" => ops.push(Op::Y),
+        Expr::Call(s, args) if args.len() == 1 && scalar(s).is_some() => {
+            compile(&args[0], ops, options, func_vars)?;
+            ops.push(Op::Scalar(scalar(s).unwrap()))
+        }
+        Expr::Apply(s, e) if scalar(s).is_some() => {
+            compile(e, ops, options, func_vars)?;
+            ops.push(Op::Scalar(scalar(s).unwrap()))
+        }
+        Expr::Call(s, args) if !is_lazy(s) => {
+            for a in args {
+                compile(a, ops, options, func_vars)?
+            }
+            ops.push(Op::Call(s.clone(), args.len()))
+        }
+        Expr::Apply(s, e) => {
+            compile(e, ops, options, func_vars)?;
+            ops.push(Op::Apply(s.clone()))
+        }
+        Expr::Vector(args) => {
+            for a in args {
+                compile(a, ops, options, func_vars)?
+            }
+            ops.push(Op::Vector(args.len()))
+        }
+        Expr::Group(e) | Expr::Spanned(_, e) => compile(e, ops, options, func_vars)?,
+        Expr::Not(e) => {
+            compile(e, ops, options, func_vars)?;
+            ops.push(Op::Not)
+        }
+        Expr::Binary(op, a, b) => match peel(a) {
+            Expr::Binary(inner, _, mid) if is_comparison(op) && is_comparison(inner) => {
+                compile(a, ops, options, func_vars)?;
+                compile(mid, ops, options, func_vars)?;
+                compile(b, ops, options, func_vars)?;
+                ops.push(Op::Chain(op.clone()))
+            }
+            _ => {
+                compile(a, ops, options, func_vars)?;
+                compile(b, ops, options, func_vars)?;
+                ops.push(Op::Binary(op.clone()))
+            }
+        },
+        _ => {
+            let tokens = expr.to_tokens();
+            let xy = tokens
+                .iter()
+                .any(|t| matches!(t, Func(s) if s == "x" || s == "y"));
+            ops.push(Op::Tokens(tokens, xy))
+        }
+    }
+    Ok(())
+}
@@ -1,15 +1,15 @@
 // as per continued fraction expansion
+use crate::types::{Complex, Float, Integer, float::Constant::Pi, ops::Pow};
 use crate::{
     complex::prime_factors,
     units::{Auto, Colors, Number, Options},
 };
-use rug::{Complex, Float, Integer, float::Constant::Pi, ops::Pow};
 pub fn fraction(value: Float, options: Options, colors: &Colors, n: usize) -> String {
     if value.clone().fract().is_zero() || !value.is_finite() || options.prec < 128 {
         return String::new();
     }
-    let e = Float::with_val(options.prec, 1.0).exp();
-    let pi = Float::with_val(options.prec, Pi);
+    let e = Float::with_val(options.precision(), 1.0).exp();
+    let pi = Float::with_val(options.precision(), Pi);
     let sign: String = if value.is_sign_negative() {
         '-'.to_string()
     } else {
@@ -90,8 +90,8 @@ pub fn fraction(value: Float, options: Options, colors: &Colors, n: usize) -> St
             };
         }
         let mut number = orig.clone().fract();
-        let mut mult = Float::with_val(options.prec, 1);
-        let mut first: Float = Float::new(options.prec);
+        let mut mult = Float::with_val(options.precision(), 1);
+        let mut first: Float = Float::new(options.precision());
         for j in 0..64 {
             let mut recip = number.clone().recip();
             let fract = recip.clone().fract();
@@ -265,8 +265,8 @@ pub fn rationalize(value: Float, options: Options) -> Option<(Integer, Integer)>
         return Some((value.to_integer().unwrap_or_default(), Integer::from(1)));
     }
     let mut number = value.clone().fract();
-    let mut mult = Float::with_val(options.prec, 1);
-    let mut first: Float = Float::new(options.prec);
+    let mut mult = Float::with_val(options.precision(), 1);
+    let mut first: Float = Float::new(options.precision());
     for j in 0..256 {
         let mut recip = number.clone().recip();
         let fract = recip.clone().fract();
@@ -300,20 +300,29 @@ pub fn c_to_rational(value: Complex, options: Options) -> Vec<Number> {
     let mut vec = Vec::new();
     if let Some(n) = re {
         vec.push(Number::from(
-            Complex::with_val(options.prec, if sign_re { n.0 } else { -n.0 }),
+            Complex::with_val(options.precision(), if sign_re { n.0 } else { -n.0 }),
+            None,
+        ));
+        vec.push(Number::from(
+            Complex::with_val(options.precision(), n.1),
             None,
         ));
-        vec.push(Number::from(Complex::with_val(options.prec, n.1), None));
     } else {
-        vec.push(Number::from(Complex::new(options.prec), None));
-        vec.push(Number::from(Complex::with_val(options.prec, 1), None));
+        vec.push(Number::from(Complex::new(options.precision()), None));
+        vec.push(Number::from(
+            Complex::with_val(options.precision(), 1),
+            None,
+        ));
     }
     if let Some(n) = im {
         vec.push(Number::from(
-            Complex::with_val(options.prec, if sign_im { n.0 } else { -n.0 }),
+            Complex::with_val(options.precision(), if sign_im { n.0 } else { -n.0 }),
+            None,
+        ));
+        vec.push(Number::from(
+            Complex::with_val(options.precision(), n.1),
             None,
         ));
-        vec.push(Number::from(Complex::with_val(options.prec, n.1), None));
     }
     vec
 }
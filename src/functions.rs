@@ -134,6 +134,23 @@ pub fn functions() -> HashSet<&'static str> {
         "ker",
         "ran",
         "rank",
+        "lu",
+        "qr",
+        "svd",
+        "chol",
+        "cholesky",
+        "schur",
+        "pinv",
+        "cond",
+        "norm2",
+        "expm",
+        "logm",
+        "sqrtm",
+        "sinm",
+        "cosm",
+        "funm",
+        "linsolve",
+        "lstsq",
         "null",
         "generalized_eigenvectors",
         "rcf",
@@ -194,6 +211,10 @@ pub fn functions() -> HashSet<&'static str> {
         "area",
         "∫",
         "integrate",
+        "integrate2",
+        "integrate3",
+        "contour",
+        "odesolve",
         "arclength",
         "roll",
         "erfi",
@@ -215,6 +236,10 @@ pub fn functions() -> HashSet<&'static str> {
         "identity",
         "quartic",
         "solve",
+        "roots",
+        "polyroots",
+        "minimize",
+        "maximize",
         "inter",
         "interpolate",
         "unity",
@@ -222,6 +247,14 @@ pub fn functions() -> HashSet<&'static str> {
         "rand_weighted",
         "lobf",
         "lineofbestfit",
+        "polyfit",
+        "expfit",
+        "logfit",
+        "powfit",
+        "fit",
+        "spline",
+        "pchip",
+        "linterp",
         "onlyreal",
         "onlyre",
         "ore",
@@ -273,6 +306,17 @@ pub fn functions() -> HashSet<&'static str> {
         "sd",
         "skew",
         "isolate",
+        "diff",
+        "simplify",
+        "expand",
+        "collect",
+        "pgcd",
+        "pdiv",
+        "prem",
+        "resultant",
+        "discriminant",
+        "apart",
+        "antiderivative",
         "kurtosis",
         "rand_poisson",
         "poisson_pmf",
@@ -327,7 +371,20 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "length(vec/mat)",
         "width(vec/mat)",
         "trace(mat)",
-        "isolate(a,f(a))",
+        "isolate(a,f(a)(,n))",
+        "funm(mat,f(a))",
+        "diff(f(a),a(,n))",
+        "simplify(f(x))",
+        "expand(f(x))",
+        "collect(f(a),a)",
+        "factor(f(a),a)",
+        "pgcd(f(a),g(a),a)",
+        "pdiv(f(a),g(a),a)",
+        "prem(f(a),g(a),a)",
+        "resultant(f(a),g(a),a)",
+        "discriminant(f(a),a)",
+        "apart(f(a),a)",
+        "antiderivative(f(a),a)",
         "determinant(mat)",
         "part(mat,x(,y))",
         "part(vec,x)",
@@ -418,7 +475,24 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "change_basis(mat(,beta),gamma)",
         "coordinate(beta, v)",
         "ran(mat)",
-        "rank(mat)",
+        "rank(mat(,tol))",
+        "lu(mat(,n))",
+        "qr(mat(,n))",
+        "svd(mat(,n))",
+        "chol(mat)",
+        "cholesky(mat)",
+        "schur(mat(,n))",
+        "pinv(mat(,tol))",
+        "cond(mat)",
+        "norm2(mat)",
+        "linsolve(mat,vec)",
+        "lstsq(mat,vec(,mode))",
+        "expm(mat)",
+        "logm(mat)",
+        "sqrtm(mat)",
+        "sinm(mat)",
+        "cosm(mat)",
+        "funm(mat,f(x))",
         "null(mat)",
         "mat(a,f(a),start,end)",
         "reverse(vec)",
@@ -464,7 +538,12 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "slope(a,f(a),x(,nth)(,combine))",
         "taylor(a,f(a),p,n(,x))",
         "lim(a,f(a),x(,side))",
-        "area(a,f(a),start,end(,nth)(,combine)",
+        "area(a,f(a),start,end(,nth)(,combine)(,tol)",
+        "integrate(a,f(a),start,end(,tol))",
+        "integrate2(a,b,f(a,b),start_b,end_b,start_a,end_a(,tol))",
+        "integrate3(a,b,c,f(a,b,c),start_c,end_c,start_b,end_b,start_a,end_a(,tol))",
+        "contour(z,t,f(z),path(t),start,end(,tol))",
+        "odesolve(t,y,f(t,y),t0,y0,t1(,n)(,stiff)(,tol))",
         "arclength(a,f(a),start,end)",
         "roll(vec/mat)",
         "erfi(x)",
@@ -477,12 +556,25 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "η(x)",
         "identity(n)",
         "quartic(a,b,c,d,e(,real))",
+        "polyroots(vec)",
         "solve(a,f(a)(,start))",
+        "solve(a,f(a),start,end)",
+        "roots(a,f(a),start,end(,steps))",
+        "minimize(a,b,f(a,b),{a0,b0}(,lower,upper))",
+        "maximize(a,b,f(a,b),{a0,b0}(,lower,upper))",
         "interpolate(mat,x)",
         "unity(b,p)",
         "iter(a,f(a),x,n(,steps))",
         "rand_weighted(mat)",
         "lineofbestfit(mat(,x))",
+        "polyfit(mat,degree(,mode))",
+        "expfit(mat(,mode))",
+        "logfit(mat(,mode))",
+        "powfit(mat(,mode))",
+        "fit(x,a,b,f(x,a,b),mat,{a0,b0}(,mode))",
+        "spline(mat(,x)(,start slope,end slope))",
+        "pchip(mat(,x))",
+        "linterp(mat(,x))",
         "onlyreal(x)",
         "onlyimag(x)",
         "mod(a,b)",
@@ -813,6 +905,7 @@ pub fn options_list() -> HashSet<&'static str> {
         "siunits=",
         "keepzeros=",
         "polar=",
+        "exact=",
         "frac=",
         "fractions=",
         "fractionsv=",
@@ -846,6 +939,7 @@ pub fn options_list() -> HashSet<&'static str> {
         "color=",
         "lines=",
         "angle=",
+        "float_type=",
         "notation=",
         "graph=",
         "saveto=",
@@ -3,12 +3,14 @@
 use crate::misc::get_terminal_dimensions;
 #[cfg(unix)]
 use crate::misc::get_terminal_dimensions_pixel;
+use crate::types::{Complex, Float, float::Constant::Pi, ops::Pow};
 use crate::{
     complex::{
         NumStr,
         NumStr::{Func, Matrix, Num, Vector},
         set_prec,
     },
+    expr::CompiledExpr,
     load_vars::set_commands_or_vars,
     math::do_math,
     misc::{place_funcvar, place_funcvarxy, place_var, place_varxy, prompt},
@@ -20,7 +22,6 @@ use crate::{
         HowGraphing, Number, Options, Variable,
     },
 };
-use rug::{Complex, Float, float::Constant::Pi, ops::Pow};
 use std::{
     fs,
     fs::File,
@@ -569,14 +570,18 @@ pub fn get_list_2d(
     let mut no_opt_re = false;
     let mut no_opt_im = false;
     let mut timer = (Instant::now(), Instant::now());
+    let mut compiled = CompiledExpr::new(&func.0, &func.1, func.2).ok();
     for i in 0..=func.2.samples_2d {
         let n = func.2.xr.0 + i as f64 * den_range;
-        let num = NumStr::new(Number::from(Complex::with_val(func.2.prec, n), None));
-        match do_math(
-            place_varxy(func.0.clone(), num.clone()),
-            func.2,
-            place_funcvarxy(func.1.clone(), num),
-        ) {
+        let num = NumStr::new(Number::from(Complex::with_val(func.2.precision(), n), None));
+        match match compiled.as_mut() {
+            Some(c) => c.eval(&num, &num),
+            None => do_math(
+                place_varxy(func.0.clone(), num.clone()),
+                func.2,
+                place_funcvarxy(func.1.clone(), num),
+            ),
+        } {
             Ok(Num(num)) => {
                 let num = num.number;
                 let mut r = 0.0;
@@ -916,8 +921,6 @@ pub fn get_list_3d(
     }
     let den_x_range = (func.2.xr.1 - func.2.xr.0) / func.2.samples_3d.0 as f64;
     let den_y_range = (func.2.yr.1 - func.2.yr.0) / func.2.samples_3d.1 as f64;
-    let mut modified: Vec<NumStr>;
-    let mut modifiedvars: Vec<(String, Vec<NumStr>)>;
     let mut zero = (false, false);
     let mut nan = true;
     let list = func.0.iter().any(|c| match c {
@@ -962,30 +965,38 @@ pub fn get_list_3d(
     let mut no_opt_re = false;
     let mut no_opt_im = false;
     let pi: Float = if func.2.graphtype == Domain || func.2.graphtype == DomainAlt {
-        Float::with_val(func.2.prec, Pi)
+        Float::with_val(func.2.precision(), Pi)
     } else {
-        Float::new(func.2.prec)
+        Float::new(func.2.precision())
     };
     let mut timer = (Instant::now(), Instant::now());
+    let mut compiled = CompiledExpr::new(&func.0, &func.1, func.2).ok();
+    let mut modified = Vec::new();
+    let mut modifiedvars = Vec::new();
     for i in 0..=func.2.samples_3d.1 {
         let n = func.2.yr.0 + i as f64 * den_y_range;
-        let num = NumStr::new(Number::from(Complex::with_val(func.2.prec, n), None));
-        modified = place_var(func.0.clone(), "y", num.clone());
-        modifiedvars = place_funcvar(func.1.clone(), "y", num);
-        simplify(&mut modified, &mut modifiedvars, func.2);
+        let y = NumStr::new(Number::from(Complex::with_val(func.2.precision(), n), None));
+        if compiled.is_none() {
+            modified = place_var(func.0.clone(), "y", y.clone());
+            modifiedvars = place_funcvar(func.1.clone(), "y", y.clone());
+            simplify(&mut modified, &mut modifiedvars, func.2);
+        }
         for g in 0..=func.2.samples_3d.0 {
             let f = func.2.xr.0 + g as f64 * den_x_range;
-            let num = NumStr::new(Number::from(Complex::with_val(func.2.prec, f), None));
-            match do_math(
-                place_var(modified.clone(), "x", num.clone()),
-                func.2,
-                place_funcvar(modifiedvars.clone(), "x", num),
-            ) {
+            let x = NumStr::new(Number::from(Complex::with_val(func.2.precision(), f), None));
+            match match compiled.as_mut() {
+                Some(c) => c.eval(&x, &y),
+                None => do_math(
+                    place_var(modified.clone(), "x", x.clone()),
+                    func.2,
+                    place_funcvar(modifiedvars.clone(), "x", x),
+                ),
+            } {
                 Ok(Num(num)) => {
                     let num = num.number;
                     if func.2.graphtype == Domain {
                         let abs = num.clone().abs().real().clone();
-                        if abs.clone().log2() < abs.prec() - 16 {
+                        if abs.clone().log2() < abs.prec().bits - 16 {
                             let hue: Float = 1 + (-num.clone()).arg().real().clone() / &pi;
                             let sat: Float = (1 + abs.fract()) / 2;
                             let val: Float = {
@@ -1004,7 +1015,7 @@ pub fn get_list_3d(
                         }
                     } else if func.2.graphtype == DomainAlt {
                         let abs = num.clone().abs().real().clone();
-                        if abs.clone().log2() < abs.prec() - 16 {
+                        if abs.clone().log2() < abs.prec().bits - 16 {
                             let hue: Float = 1 + (-num.clone()).arg().real().clone() / &pi;
                             let sat: Float = {
                                 let t3: Float = (abs.clone() * &pi).sin();
@@ -1,12 +1,14 @@
+pub mod calculator;
 pub mod cas;
 pub mod complex;
+pub mod error;
+pub mod expr;
 pub mod fraction;
 pub mod functions;
 #[cfg(feature = "gnuplot")]
 pub mod graph;
 pub mod help;
 pub mod load_vars;
-mod macros;
 pub mod math;
 pub mod misc;
 pub mod options;
@@ -1,11 +1,12 @@
 use crate::complex::NumStr;
+use crate::types::{Float, float::Constant::Pi};
 use crate::{
+    error::Error,
     math::do_math,
     options::set_commands,
     parse::input_var,
     units::{Colors, Number, Options, Variable},
 };
-use rug::{Float, float::Constant::Pi};
 pub fn get_file_vars(
     options: Options,
     vars: &mut Vec<Variable>,
@@ -77,7 +78,7 @@ fn get_preset_vars(
         let phi1 = args.contains("phi") && !blacklist.contains(&"phi".to_string());
         let phi2 = args.contains('φ') && !blacklist.contains(&"φ".to_string());
         if phi1 || phi2 {
-            let phi: Float = (1 + Float::with_val(options.prec, 5).sqrt()) / 2;
+            let phi: Float = (1 + Float::with_val(options.precision(), 5).sqrt()) / 2;
             if phi1 {
                 blacklist.push("phi".to_string());
                 vars.insert(
@@ -107,7 +108,7 @@ fn get_preset_vars(
         let tau1 = args.contains("tau") && !blacklist.contains(&"tau".to_string());
         let tau2 = args.contains('τ') && !blacklist.contains(&"τ".to_string());
         if pi1 || pi2 || tau1 || tau2 {
-            let pi = Float::with_val(options.prec, Pi);
+            let pi = Float::with_val(options.precision(), Pi);
             if pi1 {
                 blacklist.push("pi".to_string());
                 vars.insert(
@@ -157,7 +158,7 @@ fn get_preset_vars(
     }
     if args.contains('e') && !blacklist.contains(&"e".to_string()) {
         blacklist.push("e".to_string());
-        let e = Float::with_val(options.prec, 1).exp();
+        let e = Float::with_val(options.precision(), 1).exp();
         vars.push(Variable {
             name: vec!['e'],
             parsed: vec![NumStr::new(Number::from(e.into(), None))],
@@ -174,7 +175,7 @@ pub fn get_cli_vars(options: Options, args: String, vars: &mut Vec<Variable>) {
         let phi1 = args.contains("phi");
         let phi2 = args.contains('φ');
         if phi1 || phi2 {
-            let phi: Float = (1 + Float::with_val(options.prec, 5).sqrt()) / 2;
+            let phi: Float = (1 + Float::with_val(options.precision(), 5).sqrt()) / 2;
             if phi1 {
                 vars.insert(
                     0,
@@ -202,7 +203,7 @@ pub fn get_cli_vars(options: Options, args: String, vars: &mut Vec<Variable>) {
         let tau1 = args.contains("tau");
         let tau2 = args.contains('τ');
         if pi1 || pi2 || tau1 || tau2 {
-            let pi = Float::with_val(options.prec, Pi);
+            let pi = Float::with_val(options.precision(), Pi);
             if pi1 {
                 vars.insert(
                     vars.iter().position(|c| c.name.len() != 3).unwrap_or(0),
@@ -247,7 +248,7 @@ pub fn get_cli_vars(options: Options, args: String, vars: &mut Vec<Variable>) {
         }
     }
     if args.contains('e') {
-        let e = Float::with_val(options.prec, 1).exp();
+        let e = Float::with_val(options.precision(), 1).exp();
         vars.push(Variable {
             name: vec!['e'],
             parsed: vec![NumStr::new(Number::from(e.into(), None))],
@@ -257,10 +258,10 @@ pub fn get_cli_vars(options: Options, args: String, vars: &mut Vec<Variable>) {
     }
 }
 pub fn get_vars(options: Options) -> Vec<Variable> {
-    let pi = Float::with_val(options.prec, Pi);
+    let pi = Float::with_val(options.precision(), Pi);
     let tau: Float = pi.clone() * 2;
-    let phi: Float = (1 + Float::with_val(options.prec, 5).sqrt()) / 2;
-    let e = Float::with_val(options.prec, 1).exp();
+    let phi: Float = (1 + Float::with_val(options.precision(), 5).sqrt()) / 2;
+    let e = Float::with_val(options.precision(), 1).exp();
     vec![
         Variable {
             name: vec!['p', 'h', 'i'],
@@ -316,7 +317,7 @@ pub fn add_var(
     redef: bool,
     replace: bool,
     null: bool,
-) -> Result<(), &'static str> {
+) -> Result<(), Error> {
     if null {
         vars.remove(i);
     } else {
@@ -329,13 +330,13 @@ pub fn add_var(
                 .drain(0..=l.iter().position(|c| c == &'(').unwrap_or(0))
                 .collect::<String>();
             if st.contains(',') || st.len() == 1 {
-                return Err("bad var name");
+                return Err(Error::Parse("bad var name", None));
             }
             for i in l.split(|c| c == &',') {
                 func_vars.push((-1, i.iter().collect()));
             }
         } else if l.contains(&',') || l.is_empty() {
-            return Err("bad var name");
+            return Err(Error::Parse("bad var name", None));
         }
         let mut fvs = Vec::new();
         let mut parsed = if r.contains("pw") || r.contains("piecewise") {
@@ -450,7 +451,7 @@ pub fn add_var(
                 .push((l.iter().collect::<String>(), parsed.0.clone()))
         }
         if parsed.0.is_empty() {
-            return Err("bad input");
+            return Err(Error::Parse("bad input", None));
         } else if replace {
             vars[i] = Variable {
                 name: l.clone(),
@@ -583,24 +584,28 @@ pub fn set_commands_or_vars(
     options: &mut Options,
     vars: &mut Vec<Variable>,
     input: &[char],
-) -> Result<(), &'static str> {
+) -> Result<(), Error> {
     let n = input.iter().collect::<String>();
     let mut split = n.splitn(2, '=');
     let s = split.next().unwrap().replace(' ', "");
     let l = s;
     let r = split.next().unwrap();
-    if l.is_empty()
-        || l.chars()
-            .any(|c| !c.is_alphanumeric() && !matches!(c, '(' | ')' | ',' | '\'' | '`' | '_'))
-    {
+    if !is_var_name(&l) || set_commands(options, colors, vars, &l, r)? {
         return Ok(());
-    } else if let Err(s) = set_commands(options, colors, vars, &l, r) {
-        if s.is_empty() {
-            return Ok(());
-        }
-        return Err(s);
     }
-    let l = l.chars().collect::<Vec<char>>();
+    set_var(l.chars().collect::<Vec<char>>(), r, vars, *options)
+}
+pub fn is_var_name(l: &str) -> bool {
+    !l.is_empty()
+        && l.chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '(' | ')' | ',' | '\'' | '`' | '_'))
+}
+pub fn set_var(
+    l: Vec<char>,
+    r: &str,
+    vars: &mut Vec<Variable>,
+    options: Options,
+) -> Result<(), Error> {
     for (i, v) in vars.iter().enumerate() {
         if v.name.split(|c| c == &'(').next() == l.split(|c| c == &'(').next()
             && v.name.contains(&'(') == l.contains(&'(')
@@ -608,19 +613,19 @@ pub fn set_commands_or_vars(
                 == l.iter().filter(|c| c == &&',').count()
         {
             if r == "null" {
-                add_var(l, r, i, vars, *options, true, true, true)?
+                add_var(l, r, i, vars, options, true, true, true)?
             } else {
-                add_var(l, r, i, vars, *options, true, true, false)?
+                add_var(l, r, i, vars, options, true, true, false)?
             }
             return Ok(());
         }
     }
     for (i, j) in vars.iter().enumerate() {
         if j.name.len() <= l.len() {
-            add_var(l, r, i, vars, *options, true, false, false)?;
+            add_var(l, r, i, vars, options, true, false, false)?;
             return Ok(());
         }
     }
-    add_var(l, r, vars.len(), vars, *options, true, false, false)?;
+    add_var(l, r, vars.len(), vars, options, true, false, false)?;
     Ok(())
 }
@@ -1,8 +1,18 @@
 use crate::cas::isolate;
 #[cfg(feature = "fastrand")]
 use crate::complex::{rand_gamma, rand_norm};
+use crate::types::{
+    Complex, Float, Integer,
+    float::{
+        Constant::Pi,
+        Special::{Infinity, Nan},
+    },
+    integer::IsPrime,
+    ops::Pow,
+};
 use crate::{
     complex::{
+        Interpolation,
         LimSide::{Both, Left, Right},
         NumStr,
         NumStr::{
@@ -12,29 +22,25 @@ use crate::{
             NotEqual, Num, Or, Plus, PlusMinus, Range, RightBracket, RightCurlyBracket, Root,
             ShiftLeft, ShiftRight, Tetration, Vector, Xor,
         },
-        about_eq, add, and, area, atan, binomial, change_basis, cofactor, coordinate, cube, cubic,
-        determinant, digamma, div, eigenvalues, eigenvectors, eq, erf, erfc, eta, euleriannumbers,
-        euleriannumbersint, extrema, gamma, gcd, ge, generalized_eigenvectors, gt, hsv2rgb,
-        identity, implies, incomplete_beta, incomplete_gamma, inverse, iter, jcf, kernel, lambertw,
-        length, limit, lower_incomplete_gamma, minors, mul_units, mvec, nand, ne, nor, not,
-        nth_prime, or, pow_nth, prime_factors, quadratic, quartic, range, rcf, recursion,
-        regularized_incomplete_beta, rem, root, rref, shl, shr, slog, slope, solve, sort, sort_mat,
-        sqr, sub, subfactorial, sum, surface_area, taylor, tetration, to, to_cyl, to_polar, trace,
-        transpose, unity, variance, xor, zeta,
+        about_eq, add, and, area, atan, binomial, change_basis, chol, cofactor, cond, contour,
+        coordinate, cosm, cube, cubic, determinant, digamma, div, eigenvalues, eigenvectors, eq,
+        erf, erfc, eta, euleriannumbers, euleriannumbersint, expfit, expm, extrema, fit, fit_mode,
+        funm, gamma, gcd, ge, generalized_eigenvectors, gt, hsv2rgb, identity, implies,
+        incomplete_beta, incomplete_gamma, inverse, iter, jcf, kernel, lambertw, length, limit,
+        linsolve, logfit, logm, lower_incomplete_gamma, lstsq, lu, minimize, minors, mul_units,
+        multiple_integral, mvec, nand, ne, nor, norm2, not, nth_prime, odesolve, or, piecewise,
+        pinv, polyfit, polyroots, pow_nth, prime_factors, qr, quadratic, quartic, range, rank, rcf,
+        recursion, regularized_incomplete_beta, rem, root, roots, rref, schur, shl, shr, sinm,
+        slog, slope, solve, solve_bracket, sort, sort_mat, sqr, sqrtm, sub, subfactorial, sum,
+        surface_area, svd, taylor, tetration, to, to_cyl, to_polar, trace, transpose, unity,
+        variance, xor, zeta,
     },
+    error::Error,
+    expr::is_lazy,
     fraction::{c_to_rational, rationalize},
     misc::do_math_with_var,
     units::{AngleType, Number, Options, Units},
 };
-use rug::{
-    Complex, Float, Integer,
-    float::{
-        Constant::Pi,
-        Special::{Infinity, Nan},
-    },
-    integer::IsPrime,
-    ops::Pow,
-};
 #[cfg(feature = "fastrand")]
 use std::ops::Rem;
 use std::{cmp::Ordering, time::SystemTime};
@@ -42,9 +48,9 @@ pub fn do_math(
     mut function: Vec<NumStr>,
     options: Options,
     mut func_vars: Vec<(String, Vec<NumStr>)>,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     if function.is_empty() {
-        return Err(" ");
+        return Err(Error::Empty);
     }
     compute_funcvars(&mut function, options, &mut func_vars);
     let mut i = 0;
@@ -55,7 +61,7 @@ pub fn do_math(
                 let mut count = 1;
                 while count > 0 {
                     if j >= function.len() {
-                        return Err("curly bracket err");
+                        return Err(Error::Parse("curly bracket err", None));
                     }
                     match &function[j] {
                         LeftCurlyBracket => count += 1,
@@ -65,7 +71,7 @@ pub fn do_math(
                     j += 1;
                 }
                 if i + 1 == j - 1 {
-                    return Err("no interior vector");
+                    return Err(Error::Parse("no interior vector", None));
                 }
                 let mut single = 0;
                 let v = &function[i + 1..j - 1];
@@ -78,7 +84,7 @@ pub fn do_math(
                             match z {
                                 Num(n) => vec.push(*n),
                                 Vector(n) => mat.push(n),
-                                _ => return Err("broken matrix"),
+                                _ => return Err(Error::Parse("broken matrix", None)),
                             }
                             single = f + 1;
                         }
@@ -92,7 +98,7 @@ pub fn do_math(
                     match z {
                         Num(n) => vec.push(*n),
                         Vector(n) => mat.push(n),
-                        _ => return Err("broken matrix"),
+                        _ => return Err(Error::Parse("broken matrix", None)),
                     }
                 }
                 function.drain(i..j);
@@ -100,7 +106,7 @@ pub fn do_math(
                     if vec.is_empty() {
                         function.insert(i, Matrix(mat));
                     } else {
-                        return Err("vector err");
+                        return Err(Error::Parse("vector err", None));
                     }
                 } else {
                     function.insert(i, Vector(vec));
@@ -111,7 +117,7 @@ pub fn do_math(
                 let mut count = 1;
                 while count > 0 {
                     if j >= function.len() {
-                        return Err("round bracket err");
+                        return Err(Error::Parse("round bracket err", None));
                     }
                     match &function[j] {
                         LeftBracket => count += 1,
@@ -121,7 +127,7 @@ pub fn do_math(
                     j += 1;
                 }
                 if i + 1 == j - 1 {
-                    return Err("no interior bracket");
+                    return Err(Error::Parse("no interior bracket", None));
                 }
                 if i != 0 {
                     if let Func(k) = &function[i - 1] {
@@ -136,6 +142,13 @@ pub fn do_math(
                                 | "inter"
                                 | "interpolate"
                                 | "lobf"
+                                | "polyfit"
+                                | "expfit"
+                                | "powfit"
+                                | "logfit"
+                                | "spline"
+                                | "pchip"
+                                | "linterp"
                                 | "plane"
                                 | "lineofbestfit"
                                 | "ψ"
@@ -201,6 +214,14 @@ pub fn do_math(
                                 | "generalized_eigenvectors"
                                 | "change_basis"
                                 | "coordinate"
+                                | "lu"
+                                | "linsolve"
+                                | "lstsq"
+                                | "qr"
+                                | "schur"
+                                | "svd"
+                                | "rank"
+                                | "pinv"
                                 | "mod"
                                 | "covariance"
                                 | "cov"
@@ -288,36 +309,7 @@ pub fn do_math(
                                 function[i - 1] = do_math(v, options, func_vars.clone())?;
                             }
                             continue;
-                        } else if matches!(
-                            k.as_str(),
-                            "sum"
-                                | "area"
-                                | "surfacearea"
-                                | "sarea"
-                                | "solve"
-                                | "∫"
-                                | "length"
-                                | "slope"
-                                | "taylor"
-                                | "iter"
-                                | "extrema"
-                                | "summation"
-                                | "prod"
-                                | "product"
-                                | "Σ"
-                                | "Π"
-                                | "vec"
-                                | "mat"
-                                | "piecewise"
-                                | "pw"
-                                | "D"
-                                | "integrate"
-                                | "arclength"
-                                | "lim"
-                                | "limit"
-                                | "set"
-                                | "isolate"
-                        ) {
+                        } else if is_lazy(k) {
                             i = j - 1;
                             continue;
                         } else {
@@ -360,44 +352,11 @@ pub fn do_math(
         }
     }
     i = 0;
-    let to_deg = match options.angle {
-        AngleType::Degrees => 180 / Complex::with_val(options.prec, Pi),
-        AngleType::Radians => Complex::with_val(options.prec, 1),
-        AngleType::Gradians => 200 / Complex::with_val(options.prec, Pi),
-    };
+    let to_deg = to_deg(options);
     while i < function.len().saturating_sub(1) {
         if let Func(s) = &function[i].clone() {
             if !matches!(s.as_str(), "epoch" | "rnd" | "rand") {
-                if matches!(
-                    s.as_str(),
-                    "sum"
-                        | "area"
-                        | "sarea"
-                        | "surfacearea"
-                        | "solve"
-                        | "∫"
-                        | "length"
-                        | "slope"
-                        | "taylor"
-                        | "iter"
-                        | "extrema"
-                        | "product"
-                        | "prod"
-                        | "summation"
-                        | "Σ"
-                        | "Π"
-                        | "vec"
-                        | "mat"
-                        | "piecewise"
-                        | "pw"
-                        | "D"
-                        | "integrate"
-                        | "arclength"
-                        | "lim"
-                        | "limit"
-                        | "set"
-                        | "isolate"
-                ) {
+                if is_lazy(s) {
                     let mut place = Vec::new();
                     let mut count = 0;
                     let mut count2 = 0;
@@ -494,7 +453,86 @@ pub fn do_math(
                                     )?
                                     .num()?
                                 } else {
-                                    Number::from(Complex::new(options.prec), None)
+                                    Number::from(Complex::new(options.precision()), None)
+                                },
+                            )?;
+                            function.drain(i + 1..=*place.last().unwrap());
+                        }
+                        ("funm", Func(var)) if place.len() == 3 => {
+                            function[i] = funm(
+                                do_math(
+                                    function[place[0] + 1..place[1]].to_vec(),
+                                    options,
+                                    func_vars.clone(),
+                                )?
+                                .mat()?,
+                                function[place[1] + 1..place[2]].to_vec(),
+                                func_vars.clone(),
+                                options,
+                                var.to_string(),
+                            )?;
+                            function.drain(i + 1..=*place.last().unwrap());
+                        }
+                        ("solve", Func(var)) if place.len() == 4 => {
+                            function[i] = solve_bracket(
+                                function[place[0] + 1..place[1]].to_vec(),
+                                func_vars.clone(),
+                                options,
+                                var.to_string(),
+                                do_math(
+                                    function[place[1] + 1..place[2]].to_vec(),
+                                    options,
+                                    func_vars.clone(),
+                                )?
+                                .num()?,
+                                do_math(
+                                    function[place[2] + 1..place[3]].to_vec(),
+                                    options,
+                                    func_vars.clone(),
+                                )?
+                                .num()?,
+                            )?;
+                            function.drain(i + 1..=*place.last().unwrap());
+                        }
+                        ("roots", Func(var)) if place.len() == 4 || place.len() == 5 => {
+                            function[i] = roots(
+                                function[place[0] + 1..place[1]].to_vec(),
+                                func_vars.clone(),
+                                options,
+                                var.to_string(),
+                                do_math(
+                                    function[place[1] + 1..place[2]].to_vec(),
+                                    options,
+                                    func_vars.clone(),
+                                )?
+                                .num()?,
+                                do_math(
+                                    function[place[2] + 1..place[3]].to_vec(),
+                                    options,
+                                    func_vars.clone(),
+                                )?
+                                .num()?,
+                                if place.len() == 5 {
+                                    let n = do_math(
+                                        function[place[3] + 1..place[4]].to_vec(),
+                                        options,
+                                        func_vars.clone(),
+                                    )?
+                                    .num()?
+                                    .number;
+                                    if !n.imag().is_zero()
+                                        || !n.real().is_integer()
+                                        || n.real().is_sign_negative()
+                                        || n.real().is_zero()
+                                    {
+                                        return Err(Error::Domain(
+                                            "roots steps must be a positive integer",
+                                            None,
+                                        ));
+                                    }
+                                    n.real().to_f64() as usize
+                                } else {
+                                    256
                                 },
                             )?;
                             function.drain(i + 1..=*place.last().unwrap());
@@ -513,7 +551,7 @@ pub fn do_math(
                                     )?
                                     .num()?
                                 } else {
-                                    Number::from(Complex::new(options.prec), None)
+                                    Number::from(Complex::new(options.precision()), None)
                                 },
                             )?;
                             function.drain(i + 1..=*place.last().unwrap());
@@ -565,6 +603,295 @@ pub fn do_math(
                             )?;
                             function.drain(i + 1..=*place.last().unwrap());
                         }
+                        ("integrate2" | "integrate3", Func(var))
+                            if matches!(
+                                (s.as_str(), place.len()),
+                                ("integrate2", 7 | 8) | ("integrate3", 10 | 11)
+                            ) =>
+                        {
+                            let n = if s == "integrate2" { 2 } else { 3 };
+                            let mut vars = vec![var.to_string()];
+                            for p in &place[..n - 1] {
+                                if let Func(v) = &function[p + 1] {
+                                    vars.push(v.to_string())
+                                } else {
+                                    return Err(Error::Parse("bad var", None));
+                                }
+                            }
+                            let arg = |k: usize| function[place[k] + 1..place[k + 1]].to_vec();
+                            let bounds: Vec<(String, Vec<NumStr>, Vec<NumStr>)> = vars
+                                .into_iter()
+                                .enumerate()
+                                .map(|(k, v)| (v, arg(3 * n - 2 - 2 * k), arg(3 * n - 1 - 2 * k)))
+                                .collect();
+                            let tol = if place.len() == 3 * n + 2 {
+                                Some(
+                                    do_math(arg(3 * n), options, func_vars.clone())?
+                                        .num()?
+                                        .number
+                                        .real()
+                                        .clone(),
+                                )
+                            } else {
+                                None
+                            };
+                            let (value, err) = multiple_integral(
+                                arg(n - 1),
+                                func_vars.clone(),
+                                options,
+                                &bounds,
+                                tol.as_ref(),
+                            )?;
+                            function[i] = if tol.is_some() {
+                                Vector(vec![value.clone(), Number::from(err.into(), value.units)])
+                            } else {
+                                NumStr::new(value)
+                            };
+                            function.drain(i + 1..=*place.last().unwrap());
+                        }
+                        ("contour", Func(var)) if place.len() == 6 || place.len() == 7 => {
+                            if let Func(var2) = &function[place[0] + 1] {
+                                let (value, err) = contour(
+                                    function[place[1] + 1..place[2]].to_vec(),
+                                    func_vars.clone(),
+                                    options,
+                                    var.to_string(),
+                                    function[place[2] + 1..place[3]].to_vec(),
+                                    var2.to_string(),
+                                    do_math(
+                                        function[place[3] + 1..place[4]].to_vec(),
+                                        options,
+                                        func_vars.clone(),
+                                    )?
+                                    .num()?
+                                    .number,
+                                    do_math(
+                                        function[place[4] + 1..place[5]].to_vec(),
+                                        options,
+                                        func_vars.clone(),
+                                    )?
+                                    .num()?
+                                    .number,
+                                    if place.len() == 7 {
+                                        Some(
+                                            do_math(
+                                                function[place[5] + 1..place[6]].to_vec(),
+                                                options,
+                                                func_vars.clone(),
+                                            )?
+                                            .num()?
+                                            .number
+                                            .real()
+                                            .clone(),
+                                        )
+                                    } else {
+                                        None
+                                    },
+                                )?;
+                                function[i] = if place.len() == 7 {
+                                    Vector(vec![
+                                        value.clone(),
+                                        Number::from(err.into(), value.units),
+                                    ])
+                                } else {
+                                    NumStr::new(value)
+                                };
+                                function.drain(i + 1..=*place.last().unwrap());
+                            } else {
+                                return Err(Error::Parse("bad var", None));
+                            }
+                        }
+                        ("minimize" | "maximize", Func(var)) if place.len() >= 3 => {
+                            let vector = |f: &[NumStr]| -> Result<Vec<Number>, Error> {
+                                match do_math(f.to_vec(), options, func_vars.clone())? {
+                                    Num(n) => Ok(vec![*n]),
+                                    v => v.vec(),
+                                }
+                            };
+                            let k = place.len();
+                            let n = vector(&function[place[k - 2] + 1..place[k - 1]])?.len();
+                            if n == 0 || (n + 2 != k && n + 4 != k) {
+                                return Err(Error::Dimension(
+                                    "minimize needs a starting value for each variable",
+                                    None,
+                                ));
+                            }
+                            let mut vars = vec![var.to_string()];
+                            for j in 0..n - 1 {
+                                match &function[place[j] + 1..place[j + 1]] {
+                                    [Func(v)] => vars.push(v.to_string()),
+                                    _ => {
+                                        return Err(Error::Parse(
+                                            "minimize expects a variable name",
+                                            None,
+                                        ));
+                                    }
+                                }
+                            }
+                            let bounds = if k == n + 4 {
+                                Some((
+                                    vector(&function[place[n + 1] + 1..place[n + 2]])?,
+                                    vector(&function[place[n + 2] + 1..place[n + 3]])?,
+                                ))
+                            } else {
+                                None
+                            };
+                            function[i] = minimize(
+                                function[place[n - 1] + 1..place[n]].to_vec(),
+                                func_vars.clone(),
+                                options,
+                                vars,
+                                vector(&function[place[n] + 1..place[n + 1]])?,
+                                bounds,
+                                s == "maximize",
+                            )?;
+                            function.drain(i + 1..=*place.last().unwrap());
+                        }
+                        ("fit", Func(var)) if place.len() >= 4 => {
+                            let k = place.len();
+                            let arg = |j: usize| -> Result<Vec<Number>, Error> {
+                                Ok(
+                                    match do_math(
+                                        function[place[j] + 1..place[j + 1]].to_vec(),
+                                        options,
+                                        func_vars.clone(),
+                                    )? {
+                                        Num(n) => vec![*n],
+                                        v => v.vec()?,
+                                    },
+                                )
+                            };
+                            //the last argument is either the starting values or a mode after them
+                            let last = arg(k - 2)?;
+                            let (start, mode) = if last.len() + 4 != k && last.len() == 1 {
+                                match arg(k - 3) {
+                                    Ok(start) if start.len() + 5 == k => {
+                                        (start, Some(last[0].number.clone()))
+                                    }
+                                    _ => (last, None),
+                                }
+                            } else {
+                                (last, None)
+                            };
+                            let n = start.len();
+                            if n + 4 + mode.is_some() as usize != k {
+                                return Err(Error::Dimension(
+                                    "fit needs a starting value for each parameter",
+                                    None,
+                                ));
+                            }
+                            let mut params = Vec::new();
+                            for j in 0..n {
+                                match &function[place[j] + 1..place[j + 1]] {
+                                    [Func(v)] => params.push(v.to_string()),
+                                    _ => {
+                                        return Err(Error::Parse(
+                                            "fit expects a parameter name",
+                                            None,
+                                        ));
+                                    }
+                                }
+                            }
+                            let fit = fit(
+                                function[place[n] + 1..place[n + 1]].to_vec(),
+                                func_vars.clone(),
+                                options,
+                                var.to_string(),
+                                params,
+                                &do_math(
+                                    function[place[n + 1] + 1..place[n + 2]].to_vec(),
+                                    options,
+                                    func_vars.clone(),
+                                )?
+                                .mat()?,
+                                start,
+                            )?;
+                            function[i] = fit_mode(fit, mode)?;
+                            function.drain(i + 1..=*place.last().unwrap());
+                        }
+                        ("odesolve", Func(var)) if (6..=9).contains(&place.len()) => {
+                            if let Func(var2) = &function[place[0] + 1] {
+                                function[i] = odesolve(
+                                    function[place[1] + 1..place[2]].to_vec(),
+                                    func_vars.clone(),
+                                    options,
+                                    var.to_string(),
+                                    var2.to_string(),
+                                    do_math(
+                                        function[place[2] + 1..place[3]].to_vec(),
+                                        options,
+                                        func_vars.clone(),
+                                    )?
+                                    .num()?,
+                                    do_math(
+                                        function[place[3] + 1..place[4]].to_vec(),
+                                        options,
+                                        func_vars.clone(),
+                                    )?,
+                                    do_math(
+                                        function[place[4] + 1..place[5]].to_vec(),
+                                        options,
+                                        func_vars.clone(),
+                                    )?
+                                    .num()?
+                                    .number
+                                    .real()
+                                    .clone(),
+                                    if place.len() >= 7 {
+                                        let n = do_math(
+                                            function[place[5] + 1..place[6]].to_vec(),
+                                            options,
+                                            func_vars.clone(),
+                                        )?
+                                        .num()?
+                                        .number;
+                                        if !n.imag().is_zero()
+                                            || !n.real().is_integer()
+                                            || n.real().is_sign_negative()
+                                        {
+                                            return Err(Error::Domain(
+                                                "odesolve steps must be a natural number",
+                                                None,
+                                            ));
+                                        }
+                                        n.real()
+                                            .to_integer()
+                                            .unwrap_or_default()
+                                            .to_usize()
+                                            .unwrap_or_default()
+                                    } else {
+                                        0
+                                    },
+                                    place.len() >= 8
+                                        && !do_math(
+                                            function[place[6] + 1..place[7]].to_vec(),
+                                            options,
+                                            func_vars.clone(),
+                                        )?
+                                        .num()?
+                                        .number
+                                        .is_zero(),
+                                    if place.len() == 9 {
+                                        Some(
+                                            do_math(
+                                                function[place[7] + 1..place[8]].to_vec(),
+                                                options,
+                                                func_vars.clone(),
+                                            )?
+                                            .num()?
+                                            .number
+                                            .real()
+                                            .clone(),
+                                        )
+                                    } else {
+                                        None
+                                    },
+                                )?;
+                                function.drain(i + 1..=*place.last().unwrap());
+                            } else {
+                                return Err(Error::Parse("bad var", None));
+                            }
+                        }
                         ("surfacearea" | "sarea", Func(var)) if place.len() == 7 => {
                             if let Func(var2) = &function[place[0] + 1] {
                                 function[i] = NumStr::new(surface_area(
@@ -591,7 +918,7 @@ pub fn do_math(
                                 )?);
                                 function.drain(i + 1..=*place.last().unwrap());
                             } else {
-                                return Err("bad var");
+                                return Err(Error::Parse("bad var", None));
                             }
                         }
                         ("length" | "arclength", Func(var)) if place.len() == 4 => {
@@ -617,8 +944,11 @@ pub fn do_math(
                             function.drain(i + 1..=*place.last().unwrap());
                         }
                         ("∫" | "area" | "integrate", Func(var))
-                            if place.len() == 4 || place.len() == 5 || place.len() == 6 =>
+                            if (4..=7).contains(&place.len())
+                                && (s != "integrate" || place.len() <= 5) =>
                         {
+                            //integrate(x,f,a,b,tol) is area(x,f,a,b,1,1,tol)
+                            let integrate = s == "integrate";
                             function[i] = area(
                                 function[place[0] + 1..place[1]].to_vec(),
                                 func_vars.clone(),
@@ -637,7 +967,7 @@ pub fn do_math(
                                     func_vars.clone(),
                                 )?
                                 .num()?,
-                                if place.len() == 5 {
+                                if place.len() >= 5 && !integrate {
                                     do_math(
                                         function[place[3] + 1..place[4]].to_vec(),
                                         options,
@@ -646,18 +976,65 @@ pub fn do_math(
                                     .num()?
                                     .number
                                 } else {
-                                    Complex::with_val(options.prec, 1)
+                                    Complex::with_val(options.precision(), 1)
+                                },
+                                match place.len() {
+                                    6 => false,
+                                    7 => !do_math(
+                                        function[place[4] + 1..place[5]].to_vec(),
+                                        options,
+                                        func_vars.clone(),
+                                    )?
+                                    .num()?
+                                    .number
+                                    .is_zero(),
+                                    _ => true,
+                                },
+                                if place.len() == 7 || (integrate && place.len() == 5) {
+                                    Some(
+                                        do_math(
+                                            function[place[place.len() - 2] + 1
+                                                ..place[place.len() - 1]]
+                                                .to_vec(),
+                                            options,
+                                            func_vars.clone(),
+                                        )?
+                                        .num()?
+                                        .number
+                                        .real()
+                                        .clone(),
+                                    )
+                                } else {
+                                    None
                                 },
-                                place.len() != 6,
                             )?;
                             function.drain(i + 1..=*place.last().unwrap());
                         }
-                        ("isolate", Func(var)) if place.len() == 2 => {
+                        ("isolate", Func(var)) if place.len() == 2 || place.len() == 3 => {
+                            let n = if place.len() == 3 {
+                                let n = do_math(
+                                    function[place[1] + 1..place[2]].to_vec(),
+                                    options,
+                                    func_vars.clone(),
+                                )?
+                                .num()?
+                                .number;
+                                if !n.imag().is_zero() || !n.real().is_integer() {
+                                    return Err(Error::Domain(
+                                        "isolate branch must be an integer",
+                                        None,
+                                    ));
+                                }
+                                n
+                            } else {
+                                Complex::new(options.precision())
+                            };
                             function[i] = isolate(
                                 &function[place[0] + 1..place[1]],
                                 func_vars.clone(),
                                 options,
                                 var.to_string(),
+                                n,
                             )?;
                             function.drain(i + 1..=*place.last().unwrap());
                         }
@@ -776,7 +1153,7 @@ pub fn do_math(
                                 n
                             } else {
                                 NumStr::new(Number::from(
-                                    Complex::with_val(options.prec, Nan),
+                                    Complex::with_val(options.precision(), Nan),
                                     None,
                                 ))
                             };
@@ -801,12 +1178,12 @@ pub fn do_math(
                             .num()?
                             .number;
                             if !start.imag().is_zero() || !end.imag().is_zero() {
-                                return Err("imag start/end");
+                                return Err(Error::Domain("imag start/end", None));
                             }
                             if !start.real().clone().fract().is_zero()
                                 || !end.real().clone().fract().is_zero()
                             {
-                                return Err("fractional start/end");
+                                return Err(Error::Domain("fractional start/end", None));
                             }
                             let start = start.real();
                             let end = end.real();
@@ -851,20 +1228,21 @@ pub fn do_math(
                             } {
                                 Ok(Num(a)) => Num(a),
                                 Ok(Vector(a)) => NumStr::new(Number::from(
-                                    a.iter().fold(Complex::new(options.prec), |sum, val| {
-                                        sum + val.number.clone()
-                                    }),
+                                    a.iter()
+                                        .fold(Complex::new(options.precision()), |sum, val| {
+                                            sum + val.number.clone()
+                                        }),
                                     None,
                                 )),
                                 Ok(Matrix(a)) => NumStr::new(Number::from(
                                     a.iter()
                                         .flatten()
-                                        .fold(Complex::new(options.prec), |sum, val| {
+                                        .fold(Complex::new(options.precision()), |sum, val| {
                                             sum + val.number.clone()
                                         }),
                                     None,
                                 )),
-                                _ => return Err("sum err"),
+                                _ => return Err(Error::Unsupported("sum err", None)),
                             };
                         }
                         ("product" | "prod" | "Π", _) if place.len() <= 1 => {
@@ -879,2895 +1257,3074 @@ pub fn do_math(
                             } {
                                 Ok(Num(a)) => Num(a),
                                 Ok(Vector(a)) => NumStr::new(Number::from(
-                                    a.iter()
-                                        .fold(Complex::with_val(options.prec, 1), |sum, val| {
-                                            sum * val.number.clone()
-                                        }),
+                                    a.iter().fold(
+                                        Complex::with_val(options.precision(), 1),
+                                        |sum, val| sum * val.number.clone(),
+                                    ),
                                     None,
                                 )),
                                 Ok(Matrix(a)) => NumStr::new(Number::from(
-                                    a.iter()
-                                        .flatten()
-                                        .fold(Complex::with_val(options.prec, 1), |sum, val| {
-                                            sum * val.number.clone()
-                                        }),
+                                    a.iter().flatten().fold(
+                                        Complex::with_val(options.precision(), 1),
+                                        |sum, val| sum * val.number.clone(),
+                                    ),
                                     None,
                                 )),
-                                _ => return Err("prod err"),
+                                _ => return Err(Error::Unsupported("prod err", None)),
                             };
                         }
-                        (_, _) => return Err("arg/var err with sum/prod/vec/slope or similar"),
+                        (_, _) => {
+                            return Err(Error::Arity(
+                                "arg/var err with sum/prod/vec/slope or similar",
+                                None,
+                            ));
+                        }
                     }
                 } else {
-                    let arg = function.remove(i + 1);
-                    function[i] = match arg.clone() {
-                        Matrix(a) => match s.as_str() {
-                            "plane" => {
-                                if a.len() != 3 || a.iter().any(|a| a.len() != 3) {
-                                    return Err("dimensions too high");
-                                }
-                                let x1 = &a[0][0].number;
-                                let y1 = &a[0][1].number;
-                                let z1 = &a[0][2].number;
-                                let x2 = &a[1][0].number;
-                                let y2 = &a[1][1].number;
-                                let z2 = &a[1][2].number;
-                                let x3 = &a[2][0].number;
-                                let y3 = &a[2][1].number;
-                                let z3 = &a[2][2].number;
-                                let t1 = z2 - z3.clone() * y2 / y3;
-                                let t2 = x2 - x3.clone() * y2 / y3;
-                                let t3 = t1 / t2.clone();
-                                let c1 = z1 - x1 * t3.clone() - y1.clone() / y3 * (z3 - x3 * t3);
-                                let c2 = 1 - x1 / t2.clone() + x1.clone() * y2 / y3 / t2.clone()
-                                    - y1.clone() / y3
-                                    + x3.clone() * y1 / y3 / t2.clone()
-                                    - x3.clone() * y1 * y2 / y3 / y3 / t2.clone();
-                                let c: Complex = c1 / c2;
-                                let b = (z3
-                                    - c.clone()
-                                    - x3 * (z2 - c.clone() - y2.clone() / y3 * (z3 - c.clone()))
-                                        / t2)
-                                    / y3;
-                                let a = (z2 - c.clone() - b.clone() * y2) / x2;
-                                if function.len() > i + 2 {
-                                    let x = function.remove(i + 1).num()?.number;
-                                    let y = function.remove(i + 1).num()?.number;
-                                    NumStr::new(Number::from(a * x + b * y + c, None))
-                                } else {
-                                    Vector(vec![
-                                        Number::from(a, None),
-                                        Number::from(b, None),
-                                        Number::from(c, None),
-                                    ])
-                                }
-                            }
-                            "lobf" | "lineofbestfit" => {
-                                if a.is_empty() || a.iter().any(|a| a.len() != 2) {
-                                    return Err("dimensions too high");
-                                }
-                                let mut xsum = Complex::new(options.prec);
-                                let mut ysum = Complex::new(options.prec);
-                                let mut xxsum = Complex::new(options.prec);
-                                let mut xysum = Complex::new(options.prec);
-                                for row in &a {
-                                    let x = row[0].number.clone();
-                                    let y = row[1].number.clone();
-                                    xsum += x.clone();
-                                    ysum += y.clone();
-                                    xxsum += sqr(x.clone());
-                                    xysum += x * y;
-                                }
-                                let m: Complex = (a.len() * xysum - xsum.clone() * ysum.clone())
-                                    / (a.len() * xxsum - sqr(xsum.clone()));
-                                let b = (ysum - m.clone() * xsum) / a.len();
-                                if function.len() > i + 1 {
-                                    let x = function.remove(i + 1).num()?.number;
-                                    NumStr::new(Number::from(m * x + b, a[0][1].units))
-                                } else {
-                                    Vector(vec![Number::from(m, None), Number::from(b, None)])
-                                }
-                            }
-                            "inter" | "interpolate" => {
-                                if function.len() > i + 1 {
-                                    if !a.is_empty() && a.iter().all(|a| a.len() == 2) {
-                                        let x = function.remove(i + 1).num()?.number;
-                                        let mut sum = Complex::new(options.prec);
-                                        for i in 0..a.len() {
-                                            let mut prod = Complex::with_val(options.prec, 1);
-                                            for j in 0..a.len() {
-                                                if j != i {
-                                                    prod *= (x.clone() - a[j][0].number.clone())
-                                                        / (a[i][0].number.clone()
-                                                            - a[j][0].number.clone())
-                                                }
-                                            }
-                                            sum += prod * a[i][1].number.clone()
-                                        }
-                                        NumStr::new(Number::from(sum, a[0][1].units))
-                                    } else {
-                                        return Err("dimensions too high");
-                                    }
-                                } else {
-                                    return Err("no x value given");
-                                }
-                            }
-                            "sort" => Matrix(sort_mat(a, options.prec)),
-                            "max" => {
-                                let mut vec = Vec::with_capacity(a.len().max(1));
-                                for j in a {
-                                    let mut max = j[0].clone();
-                                    for i in j {
-                                        if i.number.real() > max.number.real() {
-                                            max = i
-                                        }
-                                    }
-                                    vec.push(max)
-                                }
-                                Vector(vec)
-                            }
-                            "min" => {
-                                let mut vec = Vec::with_capacity(a.len().max(1));
-                                for j in a {
-                                    let mut min = j[0].clone();
-                                    for i in j {
-                                        if i.number.real() < min.number.real() {
-                                            min = i
-                                        }
-                                    }
-                                    vec.push(min)
-                                }
-                                Vector(vec)
-                            }
-                            "flatten" => Vector(a.into_iter().flatten().collect::<Vec<Number>>()),
-                            "cofactor" | "cofactors" | "cof" => Matrix(cofactor(&a)?),
-                            "minor" | "minors" => Matrix(minors(&a)?),
-                            "adjugate" | "adj" => Matrix(transpose(&cofactor(&a)?)),
-                            "inverse" | "inv" => Matrix(inverse(&a)?),
-                            "transpose" | "trans" => Matrix(transpose(&a)),
-                            "len" => NumStr::new(Number::from(
-                                Complex::with_val(options.prec, a.len()),
-                                None,
-                            )),
-                            "wid" | "width" => NumStr::new(Number::from(
-                                Complex::with_val(options.prec, a[0].len()),
-                                None,
-                            )),
-                            "tr" | "trace" => NumStr::new(trace(&a)),
-                            "det" | "norm" | "determinant" => NumStr::new(determinant(&a)?),
-                            "part" => {
-                                if function.len() > i + 2 {
-                                    match (function.remove(i + 1), function.remove(i + 1)) {
-                                        (Num(b), Num(c)) => {
-                                            let b = b.number;
-                                            let c = c.number;
-                                            let n1 =
-                                                b.clone().real().to_integer().unwrap_or_default();
-                                            let getcol = n1 == -1;
-                                            let n1 = n1.to_usize().unwrap_or_default();
-                                            let n2 = c
-                                                .clone()
-                                                .real()
-                                                .to_integer()
-                                                .unwrap_or_default()
-                                                .to_usize()
-                                                .unwrap_or_default();
-                                            if getcol {
-                                                if a.iter().all(|a| n2 < a.len()) {
-                                                    Vector(
-                                                        a.iter()
-                                                            .map(|a| a[n2].clone())
-                                                            .collect::<Vec<Number>>(),
-                                                    )
-                                                } else {
-                                                    return Err("out of range");
-                                                }
-                                            } else if n1 < a.len() && n2 < a[n1].len() {
-                                                NumStr::new(a[n1][n2].clone())
-                                            } else {
-                                                return Err("not in matrix");
-                                            }
-                                        }
-                                        (Num(b), Vector(c)) => {
-                                            let b = b.number;
-                                            let n1 =
-                                                b.clone().real().to_integer().unwrap_or_default();
-                                            let getcol = n1 == -1;
-                                            let n1 = n1.to_usize().unwrap_or_default();
-                                            if getcol {
-                                                let mut mat = Vec::with_capacity(c.len().max(1));
-                                                for n in c {
-                                                    let n = n
-                                                        .number
-                                                        .clone()
-                                                        .real()
-                                                        .to_integer()
-                                                        .unwrap_or_default()
-                                                        .to_usize()
-                                                        .unwrap_or_default();
-                                                    if a.iter().all(|a| n < a.len()) {
-                                                        mat.push(
-                                                            a.iter()
-                                                                .map(|a| a[n].clone())
-                                                                .collect::<Vec<Number>>(),
-                                                        )
-                                                    } else {
-                                                        return Err("out of range");
-                                                    }
-                                                }
-                                                Matrix(transpose(&mat))
-                                            } else {
-                                                let mut vec = Vec::with_capacity(c.len().max(1));
-                                                for n in c {
-                                                    let n2 = n
-                                                        .number
-                                                        .clone()
-                                                        .real()
-                                                        .to_integer()
-                                                        .unwrap_or_default()
-                                                        .to_usize()
-                                                        .unwrap_or_default();
-                                                    if n1 < a.len() && n2 < a[n1].len() {
-                                                        vec.push(a[n1][n2].clone())
-                                                    } else {
-                                                        return Err("not in matrix");
-                                                    }
-                                                }
-                                                Vector(vec)
-                                            }
-                                        }
-                                        (Vector(b), Num(c)) => {
-                                            let c = c.number;
-                                            let n2 = c
-                                                .clone()
-                                                .real()
-                                                .to_integer()
-                                                .unwrap_or_default()
-                                                .to_usize()
-                                                .unwrap_or_default();
-                                            let mut vec = Vec::with_capacity(b.len().max(1));
-                                            for n in b {
-                                                let n1 = n
-                                                    .number
-                                                    .clone()
-                                                    .real()
-                                                    .to_integer()
-                                                    .unwrap_or_default()
-                                                    .to_usize()
-                                                    .unwrap_or_default();
-                                                if n1 < a.len() && n2 < a[n1].len() {
-                                                    vec.push(a[n1][n2].clone())
-                                                } else {
-                                                    return Err("not in matrix");
-                                                }
-                                            }
-                                            Vector(vec)
-                                        }
-                                        (Vector(b), Vector(c)) => {
-                                            let mut mat = Vec::with_capacity(b.len().max(1));
-                                            for g in b {
-                                                let mut vec = Vec::with_capacity(c.len().max(1));
-                                                let n1 = g
-                                                    .number
-                                                    .clone()
-                                                    .real()
-                                                    .to_integer()
-                                                    .unwrap_or_default()
-                                                    .to_usize()
-                                                    .unwrap_or_default();
-                                                for n in c.clone() {
-                                                    let n2 = n
-                                                        .number
-                                                        .clone()
-                                                        .real()
-                                                        .to_integer()
-                                                        .unwrap_or_default()
-                                                        .to_usize()
-                                                        .unwrap_or_default();
-                                                    if n1 < a.len() && n2 < a[n1].len() {
-                                                        vec.push(a[n1][n2].clone())
-                                                    } else {
-                                                        return Err("not in matrix");
-                                                    }
-                                                }
-                                                mat.push(vec);
-                                            }
-                                            Matrix(mat)
-                                        }
-                                        _ => return Err("wrong part num"),
-                                    }
-                                } else if function.len() > i + 1 {
-                                    match function.remove(i + 1) {
-                                        Num(b) => {
-                                            let b = b.number;
-                                            let n = b
-                                                .clone()
-                                                .real()
-                                                .to_integer()
-                                                .unwrap_or_default()
-                                                .to_usize()
-                                                .unwrap_or_default();
-                                            if n < a.len() {
-                                                Vector(a[n].clone())
-                                            } else {
-                                                return Err("out of range");
-                                            }
-                                        }
-                                        Vector(b) => {
-                                            let mut vec = Vec::with_capacity(b.len().max(1));
-                                            for i in b {
-                                                let n = i
-                                                    .number
-                                                    .clone()
-                                                    .real()
-                                                    .to_integer()
-                                                    .unwrap_or_default()
-                                                    .to_usize()
-                                                    .unwrap_or_default();
-                                                if n < a.len() {
-                                                    vec.push(a[n].clone());
-                                                } else {
-                                                    return Err("out of range");
-                                                }
-                                            }
-                                            Matrix(vec)
-                                        }
-                                        _ => return Err("non num/vec"),
-                                    }
-                                } else {
-                                    return Err("no arg");
-                                }
-                            }
-                            "weighted_mean" => {
-                                if a.iter().any(|a| a.len() != 2) {
-                                    return Err("bad data");
-                                }
-                                NumStr::new(Number::from(
-                                    a.iter().fold(Complex::new(options.prec), |sum, val| {
-                                        sum + val[0].number.clone() * val[1].number.clone()
-                                    }) / a.iter().fold(Complex::new(options.prec), |sum, val| {
-                                        sum + val[1].number.clone()
-                                    }),
-                                    a[0][0].units,
-                                ))
-                            }
-                            "mean" | "μ" => NumStr::new(Number::from(
-                                a.iter()
-                                    .flatten()
-                                    .fold(Complex::new(options.prec), |sum, val| {
-                                        sum + val.number.clone()
-                                    })
-                                    / a.iter().fold(0, |sum, a| sum + a.len()),
-                                a[0][0].units,
-                            )),
-                            "mode" => {
-                                let mut most = (Vec::with_capacity(a.len().max(1)), 0);
-                                for i in a.iter().flatten() {
-                                    let mut count = 0;
-                                    for j in a.iter().flatten() {
-                                        if i == j {
-                                            count += 1;
-                                        }
-                                    }
-                                    if count > most.1 {
-                                        most.0.clear();
-                                        most.0.push(i.clone());
-                                        most.1 = count;
-                                    }
-                                    if count == most.1 && !most.0.iter().any(|j| i == j) {
-                                        most.0.push(i.clone())
-                                    }
-                                }
-                                if most.0.len() == 1 {
-                                    NumStr::new(most.0[0].clone())
-                                } else {
-                                    Vector(most.0)
-                                }
-                            }
-                            "median" => {
-                                let a = sort(a.iter().flatten().cloned().collect::<Vec<Number>>());
-                                if a.len() % 2 == 0 {
-                                    Vector(vec![a[a.len() / 2 - 1].clone(), a[a.len() / 2].clone()])
-                                } else {
-                                    NumStr::new(a[a.len() / 2].clone())
-                                }
-                            }
-                            "all" => {
-                                let mut res = true;
-                                for a in a.iter().flatten() {
-                                    if !(a.number.imag().is_zero() && a.number.real() == &1) {
-                                        res = false
-                                    }
-                                }
-                                NumStr::new(Number::from(
-                                    Complex::with_val(options.prec, res as u8),
-                                    None,
-                                ))
-                            }
-                            "any" => {
-                                let mut res = false;
-                                for a in a.iter().flatten() {
-                                    if a.number.imag().is_zero() && a.number.real() == &1 {
-                                        res = true
-                                    }
-                                }
-                                NumStr::new(Number::from(
-                                    Complex::with_val(options.prec, res as u8),
-                                    None,
-                                ))
-                            }
-                            "eigenvalues" => {
-                                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
-                                    function.remove(i + 1);
-                                    eigenvalues(&a, true)?
-                                } else {
-                                    eigenvalues(&a, false)?
-                                }
-                            }
-                            "eigenvectors" => {
-                                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
-                                    function.remove(i + 1);
-                                    eigenvectors(&a, true)?
-                                } else {
-                                    eigenvectors(&a, false)?
-                                }
-                            }
-                            "rref" => Matrix(rref(a)?),
-                            "ker" => Matrix(kernel(a)?),
-                            "ran" => Matrix(range(a)?),
-                            "generalized_eigenvectors" => {
-                                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
-                                    function.remove(i + 1);
-                                    generalized_eigenvectors(&a, true)?
-                                } else {
-                                    generalized_eigenvectors(&a, false)?
-                                }
-                            }
-                            "rcf" => rcf(a)?,
-                            "jcf" => jcf(a)?,
-                            "change_basis" => {
-                                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
-                                    let beta = function.remove(i + 1).mat()?;
-                                    if function.len() > i + 1
-                                        && !matches!(&function[i + 1], Func(_))
-                                    {
-                                        change_basis(a, &beta, &function.remove(i + 1).mat()?)?
-                                    } else {
-                                        let i = identity(a.len(), a[0][0].number.prec().0);
-                                        change_basis(a, &i, &beta)?
-                                    }
-                                } else {
-                                    return Err("missing arg");
-                                }
-                            }
-                            "coordinate" => {
-                                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
-                                    let v = function.remove(i + 1).vec()?;
-                                    coordinate(v, a)?
-                                } else {
-                                    return Err("missing arg");
-                                }
-                            }
-                            "rank" => NumStr::new(Number::from(
-                                Complex::with_val(a[0][0].number.prec(), range(a)?.len()),
-                                None,
-                            )),
-                            "null" => NumStr::new(Number::from(
-                                Complex::with_val(a[0][0].number.prec(), kernel(a)?.len()),
-                                None,
-                            )),
-                            "to_list" => {
-                                let mut vec = Vec::new();
-                                for a in a {
-                                    if a.len() != 2 {
-                                        return Err("bad list");
-                                    }
-                                    for _ in 0..a[1]
-                                        .number
-                                        .real()
-                                        .to_integer()
-                                        .unwrap_or_default()
-                                        .to_usize()
-                                        .unwrap_or_default()
-                                    {
-                                        vec.push(a[0].clone())
-                                    }
-                                }
-                                if vec.is_empty() {
-                                    return Err("bad list");
-                                }
-                                Vector(sort(vec))
-                            }
-                            "to_freq" => {
-                                if a.is_empty() {
-                                    return Err("bad list");
-                                }
-                                let mut a = sort(a.into_iter().flatten().collect::<Vec<Number>>());
-                                let mut last = a[0].clone();
-                                let mut count = 1;
-                                a.remove(0);
-                                let mut mat = Vec::new();
-                                for a in a {
-                                    if a != last {
-                                        mat.push(vec![
-                                            last.clone(),
-                                            Number::from(
-                                                Complex::with_val(options.prec, count),
-                                                None,
-                                            ),
-                                        ]);
-                                        last = a;
-                                        count = 0;
-                                    }
-                                    count += 1;
-                                }
-                                mat.push(vec![
-                                    last.clone(),
-                                    Number::from(Complex::with_val(options.prec, count), None),
-                                ]);
-                                Matrix(mat)
-                            }
-                            #[cfg(feature = "fastrand")]
-                            "rand_weighted" => {
-                                if a.iter().any(|a| a.len() != 2) {
-                                    return Err("bad data");
-                                }
-                                let mut sum = Integer::new();
-                                for i in &a {
-                                    sum += i[1].number.real().to_integer().unwrap_or_default();
-                                }
-                                let n = sum.to_u128().unwrap_or_default();
-                                if n == 0 {
-                                    return Err("bad data");
-                                }
-                                let max = u128::MAX - u128::MAX.rem(n);
-                                let mut rnd = u128::MAX;
-                                while rnd >= max {
-                                    rnd = fastrand::u128(..);
-                                }
-                                rnd = rnd.rem(n) + 1;
-                                let mut num =
-                                    Number::from(Complex::with_val(options.prec, Nan), None);
-                                for i in &a {
-                                    rnd = rnd.saturating_sub(
-                                        i[1].number
-                                            .real()
-                                            .to_integer()
-                                            .unwrap_or_default()
-                                            .to_u128()
-                                            .unwrap_or_default(),
-                                    );
-                                    if rnd == 0 {
-                                        num = i[0].clone();
-                                        break;
-                                    }
-                                }
-                                NumStr::new(num)
-                            }
-                            #[cfg(feature = "fastrand")]
-                            "roll" => {
-                                let mut sum: Integer = Integer::new();
-                                for i in a {
-                                    if i.len() != 2 {
-                                        return Err("bad dice data");
-                                    }
-                                    let a = i[0].number.real().to_integer().unwrap_or_default();
-                                    if a > u128::MAX || a == 0 {
-                                        return Err("dice too large or bad dice data");
-                                    }
-                                    let n = a.to_u128().unwrap_or_default();
-                                    let max = u128::MAX - u128::MAX.rem(n);
-                                    let end = i[1]
-                                        .number
-                                        .real()
-                                        .to_integer()
-                                        .unwrap_or_default()
-                                        .to_u128()
-                                        .unwrap_or_default();
-                                    let mut i = 0;
-                                    while i < end {
-                                        let rnd = fastrand::u128(..);
-                                        if rnd < max {
-                                            sum += rnd.rem(n) + 1;
-                                            i += 1;
-                                        }
-                                    }
-                                }
-                                NumStr::new(Number::from(
-                                    Complex::with_val(options.prec, sum),
-                                    None,
-                                ))
-                            }
-                            "dice" => {
-                                let mut faces = Vec::new();
-                                for a in a {
-                                    if a.len() != 2 {
-                                        return Err("bad list");
-                                    }
-                                    for _ in 0..a[1]
-                                        .number
-                                        .real()
-                                        .to_integer()
-                                        .unwrap_or_default()
-                                        .to_usize()
-                                        .unwrap_or_default()
-                                    {
-                                        faces.push(
-                                            a[0].number
-                                                .real()
-                                                .to_integer()
-                                                .unwrap_or_default()
-                                                .to_usize()
-                                                .unwrap_or_default(),
-                                        )
-                                    }
-                                }
-                                if faces.iter().any(|c| c == &0) {
-                                    return Err("bad face value");
-                                }
-                                if faces.is_empty() {
-                                    Vector(vec![Number::from(
-                                        Complex::with_val(options.prec, 1),
-                                        None,
-                                    )])
-                                } else if faces.len() == 1 {
-                                    Vector(vec![
-                                        Number::from(
-                                            Complex::with_val(options.prec, 1),
-                                            None
-                                        );
-                                        faces[0]
-                                    ])
-                                } else {
-                                    let mut last = vec![Integer::from(1); faces[0]];
-                                    let mut current = last.clone();
-                                    for i in 1..faces.len() {
-                                        current = Vec::new();
-                                        for p in 0..=faces[0..=i].iter().sum::<usize>() - i - 1 {
-                                            let value = last[(p + 1).saturating_sub(faces[i])
-                                                ..=p.min(faces[0..i].iter().sum::<usize>() - i)]
-                                                .iter()
-                                                .sum::<Integer>();
-                                            current.push(value)
-                                        }
-                                        last.clone_from(&current)
-                                    }
-                                    Vector(
-                                        current
-                                            .iter()
-                                            .map(|a| {
-                                                Number::from(
-                                                    Complex::with_val(options.prec, a),
-                                                    None,
-                                                )
-                                            })
-                                            .collect::<Vec<Number>>(),
-                                    )
-                                }
-                            }
-                            "poly" | "polynomial" => {
-                                if i + 1 < function.len() {
-                                    if a.is_empty() {
-                                        NumStr::new(Number::from(Complex::new(options.prec), None))
-                                    } else {
-                                        let x = function.remove(i + 1).num()?;
-                                        let mut sum =
-                                            vec![
-                                                Number::from(Complex::new(options.prec), None);
-                                                a.len()
-                                            ];
-                                        for (i, v) in transpose(&a).iter().rev().enumerate() {
-                                            for (s, a) in sum.iter_mut().zip(v.iter().map(|a| {
-                                                Number::from(
-                                                    a.number.clone() * x.number.clone().pow(i),
-                                                    mul_units(
-                                                        a.units,
-                                                        Some(
-                                                            x.units
-                                                                .unwrap_or_default()
-                                                                .pow(i as f64),
-                                                        ),
-                                                    ),
-                                                )
-                                            })) {
-                                                *s = add(s, &a)
-                                            }
-                                        }
-                                        Vector(sum)
-                                    }
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "norm_combine" => {
-                                if a.is_empty() {
-                                    return Err("empty vec");
-                                }
-                                let mut mu = Complex::new(options.prec);
-                                let mut var = Complex::new(options.prec);
-                                let mut ws = Complex::new(options.prec);
-                                for d in &a {
-                                    if d.len() < 2 {
-                                        return Err("not enough data");
-                                    }
-                                    if d.len() == 2 {
-                                        mu += d[0].number.clone();
-                                        var += d[1].number.clone().pow(2);
-                                        ws += 1;
-                                    } else {
-                                        mu += d[0].number.clone() * d[2].number.clone();
-                                        var += d[1].number.clone().pow(2) * d[2].number.clone();
-                                        ws += d[2].number.clone();
-                                    }
-                                }
-                                mu /= ws.clone();
-                                for d in &a {
-                                    if d.len() == 2 {
-                                        var += (mu.clone() - d[0].number.clone()).pow(2);
-                                    } else {
-                                        var += (mu.clone() - d[0].number.clone()).pow(2)
-                                            * d[2].number.clone();
-                                    }
-                                }
-                                var /= ws;
-                                Vector(vec![Number::from(mu, None), Number::from(var.sqrt(), None)])
-                            }
-                            _ => do_functions(arg, options, &mut function, i, &to_deg, s)?,
-                        },
-                        Vector(a) => match s.as_str() {
-                            "transpose" | "trans" => Matrix(transpose(&[a])),
-                            "to_list" => {
-                                let mut vec = Vec::new();
-                                for (i, a) in a.iter().enumerate() {
-                                    let num =
-                                        Number::from(Complex::with_val(options.prec, i + 1), None);
-                                    for _ in 1..=a
-                                        .number
-                                        .real()
-                                        .to_integer()
-                                        .unwrap_or_default()
-                                        .to_usize()
-                                        .unwrap_or_default()
-                                    {
-                                        vec.push(num.clone())
-                                    }
-                                }
-                                if vec.is_empty() {
-                                    return Err("bad list");
-                                }
-                                Vector(sort(vec))
-                            }
-                            #[cfg(feature = "fastrand")]
-                            "roll" => {
-                                let mut sum: Integer = Integer::new();
-                                let mut i = 0;
-                                while i < a.len() {
-                                    let a = a[i].number.real().to_integer().unwrap_or_default();
-                                    if a > u128::MAX as f64 || a == 0 {
-                                        return Err("dice too large or bad dice data");
-                                    }
-                                    let n = a.to_u128().unwrap_or_default();
-                                    let max = u128::MAX - u128::MAX.rem(n);
-                                    let rnd = fastrand::u128(..);
-                                    if rnd < max {
-                                        sum += rnd.rem(n) + 1;
-                                        i += 1;
-                                    }
-                                }
-                                NumStr::new(Number::from(
-                                    Complex::with_val(options.prec, sum),
-                                    None,
-                                ))
-                            }
-                            "dice" => {
-                                let faces = a
-                                    .iter()
-                                    .map(|c| {
-                                        c.number
-                                            .real()
-                                            .to_integer()
-                                            .unwrap_or_default()
-                                            .to_usize()
-                                            .unwrap_or_default()
-                                    })
-                                    .collect::<Vec<usize>>();
-                                if faces.iter().any(|c| c == &0) {
-                                    return Err("bad face value");
-                                }
-                                let mut last = vec![Integer::from(1); faces[0]];
-                                if a.is_empty() {
-                                    Vector(vec![Number::from(
-                                        Complex::with_val(options.prec, 1),
-                                        None,
-                                    )])
-                                } else if faces.len() == 1 {
-                                    Vector(
-                                        last.iter()
-                                            .map(|a| {
-                                                Number::from(
-                                                    Complex::with_val(options.prec, a),
-                                                    None,
-                                                )
-                                            })
-                                            .collect::<Vec<Number>>(),
-                                    )
-                                } else {
-                                    let mut current = Vec::new();
-                                    for i in 1..faces.len() {
-                                        current = Vec::new();
-                                        for p in 0..=faces[0..=i].iter().sum::<usize>() - i - 1 {
-                                            let value = last[(p + 1).saturating_sub(faces[i])
-                                                ..=p.min(faces[0..i].iter().sum::<usize>() - i)]
-                                                .iter()
-                                                .sum::<Integer>();
-                                            current.push(value)
-                                        }
-                                        last.clone_from(&current);
-                                    }
-                                    Vector(
-                                        current
-                                            .iter()
-                                            .map(|a| {
-                                                Number::from(
-                                                    Complex::with_val(options.prec, a),
-                                                    None,
-                                                )
-                                            })
-                                            .collect::<Vec<Number>>(),
-                                    )
-                                }
-                            }
-                            "quartiles" => {
-                                if a.len() < 2 {
-                                    return Err("not enough data");
-                                }
-                                let units = a[0].units;
-                                let a = sort(a);
-                                let half1 = &a[0..a.len() / 2];
-                                let half2 = if a.len() % 2 == 0 {
-                                    &a[a.len() / 2..a.len()]
-                                } else {
-                                    &a[a.len() / 2 + 1..a.len()]
-                                };
-                                if half1.len() % 2 == 0 {
-                                    Vector(vec![
-                                        Number::from(
-                                            (half1[half1.len() / 2 - 1].number.clone()
-                                                + half1[half1.len() / 2].number.clone())
-                                                / 2,
-                                            units,
-                                        ),
-                                        Number::from(
-                                            if a.len() % 2 == 0 {
-                                                (half1[half1.len().saturating_sub(1)]
-                                                    .number
-                                                    .clone()
-                                                    + half2[0].number.clone())
-                                                    / 2
-                                            } else {
-                                                a[a.len() / 2].number.clone()
-                                            },
-                                            units,
-                                        ),
-                                        Number::from(
-                                            (half2[half2.len() / 2 - 1].number.clone()
-                                                + half2[half2.len() / 2].number.clone())
-                                                / 2,
-                                            units,
-                                        ),
-                                    ])
-                                } else {
-                                    Vector(vec![
-                                        Number::from(half1[half1.len() / 2].number.clone(), units),
-                                        Number::from(
-                                            if a.len() % 2 == 0 {
-                                                (half1[half1.len().saturating_sub(1)]
-                                                    .number
-                                                    .clone()
-                                                    + half2[0].number.clone())
-                                                    / 2
-                                            } else {
-                                                a[a.len() / 2].number.clone()
-                                            },
-                                            units,
-                                        ),
-                                        Number::from(half2[half2.len() / 2].number.clone(), units),
-                                    ])
-                                }
-                            }
-                            "percentile" => {
-                                if function.len() <= i + 1 {
-                                    return Err("not enough input");
-                                }
-                                let b = function.remove(i + 1).num()?.number;
-                                let r: Float = (b.real().clone() / 100) * a.len();
-                                let r = r
-                                    .ceil()
-                                    .to_integer()
-                                    .unwrap_or_default()
-                                    .to_usize()
-                                    .unwrap_or_default();
-                                if r > a.len() {
-                                    return Err("bad input");
-                                }
-                                NumStr::new(sort(a)[r.saturating_sub(1)].clone())
-                            }
-                            "percentilerank" => {
-                                if function.len() <= i + 1 {
-                                    return Err("not enough input");
-                                }
-                                let mut cf = 0;
-                                let mut f = 0;
-                                let b = function.remove(i + 1).num()?.number;
-                                for a in sort(a.clone()) {
-                                    if a.number.real() < b.real() {
-                                        cf += 1;
-                                    } else if a.number == b {
-                                        f += 1;
-                                    } else {
-                                        break;
-                                    }
-                                }
-                                NumStr::new(Number::from(
-                                    100 * (cf + Complex::with_val(options.prec, f) / 2) / a.len(),
-                                    None,
-                                ))
-                            }
-                            "to_freq" => {
-                                if a.is_empty() {
-                                    return Err("bad list");
-                                }
-                                let mut a = sort(a);
-                                let mut last = a[0].clone();
-                                let mut count = 1;
-                                a.remove(0);
-                                let mut mat = Vec::new();
-                                for a in a {
-                                    if a != last {
-                                        mat.push(vec![
-                                            last.clone(),
-                                            Number::from(
-                                                Complex::with_val(options.prec, count),
-                                                None,
-                                            ),
-                                        ]);
-                                        last = a;
-                                        count = 0;
-                                    }
-                                    count += 1;
-                                }
-                                mat.push(vec![
-                                    last.clone(),
-                                    Number::from(Complex::with_val(options.prec, count), None),
-                                ]);
-                                Matrix(mat)
-                            }
-                            "kurtosis" => {
-                                fn quar(z: Complex) -> Complex {
-                                    if z.imag().is_zero() {
-                                        z.pow(4)
-                                    } else {
-                                        z.clone() * &z * &z * z
-                                    }
-                                }
-                                let mean = a.iter().fold(Complex::new(options.prec), |sum, val| {
-                                    sum + val.number.clone()
-                                }) / a.len();
-                                NumStr::new(Number::from(
-                                    a.iter().fold(Complex::new(options.prec), |sum, val| {
-                                        sum + quar(val.number.clone() - mean.clone())
-                                    }) / a.len()
-                                        / sqr(variance(&a, Some(mean.clone()), options.prec).number)
-                                        - 3,
-                                    None,
-                                ))
-                            }
-                            "skew" | "skewness" => {
-                                let mean = a.iter().fold(Complex::new(options.prec), |sum, val| {
-                                    sum + val.number.clone()
-                                }) / a.len();
-                                NumStr::new(Number::from(
-                                    a.iter().fold(Complex::new(options.prec), |sum, val| {
-                                        sum + cube(val.number.clone() - mean.clone())
-                                    }) / a.len()
-                                        / cube(
-                                            variance(&a, Some(mean.clone()), options.prec)
-                                                .number
-                                                .sqrt(),
-                                        ),
-                                    None,
-                                ))
-                            }
-                            "sd" | "standarddeviation" | "σ" => NumStr::new(Number::from(
-                                variance(&a, None, options.prec).number.sqrt(),
-                                a[0].units,
-                            )),
-                            "variance" | "var" => NumStr::new(variance(&a, None, options.prec)),
-                            "covariance" | "cov" => {
-                                let mut sum = Complex::new(options.prec);
-                                if function.len() <= i + 1 {
-                                    return Err("not enough input");
-                                }
-                                let b = function.remove(i + 1).vec()?;
-                                if a.len() != b.len() {
-                                    return Err("different sized data sets");
-                                }
-                                let ma = a.iter().fold(Complex::new(options.prec), |sum, val| {
-                                    sum + val.number.clone()
-                                }) / a.len();
-                                let mb = b.iter().fold(Complex::new(options.prec), |sum, val| {
-                                    sum + val.number.clone()
-                                }) / b.len();
-                                for (a, b) in a.iter().zip(b.iter()) {
-                                    sum += (a.number.clone() - ma.clone())
-                                        * (b.number.clone() - mb.clone());
-                                }
-                                NumStr::new(Number::from(
-                                    sum / (a.len() - 1),
-                                    mul_units(a[0].units, b[0].units),
-                                ))
-                            }
-                            "all" => {
-                                let mut res = true;
-                                for a in a {
-                                    if !(a.number.imag().is_zero() && a.number.real() == &1) {
-                                        res = false
-                                    }
-                                }
-                                NumStr::new(Number::from(
-                                    Complex::with_val(options.prec, res as u8),
-                                    None,
-                                ))
-                            }
-                            "any" => {
-                                let mut res = false;
-                                for a in a {
-                                    if a.number.imag().is_zero() && a.number.real() == &1 {
-                                        res = true
-                                    }
-                                }
-                                NumStr::new(Number::from(
-                                    Complex::with_val(options.prec, res as u8),
-                                    None,
-                                ))
-                            }
-                            "sort" => Vector(sort(a)),
-                            "hsv_to_rgb" => {
-                                if a.len() == 3 {
-                                    Vector(hsv2rgb(
-                                        a[0].number.real().clone(),
-                                        a[1].number.real().clone(),
-                                        a[2].number.real().clone(),
-                                    ))
-                                } else {
-                                    return Err("not 3 length");
-                                }
-                            }
-                            "mean" | "μ" => NumStr::new(Number::from(
-                                a.iter().fold(Complex::new(options.prec), |sum, val| {
-                                    sum + val.number.clone()
-                                }) / a.len(),
-                                a[0].units,
-                            )),
-                            "geo_mean" => NumStr::new(Number::from(
-                                pow_nth(
-                                    a.iter()
-                                        .fold(Complex::with_val(options.prec, 1), |sum, val| {
-                                            sum * val.number.clone()
-                                        }),
-                                    Complex::with_val(options.prec, 1) / a.len(),
-                                ),
-                                a[0].units,
-                            )),
-                            "median" => {
-                                let a = sort(a);
-                                if a.len() % 2 == 0 {
-                                    NumStr::new(Number::from(
-                                        (a[a.len() / 2 - 1].number.clone()
-                                            + a[a.len() / 2].number.clone())
-                                            / 2,
-                                        a[0].units,
-                                    ))
-                                } else {
-                                    NumStr::new(Number::from(
-                                        a[a.len() / 2].number.clone(),
-                                        a[0].units,
-                                    ))
-                                }
-                            }
-                            "mode" => {
-                                let mut most = (Vec::new(), 0);
-                                for i in &a {
-                                    let mut count = 0;
-                                    for j in &a {
-                                        if i == j {
-                                            count += 1;
-                                        }
-                                    }
-                                    if count > most.1 {
-                                        most = (vec![i.clone()], count);
-                                    }
-                                    if count == most.1 && !most.0.iter().any(|j| i == j) {
-                                        most.0.push(i.clone())
-                                    }
-                                }
-                                if most.0.len() == 1 {
-                                    NumStr::new(most.0[0].clone())
-                                } else {
-                                    Vector(most.0)
-                                }
-                            }
-                            "max" => {
-                                let mut max = a[0].clone();
-                                for i in a {
-                                    if i.number.real() > max.number.real() {
-                                        max = i
-                                    }
-                                }
-                                NumStr::new(max)
-                            }
-                            "min" => {
-                                let mut min = a[0].clone();
-                                for i in a {
-                                    if i.number.real() < min.number.real() {
-                                        min = i
-                                    }
-                                }
-                                NumStr::new(min)
-                            }
-                            "reverse" => Vector(a.iter().rev().cloned().collect()),
-                            "len" => NumStr::new(Number::from(
-                                Complex::with_val(options.prec, a.len()),
-                                None,
-                            )),
-                            "norm" => {
-                                let units = a[0].units;
-                                let mut n = Complex::new(options.prec);
-                                for i in a {
-                                    n += sqr(i.number.abs());
-                                }
-                                NumStr::new(Number::from(n.sqrt(), units))
-                            }
-                            "normalize" => {
-                                let mut n = Complex::new(options.prec);
-                                for i in a.clone() {
-                                    n += sqr(i.number);
-                                }
-                                Vector(
-                                    a.iter()
-                                        .map(|x| {
-                                            Number::from(x.number.clone() / n.clone().sqrt(), None)
-                                        })
-                                        .collect(),
-                                )
-                            }
-                            "car" | "cartesian" => {
-                                if a.len() == 2 {
-                                    let t = a[1].number.clone() / to_deg.clone();
-                                    Vector(vec![
-                                        Number::from(
-                                            a[0].number.clone() * t.clone().cos(),
-                                            a[0].units,
-                                        ),
-                                        Number::from(
-                                            a[0].number.clone() * t.clone().sin(),
-                                            a[0].units,
-                                        ),
-                                    ])
-                                } else if a.len() == 3 {
-                                    let t1 = a[1].number.clone() / to_deg.clone();
-                                    let t2 = a[2].number.clone() / to_deg.clone();
-                                    Vector(vec![
-                                        Number::from(
-                                            a[0].number.clone()
-                                                * t1.clone().sin()
-                                                * t2.clone().cos(),
-                                            a[0].units,
-                                        ),
-                                        Number::from(
-                                            a[0].number.clone()
-                                                * t1.clone().sin()
-                                                * t2.clone().sin(),
-                                            a[0].units,
-                                        ),
-                                        Number::from(
-                                            a[0].number.clone() * t1.clone().cos(),
-                                            a[0].units,
-                                        ),
-                                    ])
-                                } else {
-                                    return Err("incorrect polar form");
-                                }
-                            }
-                            "polar" | "pol" => Vector(to_polar(a.clone(), to_deg.clone())),
-                            "cyl" | "cylinder" => Vector(to_cyl(a.clone(), to_deg.clone())),
-                            "angle" => {
-                                if function.len() > i + 1 {
-                                    let b = function.remove(i + 1).vec()?;
-                                    if a.len() == 3 && b.len() == 3 {
-                                        let c: Complex = sqr(a[0].number.clone())
-                                            + sqr(a[1].number.clone())
-                                            + sqr(a[2].number.clone());
-                                        let d: Complex = sqr(b[0].number.clone())
-                                            + sqr(b[1].number.clone())
-                                            + sqr(b[2].number.clone());
-                                        NumStr::new(Number::from(
-                                            ((a[0].number.clone() * b[0].number.clone()
-                                                + a[1].number.clone() * b[1].number.clone()
-                                                + a[2].number.clone() * b[2].number.clone())
-                                                / (c.sqrt() * d.sqrt()))
-                                            .acos()
-                                                * to_deg.clone(),
-                                            Some(Units {
-                                                angle: 1.0,
-                                                ..Units::default()
-                                            }),
-                                        ))
-                                    } else if a.len() == 2 && b.len() == 2 {
-                                        let c: Complex =
-                                            sqr(a[0].number.clone()) + sqr(a[1].number.clone());
-                                        let d: Complex =
-                                            sqr(b[0].number.clone()) + sqr(b[1].number.clone());
-                                        NumStr::new(Number::from(
-                                            ((a[0].number.clone() * b[0].number.clone()
-                                                + a[1].number.clone() * b[1].number.clone())
-                                                / (c.sqrt() * d.sqrt()))
-                                            .acos()
-                                                * to_deg.clone(),
-                                            Some(Units {
-                                                angle: 1.0,
-                                                ..Units::default()
-                                            }),
-                                        ))
-                                    } else {
-                                        return Err("cant decern angles");
-                                    }
-                                } else {
-                                    let vec = to_polar(a, to_deg.clone());
-                                    if vec.len() == 3 {
-                                        Vector(vec[1..=2].to_vec())
-                                    } else if vec.len() == 2 {
-                                        NumStr::new(vec[1].clone())
-                                    } else {
-                                        return Err("cant decern angles");
-                                    }
-                                }
-                            }
-                            "cross" => {
-                                if function.len() > i + 1 {
-                                    let b = function.remove(i + 1).vec()?;
-                                    let units = mul_units(a[0].units, b[0].units);
-                                    if a.len() == 3 && b.len() == 3 {
-                                        Vector(vec![
-                                            Number::from(
-                                                a[1].number.clone() * &b[2].number
-                                                    - a[2].number.clone() * &b[1].number,
-                                                units,
-                                            ),
-                                            Number::from(
-                                                a[2].number.clone() * &b[0].number
-                                                    - a[0].number.clone() * &b[2].number,
-                                                units,
-                                            ),
-                                            Number::from(
-                                                a[0].number.clone() * &b[1].number
-                                                    - a[1].number.clone() * &b[0].number,
-                                                units,
-                                            ),
-                                        ])
-                                    } else if a.len() == 2 && b.len() == 2 {
-                                        NumStr::new(Number::from(
-                                            a[0].number.clone() * &b[1].number
-                                                - a[1].number.clone() * &b[0].number,
-                                            units,
-                                        ))
-                                    } else {
-                                        return Err("cant cross");
-                                    }
-                                } else {
-                                    return Err("no args");
-                                }
-                            }
-                            "project" | "proj" => {
-                                if function.len() > i + 1 {
-                                    let b = function.remove(i + 1).clone().vec()?;
-                                    if b.len() == a.len() {
-                                        let mut dot = Complex::new(options.prec);
-                                        for i in a
-                                            .iter()
-                                            .zip(b.iter())
-                                            .map(|(a, b)| a.number.clone() * b.number.clone())
-                                        {
-                                            dot += i;
-                                        }
-                                        let mut norm = Complex::new(options.prec);
-                                        for i in &b {
-                                            norm += sqr(i.number.clone().abs());
-                                        }
-                                        Vector(
-                                            b.iter()
-                                                .map(|n| {
-                                                    Number::from(
-                                                        dot.clone() / norm.clone()
-                                                            * n.number.clone(),
-                                                        n.units,
-                                                    )
-                                                })
-                                                .collect::<Vec<Number>>(),
-                                        )
-                                    } else {
-                                        return Err("cant project");
-                                    }
-                                } else {
-                                    return Err("no args");
-                                }
-                            }
-                            "oproject" | "oproj" => {
-                                if function.len() > i + 1 {
-                                    let b = function.remove(i + 1).clone().vec()?;
-                                    if b.len() == a.len() {
-                                        let mut dot = Complex::new(options.prec);
-                                        for i in a
-                                            .iter()
-                                            .zip(b.iter())
-                                            .map(|(a, b)| a.number.clone() * b.number.clone())
-                                        {
-                                            dot += i;
-                                        }
-                                        let mut norm = Complex::new(options.prec);
-                                        for i in &b {
-                                            norm += sqr(i.number.clone().abs());
-                                        }
-                                        Vector(
-                                            b.iter()
-                                                .zip(a.iter())
-                                                .map(|(n, a)| {
-                                                    Number::from(
-                                                        a.number.clone()
-                                                            - dot.clone() / norm.clone()
-                                                                * n.number.clone(),
-                                                        a.units,
-                                                    )
-                                                })
-                                                .collect::<Vec<Number>>(),
-                                        )
-                                    } else {
-                                        return Err("cant project");
-                                    }
-                                } else {
-                                    return Err("no args");
-                                }
-                            }
-                            "dot" => {
-                                if function.len() > i + 1 {
-                                    let mut n = Complex::new(options.prec);
-                                    let b = function.remove(i + 1).vec()?;
-                                    for i in a
-                                        .iter()
-                                        .zip(b.iter())
-                                        .map(|(a, b)| a.number.clone() * b.number.clone())
-                                    {
-                                        n += i;
-                                    }
-                                    NumStr::new(Number::from(n, mul_units(a[0].units, b[0].units)))
-                                } else {
-                                    return Err("no args");
-                                }
-                            }
-                            "part" => {
-                                if function.len() > i + 1 {
-                                    match function.remove(i + 1) {
-                                        Num(b) => {
-                                            let b = b.number;
-                                            let n = b
-                                                .clone()
-                                                .real()
-                                                .to_integer()
-                                                .unwrap_or_default()
-                                                .to_usize()
-                                                .unwrap_or_default();
-                                            if n < a.len() {
-                                                NumStr::new(a[n].clone())
-                                            } else {
-                                                return Err("out of range");
-                                            }
-                                        }
-                                        Vector(b) => {
-                                            let mut vec = Vec::new();
-                                            for i in b {
-                                                let n = i
-                                                    .number
-                                                    .clone()
-                                                    .real()
-                                                    .to_integer()
-                                                    .unwrap_or_default()
-                                                    .to_usize()
-                                                    .unwrap_or_default();
-                                                if n < a.len() {
-                                                    vec.push(a[n].clone());
-                                                } else {
-                                                    return Err("out of range");
-                                                }
-                                            }
-                                            Vector(vec)
-                                        }
-                                        _ => return Err("non num/vec"),
-                                    }
-                                } else {
-                                    return Err("no args");
-                                }
-                            }
-                            "split" => Matrix(
-                                a.iter()
-                                    .map(|a| {
-                                        vec![
-                                            Number::from(a.number.real().clone().into(), None),
-                                            Number::from(a.number.imag().clone().into(), None),
-                                        ]
-                                    })
-                                    .collect::<Vec<Vec<Number>>>(),
-                            ),
-                            "uniq" => {
-                                let mut a = a;
-                                a.dedup();
-                                Vector(a)
-                            }
-                            "factors" | "factor" => {
-                                let mut fail = false;
-                                let mut mat = Vec::new();
-                                for num in a {
-                                    let num = num.number;
-                                    if num.imag().clone().is_zero() {
-                                        if num.real().clone().fract().is_zero() {
-                                            let mut vec = Vec::new();
-                                            let n = num
-                                                .real()
-                                                .to_integer()
-                                                .unwrap_or_default()
-                                                .to_usize()
-                                                .unwrap_or_default();
-                                            for i in 1..=n {
-                                                if n % i == 0 {
-                                                    vec.push(Number::from(
-                                                        Complex::with_val(options.prec, i),
-                                                        None,
-                                                    ));
-                                                }
-                                            }
-                                            mat.push(vec);
-                                        } else {
-                                            fail = true;
-                                            break;
-                                        }
-                                    } else {
-                                        fail = true;
-                                        break;
-                                    }
-                                }
-                                if fail {
-                                    NumStr::new(Number::from(
-                                        Complex::with_val(options.prec, Nan),
-                                        None,
-                                    ))
-                                } else {
-                                    Matrix(mat)
-                                }
-                            }
-                            "union" => {
-                                if function.len() > i + 1 {
-                                    if let Vector(b) = function.remove(i + 1) {
-                                        let mut a = a;
-                                        a.extend(b);
-                                        a = sort(a);
-                                        a.dedup();
-                                        Vector(a)
-                                    } else {
-                                        return Err("arg not vector");
-                                    }
-                                } else {
-                                    return Err("arg not vector");
-                                }
-                            }
-                            "intersection" => {
-                                if function.len() > i + 1 {
-                                    if let Vector(b) = function.remove(i + 1) {
-                                        let mut v = Vec::new();
-                                        'main: for n1 in a {
-                                            for n2 in &b {
-                                                if &n1 == n2 {
-                                                    v.push(n2.clone());
-                                                    continue 'main;
-                                                }
-                                            }
-                                        }
-                                        Vector(v)
-                                    } else {
-                                        return Err("arg not vector");
-                                    }
-                                } else {
-                                    return Err("arg not vector");
-                                }
-                            }
-                            "set_difference" => {
-                                if function.len() > i + 1 {
-                                    if let Vector(b) = function.remove(i + 1) {
-                                        let mut v = Vec::new();
-                                        'main: for n1 in a {
-                                            for n2 in &b {
-                                                if &n1 == n2 {
-                                                    continue 'main;
-                                                }
-                                            }
-                                            v.push(n1);
-                                        }
-                                        Vector(v)
-                                    } else {
-                                        return Err("arg not vector");
-                                    }
-                                } else {
-                                    return Err("arg not vector");
-                                }
-                            }
-                            "symmetric_difference" => {
-                                if function.len() > i + 1 {
-                                    if let Vector(b) = function.remove(i + 1) {
-                                        let mut a1 = Vec::new();
-                                        'main: for n1 in a.clone() {
-                                            for n2 in &b {
-                                                if &n1 == n2 {
-                                                    continue 'main;
-                                                }
-                                            }
-                                            a1.push(n1);
-                                        }
-                                        let mut a2 = Vec::new();
-                                        'main: for n1 in b.clone() {
-                                            for n2 in &a {
-                                                if &n1 == n2 {
-                                                    continue 'main;
-                                                }
-                                            }
-                                            a2.push(n1);
-                                        }
-                                        a2.extend(a1);
-                                        let mut u = sort(a2);
-                                        u.dedup();
-                                        Vector(u)
-                                    } else {
-                                        return Err("arg not vector");
-                                    }
-                                } else {
-                                    return Err("arg not vector");
-                                }
-                            }
-                            "cartesian_product" => {
-                                if function.len() > i + 1 {
-                                    if let Vector(b) = function.remove(i + 1) {
-                                        let mut m = Vec::new();
-                                        for n1 in a {
-                                            for n2 in b.clone() {
-                                                m.push(vec![n1.clone(), n2])
-                                            }
-                                        }
-                                        Matrix(m)
-                                    } else {
-                                        return Err("arg not vector");
-                                    }
-                                } else {
-                                    return Err("arg not vector");
-                                }
-                            }
-                            "power_set" => {
-                                let mut m = Vec::new();
-                                for i in 0..1 << a.len() {
-                                    let mut v = Vec::new();
-                                    for (j, n) in a.iter().enumerate() {
-                                        if (i >> j) & 1 == 1 {
-                                            v.push(n.clone())
-                                        }
-                                    }
-                                    m.push(v);
-                                }
-                                Matrix(m)
-                            }
-                            "set_fix" => {
-                                let mut a = sort(a);
-                                a.dedup();
-                                Vector(a)
-                            }
-                            "subset" => {
-                                if function.len() > i + 1 {
-                                    if let Vector(b) = function.remove(i + 1) {
-                                        let mut ainb = true;
-                                        'main: for n1 in b {
-                                            for n2 in &a {
-                                                if &n1 == n2 {
-                                                    continue 'main;
-                                                }
-                                            }
-                                            ainb = false;
-                                        }
-                                        NumStr::new(Number::from(
-                                            Complex::with_val(options.prec, ainb as u8),
-                                            None,
-                                        ))
-                                    } else {
-                                        return Err("arg not vector");
-                                    }
-                                } else {
-                                    return Err("arg not vector");
-                                }
-                            }
-                            "element" => {
-                                if function.len() > i + 1 {
-                                    if let Num(b) = function.remove(i + 1) {
-                                        let mut ainb = false;
-                                        for n2 in &a {
-                                            if &*b == n2 {
-                                                ainb = true;
-                                            }
-                                        }
-                                        NumStr::new(Number::from(
-                                            Complex::with_val(options.prec, ainb as u8),
-                                            None,
-                                        ))
-                                    } else {
-                                        return Err("arg not vector");
-                                    }
-                                } else {
-                                    return Err("arg not vector");
-                                }
-                            }
-                            "extend" | "link" => {
-                                if function.len() > i + 1 {
-                                    match function.remove(i + 1) {
-                                        Num(n) => {
-                                            let mut a = a;
-                                            a.push(*n);
-                                            Vector(a)
-                                        }
-                                        Vector(v) => {
-                                            let mut a = a;
-                                            a.extend(v);
-                                            Vector(a)
-                                        }
-                                        _ => return Err("bad arg"),
-                                    }
-                                } else {
-                                    return Err("bad arg");
-                                }
-                            }
-                            "remove" => {
-                                if function.len() > i + 1 {
-                                    match function.remove(i + 1) {
-                                        Num(n) => {
-                                            let mut a = a;
-                                            let n = n
-                                                .number
-                                                .real()
-                                                .to_integer()
-                                                .unwrap_or_default()
-                                                .to_usize()
-                                                .unwrap_or_default();
-                                            if n >= a.len() {
-                                                return Err("bad range");
-                                            }
-                                            a.remove(n);
-                                            Vector(a)
-                                        }
-                                        Vector(v) => {
-                                            let mut a = a;
-                                            for n in v {
-                                                let n = n
-                                                    .number
-                                                    .real()
-                                                    .to_integer()
-                                                    .unwrap_or_default()
-                                                    .to_usize()
-                                                    .unwrap_or_default();
-                                                if n >= a.len() {
-                                                    return Err("bad range");
-                                                }
-                                                a.remove(n);
-                                            }
-                                            Vector(a)
-                                        }
-                                        _ => return Err("bad range"),
-                                    }
-                                } else {
-                                    return Err("bad range");
-                                }
-                            }
-                            "rationalize" => Matrix(
-                                a.iter()
-                                    .map(|c| c_to_rational(c.number.clone(), options))
-                                    .collect::<Vec<Vec<Number>>>(),
-                            ),
-                            "prime_factors" => {
-                                if a.len() != 2 {
-                                    return Err("expected vector length 2");
-                                }
-                                let mut p1 = prime_factors(
-                                    a[0].number.real().to_integer().unwrap_or_default(),
-                                );
-                                let p2 = prime_factors(
-                                    a[1].number.real().to_integer().unwrap_or_default(),
-                                );
-                                for p in p1.iter_mut() {
-                                    for m in &p2 {
-                                        if p.0 == m.0 {
-                                            p.1 -= m.1
-                                        }
-                                    }
-                                }
-                                for m in p2.iter() {
-                                    if !p1.iter().any(|p| p.0 == m.0) {
-                                        p1.push((m.0.clone(), -m.1));
-                                    }
-                                }
-                                Matrix(
-                                    p1.iter()
-                                        .map(|p| {
-                                            vec![
-                                                Number::from(
-                                                    Complex::with_val(options.prec, p.0.clone()),
-                                                    None,
-                                                ),
-                                                Number::from(
-                                                    Complex::with_val(options.prec, p.1),
-                                                    None,
-                                                ),
-                                            ]
-                                        })
-                                        .collect::<Vec<Vec<Number>>>(),
-                                )
-                            }
-                            "poly" | "polynomial" => {
-                                if i + 1 < function.len() {
-                                    let x = function.remove(i + 1).num()?;
-                                    let mut sum = Number::from(Complex::new(options.prec), None);
-                                    for (i, a) in a.iter().rev().enumerate() {
-                                        let n = Number::from(
-                                            a.number.clone() * x.number.clone().pow(i),
-                                            mul_units(
-                                                a.units,
-                                                Some(x.units.unwrap_or_default().pow(i as f64)),
-                                            ),
-                                        );
-                                        if i == 0 { sum = n } else { sum = add(&sum, &n) }
-                                    }
-                                    NumStr::new(sum)
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            _ => do_functions(arg, options, &mut function, i, &to_deg, s)?,
-                        },
-                        _ => match s.as_str() {
-                            "rationalize" => Vector(c_to_rational(arg.num()?.number, options)),
-                            "domain_coloring_rgb" => {
-                                let pi = Float::with_val(options.prec, Pi);
-                                let num = arg.num()?.number;
-                                let hue: Float = 1 + (-num.clone()).arg().real().clone() / &pi;
-                                let sat: Float = (1 + num.clone().abs().real().clone().fract()) / 2;
-                                let val: Float = {
-                                    let (r, i) = (num * &pi).into_real_imag();
-                                    let t1: Float = r.sin();
-                                    let t2: Float = i.sin();
-                                    (t1 * t2).abs().pow(0.125)
-                                };
-                                Vector(hsv2rgb(3 * hue, sat, val))
-                            }
-                            "multinomial" => {
-                                let mut numerator: Complex = arg.num()?.number + 1;
-                                let mut divisor = gamma(numerator.clone());
-                                while i + 1 < function.len() && !matches!(&function[i + 1], Func(_))
-                                {
-                                    let temp = function.remove(i + 1).num()?.number;
-                                    numerator += temp.clone();
-                                    let temp = temp.clone() + 1;
-                                    divisor *= gamma(temp);
-                                }
-                                NumStr::new(Number::from(gamma(numerator) / divisor, None))
-                            }
-                            "Β" | "B" | "beta" => {
-                                if i + 1 < function.len() {
-                                    let a = arg.num()?.number;
-                                    let b = function.remove(i + 1).num()?.number;
-                                    if i + 1 < function.len() {
-                                        let x = function.remove(i + 1).num()?.number;
-                                        NumStr::new(Number::from(incomplete_beta(a, b, x), None))
-                                    } else if a.imag().is_zero() && b.imag().is_zero() {
-                                        NumStr::new(Number::from(
-                                            gamma(a.clone()) * gamma(b.clone())
-                                                / gamma(a + b.clone()),
-                                            None,
-                                        ))
-                                    } else {
-                                        NumStr::new(Number::from(
-                                            incomplete_beta(
-                                                Complex::with_val(options.prec, 1),
-                                                a,
-                                                b,
-                                            ),
-                                            None,
-                                        ))
-                                    }
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "gamma_pdf" => {
-                                if i + 2 < function.len() {
-                                    let x = arg.num()?.number;
-                                    let a = function.remove(i + 1).num()?.number;
-                                    let b = function.remove(i + 1).num()?.number;
-                                    NumStr::new(Number::from(
-                                        pow_nth(b.clone(), -a.clone()) / gamma(a.clone())
-                                            * pow_nth(x.clone(), a - 1)
-                                            * (-x / b).exp(),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "gamma_cdf" => {
-                                if i + 2 < function.len() {
-                                    let x = arg.num()?.number;
-                                    let a = function.remove(i + 1).num()?.number;
-                                    let b = function.remove(i + 1).num()?.number;
-                                    NumStr::new(Number::from(
-                                        1 - incomplete_gamma(a.clone(), x / b) / gamma(a),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "I" | "betaC" | "beta_cdf" => {
-                                if i + 2 < function.len() {
-                                    let x = arg.num()?.number;
-                                    let a = function.remove(i + 1).num()?.number;
-                                    let b = function.remove(i + 1).num()?.number;
-                                    if i + 1 < function.len() {
-                                        let z = function.remove(i + 1).num()?.number;
-                                        NumStr::new(Number::from(
-                                            regularized_incomplete_beta(a, b.clone(), z.clone())
-                                                - regularized_incomplete_beta(x, b, z),
-                                            None,
-                                        ))
-                                    } else {
-                                        NumStr::new(Number::from(
-                                            regularized_incomplete_beta(x, a, b),
-                                            None,
-                                        ))
-                                    }
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "betaP" | "beta_pdf" => {
-                                if i + 2 < function.len() {
-                                    let x = arg.num()?.number;
-                                    let alpha = function.remove(i + 1).num()?.number;
-                                    let beta = function.remove(i + 1).num()?.number;
-                                    let c: Complex = 1 - x.clone();
-                                    NumStr::new(Number::from(
-                                        gamma(alpha.clone() + beta.clone())
-                                            * pow_nth(x, alpha.clone() - 1)
-                                            * pow_nth(c, beta.clone() - 1)
-                                            / (gamma(alpha) * gamma(beta)),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "normP" | "norm_pdf" => {
-                                if i + 2 < function.len() {
-                                    let x = arg.num()?.number;
-                                    let mu = function.remove(i + 1).num()?.number;
-                                    let sigma = function.remove(i + 1).num()?.number;
-                                    let n: Complex = sqr(x - mu);
-                                    let n: Complex = -n / (2 * sqr(sigma.clone()));
-                                    let tau: Complex = 2 * Complex::with_val(options.prec, Pi);
-                                    NumStr::new(Number::from(n.exp() / (sigma * tau.sqrt()), None))
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "normD" | "norm_cdf" => {
-                                let mut a = arg.num()?.number;
-                                if i + 2 < function.len() {
-                                    a -= function.remove(i + 1).num()?.number;
-                                    a /= function.remove(i + 1).num()?.number;
-                                }
-                                if a.imag().is_zero() {
-                                    let two = Float::with_val(options.prec, 2);
-                                    NumStr::new(Number::from(
-                                        ((-a / two.clone().sqrt()).real().clone().erfc() / two)
-                                            .into(),
-                                        None,
-                                    ))
-                                } else {
-                                    let two = Float::with_val(options.prec, 2);
-                                    NumStr::new(Number::from(
-                                        erf(-a / two.clone().sqrt()) / two,
-                                        None,
-                                    ))
-                                }
-                            }
-                            "lognorm_cdf" => {
-                                let mut a = arg.num()?.number.ln();
-                                if i + 2 < function.len() {
-                                    a -= function.remove(i + 1).num()?.number;
-                                    a /= function.remove(i + 1).num()?.number;
-                                }
-                                if a.imag().is_zero() {
-                                    let two = Float::with_val(options.prec, 2);
-                                    NumStr::new(Number::from(
-                                        ((-a / two.clone().sqrt()).real().clone().erfc() / two)
-                                            .into(),
-                                        None,
-                                    ))
-                                } else {
-                                    let two = Float::with_val(options.prec, 2);
-                                    NumStr::new(Number::from(
-                                        erf(-a / two.clone().sqrt()) / two,
-                                        None,
-                                    ))
-                                }
-                            }
-                            "lognorm_pdf" => {
-                                if i + 2 < function.len() {
-                                    let x = arg.num()?.number;
-                                    let mu = function.remove(i + 1).num()?.number;
-                                    let sigma = function.remove(i + 1).num()?.number;
-                                    let n: Complex = sqr(x.clone().ln() - mu);
-                                    let n: Complex = -n / (2 * sqr(sigma.clone()));
-                                    let tau: Complex = 2 * Complex::with_val(options.prec, Pi);
-                                    NumStr::new(Number::from(
-                                        n.exp() / (sigma * tau.sqrt() * x),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "poisson_pmf" => {
-                                if i + 1 < function.len() {
-                                    let k = arg.num()?.number;
-                                    let l = function.remove(i + 1).num()?.number;
-                                    NumStr::new(Number::from(
-                                        pow_nth(l.clone(), k.clone()) * (-l).exp() / gamma(k + 1),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "poisson_cdf" => {
-                                if i + 1 < function.len() {
-                                    let k = arg.num()?.number;
-                                    let l = function.remove(i + 1).num()?.number;
-                                    NumStr::new(Number::from(
-                                        incomplete_gamma(k.clone() + 1, l) / gamma(k + 1),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "binomial_cdf" => {
-                                if i + 2 < function.len() {
-                                    let k = arg.num()?.number;
-                                    let n = function.remove(i + 1).num()?.number;
-                                    let p = function.remove(i + 1).num()?.number;
-                                    let q: Complex = 1 - p.clone();
-                                    NumStr::new(Number::from(
-                                        regularized_incomplete_beta(q, n - k.clone(), 1 + k),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "binomial_pmf" => {
-                                if i + 2 < function.len() {
-                                    let k = arg.num()?.number;
-                                    let n = function.remove(i + 1).num()?.number;
-                                    let p = function.remove(i + 1).num()?.number;
-                                    let q: Complex = 1 - p.clone();
-                                    NumStr::new(Number::from(
-                                        binomial(n.clone(), k.clone())
-                                            * pow_nth(p, k.clone())
-                                            * pow_nth(q, n - k),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
+                    apply(&mut function, i, s, options, &to_deg)?;
+                }
+            }
+        }
+        i += 1;
+    }
+    i = 0;
+    while i < function.len() {
+        if let Func(s) = &function[i] {
+            if let Some(n) = constant(s, options)? {
+                function[i] = n
+            }
+        }
+        i += 1;
+    }
+    i = 1;
+    while i < function.len().saturating_sub(1) {
+        function[i] = match &function[i] {
+            op @ (Modulo | Range) => binary(op, &function[i - 1], &function[i + 1], options)?,
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        function.remove(i + 1);
+        function.remove(i - 1);
+    }
+    i = function.len().saturating_sub(2);
+    while i != 0 {
+        function[i] = match &function[i] {
+            op @ (Exponent | Tetration | Root) => {
+                binary(op, &function[i - 1], &function[i + 1], options)?
+            }
+            _ => {
+                i -= 1;
+                continue;
+            }
+        };
+        function.remove(i + 1);
+        function.remove(i - 1);
+        i = i.saturating_sub(2);
+    }
+    i = 1;
+    while i < function.len().saturating_sub(1) {
+        function[i] = match &function[i] {
+            InternalMultiplication => binary(
+                &InternalMultiplication,
+                &function[i - 1],
+                &function[i + 1],
+                options,
+            )?,
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        function.remove(i + 1);
+        function.remove(i - 1);
+    }
+    i = 1;
+    while i < function.len().saturating_sub(1) {
+        function[i] = match &function[i] {
+            op @ (Multiplication | Division) => {
+                binary(op, &function[i - 1], &function[i + 1], options)?
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        function.remove(i + 1);
+        function.remove(i - 1);
+    }
+    i = 1;
+    while i < function.len().saturating_sub(1) {
+        function[i] = match &function[i] {
+            op @ (PlusMinus | Plus | Minus) => {
+                binary(op, &function[i - 1], &function[i + 1], options)?
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        function.remove(i + 1);
+        function.remove(i - 1);
+    }
+    if options.units {
+        i = 1;
+        while i < function.len().saturating_sub(1) {
+            function[i] = match &function[i] {
+                Conversion => binary(&Conversion, &function[i - 1], &function[i + 1], options)?,
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+            function.remove(i + 1);
+            function.remove(i - 1);
+        }
+    }
+    i = 1;
+    while i < function.len().saturating_sub(1) {
+        if is_comparison(&function[i]) {
+            check_units(&function[i - 1], &function[i + 1], options)?
+        }
+        function[i] = match &function[i] {
+            Lesser => {
+                if i + 3 < function.len()
+                    && matches!(
+                        &function[i + 2],
+                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
+                    )
+                {
+                    function[i - 1] = function[i + 1].func(&function[i - 1], gt)?;
+                    function[i] = And;
+                    continue;
+                }
+                function[i + 1].func(&function[i - 1], gt)?
+            }
+            LesserEqual => {
+                if i + 3 < function.len()
+                    && matches!(
+                        &function[i + 2],
+                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
+                    )
+                {
+                    function[i - 1] = function[i + 1].func(&function[i - 1], ge)?;
+                    function[i] = And;
+                    continue;
+                }
+                function[i + 1].func(&function[i - 1], ge)?
+            }
+            Greater => {
+                if i + 3 < function.len()
+                    && matches!(
+                        &function[i + 2],
+                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
+                    )
+                {
+                    function[i - 1] = function[i - 1].func(&function[i + 1], gt)?;
+                    function[i] = And;
+                    continue;
+                }
+                function[i - 1].func(&function[i + 1], gt)?
+            }
+            GreaterEqual => {
+                if i + 3 < function.len()
+                    && matches!(
+                        &function[i + 2],
+                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
+                    )
+                {
+                    function[i - 1] = function[i - 1].func(&function[i + 1], ge)?;
+                    function[i] = And;
+                    continue;
+                }
+                function[i - 1].func(&function[i + 1], ge)?
+            }
+            Equal => {
+                if i + 3 < function.len()
+                    && matches!(
+                        &function[i + 2],
+                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
+                    )
+                {
+                    function[i - 1] = function[i - 1].func(&function[i + 1], eq)?;
+                    function[i] = And;
+                    continue;
+                }
+                function[i - 1].func(&function[i + 1], eq)?
+            }
+            NotEqual => {
+                if i + 3 < function.len()
+                    && matches!(
+                        &function[i + 2],
+                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
+                    )
+                {
+                    function[i - 1] = function[i - 1].func(&function[i + 1], ne)?;
+                    function[i] = And;
+                    continue;
+                }
+                function[i - 1].func(&function[i + 1], ne)?
+            }
+            NearEqual => {
+                if i + 3 < function.len()
+                    && matches!(
+                        &function[i + 2],
+                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
+                    )
+                {
+                    function[i - 1] = function[i - 1].func(&function[i + 1], about_eq)?;
+                    function[i] = And;
+                    continue;
+                }
+                function[i - 1].func(&function[i + 1], about_eq)?
+            }
+            op @ (ShiftRight | ShiftLeft) => {
+                binary(op, &function[i - 1], &function[i + 1], options)?
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        function.remove(i + 1);
+        function.remove(i - 1);
+    }
+    if !function.is_empty() && function[0] == Not {
+        function[0] = not(&function.remove(1))?;
+    }
+    i = 1;
+    while i < function.len().saturating_sub(1) {
+        function[i] = match &function[i] {
+            And => function[i - 1].func(&function[i + 1], and)?,
+            Or => function[i - 1].func(&function[i + 1], or)?,
+            Xor => function[i - 1].func(&function[i + 1], xor)?,
+            Implies => function[i - 1].func(&function[i + 1], implies)?,
+            Nand => function[i - 1].func(&function[i + 1], nand)?,
+            Nor => function[i - 1].func(&function[i + 1], nor)?,
+            Converse => function[i + 1].func(&function[i - 1], implies)?,
+            Not => {
+                function[i] = not(&function.remove(i + 1))?;
+                continue;
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        function.remove(i + 1);
+        function.remove(i - 1);
+    }
+    if function.len() == 1 {
+        Ok(function.remove(0))
+    } else {
+        Err(Error::Parse("failed to compute", None))
+    }
+}
+pub fn call(s: &str, args: Vec<NumStr>, options: Options) -> Result<NumStr, Error> {
+    if args.is_empty() {
+        return constant(s, options)?.ok_or(Error::Arity("not enough args", None));
+    }
+    let mut function = Vec::with_capacity(args.len() + 1);
+    function.push(Func(s.to_string()));
+    function.extend(args);
+    apply(&mut function, 0, s, options, &to_deg(options))?;
+    if function.len() == 1 {
+        Ok(function.remove(0))
+    } else {
+        Err(Error::Arity("too many args", None))
+    }
+}
+pub fn binary(op: &NumStr, a: &NumStr, b: &NumStr, options: Options) -> Result<NumStr, Error> {
+    match op {
+        Modulo => a.func(b, rem),
+        Range => to(a, b),
+        Exponent => a.pow(b),
+        Tetration => a.func(b, tetration),
+        Root => a.func(b, root),
+        InternalMultiplication | Multiplication => a.mul(b),
+        Division => a.func(b, div),
+        PlusMinus => {
+            check_units(a, b, options)?;
+            a.pm(b)
+        }
+        Plus => {
+            check_units(a, b, options)?;
+            a.func(b, add)
+        }
+        Minus => {
+            check_units(a, b, options)?;
+            a.func(b, sub)
+        }
+        Conversion if options.units => a.func(b, div),
+        ShiftRight => a.func(b, shr),
+        ShiftLeft => a.func(b, shl),
+        And => a.func(b, and),
+        Or => a.func(b, or),
+        Xor => a.func(b, xor),
+        Implies => a.func(b, implies),
+        Nand => a.func(b, nand),
+        Nor => a.func(b, nor),
+        Converse => b.func(a, implies),
+        _ => {
+            check_units(a, b, options)?;
+            compare(op, a, b)
+        }
+    }
+}
+///with units on, adding or comparing numbers of different units, 1m+1s, is an error instead
+///of a unitless result, a plain number on either side is still allowed
+fn check_units(a: &NumStr, b: &NumStr, options: Options) -> Result<(), Error> {
+    if !options.units {
+        return Ok(());
+    }
+    let clash = std::cell::Cell::new(false);
+    a.func(b, |x, y| {
+        let units = |n: &Number| n.units.filter(|u| !u.is_none());
+        if let (Some(u), Some(v)) = (units(x), units(y)) {
+            if u != v {
+                clash.set(true)
+            }
+        }
+        x.clone()
+    })?;
+    if clash.get() {
+        Err(Error::Unit("incompatible units", None))
+    } else {
+        Ok(())
+    }
+}
+pub fn compare(op: &NumStr, a: &NumStr, b: &NumStr) -> Result<NumStr, Error> {
+    match op {
+        Lesser => b.func(a, gt),
+        LesserEqual => b.func(a, ge),
+        Greater => a.func(b, gt),
+        GreaterEqual => a.func(b, ge),
+        Equal => a.func(b, eq),
+        NotEqual => a.func(b, ne),
+        NearEqual => a.func(b, about_eq),
+        _ => Err(Error::Parse("failed to compute", None)),
+    }
+}
+pub fn is_comparison(op: &NumStr) -> bool {
+    matches!(
+        op,
+        Lesser | Greater | Equal | NotEqual | NearEqual | LesserEqual | GreaterEqual
+    )
+}
+pub fn constant(s: &str, options: Options) -> Result<Option<NumStr>, Error> {
+    Ok(Some(match s {
+        #[cfg(feature = "fastrand")]
+        "rnd" | "rand" => NumStr::new(Number::from(
+            Complex::with_val(options.precision(), fastrand::u128(..)) / u128::MAX,
+            None,
+        )),
+        "epoch" => NumStr::new(Number::from(
+            Complex::with_val(
+                options.precision(),
+                match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+                    Ok(n) => n.as_nanos(),
+                    _ => return Err(Error::Domain("epoch fail", None)),
+                },
+            ) / 1000000000,
+            Some(Units {
+                second: 1.0,
+                ..Units::default()
+            }),
+        )),
+        _ => return Ok(None),
+    }))
+}
+pub fn to_deg(options: Options) -> Complex {
+    match options.angle {
+        AngleType::Degrees => 180 / Complex::with_val(options.precision(), Pi),
+        AngleType::Radians => Complex::with_val(options.precision(), 1),
+        AngleType::Gradians => 200 / Complex::with_val(options.precision(), Pi),
+    }
+}
+fn apply(
+    function: &mut Vec<NumStr>,
+    i: usize,
+    s: &str,
+    options: Options,
+    to_deg: &Complex,
+) -> Result<(), Error> {
+    let arg = function.remove(i + 1);
+    function[i] = match arg.clone() {
+        Matrix(a) => match s {
+            "plane" => {
+                if a.len() != 3 || a.iter().any(|a| a.len() != 3) {
+                    return Err(Error::Dimension("dimensions too high", None));
+                }
+                let x1 = &a[0][0].number;
+                let y1 = &a[0][1].number;
+                let z1 = &a[0][2].number;
+                let x2 = &a[1][0].number;
+                let y2 = &a[1][1].number;
+                let z2 = &a[1][2].number;
+                let x3 = &a[2][0].number;
+                let y3 = &a[2][1].number;
+                let z3 = &a[2][2].number;
+                let t1 = z2 - z3.clone() * y2 / y3;
+                let t2 = x2 - x3.clone() * y2 / y3;
+                let t3 = t1 / t2.clone();
+                let c1 = z1 - x1 * t3.clone() - y1.clone() / y3 * (z3 - x3 * t3);
+                let c2 = 1 - x1 / t2.clone() + x1.clone() * y2 / y3 / t2.clone() - y1.clone() / y3
+                    + x3.clone() * y1 / y3 / t2.clone()
+                    - x3.clone() * y1 * y2 / y3 / y3 / t2.clone();
+                let c: Complex = c1 / c2;
+                let b = (z3
+                    - c.clone()
+                    - x3 * (z2 - c.clone() - y2.clone() / y3 * (z3 - c.clone())) / t2)
+                    / y3;
+                let a = (z2 - c.clone() - b.clone() * y2) / x2;
+                if function.len() > i + 2 {
+                    let x = function.remove(i + 1).num()?.number;
+                    let y = function.remove(i + 1).num()?.number;
+                    NumStr::new(Number::from(a * x + b * y + c, None))
+                } else {
+                    Vector(vec![
+                        Number::from(a, None),
+                        Number::from(b, None),
+                        Number::from(c, None),
+                    ])
+                }
+            }
+            "lobf" | "lineofbestfit" => {
+                if a.is_empty() || a.iter().any(|a| a.len() != 2) {
+                    return Err(Error::Dimension("dimensions too high", None));
+                }
+                let mut xsum = Complex::new(options.precision());
+                let mut ysum = Complex::new(options.precision());
+                let mut xxsum = Complex::new(options.precision());
+                let mut xysum = Complex::new(options.precision());
+                for row in &a {
+                    let x = row[0].number.clone();
+                    let y = row[1].number.clone();
+                    xsum += x.clone();
+                    ysum += y.clone();
+                    xxsum += sqr(x.clone());
+                    xysum += x * y;
+                }
+                let m: Complex = (a.len() * xysum - xsum.clone() * ysum.clone())
+                    / (a.len() * xxsum - sqr(xsum.clone()));
+                let b = (ysum - m.clone() * xsum) / a.len();
+                if function.len() > i + 1 {
+                    let x = function.remove(i + 1).num()?.number;
+                    NumStr::new(Number::from(m * x + b, a[0][1].units))
+                } else {
+                    Vector(vec![Number::from(m, None), Number::from(b, None)])
+                }
+            }
+            "polyfit" => {
+                if function.len() > i + 1 {
+                    let degree = function.remove(i + 1).num()?.number;
+                    if !degree.imag().is_zero()
+                        || degree.real().is_sign_negative()
+                        || !degree.real().is_integer()
+                    {
+                        return Err(Error::Domain(
+                            "polyfit degree must be a nonnegative integer",
+                            None,
+                        ));
+                    }
+                    let fit = polyfit(&a, degree.real().to_f64() as usize)?;
+                    let mode = if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                        Some(function.remove(i + 1).num()?.number)
+                    } else {
+                        None
+                    };
+                    fit_mode(fit, mode)?
+                } else {
+                    return Err(Error::Arity("no degree given", None));
+                }
+            }
+            "spline" | "pchip" | "linterp" => {
+                let at = if function.len() > i + 1 {
+                    Some(function.remove(i + 1))
+                } else {
+                    None
+                };
+                let kind = match s {
+                    "linterp" => Interpolation::Linear,
+                    "pchip" => Interpolation::Pchip,
+                    _ if function.len() > i + 2 => {
+                        let a = function.remove(i + 1).num()?.number;
+                        let b = function.remove(i + 1).num()?.number;
+                        Interpolation::Clamped(a.real().clone(), b.real().clone())
+                    }
+                    _ => Interpolation::Natural,
+                };
+                piecewise(&a, kind, at, options.samples_2d)?
+            }
+            "expfit" | "powfit" | "logfit" => {
+                let fit = if s == "logfit" {
+                    logfit(&a)?
+                } else {
+                    expfit(&a, s == "powfit")?
+                };
+                let mode = if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    Some(function.remove(i + 1).num()?.number)
+                } else {
+                    None
+                };
+                fit_mode(fit, mode)?
+            }
+            "inter" | "interpolate" => {
+                if function.len() > i + 1 {
+                    if !a.is_empty() && a.iter().all(|a| a.len() == 2) {
+                        let x = function.remove(i + 1).num()?.number;
+                        let mut sum = Complex::new(options.precision());
+                        for i in 0..a.len() {
+                            let mut prod = Complex::with_val(options.precision(), 1);
+                            for j in 0..a.len() {
+                                if j != i {
+                                    prod *= (x.clone() - a[j][0].number.clone())
+                                        / (a[i][0].number.clone() - a[j][0].number.clone())
                                 }
                             }
-                            "neg_binomial_cdf" => {
-                                if i + 2 < function.len() {
-                                    let k = arg.num()?.number;
-                                    let r = function.remove(i + 1).num()?.number;
-                                    let p = function.remove(i + 1).num()?.number;
-                                    NumStr::new(Number::from(
-                                        regularized_incomplete_beta(p, r, k + 1),
-                                        None,
-                                    ))
+                            sum += prod * a[i][1].number.clone()
+                        }
+                        NumStr::new(Number::from(sum, a[0][1].units))
+                    } else {
+                        return Err(Error::Dimension("dimensions too high", None));
+                    }
+                } else {
+                    return Err(Error::Arity("no x value given", None));
+                }
+            }
+            "sort" => Matrix(sort_mat(a, options.precision())),
+            "max" => {
+                let mut vec = Vec::with_capacity(a.len().max(1));
+                for j in a {
+                    let mut max = j[0].clone();
+                    for i in j {
+                        if i.number.real() > max.number.real() {
+                            max = i
+                        }
+                    }
+                    vec.push(max)
+                }
+                Vector(vec)
+            }
+            "min" => {
+                let mut vec = Vec::with_capacity(a.len().max(1));
+                for j in a {
+                    let mut min = j[0].clone();
+                    for i in j {
+                        if i.number.real() < min.number.real() {
+                            min = i
+                        }
+                    }
+                    vec.push(min)
+                }
+                Vector(vec)
+            }
+            "flatten" => Vector(a.into_iter().flatten().collect::<Vec<Number>>()),
+            "cofactor" | "cofactors" | "cof" => Matrix(cofactor(&a)?),
+            "minor" | "minors" => Matrix(minors(&a)?),
+            "adjugate" | "adj" => Matrix(transpose(&cofactor(&a)?)),
+            "inverse" | "inv" => Matrix(inverse(&a)?),
+            "transpose" | "trans" => Matrix(transpose(&a)),
+            "len" => NumStr::new(Number::from(
+                Complex::with_val(options.precision(), a.len()),
+                None,
+            )),
+            "wid" | "width" => NumStr::new(Number::from(
+                Complex::with_val(options.precision(), a[0].len()),
+                None,
+            )),
+            "tr" | "trace" => NumStr::new(trace(&a)),
+            "det" | "norm" | "determinant" => NumStr::new(determinant(&a)?),
+            "part" => {
+                if function.len() > i + 2 {
+                    match (function.remove(i + 1), function.remove(i + 1)) {
+                        (Num(b), Num(c)) => {
+                            let b = b.number;
+                            let c = c.number;
+                            let n1 = b.clone().real().to_integer().unwrap_or_default();
+                            let getcol = n1 == -1;
+                            let n1 = n1.to_usize().unwrap_or_default();
+                            let n2 = c
+                                .clone()
+                                .real()
+                                .to_integer()
+                                .unwrap_or_default()
+                                .to_usize()
+                                .unwrap_or_default();
+                            if getcol {
+                                if a.iter().all(|a| n2 < a.len()) {
+                                    Vector(a.iter().map(|a| a[n2].clone()).collect::<Vec<Number>>())
                                 } else {
-                                    return Err("not enough args");
+                                    return Err(Error::Domain("out of range", None));
                                 }
+                            } else if n1 < a.len() && n2 < a[n1].len() {
+                                NumStr::new(a[n1][n2].clone())
+                            } else {
+                                return Err(Error::Dimension("not in matrix", None));
                             }
-                            "neg_binomial_pmf" => {
-                                if i + 2 < function.len() {
-                                    let k = arg.num()?.number;
-                                    let r = function.remove(i + 1).num()?.number;
-                                    let p = function.remove(i + 1).num()?.number;
-                                    let q: Complex = 1 - p.clone();
-                                    NumStr::new(Number::from(
-                                        binomial(k.clone() + r.clone() - 1, k.clone())
-                                            * pow_nth(p, r)
-                                            * pow_nth(q, k),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
+                        }
+                        (Num(b), Vector(c)) => {
+                            let b = b.number;
+                            let n1 = b.clone().real().to_integer().unwrap_or_default();
+                            let getcol = n1 == -1;
+                            let n1 = n1.to_usize().unwrap_or_default();
+                            if getcol {
+                                let mut mat = Vec::with_capacity(c.len().max(1));
+                                for n in c {
+                                    let n = n
+                                        .number
+                                        .clone()
+                                        .real()
+                                        .to_integer()
+                                        .unwrap_or_default()
+                                        .to_usize()
+                                        .unwrap_or_default();
+                                    if a.iter().all(|a| n < a.len()) {
+                                        mat.push(
+                                            a.iter().map(|a| a[n].clone()).collect::<Vec<Number>>(),
+                                        )
+                                    } else {
+                                        return Err(Error::Domain("out of range", None));
+                                    }
                                 }
-                            }
-                            "hypergeometric_cdf" => {
-                                if i + 3 < function.len() {
-                                    let mut k = arg.num()?.number.real().clone().floor();
-                                    let pop = function.remove(i + 1).num()?.number;
-                                    let success = function.remove(i + 1).num()?.number;
-                                    let draws = function.remove(i + 1).num()?.number;
-                                    let mut sum = Complex::new(options.prec);
-                                    while k >= 0 {
-                                        sum += binomial(success.clone(), k.clone().into())
-                                            * binomial(
-                                                pop.clone() - success.clone(),
-                                                draws.clone() - k.clone(),
-                                            )
-                                            / binomial(pop.clone(), draws.clone());
-                                        k -= 1
+                                Matrix(transpose(&mat))
+                            } else {
+                                let mut vec = Vec::with_capacity(c.len().max(1));
+                                for n in c {
+                                    let n2 = n
+                                        .number
+                                        .clone()
+                                        .real()
+                                        .to_integer()
+                                        .unwrap_or_default()
+                                        .to_usize()
+                                        .unwrap_or_default();
+                                    if n1 < a.len() && n2 < a[n1].len() {
+                                        vec.push(a[n1][n2].clone())
+                                    } else {
+                                        return Err(Error::Dimension("not in matrix", None));
                                     }
-                                    NumStr::new(Number::from(
-                                        Complex::with_val(options.prec, sum),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
                                 }
+                                Vector(vec)
                             }
-                            "hypergeometric_pmf" => {
-                                if i + 3 < function.len() {
-                                    let k = arg.num()?.number;
-                                    let pop = function.remove(i + 1).num()?.number;
-                                    let success = function.remove(i + 1).num()?.number;
-                                    let draws = function.remove(i + 1).num()?.number;
-                                    NumStr::new(Number::from(
-                                        binomial(success.clone(), k.clone())
-                                            * binomial(
-                                                pop.clone() - success.clone(),
-                                                draws.clone() - k,
-                                            )
-                                            / binomial(pop, draws),
-                                        None,
-                                    ))
+                        }
+                        (Vector(b), Num(c)) => {
+                            let c = c.number;
+                            let n2 = c
+                                .clone()
+                                .real()
+                                .to_integer()
+                                .unwrap_or_default()
+                                .to_usize()
+                                .unwrap_or_default();
+                            let mut vec = Vec::with_capacity(b.len().max(1));
+                            for n in b {
+                                let n1 = n
+                                    .number
+                                    .clone()
+                                    .real()
+                                    .to_integer()
+                                    .unwrap_or_default()
+                                    .to_usize()
+                                    .unwrap_or_default();
+                                if n1 < a.len() && n2 < a[n1].len() {
+                                    vec.push(a[n1][n2].clone())
                                 } else {
-                                    return Err("not enough args");
+                                    return Err(Error::Dimension("not in matrix", None));
                                 }
                             }
-                            #[cfg(feature = "fastrand")]
-                            "rand_hypergeometric" => {
-                                if i + 2 < function.len() {
-                                    let mut pop = arg.num()?.number.real().clone();
-                                    let mut success =
-                                        function.remove(i + 1).num()?.number.real().clone();
-                                    let mut draws =
-                                        function.remove(i + 1).num()?.number.real().clone();
-                                    let mut sum = Integer::new();
-                                    while draws > 0 {
-                                        if success.clone() / pop.clone()
-                                            > Float::with_val(options.prec, fastrand::u128(..))
-                                                / u128::MAX
-                                        {
-                                            sum += 1;
-                                            success -= 1;
-                                        }
-                                        pop -= 1;
-                                        draws -= 1
+                            Vector(vec)
+                        }
+                        (Vector(b), Vector(c)) => {
+                            let mut mat = Vec::with_capacity(b.len().max(1));
+                            for g in b {
+                                let mut vec = Vec::with_capacity(c.len().max(1));
+                                let n1 = g
+                                    .number
+                                    .clone()
+                                    .real()
+                                    .to_integer()
+                                    .unwrap_or_default()
+                                    .to_usize()
+                                    .unwrap_or_default();
+                                for n in c.clone() {
+                                    let n2 = n
+                                        .number
+                                        .clone()
+                                        .real()
+                                        .to_integer()
+                                        .unwrap_or_default()
+                                        .to_usize()
+                                        .unwrap_or_default();
+                                    if n1 < a.len() && n2 < a[n1].len() {
+                                        vec.push(a[n1][n2].clone())
+                                    } else {
+                                        return Err(Error::Dimension("not in matrix", None));
                                     }
-                                    NumStr::new(Number::from(
-                                        Complex::with_val(options.prec, sum),
-                                        None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
                                 }
+                                mat.push(vec);
                             }
-                            "neg_hypergeometric_cdf" => {
-                                if i + 3 < function.len() {
-                                    let mut k = arg.num()?.number.real().clone().floor();
-                                    let pop = function.remove(i + 1).num()?.number;
-                                    let success = function.remove(i + 1).num()?.number;
-                                    let fails = function.remove(i + 1).num()?.number;
-                                    let mut sum = Complex::new(options.prec);
-                                    while k >= 0 {
-                                        sum += binomial(
-                                            k.clone() + fails.clone() - 1,
-                                            k.clone().into(),
-                                        ) * binomial(
-                                            pop.clone() - k.clone() - fails.clone(),
-                                            success.clone() - k.clone(),
-                                        ) / binomial(pop.clone(), success.clone());
-                                        k -= 1
-                                    }
-                                    NumStr::new(Number::from(
-                                        Complex::with_val(options.prec, sum),
-                                        None,
-                                    ))
+                            Matrix(mat)
+                        }
+                        _ => return Err(Error::Dimension("wrong part num", None)),
+                    }
+                } else if function.len() > i + 1 {
+                    match function.remove(i + 1) {
+                        Num(b) => {
+                            let b = b.number;
+                            let n = b
+                                .clone()
+                                .real()
+                                .to_integer()
+                                .unwrap_or_default()
+                                .to_usize()
+                                .unwrap_or_default();
+                            if n < a.len() {
+                                Vector(a[n].clone())
+                            } else {
+                                return Err(Error::Domain("out of range", None));
+                            }
+                        }
+                        Vector(b) => {
+                            let mut vec = Vec::with_capacity(b.len().max(1));
+                            for i in b {
+                                let n = i
+                                    .number
+                                    .clone()
+                                    .real()
+                                    .to_integer()
+                                    .unwrap_or_default()
+                                    .to_usize()
+                                    .unwrap_or_default();
+                                if n < a.len() {
+                                    vec.push(a[n].clone());
                                 } else {
-                                    return Err("not enough args");
+                                    return Err(Error::Domain("out of range", None));
                                 }
                             }
-                            "neg_hypergeometric_pmf" => {
-                                if i + 3 < function.len() {
-                                    let k = arg.num()?.number;
-                                    let pop = function.remove(i + 1).num()?.number;
-                                    let success = function.remove(i + 1).num()?.number;
-                                    let fails = function.remove(i + 1).num()?.number;
-                                    NumStr::new(Number::from(
-                                        binomial(k.clone() + fails.clone() - 1, k.clone())
-                                            * binomial(
-                                                pop.clone() - k.clone() - fails,
-                                                success.clone() - k,
-                                            )
-                                            / binomial(pop, success),
-                                        None,
-                                    ))
+                            Matrix(vec)
+                        }
+                        _ => return Err(Error::Type("non num/vec", None)),
+                    }
+                } else {
+                    return Err(Error::Arity("no arg", None));
+                }
+            }
+            "weighted_mean" => {
+                if a.iter().any(|a| a.len() != 2) {
+                    return Err(Error::Domain("bad data", None));
+                }
+                NumStr::new(Number::from(
+                    a.iter()
+                        .fold(Complex::new(options.precision()), |sum, val| {
+                            sum + val[0].number.clone() * val[1].number.clone()
+                        })
+                        / a.iter()
+                            .fold(Complex::new(options.precision()), |sum, val| {
+                                sum + val[1].number.clone()
+                            }),
+                    a[0][0].units,
+                ))
+            }
+            "mean" | "μ" => NumStr::new(Number::from(
+                a.iter()
+                    .flatten()
+                    .fold(Complex::new(options.precision()), |sum, val| {
+                        sum + val.number.clone()
+                    })
+                    / a.iter().fold(0, |sum, a| sum + a.len()),
+                a[0][0].units,
+            )),
+            "mode" => {
+                let mut most = (Vec::with_capacity(a.len().max(1)), 0);
+                for i in a.iter().flatten() {
+                    let mut count = 0;
+                    for j in a.iter().flatten() {
+                        if i == j {
+                            count += 1;
+                        }
+                    }
+                    if count > most.1 {
+                        most.0.clear();
+                        most.0.push(i.clone());
+                        most.1 = count;
+                    }
+                    if count == most.1 && !most.0.iter().any(|j| i == j) {
+                        most.0.push(i.clone())
+                    }
+                }
+                if most.0.len() == 1 {
+                    NumStr::new(most.0[0].clone())
+                } else {
+                    Vector(most.0)
+                }
+            }
+            "median" => {
+                let a = sort(a.iter().flatten().cloned().collect::<Vec<Number>>());
+                if a.len() % 2 == 0 {
+                    Vector(vec![a[a.len() / 2 - 1].clone(), a[a.len() / 2].clone()])
+                } else {
+                    NumStr::new(a[a.len() / 2].clone())
+                }
+            }
+            "all" => {
+                let mut res = true;
+                for a in a.iter().flatten() {
+                    if !(a.number.imag().is_zero() && a.number.real() == &1) {
+                        res = false
+                    }
+                }
+                NumStr::new(Number::from(
+                    Complex::with_val(options.precision(), res as u8),
+                    None,
+                ))
+            }
+            "any" => {
+                let mut res = false;
+                for a in a.iter().flatten() {
+                    if a.number.imag().is_zero() && a.number.real() == &1 {
+                        res = true
+                    }
+                }
+                NumStr::new(Number::from(
+                    Complex::with_val(options.precision(), res as u8),
+                    None,
+                ))
+            }
+            "eigenvalues" => {
+                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    function.remove(i + 1);
+                    eigenvalues(&a, true)?
+                } else {
+                    eigenvalues(&a, false)?
+                }
+            }
+            "eigenvectors" => {
+                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    function.remove(i + 1);
+                    eigenvectors(&a, true)?
+                } else {
+                    eigenvectors(&a, false)?
+                }
+            }
+            "rref" => Matrix(rref(a)?),
+            "ker" => Matrix(kernel(a)?),
+            "ran" => Matrix(range(a)?),
+            "generalized_eigenvectors" => {
+                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    function.remove(i + 1);
+                    generalized_eigenvectors(&a, true)?
+                } else {
+                    generalized_eigenvectors(&a, false)?
+                }
+            }
+            "rcf" => rcf(a)?,
+            "jcf" => jcf(a)?,
+            "change_basis" => {
+                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    let beta = function.remove(i + 1).mat()?;
+                    if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                        change_basis(a, &beta, &function.remove(i + 1).mat()?)?
+                    } else {
+                        let i = identity(a.len(), a[0][0].number.prec().0);
+                        change_basis(a, &i, &beta)?
+                    }
+                } else {
+                    return Err(Error::Arity("missing arg", None));
+                }
+            }
+            "linsolve" => {
+                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    let b = function.remove(i + 1).vec()?;
+                    linsolve(&a, &b)?
+                } else {
+                    return Err(Error::Arity("missing arg", None));
+                }
+            }
+            "lstsq" => {
+                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    let (x, r) = lstsq(&a, &function.remove(i + 1).vec()?)?;
+                    if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                        let mode = function.remove(i + 1).num()?.number;
+                        if mode == 0 {
+                            Vector(x)
+                        } else if mode == 1 {
+                            NumStr::new(r)
+                        } else {
+                            return Err(Error::Domain("lstsq mode must be 0 or 1", None));
+                        }
+                    } else {
+                        Vector(x)
+                    }
+                } else {
+                    return Err(Error::Arity("missing arg", None));
+                }
+            }
+            "coordinate" => {
+                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    let v = function.remove(i + 1).vec()?;
+                    coordinate(v, a)?
+                } else {
+                    return Err(Error::Arity("missing arg", None));
+                }
+            }
+            "rank" => {
+                let tol = if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    Some(function.remove(i + 1).num()?.number.real().clone())
+                } else {
+                    None
+                };
+                let rank = rank(&a, tol)?;
+                NumStr::new(Number::from(
+                    Complex::with_val(a[0][0].number.prec(), rank),
+                    None,
+                ))
+            }
+            "lu" => {
+                let [p, l, u] = lu(&a)?;
+                factors(function, i, vec![p, l, u])?
+            }
+            "qr" => {
+                let [q, r] = qr(&a)?;
+                factors(function, i, vec![q, r])?
+            }
+            "schur" => {
+                let [q, t] = schur(&a)?;
+                factors(function, i, vec![q, t])?
+            }
+            "svd" => {
+                let (u, s, v) = svd(&a)?;
+                if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    let prec = a[0][0].number.prec().0;
+                    let mut d =
+                        vec![vec![Number::from(Complex::new(prec), None); s.len()]; s.len()];
+                    for (i, (r, s)) in d.iter_mut().zip(s).enumerate() {
+                        r[i] = s
+                    }
+                    factors(function, i, vec![u, d, v])?
+                } else {
+                    Vector(s)
+                }
+            }
+            "chol" | "cholesky" => Matrix(chol(&a)?),
+            "pinv" => {
+                let tol = if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+                    Some(function.remove(i + 1).num()?.number.real().clone())
+                } else {
+                    None
+                };
+                Matrix(pinv(&a, tol)?)
+            }
+            "expm" => Matrix(expm(&a)?),
+            "logm" => Matrix(logm(&a)?),
+            "sqrtm" => Matrix(sqrtm(&a)?),
+            "sinm" => Matrix(sinm(&a)?),
+            "cosm" => Matrix(cosm(&a)?),
+            "cond" => NumStr::new(cond(&a)?),
+            "norm2" => NumStr::new(norm2(&a)?),
+            "null" => NumStr::new(Number::from(
+                Complex::with_val(a[0][0].number.prec(), kernel(a)?.len()),
+                None,
+            )),
+            "to_list" => {
+                let mut vec = Vec::new();
+                for a in a {
+                    if a.len() != 2 {
+                        return Err(Error::Type("bad list", None));
+                    }
+                    for _ in 0..a[1]
+                        .number
+                        .real()
+                        .to_integer()
+                        .unwrap_or_default()
+                        .to_usize()
+                        .unwrap_or_default()
+                    {
+                        vec.push(a[0].clone())
+                    }
+                }
+                if vec.is_empty() {
+                    return Err(Error::Type("bad list", None));
+                }
+                Vector(sort(vec))
+            }
+            "to_freq" => {
+                if a.is_empty() {
+                    return Err(Error::Type("bad list", None));
+                }
+                let mut a = sort(a.into_iter().flatten().collect::<Vec<Number>>());
+                let mut last = a[0].clone();
+                let mut count = 1;
+                a.remove(0);
+                let mut mat = Vec::new();
+                for a in a {
+                    if a != last {
+                        mat.push(vec![
+                            last.clone(),
+                            Number::from(Complex::with_val(options.precision(), count), None),
+                        ]);
+                        last = a;
+                        count = 0;
+                    }
+                    count += 1;
+                }
+                mat.push(vec![
+                    last.clone(),
+                    Number::from(Complex::with_val(options.precision(), count), None),
+                ]);
+                Matrix(mat)
+            }
+            #[cfg(feature = "fastrand")]
+            "rand_weighted" => {
+                if a.iter().any(|a| a.len() != 2) {
+                    return Err(Error::Domain("bad data", None));
+                }
+                let mut sum = Integer::new();
+                for i in &a {
+                    sum += i[1].number.real().to_integer().unwrap_or_default();
+                }
+                let n = sum.to_u128().unwrap_or_default();
+                if n == 0 {
+                    return Err(Error::Domain("bad data", None));
+                }
+                let max = u128::MAX - u128::MAX.rem(n);
+                let mut rnd = u128::MAX;
+                while rnd >= max {
+                    rnd = fastrand::u128(..);
+                }
+                rnd = rnd.rem(n) + 1;
+                let mut num = Number::from(Complex::with_val(options.precision(), Nan), None);
+                for i in &a {
+                    rnd = rnd.saturating_sub(
+                        i[1].number
+                            .real()
+                            .to_integer()
+                            .unwrap_or_default()
+                            .to_u128()
+                            .unwrap_or_default(),
+                    );
+                    if rnd == 0 {
+                        num = i[0].clone();
+                        break;
+                    }
+                }
+                NumStr::new(num)
+            }
+            #[cfg(feature = "fastrand")]
+            "roll" => {
+                let mut sum: Integer = Integer::new();
+                for i in a {
+                    if i.len() != 2 {
+                        return Err(Error::Domain("bad dice data", None));
+                    }
+                    let a = i[0].number.real().to_integer().unwrap_or_default();
+                    if a > u128::MAX || a == 0 {
+                        return Err(Error::Domain("dice too large or bad dice data", None));
+                    }
+                    let n = a.to_u128().unwrap_or_default();
+                    let max = u128::MAX - u128::MAX.rem(n);
+                    let end = i[1]
+                        .number
+                        .real()
+                        .to_integer()
+                        .unwrap_or_default()
+                        .to_u128()
+                        .unwrap_or_default();
+                    let mut i = 0;
+                    while i < end {
+                        let rnd = fastrand::u128(..);
+                        if rnd < max {
+                            sum += rnd.rem(n) + 1;
+                            i += 1;
+                        }
+                    }
+                }
+                NumStr::new(Number::from(
+                    Complex::with_val(options.precision(), sum),
+                    None,
+                ))
+            }
+            "dice" => {
+                let mut faces = Vec::new();
+                for a in a {
+                    if a.len() != 2 {
+                        return Err(Error::Type("bad list", None));
+                    }
+                    for _ in 0..a[1]
+                        .number
+                        .real()
+                        .to_integer()
+                        .unwrap_or_default()
+                        .to_usize()
+                        .unwrap_or_default()
+                    {
+                        faces.push(
+                            a[0].number
+                                .real()
+                                .to_integer()
+                                .unwrap_or_default()
+                                .to_usize()
+                                .unwrap_or_default(),
+                        )
+                    }
+                }
+                if faces.iter().any(|c| c == &0) {
+                    return Err(Error::Domain("bad face value", None));
+                }
+                if faces.is_empty() {
+                    Vector(vec![Number::from(
+                        Complex::with_val(options.precision(), 1),
+                        None,
+                    )])
+                } else if faces.len() == 1 {
+                    Vector(vec![
+                        Number::from(
+                            Complex::with_val(options.precision(), 1),
+                            None
+                        );
+                        faces[0]
+                    ])
+                } else {
+                    let mut last = vec![Integer::from(1); faces[0]];
+                    let mut current = last.clone();
+                    for i in 1..faces.len() {
+                        current = Vec::new();
+                        for p in 0..=faces[0..=i].iter().sum::<usize>() - i - 1 {
+                            let value = last[(p + 1).saturating_sub(faces[i])
+                                ..=p.min(faces[0..i].iter().sum::<usize>() - i)]
+                                .iter()
+                                .sum::<Integer>();
+                            current.push(value)
+                        }
+                        last.clone_from(&current)
+                    }
+                    Vector(
+                        current
+                            .iter()
+                            .map(|a| Number::from(Complex::with_val(options.precision(), a), None))
+                            .collect::<Vec<Number>>(),
+                    )
+                }
+            }
+            "poly" | "polynomial" => {
+                if i + 1 < function.len() {
+                    if a.is_empty() {
+                        NumStr::new(Number::from(Complex::new(options.precision()), None))
+                    } else {
+                        let x = function.remove(i + 1).num()?;
+                        let mut sum =
+                            vec![Number::from(Complex::new(options.precision()), None); a.len()];
+                        for (i, v) in transpose(&a).iter().rev().enumerate() {
+                            for (s, a) in sum.iter_mut().zip(v.iter().map(|a| {
+                                Number::from(
+                                    a.number.clone() * x.number.clone().pow(i),
+                                    mul_units(
+                                        a.units,
+                                        Some(x.units.unwrap_or_default().pow(i as f64)),
+                                    ),
+                                )
+                            })) {
+                                *s = add(s, &a)
+                            }
+                        }
+                        Vector(sum)
+                    }
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "norm_combine" => {
+                if a.is_empty() {
+                    return Err(Error::Dimension("empty vec", None));
+                }
+                let mut mu = Complex::new(options.precision());
+                let mut var = Complex::new(options.precision());
+                let mut ws = Complex::new(options.precision());
+                for d in &a {
+                    if d.len() < 2 {
+                        return Err(Error::Domain("not enough data", None));
+                    }
+                    if d.len() == 2 {
+                        mu += d[0].number.clone();
+                        var += d[1].number.clone().pow(2);
+                        ws += 1;
+                    } else {
+                        mu += d[0].number.clone() * d[2].number.clone();
+                        var += d[1].number.clone().pow(2) * d[2].number.clone();
+                        ws += d[2].number.clone();
+                    }
+                }
+                mu /= ws.clone();
+                for d in &a {
+                    if d.len() == 2 {
+                        var += (mu.clone() - d[0].number.clone()).pow(2);
+                    } else {
+                        var += (mu.clone() - d[0].number.clone()).pow(2) * d[2].number.clone();
+                    }
+                }
+                var /= ws;
+                Vector(vec![Number::from(mu, None), Number::from(var.sqrt(), None)])
+            }
+            _ => do_functions(arg, options, function, i, to_deg, s)?,
+        },
+        Vector(a) => match s {
+            "transpose" | "trans" => Matrix(transpose(&[a])),
+            "to_list" => {
+                let mut vec = Vec::new();
+                for (i, a) in a.iter().enumerate() {
+                    let num = Number::from(Complex::with_val(options.precision(), i + 1), None);
+                    for _ in 1..=a
+                        .number
+                        .real()
+                        .to_integer()
+                        .unwrap_or_default()
+                        .to_usize()
+                        .unwrap_or_default()
+                    {
+                        vec.push(num.clone())
+                    }
+                }
+                if vec.is_empty() {
+                    return Err(Error::Type("bad list", None));
+                }
+                Vector(sort(vec))
+            }
+            #[cfg(feature = "fastrand")]
+            "roll" => {
+                let mut sum: Integer = Integer::new();
+                let mut i = 0;
+                while i < a.len() {
+                    let a = a[i].number.real().to_integer().unwrap_or_default();
+                    if a > u128::MAX as f64 || a == 0 {
+                        return Err(Error::Domain("dice too large or bad dice data", None));
+                    }
+                    let n = a.to_u128().unwrap_or_default();
+                    let max = u128::MAX - u128::MAX.rem(n);
+                    let rnd = fastrand::u128(..);
+                    if rnd < max {
+                        sum += rnd.rem(n) + 1;
+                        i += 1;
+                    }
+                }
+                NumStr::new(Number::from(
+                    Complex::with_val(options.precision(), sum),
+                    None,
+                ))
+            }
+            "dice" => {
+                let faces = a
+                    .iter()
+                    .map(|c| {
+                        c.number
+                            .real()
+                            .to_integer()
+                            .unwrap_or_default()
+                            .to_usize()
+                            .unwrap_or_default()
+                    })
+                    .collect::<Vec<usize>>();
+                if faces.iter().any(|c| c == &0) {
+                    return Err(Error::Domain("bad face value", None));
+                }
+                let mut last = vec![Integer::from(1); faces[0]];
+                if a.is_empty() {
+                    Vector(vec![Number::from(
+                        Complex::with_val(options.precision(), 1),
+                        None,
+                    )])
+                } else if faces.len() == 1 {
+                    Vector(
+                        last.iter()
+                            .map(|a| Number::from(Complex::with_val(options.precision(), a), None))
+                            .collect::<Vec<Number>>(),
+                    )
+                } else {
+                    let mut current = Vec::new();
+                    for i in 1..faces.len() {
+                        current = Vec::new();
+                        for p in 0..=faces[0..=i].iter().sum::<usize>() - i - 1 {
+                            let value = last[(p + 1).saturating_sub(faces[i])
+                                ..=p.min(faces[0..i].iter().sum::<usize>() - i)]
+                                .iter()
+                                .sum::<Integer>();
+                            current.push(value)
+                        }
+                        last.clone_from(&current);
+                    }
+                    Vector(
+                        current
+                            .iter()
+                            .map(|a| Number::from(Complex::with_val(options.precision(), a), None))
+                            .collect::<Vec<Number>>(),
+                    )
+                }
+            }
+            "quartiles" => {
+                if a.len() < 2 {
+                    return Err(Error::Domain("not enough data", None));
+                }
+                let units = a[0].units;
+                let a = sort(a);
+                let half1 = &a[0..a.len() / 2];
+                let half2 = if a.len() % 2 == 0 {
+                    &a[a.len() / 2..a.len()]
+                } else {
+                    &a[a.len() / 2 + 1..a.len()]
+                };
+                if half1.len() % 2 == 0 {
+                    Vector(vec![
+                        Number::from(
+                            (half1[half1.len() / 2 - 1].number.clone()
+                                + half1[half1.len() / 2].number.clone())
+                                / 2,
+                            units,
+                        ),
+                        Number::from(
+                            if a.len() % 2 == 0 {
+                                (half1[half1.len().saturating_sub(1)].number.clone()
+                                    + half2[0].number.clone())
+                                    / 2
+                            } else {
+                                a[a.len() / 2].number.clone()
+                            },
+                            units,
+                        ),
+                        Number::from(
+                            (half2[half2.len() / 2 - 1].number.clone()
+                                + half2[half2.len() / 2].number.clone())
+                                / 2,
+                            units,
+                        ),
+                    ])
+                } else {
+                    Vector(vec![
+                        Number::from(half1[half1.len() / 2].number.clone(), units),
+                        Number::from(
+                            if a.len() % 2 == 0 {
+                                (half1[half1.len().saturating_sub(1)].number.clone()
+                                    + half2[0].number.clone())
+                                    / 2
+                            } else {
+                                a[a.len() / 2].number.clone()
+                            },
+                            units,
+                        ),
+                        Number::from(half2[half2.len() / 2].number.clone(), units),
+                    ])
+                }
+            }
+            "percentile" => {
+                if function.len() <= i + 1 {
+                    return Err(Error::Arity("not enough input", None));
+                }
+                let b = function.remove(i + 1).num()?.number;
+                let r: Float = (b.real().clone() / 100) * a.len();
+                let r = r
+                    .ceil()
+                    .to_integer()
+                    .unwrap_or_default()
+                    .to_usize()
+                    .unwrap_or_default();
+                if r > a.len() {
+                    return Err(Error::Parse("bad input", None));
+                }
+                NumStr::new(sort(a)[r.saturating_sub(1)].clone())
+            }
+            "percentilerank" => {
+                if function.len() <= i + 1 {
+                    return Err(Error::Arity("not enough input", None));
+                }
+                let mut cf = 0;
+                let mut f = 0;
+                let b = function.remove(i + 1).num()?.number;
+                for a in sort(a.clone()) {
+                    if a.number.real() < b.real() {
+                        cf += 1;
+                    } else if a.number == b {
+                        f += 1;
+                    } else {
+                        break;
+                    }
+                }
+                NumStr::new(Number::from(
+                    100 * (cf + Complex::with_val(options.precision(), f) / 2) / a.len(),
+                    None,
+                ))
+            }
+            "to_freq" => {
+                if a.is_empty() {
+                    return Err(Error::Type("bad list", None));
+                }
+                let mut a = sort(a);
+                let mut last = a[0].clone();
+                let mut count = 1;
+                a.remove(0);
+                let mut mat = Vec::new();
+                for a in a {
+                    if a != last {
+                        mat.push(vec![
+                            last.clone(),
+                            Number::from(Complex::with_val(options.precision(), count), None),
+                        ]);
+                        last = a;
+                        count = 0;
+                    }
+                    count += 1;
+                }
+                mat.push(vec![
+                    last.clone(),
+                    Number::from(Complex::with_val(options.precision(), count), None),
+                ]);
+                Matrix(mat)
+            }
+            "kurtosis" => {
+                fn quar(z: Complex) -> Complex {
+                    if z.imag().is_zero() {
+                        z.pow(4)
+                    } else {
+                        z.clone() * &z * &z * z
+                    }
+                }
+                let mean = a
+                    .iter()
+                    .fold(Complex::new(options.precision()), |sum, val| {
+                        sum + val.number.clone()
+                    })
+                    / a.len();
+                NumStr::new(Number::from(
+                    a.iter()
+                        .fold(Complex::new(options.precision()), |sum, val| {
+                            sum + quar(val.number.clone() - mean.clone())
+                        })
+                        / a.len()
+                        / sqr(variance(&a, Some(mean.clone()), options.precision()).number)
+                        - 3,
+                    None,
+                ))
+            }
+            "skew" | "skewness" => {
+                let mean = a
+                    .iter()
+                    .fold(Complex::new(options.precision()), |sum, val| {
+                        sum + val.number.clone()
+                    })
+                    / a.len();
+                NumStr::new(Number::from(
+                    a.iter()
+                        .fold(Complex::new(options.precision()), |sum, val| {
+                            sum + cube(val.number.clone() - mean.clone())
+                        })
+                        / a.len()
+                        / cube(
+                            variance(&a, Some(mean.clone()), options.precision())
+                                .number
+                                .sqrt(),
+                        ),
+                    None,
+                ))
+            }
+            "sd" | "standarddeviation" | "σ" => NumStr::new(Number::from(
+                variance(&a, None, options.precision()).number.sqrt(),
+                a[0].units,
+            )),
+            "variance" | "var" => NumStr::new(variance(&a, None, options.precision())),
+            "covariance" | "cov" => {
+                let mut sum = Complex::new(options.precision());
+                if function.len() <= i + 1 {
+                    return Err(Error::Arity("not enough input", None));
+                }
+                let b = function.remove(i + 1).vec()?;
+                if a.len() != b.len() {
+                    return Err(Error::Dimension("different sized data sets", None));
+                }
+                let ma = a
+                    .iter()
+                    .fold(Complex::new(options.precision()), |sum, val| {
+                        sum + val.number.clone()
+                    })
+                    / a.len();
+                let mb = b
+                    .iter()
+                    .fold(Complex::new(options.precision()), |sum, val| {
+                        sum + val.number.clone()
+                    })
+                    / b.len();
+                for (a, b) in a.iter().zip(b.iter()) {
+                    sum += (a.number.clone() - ma.clone()) * (b.number.clone() - mb.clone());
+                }
+                NumStr::new(Number::from(
+                    sum / (a.len() - 1),
+                    mul_units(a[0].units, b[0].units),
+                ))
+            }
+            "all" => {
+                let mut res = true;
+                for a in a {
+                    if !(a.number.imag().is_zero() && a.number.real() == &1) {
+                        res = false
+                    }
+                }
+                NumStr::new(Number::from(
+                    Complex::with_val(options.precision(), res as u8),
+                    None,
+                ))
+            }
+            "any" => {
+                let mut res = false;
+                for a in a {
+                    if a.number.imag().is_zero() && a.number.real() == &1 {
+                        res = true
+                    }
+                }
+                NumStr::new(Number::from(
+                    Complex::with_val(options.precision(), res as u8),
+                    None,
+                ))
+            }
+            "sort" => Vector(sort(a)),
+            "polyroots" => Vector(
+                polyroots(
+                    a.into_iter().rev().map(|a| a.number).collect(),
+                    options.precision(),
+                )?
+                .into_iter()
+                .map(|a| Number::from(a, None))
+                .collect(),
+            ),
+            "hsv_to_rgb" => {
+                if a.len() == 3 {
+                    Vector(hsv2rgb(
+                        a[0].number.real().clone(),
+                        a[1].number.real().clone(),
+                        a[2].number.real().clone(),
+                    ))
+                } else {
+                    return Err(Error::Dimension("not 3 length", None));
+                }
+            }
+            "mean" | "μ" => NumStr::new(Number::from(
+                a.iter()
+                    .fold(Complex::new(options.precision()), |sum, val| {
+                        sum + val.number.clone()
+                    })
+                    / a.len(),
+                a[0].units,
+            )),
+            "geo_mean" => NumStr::new(Number::from(
+                pow_nth(
+                    a.iter()
+                        .fold(Complex::with_val(options.precision(), 1), |sum, val| {
+                            sum * val.number.clone()
+                        }),
+                    Complex::with_val(options.precision(), 1) / a.len(),
+                ),
+                a[0].units,
+            )),
+            "median" => {
+                let a = sort(a);
+                if a.len() % 2 == 0 {
+                    NumStr::new(Number::from(
+                        (a[a.len() / 2 - 1].number.clone() + a[a.len() / 2].number.clone()) / 2,
+                        a[0].units,
+                    ))
+                } else {
+                    NumStr::new(Number::from(a[a.len() / 2].number.clone(), a[0].units))
+                }
+            }
+            "mode" => {
+                let mut most = (Vec::new(), 0);
+                for i in &a {
+                    let mut count = 0;
+                    for j in &a {
+                        if i == j {
+                            count += 1;
+                        }
+                    }
+                    if count > most.1 {
+                        most = (vec![i.clone()], count);
+                    }
+                    if count == most.1 && !most.0.iter().any(|j| i == j) {
+                        most.0.push(i.clone())
+                    }
+                }
+                if most.0.len() == 1 {
+                    NumStr::new(most.0[0].clone())
+                } else {
+                    Vector(most.0)
+                }
+            }
+            "max" => {
+                let mut max = a[0].clone();
+                for i in a {
+                    if i.number.real() > max.number.real() {
+                        max = i
+                    }
+                }
+                NumStr::new(max)
+            }
+            "min" => {
+                let mut min = a[0].clone();
+                for i in a {
+                    if i.number.real() < min.number.real() {
+                        min = i
+                    }
+                }
+                NumStr::new(min)
+            }
+            "reverse" => Vector(a.iter().rev().cloned().collect()),
+            "len" => NumStr::new(Number::from(
+                Complex::with_val(options.precision(), a.len()),
+                None,
+            )),
+            "norm" => {
+                let units = a[0].units;
+                let mut n = Complex::new(options.precision());
+                for i in a {
+                    n += sqr(i.number.abs());
+                }
+                NumStr::new(Number::from(n.sqrt(), units))
+            }
+            "normalize" => {
+                let mut n = Complex::new(options.precision());
+                for i in a.clone() {
+                    n += sqr(i.number);
+                }
+                Vector(
+                    a.iter()
+                        .map(|x| Number::from(x.number.clone() / n.clone().sqrt(), None))
+                        .collect(),
+                )
+            }
+            "car" | "cartesian" => {
+                if a.len() == 2 {
+                    let t = a[1].number.clone() / to_deg.clone();
+                    Vector(vec![
+                        Number::from(a[0].number.clone() * t.clone().cos(), a[0].units),
+                        Number::from(a[0].number.clone() * t.clone().sin(), a[0].units),
+                    ])
+                } else if a.len() == 3 {
+                    let t1 = a[1].number.clone() / to_deg.clone();
+                    let t2 = a[2].number.clone() / to_deg.clone();
+                    Vector(vec![
+                        Number::from(
+                            a[0].number.clone() * t1.clone().sin() * t2.clone().cos(),
+                            a[0].units,
+                        ),
+                        Number::from(
+                            a[0].number.clone() * t1.clone().sin() * t2.clone().sin(),
+                            a[0].units,
+                        ),
+                        Number::from(a[0].number.clone() * t1.clone().cos(), a[0].units),
+                    ])
+                } else {
+                    return Err(Error::Parse("incorrect polar form", None));
+                }
+            }
+            "polar" | "pol" => Vector(to_polar(a.clone(), to_deg.clone())),
+            "cyl" | "cylinder" => Vector(to_cyl(a.clone(), to_deg.clone())),
+            "angle" => {
+                if function.len() > i + 1 {
+                    let b = function.remove(i + 1).vec()?;
+                    if a.len() == 3 && b.len() == 3 {
+                        let c: Complex = sqr(a[0].number.clone())
+                            + sqr(a[1].number.clone())
+                            + sqr(a[2].number.clone());
+                        let d: Complex = sqr(b[0].number.clone())
+                            + sqr(b[1].number.clone())
+                            + sqr(b[2].number.clone());
+                        NumStr::new(Number::from(
+                            ((a[0].number.clone() * b[0].number.clone()
+                                + a[1].number.clone() * b[1].number.clone()
+                                + a[2].number.clone() * b[2].number.clone())
+                                / (c.sqrt() * d.sqrt()))
+                            .acos()
+                                * to_deg.clone(),
+                            Some(Units {
+                                angle: 1.0,
+                                ..Units::default()
+                            }),
+                        ))
+                    } else if a.len() == 2 && b.len() == 2 {
+                        let c: Complex = sqr(a[0].number.clone()) + sqr(a[1].number.clone());
+                        let d: Complex = sqr(b[0].number.clone()) + sqr(b[1].number.clone());
+                        NumStr::new(Number::from(
+                            ((a[0].number.clone() * b[0].number.clone()
+                                + a[1].number.clone() * b[1].number.clone())
+                                / (c.sqrt() * d.sqrt()))
+                            .acos()
+                                * to_deg.clone(),
+                            Some(Units {
+                                angle: 1.0,
+                                ..Units::default()
+                            }),
+                        ))
+                    } else {
+                        return Err(Error::Domain("cant decern angles", None));
+                    }
+                } else {
+                    let vec = to_polar(a, to_deg.clone());
+                    if vec.len() == 3 {
+                        Vector(vec[1..=2].to_vec())
+                    } else if vec.len() == 2 {
+                        NumStr::new(vec[1].clone())
+                    } else {
+                        return Err(Error::Domain("cant decern angles", None));
+                    }
+                }
+            }
+            "cross" => {
+                if function.len() > i + 1 {
+                    let b = function.remove(i + 1).vec()?;
+                    let units = mul_units(a[0].units, b[0].units);
+                    if a.len() == 3 && b.len() == 3 {
+                        Vector(vec![
+                            Number::from(
+                                a[1].number.clone() * &b[2].number
+                                    - a[2].number.clone() * &b[1].number,
+                                units,
+                            ),
+                            Number::from(
+                                a[2].number.clone() * &b[0].number
+                                    - a[0].number.clone() * &b[2].number,
+                                units,
+                            ),
+                            Number::from(
+                                a[0].number.clone() * &b[1].number
+                                    - a[1].number.clone() * &b[0].number,
+                                units,
+                            ),
+                        ])
+                    } else if a.len() == 2 && b.len() == 2 {
+                        NumStr::new(Number::from(
+                            a[0].number.clone() * &b[1].number - a[1].number.clone() * &b[0].number,
+                            units,
+                        ))
+                    } else {
+                        return Err(Error::Dimension("cant cross", None));
+                    }
+                } else {
+                    return Err(Error::Arity("no args", None));
+                }
+            }
+            "project" | "proj" => {
+                if function.len() > i + 1 {
+                    let b = function.remove(i + 1).clone().vec()?;
+                    if b.len() == a.len() {
+                        let mut dot = Complex::new(options.precision());
+                        for i in a
+                            .iter()
+                            .zip(b.iter())
+                            .map(|(a, b)| a.number.clone() * b.number.clone())
+                        {
+                            dot += i;
+                        }
+                        let mut norm = Complex::new(options.precision());
+                        for i in &b {
+                            norm += sqr(i.number.clone().abs());
+                        }
+                        Vector(
+                            b.iter()
+                                .map(|n| {
+                                    Number::from(
+                                        dot.clone() / norm.clone() * n.number.clone(),
+                                        n.units,
+                                    )
+                                })
+                                .collect::<Vec<Number>>(),
+                        )
+                    } else {
+                        return Err(Error::Dimension("cant project", None));
+                    }
+                } else {
+                    return Err(Error::Arity("no args", None));
+                }
+            }
+            "oproject" | "oproj" => {
+                if function.len() > i + 1 {
+                    let b = function.remove(i + 1).clone().vec()?;
+                    if b.len() == a.len() {
+                        let mut dot = Complex::new(options.precision());
+                        for i in a
+                            .iter()
+                            .zip(b.iter())
+                            .map(|(a, b)| a.number.clone() * b.number.clone())
+                        {
+                            dot += i;
+                        }
+                        let mut norm = Complex::new(options.precision());
+                        for i in &b {
+                            norm += sqr(i.number.clone().abs());
+                        }
+                        Vector(
+                            b.iter()
+                                .zip(a.iter())
+                                .map(|(n, a)| {
+                                    Number::from(
+                                        a.number.clone()
+                                            - dot.clone() / norm.clone() * n.number.clone(),
+                                        a.units,
+                                    )
+                                })
+                                .collect::<Vec<Number>>(),
+                        )
+                    } else {
+                        return Err(Error::Dimension("cant project", None));
+                    }
+                } else {
+                    return Err(Error::Arity("no args", None));
+                }
+            }
+            "dot" => {
+                if function.len() > i + 1 {
+                    let mut n = Complex::new(options.precision());
+                    let b = function.remove(i + 1).vec()?;
+                    for i in a
+                        .iter()
+                        .zip(b.iter())
+                        .map(|(a, b)| a.number.clone() * b.number.clone())
+                    {
+                        n += i;
+                    }
+                    NumStr::new(Number::from(n, mul_units(a[0].units, b[0].units)))
+                } else {
+                    return Err(Error::Arity("no args", None));
+                }
+            }
+            "part" => {
+                if function.len() > i + 1 {
+                    match function.remove(i + 1) {
+                        Num(b) => {
+                            let b = b.number;
+                            let n = b
+                                .clone()
+                                .real()
+                                .to_integer()
+                                .unwrap_or_default()
+                                .to_usize()
+                                .unwrap_or_default();
+                            if n < a.len() {
+                                NumStr::new(a[n].clone())
+                            } else {
+                                return Err(Error::Domain("out of range", None));
+                            }
+                        }
+                        Vector(b) => {
+                            let mut vec = Vec::new();
+                            for i in b {
+                                let n = i
+                                    .number
+                                    .clone()
+                                    .real()
+                                    .to_integer()
+                                    .unwrap_or_default()
+                                    .to_usize()
+                                    .unwrap_or_default();
+                                if n < a.len() {
+                                    vec.push(a[n].clone());
                                 } else {
-                                    return Err("not enough args");
+                                    return Err(Error::Domain("out of range", None));
                                 }
                             }
-                            #[cfg(feature = "fastrand")]
-                            "rand_neg_hypergeometric" => {
-                                if i + 2 < function.len() {
-                                    let mut pop = arg.num()?.number.real().clone();
-                                    let mut success =
-                                        function.remove(i + 1).num()?.number.real().clone();
-                                    let mut fails =
-                                        function.remove(i + 1).num()?.number.real().clone();
-                                    let mut sum = Integer::new();
-                                    while fails > 0 {
-                                        if success.clone() / pop.clone()
-                                            > Float::with_val(options.prec, fastrand::u128(..))
-                                                / u128::MAX
-                                        {
-                                            sum += 1;
-                                            success -= 1;
-                                        } else {
-                                            fails -= 1
-                                        }
-                                        pop -= 1;
-                                    }
-                                    NumStr::new(Number::from(
-                                        Complex::with_val(options.prec, sum),
+                            Vector(vec)
+                        }
+                        _ => return Err(Error::Type("non num/vec", None)),
+                    }
+                } else {
+                    return Err(Error::Arity("no args", None));
+                }
+            }
+            "split" => Matrix(
+                a.iter()
+                    .map(|a| {
+                        vec![
+                            Number::from(a.number.real().clone().into(), None),
+                            Number::from(a.number.imag().clone().into(), None),
+                        ]
+                    })
+                    .collect::<Vec<Vec<Number>>>(),
+            ),
+            "uniq" => {
+                let mut a = a;
+                a.dedup();
+                Vector(a)
+            }
+            "factors" | "factor" => {
+                let mut fail = false;
+                let mut mat = Vec::new();
+                for num in a {
+                    let num = num.number;
+                    if num.imag().clone().is_zero() {
+                        if num.real().clone().fract().is_zero() {
+                            let mut vec = Vec::new();
+                            let n = num
+                                .real()
+                                .to_integer()
+                                .unwrap_or_default()
+                                .to_usize()
+                                .unwrap_or_default();
+                            for i in 1..=n {
+                                if n % i == 0 {
+                                    vec.push(Number::from(
+                                        Complex::with_val(options.precision(), i),
                                         None,
-                                    ))
-                                } else {
-                                    return Err("not enough args");
-                                }
-                            }
-                            "geometric_cdf" => {
-                                if i + 1 < function.len() {
-                                    let k = arg.num()?.number;
-                                    let p = function.remove(i + 1).num()?.number;
-                                    let q: Complex = 1 - p.clone();
-                                    NumStr::new(Number::from(1 - pow_nth(q, k), None))
-                                } else {
-                                    return Err("not enough args");
+                                    ));
                                 }
                             }
-                            "geometric_pmf" => {
-                                if i + 1 < function.len() {
-                                    let k = arg.num()?.number;
-                                    let p = function.remove(i + 1).num()?.number;
-                                    let q: Complex = 1 - p.clone();
-                                    NumStr::new(Number::from(pow_nth(q, k - 1) * p, None))
-                                } else {
-                                    return Err("not enough args");
+                            mat.push(vec);
+                        } else {
+                            fail = true;
+                            break;
+                        }
+                    } else {
+                        fail = true;
+                        break;
+                    }
+                }
+                if fail {
+                    NumStr::new(Number::from(
+                        Complex::with_val(options.precision(), Nan),
+                        None,
+                    ))
+                } else {
+                    Matrix(mat)
+                }
+            }
+            "union" => {
+                if function.len() > i + 1 {
+                    if let Vector(b) = function.remove(i + 1) {
+                        let mut a = a;
+                        a.extend(b);
+                        a = sort(a);
+                        a.dedup();
+                        Vector(a)
+                    } else {
+                        return Err(Error::Type("arg not vector", None));
+                    }
+                } else {
+                    return Err(Error::Type("arg not vector", None));
+                }
+            }
+            "intersection" => {
+                if function.len() > i + 1 {
+                    if let Vector(b) = function.remove(i + 1) {
+                        let mut v = Vec::new();
+                        'main: for n1 in a {
+                            for n2 in &b {
+                                if &n1 == n2 {
+                                    v.push(n2.clone());
+                                    continue 'main;
                                 }
                             }
-                            "quartic" => {
-                                if i + 5 < function.len() {
-                                    let a = arg.num()?;
-                                    let b = function.remove(i + 1).num()?;
-                                    let c = function.remove(i + 1).num()?;
-                                    let d = function.remove(i + 1).num()?;
-                                    let e = function.remove(i + 1).num()?;
-                                    function.remove(i + 1);
-                                    let n = quartic(a, b, c, d, e, true);
-                                    if n.len() == 1 {
-                                        NumStr::new(n[0].clone())
-                                    } else {
-                                        Vector(n)
-                                    }
-                                } else if i + 4 < function.len() {
-                                    let a = arg.num()?;
-                                    let b = function.remove(i + 1).num()?;
-                                    let c = function.remove(i + 1).num()?;
-                                    let d = function.remove(i + 1).num()?;
-                                    let e = function.remove(i + 1).num()?;
-                                    let n = quartic(a, b, c, d, e, false);
-                                    if n.len() == 1 {
-                                        NumStr::new(n[0].clone())
-                                    } else {
-                                        Vector(n)
-                                    }
-                                } else if i + 3 < function.len() {
-                                    let b = arg.num()?;
-                                    let c = function.remove(i + 1).num()?;
-                                    let d = function.remove(i + 1).num()?;
-                                    let e = function.remove(i + 1).num()?;
-                                    let n = quartic(
-                                        Number::from(Complex::with_val(options.prec, 1), None),
-                                        b,
-                                        c,
-                                        d,
-                                        e,
-                                        false,
-                                    );
-                                    if n.len() == 1 {
-                                        NumStr::new(n[0].clone())
-                                    } else {
-                                        Vector(n)
-                                    }
-                                } else {
-                                    return Err("not enough args");
+                        }
+                        Vector(v)
+                    } else {
+                        return Err(Error::Type("arg not vector", None));
+                    }
+                } else {
+                    return Err(Error::Type("arg not vector", None));
+                }
+            }
+            "set_difference" => {
+                if function.len() > i + 1 {
+                    if let Vector(b) = function.remove(i + 1) {
+                        let mut v = Vec::new();
+                        'main: for n1 in a {
+                            for n2 in &b {
+                                if &n1 == n2 {
+                                    continue 'main;
                                 }
                             }
-                            "cubic" => {
-                                if i + 4 < function.len() {
-                                    let a = arg.num()?;
-                                    let b = function.remove(i + 1).num()?;
-                                    let c = function.remove(i + 1).num()?;
-                                    let d = function.remove(i + 1).num()?;
-                                    function.remove(i + 1);
-                                    let n = cubic(a, b, c, d, true);
-                                    if n.len() == 1 {
-                                        NumStr::new(n[0].clone())
-                                    } else {
-                                        Vector(n)
-                                    }
-                                } else if i + 3 < function.len() {
-                                    let a = arg.num()?;
-                                    let b = function.remove(i + 1).num()?;
-                                    let c = function.remove(i + 1).num()?;
-                                    let d = function.remove(i + 1).num()?;
-                                    let n = cubic(a, b, c, d, false);
-                                    if n.len() == 1 {
-                                        NumStr::new(n[0].clone())
-                                    } else {
-                                        Vector(n)
-                                    }
-                                } else if i + 2 < function.len() {
-                                    let b = arg.num()?;
-                                    let c = function.remove(i + 1).num()?;
-                                    let d = function.remove(i + 1).num()?;
-                                    let n = cubic(
-                                        Number::from(Complex::with_val(options.prec, 1), None),
-                                        b,
-                                        c,
-                                        d,
-                                        false,
-                                    );
-                                    if n.len() == 1 {
-                                        NumStr::new(n[0].clone())
-                                    } else {
-                                        Vector(n)
-                                    }
-                                } else {
-                                    return Err("not enough args");
+                            v.push(n1);
+                        }
+                        Vector(v)
+                    } else {
+                        return Err(Error::Type("arg not vector", None));
+                    }
+                } else {
+                    return Err(Error::Type("arg not vector", None));
+                }
+            }
+            "symmetric_difference" => {
+                if function.len() > i + 1 {
+                    if let Vector(b) = function.remove(i + 1) {
+                        let mut a1 = Vec::new();
+                        'main: for n1 in a.clone() {
+                            for n2 in &b {
+                                if &n1 == n2 {
+                                    continue 'main;
                                 }
                             }
-                            "quad" | "quadratic" => {
-                                if i + 3 < function.len() {
-                                    let a = arg.num()?;
-                                    let b = function.remove(i + 1).num()?;
-                                    let c = function.remove(i + 1).num()?;
-                                    function.remove(i + 1);
-                                    let n = quadratic(a, b, c, true);
-                                    if n.is_empty() {
-                                        NumStr::new(Number::from(
-                                            Complex::with_val(options.prec, Nan),
-                                            None,
-                                        ))
-                                    } else if n.len() == 1 {
-                                        NumStr::new(n[0].clone())
-                                    } else {
-                                        Vector(n)
-                                    }
-                                } else if i + 2 < function.len() {
-                                    let a = arg.num()?;
-                                    let b = function.remove(i + 1).num()?;
-                                    let c = function.remove(i + 1).num()?;
-                                    let n = quadratic(a, b, c, false);
-                                    if n.len() == 1 {
-                                        NumStr::new(n[0].clone())
-                                    } else {
-                                        Vector(n)
-                                    }
-                                } else if i + 1 < function.len() {
-                                    let b = arg.num()?;
-                                    let c = function.remove(i + 1).num()?;
-                                    let n = quadratic(
-                                        Number::from(Complex::with_val(options.prec, 1), None),
-                                        b,
-                                        c,
-                                        false,
-                                    );
-                                    if n.len() == 1 {
-                                        NumStr::new(n[0].clone())
-                                    } else {
-                                        Vector(n)
-                                    }
-                                } else {
-                                    return Err("not enough args");
+                            a1.push(n1);
+                        }
+                        let mut a2 = Vec::new();
+                        'main: for n1 in b.clone() {
+                            for n2 in &a {
+                                if &n1 == n2 {
+                                    continue 'main;
                                 }
                             }
-                            "cossin" => {
-                                let (a, b) = arg.num()?.number.sin_cos(Complex::new(options.prec));
-                                Vector(vec![Number::from(b, None), Number::from(a, None)])
-                            }
-                            "sincos" => {
-                                let (a, b) = arg.num()?.number.sin_cos(Complex::new(options.prec));
-                                Vector(vec![Number::from(a, None), Number::from(b, None)])
-                            }
-                            "split" => {
-                                let a = arg.num()?.number;
-                                Vector(vec![
-                                    Number::from(a.real().clone().into(), None),
-                                    Number::from(a.imag().clone().into(), None),
-                                ])
-                            }
-                            "ceil" => {
-                                let a = arg.num()?;
-                                let units = a.units;
-                                let m = if i + 1 < function.len() {
-                                    Complex::with_val(a.number.prec(), options.base.1)
-                                        .pow(function.remove(i + 1).num()?.number)
-                                } else {
-                                    Complex::with_val(a.number.prec(), 1)
-                                };
-                                let a = a.number * m.clone();
-                                NumStr::new(Number::from(
-                                    Complex::with_val(
-                                        options.prec,
-                                        (a.real().clone().ceil(), a.imag().clone().ceil()),
-                                    ) / m,
-                                    units,
-                                ))
-                            }
-                            "floor" => {
-                                let a = arg.num()?;
-                                let units = a.units;
-                                let m = if i + 1 < function.len() {
-                                    Complex::with_val(a.number.prec(), options.base.1)
-                                        .pow(function.remove(i + 1).num()?.number)
-                                } else {
-                                    Complex::with_val(a.number.prec(), 1)
-                                };
-                                let a = a.number * m.clone();
-                                NumStr::new(Number::from(
-                                    Complex::with_val(
-                                        options.prec,
-                                        (a.real().clone().floor(), a.imag().clone().floor()),
-                                    ) / m,
-                                    units,
-                                ))
+                            a2.push(n1);
+                        }
+                        a2.extend(a1);
+                        let mut u = sort(a2);
+                        u.dedup();
+                        Vector(u)
+                    } else {
+                        return Err(Error::Type("arg not vector", None));
+                    }
+                } else {
+                    return Err(Error::Type("arg not vector", None));
+                }
+            }
+            "cartesian_product" => {
+                if function.len() > i + 1 {
+                    if let Vector(b) = function.remove(i + 1) {
+                        let mut m = Vec::new();
+                        for n1 in a {
+                            for n2 in b.clone() {
+                                m.push(vec![n1.clone(), n2])
                             }
-                            "round" => {
-                                let a = arg.num()?;
-                                let units = a.units;
-                                let m = if i + 1 < function.len() {
-                                    Complex::with_val(a.number.prec(), options.base.1)
-                                        .pow(function.remove(i + 1).num()?.number)
-                                } else {
-                                    Complex::with_val(a.number.prec(), 1)
-                                };
-                                let a = a.number * m.clone();
-                                NumStr::new(Number::from(
-                                    Complex::with_val(
-                                        options.prec,
-                                        (a.real().clone().round(), a.imag().clone().round()),
-                                    ) / m,
-                                    units,
-                                ))
+                        }
+                        Matrix(m)
+                    } else {
+                        return Err(Error::Type("arg not vector", None));
+                    }
+                } else {
+                    return Err(Error::Type("arg not vector", None));
+                }
+            }
+            "power_set" => {
+                let mut m = Vec::new();
+                for i in 0..1 << a.len() {
+                    let mut v = Vec::new();
+                    for (j, n) in a.iter().enumerate() {
+                        if (i >> j) & 1 == 1 {
+                            v.push(n.clone())
+                        }
+                    }
+                    m.push(v);
+                }
+                Matrix(m)
+            }
+            "set_fix" => {
+                let mut a = sort(a);
+                a.dedup();
+                Vector(a)
+            }
+            "subset" => {
+                if function.len() > i + 1 {
+                    if let Vector(b) = function.remove(i + 1) {
+                        let mut ainb = true;
+                        'main: for n1 in b {
+                            for n2 in &a {
+                                if &n1 == n2 {
+                                    continue 'main;
+                                }
                             }
-                            "int" | "trunc" => {
-                                let a = arg.num()?;
-                                let units = a.units;
-                                let m = if i + 1 < function.len() {
-                                    Complex::with_val(a.number.prec(), options.base.1)
-                                        .pow(function.remove(i + 1).num()?.number)
-                                } else {
-                                    Complex::with_val(a.number.prec(), 1)
-                                };
-                                let a = a.number * m.clone();
-                                NumStr::new(Number::from(
-                                    Complex::with_val(
-                                        options.prec,
-                                        (a.real().clone().trunc(), a.imag().clone().trunc()),
-                                    ) / m,
-                                    units,
-                                ))
+                            ainb = false;
+                        }
+                        NumStr::new(Number::from(
+                            Complex::with_val(options.precision(), ainb as u8),
+                            None,
+                        ))
+                    } else {
+                        return Err(Error::Type("arg not vector", None));
+                    }
+                } else {
+                    return Err(Error::Type("arg not vector", None));
+                }
+            }
+            "element" => {
+                if function.len() > i + 1 {
+                    if let Num(b) = function.remove(i + 1) {
+                        let mut ainb = false;
+                        for n2 in &a {
+                            if &*b == n2 {
+                                ainb = true;
                             }
-                            "frac" | "fract" => {
-                                let a = arg.num()?;
-                                let units = a.units;
-                                let m = if i + 1 < function.len() {
-                                    Complex::with_val(a.number.prec(), options.base.1)
-                                        .pow(function.remove(i + 1).num()?.number)
-                                } else {
-                                    Complex::with_val(a.number.prec(), 1)
-                                };
-                                let a = a.number * m.clone();
-                                NumStr::new(Number::from(
-                                    Complex::with_val(
-                                        options.prec,
-                                        (a.real().clone().fract(), a.imag().clone().fract()),
-                                    ) / m,
-                                    units,
-                                ))
+                        }
+                        NumStr::new(Number::from(
+                            Complex::with_val(options.precision(), ainb as u8),
+                            None,
+                        ))
+                    } else {
+                        return Err(Error::Type("arg not vector", None));
+                    }
+                } else {
+                    return Err(Error::Type("arg not vector", None));
+                }
+            }
+            "extend" | "link" => {
+                if function.len() > i + 1 {
+                    match function.remove(i + 1) {
+                        Num(n) => {
+                            let mut a = a;
+                            a.push(*n);
+                            Vector(a)
+                        }
+                        Vector(v) => {
+                            let mut a = a;
+                            a.extend(v);
+                            Vector(a)
+                        }
+                        _ => return Err(Error::Arity("bad arg", None)),
+                    }
+                } else {
+                    return Err(Error::Arity("bad arg", None));
+                }
+            }
+            "remove" => {
+                if function.len() > i + 1 {
+                    match function.remove(i + 1) {
+                        Num(n) => {
+                            let mut a = a;
+                            let n = n
+                                .number
+                                .real()
+                                .to_integer()
+                                .unwrap_or_default()
+                                .to_usize()
+                                .unwrap_or_default();
+                            if n >= a.len() {
+                                return Err(Error::Domain("bad range", None));
                             }
-                            "iden" | "identity" => Matrix(identity(
-                                arg.num()?
+                            a.remove(n);
+                            Vector(a)
+                        }
+                        Vector(v) => {
+                            let mut a = a;
+                            for n in v {
+                                let n = n
                                     .number
                                     .real()
                                     .to_integer()
                                     .unwrap_or_default()
                                     .to_usize()
-                                    .unwrap_or_default(),
-                                options.prec,
-                            )),
-                            "rotate" => {
-                                if i + 2 < function.len() {
-                                    let (sina, cosa) = (arg.num()?.number / to_deg.clone())
-                                        .sin_cos(Complex::new(options.prec));
-                                    let (sinb, cosb) = (function.remove(i + 1).num()?.number
-                                        / to_deg.clone())
-                                    .sin_cos(Complex::new(options.prec));
-                                    let (sinc, cosc) = (function.remove(i + 1).num()?.number
-                                        / to_deg.clone())
-                                    .sin_cos(Complex::new(options.prec));
-                                    Matrix(vec![
-                                        vec![
-                                            Number::from(cosa.clone() * cosb.clone(), None),
-                                            Number::from(
-                                                cosa.clone() * sinb.clone() * sinc.clone()
-                                                    - sina.clone() * cosc.clone(),
-                                                None,
-                                            ),
-                                            Number::from(
-                                                cosa.clone() * sinb.clone() * cosc.clone()
-                                                    + sina.clone() * sinc.clone(),
-                                                None,
-                                            ),
-                                        ],
-                                        vec![
-                                            Number::from(sina.clone() * cosb.clone(), None),
-                                            Number::from(
-                                                sina.clone() * sinb.clone() * sinc.clone()
-                                                    + cosa.clone() * cosc.clone(),
-                                                None,
-                                            ),
-                                            Number::from(
-                                                sina.clone() * sinb.clone() * cosc.clone()
-                                                    - cosa.clone() * sinc.clone(),
-                                                None,
-                                            ),
-                                        ],
-                                        vec![
-                                            Number::from(-sinb.clone(), None),
-                                            Number::from(cosb.clone() * sinc.clone(), None),
-                                            Number::from(cosb.clone() * cosc.clone(), None),
-                                        ],
-                                    ])
-                                } else {
-                                    let (sin, cos) = (arg.num()?.number / to_deg.clone())
-                                        .sin_cos(Complex::new(options.prec));
-                                    Matrix(vec![
-                                        vec![
-                                            Number::from(cos.clone(), None),
-                                            Number::from(-sin.clone(), None),
-                                        ],
-                                        vec![Number::from(sin, None), Number::from(cos, None)],
-                                    ])
-                                }
-                            }
-                            "prime_factors" => {
-                                let a = arg.num()?.number;
-                                if a.imag().is_zero() {
-                                    if a.real().clone().fract().is_zero() {
-                                        let n = a.real().to_integer().unwrap_or_default();
-                                        let m = prime_factors(n);
-                                        if m.is_empty() {
-                                            NumStr::new(Number::from(
-                                                Complex::with_val(options.prec, Nan),
-                                                None,
-                                            ))
-                                        } else {
-                                            Matrix(
-                                                m.iter()
-                                                    .map(|(a, b)| {
-                                                        vec![
-                                                            Number::from(
-                                                                Complex::with_val(options.prec, a),
-                                                                None,
-                                                            ),
-                                                            Number::from(
-                                                                Complex::with_val(options.prec, b),
-                                                                None,
-                                                            ),
-                                                        ]
-                                                    })
-                                                    .collect::<Vec<Vec<Number>>>(),
-                                            )
-                                        }
-                                    } else if let Some(a) = rationalize(a.real().clone(), options) {
-                                        let mut p1 = prime_factors(a.0);
-                                        let p2 = prime_factors(a.1);
-                                        for p in p1.iter_mut() {
-                                            for m in &p2 {
-                                                if p.0 == m.0 {
-                                                    p.1 -= m.1
-                                                }
-                                            }
-                                        }
-                                        for m in p2.iter() {
-                                            if !p1.iter().any(|p| p.0 == m.0) {
-                                                p1.push((m.0.clone(), -m.1));
-                                            }
-                                        }
-                                        Matrix(
-                                            p1.iter()
-                                                .map(|p| {
-                                                    vec![
-                                                        Number::from(
-                                                            Complex::with_val(
-                                                                options.prec,
-                                                                p.0.clone(),
-                                                            ),
-                                                            None,
-                                                        ),
-                                                        Number::from(
-                                                            Complex::with_val(options.prec, p.1),
-                                                            None,
-                                                        ),
-                                                    ]
-                                                })
-                                                .collect::<Vec<Vec<Number>>>(),
-                                        )
-                                    } else {
-                                        NumStr::new(Number::from(
-                                            Complex::with_val(options.prec, Nan),
-                                            None,
-                                        ))
-                                    }
-                                } else {
-                                    NumStr::new(Number::from(
-                                        Complex::with_val(options.prec, Nan),
-                                        None,
-                                    ))
-                                }
-                            }
-                            "factors" | "factor" => {
-                                let a = arg.num()?.number;
-                                if a.imag().clone().is_zero() {
-                                    if a.real().clone().fract().is_zero() {
-                                        let mut vec = Vec::new();
-                                        let n = a
-                                            .real()
-                                            .to_integer()
-                                            .unwrap_or_default()
-                                            .to_usize()
-                                            .unwrap_or_default();
-                                        for i in 1..=n {
-                                            if n % i == 0 {
-                                                vec.push(Number::from(
-                                                    Complex::with_val(options.prec, i),
-                                                    None,
-                                                ));
-                                            }
-                                        }
-                                        Vector(vec)
-                                    } else {
-                                        NumStr::new(Number::from(
-                                            Complex::with_val(options.prec, Nan),
-                                            None,
-                                        ))
-                                    }
-                                } else {
-                                    NumStr::new(Number::from(
-                                        Complex::with_val(options.prec, Nan),
-                                        None,
-                                    ))
-                                }
-                            }
-                            "unity" => {
-                                let vec = if i + 1 < function.len()
-                                    && !matches!(&function[i + 1], Func(_))
-                                {
-                                    unity(arg.num()?.number, function.remove(i + 1).num()?.number)
-                                } else {
-                                    unity(Complex::with_val(options.prec, 1), arg.num()?.number)
-                                };
-                                if vec.is_empty() {
-                                    Vector(vec![Number::from(
-                                        Complex::with_val(options.prec, Nan),
-                                        None,
-                                    )])
-                                } else {
-                                    Vector(vec)
+                                    .unwrap_or_default();
+                                if n >= a.len() {
+                                    return Err(Error::Domain("bad range", None));
                                 }
+                                a.remove(n);
                             }
-                            _ => do_functions(arg, options, &mut function, i, &to_deg, s)?,
-                        },
+                            Vector(a)
+                        }
+                        _ => return Err(Error::Domain("bad range", None)),
                     }
+                } else {
+                    return Err(Error::Domain("bad range", None));
                 }
             }
-        }
-        i += 1;
-    }
-    i = 0;
-    while i < function.len() {
-        if let Func(s) = &function[i] {
-            function[i] = match s.as_str() {
-                #[cfg(feature = "fastrand")]
-                "rnd" | "rand" => NumStr::new(Number::from(
-                    Complex::with_val(options.prec, fastrand::u128(..)) / u128::MAX,
-                    None,
-                )),
-                "epoch" => NumStr::new(Number::from(
-                    Complex::with_val(
-                        options.prec,
-                        match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-                            Ok(n) => n.as_nanos(),
-                            _ => return Err("epoch fail"),
-                        },
-                    ) / 1000000000,
-                    Some(Units {
-                        second: 1.0,
-                        ..Units::default()
-                    }),
-                )),
-                _ => {
-                    i += 1;
-                    continue;
+            "rationalize" => Matrix(
+                a.iter()
+                    .map(|c| c_to_rational(c.number.clone(), options))
+                    .collect::<Vec<Vec<Number>>>(),
+            ),
+            "prime_factors" => {
+                if a.len() != 2 {
+                    return Err(Error::Dimension("expected vector length 2", None));
                 }
+                let mut p1 = prime_factors(a[0].number.real().to_integer().unwrap_or_default());
+                let p2 = prime_factors(a[1].number.real().to_integer().unwrap_or_default());
+                for p in p1.iter_mut() {
+                    for m in &p2 {
+                        if p.0 == m.0 {
+                            p.1 -= m.1
+                        }
+                    }
+                }
+                for m in p2.iter() {
+                    if !p1.iter().any(|p| p.0 == m.0) {
+                        p1.push((m.0.clone(), -m.1));
+                    }
+                }
+                Matrix(
+                    p1.iter()
+                        .map(|p| {
+                            vec![
+                                Number::from(
+                                    Complex::with_val(options.precision(), p.0.clone()),
+                                    None,
+                                ),
+                                Number::from(Complex::with_val(options.precision(), p.1), None),
+                            ]
+                        })
+                        .collect::<Vec<Vec<Number>>>(),
+                )
             }
-        } else {
-            i += 1;
-        }
-    }
-    i = 1;
-    while i < function.len().saturating_sub(1) {
-        function[i] = match &function[i] {
-            Modulo => function[i - 1].func(&function[i + 1], rem)?,
-            Range => to(&function[i - 1], &function[i + 1])?,
-            _ => {
-                i += 1;
-                continue;
+            "poly" | "polynomial" => {
+                if i + 1 < function.len() {
+                    let x = function.remove(i + 1).num()?;
+                    let mut sum = Number::from(Complex::new(options.precision()), None);
+                    for (i, a) in a.iter().rev().enumerate() {
+                        let n = Number::from(
+                            a.number.clone() * x.number.clone().pow(i),
+                            mul_units(a.units, Some(x.units.unwrap_or_default().pow(i as f64))),
+                        );
+                        if i == 0 { sum = n } else { sum = add(&sum, &n) }
+                    }
+                    NumStr::new(sum)
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
             }
-        };
-        function.remove(i + 1);
-        function.remove(i - 1);
-    }
-    i = function.len().saturating_sub(2);
-    while i != 0 {
-        function[i] = match &function[i] {
-            Exponent => function[i - 1].pow(&function[i + 1])?,
-            Tetration => function[i - 1].func(&function[i + 1], tetration)?,
-            Root => function[i - 1].func(&function[i + 1], root)?,
-            _ => {
-                i -= 1;
-                continue;
+            _ => do_functions(arg, options, function, i, to_deg, s)?,
+        },
+        _ => match s {
+            "rationalize" => Vector(c_to_rational(arg.num()?.number, options)),
+            "domain_coloring_rgb" => {
+                let pi = Float::with_val(options.precision(), Pi);
+                let num = arg.num()?.number;
+                let hue: Float = 1 + (-num.clone()).arg().real().clone() / &pi;
+                let sat: Float = (1 + num.clone().abs().real().clone().fract()) / 2;
+                let val: Float = {
+                    let (r, i) = (num * &pi).into_real_imag();
+                    let t1: Float = r.sin();
+                    let t2: Float = i.sin();
+                    (t1 * t2).abs().pow(0.125)
+                };
+                Vector(hsv2rgb(3 * hue, sat, val))
             }
-        };
-        function.remove(i + 1);
-        function.remove(i - 1);
-        i = i.saturating_sub(2);
-    }
-    i = 1;
-    while i < function.len().saturating_sub(1) {
-        function[i] = match &function[i] {
-            InternalMultiplication => function[i - 1].mul(&function[i + 1])?,
-            _ => {
-                i += 1;
-                continue;
+            "multinomial" => {
+                let mut numerator: Complex = arg.num()?.number + 1;
+                let mut divisor = gamma(numerator.clone());
+                while i + 1 < function.len() && !matches!(&function[i + 1], Func(_)) {
+                    let temp = function.remove(i + 1).num()?.number;
+                    numerator += temp.clone();
+                    let temp = temp.clone() + 1;
+                    divisor *= gamma(temp);
+                }
+                NumStr::new(Number::from(gamma(numerator) / divisor, None))
             }
-        };
-        function.remove(i + 1);
-        function.remove(i - 1);
-    }
-    i = 1;
-    while i < function.len().saturating_sub(1) {
-        function[i] = match &function[i] {
-            Multiplication => function[i - 1].mul(&function[i + 1])?,
-            Division => function[i - 1].func(&function[i + 1], div)?,
-            _ => {
-                i += 1;
-                continue;
+            "Β" | "B" | "beta" => {
+                if i + 1 < function.len() {
+                    let a = arg.num()?.number;
+                    let b = function.remove(i + 1).num()?.number;
+                    if i + 1 < function.len() {
+                        let x = function.remove(i + 1).num()?.number;
+                        NumStr::new(Number::from(incomplete_beta(a, b, x), None))
+                    } else if a.imag().is_zero() && b.imag().is_zero() {
+                        NumStr::new(Number::from(
+                            gamma(a.clone()) * gamma(b.clone()) / gamma(a + b.clone()),
+                            None,
+                        ))
+                    } else {
+                        NumStr::new(Number::from(
+                            incomplete_beta(Complex::with_val(options.precision(), 1), a, b),
+                            None,
+                        ))
+                    }
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
             }
-        };
-        function.remove(i + 1);
-        function.remove(i - 1);
-    }
-    i = 1;
-    while i < function.len().saturating_sub(1) {
-        function[i] = match &function[i] {
-            PlusMinus => function[i - 1].pm(&function[i + 1])?,
-            Plus => function[i - 1].func(&function[i + 1], add)?,
-            Minus => function[i - 1].func(&function[i + 1], sub)?,
-            _ => {
-                i += 1;
-                continue;
+            "gamma_pdf" => {
+                if i + 2 < function.len() {
+                    let x = arg.num()?.number;
+                    let a = function.remove(i + 1).num()?.number;
+                    let b = function.remove(i + 1).num()?.number;
+                    NumStr::new(Number::from(
+                        pow_nth(b.clone(), -a.clone()) / gamma(a.clone())
+                            * pow_nth(x.clone(), a - 1)
+                            * (-x / b).exp(),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
             }
-        };
-        function.remove(i + 1);
-        function.remove(i - 1);
-    }
-    if options.units {
-        i = 1;
-        while i < function.len().saturating_sub(1) {
-            function[i] = match &function[i] {
-                Conversion => function[i - 1].func(&function[i + 1], div)?,
-                _ => {
-                    i += 1;
-                    continue;
+            "gamma_cdf" => {
+                if i + 2 < function.len() {
+                    let x = arg.num()?.number;
+                    let a = function.remove(i + 1).num()?.number;
+                    let b = function.remove(i + 1).num()?.number;
+                    NumStr::new(Number::from(
+                        1 - incomplete_gamma(a.clone(), x / b) / gamma(a),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
                 }
-            };
-            function.remove(i + 1);
-            function.remove(i - 1);
-        }
-    }
-    i = 1;
-    while i < function.len().saturating_sub(1) {
-        function[i] = match &function[i] {
-            Lesser => {
-                if i + 3 < function.len()
-                    && matches!(
-                        &function[i + 2],
-                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
-                    )
-                {
-                    function[i - 1] = function[i + 1].func(&function[i - 1], gt)?;
-                    function[i] = And;
-                    continue;
+            }
+            "I" | "betaC" | "beta_cdf" => {
+                if i + 2 < function.len() {
+                    let x = arg.num()?.number;
+                    let a = function.remove(i + 1).num()?.number;
+                    let b = function.remove(i + 1).num()?.number;
+                    if i + 1 < function.len() {
+                        let z = function.remove(i + 1).num()?.number;
+                        NumStr::new(Number::from(
+                            regularized_incomplete_beta(a, b.clone(), z.clone())
+                                - regularized_incomplete_beta(x, b, z),
+                            None,
+                        ))
+                    } else {
+                        NumStr::new(Number::from(regularized_incomplete_beta(x, a, b), None))
+                    }
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "betaP" | "beta_pdf" => {
+                if i + 2 < function.len() {
+                    let x = arg.num()?.number;
+                    let alpha = function.remove(i + 1).num()?.number;
+                    let beta = function.remove(i + 1).num()?.number;
+                    let c: Complex = 1 - x.clone();
+                    NumStr::new(Number::from(
+                        gamma(alpha.clone() + beta.clone())
+                            * pow_nth(x, alpha.clone() - 1)
+                            * pow_nth(c, beta.clone() - 1)
+                            / (gamma(alpha) * gamma(beta)),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "normP" | "norm_pdf" => {
+                if i + 2 < function.len() {
+                    let x = arg.num()?.number;
+                    let mu = function.remove(i + 1).num()?.number;
+                    let sigma = function.remove(i + 1).num()?.number;
+                    let n: Complex = sqr(x - mu);
+                    let n: Complex = -n / (2 * sqr(sigma.clone()));
+                    let tau: Complex = 2 * Complex::with_val(options.precision(), Pi);
+                    NumStr::new(Number::from(n.exp() / (sigma * tau.sqrt()), None))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "normD" | "norm_cdf" => {
+                let mut a = arg.num()?.number;
+                if i + 2 < function.len() {
+                    a -= function.remove(i + 1).num()?.number;
+                    a /= function.remove(i + 1).num()?.number;
+                }
+                if a.imag().is_zero() {
+                    let two = Float::with_val(options.precision(), 2);
+                    NumStr::new(Number::from(
+                        ((-a / two.clone().sqrt()).real().clone().erfc()? / two).into(),
+                        None,
+                    ))
+                } else {
+                    let two = Float::with_val(options.precision(), 2);
+                    NumStr::new(Number::from(erf(-a / two.clone().sqrt()) / two, None))
+                }
+            }
+            "lognorm_cdf" => {
+                let mut a = arg.num()?.number.ln();
+                if i + 2 < function.len() {
+                    a -= function.remove(i + 1).num()?.number;
+                    a /= function.remove(i + 1).num()?.number;
+                }
+                if a.imag().is_zero() {
+                    let two = Float::with_val(options.precision(), 2);
+                    NumStr::new(Number::from(
+                        ((-a / two.clone().sqrt()).real().clone().erfc()? / two).into(),
+                        None,
+                    ))
+                } else {
+                    let two = Float::with_val(options.precision(), 2);
+                    NumStr::new(Number::from(erf(-a / two.clone().sqrt()) / two, None))
+                }
+            }
+            "lognorm_pdf" => {
+                if i + 2 < function.len() {
+                    let x = arg.num()?.number;
+                    let mu = function.remove(i + 1).num()?.number;
+                    let sigma = function.remove(i + 1).num()?.number;
+                    let n: Complex = sqr(x.clone().ln() - mu);
+                    let n: Complex = -n / (2 * sqr(sigma.clone()));
+                    let tau: Complex = 2 * Complex::with_val(options.precision(), Pi);
+                    NumStr::new(Number::from(n.exp() / (sigma * tau.sqrt() * x), None))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "poisson_pmf" => {
+                if i + 1 < function.len() {
+                    let k = arg.num()?.number;
+                    let l = function.remove(i + 1).num()?.number;
+                    NumStr::new(Number::from(
+                        pow_nth(l.clone(), k.clone()) * (-l).exp() / gamma(k + 1),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "poisson_cdf" => {
+                if i + 1 < function.len() {
+                    let k = arg.num()?.number;
+                    let l = function.remove(i + 1).num()?.number;
+                    NumStr::new(Number::from(
+                        incomplete_gamma(k.clone() + 1, l) / gamma(k + 1),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "binomial_cdf" => {
+                if i + 2 < function.len() {
+                    let k = arg.num()?.number;
+                    let n = function.remove(i + 1).num()?.number;
+                    let p = function.remove(i + 1).num()?.number;
+                    let q: Complex = 1 - p.clone();
+                    NumStr::new(Number::from(
+                        regularized_incomplete_beta(q, n - k.clone(), 1 + k),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "binomial_pmf" => {
+                if i + 2 < function.len() {
+                    let k = arg.num()?.number;
+                    let n = function.remove(i + 1).num()?.number;
+                    let p = function.remove(i + 1).num()?.number;
+                    let q: Complex = 1 - p.clone();
+                    NumStr::new(Number::from(
+                        binomial(n.clone(), k.clone()) * pow_nth(p, k.clone()) * pow_nth(q, n - k),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "neg_binomial_cdf" => {
+                if i + 2 < function.len() {
+                    let k = arg.num()?.number;
+                    let r = function.remove(i + 1).num()?.number;
+                    let p = function.remove(i + 1).num()?.number;
+                    NumStr::new(Number::from(regularized_incomplete_beta(p, r, k + 1), None))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "neg_binomial_pmf" => {
+                if i + 2 < function.len() {
+                    let k = arg.num()?.number;
+                    let r = function.remove(i + 1).num()?.number;
+                    let p = function.remove(i + 1).num()?.number;
+                    let q: Complex = 1 - p.clone();
+                    NumStr::new(Number::from(
+                        binomial(k.clone() + r.clone() - 1, k.clone())
+                            * pow_nth(p, r)
+                            * pow_nth(q, k),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "hypergeometric_cdf" => {
+                if i + 3 < function.len() {
+                    let mut k = arg.num()?.number.real().clone().floor();
+                    let pop = function.remove(i + 1).num()?.number;
+                    let success = function.remove(i + 1).num()?.number;
+                    let draws = function.remove(i + 1).num()?.number;
+                    let mut sum = Complex::new(options.precision());
+                    while k >= 0 {
+                        sum += binomial(success.clone(), k.clone().into())
+                            * binomial(pop.clone() - success.clone(), draws.clone() - k.clone())
+                            / binomial(pop.clone(), draws.clone());
+                        k -= 1
+                    }
+                    NumStr::new(Number::from(
+                        Complex::with_val(options.precision(), sum),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            "hypergeometric_pmf" => {
+                if i + 3 < function.len() {
+                    let k = arg.num()?.number;
+                    let pop = function.remove(i + 1).num()?.number;
+                    let success = function.remove(i + 1).num()?.number;
+                    let draws = function.remove(i + 1).num()?.number;
+                    NumStr::new(Number::from(
+                        binomial(success.clone(), k.clone())
+                            * binomial(pop.clone() - success.clone(), draws.clone() - k)
+                            / binomial(pop, draws),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
+            }
+            #[cfg(feature = "fastrand")]
+            "rand_hypergeometric" => {
+                if i + 2 < function.len() {
+                    let mut pop = arg.num()?.number.real().clone();
+                    let mut success = function.remove(i + 1).num()?.number.real().clone();
+                    let mut draws = function.remove(i + 1).num()?.number.real().clone();
+                    let mut sum = Integer::new();
+                    while draws > 0 {
+                        if success.clone() / pop.clone()
+                            > Float::with_val(options.precision(), fastrand::u128(..)) / u128::MAX
+                        {
+                            sum += 1;
+                            success -= 1;
+                        }
+                        pop -= 1;
+                        draws -= 1
+                    }
+                    NumStr::new(Number::from(
+                        Complex::with_val(options.precision(), sum),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
                 }
-                function[i + 1].func(&function[i - 1], gt)?
             }
-            LesserEqual => {
-                if i + 3 < function.len()
-                    && matches!(
-                        &function[i + 2],
-                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
-                    )
-                {
-                    function[i - 1] = function[i + 1].func(&function[i - 1], ge)?;
-                    function[i] = And;
-                    continue;
+            "neg_hypergeometric_cdf" => {
+                if i + 3 < function.len() {
+                    let mut k = arg.num()?.number.real().clone().floor();
+                    let pop = function.remove(i + 1).num()?.number;
+                    let success = function.remove(i + 1).num()?.number;
+                    let fails = function.remove(i + 1).num()?.number;
+                    let mut sum = Complex::new(options.precision());
+                    while k >= 0 {
+                        sum += binomial(k.clone() + fails.clone() - 1, k.clone().into())
+                            * binomial(
+                                pop.clone() - k.clone() - fails.clone(),
+                                success.clone() - k.clone(),
+                            )
+                            / binomial(pop.clone(), success.clone());
+                        k -= 1
+                    }
+                    NumStr::new(Number::from(
+                        Complex::with_val(options.precision(), sum),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
                 }
-                function[i + 1].func(&function[i - 1], ge)?
             }
-            Greater => {
-                if i + 3 < function.len()
-                    && matches!(
-                        &function[i + 2],
-                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
-                    )
-                {
-                    function[i - 1] = function[i - 1].func(&function[i + 1], gt)?;
-                    function[i] = And;
-                    continue;
+            "neg_hypergeometric_pmf" => {
+                if i + 3 < function.len() {
+                    let k = arg.num()?.number;
+                    let pop = function.remove(i + 1).num()?.number;
+                    let success = function.remove(i + 1).num()?.number;
+                    let fails = function.remove(i + 1).num()?.number;
+                    NumStr::new(Number::from(
+                        binomial(k.clone() + fails.clone() - 1, k.clone())
+                            * binomial(pop.clone() - k.clone() - fails, success.clone() - k)
+                            / binomial(pop, success),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
                 }
-                function[i - 1].func(&function[i + 1], gt)?
             }
-            GreaterEqual => {
-                if i + 3 < function.len()
-                    && matches!(
-                        &function[i + 2],
-                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
-                    )
-                {
-                    function[i - 1] = function[i - 1].func(&function[i + 1], ge)?;
-                    function[i] = And;
-                    continue;
+            #[cfg(feature = "fastrand")]
+            "rand_neg_hypergeometric" => {
+                if i + 2 < function.len() {
+                    let mut pop = arg.num()?.number.real().clone();
+                    let mut success = function.remove(i + 1).num()?.number.real().clone();
+                    let mut fails = function.remove(i + 1).num()?.number.real().clone();
+                    let mut sum = Integer::new();
+                    while fails > 0 {
+                        if success.clone() / pop.clone()
+                            > Float::with_val(options.precision(), fastrand::u128(..)) / u128::MAX
+                        {
+                            sum += 1;
+                            success -= 1;
+                        } else {
+                            fails -= 1
+                        }
+                        pop -= 1;
+                    }
+                    NumStr::new(Number::from(
+                        Complex::with_val(options.precision(), sum),
+                        None,
+                    ))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
                 }
-                function[i - 1].func(&function[i + 1], ge)?
             }
-            Equal => {
-                if i + 3 < function.len()
-                    && matches!(
-                        &function[i + 2],
-                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
-                    )
-                {
-                    function[i - 1] = function[i - 1].func(&function[i + 1], eq)?;
-                    function[i] = And;
-                    continue;
+            "geometric_cdf" => {
+                if i + 1 < function.len() {
+                    let k = arg.num()?.number;
+                    let p = function.remove(i + 1).num()?.number;
+                    let q: Complex = 1 - p.clone();
+                    NumStr::new(Number::from(1 - pow_nth(q, k), None))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
                 }
-                function[i - 1].func(&function[i + 1], eq)?
             }
-            NotEqual => {
-                if i + 3 < function.len()
-                    && matches!(
-                        &function[i + 2],
-                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
-                    )
-                {
-                    function[i - 1] = function[i - 1].func(&function[i + 1], ne)?;
-                    function[i] = And;
-                    continue;
+            "geometric_pmf" => {
+                if i + 1 < function.len() {
+                    let k = arg.num()?.number;
+                    let p = function.remove(i + 1).num()?.number;
+                    let q: Complex = 1 - p.clone();
+                    NumStr::new(Number::from(pow_nth(q, k - 1) * p, None))
+                } else {
+                    return Err(Error::Arity("not enough args", None));
                 }
-                function[i - 1].func(&function[i + 1], ne)?
             }
-            NearEqual => {
-                if i + 3 < function.len()
-                    && matches!(
-                        &function[i + 2],
-                        Lesser | Greater | Equal | NearEqual | LesserEqual | GreaterEqual
-                    )
-                {
-                    function[i - 1] = function[i - 1].func(&function[i + 1], about_eq)?;
-                    function[i] = And;
-                    continue;
+            "quartic" => {
+                if i + 5 < function.len() {
+                    let a = arg.num()?;
+                    let b = function.remove(i + 1).num()?;
+                    let c = function.remove(i + 1).num()?;
+                    let d = function.remove(i + 1).num()?;
+                    let e = function.remove(i + 1).num()?;
+                    function.remove(i + 1);
+                    let n = quartic(a, b, c, d, e, true);
+                    if n.len() == 1 {
+                        NumStr::new(n[0].clone())
+                    } else {
+                        Vector(n)
+                    }
+                } else if i + 4 < function.len() {
+                    let a = arg.num()?;
+                    let b = function.remove(i + 1).num()?;
+                    let c = function.remove(i + 1).num()?;
+                    let d = function.remove(i + 1).num()?;
+                    let e = function.remove(i + 1).num()?;
+                    let n = quartic(a, b, c, d, e, false);
+                    if n.len() == 1 {
+                        NumStr::new(n[0].clone())
+                    } else {
+                        Vector(n)
+                    }
+                } else if i + 3 < function.len() {
+                    let b = arg.num()?;
+                    let c = function.remove(i + 1).num()?;
+                    let d = function.remove(i + 1).num()?;
+                    let e = function.remove(i + 1).num()?;
+                    let n = quartic(
+                        Number::from(Complex::with_val(options.precision(), 1), None),
+                        b,
+                        c,
+                        d,
+                        e,
+                        false,
+                    );
+                    if n.len() == 1 {
+                        NumStr::new(n[0].clone())
+                    } else {
+                        Vector(n)
+                    }
+                } else {
+                    return Err(Error::Arity("not enough args", None));
                 }
-                function[i - 1].func(&function[i + 1], about_eq)?
             }
-            ShiftRight => function[i - 1].func(&function[i + 1], shr)?,
-            ShiftLeft => function[i - 1].func(&function[i + 1], shl)?,
-            _ => {
-                i += 1;
-                continue;
+            "cubic" => {
+                if i + 4 < function.len() {
+                    let a = arg.num()?;
+                    let b = function.remove(i + 1).num()?;
+                    let c = function.remove(i + 1).num()?;
+                    let d = function.remove(i + 1).num()?;
+                    function.remove(i + 1);
+                    let n = cubic(a, b, c, d, true);
+                    if n.len() == 1 {
+                        NumStr::new(n[0].clone())
+                    } else {
+                        Vector(n)
+                    }
+                } else if i + 3 < function.len() {
+                    let a = arg.num()?;
+                    let b = function.remove(i + 1).num()?;
+                    let c = function.remove(i + 1).num()?;
+                    let d = function.remove(i + 1).num()?;
+                    let n = cubic(a, b, c, d, false);
+                    if n.len() == 1 {
+                        NumStr::new(n[0].clone())
+                    } else {
+                        Vector(n)
+                    }
+                } else if i + 2 < function.len() {
+                    let b = arg.num()?;
+                    let c = function.remove(i + 1).num()?;
+                    let d = function.remove(i + 1).num()?;
+                    let n = cubic(
+                        Number::from(Complex::with_val(options.precision(), 1), None),
+                        b,
+                        c,
+                        d,
+                        false,
+                    );
+                    if n.len() == 1 {
+                        NumStr::new(n[0].clone())
+                    } else {
+                        Vector(n)
+                    }
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
             }
-        };
-        function.remove(i + 1);
-        function.remove(i - 1);
-    }
-    if !function.is_empty() && function[0] == Not {
-        function[0] = not(&function.remove(1))?;
-    }
-    i = 1;
-    while i < function.len().saturating_sub(1) {
-        function[i] = match &function[i] {
-            And => function[i - 1].func(&function[i + 1], and)?,
-            Or => function[i - 1].func(&function[i + 1], or)?,
-            Xor => function[i - 1].func(&function[i + 1], xor)?,
-            Implies => function[i - 1].func(&function[i + 1], implies)?,
-            Nand => function[i - 1].func(&function[i + 1], nand)?,
-            Nor => function[i - 1].func(&function[i + 1], nor)?,
-            Converse => function[i + 1].func(&function[i - 1], implies)?,
-            Not => {
-                function[i] = not(&function.remove(i + 1))?;
-                continue;
+            "quad" | "quadratic" => {
+                if i + 3 < function.len() {
+                    let a = arg.num()?;
+                    let b = function.remove(i + 1).num()?;
+                    let c = function.remove(i + 1).num()?;
+                    function.remove(i + 1);
+                    let n = quadratic(a, b, c, true);
+                    if n.is_empty() {
+                        NumStr::new(Number::from(
+                            Complex::with_val(options.precision(), Nan),
+                            None,
+                        ))
+                    } else if n.len() == 1 {
+                        NumStr::new(n[0].clone())
+                    } else {
+                        Vector(n)
+                    }
+                } else if i + 2 < function.len() {
+                    let a = arg.num()?;
+                    let b = function.remove(i + 1).num()?;
+                    let c = function.remove(i + 1).num()?;
+                    let n = quadratic(a, b, c, false);
+                    if n.len() == 1 {
+                        NumStr::new(n[0].clone())
+                    } else {
+                        Vector(n)
+                    }
+                } else if i + 1 < function.len() {
+                    let b = arg.num()?;
+                    let c = function.remove(i + 1).num()?;
+                    let n = quadratic(
+                        Number::from(Complex::with_val(options.precision(), 1), None),
+                        b,
+                        c,
+                        false,
+                    );
+                    if n.len() == 1 {
+                        NumStr::new(n[0].clone())
+                    } else {
+                        Vector(n)
+                    }
+                } else {
+                    return Err(Error::Arity("not enough args", None));
+                }
             }
-            _ => {
-                i += 1;
-                continue;
+            "cossin" => {
+                let (a, b) = arg.num()?.number.sin_cos(Complex::new(options.precision()));
+                Vector(vec![Number::from(b, None), Number::from(a, None)])
             }
-        };
-        function.remove(i + 1);
-        function.remove(i - 1);
-    }
-    if function.len() == 1 {
-        Ok(function[0].clone())
+            "sincos" => {
+                let (a, b) = arg.num()?.number.sin_cos(Complex::new(options.precision()));
+                Vector(vec![Number::from(a, None), Number::from(b, None)])
+            }
+            "split" => {
+                let a = arg.num()?.number;
+                Vector(vec![
+                    Number::from(a.real().clone().into(), None),
+                    Number::from(a.imag().clone().into(), None),
+                ])
+            }
+            "ceil" => {
+                let a = arg.num()?;
+                let units = a.units;
+                let m = if i + 1 < function.len() {
+                    Complex::with_val(a.number.prec(), options.base.1)
+                        .pow(function.remove(i + 1).num()?.number)
+                } else {
+                    Complex::with_val(a.number.prec(), 1)
+                };
+                let a = a.number * m.clone();
+                NumStr::new(Number::from(
+                    Complex::with_val(
+                        options.precision(),
+                        (a.real().clone().ceil(), a.imag().clone().ceil()),
+                    ) / m,
+                    units,
+                ))
+            }
+            "floor" => {
+                let a = arg.num()?;
+                let units = a.units;
+                let m = if i + 1 < function.len() {
+                    Complex::with_val(a.number.prec(), options.base.1)
+                        .pow(function.remove(i + 1).num()?.number)
+                } else {
+                    Complex::with_val(a.number.prec(), 1)
+                };
+                let a = a.number * m.clone();
+                NumStr::new(Number::from(
+                    Complex::with_val(
+                        options.precision(),
+                        (a.real().clone().floor(), a.imag().clone().floor()),
+                    ) / m,
+                    units,
+                ))
+            }
+            "round" => {
+                let a = arg.num()?;
+                let units = a.units;
+                let m = if i + 1 < function.len() {
+                    Complex::with_val(a.number.prec(), options.base.1)
+                        .pow(function.remove(i + 1).num()?.number)
+                } else {
+                    Complex::with_val(a.number.prec(), 1)
+                };
+                let a = a.number * m.clone();
+                NumStr::new(Number::from(
+                    Complex::with_val(
+                        options.precision(),
+                        (a.real().clone().round(), a.imag().clone().round()),
+                    ) / m,
+                    units,
+                ))
+            }
+            "int" | "trunc" => {
+                let a = arg.num()?;
+                let units = a.units;
+                let m = if i + 1 < function.len() {
+                    Complex::with_val(a.number.prec(), options.base.1)
+                        .pow(function.remove(i + 1).num()?.number)
+                } else {
+                    Complex::with_val(a.number.prec(), 1)
+                };
+                let a = a.number * m.clone();
+                NumStr::new(Number::from(
+                    Complex::with_val(
+                        options.precision(),
+                        (a.real().clone().trunc(), a.imag().clone().trunc()),
+                    ) / m,
+                    units,
+                ))
+            }
+            "frac" | "fract" => {
+                let a = arg.num()?;
+                let units = a.units;
+                let m = if i + 1 < function.len() {
+                    Complex::with_val(a.number.prec(), options.base.1)
+                        .pow(function.remove(i + 1).num()?.number)
+                } else {
+                    Complex::with_val(a.number.prec(), 1)
+                };
+                let a = a.number * m.clone();
+                NumStr::new(Number::from(
+                    Complex::with_val(
+                        options.precision(),
+                        (a.real().clone().fract(), a.imag().clone().fract()),
+                    ) / m,
+                    units,
+                ))
+            }
+            "iden" | "identity" => Matrix(identity(
+                arg.num()?
+                    .number
+                    .real()
+                    .to_integer()
+                    .unwrap_or_default()
+                    .to_usize()
+                    .unwrap_or_default(),
+                options.precision(),
+            )),
+            "rotate" => {
+                if i + 2 < function.len() {
+                    let (sina, cosa) = (arg.num()?.number / to_deg.clone())
+                        .sin_cos(Complex::new(options.precision()));
+                    let (sinb, cosb) = (function.remove(i + 1).num()?.number / to_deg.clone())
+                        .sin_cos(Complex::new(options.precision()));
+                    let (sinc, cosc) = (function.remove(i + 1).num()?.number / to_deg.clone())
+                        .sin_cos(Complex::new(options.precision()));
+                    Matrix(vec![
+                        vec![
+                            Number::from(cosa.clone() * cosb.clone(), None),
+                            Number::from(
+                                cosa.clone() * sinb.clone() * sinc.clone()
+                                    - sina.clone() * cosc.clone(),
+                                None,
+                            ),
+                            Number::from(
+                                cosa.clone() * sinb.clone() * cosc.clone()
+                                    + sina.clone() * sinc.clone(),
+                                None,
+                            ),
+                        ],
+                        vec![
+                            Number::from(sina.clone() * cosb.clone(), None),
+                            Number::from(
+                                sina.clone() * sinb.clone() * sinc.clone()
+                                    + cosa.clone() * cosc.clone(),
+                                None,
+                            ),
+                            Number::from(
+                                sina.clone() * sinb.clone() * cosc.clone()
+                                    - cosa.clone() * sinc.clone(),
+                                None,
+                            ),
+                        ],
+                        vec![
+                            Number::from(-sinb.clone(), None),
+                            Number::from(cosb.clone() * sinc.clone(), None),
+                            Number::from(cosb.clone() * cosc.clone(), None),
+                        ],
+                    ])
+                } else {
+                    let (sin, cos) = (arg.num()?.number / to_deg.clone())
+                        .sin_cos(Complex::new(options.precision()));
+                    Matrix(vec![
+                        vec![
+                            Number::from(cos.clone(), None),
+                            Number::from(-sin.clone(), None),
+                        ],
+                        vec![Number::from(sin, None), Number::from(cos, None)],
+                    ])
+                }
+            }
+            "prime_factors" => {
+                let a = arg.num()?.number;
+                if a.imag().is_zero() {
+                    if a.real().clone().fract().is_zero() {
+                        let n = a.real().to_integer().unwrap_or_default();
+                        let m = prime_factors(n);
+                        if m.is_empty() {
+                            NumStr::new(Number::from(
+                                Complex::with_val(options.precision(), Nan),
+                                None,
+                            ))
+                        } else {
+                            Matrix(
+                                m.iter()
+                                    .map(|(a, b)| {
+                                        vec![
+                                            Number::from(
+                                                Complex::with_val(options.precision(), a),
+                                                None,
+                                            ),
+                                            Number::from(
+                                                Complex::with_val(options.precision(), b),
+                                                None,
+                                            ),
+                                        ]
+                                    })
+                                    .collect::<Vec<Vec<Number>>>(),
+                            )
+                        }
+                    } else if let Some(a) = rationalize(a.real().clone(), options) {
+                        let mut p1 = prime_factors(a.0);
+                        let p2 = prime_factors(a.1);
+                        for p in p1.iter_mut() {
+                            for m in &p2 {
+                                if p.0 == m.0 {
+                                    p.1 -= m.1
+                                }
+                            }
+                        }
+                        for m in p2.iter() {
+                            if !p1.iter().any(|p| p.0 == m.0) {
+                                p1.push((m.0.clone(), -m.1));
+                            }
+                        }
+                        Matrix(
+                            p1.iter()
+                                .map(|p| {
+                                    vec![
+                                        Number::from(
+                                            Complex::with_val(options.precision(), p.0.clone()),
+                                            None,
+                                        ),
+                                        Number::from(
+                                            Complex::with_val(options.precision(), p.1),
+                                            None,
+                                        ),
+                                    ]
+                                })
+                                .collect::<Vec<Vec<Number>>>(),
+                        )
+                    } else {
+                        NumStr::new(Number::from(
+                            Complex::with_val(options.precision(), Nan),
+                            None,
+                        ))
+                    }
+                } else {
+                    NumStr::new(Number::from(
+                        Complex::with_val(options.precision(), Nan),
+                        None,
+                    ))
+                }
+            }
+            "factors" | "factor" => {
+                let a = arg.num()?.number;
+                if a.imag().clone().is_zero() {
+                    if a.real().clone().fract().is_zero() {
+                        let mut vec = Vec::new();
+                        let n = a
+                            .real()
+                            .to_integer()
+                            .unwrap_or_default()
+                            .to_usize()
+                            .unwrap_or_default();
+                        for i in 1..=n {
+                            if n % i == 0 {
+                                vec.push(Number::from(
+                                    Complex::with_val(options.precision(), i),
+                                    None,
+                                ));
+                            }
+                        }
+                        Vector(vec)
+                    } else {
+                        NumStr::new(Number::from(
+                            Complex::with_val(options.precision(), Nan),
+                            None,
+                        ))
+                    }
+                } else {
+                    NumStr::new(Number::from(
+                        Complex::with_val(options.precision(), Nan),
+                        None,
+                    ))
+                }
+            }
+            "unity" => {
+                let vec = if i + 1 < function.len() && !matches!(&function[i + 1], Func(_)) {
+                    unity(arg.num()?.number, function.remove(i + 1).num()?.number)
+                } else {
+                    unity(Complex::with_val(options.precision(), 1), arg.num()?.number)
+                };
+                if vec.is_empty() {
+                    Vector(vec![Number::from(
+                        Complex::with_val(options.precision(), Nan),
+                        None,
+                    )])
+                } else {
+                    Vector(vec)
+                }
+            }
+            _ => do_functions(arg, options, function, i, to_deg, s)?,
+        },
+    };
+    Ok(())
+}
+fn factors(
+    function: &mut Vec<NumStr>,
+    i: usize,
+    parts: Vec<Vec<Vec<Number>>>,
+) -> Result<NumStr, Error> {
+    if function.len() > i + 1 && !matches!(&function[i + 1], Func(_)) {
+        let n = function.remove(i + 1).num()?.number.real().to_f64() as usize;
+        if n == 0 || n > parts.len() {
+            return Err(Error::Domain("factor index out of range", None));
+        }
+        Ok(Matrix(parts[n - 1].clone()))
     } else {
-        Err("failed to compute")
+        Ok(Matrix(
+            parts
+                .into_iter()
+                .reduce(|a, b| {
+                    a.into_iter()
+                        .zip(b)
+                        .map(|(mut a, b)| {
+                            a.extend(b);
+                            a
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        ))
     }
 }
 fn do_functions(
@@ -3777,7 +4334,7 @@ fn do_functions(
     k: usize,
     to_deg: &Complex,
     s: &str,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     if function.len() > k + 1 {
         match (a.clone(), function[k + 1].clone()) {
             (Num(a), Num(b)) => {
@@ -3871,12 +4428,12 @@ fn do_functions(
             (Matrix(a), Vector(b)) if a.len() == b.len() => {
                 function.remove(k + 1);
                 let mut mat = Vec::new();
-                for i in 0..a.len() {
+                for (a, b) in a.iter().zip(b.iter()) {
                     let mut vec = Vec::new();
-                    for j in 0..a[i].len() {
+                    for a in a {
                         vec.push(functions(
-                            a[i][j].clone(),
-                            Some(b[i].clone()),
+                            a.clone(),
+                            Some(b.clone()),
                             to_deg.clone(),
                             s,
                             options,
@@ -3889,12 +4446,12 @@ fn do_functions(
             (Vector(a), Matrix(b)) if a.len() == b.len() => {
                 function.remove(k + 1);
                 let mut mat = Vec::new();
-                for i in 0..b.len() {
+                for (a, b) in a.iter().zip(b.iter()) {
                     let mut vec = Vec::new();
-                    for j in 0..b[i].len() {
+                    for b in b {
                         vec.push(functions(
-                            a[i].clone(),
-                            Some(b[i][j].clone()),
+                            a.clone(),
+                            Some(b.clone()),
                             to_deg.clone(),
                             s,
                             options,
@@ -3933,7 +4490,57 @@ fn do_functions(
             s,
             options,
         )?)),
-        _ => Err("str err1"),
+        _ => Err(Error::Parse("str err1", None)),
+    }
+}
+pub type Scalar = fn(Complex, &Complex) -> Complex;
+pub fn scalar(s: &str) -> Option<Scalar> {
+    Some(match s {
+        "sin" => |a, to_deg| (a / to_deg).sin(),
+        "csc" => |a, to_deg| (a / to_deg).sin().recip(),
+        "cos" => |a, to_deg| (a / to_deg).cos(),
+        "sec" => |a, to_deg| (a / to_deg).cos().recip(),
+        "tan" => |a, to_deg| (a / to_deg).tan(),
+        "cot" => |a, to_deg| (a / to_deg).tan().recip(),
+        "sinh" => |a, _| a.sinh(),
+        "csch" => |a, _| a.sinh().recip(),
+        "cosh" => |a, _| a.cosh(),
+        "sech" => |a, _| a.cosh().recip(),
+        "tanh" => |a, _| a.tanh(),
+        "coth" => |a, _| a.tanh().recip(),
+        "asinh" | "arcsinh" => |a, _| a.asinh(),
+        "acsch" | "arccsch" => |a, _| a.recip().asinh(),
+        "acosh" | "arccosh" => |a, _| a.acosh(),
+        "asech" | "arcsech" => |a, _| a.recip().acosh(),
+        "atanh" | "arctanh" => |a, _| a.atanh(),
+        "acoth" | "arccoth" => |a, _| a.recip().atanh(),
+        "ln" | "aexp" => |a, _| a.ln(),
+        "arg" => |a, _| a.arg(),
+        "fact" | "factorial" => |a, _| gamma(a + 1),
+        _ => return None,
+    })
+}
+pub fn map_scalar(a: NumStr, f: Scalar, to_deg: &Complex) -> Result<NumStr, Error> {
+    let f = |mut a: Complex| {
+        if a.imag().is_zero() && !a.imag().is_sign_positive() {
+            a = Complex::with_val(a.prec(), a.real())
+        }
+        let n = f(a, to_deg);
+        if n.imag().is_zero() && !n.imag().is_sign_positive() {
+            Number::from(n.real().clone().into(), None)
+        } else {
+            Number::from(n, None)
+        }
+    };
+    match a {
+        Num(a) => Ok(NumStr::new(f(a.number))),
+        Vector(a) => Ok(Vector(a.into_iter().map(|a| f(a.number)).collect())),
+        Matrix(a) => Ok(Matrix(
+            a.into_iter()
+                .map(|a| a.into_iter().map(|a| f(a.number)).collect())
+                .collect(),
+        )),
+        _ => Err(Error::Parse("str err1", None)),
     }
 }
 fn functions(
@@ -3942,7 +4549,7 @@ fn functions(
     to_deg: Complex,
     s: &str,
     options: Options,
-) -> Result<Number, &'static str> {
+) -> Result<Number, Error> {
     if a.number.imag().is_zero() && !a.number.imag().is_sign_positive() {
         a.number = Complex::with_val(a.number.prec(), a.number.real())
     }
@@ -4066,7 +4673,7 @@ fn functions(
                         }),
                     )
                 } else {
-                    return Err("not enough args");
+                    return Err(Error::Arity("not enough args", None));
                 }
             }
             "hypot" => {
@@ -4076,7 +4683,7 @@ fn functions(
                         a.units,
                     )
                 } else {
-                    return Err("not enough args");
+                    return Err(Error::Arity("not enough args", None));
                 }
             }
             "atan" | "arctan" => {
@@ -4108,32 +4715,32 @@ fn functions(
             "cbrt" | "acube" => Number::from(
                 if a.number.imag().is_zero() {
                     if a.number.real().is_zero() {
-                        Complex::new(options.prec)
+                        Complex::new(options.precision())
                     } else if a.number.real().is_sign_positive() {
-                        pow_nth(a.number, Complex::with_val(options.prec, 3).recip())
+                        pow_nth(a.number, Complex::with_val(options.precision(), 3).recip())
                     } else {
-                        -pow_nth(-a.number, Complex::with_val(options.prec, 3).recip())
+                        -pow_nth(-a.number, Complex::with_val(options.precision(), 3).recip())
                     }
                 } else {
-                    pow_nth(a.number, Complex::with_val(options.prec, 3).recip())
+                    pow_nth(a.number, Complex::with_val(options.precision(), 3).recip())
                 },
                 a.units.map(|a| a.root(3.0)),
             ),
             "abs" | "norm" => Number::from(a.number.abs(), a.units),
             "recip" => Number::from(a.number.recip(), a.units.map(|a| a.pow(-1.0))),
-            "units" => Number::from(Complex::with_val(options.prec, 1), a.units),
+            "units" => Number::from(Complex::with_val(options.precision(), 1), a.units),
             "onlyreal" | "onlyre" | "ore" => {
-                if -a.number.imag().clone().abs().log10() > a.number.prec().0 / 4 {
+                if -a.number.imag().clone().abs().log10() > a.number.prec().0.bits / 4 {
                     Number::from(a.number.real().clone().into(), a.units)
                 } else {
-                    Number::from(Complex::with_val(options.prec, Nan), None)
+                    Number::from(Complex::with_val(options.precision(), Nan), None)
                 }
             }
             "onlyimag" | "onlyim" | "oim" => {
-                if -a.number.real().clone().abs().log10() > a.number.prec().0 / 4 {
+                if -a.number.real().clone().abs().log10() > a.number.prec().0.bits / 4 {
                     Number::from(a.number.imag().clone().into(), a.units)
                 } else {
-                    Number::from(Complex::with_val(options.prec, Nan), None)
+                    Number::from(Complex::with_val(options.precision(), Nan), None)
                 }
             }
             "re" | "real" => Number::from(a.number.real().clone().into(), a.units),
@@ -4151,12 +4758,18 @@ fn functions(
                     } else {
                         real.next_toward(b.number.real());
                     }
-                    Number::from(Complex::with_val(options.prec, (real, imag)), a.units)
+                    Number::from(
+                        Complex::with_val(options.precision(), (real, imag)),
+                        a.units,
+                    )
                 } else {
                     let mut real: Float = a.number.real().clone();
                     let imag: Float = a.number.imag().clone();
                     real.next_up();
-                    Number::from(Complex::with_val(options.prec, (real, imag)), a.units)
+                    Number::from(
+                        Complex::with_val(options.precision(), (real, imag)),
+                        a.units,
+                    )
                 }
             }
             #[cfg(feature = "fastrand")]
@@ -4164,7 +4777,7 @@ fn functions(
                 if let Some(b) = c {
                     Number::from(rand_norm(a.number, b.number), a.units)
                 } else {
-                    return Err("not enough args");
+                    return Err(Error::Arity("not enough args", None));
                 }
             }
             #[cfg(feature = "fastrand")]
@@ -4174,13 +4787,14 @@ fn functions(
                     let a = a.number;
                     let b = b.number;
                     Number::from(
-                        (b.clone() - a.clone()) * Float::with_val(options.prec, fastrand::u128(..))
+                        (b.clone() - a.clone())
+                            * Float::with_val(options.precision(), fastrand::u128(..))
                             / u128::MAX
                             + if a.real() < b.real() { a } else { b },
                         units,
                     )
                 } else {
-                    return Err("not enough args");
+                    return Err(Error::Arity("not enough args", None));
                 }
             }
             #[cfg(feature = "fastrand")]
@@ -4217,7 +4831,7 @@ fn functions(
                         .unwrap_or_default();
                     Number::from(
                         Complex::with_val(
-                            options.prec,
+                            options.precision(),
                             (
                                 fastrand::i128(ar.min(br)..=br.max(ar)),
                                 fastrand::i128(ai.min(bi)..=bi.max(ai)),
@@ -4226,11 +4840,13 @@ fn functions(
                         units,
                     )
                 } else {
-                    return Err("not enough args");
+                    return Err(Error::Arity("not enough args", None));
                 }
             }
-            _ => return Err("unreachable"),
+            _ => return Err(Error::Unsupported("unreachable", None)),
         }
+    } else if let Some(f) = scalar(s) {
+        Number::from(f(a.number, &to_deg), None)
     } else {
         let a = a.number;
         let mut d = None;
@@ -4243,29 +4859,10 @@ fn functions(
         }
         Number::from(
             match s {
-                "sin" => (a / to_deg).sin(),
-                "csc" => (a / to_deg).sin().recip(),
-                "cos" => (a / to_deg).cos(),
-                "sec" => (a / to_deg).cos().recip(),
-                "tan" => (a / to_deg).tan(),
-                "cot" => (a / to_deg).tan().recip(),
-                "sinh" => a.sinh(),
-                "csch" => a.sinh().recip(),
-                "cosh" => a.cosh(),
-                "sech" => a.cosh().recip(),
-                "tanh" => a.tanh(),
-                "coth" => a.tanh().recip(),
-                "asinh" | "arcsinh" => a.asinh(),
-                "acsch" | "arccsch" => a.recip().asinh(),
-                "acosh" | "arccosh" => a.acosh(),
-                "asech" | "arcsech" => a.clone().recip().acosh(),
-                "atanh" | "arctanh" => a.clone().atanh(),
-                "acoth" | "arccoth" => a.clone().recip().atanh(),
                 "cis" => {
                     let b = a / to_deg.clone();
-                    b.clone().cos() + b.sin() * Complex::with_val(options.prec, (0.0, 1.0))
+                    b.clone().cos() + b.sin() * Complex::with_val(options.precision(), (0.0, 1.0))
                 }
-                "ln" | "aexp" => a.ln(),
                 "W" | "productlog" | "lambertw" => {
                     if let Some(b) = d {
                         lambertw(b, a.real().to_integer().unwrap_or_default())
@@ -4279,12 +4876,12 @@ fn functions(
                         let b = b.ln();
                         if a.is_zero() {
                             if b.is_zero() {
-                                Complex::with_val(options.prec, Nan)
+                                Complex::with_val(options.precision(), Nan)
                             } else {
-                                Complex::with_val(options.prec, Infinity)
+                                Complex::with_val(options.precision(), Infinity)
                             }
                         } else if b.real().is_infinite() {
-                            -Complex::with_val(options.prec, Infinity)
+                            -Complex::with_val(options.precision(), Infinity)
                         } else {
                             b / a
                         }
@@ -4306,16 +4903,16 @@ fn functions(
                         if a.real() > &1 {
                             slog(&a, &b)
                         } else {
-                            Complex::with_val(options.prec, Nan)
+                            Complex::with_val(options.precision(), Nan)
                         }
                     } else {
-                        return Err("no args");
+                        return Err(Error::Arity("no args", None));
                     }
                 }
                 "Ap" => {
                     if let Some(b) = d {
                         if a.real().is_sign_negative() {
-                            Complex::with_val(options.prec, Nan)
+                            Complex::with_val(options.precision(), Nan)
                         } else {
                             pub fn pow_n(z: Complex, n: usize) -> Complex {
                                 if !z.imag().is_zero() && n <= 256 {
@@ -4328,7 +4925,7 @@ fn functions(
                                     z.pow(n)
                                 }
                             }
-                            let mut sum = Complex::new(options.prec);
+                            let mut sum = Complex::new(options.precision());
                             let n = a
                                 .real()
                                 .to_integer()
@@ -4341,7 +4938,7 @@ fn functions(
                             sum
                         }
                     } else {
-                        return Err("no args");
+                        return Err(Error::Arity("no args", None));
                     }
                 }
                 "An" => {
@@ -4355,7 +4952,7 @@ fn functions(
                                 .unwrap_or_default(),
                         )
                     } else {
-                        return Err("no args");
+                        return Err(Error::Arity("no args", None));
                     }
                 }
                 "P" => {
@@ -4366,8 +4963,11 @@ fn functions(
                             && b.imag().is_zero()
                         {
                             let a = a + Complex::with_val(
-                                options.prec,
-                                (0, Float::with_val(options.prec, 0.5).pow(options.prec / 2)),
+                                options.precision(),
+                                (
+                                    0,
+                                    Float::with_val(options.precision(), 0.5).pow(options.prec / 2),
+                                ),
                             );
                             (gamma(a.clone() + 1) / gamma(a.clone() - b + 1))
                                 .real()
@@ -4377,35 +4977,35 @@ fn functions(
                             gamma(a.clone() + 1) / gamma(a.clone() - b + 1)
                         }
                     } else {
-                        return Err("no args");
+                        return Err(Error::Arity("no args", None));
                     }
                 }
                 "C" | "bi" | "binomial" => {
                     if let Some(b) = d {
                         binomial(a, b)
                     } else {
-                        return Err("no args");
+                        return Err(Error::Arity("no args", None));
                     }
                 }
                 "pochhammer" | "ph" => {
                     if let Some(b) = d {
                         if !a.real().is_sign_positive() && a.imag().is_zero() && b.imag().is_zero()
                         {
-                            pow_nth(Complex::with_val(options.prec, -1), b.clone())
+                            pow_nth(Complex::with_val(options.precision(), -1), b.clone())
                                 * gamma(1 - a.clone())
                                 / gamma(1 - a - b)
                         } else {
                             gamma(b.clone() + a.clone()) / gamma(a.clone())
                         }
                     } else {
-                        return Err("not enough args");
+                        return Err(Error::Arity("not enough args", None));
                     }
                 }
                 "lower_gamma" | "γ" => {
                     if let Some(b) = d {
                         lower_incomplete_gamma(a, b)
                     } else {
-                        return Err("not enough args");
+                        return Err(Error::Arity("not enough args", None));
                     }
                 }
                 "gamma" | "Γ" => {
@@ -4417,20 +5017,18 @@ fn functions(
                 }
                 "sgn" | "sign" => {
                     if a.is_zero() {
-                        Complex::new(options.prec)
+                        Complex::new(options.precision())
                     } else {
                         a.clone() / a.abs()
                     }
                 }
-                "arg" => a.arg(),
                 "doublefact" | "doublefactorial" => {
-                    let two = Complex::with_val(options.prec, 2);
-                    let pi = Complex::with_val(options.prec, Pi);
+                    let two = Complex::with_val(options.precision(), 2);
+                    let pi = Complex::with_val(options.precision(), Pi);
                     two.pow(a.clone() / 2 + (1 - (pi.clone() * a.clone()).cos()) / 4)
                         * pi.clone().pow(((pi * a.clone()).cos() - 1) / 4)
                         * gamma(a.clone() / 2 + 1)
                 }
-                "fact" | "factorial" => gamma(a.clone() + 1),
                 "subfact" | "subfactorial" => {
                     let near = a.real().clone().fract().is_zero();
                     let neg = a.real().is_sign_negative();
@@ -4438,17 +5036,20 @@ fn functions(
                     if !neari || neg || !near {
                         if neg && near && neari {
                             let a = a + Complex::with_val(
-                                options.prec,
-                                (0, Float::with_val(options.prec, 0.5).pow(options.prec / 8)),
+                                options.precision(),
+                                (
+                                    0,
+                                    Float::with_val(options.precision(), 0.5).pow(options.prec / 8),
+                                ),
                             );
                             subfactorial(a)
                         } else {
                             subfactorial(a)
                         }
                     } else if a.real().is_zero() {
-                        Complex::with_val(options.prec, 1)
+                        Complex::with_val(options.precision(), 1)
                     } else {
-                        (gamma(a.clone() + 1) / Float::with_val(options.prec, 1).exp())
+                        (gamma(a.clone() + 1) / Float::with_val(options.precision(), 1).exp())
                             .real()
                             .clone()
                             .round()
@@ -4457,31 +5058,17 @@ fn functions(
                 }
                 "sinc" => a.clone().sin() / a,
                 "conj" | "conjugate" => a.conj(),
-                "erf" => {
-                    if a.imag().is_zero() {
-                        a.real().clone().erf().into()
-                    } else {
-                        erf(a)
-                    }
-                }
-                "erfc" => {
-                    if a.imag().is_zero() {
-                        a.real().clone().erfc().into()
-                    } else {
-                        erfc(a)
-                    }
-                }
+                "erf" if a.imag().is_zero() => a.real().clone().erf()?.into(),
+                "erfc" if a.imag().is_zero() => a.real().clone().erfc()?.into(),
+                "ai" if a.imag().is_zero() => a.real().clone().ai()?.into(),
+                "zeta" | "ζ" if a.imag().is_zero() => a.real().clone().zeta()?.into(),
+                "erf" => erf(a),
+                "erfc" => erfc(a),
                 "erfi" => {
-                    let i = Complex::with_val(options.prec, (0, 1));
+                    let i = Complex::with_val(options.precision(), (0, 1));
                     -i.clone() * erf(i * a)
                 }
-                "ai" => {
-                    if a.imag().is_zero() {
-                        a.real().clone().ai().into()
-                    } else {
-                        Complex::with_val(options.prec, Nan)
-                    }
-                }
+                "ai" => Complex::with_val(options.precision(), Nan),
                 "trigamma" => digamma(a, 1),
                 "digamma" | "polygamma" | "ψ" => {
                     if let Some(b) = d {
@@ -4495,42 +5082,36 @@ fn functions(
                         )
                     } else if a.imag().is_zero() {
                         if a.real().is_sign_negative() && a.real().clone().fract().is_zero() {
-                            Complex::with_val(options.prec, Infinity)
+                            Complex::with_val(options.precision(), Infinity)
                         } else {
-                            a.real().clone().digamma().into()
+                            a.real().clone().digamma()?.into()
                         }
                     } else {
                         digamma(a, 0)
                     }
                 }
                 "eta" | "η" => eta(a),
-                "zeta" | "ζ" => {
-                    if a.imag().is_zero() {
-                        a.real().clone().zeta().into()
-                    } else {
-                        zeta(a)
-                    }
-                }
+                "zeta" | "ζ" => zeta(a),
                 "nth_prime" | "prime" => {
                     if a.imag().is_zero() && a.real().clone().fract().is_zero() {
                         Complex::with_val(
-                            options.prec,
+                            options.precision(),
                             nth_prime(a.real().to_integer().unwrap_or_default()),
                         )
                     } else {
-                        Complex::with_val(options.prec, Nan)
+                        Complex::with_val(options.precision(), Nan)
                     }
                 }
                 "mod" => {
                     if let Some(b) = d {
                         let c = a.clone() / b.clone();
                         let c = Complex::with_val(
-                            options.prec,
+                            options.precision(),
                             (c.real().clone().floor(), c.imag().clone().floor()),
                         );
                         a - b * c
                     } else {
-                        return Err("not enough args");
+                        return Err(Error::Arity("not enough args", None));
                     }
                 }
                 "lcm" => {
@@ -4540,14 +5121,17 @@ fn functions(
                             || b.real().is_zero()
                             || a.real().is_zero()
                         {
-                            Complex::with_val(options.prec, Nan)
+                            Complex::with_val(options.precision(), Nan)
                         } else {
                             let a = a.real().clone().abs().to_integer().unwrap_or_default();
                             let b = b.real().clone().abs().to_integer().unwrap_or_default();
-                            Complex::with_val(options.prec, a.clone() * b.clone() / gcd(a, b))
+                            Complex::with_val(
+                                options.precision(),
+                                a.clone() * b.clone() / gcd(a, b),
+                            )
                         }
                     } else {
-                        return Err("not enough args");
+                        return Err(Error::Arity("not enough args", None));
                     }
                 }
                 "gcd" | "gcf" => {
@@ -4557,10 +5141,10 @@ fn functions(
                             || b.real().is_zero()
                             || a.real().is_zero()
                         {
-                            Complex::with_val(options.prec, Nan)
+                            Complex::with_val(options.precision(), Nan)
                         } else {
                             Complex::with_val(
-                                options.prec,
+                                options.precision(),
                                 gcd(
                                     a.real().clone().abs().to_integer().unwrap_or_default(),
                                     b.real().clone().abs().to_integer().unwrap_or_default(),
@@ -4568,13 +5152,13 @@ fn functions(
                             )
                         }
                     } else {
-                        return Err("not enough args");
+                        return Err(Error::Arity("not enough args", None));
                     }
                 }
-                "is_nan" => Complex::with_val(options.prec, a.real().is_nan() as u8),
-                "is_inf" => Complex::with_val(options.prec, a.real().is_infinite() as u8),
+                "is_nan" => Complex::with_val(options.precision(), a.real().is_nan() as u8),
+                "is_inf" => Complex::with_val(options.precision(), a.real().is_infinite() as u8),
                 "is_fin" | "is_finite" => {
-                    Complex::with_val(options.prec, a.real().is_finite() as u8)
+                    Complex::with_val(options.precision(), a.real().is_finite() as u8)
                 }
                 "isprime" | "is_prime" => {
                     if a.imag().is_zero()
@@ -4582,7 +5166,7 @@ fn functions(
                         && a.real().is_finite()
                     {
                         Complex::with_val(
-                            options.prec,
+                            options.precision(),
                             (a.real()
                                 .to_integer()
                                 .unwrap_or_default()
@@ -4590,7 +5174,7 @@ fn functions(
                                 != IsPrime::No) as u8,
                         )
                     } else {
-                        Complex::new(options.prec)
+                        Complex::new(options.precision())
                     }
                 }
                 #[cfg(feature = "fastrand")]
@@ -4598,17 +5182,19 @@ fn functions(
                     if let Some(b) = d {
                         rand_gamma(a.real().clone(), b.real().clone()).into()
                     } else {
-                        return Err("not enough args");
+                        return Err(Error::Arity("not enough args", None));
                     }
                 }
                 #[cfg(feature = "fastrand")]
                 "rand_beta" => {
                     if let Some(b) = d {
-                        let x = rand_gamma(a.real().clone(), Float::with_val(options.prec, 1));
-                        let y = rand_gamma(b.real().clone(), Float::with_val(options.prec, 1));
+                        let x =
+                            rand_gamma(a.real().clone(), Float::with_val(options.precision(), 1));
+                        let y =
+                            rand_gamma(b.real().clone(), Float::with_val(options.precision(), 1));
                         (x.clone() / (x + y)).into()
                     } else {
-                        return Err("not enough args");
+                        return Err(Error::Arity("not enough args", None));
                     }
                 }
                 #[cfg(feature = "fastrand")]
@@ -4616,15 +5202,17 @@ fn functions(
                     if let Some(b) = d {
                         rand_norm(a, b).exp()
                     } else {
-                        return Err("not enough args");
+                        return Err(Error::Arity("not enough args", None));
                     }
                 }
                 #[cfg(feature = "fastrand")]
                 "rand_bernoulli" => {
-                    if *a.real() > Float::with_val(options.prec, fastrand::u128(..)) / u128::MAX {
-                        Complex::with_val(options.prec, 1)
+                    if *a.real()
+                        > Float::with_val(options.precision(), fastrand::u128(..)) / u128::MAX
+                    {
+                        Complex::with_val(options.precision(), 1)
                     } else {
-                        Complex::new(options.prec)
+                        Complex::new(options.precision())
                     }
                 }
                 #[cfg(feature = "fastrand")]
@@ -4634,14 +5222,17 @@ fn functions(
                         let mut n = a.real().to_integer().unwrap_or_default();
                         let mut sum = Integer::new();
                         while n > 0 {
-                            if *p > Float::with_val(options.prec, fastrand::u128(..)) / u128::MAX {
+                            if *p
+                                > Float::with_val(options.precision(), fastrand::u128(..))
+                                    / u128::MAX
+                            {
                                 sum += 1;
                             }
                             n -= 1
                         }
-                        Complex::with_val(options.prec, sum)
+                        Complex::with_val(options.precision(), sum)
                     } else {
-                        return Err("not enough args");
+                        return Err(Error::Arity("not enough args", None));
                     }
                 }
                 #[cfg(feature = "fastrand")]
@@ -4649,43 +5240,47 @@ fn functions(
                     if let Some(b) = d {
                         let p = b.real();
                         if *p <= 0 {
-                            return Err("p must be greater then 0");
+                            return Err(Error::Domain("p must be greater then 0", None));
                         }
                         let mut r = a.real().to_integer().unwrap_or_default();
                         let mut sum = Integer::new();
                         while r > 0 {
-                            if *p > Float::with_val(options.prec, fastrand::u128(..)) / u128::MAX {
+                            if *p
+                                > Float::with_val(options.precision(), fastrand::u128(..))
+                                    / u128::MAX
+                            {
                                 r -= 1
                             } else {
                                 sum += 1;
                             }
                         }
-                        Complex::with_val(options.prec, sum)
+                        Complex::with_val(options.precision(), sum)
                     } else {
-                        return Err("not enough args");
+                        return Err(Error::Arity("not enough args", None));
                     }
                 }
                 #[cfg(feature = "fastrand")]
                 "rand_geometric" => {
                     let q: Float = 1 - a.real().clone();
-                    let n: Float = (Float::with_val(options.prec, fastrand::u128(..)) / u128::MAX)
-                        .ln()
-                        / q.ln();
+                    let n: Float =
+                        (Float::with_val(options.precision(), fastrand::u128(..)) / u128::MAX).ln()
+                            / q.ln();
                     n.ceil().into()
                 }
                 #[cfg(feature = "fastrand")]
                 "rand_poisson" => {
-                    let mut prod = Complex::with_val(options.prec, 1);
+                    let mut prod = Complex::with_val(options.precision(), 1);
                     let lim = (-a).exp();
                     let mut n = Integer::new();
                     while lim.real() < prod.real() {
-                        prod *= Float::with_val(options.prec, fastrand::u128(..)) / u128::MAX;
+                        prod *=
+                            Float::with_val(options.precision(), fastrand::u128(..)) / u128::MAX;
                         n += 1;
                     }
-                    Complex::with_val(options.prec, n - 1)
+                    Complex::with_val(options.precision(), n - 1)
                 }
                 _ => {
-                    return Err("wrong input type");
+                    return Err(Error::Type("wrong input type", None));
                 }
             },
             None,
@@ -10,6 +10,8 @@ use crate::{
             ShiftLeft, ShiftRight, Tetration, Vector, Xor,
         },
     },
+    error::Error,
+    expr::{Expr, is_lazy},
     functions::functions,
     math::do_math,
     print::{custom_units, get_output},
@@ -459,7 +461,7 @@ pub fn to_output(input: &[char], vars: &[Variable], color: bool, colors: &Colors
     }
 }
 pub fn handle_err(
-    err: &str,
+    err: Error,
     vars: &[Variable],
     input: &[char],
     options: Options,
@@ -467,20 +469,33 @@ pub fn handle_err(
     start: usize,
     end: usize,
 ) {
+    let prompt = prompt(options, colors);
+    let caret = match err.span() {
+        Some((a, b)) if a >= start && a < end => format!(
+            "{}{}\x1b[G\n",
+            " ".repeat(no_col_len(&prompt, options.color == Auto::True) + a - start),
+            "^".repeat(b.min(end).saturating_sub(a).max(1))
+        ),
+        _ => String::new(),
+    };
+    let err = err.message();
     let num = if cfg!(feature = "bin-deps") {
-        err.len().div_ceil(get_terminal_dimensions().0) - 1
+        err.len()
+            .div_ceil(get_terminal_dimensions().0)
+            .saturating_sub(1)
     } else {
         err.len()
-    };
+    } + usize::from(!caret.is_empty());
     print!(
-        "\x1b[J\x1b[G\n{}{}\x1b[G\x1b[A\x1b[K{}{}{}",
+        "\x1b[J\x1b[G\n{}{}{}\x1b[G\x1b[A\x1b[K{}{}{}",
+        caret,
         err,
         if num == 0 {
             String::new()
         } else {
             format!("\x1b[{num}A")
         },
-        prompt(options, colors),
+        prompt,
         to_output(
             &input[start..end],
             vars,
@@ -530,17 +545,13 @@ pub fn place_funcvarxy(
                             i.1[j] = num.clone();
                         } else {
                             match s.as_str() {
-                                "sum" | "summation" | "prod" | "product" | "Σ" | "Π" | "vec"
-                                | "mat" | "D" | "integrate" | "arclength" | "∫" | "area"
-                                | "surfacearea" | "sarea" | "solve" | "length" | "slope"
-                                | "lim" | "set" | "limit" | "iter" | "extrema" | "taylor"
-                                | "isolate"
-                                    if j + 2 < i.1.len()
-                                        && if let Func(s) = &i.1[j + 2] {
-                                            matches!(s.as_str(), "x" | "y")
-                                        } else {
-                                            false
-                                        } =>
+                                s if is_lazy(s)
+                                    && j + 2 < i.1.len()
+                                    && if let Func(s) = &i.1[j + 2] {
+                                        matches!(s.as_str(), "x" | "y")
+                                    } else {
+                                        false
+                                    } =>
                                 {
                                     bracket += 1;
                                     j += 3;
@@ -553,7 +564,8 @@ pub fn place_funcvarxy(
                                         },
                                     ))
                                 }
-                                "surfacearea" | "sarea"
+                                "surfacearea" | "sarea" | "integrate2" | "integrate3"
+                                | "contour" | "odesolve" | "minimize" | "maximize" | "fit"
                                     if j + 4 < i.1.len()
                                         && if let Func(s) = &i.1[j + 4] {
                                             matches!(s.as_str(), "x" | "y")
@@ -572,6 +584,25 @@ pub fn place_funcvarxy(
                                         },
                                     ))
                                 }
+                                "integrate3"
+                                    if j + 6 < i.1.len()
+                                        && if let Func(s) = &i.1[j + 6] {
+                                            matches!(s.as_str(), "x" | "y")
+                                        } else {
+                                            false
+                                        } =>
+                                {
+                                    bracket += 1;
+                                    j += 7;
+                                    sum.push((
+                                        bracket,
+                                        if let Func(s) = &i.1[j - 1] {
+                                            s.to_string()
+                                        } else {
+                                            String::new()
+                                        },
+                                    ))
+                                }
                                 _ => {}
                             }
                         }
@@ -600,16 +631,13 @@ pub fn place_varxy(mut func: Vec<NumStr>, num: NumStr) -> Vec<NumStr> {
                     func[i] = num.clone();
                 } else {
                     match s.as_str() {
-                        "sum" | "summation" | "prod" | "product" | "Σ" | "Π" | "vec" | "mat"
-                        | "D" | "integrate" | "arclength" | "∫" | "area" | "surfacearea"
-                        | "sarea" | "solve" | "length" | "slope" | "lim" | "limit" | "set"
-                        | "iter" | "extrema" | "taylor" | "isolate"
-                            if i + 2 < func.len()
-                                && if let Func(s) = &func[i + 2] {
-                                    matches!(s.as_str(), "x" | "y")
-                                } else {
-                                    false
-                                } =>
+                        s if is_lazy(s)
+                            && i + 2 < func.len()
+                            && if let Func(s) = &func[i + 2] {
+                                matches!(s.as_str(), "x" | "y")
+                            } else {
+                                false
+                            } =>
                         {
                             bracket += 1;
                             i += 3;
@@ -622,7 +650,8 @@ pub fn place_varxy(mut func: Vec<NumStr>, num: NumStr) -> Vec<NumStr> {
                                 },
                             ))
                         }
-                        "surfacearea" | "sarea"
+                        "surfacearea" | "sarea" | "integrate2" | "integrate3" | "contour"
+                        | "odesolve" | "minimize" | "maximize" | "fit"
                             if i + 4 < func.len()
                                 && if let Func(s) = &func[i + 4] {
                                     matches!(s.as_str(), "x" | "y")
@@ -641,6 +670,25 @@ pub fn place_varxy(mut func: Vec<NumStr>, num: NumStr) -> Vec<NumStr> {
                                 },
                             ))
                         }
+                        "integrate3"
+                            if i + 6 < func.len()
+                                && if let Func(s) = &func[i + 6] {
+                                    matches!(s.as_str(), "x" | "y")
+                                } else {
+                                    false
+                                } =>
+                        {
+                            bracket += 1;
+                            i += 7;
+                            sum.push((
+                                bracket,
+                                if let Func(s) = &func[i - 1] {
+                                    s.to_string()
+                                } else {
+                                    String::new()
+                                },
+                            ))
+                        }
                         _ => {}
                     }
                 }
@@ -656,95 +704,66 @@ pub fn place_funcvar(
     var: &str,
     num: NumStr,
 ) -> Vec<(String, Vec<NumStr>)> {
-    if !var.is_empty() {
-        for i in funcvar.iter_mut() {
-            if !i.0.contains('(') {
-                let mut sum = Vec::new();
-                let mut bracket = 0;
-                let mut j = 0;
-                while i.1.len() > j {
-                    match &i.1[j] {
-                        LeftBracket => bracket += 1,
-                        RightBracket => bracket -= 1,
-                        Comma if sum.contains(&bracket) => {
-                            sum.remove(0);
-                        }
-                        Func(s) => {
-                            if s == var && sum.is_empty() {
-                                i.1[j] = num.clone();
-                            } else {
-                                match s.as_str() {
-                                    "sum" | "summation" | "prod" | "product" | "Σ" | "Π"
-                                    | "vec" | "mat" | "D" | "integrate" | "arclength" | "∫"
-                                    | "area" | "solve" | "length" | "slope" | "lim" | "limit"
-                                    | "set" | "iter" | "extrema" | "surfacearea" | "sarea"
-                                    | "taylor" | "isolate"
-                                        if j + 2 < i.1.len()
-                                            && i.1[j + 2] == Func(var.to_string()) =>
-                                    {
-                                        j += 3;
-                                        sum.push(bracket)
-                                    }
-                                    "surfacearea" | "sarea"
-                                        if j + 4 < i.1.len()
-                                            && i.1[j + 4] == Func(var.to_string()) =>
-                                    {
-                                        j += 5;
-                                        sum.push(bracket)
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
-                    j += 1;
-                }
-            }
+    for i in funcvar.iter_mut() {
+        if !i.0.contains('(') {
+            i.1 = place_var(std::mem::take(&mut i.1), var, num.clone())
         }
     }
     funcvar
 }
-pub fn place_var(mut func: Vec<NumStr>, var: &str, num: NumStr) -> Vec<NumStr> {
-    if !var.is_empty() {
-        let mut sum = Vec::new();
-        let mut bracket = 0;
-        let mut i = 0;
-        while func.len() > i {
-            match &func[i] {
-                LeftBracket => bracket += 1,
-                RightBracket => bracket -= 1,
-                Comma if sum.contains(&bracket) => {
-                    sum.remove(0);
-                }
-                Func(s) => {
-                    if s == var && sum.is_empty() {
-                        func[i] = num.clone();
-                    } else {
-                        match s.as_str() {
-                            "sum" | "summation" | "prod" | "product" | "Σ" | "Π" | "vec"
-                            | "mat" | "D" | "integrate" | "arclength" | "∫" | "area" | "solve"
-                            | "length" | "slope" | "lim" | "set" | "limit" | "iter" | "extrema"
-                            | "surfacearea" | "sarea" | "taylor" | "isolate"
-                                if i + 2 < func.len() && func[i + 2] == Func(var.to_string()) =>
-                            {
-                                i += 3;
-                                sum.push(bracket)
-                            }
-                            "surfacearea" | "sarea"
-                                if i + 4 < func.len() && func[i + 4] == Func(var.to_string()) =>
-                            {
-                                i += 5;
-                                sum.push(bracket)
-                            }
-                            _ => {}
+pub fn place_var(func: Vec<NumStr>, var: &str, num: NumStr) -> Vec<NumStr> {
+    if var.is_empty() {
+        return func;
+    }
+    match Expr::from_tokens(&func) {
+        Ok(expr) => expr.place_var(var, &num).to_tokens(),
+        Err(_) => place_tokens(func, var, num),
+    }
+}
+///the token scan for input the tree does not read, a lazy call binding var keeps it up to the next comma
+fn place_tokens(mut func: Vec<NumStr>, var: &str, num: NumStr) -> Vec<NumStr> {
+    let mut sum = Vec::new();
+    let mut bracket = 0;
+    let mut i = 0;
+    while func.len() > i {
+        match &func[i] {
+            LeftBracket => bracket += 1,
+            RightBracket => bracket -= 1,
+            Comma if sum.contains(&bracket) => {
+                sum.remove(0);
+            }
+            Func(s) => {
+                if s == var && sum.is_empty() {
+                    func[i] = num.clone();
+                } else {
+                    match s.as_str() {
+                        s if is_lazy(s)
+                            && i + 2 < func.len()
+                            && func[i + 2] == Func(var.to_string()) =>
+                        {
+                            i += 3;
+                            sum.push(bracket)
                         }
+                        "surfacearea" | "sarea" | "integrate2" | "integrate3" | "contour"
+                        | "odesolve" | "minimize" | "maximize" | "fit"
+                            if i + 4 < func.len() && func[i + 4] == Func(var.to_string()) =>
+                        {
+                            i += 5;
+                            sum.push(bracket)
+                        }
+                        "integrate3"
+                            if i + 6 < func.len() && func[i + 6] == Func(var.to_string()) =>
+                        {
+                            i += 7;
+                            sum.push(bracket)
+                        }
+                        _ => {}
                     }
                 }
-                _ => {}
             }
-            i += 1;
+            _ => {}
         }
+        i += 1;
     }
     func
 }
@@ -754,7 +773,7 @@ pub fn do_math_with_var(
     func_vars: Vec<(String, Vec<NumStr>)>,
     var: &str,
     num: NumStr,
-) -> Result<NumStr, &'static str> {
+) -> Result<NumStr, Error> {
     do_math(
         place_var(function, var, num.clone()),
         options,
@@ -792,8 +811,17 @@ pub fn parsed_to_string(
         i += 1;
     }
     let mut out = String::new();
-    for i in input {
+    let mut input = input.into_iter().peekable();
+    while let Some(i) = input.next() {
         out.push_str(&match i {
+            Num(n)
+                if n.units.is_none()
+                    && n.number == -1
+                    && input.peek() == Some(&InternalMultiplication) =>
+            {
+                input.next();
+                "-".to_string()
+            }
             Num(n) => {
                 let n = custom_units(*n, *options, colors);
                 let n = get_output(*options, colors, &n);
@@ -1,7 +1,9 @@
 use crate::complex::NumStr;
 use crate::help::man;
+use crate::types::{Complex, Float, float::Special::Nan};
 use crate::{
     complex::NumStr::{Matrix, Num, Vector},
+    error::Error,
     help::{help, help_for},
     load_vars::get_vars,
     math::do_math,
@@ -12,7 +14,7 @@ use crate::{
         AngleType::{Degrees, Gradians, Radians},
         Auto, Colors, GraphType, HowGraphing,
         Notation::{LargeEngineering, Normal, Scientific, SmallEngineering},
-        Number, Options, Variable,
+        Number, Options, Type, Variable,
     },
 };
 #[cfg(feature = "bin-deps")]
@@ -20,7 +22,6 @@ use crossterm::{
     execute, terminal,
     terminal::{Clear, ClearType},
 };
-use rug::{Complex, Float, float::Special::Nan};
 use std::{
     fs::File,
     io::{BufRead, BufReader, Stdout, Write},
@@ -32,7 +33,7 @@ pub fn arg_opts(
     args: &mut Vec<String>,
     vars: &[Variable],
     soft: bool,
-) -> Result<bool, &'static str> {
+) -> Result<bool, Error> {
     let mut default = false;
     if soft {
         args.remove(0);
@@ -85,14 +86,14 @@ pub fn arg_opts(
                         split.next().unwrap(),
                         split.next().unwrap(),
                     ) {
-                        Err(s) if !s.is_empty() => {
+                        Err(s) => {
                             if soft {
                                 break;
                             } else {
                                 return Err(s);
                             }
                         }
-                        Ok(()) => {
+                        Ok(false) => {
                             if soft {
                                 break;
                             } else {
@@ -100,7 +101,7 @@ pub fn arg_opts(
                                 process::exit(1);
                             }
                         }
-                        _ => {}
+                        Ok(true) => {}
                     }
                 } else if !silent_commands(options, &arg.chars().collect::<Vec<char>>()) {
                     if soft {
@@ -123,7 +124,7 @@ pub fn file_opts(
     vars: &[Variable],
     check: Vec<usize>,
     soft: bool,
-) -> Result<Vec<usize>, &'static str> {
+) -> Result<Vec<usize>, Error> {
     let mut err = Vec::new();
     if let Ok(file) = File::open(file_path) {
         let reader = BufReader::new(file);
@@ -143,14 +144,14 @@ pub fn file_opts(
                     split.next().unwrap(),
                     split.next().unwrap(),
                 ) {
-                    Err(s) if !s.is_empty() => {
+                    Err(s) => {
                         if soft {
                             err.push(i)
                         } else {
                             return Err(s);
                         }
                     }
-                    Ok(()) => {
+                    Ok(false) => {
                         if soft {
                             err.push(i);
                         } else {
@@ -158,7 +159,7 @@ pub fn file_opts(
                             process::exit(1);
                         }
                     }
-                    _ => {}
+                    Ok(true) => {}
                 }
             } else if !silent_commands(options, &line.chars().collect::<Vec<char>>()) {
                 if soft {
@@ -172,13 +173,109 @@ pub fn file_opts(
     }
     Ok(err)
 }
+///reparses the variables after a change of prec or float_type so their values use the new one
+fn rebuild_vars(vars: &mut [Variable], options: &Options) -> Result<(), Error> {
+    if vars.is_empty() {
+        return Ok(());
+    }
+    let v = get_vars(*options);
+    for var in vars.iter_mut() {
+        for i in &v {
+            if i.name == var.name && i.unparsed == var.unparsed {
+                *var = i.clone();
+            }
+        }
+    }
+    for (i, var) in vars.to_vec().iter().enumerate() {
+        if !var.unparsed.is_empty() {
+            let mut func_vars: Vec<(isize, String)> = Vec::new();
+            if var.name.contains(&'(') {
+                let mut l = var.name.clone();
+                l.drain(0..=l.iter().position(|c| c == &'(').unwrap());
+                l.pop();
+                for i in l.split(|c| c == &',') {
+                    func_vars.push((-1, i.iter().collect()));
+                }
+            }
+            let mut fvs = Vec::new();
+            let mut unparsed = var.unparsed.clone();
+            if unparsed.contains(':') {
+                let un = unparsed;
+                let mut split = un.split(':').collect::<Vec<&str>>();
+                unparsed = split.pop().unwrap().to_string();
+                for i in split {
+                    if i.contains('=') {
+                        let mut split = i.splitn(2, '=');
+                        let s = split.next().unwrap().to_string();
+                        let parsed = input_var(
+                            split.next().unwrap(),
+                            vars,
+                            &mut func_vars,
+                            &mut 0,
+                            *options,
+                            false,
+                            0,
+                            s.chars().collect::<Vec<char>>(),
+                            false,
+                            &mut Vec::new(),
+                            None,
+                            None,
+                        )?;
+                        func_vars.push((-1, s.clone()));
+                        fvs.push((s, parsed.0));
+                        fvs.extend(parsed.1)
+                    }
+                }
+            }
+            let mut parsed = match input_var(
+                &unparsed,
+                vars,
+                &mut func_vars,
+                &mut 0,
+                *options,
+                false,
+                0,
+                var.name.clone(),
+                false,
+                &mut Vec::new(),
+                None,
+                None,
+            ) {
+                Ok(n) => (n.0, n.1),
+                _ => return Err(Error::Setting("prec crash", None)),
+            };
+            parsed.1.extend(fvs);
+            if var.name.contains(&'(')
+                && var
+                    .unparsed
+                    .contains(var.name.split(|c| c == &'(').next().unwrap())
+                && (var.unparsed.contains("piecewise") || var.unparsed.contains("pw"))
+            {
+                parsed
+                    .1
+                    .push((var.name.iter().collect::<String>(), parsed.0.clone()))
+            }
+            vars[i].parsed = if var.name.contains(&'(') {
+                parsed.0
+            } else {
+                vec![
+                    do_math(parsed.0, *options, parsed.1.clone()).unwrap_or(NumStr::new(
+                        Number::from(Complex::new(options.precision()), None),
+                    )),
+                ]
+            };
+            vars[i].funcvars = parsed.1;
+        }
+    }
+    Ok(())
+}
 pub fn set_commands(
     options: &mut Options,
     colors: &mut Colors,
     vars: &mut [Variable],
     l: &str,
     o: &str,
-) -> Result<(), &'static str> {
+) -> Result<bool, Error> {
     let s = o.replace(" ", "");
     let r = s.as_str();
     match l {
@@ -187,7 +284,7 @@ pub fn set_commands(
                 "1" | "true" | "True" | "always" => Auto::True,
                 "Auto" | "auto" => Auto::Auto,
                 "0" | "false" | "False" | "never" => Auto::False,
-                _ => return Err("not true/false/auto"),
+                _ => return Err(Error::Setting("not true/false/auto", None)),
             }
         }
         "line" | "lines" => {
@@ -195,7 +292,7 @@ pub fn set_commands(
                 "1" | "true" | "True" | "always" => Auto::True,
                 "Auto" | "auto" => Auto::Auto,
                 "0" | "false" | "False" | "never" => Auto::False,
-                _ => return Err("not true/false/auto"),
+                _ => return Err(Error::Setting("not true/false/auto", None)),
             }
         }
         "angle" => {
@@ -203,7 +300,17 @@ pub fn set_commands(
                 "deg" | "degree" | "degrees" => Degrees,
                 "rad" | "radians" | "radian" => Radians,
                 "grad" | "gradians" | "gradian" => Gradians,
-                _ => return Err("bad angle type"),
+                _ => return Err(Error::Setting("bad angle type", None)),
+            }
+        }
+        "float_type" | "floattype" => {
+            let t: Type = r.parse()?;
+            if cfg!(not(feature = "fastnum")) && t != Type::default() {
+                return Err(Error::UnsupportedBackend("requires fastnum", None));
+            }
+            if options.float_type != t {
+                options.float_type = t;
+                rebuild_vars(vars, options)?
             }
         }
         "notation" => {
@@ -212,7 +319,7 @@ pub fn set_commands(
                 "engSmall" | "e" => SmallEngineering,
                 "engLarge" | "eng" | "engineering" | "E" => LargeEngineering,
                 "normal" | "n" => Normal,
-                _ => return Err("bad notation type"),
+                _ => return Err(Error::Setting("bad notation type", None)),
             }
         }
         "graph" => {
@@ -223,7 +330,7 @@ pub fn set_commands(
                 "flat" => GraphType::Flat,
                 "domain" | "domain1" | "domain_coloring" => GraphType::Domain,
                 "domain_alt" | "domainalt" | "domain2" => GraphType::DomainAlt,
-                _ => return Err("bad graph type"),
+                _ => return Err(Error::Setting("bad graph type", None)),
             }
         }
         "saveto" => {
@@ -280,7 +387,7 @@ pub fn set_commands(
             if r == "null" {
                 colors.default_units.clear()
             } else {
-                let n = Number::from(Complex::with_val(options.prec, Nan), None);
+                let n = Number::from(Complex::with_val(options.precision(), Nan), None);
                 colors.default_units = r
                     .split(',')
                     .map(|a| {
@@ -330,7 +437,7 @@ pub fn set_commands(
         }
         "point" | "points" => {
             if r.is_empty() {
-                return Err("Invalid point type");
+                return Err(Error::Setting("Invalid point type", None));
             } else {
                 let r = r.chars().next().unwrap();
                 options.point_style = match r {
@@ -348,7 +455,7 @@ pub fn set_commands(
                     'D' => 11,
                     'r' => 12,
                     'R' => 13,
-                    _ => return Err("invalid point type"),
+                    _ => return Err(Error::Setting("invalid point type", None)),
                 }
             }
         }
@@ -357,7 +464,7 @@ pub fn set_commands(
         | "tabbed" | "comma" | "units" | "scalegraph" | "debug" | "vars" | "onaxis" | "base"
         | "ticks" | "decimal" | "deci" | "decimals" | "graphprec" | "graphprecision" | "prec"
         | "windowsize" | "precision" | "range" | "xr" | "yr" | "zr" | "vrange" | "vxr" | "vyr"
-        | "vzr" | "2d" | "3d" | "progress" => {
+        | "vzr" | "2d" | "3d" | "progress" | "exact" => {
             let mut args: Vec<Float> = Vec::new();
             {
                 let mut bracket = 0;
@@ -428,6 +535,7 @@ pub fn set_commands(
                 "siunits" => options.si_units = args[0] != 0.0,
                 "keepzeros" => options.keep_zeros = args[0] != 0.0,
                 "polar" => options.polar = args[0] != 0.0,
+                "exact" => options.exact = args[0] != 0.0,
                 "frac" | "fractions" => options.frac.num = args[0] != 0.0,
                 "fractionsv" => options.frac.vec = args[0] != 0.0,
                 "fractionsm" => options.frac.mat = args[0] != 0.0,
@@ -454,7 +562,7 @@ pub fn set_commands(
                         if (2..=36).contains(&n1) && (2..=36).contains(&n2) {
                             options.base = (n1, n2)
                         } else {
-                            return Err("out of range of 2..=36");
+                            return Err(Error::Setting("out of range of 2..=36", None));
                         }
                     } else {
                         let n = args[0]
@@ -465,7 +573,7 @@ pub fn set_commands(
                         if (2..=36).contains(&n) {
                             options.base = (n, n)
                         } else {
-                            return Err("out of range of 2..=36");
+                            return Err(Error::Setting("out of range of 2..=36", None));
                         }
                     }
                 }
@@ -496,7 +604,7 @@ pub fn set_commands(
                         -1 => usize::MAX - 1,
                         -2 => usize::MAX,
                         n if n >= 0 => n as usize,
-                        _ => return Err("Invalid decimal"),
+                        _ => return Err(Error::Setting("Invalid decimal", None)),
                     };
                 }
                 "graphprec" | "graphprecision" => match args[0]
@@ -506,7 +614,7 @@ pub fn set_commands(
                     .unwrap_or_default()
                 {
                     n if n != 0 => options.graph_prec = n,
-                    _ => return Err("Invalid graphprecision"),
+                    _ => return Err(Error::Setting("Invalid graphprecision", None)),
                 },
                 "windowsize" => {
                     if args.len() == 1 {
@@ -545,109 +653,15 @@ pub fn set_commands(
                 {
                     n if n != 0 => {
                         options.prec = n;
-                        if !vars.is_empty() {
-                            let v = get_vars(*options);
-                            for var in vars.iter_mut() {
-                                for i in &v {
-                                    if i.name == var.name && i.unparsed == var.unparsed {
-                                        *var = i.clone();
-                                    }
-                                }
-                            }
-                            for (i, var) in vars.to_vec().iter().enumerate() {
-                                if !var.unparsed.is_empty() {
-                                    let mut func_vars: Vec<(isize, String)> = Vec::new();
-                                    if var.name.contains(&'(') {
-                                        let mut l = var.name.clone();
-                                        l.drain(0..=l.iter().position(|c| c == &'(').unwrap());
-                                        l.pop();
-                                        for i in l.split(|c| c == &',') {
-                                            func_vars.push((-1, i.iter().collect()));
-                                        }
-                                    }
-                                    let mut fvs = Vec::new();
-                                    let mut unparsed = var.unparsed.clone();
-                                    if unparsed.contains(':') {
-                                        let un = unparsed;
-                                        let mut split = un.split(':').collect::<Vec<&str>>();
-                                        unparsed = split.pop().unwrap().to_string();
-                                        for i in split {
-                                            if i.contains('=') {
-                                                let mut split = i.splitn(2, '=');
-                                                let s = split.next().unwrap().to_string();
-                                                let parsed = input_var(
-                                                    split.next().unwrap(),
-                                                    vars,
-                                                    &mut func_vars,
-                                                    &mut 0,
-                                                    *options,
-                                                    false,
-                                                    0,
-                                                    s.chars().collect::<Vec<char>>(),
-                                                    false,
-                                                    &mut Vec::new(),
-                                                    None,
-                                                    None,
-                                                )?;
-                                                func_vars.push((-1, s.clone()));
-                                                fvs.push((s, parsed.0));
-                                                fvs.extend(parsed.1)
-                                            }
-                                        }
-                                    }
-                                    let mut parsed = match input_var(
-                                        &unparsed,
-                                        vars,
-                                        &mut func_vars,
-                                        &mut 0,
-                                        *options,
-                                        false,
-                                        0,
-                                        var.name.clone(),
-                                        false,
-                                        &mut Vec::new(),
-                                        None,
-                                        None,
-                                    ) {
-                                        Ok(n) => (n.0, n.1),
-                                        _ => return Err("prec crash"),
-                                    };
-                                    parsed.1.extend(fvs);
-                                    if var.name.contains(&'(')
-                                        && var
-                                            .unparsed
-                                            .contains(var.name.split(|c| c == &'(').next().unwrap())
-                                        && (var.unparsed.contains("piecewise")
-                                            || var.unparsed.contains("pw"))
-                                    {
-                                        parsed.1.push((
-                                            var.name.iter().collect::<String>(),
-                                            parsed.0.clone(),
-                                        ))
-                                    }
-                                    vars[i].parsed = if var.name.contains(&'(') {
-                                        parsed.0
-                                    } else {
-                                        vec![
-                                            do_math(parsed.0, *options, parsed.1.clone())
-                                                .unwrap_or(NumStr::new(Number::from(
-                                                    Complex::new(options.prec),
-                                                    None,
-                                                ))),
-                                        ]
-                                    };
-                                    vars[i].funcvars = parsed.1;
-                                }
-                            }
-                        }
+                        rebuild_vars(vars, options)?
                     }
-                    _ => return Err("Invalid precision"),
+                    _ => return Err(Error::Setting("Invalid precision", None)),
                 },
                 "range" => {
                     if args.len() == 1 {
                         let range = args[0].to_f64();
                         if range == 0.0 {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         (
                             options.xr.0,
@@ -661,7 +675,7 @@ pub fn set_commands(
                         let min = args[0].to_f64();
                         let max = args[1].to_f64();
                         if min == max {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         (
                             options.xr.0,
@@ -677,14 +691,14 @@ pub fn set_commands(
                     if args.len() == 1 {
                         let range = args[0].to_f64();
                         if range == 0.0 {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.xr = (-range, range)
                     } else {
                         let min = args[0].to_f64();
                         let max = args[1].to_f64();
                         if min == max {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.xr = (min, max)
                     }
@@ -693,14 +707,14 @@ pub fn set_commands(
                     if args.len() == 1 {
                         let range = args[0].to_f64();
                         if range == 0.0 {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.yr = (-range, range)
                     } else {
                         let min = args[0].to_f64();
                         let max = args[1].to_f64();
                         if min == max {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.yr = (min, max)
                     }
@@ -709,14 +723,14 @@ pub fn set_commands(
                     if args.len() == 1 {
                         let range = args[0].to_f64();
                         if range == 0.0 {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.zr = (-range, range)
                     } else {
                         let min = args[0].to_f64();
                         let max = args[1].to_f64();
                         if min == max {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.zr = (min, max)
                     }
@@ -725,7 +739,7 @@ pub fn set_commands(
                     if args.len() == 1 {
                         let range = args[0].to_f64();
                         if range == 0.0 {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         (
                             options.vxr.0,
@@ -739,7 +753,7 @@ pub fn set_commands(
                         let min = args[0].to_f64();
                         let max = args[1].to_f64();
                         if min == max {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         (
                             options.vxr.0,
@@ -755,14 +769,14 @@ pub fn set_commands(
                     if args.len() == 1 {
                         let range = args[0].to_f64();
                         if range == 0.0 {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.vxr = (-range, range)
                     } else {
                         let min = args[0].to_f64();
                         let max = args[1].to_f64();
                         if min == max {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.vxr = (min, max)
                     }
@@ -771,14 +785,14 @@ pub fn set_commands(
                     if args.len() == 1 {
                         let range = args[0].to_f64();
                         if range == 0.0 {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.vyr = (-range, range)
                     } else {
                         let min = args[0].to_f64();
                         let max = args[1].to_f64();
                         if min == max {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.vyr = (min, max)
                     }
@@ -787,14 +801,14 @@ pub fn set_commands(
                     if args.len() == 1 {
                         let range = args[0].to_f64();
                         if range == 0.0 {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.vzr = (-range, range)
                     } else {
                         let min = args[0].to_f64();
                         let max = args[1].to_f64();
                         if min == max {
-                            return Err("bad range");
+                            return Err(Error::Setting("bad range", None));
                         }
                         options.vzr = (min, max)
                     }
@@ -848,12 +862,12 @@ pub fn set_commands(
                         }
                     }
                 }
-                _ => return Ok(()),
+                _ => return Ok(false),
             }
         }
-        _ => return Ok(()),
+        _ => return Ok(false),
     }
-    Err("")
+    Ok(true)
 }
 pub fn silent_commands(options: &mut Options, input: &[char]) -> bool {
     match input.iter().collect::<String>().as_str() {
@@ -883,6 +897,7 @@ pub fn silent_commands(options: &mut Options, input: &[char]) -> bool {
             };
         }
         "polar" => options.polar = !options.polar,
+        "exact" => options.exact = !options.exact,
         "frac" | "fractions" => options.frac.num = !options.frac.num,
         "fractionsv" => options.frac.vec = !options.frac.vec,
         "fractionsm" => options.frac.mat = !options.frac.mat,
@@ -991,6 +1006,11 @@ pub fn commands(options: &mut Options, lines: &[String], input: &[char], stdout:
             stdout.flush().unwrap();
             options.polar = !options.polar;
         }
+        "exact" => {
+            print!("\x1b[G\x1b[A\x1b[K");
+            stdout.flush().unwrap();
+            options.exact = !options.exact;
+        }
         "frac" | "fractions" => {
             print!("\x1b[G\x1b[A\x1b[K");
             stdout.flush().unwrap();
@@ -1207,6 +1227,7 @@ pub fn equal_to(
         })
         .to_string(),
         "polar" => format!("{}", options.polar),
+        "exact" => format!("{}", options.exact),
         "frac" | "fractions" => format!("{}", options.frac.num),
         "fractionsv" => format!("{}", options.frac.vec),
         "fractionsm" => format!("{}", options.frac.mat),
@@ -1253,6 +1274,15 @@ pub fn equal_to(
             Gradians => "grad",
         }
         .to_string(),
+        "float_type" | "floattype" => match options.float_type {
+            #[cfg(feature = "rug")]
+            Type::Rug => "rug",
+            #[cfg(feature = "fastnum")]
+            Type::Fastnum => "fastnum",
+            Type::F64 => "f64",
+            Type::F32 => "f32",
+        }
+        .to_string(),
         "notation" => match options.notation {
             SmallEngineering => "e",
             LargeEngineering => "E",
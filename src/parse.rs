@@ -1,4 +1,10 @@
+use crate::types::{
+    Complex, Integer, Rational,
+    float::Special::{Infinity, Nan},
+    ops::{CompleteRound, Pow},
+};
 use crate::{
+    cas::symbolic,
     complex::{
         NumStr,
         NumStr::{
@@ -10,19 +16,47 @@ use crate::{
         },
         pow_nth,
     },
+    error::{Error, Span},
+    expr::Expr,
     functions::functions,
     math::do_math,
+    misc::place_var,
     units::{
         GraphType, HowGraphing, Notation::SmallEngineering, Number, Options, Variable, is_unit,
         prefixes, to_unit,
     },
 };
-use rug::{
-    Complex,
-    float::Special::{Infinity, Nan},
-    ops::CompleteRound,
+use std::{
+    ops::{Deref, DerefMut, RangeBounds},
+    vec::Drain,
 };
 #[allow(clippy::type_complexity)]
+pub fn input_expr(
+    input: &str,
+    vars: &[Variable],
+    options: Options,
+) -> Result<(Expr, Vec<(String, Vec<NumStr>)>, bool), Error> {
+    let (parsed, func_vars, _, define, _) = input_tokens(
+        input,
+        vars,
+        &mut Vec::new(),
+        &mut 0,
+        options,
+        false,
+        0,
+        Vec::new(),
+        false,
+        &mut Vec::new(),
+        None,
+        None,
+    )?;
+    Ok((
+        Expr::from_spans(&parsed.tokens, &parsed.spans)?,
+        func_vars,
+        define,
+    ))
+}
+#[allow(clippy::type_complexity)]
 #[allow(clippy::too_many_arguments)]
 pub fn input_var(
     input: &str,
@@ -45,11 +79,52 @@ pub fn input_var(
         bool,
         Option<String>,
     ),
-    &'static str,
+    Error,
+> {
+    let (output, funcvars, graph, define, sumvar) = input_tokens(
+        input,
+        vars,
+        sumrec,
+        bracket,
+        options,
+        print,
+        depth,
+        blacklist,
+        isgraphing,
+        collectvars,
+        solven,
+        ison,
+    )?;
+    Ok((output.tokens, funcvars, graph, define, sumvar))
+}
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+fn input_tokens(
+    input: &str,
+    vars: &[Variable],
+    sumrec: &mut Vec<(isize, String)>,
+    bracket: &mut isize,
+    options: Options,
+    print: bool,
+    depth: usize,
+    blacklist: Vec<char>,
+    isgraphing: bool,
+    collectvars: &mut Vec<(isize, usize)>,
+    solven: Option<usize>,
+    ison: Option<usize>,
+) -> Result<
+    (
+        Tokens,
+        Vec<(String, Vec<NumStr>)>,
+        HowGraphing,
+        bool,
+        Option<String>,
+    ),
+    Error,
 > {
     let mut sumvar: Option<String> = None;
     let mut graph = HowGraphing::default();
-    let prec = (options.prec, options.prec);
+    let prec = (options.precision(), options.precision());
     let mut funcvars = Vec::new();
     if input.starts_with("history")
         || input.starts_with("his")
@@ -59,41 +134,54 @@ pub fn input_var(
         || input.starts_with("break")
         || input.is_empty()
     {
-        return Err(" ");
+        return Err(Error::Empty);
     }
-    let mut undf = false;
+    let mut undf = None;
     let mut funcfailed = false;
     let mut absfailed = false;
     let mut scientific = false;
     let mut abs: Vec<(usize, isize)> = Vec::new();
     let mut neg = false;
-    let n1 = Complex::with_val(options.prec, -1);
+    let n1 = if options.exact {
+        Number::from_rational(Rational::from(-1), None, options.precision())
+    } else {
+        Number::from(Complex::with_val(options.precision(), -1), None)
+    };
     let mut pow = String::new();
     let mut exp = (String::new(), 0);
     let mut subfact: Vec<isize> = Vec::new();
-    let mut err = "";
+    let mut err = None;
     let mut solves = Vec::new();
     let mut solvesp = Vec::new();
     let mut iso = Vec::new();
     let mut isop = Vec::new();
     let mut slope = Vec::new();
-    let mut chars = input
-        .replace('[', "(car{")
-        .replace(']', "})")
-        .chars()
-        .collect::<Vec<char>>();
+    let mut chars = Vec::new();
+    let mut place = Vec::new();
+    for (n, c) in input.chars().enumerate() {
+        match c {
+            '[' => chars.extend(['(', 'c', 'a', 'r', '{']),
+            ']' => chars.extend(['}', ')']),
+            _ => chars.push(c),
+        }
+        place.resize(chars.len(), n);
+    }
+    let len = place.last().map(|n| n + 1).unwrap_or(0);
     if chars.ends_with(&['^']) {
         chars.pop();
+        place.pop();
     }
     if chars.iter().filter(|a| **a == '|').count() % 2 == 1 {
         if chars.ends_with(&['|']) {
-            chars.insert(0, '|')
+            chars.insert(0, '|');
+            place.insert(0, 0)
         } else {
-            chars.push('|')
+            chars.push('|');
+            place.push(len)
         }
     }
     let mut sarea = 0;
-    let mut output: Vec<NumStr> = Vec::new();
+    let mut output = Tokens::default();
     let mut stack_end = Vec::new();
     let mut stack_start = Vec::new();
     let mut i = 0;
@@ -101,19 +189,23 @@ pub fn input_var(
     let mut pwr: (bool, isize, isize) = (false, 0, 0);
     while !chars.is_empty() && chars[0].is_ascii_whitespace() {
         chars.remove(0);
+        place.remove(0);
     }
     while i < chars.len() {
         if chars[i].is_ascii_whitespace() {
             if chars.len().saturating_sub(1) == i {
                 chars.remove(i);
+                place.remove(i);
             } else if (chars[i - 1].is_alphanumeric() || matches!(chars[i - 1], ')' | '}'))
                 && (chars[i + 1].is_alphanumeric() || matches!(chars[i + 1], '(' | '{'))
             {
                 chars[i] = '*'
             } else if chars[i - 1] == '+' && chars[i + 1] == '-' {
                 chars.drain(i - 1..=i);
+                place.drain(i - 1..=i);
             } else {
                 chars.remove(i);
+                place.remove(i);
             }
         } else {
             i += 1;
@@ -138,9 +230,12 @@ pub fn input_var(
         }
     }
     chars.extend(stack_end);
+    place.resize(chars.len(), len);
     for i in stack_start {
         chars.insert(0, i);
+        place.insert(0, 0);
     }
+    swap_var_args(&mut chars, &mut place);
     i = 0;
     let mut sum = (0, String::new());
     let functions = functions();
@@ -155,7 +250,12 @@ pub fn input_var(
     } else {
         chars.iter().filter(|a| a == &&'=').count()
     };
+    let mut begin = 0;
     'main: while i < chars.len() {
+        if i > begin {
+            output.close(to_span(&place, begin, i))
+        }
+        begin = i;
         let c = chars[i];
         if !matches!(
             c,
@@ -200,10 +300,15 @@ pub fn input_var(
             output.push(NumStr::new(Number::from(
                 match Complex::parse_radix(pow.as_bytes(), options.base.0) {
                     Ok(n) => n.complete(prec),
-                    _ => return Err("exponent error"),
+                    _ => {
+                        return Err(Error::Parse(
+                            "exponent error",
+                            Some(to_span(&place, i, i + 1)),
+                        ));
+                    }
                 } * pow_nth(
-                    Complex::with_val(options.prec, (0, 1)),
-                    Complex::with_val(options.prec, i),
+                    Complex::with_val(options.precision(), (0, 1)),
+                    Complex::with_val(options.precision(), i),
                 ),
                 None,
             )));
@@ -228,7 +333,10 @@ pub fn input_var(
                     if i + 1 < chars.len() && chars[i + 1] == '.' {
                         break;
                     } else if dot {
-                        return Err("invalid digit");
+                        return Err(Error::Parse(
+                            "invalid digit",
+                            Some(to_span(&place, i, i + 1)),
+                        ));
                     } else {
                         dot = true;
                         num.push('.')
@@ -248,7 +356,7 @@ pub fn input_var(
                         || chars[i] == '!'
                         || (chars.len() > i + 1 && chars[i] == '/' && chars[i + 1] == '/'))
                 {
-                    output.push(NumStr::new(Number::from(n1.clone(), None)));
+                    output.push(NumStr::new(n1.clone()));
                     output.push(InternalMultiplication);
                 } else {
                     num.insert(0, '-');
@@ -259,13 +367,30 @@ pub fn input_var(
                 num.insert(0, '-');
                 i += 1;
             }
-            output.push(NumStr::new(Number::from(
-                match Complex::parse_radix(num.clone(), options.base.0) {
-                    Ok(n) => n.complete(prec),
-                    Err(_) => return Err("probably radix error"),
-                },
-                None,
-            )));
+            output.push(NumStr::new(if options.exact {
+                match to_rational(&num, options.base.0) {
+                    Some(n) => Number::from_rational(n, None, options.precision()),
+                    None => {
+                        return Err(Error::Parse(
+                            "probably radix error",
+                            Some(to_span(&place, i, i + 1)),
+                        ));
+                    }
+                }
+            } else {
+                Number::from(
+                    match Complex::parse_radix(num.clone(), options.base.0) {
+                        Ok(n) => n.complete(prec),
+                        Err(_) => {
+                            return Err(Error::Parse(
+                                "probably radix error",
+                                Some(to_span(&place, i, i + 1)),
+                            ));
+                        }
+                    },
+                    None,
+                )
+            }));
             if scientific {
                 output.push(RightBracket);
                 scientific = false;
@@ -322,7 +447,10 @@ pub fn input_var(
                                 .skip(i + 1)
                                 .position(|&c| c == '(' || c == ')' || c == ',');
                             if pos.is_none() {
-                                return Err("bad exponent");
+                                return Err(Error::Parse(
+                                    "bad exponent",
+                                    Some(to_span(&place, i, i + 1)),
+                                ));
                             }
                             exp = (chars[i + 1..=i + pos.unwrap()].iter().collect(), *bracket);
                             i += pos.unwrap() + 1;
@@ -335,85 +463,88 @@ pub fn input_var(
                 '√' => output.push(Func("sqrt".to_string())),
                 '∛' => output.push(Func("cbrt".to_string())),
                 '¼' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 0.25),
+                    Complex::with_val(options.precision(), 0.25),
                     None,
                 ))),
                 '½' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 0.5),
+                    Complex::with_val(options.precision(), 0.5),
                     None,
                 ))),
                 '¾' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 0.75),
+                    Complex::with_val(options.precision(), 0.75),
                     None,
                 ))),
                 '⅒' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 1) / 10,
+                    Complex::with_val(options.precision(), 1) / 10,
                     None,
                 ))),
                 '⅕' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 1) / 5,
+                    Complex::with_val(options.precision(), 1) / 5,
                     None,
                 ))),
                 '⅖' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 2) / 5,
+                    Complex::with_val(options.precision(), 2) / 5,
                     None,
                 ))),
                 '⅗' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 3) / 5,
+                    Complex::with_val(options.precision(), 3) / 5,
                     None,
                 ))),
                 '⅘' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 4) / 5,
+                    Complex::with_val(options.precision(), 4) / 5,
                     None,
                 ))),
                 '⅐' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 7).recip(),
+                    Complex::with_val(options.precision(), 7).recip(),
                     None,
                 ))),
                 '⅑' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 9).recip(),
+                    Complex::with_val(options.precision(), 9).recip(),
                     None,
                 ))),
                 '⅓' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 3).recip(),
+                    Complex::with_val(options.precision(), 3).recip(),
                     None,
                 ))),
                 '⅔' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 1.5).recip(),
+                    Complex::with_val(options.precision(), 1.5).recip(),
                     None,
                 ))),
                 '⅙' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 6).recip(),
+                    Complex::with_val(options.precision(), 6).recip(),
                     None,
                 ))),
                 '⅚' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 5) / 6,
+                    Complex::with_val(options.precision(), 5) / 6,
                     None,
                 ))),
                 '⅛' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 0.125),
+                    Complex::with_val(options.precision(), 0.125),
                     None,
                 ))),
                 '⅜' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 3) / 8,
+                    Complex::with_val(options.precision(), 3) / 8,
                     None,
                 ))),
                 '⅝' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 5) / 8,
+                    Complex::with_val(options.precision(), 5) / 8,
                     None,
                 ))),
                 '⅞' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, 7) / 8,
+                    Complex::with_val(options.precision(), 7) / 8,
                     None,
                 ))),
                 '⅟' => {
                     output.push(NumStr::new(Number::from(
-                        Complex::with_val(options.prec, 1),
+                        Complex::with_val(options.precision(), 1),
                         None,
                     )));
                     output.push(Division)
                 }
-                '↉' => output.push(NumStr::new(Number::from(Complex::new(options.prec), None))),
+                '↉' => output.push(NumStr::new(Number::from(
+                    Complex::new(options.precision()),
+                    None,
+                ))),
                 '⁰' | '₀' => pow.push('0'),
                 '⁹' | '₉' => pow.push('9'),
                 '⁸' | '₈' => pow.push('8'),
@@ -501,7 +632,13 @@ pub fn input_var(
                     } else if chars[i - 1] == '<' {
                         output.push(LesserEqual);
                     } else if *bracket == 0 {
-                        return Ok((Vec::new(), Vec::new(), HowGraphing::default(), true, None));
+                        return Ok((
+                            Tokens::default(),
+                            Vec::new(),
+                            HowGraphing::default(),
+                            true,
+                            None,
+                        ));
                     } else if i == 0
                         || matches!(chars[i - 1], '(' | '{' | '[')
                         || (chars[i - 1] == '|' && !abs.is_empty())
@@ -546,7 +683,10 @@ pub fn input_var(
                             Comma | LeftBracket | LeftCurlyBracket
                         )
                     {
-                        output.push(NumStr::new(Number::from(Complex::new(options.prec), None)))
+                        output.push(NumStr::new(Number::from(
+                            Complex::new(options.precision()),
+                            None,
+                        )))
                     }
                     output.push(PlusMinus)
                 }
@@ -572,7 +712,7 @@ pub fn input_var(
                         i += 1;
                     } else if chars[i + 1] == '-' {
                         place_multiplier(&mut output, sumrec, &sumvar);
-                        output.push(NumStr::new(Number::from(n1.clone(), None)));
+                        output.push(NumStr::new(n1.clone()));
                         output.push(Division);
                         i += 1;
                     } else {
@@ -602,7 +742,10 @@ pub fn input_var(
                                 Comma | LeftBracket | LeftCurlyBracket
                             )
                         {
-                            output.push(NumStr::new(Number::from(Complex::new(options.prec), None)))
+                            output.push(NumStr::new(Number::from(
+                                Complex::new(options.precision()),
+                                None,
+                            )))
                         }
                         i += 1;
                         output.push(PlusMinus)
@@ -620,7 +763,10 @@ pub fn input_var(
                             Comma | LeftBracket | LeftCurlyBracket
                         )
                     {
-                        output.push(NumStr::new(Number::from(Complex::new(options.prec), None)))
+                        output.push(NumStr::new(Number::from(
+                            Complex::new(options.precision()),
+                            None,
+                        )))
                     }
                     i += 1;
                     output.push(PlusMinus)
@@ -661,7 +807,7 @@ pub fn input_var(
                         || (i > 1 && chars[i - 1] == '/' && chars[i - 2] == '/')
                     {
                         output.push(LeftBracket);
-                        output.push(NumStr::new(Number::from(n1.clone(), None)));
+                        output.push(NumStr::new(n1.clone()));
                         output.push(InternalMultiplication);
                         pwr.0 = true;
                         pwr.1 = *bracket;
@@ -683,7 +829,7 @@ pub fn input_var(
                         if i + 1 != chars.len()
                             && matches!(chars[i + 1], '(' | '{' | '[' | '|' | '-' | '!')
                         {
-                            output.push(NumStr::new(Number::from(n1.clone(), None)));
+                            output.push(NumStr::new(n1.clone()));
                             output.push(InternalMultiplication);
                         } else {
                             neg = true;
@@ -839,13 +985,13 @@ pub fn input_var(
                         if solves[0].1 == 2 {
                             output.push(Comma);
                             output.push(NumStr::new(Number::from(
-                                Complex::with_val(options.prec, (Nan, 1)),
+                                Complex::with_val(options.precision(), (Nan, 1)),
                                 None,
                             )));
                         } else if solves[0].1 == 1 {
                             output.push(Comma);
                             output.push(NumStr::new(Number::from(
-                                Complex::with_val(options.prec, Nan),
+                                Complex::with_val(options.precision(), Nan),
                                 None,
                             )));
                         }
@@ -875,13 +1021,13 @@ pub fn input_var(
                         if solvesp[0].1 == 2 {
                             output.push(Comma);
                             output.push(NumStr::new(Number::from(
-                                Complex::with_val(options.prec, (Nan, 1)),
+                                Complex::with_val(options.precision(), (Nan, 1)),
                                 None,
                             )));
                         } else if solvesp[0].1 == 1 {
                             output.push(Comma);
                             output.push(NumStr::new(Number::from(
-                                Complex::with_val(options.prec, Nan),
+                                Complex::with_val(options.precision(), Nan),
                                 None,
                             )));
                         }
@@ -902,7 +1048,12 @@ pub fn input_var(
                         output.push(NumStr::new(Number::from(
                             match Complex::parse_radix(exp.0.as_bytes(), options.base.0) {
                                 Ok(n) => n.complete(prec),
-                                _ => return Err("exponent error"),
+                                _ => {
+                                    return Err(Error::Parse(
+                                        "exponent error",
+                                        Some(to_span(&place, i, i + 1)),
+                                    ));
+                                }
                             },
                             None,
                         )));
@@ -1093,7 +1244,10 @@ pub fn input_var(
                         if j != -1 {
                             output.insert(
                                 j as usize,
-                                NumStr::new(Number::from(Complex::with_val(options.prec, 1), None)),
+                                NumStr::new(Number::from(
+                                    Complex::with_val(options.precision(), 1),
+                                    None,
+                                )),
                             );
                             output.insert(j as usize, LeftBracket);
                             output.insert(j as usize, Multiplication);
@@ -1104,13 +1258,13 @@ pub fn input_var(
                             | Some(RightBracket)
                             | Some(RightCurlyBracket) => {}
                             _ => output.push(NumStr::new(Number::from(
-                                Complex::with_val(options.prec, 1),
+                                Complex::with_val(options.precision(), 1),
                                 None,
                             ))),
                         }
                         output.push(Multiplication);
                         output.push(NumStr::new(Number::from(
-                            Complex::with_val(options.prec, 1) / 100,
+                            Complex::with_val(options.precision(), 1) / 100,
                             None,
                         )));
                         if j != -1 {
@@ -1121,7 +1275,7 @@ pub fn input_var(
                     }
                 }
                 '∞' => output.push(NumStr::new(Number::from(
-                    Complex::with_val(options.prec, Infinity),
+                    Complex::with_val(options.precision(), Infinity),
                     None,
                 ))),
                 '#' => {
@@ -1141,7 +1295,8 @@ pub fn input_var(
                 depthcheck = !depthcheck;
             } else if c.is_alphabetic()
                 || matches!(*c, '°' | '\'' | '`' | '_' | '∫' | '$' | '¢')
-                || (c == &'2' && word == "atan")
+                || (c == &'2' && matches!(word.as_str(), "atan" | "norm" | "integrate"))
+                || (c == &'3' && word == "integrate")
             {
                 word.push(*c);
             } else if !depthcheck {
@@ -1181,6 +1336,7 @@ pub fn input_var(
                     && word != "any"
                     && word != "unity"
                     && word != "identity"
+                    && word != "simplify"
                     && !word.ends_with("day")
                     && !word.ends_with("gravity")
                     && !word.ends_with("ly")
@@ -1209,7 +1365,10 @@ pub fn input_var(
                 word.pop();
             }
             if is_area && is_slope {
-                return Err("both ' and `");
+                return Err(Error::Parse(
+                    "both ' and `",
+                    Some(to_span(&place, i, i + 1)),
+                ));
             }
         }
         if (word == "piecewise" || word == "pw") && piecewise == 0 {
@@ -1240,6 +1399,25 @@ pub fn input_var(
                 | "set"
                 | "limit"
                 | "isolate"
+                | "fit"
+                | "maximize"
+                | "minimize"
+                | "roots"
+                | "odesolve"
+                | "contour"
+                | "integrate3"
+                | "integrate2"
+                | "funm"
+                | "diff"
+                | "collect"
+                | "factor"
+                | "discriminant"
+                | "apart"
+                | "antiderivative"
+                | "pgcd"
+                | "pdiv"
+                | "prem"
+                | "resultant"
         ) && chars.len() > i + countv + 1
             && var_overrule
             && chars[i + countv] == '('
@@ -1263,8 +1441,16 @@ pub fn input_var(
                 | "length" | "∫" | "area" | "sum" | "Σ" | "summation" | "Π" | "taylor" => {
                     place >= 3
                 }
-                "sarea" | "surfacearea" => place >= 6,
-                "solve" | "extrema" | "isolate" => place >= 1,
+                "sarea" | "surfacearea" | "integrate2" => place >= 6,
+                "integrate3" => place >= 9,
+                "contour" | "odesolve" => place >= 5,
+                "solve" | "extrema" | "isolate" | "diff" | "collect" | "factor"
+                | "discriminant" | "apart" | "antiderivative" => place >= 1,
+                "pgcd" | "pdiv" | "prem" | "resultant" => place >= 2,
+                "funm" => false,
+                "roots" => place >= 3,
+                "minimize" | "maximize" => place >= 2,
+                "fit" => place >= 3,
                 "D" | "slope" | "lim" | "limit" | "set" => place >= 2,
                 _ => place > 0,
             } {
@@ -1307,7 +1493,14 @@ pub fn input_var(
                         output.push(Func(sum.1));
                     }
                     output.push(Comma);
-                    if matches!(word.as_str(), "surfacearea" | "sarea") {
+                    for _ in 0..match word.as_str() {
+                        "surfacearea" | "sarea" | "integrate2" | "contour" | "odesolve" => 1,
+                        "integrate3" => 2,
+                        "minimize" | "maximize" | "fit" => {
+                            leading_vars(&chars[i + countv + count + 1..])
+                        }
+                        _ => 0,
+                    } {
                         sarea += 1;
                         sum.0 = *bracket + 1;
                         sum.1 = String::new();
@@ -1319,11 +1512,12 @@ pub fn input_var(
                                 break;
                             }
                         }
-                        for (i, j) in sumrec.iter().enumerate() {
-                            if j.1.chars().count() <= sum.1.len() {
-                                sumrec.insert(i, sum.clone());
-                                break;
-                            }
+                        match sumrec
+                            .iter()
+                            .position(|j| j.1.chars().count() <= sum.1.len())
+                        {
+                            Some(i) => sumrec.insert(i, sum.clone()),
+                            None => sumrec.push(sum.clone()),
                         }
                         if sumrec.iter().any(|c| c.0 == -1) {
                             output.push(Func("@".to_owned() + &sum.1));
@@ -1337,7 +1531,10 @@ pub fn input_var(
                     continue;
                 }
             } else if place == 0 {
-                if matches!(word.as_str(), "extrema" | "solve" | "isolate") {
+                if matches!(
+                    word.as_str(),
+                    "extrema" | "solve" | "isolate" | "diff" | "collect"
+                ) {
                     *bracket += 1;
                     place_multiplier(&mut output, sumrec, &sumvar);
                     output.push(Func(word.clone()));
@@ -1404,7 +1601,7 @@ pub fn input_var(
         {
             place_multiplier(&mut output, sumrec, &sumvar);
             if neg {
-                output.push(NumStr::new(Number::from(n1.clone(), None)));
+                output.push(NumStr::new(n1.clone()));
                 output.push(InternalMultiplication);
                 neg = false;
             }
@@ -1448,7 +1645,7 @@ pub fn input_var(
         {
             place_multiplier(&mut output, sumrec, &sumvar);
             if neg {
-                output.push(NumStr::new(Number::from(n1.clone(), None)));
+                output.push(NumStr::new(n1.clone()));
                 output.push(InternalMultiplication);
                 neg = false;
             }
@@ -1460,22 +1657,25 @@ pub fn input_var(
                 match word.to_ascii_lowercase().as_str() {
                     "nan" | "NaN" => {
                         output.push(NumStr::new(Number::from(
-                            Complex::with_val(options.prec, Nan),
+                            Complex::with_val(options.precision(), Nan),
                             None,
                         )));
                     }
                     "true" => {
                         output.push(NumStr::new(Number::from(
-                            Complex::with_val(options.prec, 1),
+                            Complex::with_val(options.precision(), 1),
                             None,
                         )));
                     }
                     "false" => {
-                        output.push(NumStr::new(Number::from(Complex::new(options.prec), None)));
+                        output.push(NumStr::new(Number::from(
+                            Complex::new(options.precision()),
+                            None,
+                        )));
                     }
                     "inf" => {
                         output.push(NumStr::new(Number::from(
-                            Complex::with_val(options.prec, Infinity),
+                            Complex::with_val(options.precision(), Infinity),
                             None,
                         )));
                     }
@@ -1500,6 +1700,7 @@ pub fn input_var(
             } else {
                 if chars[i] == '*' {
                     chars.remove(i);
+                    place.remove(i);
                 }
                 if is_slope {
                     slope.push((output.len(), true, nth));
@@ -1516,7 +1717,7 @@ pub fn input_var(
             if word.eq_ignore_ascii_case("not") {
                 place_multiplier(&mut output, sumrec, &sumvar);
                 if neg {
-                    output.push(NumStr::new(Number::from(n1.clone(), None)));
+                    output.push(NumStr::new(n1.clone()));
                     output.push(InternalMultiplication);
                     neg = false;
                 }
@@ -1526,6 +1727,7 @@ pub fn input_var(
             i += countv;
             if i < chars.len() && chars[i] == '*' {
                 chars.remove(i);
+                place.remove(i);
             }
             match word.to_ascii_lowercase().as_str() {
                 "and" => output.push(And),
@@ -1548,7 +1750,7 @@ pub fn input_var(
         {
             place_multiplier(&mut output, sumrec, &sumvar);
             if neg {
-                output.push(NumStr::new(Number::from(n1.clone(), None)));
+                output.push(NumStr::new(n1.clone()));
                 output.push(InternalMultiplication);
                 neg = false;
             }
@@ -1597,6 +1799,7 @@ pub fn input_var(
         } else if options.units && word.starts_with("to") && !output.is_empty() && var_overrule {
             if i != 0 && chars[i - 1] == '*' {
                 chars.remove(i - 1);
+                place.remove(i - 1);
             }
             if output.last() == Some(&Multiplication) {
                 output.pop();
@@ -1605,6 +1808,7 @@ pub fn input_var(
             output.push(Conversion);
             if chars.len() > i && (chars[i] == '*' || chars[i] == '×') {
                 chars.remove(i);
+                place.remove(i);
             }
         } else {
             for var in vars {
@@ -1635,7 +1839,10 @@ pub fn input_var(
                         }
                     }
                     if area != 0 && slope != 0 {
-                        return Err("both ' and `");
+                        return Err(Error::Parse(
+                            "both ' and `",
+                            Some(to_span(&place, i, i + 1)),
+                        ));
                     }
                     if var.name.contains(&'(')
                         && i + vn.len() + area + slope < chars.len()
@@ -1677,7 +1884,10 @@ pub fn input_var(
                         }
                         if blacklist == var.name && piecewise == 0 {
                             if collectvars.is_empty() {
-                                return Err("recursive");
+                                return Err(Error::Parse(
+                                    "recursive",
+                                    Some(to_span(&place, i, i + 1)),
+                                ));
                             }
                         } else {
                             count = 0;
@@ -1727,7 +1937,7 @@ pub fn input_var(
                             {
                                 place_multiplier(&mut output, sumrec, &sumvar);
                                 if neg {
-                                    output.push(NumStr::new(Number::from(n1.clone(), None)));
+                                    output.push(NumStr::new(n1.clone()));
                                     output.push(InternalMultiplication);
                                     neg = false;
                                 }
@@ -1824,7 +2034,11 @@ pub fn input_var(
                                         ) {
                                             Ok(f) => f,
                                             Err(s) => {
-                                                err = s;
+                                                err = Some(s.with_span(to_span(
+                                                    &place,
+                                                    i,
+                                                    i + countv,
+                                                )));
                                                 continue;
                                             }
                                         };
@@ -1839,7 +2053,7 @@ pub fn input_var(
                                         }
                                         if exit {
                                             return Ok((
-                                                Vec::new(),
+                                                Tokens::default(),
                                                 Vec::new(),
                                                 HowGraphing::default(),
                                                 true,
@@ -1952,7 +2166,11 @@ pub fn input_var(
                                             match do_math(parsed, options, func) {
                                                 Ok(f) => f,
                                                 Err(s) => {
-                                                    err = s;
+                                                    err = Some(s.with_span(to_span(
+                                                        &place,
+                                                        i,
+                                                        i + countv,
+                                                    )));
                                                     continue;
                                                 }
                                             }
@@ -2071,7 +2289,7 @@ pub fn input_var(
                                     if area != 0 && var.name.contains(&',') {
                                         output.push(Comma);
                                         output.push(NumStr::new(Number::from(
-                                            Complex::new(options.prec),
+                                            Complex::new(options.precision()),
                                             None,
                                         )))
                                     }
@@ -2084,7 +2302,7 @@ pub fn input_var(
                                     if area + slope != 1 {
                                         output.push(Comma);
                                         output.push(NumStr::new(Number::from(
-                                            Complex::with_val(options.prec, area + slope),
+                                            Complex::with_val(options.precision(), area + slope),
                                             None,
                                         )))
                                     }
@@ -2110,7 +2328,12 @@ pub fn input_var(
                                         match Complex::parse_radix(exp.0.as_bytes(), options.base.0)
                                         {
                                             Ok(n) => n.complete(prec),
-                                            _ => return Err("exponent error"),
+                                            _ => {
+                                                return Err(Error::Parse(
+                                                    "exponent error",
+                                                    Some(to_span(&place, i, i + 1)),
+                                                ));
+                                            }
                                         },
                                         None,
                                     )));
@@ -2121,7 +2344,7 @@ pub fn input_var(
                             } else {
                                 place_multiplier(&mut output, sumrec, &sumvar);
                                 if neg {
-                                    output.push(NumStr::new(Number::from(n1.clone(), None)));
+                                    output.push(NumStr::new(n1.clone()));
                                     output.push(InternalMultiplication);
                                     neg = false;
                                 }
@@ -2179,7 +2402,7 @@ pub fn input_var(
                                     ) {
                                         Ok(f) => f,
                                         Err(s) => {
-                                            err = s;
+                                            err = Some(s.with_span(to_span(&place, i, i + countv)));
                                             continue;
                                         }
                                     };
@@ -2194,7 +2417,7 @@ pub fn input_var(
                                     }
                                     if exit {
                                         return Ok((
-                                            Vec::new(),
+                                            Tokens::default(),
                                             Vec::new(),
                                             HowGraphing::default(),
                                             true,
@@ -2310,7 +2533,11 @@ pub fn input_var(
                                         vec![match do_math(parsed, options, func) {
                                             Ok(f) => f,
                                             Err(s) => {
-                                                err = s;
+                                                err = Some(s.with_span(to_span(
+                                                    &place,
+                                                    i,
+                                                    i + countv,
+                                                )));
                                                 continue;
                                             }
                                         }]
@@ -2423,7 +2650,7 @@ pub fn input_var(
                                     if area != 0 {
                                         output.push(Comma);
                                         output.push(NumStr::new(Number::from(
-                                            Complex::new(options.prec),
+                                            Complex::new(options.precision()),
                                             None,
                                         )))
                                     }
@@ -2432,7 +2659,7 @@ pub fn input_var(
                                     if area + slope != 1 {
                                         output.push(Comma);
                                         output.push(NumStr::new(Number::from(
-                                            Complex::with_val(options.prec, area + slope),
+                                            Complex::with_val(options.precision(), area + slope),
                                             None,
                                         )))
                                     }
@@ -2458,7 +2685,12 @@ pub fn input_var(
                                         match Complex::parse_radix(exp.0.as_bytes(), options.base.0)
                                         {
                                             Ok(n) => n.complete(prec),
-                                            _ => return Err("exponent error"),
+                                            _ => {
+                                                return Err(Error::Parse(
+                                                    "exponent error",
+                                                    Some(to_span(&place, i, i + 1)),
+                                                ));
+                                            }
                                         },
                                         None,
                                     )));
@@ -2475,7 +2707,10 @@ pub fn input_var(
                     {
                         if blacklist == var.name {
                             if collectvars.is_empty() {
-                                return Err("recursive");
+                                return Err(Error::Parse(
+                                    "recursive",
+                                    Some(to_span(&place, i, i + 1)),
+                                ));
                             }
                         } else {
                             i += if chars[i..i + var.name.len()].contains(&'@')
@@ -2513,7 +2748,7 @@ pub fn input_var(
                             };
                             place_multiplier(&mut output, sumrec, &sumvar);
                             if neg {
-                                output.push(NumStr::new(Number::from(n1.clone(), None)));
+                                output.push(NumStr::new(n1.clone()));
                                 output.push(InternalMultiplication);
                                 neg = false;
                             }
@@ -2529,7 +2764,10 @@ pub fn input_var(
                             if !var.parsed.is_empty() {
                                 output.push(var.parsed[0].clone());
                             } else {
-                                return Err("bad input2");
+                                return Err(Error::Parse(
+                                    "bad input2",
+                                    Some(to_span(&place, i, i + 1)),
+                                ));
                             }
                             if print {
                                 output.push(RightBracket);
@@ -2574,7 +2812,7 @@ pub fn input_var(
                 })
             {
                 if neg {
-                    output.push(NumStr::new(Number::from(n1.clone(), None)));
+                    output.push(NumStr::new(n1.clone()));
                     output.push(InternalMultiplication);
                     neg = false;
                 }
@@ -2603,7 +2841,7 @@ pub fn input_var(
                         }
                         place_multiplier(&mut output, sumrec, &sumvar);
                         output.push(NumStr::new(Number::from(
-                            Complex::with_val(options.prec, options.base.0),
+                            Complex::with_val(options.precision(), options.base.0),
                             None,
                         )));
                         if i + 1 != chars.len()
@@ -2658,7 +2896,7 @@ pub fn input_var(
                     'i' => {
                         place_multiplier(&mut output, sumrec, &sumvar);
                         output.push(NumStr::new(Number::from(
-                            Complex::with_val(options.prec, (0, 1)),
+                            Complex::with_val(options.precision(), (0, 1)),
                             None,
                         )));
                         if pwr.0
@@ -2692,7 +2930,7 @@ pub fn input_var(
                         output.push(Func('y'.to_string()));
                         output.push(Multiplication);
                         output.push(NumStr::new(Number::from(
-                            Complex::with_val(options.prec, (0, 1)),
+                            Complex::with_val(options.precision(), (0, 1)),
                             None,
                         )));
                         output.push(RightBracket);
@@ -2719,7 +2957,7 @@ pub fn input_var(
                     _ => {
                         if !collectvars.is_empty() {
                             if neg {
-                                output.push(NumStr::new(Number::from(n1.clone(), None)));
+                                output.push(NumStr::new(n1.clone()));
                                 output.push(InternalMultiplication);
                                 neg = false;
                             }
@@ -2757,7 +2995,7 @@ pub fn input_var(
                 }
             } else if !collectvars.is_empty() || solvesn != 0 || ison != 0 {
                 if neg {
-                    output.push(NumStr::new(Number::from(n1.clone(), None)));
+                    output.push(NumStr::new(n1.clone()));
                     output.push(InternalMultiplication);
                     neg = false;
                 }
@@ -2841,13 +3079,16 @@ pub fn input_var(
             {
                 output.push(Func("@".to_owned() + &wordv));
             } else {
-                undf = true
+                undf = undf.or(Some(to_span(&place, i, i + countv)))
             }
             funcfailed = false;
             absfailed = false;
             i += 1;
         }
     }
+    if i > begin {
+        output.close(to_span(&place, begin, i))
+    }
     for _ in 0..pwr.2 + ceilfoor {
         output.push(RightBracket);
     }
@@ -2855,13 +3096,13 @@ pub fn input_var(
         if s.1 == 2 {
             output.push(Comma);
             output.push(NumStr::new(Number::from(
-                Complex::with_val(options.prec, (Nan, 1)),
+                Complex::with_val(options.precision(), (Nan, 1)),
                 None,
             )));
         } else if s.1 == 1 {
             output.push(Comma);
             output.push(NumStr::new(Number::from(
-                Complex::with_val(options.prec, Nan),
+                Complex::with_val(options.precision(), Nan),
                 None,
             )));
         }
@@ -2874,13 +3115,13 @@ pub fn input_var(
         if s.1 == 2 {
             output.push(Comma);
             output.push(NumStr::new(Number::from(
-                Complex::with_val(options.prec, (Nan, 1)),
+                Complex::with_val(options.precision(), (Nan, 1)),
                 None,
             )));
         } else if s.1 == 1 {
             output.push(Comma);
             output.push(NumStr::new(Number::from(
-                Complex::with_val(options.prec, Nan),
+                Complex::with_val(options.precision(), Nan),
                 None,
             )));
         }
@@ -2914,10 +3155,15 @@ pub fn input_var(
         output.push(NumStr::new(Number::from(
             match Complex::parse_radix(pow.as_bytes(), options.base.0) {
                 Ok(n) => n.complete(prec),
-                _ => return Err("exponent error"),
+                _ => {
+                    return Err(Error::Parse(
+                        "exponent error",
+                        Some(to_span(&place, i, i + 1)),
+                    ));
+                }
             } * pow_nth(
-                Complex::with_val(options.prec, (0, 1)),
-                Complex::with_val(options.prec, i),
+                Complex::with_val(options.precision(), (0, 1)),
+                Complex::with_val(options.precision(), i),
             ),
             None,
         )));
@@ -2927,13 +3173,18 @@ pub fn input_var(
         output.push(NumStr::new(Number::from(
             match Complex::parse_radix(exp.0.as_bytes(), options.base.0) {
                 Ok(n) => n.complete(prec),
-                _ => return Err("exponent error"),
+                _ => {
+                    return Err(Error::Parse(
+                        "exponent error",
+                        Some(to_span(&place, i, i + 1)),
+                    ));
+                }
             },
             None,
         )));
     }
     if neg {
-        output.push(NumStr::new(Number::from(n1, None)));
+        output.push(NumStr::new(n1));
     }
     for _ in abs {
         output.push(RightBracket);
@@ -2997,7 +3248,10 @@ pub fn input_var(
         if nth != 1 {
             output.insert(
                 n + 6 + last - end,
-                NumStr::new(Number::from(Complex::with_val(options.prec, nth), None)),
+                NumStr::new(Number::from(
+                    Complex::with_val(options.precision(), nth),
+                    None,
+                )),
             );
             output.insert(n + 6 + last - end, Comma);
         }
@@ -3006,7 +3260,7 @@ pub fn input_var(
         if !slope && !start_to_end {
             output.insert(
                 n + 6 + last - end,
-                NumStr::new(Number::from(Complex::new(options.prec), None)),
+                NumStr::new(Number::from(Complex::new(options.precision()), None)),
             );
             output.insert(n + 6 + last - end, Comma);
         }
@@ -3098,26 +3352,33 @@ pub fn input_var(
                             || output[i + 1].str_is("epoch"))))
                     && functions.contains(s.as_str())
                     && !sumrec.iter().any(|a| a.1 == *s)
-                    && i + 1 < output.len() =>
+                    && i + 1 < output.len()
+                    && !matches!(
+                        output[i + 1],
+                        LeftBracket | RightBracket | LeftCurlyBracket | RightCurlyBracket
+                    ) =>
             {
-                if !matches!(
-                    output[i + 1],
-                    LeftBracket | RightBracket | LeftCurlyBracket | RightCurlyBracket
-                ) {
-                    output.insert(i + 2, RightBracket);
-                    output.insert(i + 1, LeftBracket);
-                }
+                output.insert(i + 2, RightBracket);
+                output.insert(i + 1, LeftBracket);
             }
             _ => {}
         }
         i += 1;
     }
-    if !err.is_empty() {
+    if let Some(err) = err {
         return Err(err);
     }
+    for v in symbolic(&mut output.tokens, &funcvars, options)? {
+        if collectvars.is_empty() && matches!(v.as_str(), "x" | "y") {
+            graph.graph = true;
+            graph.x |= v == "x";
+            graph.y |= v == "y";
+        }
+    }
     if isgraphing && (graph.x || graph.y) && !print {
-        simplify(&mut output, &mut funcvars, options)
+        simplify(&mut output.tokens, &mut funcvars, options)
     }
+    output.spans.resize(output.tokens.len(), None);
     while let Some(e) = output.last() {
         match e {
             Plus
@@ -3157,15 +3418,85 @@ pub fn input_var(
             _ => break,
         }
     }
-    if undf {
-        return Err("undefined var");
+    if let Some(span) = undf {
+        return Err(Error::Parse("undefined var", Some(span)));
     }
     if options.graphtype == GraphType::None {
         graph = HowGraphing::default()
     }
     Ok((output, funcvars, graph, false, sumvar))
 }
-fn place_multiplier(output: &mut Vec<NumStr>, sumrec: &[(isize, String)], sumvar: &Option<String>) {
+#[derive(Default)]
+struct Tokens {
+    tokens: Vec<NumStr>,
+    spans: Vec<Option<Span>>,
+}
+impl Deref for Tokens {
+    type Target = Vec<NumStr>;
+    fn deref(&self) -> &Vec<NumStr> {
+        &self.tokens
+    }
+}
+impl DerefMut for Tokens {
+    fn deref_mut(&mut self) -> &mut Vec<NumStr> {
+        &mut self.tokens
+    }
+}
+impl Tokens {
+    fn push(&mut self, token: NumStr) {
+        self.tokens.push(token);
+        self.spans.push(None)
+    }
+    fn insert(&mut self, i: usize, token: NumStr) {
+        self.tokens.insert(i, token);
+        self.spans.insert(i, None)
+    }
+    fn remove(&mut self, i: usize) -> NumStr {
+        self.spans.remove(i);
+        self.tokens.remove(i)
+    }
+    fn pop(&mut self) -> Option<NumStr> {
+        self.spans.pop();
+        self.tokens.pop()
+    }
+    fn extend<I: IntoIterator<Item = NumStr>>(&mut self, tokens: I) {
+        self.tokens.extend(tokens);
+        self.spans.resize(self.tokens.len(), None)
+    }
+    fn drain<R: RangeBounds<usize> + Clone>(&mut self, range: R) -> Drain<'_, NumStr> {
+        self.spans.drain(range.clone());
+        self.tokens.drain(range)
+    }
+    fn splice<I: IntoIterator<Item = NumStr>>(&mut self, range: std::ops::Range<usize>, tokens: I) {
+        let len = self.tokens.len();
+        self.tokens.splice(range.clone(), tokens);
+        let n = self.tokens.len() + range.len() - len;
+        self.spans.splice(range, vec![None; n]);
+    }
+    fn close(&mut self, span: Span) {
+        for s in self.spans.iter_mut().filter(|s| s.is_none()) {
+            *s = Some(span)
+        }
+    }
+}
+fn to_rational(num: &str, base: i32) -> Option<Rational> {
+    let (int, frac) = num.split_once('.').unwrap_or((num, ""));
+    let n = Integer::from_str_radix(&(int.to_string() + frac), base).ok()?;
+    Some(Rational::from((
+        n,
+        Integer::from(base).pow(frac.len() as u32),
+    )))
+}
+fn to_span(place: &[usize], start: usize, end: usize) -> Span {
+    match (
+        place.get(start).or(place.last()),
+        place.get(end - 1).or(place.last()),
+    ) {
+        (Some(a), Some(b)) => (*a, b + 1),
+        _ => (0, 0),
+    }
+}
+fn place_multiplier(output: &mut Tokens, sumrec: &[(isize, String)], sumvar: &Option<String>) {
     match output.last() {
         Some(RightCurlyBracket) | Some(RightBracket) => output.push(Multiplication),
         Some(Func(s))
@@ -3200,36 +3531,11 @@ pub fn simplify(
     let mut i = 0;
     while i < funcvars.len() {
         let v = funcvars[i].clone();
-        if !v.0.ends_with(')')
-            && v.1.iter().all(|v| {
-                if let Func(s) = &v {
-                    !(matches!(
-                        s.as_str(),
-                        "x" | "y" | "w" | "rnd" | "rand" | "epoch" | "roll"
-                    ) || s.starts_with("rand_"))
-                } else {
-                    true
-                }
-            })
-        {
+        if !v.0.ends_with(')') && !v.1.iter().any(is_varying) {
             if let Ok(n) = do_math(v.1.clone(), options, Vec::new()) {
-                for f in output.iter_mut() {
-                    if let Func(s) = &f {
-                        if *s == v.0 {
-                            *f = n.clone();
-                        }
-                    }
-                }
-                if i + 1 < funcvars.len() {
-                    for fv in funcvars[i + 1..].iter_mut() {
-                        for f in fv.1.iter_mut() {
-                            if let Func(s) = &f {
-                                if *s == v.0 {
-                                    *f = n.clone();
-                                }
-                            }
-                        }
-                    }
+                *output = place_var(std::mem::take(output), &v.0, n.clone());
+                for fv in funcvars[i + 1..].iter_mut() {
+                    fv.1 = place_var(std::mem::take(&mut fv.1), &v.0, n.clone());
                 }
                 funcvars.remove(i);
                 continue;
@@ -3237,63 +3543,133 @@ pub fn simplify(
         }
         i += 1;
     }
-    i = output.len();
-    let mut to = Vec::new();
-    while i != 0 {
-        i -= 1;
-        match &output[i] {
-            LeftBracket => {
-                if !to.is_empty() {
-                    if i != 0 {
-                        if let Func(s) = &output[i - 1] {
-                            if !s.starts_with("rand_") && functions().contains(s.as_str()) {
-                                if let Ok(n) = do_math(
-                                    output[i - 1..=to[0]].to_vec(),
-                                    options,
-                                    funcvars.clone(),
-                                ) {
-                                    output.drain(i - 1..=to[0]);
-                                    output.insert(i - 1, n);
-                                    let d = to[0] - i + 1;
-                                    to.remove(0);
-                                    for t in to.iter_mut() {
-                                        *t -= d;
-                                    }
-                                    continue;
-                                }
-                                to.remove(0);
-                                continue;
-                            }
-                        }
-                    }
-                    if let Ok(n) = do_math(output[i + 1..to[0]].to_vec(), options, funcvars.clone())
-                    {
-                        output.drain(i..=to[0]);
-                        output.insert(i, n);
-                        let d = to[0] - i + 1;
-                        to.remove(0);
-                        for t in to.iter_mut() {
-                            *t -= d;
-                        }
-                        continue;
-                    }
-                    to.remove(0);
-                }
+    if let Ok(expr) = Expr::from_tokens(output) {
+        *output = fold(expr, options, funcvars).to_tokens()
+    }
+}
+fn is_varying(n: &NumStr) -> bool {
+    if let Func(s) = n {
+        matches!(
+            s.as_str(),
+            "x" | "y" | "w" | "rnd" | "rand" | "epoch" | "roll"
+        ) || s.starts_with("rand_")
+    } else {
+        false
+    }
+}
+///evaluates the groups and function calls that depend on no variable
+fn fold(expr: Expr, options: Options, funcvars: &[(String, Vec<NumStr>)]) -> Expr {
+    let f = |e: Expr| fold(e, options, funcvars);
+    let value = |e: &Expr| {
+        let tokens = e.to_tokens();
+        if tokens
+            .iter()
+            .any(|t| is_varying(t) || funcvars.iter().any(|a| t.str_is(&a.0)))
+        {
+            None
+        } else {
+            do_math(tokens, options, funcvars.to_vec()).ok()
+        }
+    };
+    match expr {
+        Expr::Group(e) => {
+            let e = f(*e);
+            match value(&e) {
+                Some(n) => Expr::Literal(n),
+                None => Expr::Group(Box::new(e)),
             }
-            RightBracket => {
-                to.insert(0, i);
+        }
+        Expr::Call(s, args) => {
+            let call = Expr::Call(s.clone(), args.into_iter().map(f).collect());
+            if s.starts_with("rand_") || !functions().contains(s.as_str()) {
+                return call;
             }
-            Func(s)
-                if s.starts_with("rand_")
-                    || funcvars.iter().any(|a| a.0 == *s)
-                    || matches!(
-                        s.as_str(),
-                        "x" | "y" | "w" | "roll" | "rnd" | "rand" | "epoch"
-                    ) =>
-            {
-                to.clear();
+            match value(&call) {
+                Some(n) => Expr::Literal(n),
+                None => call,
             }
-            _ => {}
         }
+        Expr::Apply(s, e) => Expr::Apply(s, Box::new(f(*e))),
+        Expr::Vector(args) => Expr::Vector(args.into_iter().map(f).collect()),
+        Expr::Binary(op, a, b) => Expr::Binary(op, Box::new(f(*a)), Box::new(f(*b))),
+        Expr::Not(e) => Expr::Not(Box::new(f(*e))),
+        Expr::Spanned(span, e) => Expr::Spanned(span, Box::new(f(*e))),
+        e => e,
+    }
+}
+fn leading_vars(chars: &[char]) -> usize {
+    let mut n: usize = 0;
+    let mut i = 0;
+    loop {
+        let len = chars[i..]
+            .iter()
+            .take_while(|c| c.is_alphabetic() || matches!(c, '\'' | '`' | '_'))
+            .count();
+        if len != 0 && chars.get(i + len) == Some(&',') {
+            n += 1;
+            i += len + 1;
+        } else if chars.get(i) == Some(&'{') {
+            return n.saturating_sub(1);
+        } else {
+            return n;
+        }
+    }
+}
+fn swap_var_args(chars: &mut [char], place: &mut [usize]) {
+    for i in 0..chars.len() {
+        if i != 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '_') {
+            continue;
+        }
+        let Some((name, index)) = [
+            ("diff", 1),
+            ("collect", 1),
+            ("factor", 1),
+            ("discriminant", 1),
+            ("apart", 1),
+            ("antiderivative", 1),
+            ("pgcd", 2),
+            ("pdiv", 2),
+            ("prem", 2),
+            ("resultant", 2),
+        ]
+        .into_iter()
+        .find(|(n, _)| chars[i..].starts_with(&n.chars().chain(['(']).collect::<Vec<char>>())) else {
+            continue;
+        };
+        let i = i + name.len() + 1;
+        let mut commas = Vec::new();
+        let mut count = 0;
+        let mut end = None;
+        for (j, c) in chars.iter().enumerate().skip(i) {
+            match c {
+                '(' | '{' => count += 1,
+                ')' | '}' if count == 0 => {
+                    end = Some(j);
+                    break;
+                }
+                ')' | '}' => count -= 1,
+                ',' if count == 0 => commas.push(j),
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            return;
+        };
+        let stop = commas.get(index).copied().unwrap_or(end);
+        let Some(&comma) = commas.get(index - 1) else {
+            continue;
+        };
+        if comma + 1 == stop
+            || !chars[comma + 1..stop]
+                .iter()
+                .all(|c| c.is_alphabetic() || matches!(c, '\'' | '`' | '_'))
+        {
+            continue;
+        }
+        let len = stop - comma - 1;
+        chars[i..stop].rotate_right(len + 1);
+        place[i..stop].rotate_right(len + 1);
+        chars[i..=i + len].rotate_left(1);
+        place[i..=i + len].rotate_left(1);
     }
 }
@@ -1,9 +1,15 @@
+use crate::types::{
+    Complex, Float, Integer,
+    float::Constant::Pi,
+    ops::{CompleteRound, Pow},
+};
 use crate::{
     complex::{
         NumStr,
         NumStr::{Matrix, Num, Vector},
         to_polar,
     },
+    error::Error,
     fraction::fraction,
     help::help_for,
     load_vars::set_commands_or_vars,
@@ -19,11 +25,6 @@ use crate::{
         Variable,
     },
 };
-use rug::{
-    Complex, Float, Integer,
-    float::Constant::Pi,
-    ops::{CompleteRound, Pow},
-};
 use std::cmp::Ordering;
 #[allow(clippy::too_many_arguments)]
 pub fn print_concurrent(
@@ -336,7 +337,15 @@ pub fn print_concurrent(
     ) {
         Ok(f) => f,
         Err(s) => {
-            handle_err(s, vars, unmodified_input, options, &colors, start, end);
+            handle_err(
+                s.offset(unmodified_input.len() - unparsed.len()),
+                vars,
+                unmodified_input,
+                options,
+                &colors,
+                start,
+                end,
+            );
             return (1, HowGraphing::default(), false, false);
         }
     };
@@ -351,6 +360,8 @@ pub fn print_concurrent(
                     | "imcol"
                     | "angle"
                     | "notation"
+                    | "float_type"
+                    | "floattype"
                     | "graph"
                     | "saveto"
                     | "textc"
@@ -463,7 +474,7 @@ pub fn print_concurrent(
                         }
                         _ => {
                             handle_err(
-                                "equal sign on right side of definition",
+                                Error::Parse("equal sign on right side of definition", None),
                                 vars,
                                 unmodified_input,
                                 options,
@@ -545,7 +556,7 @@ pub fn print_concurrent(
                         }
                         _ => {
                             handle_err(
-                                "equal sign on right side of definition",
+                                Error::Parse("equal sign on right side of definition", None),
                                 vars,
                                 unmodified_input,
                                 options,
@@ -746,7 +757,7 @@ pub fn print_concurrent(
     let num = match do_math(input.0, options, input.1) {
         Ok(n) => n,
         Err(s) => {
-            return if s == " " {
+            return if s == Error::Empty {
                 clear(unmodified_input, vars, start, end, options, &colors);
                 (0, HowGraphing::default(), false, false)
             } else {
@@ -761,7 +772,8 @@ pub fn print_concurrent(
         Num(n) => {
             let n = custom_units(*n, options, &colors);
             let mut output = get_output(options, &colors, &n);
-            let (mut frac_a, frac_b) = if options.frac.num {
+            let (mut frac_a, frac_b) = if options.frac.num && !(options.exact && n.exact.is_some())
+            {
                 let n = n.number;
                 let fa = fraction(n.real().clone(), options, &colors, 0);
                 let fb = fraction(n.imag().clone(), options, &colors, 0);
@@ -983,9 +995,9 @@ pub fn print_concurrent(
                 v = to_polar(
                     v,
                     match options.angle {
-                        AngleType::Degrees => 180 / Complex::with_val(options.prec, Pi),
-                        AngleType::Radians => Complex::with_val(options.prec, 1),
-                        AngleType::Gradians => 200 / Complex::with_val(options.prec, Pi),
+                        AngleType::Degrees => 180 / Complex::with_val(options.precision(), Pi),
+                        AngleType::Radians => Complex::with_val(options.precision(), 1),
+                        AngleType::Gradians => 200 / Complex::with_val(options.precision(), Pi),
                     },
                 );
             }
@@ -1430,7 +1442,7 @@ pub fn print_concurrent(
             }
         }
         _ => handle_err(
-            "str err",
+            Error::Type("str err", None),
             vars,
             unmodified_input,
             options,
@@ -1442,11 +1454,14 @@ pub fn print_concurrent(
     (frac + 1, HowGraphing::default(), long, var)
 }
 pub fn print_answer(num: NumStr, options: Options, colors: &Colors) {
+    print!("{}", answer_to_string(num, options, colors))
+}
+pub fn answer_to_string(num: NumStr, options: Options, colors: &Colors) -> String {
     match num {
         Num(n) => {
             let n = custom_units(*n, options, colors);
             let a = get_output(options, colors, &n);
-            print!(
+            format!(
                 "{}{}{}{}",
                 a.0,
                 a.2.unwrap_or_default(),
@@ -1456,16 +1471,16 @@ pub fn print_answer(num: NumStr, options: Options, colors: &Colors) {
                 } else {
                     ""
                 }
-            );
+            )
         }
         Vector(mut v) => {
             if options.polar {
                 v = to_polar(
                     v,
                     match options.angle {
-                        AngleType::Degrees => 180 / Complex::with_val(options.prec, Pi),
-                        AngleType::Radians => Complex::with_val(options.prec, 1),
-                        AngleType::Gradians => 200 / Complex::with_val(options.prec, Pi),
+                        AngleType::Degrees => 180 / Complex::with_val(options.precision(), Pi),
+                        AngleType::Radians => Complex::with_val(options.precision(), 1),
+                        AngleType::Gradians => 200 / Complex::with_val(options.precision(), Pi),
                     },
                 );
             }
@@ -1489,19 +1504,18 @@ pub fn print_answer(num: NumStr, options: Options, colors: &Colors) {
                 if options.color == Auto::True {
                     output += &colors.text;
                 }
-                if k == v.len().saturating_sub(1) {
-                    if options.color == Auto::True {
-                        output += &colors.brackets[0].clone()
-                    }
-                    output += if options.polar { "]" } else { "}" };
-                    if options.color == Auto::True {
-                        output += &colors.text
-                    }
-                } else {
+                if k != v.len().saturating_sub(1) {
                     output += ",";
                 }
             }
-            print!(
+            if options.color == Auto::True {
+                output += &colors.brackets[0].clone()
+            }
+            output += if options.polar { "]" } else { "}" };
+            if options.color == Auto::True {
+                output += &colors.text
+            }
+            format!(
                 "{}{}",
                 output,
                 if options.color == Auto::True {
@@ -1509,7 +1523,7 @@ pub fn print_answer(num: NumStr, options: Options, colors: &Colors) {
                 } else {
                     ""
                 }
-            );
+            )
         }
         Matrix(v) => {
             let mut output = if options.multi {
@@ -1574,7 +1588,7 @@ pub fn print_answer(num: NumStr, options: Options, colors: &Colors) {
                     output += "}"
                 };
             }
-            print!(
+            format!(
                 "{}{}",
                 output,
                 if options.color == Auto::True {
@@ -1582,22 +1596,22 @@ pub fn print_answer(num: NumStr, options: Options, colors: &Colors) {
                 } else {
                     ""
                 }
-            );
+            )
         }
-        _ => {}
+        _ => String::new(),
     }
 }
 pub fn custom_units(mut number: Number, options: Options, colors: &Colors) -> Number {
     if !colors.default_units.is_empty() {
-        let mut meter = Complex::with_val(options.prec, 1);
-        let mut second = Complex::with_val(options.prec, 1);
-        let mut kilogram = Complex::with_val(options.prec, 1);
-        let mut ampere = Complex::with_val(options.prec, 1);
-        let mut mole = Complex::with_val(options.prec, 1);
-        let mut candela = Complex::with_val(options.prec, 1);
-        let mut byte = Complex::with_val(options.prec, 1);
-        let mut usd = Complex::with_val(options.prec, 1);
-        let mut unit = Complex::with_val(options.prec, 1);
+        let mut meter = Complex::with_val(options.precision(), 1);
+        let mut second = Complex::with_val(options.precision(), 1);
+        let mut kilogram = Complex::with_val(options.precision(), 1);
+        let mut ampere = Complex::with_val(options.precision(), 1);
+        let mut mole = Complex::with_val(options.precision(), 1);
+        let mut candela = Complex::with_val(options.precision(), 1);
+        let mut byte = Complex::with_val(options.precision(), 1);
+        let mut usd = Complex::with_val(options.precision(), 1);
+        let mut unit = Complex::with_val(options.precision(), 1);
         let m = Units {
             meter: 1.0,
             ..Units::default()
@@ -1657,6 +1671,7 @@ pub fn custom_units(mut number: Number, options: Options, colors: &Colors) -> Nu
             }
         }
         if let Some(u) = number.units {
+            number.exact = None;
             number.number /= meter.pow(u.meter);
             number.number /= second.pow(u.second);
             number.number /= kilogram.pow(u.kilogram);
@@ -1677,6 +1692,40 @@ pub fn get_output(
 ) -> (String, String, Option<String>) {
     let num = number.number.clone();
     let units = number.units;
+    if let (true, Some(q)) = (options.exact, &number.exact) {
+        return (
+            if q.is_integer() {
+                q.numer().to_string_radix(options.base.1)
+            } else {
+                format!(
+                    "{}/{}",
+                    q.numer().to_string_radix(options.base.1),
+                    q.denom().to_string_radix(options.base.1)
+                )
+            },
+            String::new(),
+            if options.units {
+                units.map(|units| {
+                    format!(
+                        "{}{}{}",
+                        if options.color == Auto::True {
+                            &colors.units
+                        } else {
+                            ""
+                        },
+                        units.to_string(options, colors),
+                        if options.color == Auto::True {
+                            "\x1b[0m"
+                        } else {
+                            ""
+                        }
+                    )
+                })
+            } else {
+                None
+            },
+        );
+    }
     if options.notation != Normal {
         if options.base.1 != 10 {
             let sign = if num.imag().is_sign_positive() && !num.real().is_zero() {
@@ -2173,7 +2222,7 @@ fn remove_trailing_zeros(input: &str, options: Options) -> String {
             num.insert(dec, '.');
             num = Float::parse(num)
                 .unwrap()
-                .complete(options.prec)
+                .complete(options.precision())
                 .to_integer()
                 .unwrap()
                 .to_string();
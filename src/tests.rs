@@ -1,75 +1,144 @@
-use crate::complex::NumStr;
+use crate::complex::{NumStr, sub};
+use crate::types::{Complex, Precision, Rational};
 use crate::{
-    complex::NumStr::{
-        Comma, Division, Exponent, Func, LeftBracket, Minus, Multiplication, Plus, RightBracket,
-    },
-    load_vars::get_vars,
+    calculator::Calculator,
+    complex::NumStr::{Exponent, Func, LeftBracket, Minus, Plus, RightBracket},
+    error::Error,
+    expr::{CompiledExpr, Expr, inline},
     math::do_math,
-    parse::input_var,
-    units::{Number, Options},
+    misc::{parsed_to_string, place_var},
+    parse::{input_var, simplify},
+    print::{custom_units, get_output},
+    units::{HowGraphing, Number, Options, Units, Variable},
 };
-use rug::{Complex, float::Constant::Pi};
-#[test]
-fn test_math() {
-    let output = input_var(
-        "pi+tau*e/2i^(sqrt(2))/3*3-log(2-2i,-3+i)+sqrt(2)^(sqrt(2))",
-        &get_vars(Options::default()),
+use crate::{
+    complex::NumStr::{Comma, Division, Multiplication},
+    load_vars::get_vars,
+    types::float::Constant::Pi,
+};
+fn prec() -> Precision {
+    Options::default().precision()
+}
+///the error left when only a fraction of the default precision survives
+fn tol(fraction: f64) -> f64 {
+    2f64.powf(8.0 - prec().bits as f64 * fraction)
+}
+///decimal places still right when only a fraction of the default precision survives
+fn places(fraction: f64) -> usize {
+    let digits = prec().bits as f64 * fraction * std::f64::consts::LOG10_2;
+    (digits as usize).saturating_sub(2).min(12)
+}
+///the requested tolerance exponent, or what half the default precision reaches
+fn tight(exp: u32) -> u32 {
+    (prec().bits / 2).min(exp)
+}
+///compares the input with the output both printed to the calculators decimal places
+fn assert_eval(calc: &Calculator, input: &str, output: &str) {
+    let output = calc.format(&calc.eval(output).unwrap());
+    assert_eq!(calc.format(&calc.eval(input).unwrap()), output, "{input}");
+}
+///whether every entry of the matrix the input evaluates to is within tol of zero
+fn small(calc: &Calculator, input: &str, tol: f64) -> bool {
+    calc.eval(input)
+        .unwrap()
+        .mat()
+        .unwrap()
+        .iter()
+        .flatten()
+        .all(|n| n.number.clone().abs().real().clone() < tol)
+}
+///compares the symbolic result of the input printed as the calculator shows it
+fn assert_symbolic(calc: &Calculator, input: &str, output: &str) {
+    let (expr, func_vars) = calc.parse(input).unwrap();
+    assert_eq!(
+        parsed_to_string(
+            expr.to_tokens(),
+            &calc.vars,
+            func_vars,
+            &calc.options,
+            &calc.colors
+        ),
+        output,
+        "{input}"
+    );
+}
+///parses the input to tokens as a top level entry, graphing when asked
+#[allow(clippy::type_complexity)]
+fn tokens(
+    input: &str,
+    vars: &[Variable],
+    options: Options,
+    graphing: bool,
+) -> (Vec<NumStr>, Vec<(String, Vec<NumStr>)>, HowGraphing) {
+    let (parsed, func_vars, how, _, _) = input_var(
+        input,
+        vars,
         &mut Vec::new(),
         &mut 0,
-        Options::default(),
+        options,
         false,
         0,
         Vec::new(),
-        false,
+        graphing,
         &mut Vec::new(),
         None,
         None,
     )
     .unwrap();
+    (parsed, func_vars, how)
+}
+#[test]
+fn test_math() {
+    let output = tokens(
+        "pi+tau*e/2i^(sqrt(2))/3*3-log(2-2i,-3+i)+sqrt(2)^(sqrt(2))",
+        &get_vars(Options::default()),
+        Options::default(),
+        false,
+    );
     let expected = vec![
-        NumStr::new(Number::from(Complex::with_val(512, Pi), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), Pi), None)),
         Plus,
-        NumStr::new(Number::from(2 * Complex::with_val(512, Pi), None)),
+        NumStr::new(Number::from(2 * Complex::with_val(prec(), Pi), None)),
         Multiplication,
-        NumStr::new(Number::from(Complex::with_val(512, 1).exp(), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), 1).exp(), None)),
         Division,
-        NumStr::new(Number::from(Complex::with_val(512, 2), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), 2), None)),
         Multiplication,
-        NumStr::new(Number::from(Complex::with_val(512, (0.0, 1)), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), (0.0, 1)), None)),
         Exponent,
         LeftBracket,
         Func("sqrt".to_string()),
         LeftBracket,
-        NumStr::new(Number::from(Complex::with_val(512, 2), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), 2), None)),
         RightBracket,
         RightBracket,
         Division,
-        NumStr::new(Number::from(Complex::with_val(512, 3), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), 3), None)),
         Multiplication,
-        NumStr::new(Number::from(Complex::with_val(512, 3), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), 3), None)),
         Minus,
         Func("log".to_string()),
         LeftBracket,
-        NumStr::new(Number::from(Complex::with_val(512, 2), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), 2), None)),
         Minus,
-        NumStr::new(Number::from(Complex::with_val(512, 2), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), 2), None)),
         Multiplication,
-        NumStr::new(Number::from(Complex::with_val(512, (0, 1)), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), (0, 1)), None)),
         Comma,
-        NumStr::new(Number::from(Complex::with_val(512, -3), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), -3), None)),
         Plus,
-        NumStr::new(Number::from(Complex::with_val(512, (0, 1)), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), (0, 1)), None)),
         RightBracket,
         Plus,
         Func("sqrt".to_string()),
         LeftBracket,
-        NumStr::new(Number::from(Complex::with_val(512, 2), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), 2), None)),
         RightBracket,
         Exponent,
         LeftBracket,
         Func("sqrt".to_string()),
         LeftBracket,
-        NumStr::new(Number::from(Complex::with_val(512, 2), None)),
+        NumStr::new(Number::from(Complex::with_val(prec(), 2), None)),
         RightBracket,
         RightBracket,
     ];
@@ -89,6 +158,1263 @@ fn test_math() {
         out.number.imag().to_string(),
         answer.number.imag().to_string()
     );
-    assert_eq!(&out.number.real().to_string()[..20], "2.009877988310399125");
-    assert_eq!(&out.number.imag().to_string()[..20], "4.535664430265577075");
+    let exact = Calculator::default()
+        .eval("0.2009877988310399125+4.535664430265577075i")
+        .unwrap()
+        .num()
+        .unwrap();
+    let err = (out.number - exact.number).abs().real().clone();
+    //the literals are cut after 19 digits
+    assert!(err < tol(0.75).max(2e-18));
+}
+#[test]
+fn test_calculator() {
+    let mut calc = Calculator::default();
+    calc.define("a=3").unwrap();
+    calc.define("f(x)=x^2+a").unwrap();
+    let out = calc.eval("f(2)*2").unwrap();
+    assert_eq!(calc.format(&out), "14");
+    calc.set_option("angle", "deg").unwrap();
+    let out = calc.eval("sin(90)").unwrap();
+    assert_eq!(calc.format(&out), "1");
+    assert!(calc.set_option("not_an_option", "1").is_err());
+    assert!(calc.eval("f(2)=3").is_err());
+    calc.define("a=5").unwrap();
+    assert_eq!(calc.format(&calc.eval("f(2)").unwrap()), "9");
+    assert!(
+        calc.define("prec=10")
+            .is_ok_and(|_| calc.options.prec != 10)
+    );
+    assert!(calc.define("a+1=2").is_err());
+    assert_eq!(
+        calc.format(&calc.eval("{{1,2},{3,4}}").unwrap()),
+        "1,2\n3,4"
+    );
+    calc.set_option("multi", "false").unwrap();
+    assert_eq!(
+        calc.format(&calc.eval("{{1,2},{3,4}}").unwrap()),
+        "{{1,2},{3,4}}"
+    );
+}
+#[cfg(feature = "fastnum")]
+#[test]
+fn test_float_type() {
+    let mut calc = Calculator::default();
+    let input = "(1+2i)*(3-i)/2+sqrt(16)-ln(exp(2))+sin(1+i)*cos(2-i)-atan(0.5+i)/asin(0.3)";
+    let exact = calc.eval(input).unwrap();
+    for t in ["f64", "f32", "fastnum"] {
+        if calc.set_option("float_type", t).is_err() {
+            continue;
+        }
+        let out = calc.eval(input).unwrap();
+        let diff = out.func(&exact, sub).unwrap().num().unwrap().number.abs();
+        assert!(diff.real().clone() < 1e-5, "{t}");
+    }
+    calc.set_option("float_type", "f64").unwrap();
+    let out = calc.eval("0.1+0.2").unwrap().num().unwrap().number;
+    assert_eq!(
+        out,
+        Complex::with_val(calc.options.precision(), 0.1f64 + 0.2f64)
+    );
+    let out = calc.eval("0^-1").unwrap().num().unwrap().number;
+    assert!(out.real().is_infinite());
+    let out = do_math(
+        tokens("0.1+0.2", &[], calc.options, false).0,
+        calc.options,
+        Vec::new(),
+    )
+    .unwrap();
+    assert_eq!(
+        out.num().unwrap().number,
+        Complex::with_val(calc.options.precision(), 0.1f64 + 0.2f64)
+    );
+    assert_eq!(calc.format(&calc.eval("pi").unwrap()), "3.14159265359");
+    calc.set_option("float_type", "f32").unwrap();
+    assert_eq!(calc.format(&calc.eval("16777217").unwrap()), "16777216");
+    assert_eq!(calc.format(&calc.eval("pi").unwrap()), "3.1415927");
+    for (input, out) in [
+        ("gamma(2.5)", "1.32934"),
+        ("{1,2}+1", "{2,3}"),
+        ("sum(n,n,1,3)", "6"),
+        ("3%2", "1"),
+        ("solve(x,x^2-2,0,2)", "1.41421"),
+        ("roots(x,x^2-2,-2,2)", "{-1.41421"),
+        ("solve(x,x^2-2,1)", "1.41421"),
+        ("slope(x,x^2,1)", "2"),
+    ] {
+        let out_f32 = calc.format(&calc.eval(input).unwrap());
+        assert!(out_f32.starts_with(out), "{input}: {out_f32}");
+    }
+    for t in ["fastnum", "f32"] {
+        calc.set_option("float_type", t).unwrap();
+        for (input, out) in [
+            ("1/0", "inf"),
+            ("-1/0", "-inf"),
+            ("0/0", "NaN"),
+            ("ln(0)", "-inf"),
+            ("0^(1+i)", "NaN+NaNi"),
+        ] {
+            assert_eq!(calc.format(&calc.eval(input).unwrap()), out, "{t} {input}");
+        }
+        for (input, out) in [
+            ("1/asin(0.3)", "3.28199"),
+            ("asin(2)", "1.57079"),
+            ("atanh(2)", "0.54930"),
+        ] {
+            let o = calc.format(&calc.eval(input).unwrap());
+            assert!(o.starts_with(out), "{t} {input}: {o}");
+        }
+        assert!(
+            calc.format(&calc.eval("asin(2)").unwrap())
+                .contains("+1.31695")
+        );
+    }
+    assert_eq!(calc.format(&calc.eval("1000!").unwrap()), "inf");
+    calc.set_option("float_type", "fastnum").unwrap();
+    for (input, out) in [
+        ("sin(10^30)", "-0.0901169"),
+        ("ln(10^5000)", "11512.92546"),
+        ("ln(10^-500)", "-1151.29254"),
+        ("sqrt(10^-5000)*10^2500", "1"),
+        ("acosh(10^400)", "921.72718"),
+        ("atan(10^500)", "1.5707963"),
+        ("100!", "9332621544394415268169"),
+    ] {
+        let o = calc.format(&calc.eval(input).unwrap());
+        assert!(o.starts_with(out), "{input}: {o}");
+    }
+    assert!(calc.set_option("float_type", "f16").is_err());
+}
+#[cfg(not(feature = "rug"))]
+#[test]
+fn test_without_rug() {
+    let mut calc = Calculator::new(Options {
+        multi: false,
+        ..Options::default()
+    });
+    assert_eq!(calc.options.prec, 53);
+    for (input, out) in [
+        ("1+2*3", "7"),
+        ("10!", "3628800"),
+        ("exp(i*pi)", "-1"),
+        ("sqrt(-4)", "2i"),
+        ("inverse({{1,2},{3,4}})", "{{-2,1},{1.5,-0.5}}"),
+        ("det({{1,2},{3,4}})", "-2"),
+        ("sin(pi/6)+ln(e^2)", "2.5"),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), out, "{input}");
+    }
+    for input in [
+        "erf(0.5)",
+        "zeta(3)",
+        "digamma(2)",
+        "norm_cdf(1)",
+        "resultant(x^20+7x+1,x^19-3,x)",
+        "factor(x^40-3^40,x)",
+        "antiderivative(1/((x^20+7x+1)*(x^19-3)),x)",
+        "apart(1/((x^20+7x+1)*(x^19-3)),x)",
+    ] {
+        assert!(
+            matches!(
+                calc.eval(input),
+                Err(Error::UnsupportedBackend("requires rug", _))
+            ),
+            "{input}"
+        );
+    }
+    assert!(calc.eval("erf(1+i)").is_ok());
+    for (input, output) in [
+        (
+            "factor(x^12-1,x)",
+            "((x-1)*(x+1)*(x^2-x+1)*(x^2+1)*(x^2+x+1)*(x^4-x^2+1))",
+        ),
+        (
+            "factor(x^15-1,x)",
+            "((x-1)*(x^2+x+1)*(x^4+x^3+x^2+x+1)*(x^8-x^7+x^5-x^4+x^3-x+1))",
+        ),
+        (
+            "factor(x^10+x^5+1,x)",
+            "((x^2+x+1)*(x^8-x^7+x^5-x^4+x^3-x+1))",
+        ),
+    ] {
+        assert_symbolic(&calc, input, output);
+    }
+    for (input, out) in [
+        ("rank({{1,1},{1,1.1}})", "2"),
+        ("rank({{1,2},{2,4}})", "1"),
+        ("pinv({{1,2},{2,4}})", "{{0.04,0.08},{0.08,0.16}}"),
+        ("linsolve({{2,1},{1,0.7}},{3,1.7})", "{1,1}"),
+        ("lstsq({{1,0},{1,1},{1,2}},{1,2,4})", "{0.833333333333,1.5}"),
+        ("polyfit({{0,1},{1,0},{2,1},{3,10},{4,33}},3)", "{1,-2,0,1}"),
+        ("polyroots({1,-15,85,-225,274,-120})", "{1,2,3,4,5}"),
+        ("isolate(x,(x-1)(x-2)(x-3)(x-4)(x-5))", "{1,2,3,4,5}"),
+        ("area(x,sin(x),0,pi)", "2"),
+        ("area(x,e^(-x^2),-inf,inf)", "1.772453850906"),
+        ("integrate2(x,y,x*y,0,x,0,1)", "0.125"),
+        ("minimize(x,(x-2)^2+1,0)", "{2,1}"),
+        ("minimize(x,abs(x-1),0)", "{1,0}"),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), out, "{input}");
+    }
+    for (input, out) in [
+        ("area(x,1/sqrt(x),0,1)", 2.0),
+        ("odesolve(t,y,y,0,1,1)", std::f64::consts::E),
+        ("part(minimize(x,y,(1-x)^2+100(y-x^2)^2,{-1.2,1}),0)", 1.0),
+    ] {
+        let n = calc.eval(input).unwrap().num().unwrap().number;
+        assert!((n.real().to_f64() - out).abs() < 1e-5, "{input}");
+    }
+    assert!(calc.eval("area(x,1/x,0,1)").is_err());
+    assert!(calc.set_option("float_type", "rug").is_err());
+    calc.set_option("exact", "true").unwrap();
+    for (input, out) in [
+        ("2^130", 2f64.powi(130)),
+        ("(1/7)^50", 7f64.powi(-50)),
+        ("(2^100+1/3)^2", 2f64.powi(200)),
+        ("binomial(200,100)", 9.054851465610328e58),
+        ("det({{2^100,1},{1,2^100}})", 2f64.powi(200)),
+    ] {
+        let n = calc.eval(input).unwrap().num().unwrap();
+        assert!(n.exact.is_none(), "{input}");
+        assert!(
+            (n.number.real().to_f64() / out - 1.0).abs() < 1e-12,
+            "{input}"
+        );
+    }
+    assert_eq!(calc.format(&calc.eval("(1/7)^20*7^20").unwrap()), "1");
+}
+#[test]
+fn test_exact() {
+    let mut calc = Calculator::new(Options {
+        multi: false,
+        ..Options::default()
+    });
+    calc.set_option("exact", "true").unwrap();
+    for (input, out) in [
+        ("1/3+1/6", "1/2"),
+        ("0.1+0.2", "3/10"),
+        ("(2/3)^-2", "9/4"),
+        ("det({{1,2},{3,4}})", "-2"),
+        ("inverse({{2,1},{1,1}})", "{{1,-1},{-1,2}}"),
+        ("inverse({{1,2},{3,4}})", "{{-2,1},{3/2,-1/2}}"),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), out, "{input}");
+    }
+    calc.set_option("units", "true").unwrap();
+    calc.set_option("default_units", "km").unwrap();
+    assert_eq!(calc.format(&calc.eval("3000m").unwrap()), "3 km");
+    let n = Number::from_rational(
+        Rational::from(3000),
+        Some(Units {
+            meter: 1.0,
+            ..Units::default()
+        }),
+        calc.options.precision(),
+    );
+    let n = custom_units(n, calc.options, &calc.colors);
+    assert_eq!(get_output(calc.options, &calc.colors, &n).0, "3");
+}
+#[test]
+fn test_error_span() {
+    let calc = Calculator::default();
+    let err = calc.eval("2 + qqq").unwrap_err();
+    assert_eq!(err, Error::Parse("undefined var", Some((4, 7))));
+    let err = calc.eval("[1,2] + 3.4.5").unwrap_err();
+    assert_eq!(err.span(), Some((11, 12)));
+    let err = calc.eval("1 + {1,2}*{1,2,3}").unwrap_err();
+    assert_eq!(err, Error::Dimension("mul err", Some((4, 17))));
+    let err = calc.eval("3 + inverse({{1,2}})").unwrap_err();
+    assert_eq!(err, Error::Dimension("not square", Some((4, 20))));
+    for (input, out) in [("3>2>1", "1"), ("1<3>2", "1"), ("1<2>3", "0")] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), out, "{input}");
+    }
+    for input in ["1m+1s", "2m-1s", "1m<1s", "1m==1kg", "{1m,2m}+{1m,1s}"] {
+        assert!(matches!(calc.eval(input), Err(Error::Unit(..))), "{input}");
+    }
+    for (input, out) in [("1m+100cm", "2 m"), ("1m<2m", "1")] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), out, "{input}");
+    }
+}
+#[test]
+fn test_expr() {
+    let mut calc = Calculator::default();
+    calc.define("f(x)=sin(2x)").unwrap();
+    for input in [
+        "2^3^2-5%3*{1,2}",
+        "sum(n,n^2,1,4)/f(pi/4)",
+        "norm[3,4]+log(2,8)",
+        "1<2<3&&!(2>3)",
+    ] {
+        let (parsed, func_vars, _) = tokens(input, &calc.vars, calc.options, false);
+        let expr = Expr::from_tokens(&parsed).unwrap();
+        assert_eq!(expr.to_tokens(), parsed);
+        assert_eq!(
+            expr.eval(calc.options, &func_vars).unwrap(),
+            do_math(parsed, calc.options, func_vars).unwrap()
+        );
+    }
+    for input in [
+        "sin 2",
+        "sqrt4",
+        "ln2^2",
+        "sqrt 4%3",
+        "2^sin 2*3",
+        "cos{1,2}",
+    ] {
+        let (parsed, func_vars, _) = tokens(input, &calc.vars, calc.options, false);
+        assert_eq!(
+            calc.eval(input).unwrap(),
+            do_math(parsed, calc.options, func_vars).unwrap(),
+            "{input}"
+        );
+    }
+    let (expr, _) = calc.parse("2^3^2").unwrap();
+    let Expr::Spanned((0, 5), expr) = expr else {
+        panic!()
+    };
+    let Expr::Binary(Exponent, _, rhs) = *expr else {
+        panic!()
+    };
+    assert!(matches!(*rhs, Expr::Spanned((2, 5), e) if matches!(*e, Expr::Binary(Exponent, _, _))));
+    let n = |n: i32| NumStr::new(Number::from(Complex::with_val(prec(), n), None));
+    assert_eq!(
+        Expr::Var("a".to_string())
+            .eval(calc.options, &[("a".to_string(), vec![n(2), Plus, n(3)])])
+            .unwrap(),
+        n(5)
+    );
+}
+#[test]
+fn test_compiled_expr() {
+    let mut calc = Calculator::default();
+    calc.define("a=3").unwrap();
+    calc.define("f(x)=sin(2x)").unwrap();
+    for input in [
+        "f(x)*y+a",
+        "sum(n,n*x,1,3)-1<y<x^2",
+        "{x,y}+[1,2]",
+        "ln(x)*arg(y)-fact{x,y}+acosh(y)",
+    ] {
+        let (parsed, func_vars, _) = tokens(input, &calc.vars, calc.options, true);
+        let mut compiled = CompiledExpr::new(&parsed, &func_vars, calc.options).unwrap();
+        for (x, y) in [(0.5, -1.25), (2.0, 3.0)] {
+            let x = NumStr::new(Number::from(Complex::with_val(prec(), x), None));
+            let y = NumStr::new(Number::from(Complex::with_val(prec(), y), None));
+            assert_eq!(
+                compiled.eval(&x, &y).unwrap(),
+                do_math(
+                    place_var(place_var(parsed.clone(), "x", x.clone()), "y", y.clone()),
+                    calc.options,
+                    func_vars.clone()
+                )
+                .unwrap()
+            );
+        }
+    }
+    assert_eq!(
+        inline(
+            Expr::Raw(vec![Func("b".to_string()), Plus, Func("x".to_string())]),
+            &[
+                ("a".to_string(), vec![Func("y".to_string())]),
+                ("b".to_string(), vec![Func("a".to_string()), Minus]),
+            ]
+        )
+        .unwrap(),
+        Expr::Raw(vec![
+            LeftBracket,
+            LeftBracket,
+            Func("y".to_string()),
+            RightBracket,
+            Minus,
+            RightBracket,
+            Plus,
+            Func("x".to_string())
+        ])
+    );
+    let (expr, mut func_vars) = calc.parse("(a+sum(n,n,1,3))x+sin(a)^y-(x+1)").unwrap();
+    let mut folded = expr.to_tokens();
+    simplify(&mut folded, &mut func_vars, calc.options);
+    assert!(
+        !folded
+            .iter()
+            .any(|t| ["a", "n", "sin"].iter().any(|s| t.str_is(s)))
+    );
+    assert_eq!(folded.iter().filter(|t| **t == LeftBracket).count(), 1);
+    let two = NumStr::new(Number::from(Complex::with_val(prec(), 2), None));
+    assert_eq!(
+        do_math(
+            place_var(place_var(folded, "x", two.clone()), "y", two.clone()),
+            calc.options,
+            func_vars
+        )
+        .unwrap(),
+        calc.eval("(3+6)*2+sin(3)^2-3").unwrap()
+    );
+    let var = |s: &str| Expr::Var(s.to_string());
+    let three = NumStr::new(Number::from(Complex::with_val(prec(), 3), None));
+    for (f, bound) in [
+        ("sum", vec![var("x")]),
+        ("minimize", vec![var("y"), var("x")]),
+    ] {
+        let body = vec![Expr::Binary(Plus, Box::new(var("x")), Box::new(var("y")))];
+        let call = |last: Expr| {
+            Expr::Call(
+                f.to_string(),
+                [bound.clone(), body.clone(), vec![last]].concat(),
+            )
+        };
+        assert_eq!(
+            Expr::Binary(Plus, Box::new(call(var("x"))), Box::new(var("x"))).place_var("x", &three),
+            Expr::Binary(
+                Plus,
+                Box::new(call(Expr::Literal(three.clone()))),
+                Box::new(Expr::Literal(three.clone()))
+            )
+        );
+    }
+}
+#[cfg(feature = "gnuplot")]
+#[test]
+fn test_graph() {
+    let calc = Calculator::default();
+    let dir = std::env::temp_dir().join("kalc_test_graph");
+    std::fs::create_dir_all(&dir).unwrap();
+    for input in ["sin x", "sinx", "sqrt x", "ln x^2", "2x+sin(x)"] {
+        let (parsed, func_vars, how) = tokens(input, &calc.vars, calc.options, true);
+        let (zero, _, rec_re, _) = crate::graph::get_list_2d(
+            (parsed, func_vars, calc.options, how),
+            0,
+            dir.to_str().unwrap(),
+            false,
+        );
+        assert!(zero.0 && rec_re > calc.options.samples_2d / 2, "{input}");
+    }
+    std::fs::remove_dir_all(dir).unwrap();
+}
+#[test]
+fn test_lu() {
+    let calc = Calculator::default();
+    let m = "{{2,1,0,0,3},{1,4,1,0,0},{0,1,5,1,0},{0,0,1,6,1},{3,0,0,1,7}}";
+    let det = calc
+        .eval(&format!("det({m})"))
+        .unwrap()
+        .num()
+        .unwrap()
+        .number;
+    assert!((det - 320u32).abs().real().clone() < tol(0.75));
+    assert!(small(
+        &calc,
+        &format!("{m}*inverse({m})-iden(5)"),
+        tol(0.75)
+    ));
+    assert!(small(
+        &calc,
+        &format!("lu({m},1)*{m}-lu({m},2)*lu({m},3)"),
+        tol(0.75)
+    ));
+    let rank = calc.eval("rank({{1,2,3},{2,4,6},{1,0,1}})").unwrap();
+    assert_eq!(rank.num().unwrap().number, 2);
+    let det = calc.eval("det(iden(12)*2)").unwrap();
+    assert_eq!(det.num().unwrap().number, 4096);
+    let m = "{{1,-5,3,-8,-7,8,-6,2,9,-8},{7,-3,-8,-7,4,4,-7,-2,-7,8},{4,-8,9,-6,-2,9,-8,9,9,3},{-8,-2,-8,8,-5,0,4,-5,8,-6},{9,0,8,-4,-6,9,9,-3,2,-6},{8,-7,9,-8,-3,6,8,4,1,5},{9,5,2,0,-2,-4,-2,-7,9,0},{7,6,1,5,0,-7,-6,7,4,-4},{1,-5,6,4,-8,-7,8,9,1,1},{2,6,9,5,-7,-7,-1,6,-7,-8}}";
+    assert_eq!(
+        calc.format(&calc.eval(&format!("det({m})")).unwrap()),
+        "778481383"
+    );
+}
+#[test]
+fn test_eigenvalues() {
+    let calc = Calculator::default();
+    let m = format!(
+        "{{{}}}",
+        (0..20)
+            .map(|i| format!(
+                "{{{}}}",
+                (0..20)
+                    .map(|j| ((i * 7 + j * 3) % 11).to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ))
+            .collect::<Vec<String>>()
+            .join(",")
+    );
+    let l = calc
+        .eval(&format!("eigenvalues({m})"))
+        .unwrap()
+        .vec()
+        .unwrap();
+    assert_eq!(l.len(), 20);
+    let tr = calc
+        .eval(&format!("trace({m})"))
+        .unwrap()
+        .num()
+        .unwrap()
+        .number;
+    let sum = l.iter().fold(Complex::new(prec()), |s, n| s + &n.number);
+    assert!((sum - tr).abs().real().clone() < tol(0.75));
+    let m = "{{1,2,0,0,0,0},{0,1,2,0,0,0},{0,0,1,2,0,0},{0,0,0,2,2,0},{0,0,0,0,3,1},{1,0,0,0,0,4}}";
+    let v = calc
+        .eval(&format!("eigenvectors({m})"))
+        .unwrap()
+        .mat()
+        .unwrap();
+    assert_eq!(v.len(), 6);
+    for v in v {
+        let v = NumStr::Vector(v);
+        let r = calc.eval(m).unwrap().mul(&v).unwrap().vec().unwrap();
+        let v = v.vec().unwrap();
+        let l = r[5].number.clone() / &v[5].number;
+        assert!(
+            r.iter()
+                .zip(v)
+                .all(|(r, v)| (r.number.clone() - v.number * &l).abs().real().clone() < tol(0.75))
+        );
+    }
+}
+#[test]
+fn test_decompositions() {
+    let calc = Calculator::default();
+    let a = "{{1,2,i},{3,4,5},{0,6,7},{1,1,2}}";
+    assert!(small(&calc, &format!("qr({a},1)*qr({a},2)-{a}"), tol(0.75)));
+    assert!(small(
+        &calc,
+        &format!("svd({a},1)*svd({a},2)*conj(trans(svd({a},3)))-{a}"),
+        tol(0.75)
+    ));
+    assert!(small(&calc, &format!("{a}*pinv({a})*{a}-{a}"), tol(0.75)));
+    let b = "{{4,1,2,0,1},{1,5,0,1,2},{2,0,6,1,0},{0,1,1,7,3},{1,2,0,3,8}}";
+    assert!(small(
+        &calc,
+        &format!("chol({b})*trans(chol({b}))-{b}"),
+        tol(0.75)
+    ));
+    assert!(small(
+        &calc,
+        &format!("schur({b},1)*schur({b},2)*conj(trans(schur({b},1)))-{b}"),
+        tol(0.75)
+    ));
+    assert!(calc.eval("chol({{1,2},{2,1}})").is_err());
+    let n = calc
+        .eval("norm2({{3,0},{4,5}})^2")
+        .unwrap()
+        .num()
+        .unwrap()
+        .number;
+    assert!((n - 45u8).abs().real().clone() < tol(0.75));
+    let r = calc
+        .eval("rank({{1.5,3},{3,6}})")
+        .unwrap()
+        .num()
+        .unwrap()
+        .number;
+    assert_eq!(r, 1);
+    let c = "{{1,1},{1,1.1}}";
+    for (input, out) in [
+        (format!("rank({c})"), 2),
+        (format!("rank({c},0.1)"), 1),
+        ("rank({{1,1},{1,1+2^-40}})".to_string(), 2),
+    ] {
+        assert_eq!(
+            calc.eval(&input).unwrap().num().unwrap().number,
+            out,
+            "{input}"
+        );
+    }
+    assert!(small(&calc, &format!("pinv({c})-inverse({c})"), tol(0.75)));
+    for input in ["cond({{1,0},{0,0}})", "cond({{0,0},{0,0}})"] {
+        let c = calc.eval(input).unwrap().num().unwrap().number;
+        assert!(
+            c.real().is_infinite() && c.real().is_sign_positive() && c.imag().is_zero(),
+            "{input}"
+        );
+    }
+    assert!(matches!(
+        calc.eval("rank({{}})"),
+        Err(Error::Dimension("invalid matrix", _))
+    ));
+}
+#[test]
+fn test_jordan() {
+    let calc = Calculator::new(Options {
+        decimal_places: places(0.5),
+        ..Options::default()
+    });
+    let a = "{{1,1,0,0,0},{0,1,0,0,0},{0,0,2,0,0},{0,0,0,3,0},{0,0,0,0,4}}";
+    let p = "{{1,0,0,0,0},{1,1,0,0,0},{0,1,1,0,0},{1,0,1,1,0},{0,0,1,0,1}}";
+    let b = format!(
+        "{p}*{{{{2,1,0,0,0}},{{0,2,1,0,0}},{{0,0,2,0,0}},{{0,0,0,2,0}},{{0,0,0,0,5}}}}*inverse({p})"
+    );
+    for (input, output) in [
+        (format!("generalized_eigenvectors({a})"), "iden(5)"),
+        (format!("jcf({a})"), a),
+        (
+            "jcf({{5,4,2,1,0},{0,1,-1,-1,0},{-1,-1,3,0,0},{1,1,-1,2,0},{0,0,0,0,7}})".to_string(),
+            "{{4,1,0,0,0},{0,4,0,0,0},{0,0,2,0,0},{0,0,0,1,0},{0,0,0,0,7}}",
+        ),
+        (
+            format!("jcf({b})"),
+            "{{5,0,0,0,0},{0,2,1,0,0},{0,0,2,1,0},{0,0,0,2,0},{0,0,0,0,2}}",
+        ),
+    ] {
+        assert_eval(&calc, &input, output);
+    }
+    assert_eval(&calc, &format!("rank(generalized_eigenvectors({b}))"), "5");
+    assert_eq!(
+        calc.eval(&format!("eigenvectors({b})"))
+            .unwrap()
+            .mat()
+            .unwrap()
+            .len(),
+        3
+    );
+}
+#[test]
+fn test_matrix_functions() {
+    let calc = Calculator::default();
+    //funm differentiates numerically on clustered eigenvalues
+    let tol = tol(0.5).max(1e-30);
+    let a = "{{1,2,0},{-1,3,1},{0,1,5}}";
+    assert!(small(&calc, "expm({{0,pi},{-pi,0}})+iden(2)", tol));
+    assert!(small(&calc, &format!("logm(expm({a}))-{a}"), tol));
+    assert!(small(&calc, &format!("sqrtm({a})^2-{a}"), tol));
+    assert!(small(
+        &calc,
+        &format!("sinm({a})^2+cosm({a})^2-iden(3)"),
+        tol
+    ));
+    assert!(small(&calc, &format!("funm({a},x^3-x)-({a}^3-{a})"), tol));
+    assert!(small(&calc, "funm({{2,1},{0,2}},x^2)-{{4,4},{0,4}}", tol));
+    let b = "{{1,1,0},{-1,3,0},{1,1,5}}";
+    assert!(small(&calc, &format!("funm({b},exp(x))-expm({b})"), tol));
+    assert!(small(&calc, &format!("funm({b},sin(x))-sinm({b})"), tol));
+    let c = "{{2,1,1},{0,5,1},{0,0,2}}";
+    assert!(small(&calc, &format!("funm({c},exp(x))-expm({c})"), tol));
+    assert!(small(&calc, "sqrtm({{-4,0},{0,9}})-{{2i,0},{0,3}}", tol));
+    for input in ["logm({{0,0},{0,0}})", "logm({{1,2},{2,4}})"] {
+        assert!(
+            matches!(
+                calc.eval(input),
+                Err(Error::Domain("singular matrix has no logarithm", _))
+            ),
+            "{input}"
+        );
+    }
+    for input in [
+        "expm({{1m,0},{0,1m}})",
+        "logm({{1s,0},{0,1}})",
+        "cosm({{1kg}})",
+    ] {
+        assert!(matches!(calc.eval(input), Err(Error::Unit(..))), "{input}");
+    }
+}
+#[test]
+fn test_linsolve() {
+    let calc = Calculator::new(Options {
+        multi: false,
+        ..Options::default()
+    });
+    let x = calc.eval("linsolve({{2,1},{1,3}},{3,5})").unwrap();
+    assert!(
+        x.func(&calc.eval("{0.8,1.4}").unwrap(), sub)
+            .unwrap()
+            .vec()
+            .unwrap()
+            .iter()
+            .all(|n| n.number.clone().abs().real().clone() < tol(0.75))
+    );
+    let general = calc.eval("linsolve({{1,2,3},{2,4,6.5}},{1,2})").unwrap();
+    assert_eq!(calc.format(&general), "{{1,0,0},{-2,1,0}}");
+    assert!(calc.eval("linsolve({{1,2},{2,4}},{1,3})").is_err());
+    assert_eq!(
+        calc.format(&calc.eval("linsolve({{2,1},{1,0.7}},{3,1.7})").unwrap()),
+        "{1,1}"
+    );
+    #[cfg(feature = "rug")]
+    for input in [
+        "linsolve({{1,1},{1,1+2^-40}},{2,2+2^-40})",
+        "lstsq({{1,1},{1,1+2^-40},{0,0}},{2,2+2^-40,0})",
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), "{1,1}", "{input}");
+    }
+    let x = calc.eval("lstsq({{1,0},{1,1},{1,2}},{6,0,0})").unwrap();
+    assert_eq!(calc.format(&x), "{5,-3}");
+    let r = calc.eval("lstsq({{1,0},{1,1},{1,2}},{6,0,0},1)^2").unwrap();
+    assert!((r.num().unwrap().number - 6u8).abs().real().clone() < tol(0.75));
+    let x = calc.eval("lstsq({{1,0},{1,1},{1,2}},{6,0,0},0)").unwrap();
+    assert_eq!(calc.format(&x), "{5,-3}");
+    assert!(calc.eval("lstsq({{1,0},{1,1},{1,2}},{6,0,0},2)").is_err());
+}
+#[test]
+fn test_area() {
+    let calc = Calculator::new(Options {
+        decimal_places: places(0.75),
+        ..Options::default()
+    });
+    for (input, output) in [
+        ("area(x,1/sqrt(x),0,1)", "2"),
+        ("area(x,ln(x),0,1)", "-1"),
+        ("area(x,e^(-x^2),-inf,inf)", "1.772453850906"),
+        ("area(x,1/x^2,1,inf)", "1"),
+        ("area(x,e^(-10000(x-0.3)^2),0,1)", "0.017724538509"),
+        ("area(x,x^3,2,0,1,1,10^-30)", "{-4,0}"),
+        ("area(x,exp(x),-inf,0,0.5)", "1"),
+        ("area(x,1,0,4,0.5)", "2.256758334191"),
+        ("area(x,abs(x-0.3),0,1)", "0.29"),
+        ("area(x,floor(x),0,3.5)", "4.5"),
+        ("area(x,sgn(x-0.3),0,1)", "0.4"),
+    ] {
+        assert_eval(&calc, input, output);
+    }
+    for (input, exact) in [
+        ("area(x,1/sqrt(1-x^2),-1,1,1,1,10^-6)", "pi"),
+        ("area(x,abs(x-0.3),0,1,1,1,10^-6)", "0.29"),
+        ("area(x,floor(x),0,3.5,1,1,10^-8)", "4.5"),
+        ("area(x,sgn(x-0.3),0,1,1,1,10^-8)", "0.4"),
+        ("integrate(x,1/sqrt(x),0,1,10^-10)", "2"),
+    ] {
+        let NumStr::Vector(v) = calc.eval(input).unwrap() else {
+            unreachable!()
+        };
+        let exact = calc.eval(exact).unwrap().num().unwrap().number;
+        let err = (v[0].number.clone() - exact).abs();
+        assert!(err.real() <= v[1].number.real(), "{input}");
+    }
+    assert!(calc.eval("integrate(x,1/x,0,1,10^-10)").is_err());
+    assert!(calc.eval("area(x,1/x,0,1)").is_err());
+    let out = calc.eval("area(x,sin(x),0,pi)-2").unwrap();
+    assert!(out.num().unwrap().number.abs().real().clone() < 1e-100);
+    let out = calc.eval("area(x,sin(x),0,pi,1,1,10^-20)").unwrap();
+    let NumStr::Vector(v) = out else {
+        unreachable!()
+    };
+    assert!(v[1].number.real() < &1e-20 && v[1].number.real().is_sign_positive());
+}
+#[test]
+fn test_multiple_integrals() {
+    let calc = Calculator::new(Options {
+        decimal_places: places(0.75),
+        ..Options::default()
+    });
+    for (input, output) in [
+        ("integrate2(x,y,x*y,0,x,0,1)", "0.125"),
+        (
+            "integrate2(x,y,1,-sqrt(1-x^2),sqrt(1-x^2),-1,1)",
+            "3.14159265359",
+        ),
+        ("contour(z,t,1/z,e^(i*t),0,2pi)", "6.28318530718i"),
+        ("integrate2(x,y,x*y,0,x,0,1,10^-20)", "{0.125,0}"),
+        (
+            "contour(z,t,z^2,t+i*t,0,1)",
+            "-0.666666666667+0.666666666667i",
+        ),
+    ] {
+        assert_eval(&calc, input, output);
+    }
+    let exp = tight(66);
+    let contour = format!("contour(z,t,1/z,e^(i*t),0,2pi,2^-{exp})");
+    assert_eval(&calc, &format!("part({contour},0)"), "6.28318530718i");
+    let err = calc.eval(&format!("part({contour},1)")).unwrap();
+    assert!(err.num().unwrap().number.real().clone() < 2f64.powi(-(exp as i32)));
+    let calc = Calculator::new(Options {
+        prec: prec().bits.min(256),
+        ..calc.options
+    });
+    assert_eval(
+        &calc,
+        "integrate3(x,y,z,1,0,1-x-y,0,1-x,0,1)",
+        "0.166666666667",
+    );
+}
+#[test]
+fn test_odesolve() {
+    let calc = Calculator::new(Options {
+        multi: false,
+        decimal_places: places(0.5),
+        ..Options::default()
+    });
+    for (input, output) in [
+        ("odesolve(t,y,y,0,1,1)", "2.718281828459"),
+        ("odesolve(t,y,y,1,1,0)", "0.367879441171"),
+        ("odesolve(t,y,{part(y,1),-part(y,0)},0,{0,1},pi/2)", "{1,0}"),
+        (
+            "odesolve(t,y,{part(y,1),-part(y,0)},0,{0,1},pi,2)",
+            "{{0,0,1},{1.570796326795,1,0},{3.14159265359,0,-1}}",
+        ),
+    ] {
+        assert_eval(&calc, input, output);
+    }
+    let stiff = calc
+        .eval(
+            "odesolve(t,y,-1000(y-cos(t)),0,0,1,0,1)-(10^6cos(1)+1000sin(1)-10^6e^-1000)/(10^6+1)",
+        )
+        .unwrap();
+    assert!(stiff.num().unwrap().number.abs().real().clone() < 1e-7);
+    assert!(calc.eval("odesolve(t,y,y^2,0,1,2)").is_err());
+    for input in ["odesolve(t,y,y,0,1,1,2.5)", "odesolve(t,y,y,0,1,1,-1)"] {
+        assert!(
+            matches!(calc.eval(input), Err(Error::Domain(..))),
+            "{input}"
+        );
+    }
+    let exp = tight(60);
+    let out = calc
+        .eval(&format!("odesolve(t,y,y,0,1,1,0,0,2^-{exp})-e"))
+        .unwrap();
+    assert!(out.num().unwrap().number.abs().real().clone() < 2f64.powi(10 - exp as i32));
+    let out = calc.eval("odesolve(t,y,y,0,1,1)-e").unwrap();
+    assert!(out.num().unwrap().number.abs().real().clone() < 2f64.powi(8 - tight(48) as i32));
+}
+#[test]
+fn test_roots() {
+    let calc = Calculator::default();
+    for (input, output) in [
+        ("solve(x,x^2-2,0,2)", "1.414213562373"),
+        ("solve(x,cos(x)-x,0,1)", "0.739085133215"),
+        ("solve(x,x^2-4,1)", "2"),
+        ("roots(x,x^3-x,-2,2)", "{-1,0,1}"),
+        ("roots(x,tan(x),0,5)", "{0,3.14159265359}"),
+        ("roots(x,(x-2)/(x-1),0,3,2)", "{2}"),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), output, "{input}");
+    }
+    assert!(calc.eval("solve(x,x^2+1,0,2)").is_err());
+    assert!(calc.eval("solve(x,x^2+1)").is_err());
+    for input in [
+        "roots(x,x^2-1,-2,2,0)",
+        "roots(x,x^2-1,-2,2,-4)",
+        "roots(x,x^2-1,-2,2,2.5)",
+    ] {
+        assert!(
+            matches!(calc.eval(input), Err(Error::Domain(..))),
+            "{input}"
+        );
+    }
+}
+#[test]
+fn test_polyroots() {
+    let calc = Calculator::default();
+    for (input, output) in [
+        ("polyroots({1,-15,85,-225,274,-120})", "{1,2,3,4,5}"),
+        ("polyroots({2,3})", "{-1.5}"),
+        ("polyroots({0,1,-1})", "{1}"),
+        ("isolate(x,(x-1)(x-2)(x-3)(x-4)(x-5)(x-6))", "{1,2,3,4,5,6}"),
+    ] {
+        assert_eval(&calc, input, output);
+    }
+    let fivefold = Calculator::new(Options {
+        decimal_places: places(0.2),
+        ..Options::default()
+    });
+    assert_eval(&fivefold, "polyroots({1,-5,10,-10,5,-1})", "{1,1,1,1,1}");
+    let r = calc
+        .eval("polyroots({1,0,0,0,0,-1})")
+        .unwrap()
+        .vec()
+        .unwrap();
+    assert_eq!(r.len(), 5);
+    assert_eq!(
+        calc.format(&calc.eval("norm(polyroots({1,0,0,0,0,-1})^5-1)").unwrap()),
+        "0"
+    );
+    for input in ["polyroots({0,0})", "polyroots({0})"] {
+        assert!(
+            matches!(calc.eval(input), Err(Error::Domain(..))),
+            "{input}"
+        );
+    }
+}
+#[test]
+fn test_minimize() {
+    let calc = Calculator::new(Options {
+        decimal_places: places(0.5),
+        ..Options::default()
+    });
+    for (input, output) in [
+        ("minimize(x,(x-2)^2+1,0)", "{2,1}"),
+        ("minimize(x,y,(1-x)^2+100(y-x^2)^2,{-1.2,1})", "{1,1,0}"),
+        ("maximize(x,y,-(x-1)^2-(y+2)^2+3,{0,0})", "{1,-2,3}"),
+        (
+            "minimize(x,y,(x-3)^2+(y-3)^2,{0,0},{-1,-1},{1,2})",
+            "{1,2,5}",
+        ),
+        ("minimize(x,abs(x-1),0)", "{1,0}"),
+    ] {
+        assert_eval(&calc, input, output);
+    }
+    #[cfg(feature = "rug")]
+    assert_eq!(
+        calc.format(&calc.eval("minimize(x,(x-10^30-5)^4,10^30)").unwrap()),
+        "{1000000000000000000000000000005,0}"
+    );
+    assert!(matches!(
+        calc.eval("minimize(x,x,0)"),
+        Err(Error::NotConverged("minimize did not converge", _))
+    ));
+    assert!(matches!(
+        calc.eval("maximize(x,x,0)"),
+        Err(Error::NotConverged("maximize did not converge", _))
+    ));
+    assert_eval(&calc, "minimize(x,(x-2m)^2,0m)", "{2 m,0 m^2}");
+    assert_eval(&calc, "maximize(x,-(x-3s)^2+1s^2,1s)", "{3 s,1 s^2}");
+}
+#[test]
+fn test_fit() {
+    let calc = Calculator::new(Options {
+        multi: false,
+        decimal_places: places(0.5),
+        ..Options::default()
+    });
+    for (input, output) in [
+        ("polyfit({{0,1},{1,2},{2,2},{3,5}},1)", "{1.2,0.7}"),
+        ("polyfit({{0,1},{1,2},{2,2},{3,5}},1,0)", "{1.2,0.7}"),
+        ("polyfit({{0,1},{1,2},{2,2},{3,5}},1,1)", "0.8"),
+        (
+            "polyfit({{0,1},{1,2},{2,2},{3,5}},1,2)",
+            "{0.3,0.1,-1.1,0.7}",
+        ),
+        (
+            "polyfit({{0m,1s},{1m,3s},{2m,7s},{3m,13s}},2)",
+            "{1 m^-2 s,1 m^-1 s,1 s}",
+        ),
+        (
+            "polyfit({{0m,1s},{1m,3s},{2m,7s},{3m,13s}},2,2)",
+            "{0 s,0 s,0 s,0 s}",
+        ),
+        ("expfit({{0s,2m},{1s,2e m},{2s,2e^2m}})", "{2 m,1 s^-1}"),
+        ("expfit({{0s,2m},{1s,2e m},{2s,2e^2m}},1)", "1"),
+        ("expfit({{0,-2},{1,-2e},{2,-2e^2}})", "{-2,1}"),
+        ("logfit({{1,1},{e,3},{e^2,5}})", "{1,2}"),
+        ("logfit({{1,1},{e,3},{e^2,5}},2)", "{0,0,0}"),
+        ("powfit({{1,3},{2,12},{3,27}})", "{3,2}"),
+        ("powfit({{1,3},{2,12},{3,27}},1)", "1"),
+        (
+            "fit(x,a,b,c,a*sin(b*x)+c,{{0,1},{1,1+2sin(0.5)},{2,1+2sin(1)},{3,1+2sin(1.5)}},{1,1,0})",
+            "{2,0.5,1}",
+        ),
+        ("fit(x,a,a*x,{{1,2},{2,4.1}},{1})", "{2.04}"),
+        ("fit(x,a,a*x,{{1,2},{2,4.1}},1)", "{2.04}"),
+        ("fit(x,a,a*x,{{1,2},{2,4.1}},{1},1)", "0.999092970522"),
+        ("fit(x,a,a*x,{{1,2},{2,4.1}},1,2)", "{-0.04,0.02}"),
+        (
+            "fit(x,a,b,a*x+b,{{0s,1m},{1s,3m},{2s,5m}},{1,0})",
+            "{2 m s^-1,1 m}",
+        ),
+        (
+            "fit(x,a,b,a*x+b,{{0s,1m},{1s,3m},{2s,5m}},{1,0},2)",
+            "{0 m,0 m,0 m}",
+        ),
+    ] {
+        assert_eval(&calc, input, output);
+    }
+    #[cfg(feature = "rug")]
+    {
+        let mut calc = Calculator::new(Options {
+            multi: false,
+            ..Options::default()
+        });
+        calc.set_option("prec", "128").unwrap();
+        let data = (100000..100008)
+            .map(|x: i128| format!("{{{x},{}}}", x.pow(5) - 3 * x.pow(2) + 1))
+            .collect::<Vec<String>>()
+            .join(",");
+        let out = calc.eval(&format!("polyfit({{{data}}},5)")).unwrap();
+        assert_eq!(calc.format(&out), "{1,0,0,-3,0,1}");
+    }
+    for input in [
+        "polyfit({{1,1},{1,2},{1,3}},1)",
+        "polyfit({{0,1},{1,2},{2,2}},-1)",
+        "polyfit({{0,1},{1,2},{2,2}},1.5)",
+        "polyfit({{0,1},{1,2},{2,2}},1,3)",
+        "logfit({{0,1},{1,2},{2,2}})",
+        "logfit({{-1,1},{1,2},{2,2}})",
+        "powfit({{0,1},{1,2},{2,2}})",
+        "powfit({{1,1},{2,0},{3,2}})",
+        "expfit({{0,1},{1,0},{2,2}})",
+        "fit(x,a,a*x,{{1,2},{2,4.1}},{1},3)",
+    ] {
+        assert!(
+            matches!(calc.eval(input), Err(Error::Domain(..))),
+            "{input}"
+        );
+    }
+}
+#[test]
+fn test_interpolation() {
+    let mut calc = Calculator::default();
+    for (input, output) in [
+        ("spline({{0,0},{1,1},{2,8},{3,27}},1.5,0,27)", "3.375"),
+        ("spline({{0,0},{1,1},{2,8},{3,27}},1.5)", "3.15"),
+        ("pchip({{0,0},{1,1},{2,1},{3,5}},{0.5,1.5})", "{0.6875,1}"),
+        ("linterp({{0,0},{1,2},{3,4}},2)", "3"),
+        ("spline({{0s,0m},{1s,1m},{2s,0m}},0.5s)", "0.6875 m"),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), output, "{input}");
+    }
+    assert_eq!(
+        calc.eval("linterp({{0,0},{1,1}})")
+            .unwrap()
+            .mat()
+            .unwrap()
+            .len(),
+        calc.options.samples_2d + 1
+    );
+    calc.options.samples_2d = 64;
+    assert_eq!(
+        calc.eval("spline({{0,0},{1,1},{2,8},{3,27}})")
+            .unwrap()
+            .mat()
+            .unwrap()
+            .len(),
+        64
+    );
+    calc.define("f(x)=spline({{0,0},{1,1},{2,8},{3,27}},x)")
+        .unwrap();
+    assert_eq!(calc.format(&calc.eval("f(1.5)").unwrap()), "3.15");
+    assert!(calc.eval("spline({{0,0},{0,1},{1,1}},0.5)").is_err());
+}
+#[test]
+fn test_diff() {
+    let mut calc = Calculator::default();
+    calc.define("g(x)=x^2+1").unwrap();
+    calc.define("h(x)=diff(x^3,x)").unwrap();
+    for (input, output) in [
+        ("diff(x^3,x)", "(3*x^2)"),
+        ("diff(sin(x)^2,x)", "(2*sin(x)*cos(x))"),
+        ("diff(x^x,x)", "(x^x*(ln(x)+1))"),
+        ("diff(ln(x)/x,x)", "((1-ln(x))/x^2)"),
+        ("diff(g(x),x)", "(2*x)"),
+        ("diff(atan(x),x,2)", "(-2*x/(1+x^2)^2)"),
+        ("diff({x,x^2},x)", "({1,2*x})"),
+        ("diff(zeta(x),x)", "(slope(x,zeta(x),x))"),
+        ("diff((1+i)*x^2,x)", "((2+2i)*x)"),
+        ("diff(x^2-(1+i)*x,x)", "(2*x-(1+i))"),
+        ("diff(asec(x),x)", "(1/(abs(x)*sqrt(x^2-1)))"),
+        ("diff(acsc(x),x)", "(-1/(abs(x)*sqrt(x^2-1)))"),
+        ("diff(root(x,3),x)", "(root(x,3)/(3*x))"),
+        ("diff(sum(n,n*x,1,3),x)", "(sum(n,n,1,3))"),
+        ("diff(sum(x,x^2,1,3),x)", "(0)"),
+        (
+            "diff(prod(n,x+n,1,3),x)",
+            "(prod(n,x+n,1,3)*sum(n,1/(x+n),1,3))",
+        ),
+    ] {
+        assert_symbolic(&calc, input, output);
+    }
+    for (input, output) in [
+        ("h(2)", "12"),
+        ("set(x,diff(g(x)^2,x),2)", "40"),
+        ("set(y,diff(y*h(y),y),2)", "36"),
+        ("set(x,diff(atan(x),x,2),1)", "-0.5"),
+        ("set(x,diff(cos(x),x)+sin(x),1)*2^100", "0"),
+        ("set(x,diff(sum(n,n*x^n,1,3),x),2)", "45"),
+        ("set(x,diff(prod(n,x+n,1,3),x),1)", "26"),
+        ("set(x,diff(root(x,3),x),8)", "0.083333333333"),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), output, "{input}");
+    }
+    assert!(calc.eval("diff(x,x,-1)").is_err());
+}
+#[test]
+fn test_simplify() {
+    let mut calc = Calculator::default();
+    calc.define("p(a,b,x)=collect(a*x^2+b*x+x^2+2a*x,x)")
+        .unwrap();
+    for (input, output) in [
+        ("simplify(x*x+2x-x)", "(x^2+x)"),
+        ("simplify((x+1)^2-(x^2+2x+1))", "(0)"),
+        ("simplify(3sin(y)^2+3cos(y)^2+x)", "(x+3)"),
+        ("simplify(exp(x)*exp(2x))", "(exp(3*x))"),
+        ("simplify(e^(2ln(x)))", "(x^2)"),
+        ("simplify(sin(x)/cos(x))", "(tan(x))"),
+        ("simplify((x^3-x)/(x^2+x))", "(x-1)"),
+        ("simplify(1/(x-1)-1/(x+1))", "(2/(x^2-1))"),
+        ("simplify(x/2+x/3)", "(5*x/6)"),
+        ("simplify(sqrt(x^2))", "(sqrt(x^2))"),
+        ("simplify(diff(sqrt(1-x^2),x))", "(-x/sqrt(1-x^2))"),
+        ("simplify((x^2)^y)", "((x^2)^y)"),
+        ("simplify((x^3)^2*sqrt(x)^y)", "(x^(y/2+6))"),
+        ("simplify(ln(exp(x)))", "(ln(exp(x)))"),
+        ("expand(2(x+1)(x-2))", "(2*x^2-2*x-4)"),
+        ("expand((x+y+1)^2)", "(x^2+2*x*y+y^2+2*x+2*y+1)"),
+        ("collect((x+y)^3,x)", "(x^3+3*y*x^2+3*y^2*x+y^3)"),
+        ("simplify(x/(1+i)+x/(1+i))", "((1-i)*x)"),
+        ("expand((x+1+i)^2)", "(x^2+(2+2i)*x+2i)"),
+        ("simplify(0/0)", "(0/0)"),
+        ("simplify((x-x)/(y-y))", "(0/0)"),
+        ("simplify(x/0)", "(x/0)"),
+    ] {
+        assert_symbolic(&calc, input, output);
+    }
+    for (input, output) in [
+        ("p(1,2,3)", "30"),
+        ("set(x,simplify((x^2-1)/(x-1)),3)", "4"),
+        ("set(x,simplify(0*x/0),3)", "NaN"),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), output, "{input}");
+    }
+    #[cfg(feature = "rug")]
+    assert_eq!(
+        calc.format(
+            &calc
+                .eval("set(x,(simplify(10^(-80)*x+1)-1)*10^80,2)")
+                .unwrap()
+        ),
+        "2"
+    );
+}
+#[test]
+fn test_factor() {
+    let mut calc = Calculator::default();
+    for (input, output) in [
+        ("factor(x^4-1,x)", "((x-1)*(x+1)*(x^2+1))"),
+        ("factor(2x^2-2,x)", "(2*(x-1)*(x+1))"),
+        ("factor(x^3-3x^2+3x-1,x)", "((x-1)^3)"),
+        ("factor(x^4+4,x)", "((x^2-2*x+2)*(x^2+2*x+2))"),
+        ("factor(x^5+x+1,x)", "((x^2+x+1)*(x^3-x^2+1))"),
+        ("factor(x^4-10x^2+1,x)", "(x^4-10*x^2+1)"),
+        (
+            "factor((3x^3+7x-5)(2x^4-x+9)(x^5+11),x)",
+            "((3*x^3+7*x-5)*(2*x^4-x+9)*(x^5+11))",
+        ),
+        ("pgcd(x^2-1,x^2+2x+1,x)", "(x+1)"),
+        ("pdiv(x^3+1,x-2,x)", "({x^2+2*x+4,9})"),
+        ("prem(x^3+1,2x-2,x)", "(16)"),
+        ("resultant(x^2-1,x-2,x)", "(3)"),
+        ("discriminant(x^2+x+1,x)", "(-3)"),
+        ("apart(1/(x^2-1),x)", "(1/(2*(x-1))-1/(2*(x+1)))"),
+        ("apart((x^3+1)/(x^2-1),x)", "(x+1/(x-1))"),
+        ("apart(1/(x^2*(x+1)),x)", "(1/x^2-1/x+1/(x+1))"),
+        ("apart(1/(x^3+x),x)", "(1/x-x/(x^2+1))"),
+    ] {
+        assert_symbolic(&calc, input, output);
+    }
+    assert!(calc.parse("factor(x^2-pi,x)").is_err());
+    calc.define("f(x)=apart(1/(x^2-1),x)").unwrap();
+    for (input, output) in [
+        ("factor(12)", "{1,2,3,4,6,12}"),
+        ("f(3)", "0.125"),
+        ("set(y,factor(y^2-1,y),3)", "8"),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), output, "{input}");
+    }
+}
+#[test]
+fn test_antiderivative() {
+    let mut calc = Calculator::default();
+    for (input, output) in [
+        ("antiderivative(x^2+3x,x)", "(x^3/3+3*x^2/2)"),
+        (
+            "antiderivative(1/(x^2-1),x)",
+            "(ln(abs(x-1))/2-ln(abs(x+1))/2)",
+        ),
+        ("antiderivative(1/(x^2+1)^2,x)", "(x/(x^2+1)/2+atan(x)/2)"),
+        ("antiderivative(1/(x-1)^2,x)", "(-1/(x-1))"),
+        ("antiderivative(x*exp(x^2),x)", "(exp(x^2)/2)"),
+        ("antiderivative(x*exp(x),x)", "(x*exp(x)-exp(x))"),
+        ("antiderivative(x*ln(x),x)", "(x^2*ln(x)/2-x^2/4)"),
+        ("antiderivative(sin(x)^2*cos(x),x)", "(sin(x)^3/3)"),
+        ("antiderivative(2x/(x^2+1),x)", "(ln(abs(x^2+1)))"),
+        ("antiderivative(1/sqrt(x),x)", "(2*sqrt(x))"),
+        ("antiderivative(1/sqrt(1-x^2),x)", "(asin(x))"),
+        ("antiderivative(-1/sqrt(1-x^2),x)", "(acos(x))"),
+        ("antiderivative(1/(1+x^2),x)", "(atan(x))"),
+        ("antiderivative(1/sqrt(x^2+1),x)", "(asinh(x))"),
+        ("antiderivative(1/sqrt(4-9x^2),x)", "(asin(3*x/2)/3)"),
+        ("antiderivative(1/sqrt(2x-x^2),x)", "(asin(x-1))"),
+        ("antiderivative(2/sqrt(x^2+2x+5),x)", "(2*asinh((x+1)/2))"),
+    ] {
+        assert_symbolic(&calc, input, output);
+    }
+    assert!(calc.parse("antiderivative(exp(x^2),x)").is_err());
+    for f in [
+        "1/(x^2+x+1)",
+        "1/(x^2-2)",
+        "x^2*sin(x)",
+        "(2x+3)/(x^2+2x+5)",
+        "1/(x^3+x)",
+        "atan(x)",
+        "1/sqrt(16-x^2)",
+        "-1/sqrt(25-x^2)",
+        "1/sqrt(3x^2+x+1)",
+        "1/sqrt(x^2-1)",
+    ] {
+        calc.define(&format!("F(x)=antiderivative({f},x)")).unwrap();
+        let a = calc.eval("F(3)-F(2)").unwrap().num().unwrap().number;
+        let b = calc
+            .eval(&format!("area(x,{f},2,3)"))
+            .unwrap()
+            .num()
+            .unwrap()
+            .number;
+        assert!((a - b).abs().real().to_f64() < 1e-9, "{f}");
+    }
+}
+#[test]
+fn test_isolate() {
+    let mut calc = Calculator::default();
+    for (input, output) in [
+        ("isolate(x,sin(x)-0.5)", "{0.523598775598,2.617993877991}"),
+        ("isolate(x,sin(2x))", "{0,1.570796326795}"),
+        ("isolate(x,tan(x)-1,-1)", "{-2.356194490192}"),
+        ("isolate(x,exp(x)-2,1)", "{0.69314718056+6.28318530718i}"),
+        ("isolate(x,2^x-8)", "{3}"),
+        ("isolate(x,log(2,x)-3)", "{8}"),
+        ("isolate(x,abs(x-1)-3)", "{-2,4}"),
+        ("isolate(x,sqrt(x)-3)", "{9}"),
+        ("isolate(x,sqrt(x)+3)", "{}"),
+        ("isolate(x,x^(1/3)-2)", "{8}"),
+        ("isolate(x,cbrt(x)+2)", "{-8}"),
+        ("isolate(x,cosh(x)-1,1)", "{6.28318530718i}"),
+        ("isolate(x,tanh(x),1)", "{3.14159265359i}"),
+        (
+            "isolate(x,sin(x^2-x))",
+            "{-1.341627718511,0,1,2.341627718511}",
+        ),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), output, "{input}");
+    }
+    for (input, output) in [
+        (
+            "isolate(x,sin(x)^2-sin(x))",
+            "{0,1.570796326795,3.14159265359}",
+        ),
+        ("isolate(x,(x-pi)(x-2))", "{2,3.14159265359}"),
+        ("isolate(x,x/(x-1)-2)", "{2}"),
+        ("isolate(x,(x^2-1)/(x-1))", "{-1}"),
+        ("isolate(x,sin x-0.5)", "{0.523598775598,2.617993877991}"),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), output, "{input}");
+    }
+    assert!(calc.eval("isolate(x,sin(x),0.5)").is_err());
+    for input in [
+        "isolate(x,log(0,x)-1)",
+        "isolate(x,log(1,x)-1)",
+        "isolate(x,log(-2,x)-1)",
+    ] {
+        assert!(
+            matches!(calc.eval(input), Err(Error::Domain(..))),
+            "{input}"
+        );
+    }
+    assert_eq!(
+        calc.format(&calc.eval("isolate(x,log(x,4)-2)").unwrap()),
+        "{2}"
+    );
+    for n in -2..=2 {
+        let NumStr::Vector(v) = calc.eval(&format!("isolate(x,sin(x)-0.5,{n})")).unwrap() else {
+            unreachable!()
+        };
+        let pi = std::f64::consts::PI;
+        let turn = 2.0 * pi * n as f64;
+        assert_eq!(v.len(), 2);
+        assert!((v[0].number.real().to_f64() - (pi / 6.0 + turn)).abs() < 1e-9);
+        assert!((v[1].number.real().to_f64() - (5.0 * pi / 6.0 + turn)).abs() < 1e-9);
+    }
+    calc.options.angle = crate::units::AngleType::Degrees;
+    for (input, output) in [
+        ("isolate(x,sin(x)-0.5,1)", "{390,510}"),
+        ("isolate(x,cos(x)^2-0.25)", "{-120,-60,60,120}"),
+    ] {
+        assert_eq!(calc.format(&calc.eval(input).unwrap()), output, "{input}");
+    }
 }
@@ -1,141 +1,114 @@
-mod cdecimal;
-mod cf32;
-mod cf64;
-mod complex;
+pub mod complex;
+#[cfg(feature = "fastnum")]
 mod decimal;
-mod float;
-mod integer;
+pub mod float;
+pub mod integer;
+pub mod ops;
+pub mod rational;
+mod real;
 
-pub use cdecimal::CDecimal;
-pub use cf32::CF32;
-pub use cf64::CF64;
 pub use complex::Complex;
+#[cfg(feature = "fastnum")]
 pub use decimal::Decimal;
 pub use float::Float;
 pub use integer::Integer;
+pub use rational::Rational;
 
-use rug::ops::Pow as RugPow;
+pub use crate::units::Type;
+
+///the per backend complex types are now all a Complex, whose backend is picked at runtime by
+///Precision::ty
+#[deprecated(note = "use Complex with Type::F64")]
+pub type CF64 = Complex;
+#[deprecated(note = "use Complex with Type::F32")]
+pub type CF32 = Complex;
+#[cfg(feature = "fastnum")]
+#[deprecated(note = "use Complex with Type::Fastnum")]
+pub type CDecimal = Complex;
 
 //TODO malachite num maybe
 //TODO make real only an option
 
-#[derive(PartialEq)]
-pub enum IsPrime {
-    No,
-    Probably,
-    Yes,
-}
-impl From<rug::integer::IsPrime> for IsPrime {
-    fn from(value: rug::integer::IsPrime) -> Self {
-        match value {
-            rug::integer::IsPrime::No => IsPrime::No,
-            rug::integer::IsPrime::Probably => IsPrime::Probably,
-            rug::integer::IsPrime::Yes => IsPrime::Yes,
-        }
+///the backend and the bits a number is created with, f64 and f32 ignore the bits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Precision {
+    pub ty: Type,
+    pub bits: u32,
+}
+impl Precision {
+    pub fn new(ty: Type, bits: u32) -> Precision {
+        Precision { ty, bits }
+    }
+    ///the same backend with other bits
+    pub fn with_bits(self, bits: u32) -> Precision {
+        Precision { ty: self.ty, bits }
     }
 }
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
-
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub enum Type {
-    Rug,
-    Fastnum,
-    F64,
-    F32,
+///more working bits in the same backend
+impl std::ops::Mul<u32> for Precision {
+    type Output = Precision;
+    fn mul(self, rhs: u32) -> Precision {
+        self.with_bits(self.bits * rhs)
+    }
 }
-
-pub enum Special {
-    Pi,
-    Nan,
-    Infinity,
+impl std::ops::Add<u32> for Precision {
+    type Output = Precision;
+    fn add(self, rhs: u32) -> Precision {
+        self.with_bits(self.bits + rhs)
+    }
 }
-
 pub trait Prec {
-    fn prec(&self) -> u32;
-    fn set_prec(&mut self, new_prec: u32);
+    fn precision(&self) -> Precision;
 }
-pub trait DivFloor {
-    fn div_floor(self, rhs: f64) -> Self;
-}
-impl DivFloor for f64 {
-    fn div_floor(self, rhs: f64) -> Self {
-        (self / rhs).floor()
+impl Prec for Precision {
+    fn precision(&self) -> Precision {
+        *self
     }
 }
-pub trait Parse<T> {
-    fn parse(prec: u32, s: T) -> Option<Self>
-    where
-        Self: Sized;
-    fn parse_radix(prec: u32, s: T, base: i32) -> Option<Self>
-    where
-        Self: Sized;
-}
-pub trait ParseU<T> {
-    fn parse(t: Type, prec: u32, s: T) -> Option<Self>
-    where
-        Self: Sized;
-    fn parse_radix(t: Type, prec: u32, s: T, base: i32) -> Option<Self>
-    where
-        Self: Sized;
-}
-pub trait WithVal<T> {
-    fn with_val(obj: Type, prec: u32, val: T) -> Self;
-}
-pub trait NewVal {
-    fn new(obj: Type, prec: u32) -> Self;
-}
-pub trait WithValDeci<T> {
-    fn with_val(prec: u32, val: T) -> Self;
-}
-pub trait NewDeciVal {
-    fn new(prec: u32) -> Self;
-}
-pub trait Pow<T> {
-    fn pow(self, val: T) -> Self;
-}
-pub trait Rt<T> {
-    fn root(self, val: T) -> Self;
+impl Prec for (Precision, Precision) {
+    fn precision(&self) -> Precision {
+        self.0
+    }
 }
-pub trait SinhCosh {
-    fn sinh_cosh(self) -> (Self, Self)
-    where
-        Self: Sized;
+impl Prec for &Precision {
+    fn precision(&self) -> Precision {
+        **self
+    }
 }
-pub trait SpecialValuesDeci {
-    fn pi(prec: u32) -> Self;
-    fn nan(prec: u32) -> Self;
-    fn inf(prec: u32) -> Self;
+///the less precise of two backends, mixed operands are computed in it
+pub(crate) fn lower(a: Type, b: Type) -> Type {
+    fn rank(t: Type) -> u8 {
+        match t {
+            #[cfg(feature = "rug")]
+            Type::Rug => 0,
+            #[cfg(feature = "fastnum")]
+            Type::Fastnum => 1,
+            Type::F64 => 2,
+            Type::F32 => 3,
+        }
+    }
+    if rank(a) >= rank(b) { a } else { b }
 }
-pub trait SpecialValues {
-    fn pi(t: Type, prec: u32) -> Self;
-    fn nan(t: Type, prec: u32) -> Self;
-    fn inf(t: Type, prec: u32) -> Self;
+///the exact numbers, i128 based without rug
+pub trait Exact {
+    fn is_finite(&self) -> bool;
 }
-
-impl SpecialValuesDeci for f64 {
-    fn pi(_: u32) -> Self {
-        std::f64::consts::PI
-    }
-    fn nan(_: u32) -> Self {
-        f64::NAN
-    }
-    fn inf(_: u32) -> Self {
-        f64::INFINITY
+impl Exact for Integer {
+    fn is_finite(&self) -> bool {
+        Integer::is_finite(self)
     }
 }
-impl SpecialValuesDeci for f32 {
-    fn pi(_: u32) -> Self {
-        std::f32::consts::PI
-    }
-    fn nan(_: u32) -> Self {
-        f32::NAN
-    }
-    fn inf(_: u32) -> Self {
-        f32::INFINITY
+impl Exact for Rational {
+    fn is_finite(&self) -> bool {
+        Rational::is_finite(self)
     }
 }
-use crate::macros::impls::*;
-impl_types!(f64, f32, i32, u64, u128);
-impl_sinh_cosh!(f64, f32, fastnum::decimal::D512, fastnum::decimal::D256);
+///whether an exact result is still exact, the i128 numbers without rug can overflow
+#[cfg(feature = "rug")]
+pub fn fits<T: Exact>(_: &T) -> bool {
+    true
+}
+#[cfg(not(feature = "rug"))]
+pub fn fits<T: Exact>(q: &T) -> bool {
+    q.is_finite()
+}
@@ -1,312 +1,547 @@
+#[cfg(feature = "fastnum")]
+use super::Decimal;
 use super::{
-    CDecimal, CF32, CF64, Decimal, Float, Integer, NewVal, ParseU, Prec, Special, SpecialValues,
-    SpecialValuesDeci, Type, WithVal, WithValDeci,
-};
-use crate::macros::impls::{
-    impl_complex, impl_from_complex_tuple, impl_neg, impl_new_val, impl_self_ops,
+    Integer, Prec, Precision, Rational, Type,
+    float::{Constant, Float, ParseFloatError, ParseIncomplete, Special, ToFloat},
+    lower,
+    ops::{CompleteRound, Pow},
+    real::cplx,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter};
-use std::ops::{Add, Div, Mul};
-#[derive(Clone, PartialEq)]
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, Div, Mul, Neg, Shl, Shr, Sub},
+};
+///a complex number as two Floats of one backend
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub enum Complex {
-    Rug(rug::Complex),
-    Fastnum(CDecimal),
-    F64(CF64),
-    F32(CF32),
+pub struct Complex {
+    re: Float,
+    im: Float,
 }
-
-impl Prec for Complex {
-    fn prec(&self) -> u32 {
-        match self {
-            Self::Rug(a) => a.prec().0,
-            Self::Fastnum(a) => a.prec(),
-            Self::F64(a) => a.prec(),
-            Self::F32(a) => a.prec(),
-        }
+///values a Complex can be created from in a given precision
+pub trait ToComplex {
+    fn to_complex(self, prec: Precision) -> Complex;
+    ///the value as an operand, Floats and Complexes keep their own backend
+    fn operand(self, prec: Precision) -> Complex
+    where
+        Self: Sized,
+    {
+        self.to_complex(prec)
     }
-    fn set_prec(&mut self, new_prec: u32) {
-        match self {
-            Self::Rug(a) => a.set_prec(new_prec),
-            Self::Fastnum(a) => a.set_prec(new_prec),
-            Self::F64(_) => {}
-            Self::F32(_) => {}
-        }
+}
+macro_rules! to_complex {
+    ($($t:ty),*) => {
+        $(
+            impl ToComplex for $t {
+                fn to_complex(self, prec: Precision) -> Complex {
+                    Complex::from(self.to_float(prec))
+                }
+            }
+        )*
+    };
+}
+to_complex!(
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    bool,
+    &i8,
+    &i16,
+    &i32,
+    &i64,
+    &i128,
+    &isize,
+    &u8,
+    &u16,
+    &u32,
+    &u64,
+    &u128,
+    &usize,
+    &f32,
+    &f64,
+    Integer,
+    &Integer,
+    Rational,
+    &Rational,
+    Constant,
+    Special,
+    ParseIncomplete
+);
+impl ToComplex for Float {
+    fn to_complex(self, prec: Precision) -> Complex {
+        Complex::from(self.to_float(prec))
+    }
+    fn operand(self, _: Precision) -> Complex {
+        Complex::from(self)
     }
 }
-
-impl ParseU<&str> for Complex {
-    fn parse(t: Type, prec: u32, s: &str) -> Option<Self> {
-        match t {
-            Type::Rug => Float::parse(t, prec, s).map(|a| a.into()),
-            Type::Fastnum => Float::parse(t, prec, s).map(|a| a.into()),
-            Type::F64 => Float::parse(t, prec, s).map(|a| a.into()),
-            Type::F32 => Float::parse(t, prec, s).map(|a| a.into()),
-        }
+impl ToComplex for &Float {
+    fn to_complex(self, prec: Precision) -> Complex {
+        self.clone().to_complex(prec)
     }
-    fn parse_radix(t: Type, prec: u32, s: &str, base: i32) -> Option<Self> {
-        match t {
-            Type::Rug => Float::parse_radix(t, prec, s, base).map(|a| a.into()),
-            Type::Fastnum => Float::parse_radix(t, prec, s, base).map(|a| a.into()),
-            Type::F64 => Float::parse_radix(t, prec, s, base).map(|a| a.into()),
-            Type::F32 => Float::parse_radix(t, prec, s, base).map(|a| a.into()),
-        }
+    fn operand(self, _: Precision) -> Complex {
+        Complex::from(self.clone())
     }
 }
-
-impl SpecialValues for Complex {
-    fn pi(t: Type, prec: u32) -> Self {
-        match t {
-            Type::Rug => Self::Rug(rug::Complex::with_val(prec, rug::float::Constant::Pi)),
-            Type::Fastnum => Self::Fastnum(Decimal::pi(prec).into()),
-            Type::F64 => Self::F64(f64::pi(prec).into()),
-            Type::F32 => Self::F32(f32::pi(prec).into()),
+impl ToComplex for Complex {
+    fn to_complex(self, prec: Precision) -> Complex {
+        Complex {
+            re: self.re.to_float(prec),
+            im: self.im.to_float(prec),
         }
     }
-    fn nan(t: Type, prec: u32) -> Self {
-        match t {
-            Type::Rug => Self::Rug(rug::Complex::with_val(prec, rug::float::Special::Nan)),
-            Type::Fastnum => Self::Fastnum(Decimal::nan(prec).into()),
-            Type::F64 => Self::F64(f64::nan(prec).into()),
-            Type::F32 => Self::F32(f32::nan(prec).into()),
-        }
+    fn operand(self, _: Precision) -> Complex {
+        self
     }
-    fn inf(t: Type, prec: u32) -> Self {
-        match t {
-            Type::Rug => Self::Rug(rug::Complex::with_val(prec, rug::float::Special::Infinity)),
-            Type::Fastnum => Self::Fastnum(Decimal::inf(prec).into()),
-            Type::F64 => Self::F64(f64::inf(prec).into()),
-            Type::F32 => Self::F32(f32::inf(prec).into()),
+}
+impl ToComplex for &Complex {
+    fn to_complex(self, prec: Precision) -> Complex {
+        self.clone().to_complex(prec)
+    }
+    fn operand(self, _: Precision) -> Complex {
+        self.clone()
+    }
+}
+impl<A: ToFloat, B: ToFloat> ToComplex for (A, B) {
+    fn to_complex(self, prec: Precision) -> Complex {
+        Complex {
+            re: self.0.to_float(prec),
+            im: self.1.to_float(prec),
         }
     }
 }
-
 impl From<Float> for Complex {
-    fn from(value: Float) -> Self {
-        match value {
-            Float::Rug(a) => Complex::Rug(a.into()),
-            Float::Fastnum(a) => Complex::Fastnum(a.into()),
-            Float::F64(a) => Complex::F64(a.into()),
-            Float::F32(a) => Complex::F32(a.into()),
-        }
+    fn from(re: Float) -> Complex {
+        let im = Float::new(re.prec());
+        Complex { re, im }
     }
 }
-
 impl From<(Float, Float)> for Complex {
-    fn from(value: (Float, Float)) -> Self {
+    fn from((re, im): (Float, Float)) -> Complex {
+        let (re, im) = re.pair(im);
+        Complex { re, im }
+    }
+}
+#[cfg(feature = "rug")]
+impl From<rug::Complex> for Complex {
+    fn from(value: rug::Complex) -> Complex {
+        let (re, im) = value.into_real_imag();
+        Complex {
+            re: Float::Rug(re),
+            im: Float::Rug(im),
+        }
+    }
+}
+///both parts in their backend, the rug ones as an mpc number
+enum Parts {
+    #[cfg(feature = "rug")]
+    Rug(rug::Complex),
+    #[cfg(feature = "fastnum")]
+    Fastnum((Decimal, Decimal)),
+    F64((f64, f64)),
+    F32((f32, f32)),
+}
+impl From<Parts> for Complex {
+    fn from(value: Parts) -> Complex {
         match value {
-            (Float::Rug(a), Float::Rug(b)) => Complex::Rug((a, b).into()),
-            (Float::Fastnum(a), Float::Fastnum(b)) => Complex::Fastnum((a, b).into()),
-            (Float::F64(a), Float::F64(b)) => Complex::F64((a, b).into()),
-            (Float::F32(a), Float::F32(b)) => Complex::F32((a, b).into()),
-            _ => unreachable!(),
+            #[cfg(feature = "rug")]
+            Parts::Rug(a) => Complex::from(a),
+            #[cfg(feature = "fastnum")]
+            Parts::Fastnum((re, im)) => Complex {
+                re: Float::Fastnum(re),
+                im: Float::Fastnum(im),
+            },
+            Parts::F64((re, im)) => Complex {
+                re: Float::F64(re),
+                im: Float::F64(im),
+            },
+            Parts::F32((re, im)) => Complex {
+                re: Float::F32(re),
+                im: Float::F32(im),
+            },
         }
     }
 }
-
-impl Add<Float> for Complex {
-    type Output = Self;
-    fn add(self, rhs: Float) -> Self::Output {
-        self + Complex::from(rhs)
+///applies the rug expression or the cplx one to the parts
+macro_rules! each {
+    ($a:expr, $x:ident => $rug:expr, $e:expr) => {
+        Complex::from(match $a.parts() {
+            #[cfg(feature = "rug")]
+            Parts::Rug($x) => Parts::Rug($rug),
+            #[cfg(feature = "fastnum")]
+            Parts::Fastnum($x) => Parts::Fastnum($e),
+            Parts::F64($x) => Parts::F64($e),
+            Parts::F32($x) => Parts::F32($e),
+        })
+    };
+}
+///the same for two operands brought to one backend
+macro_rules! each2 {
+    ($a:expr, $b:expr, $x:ident, $y:ident => $rug:expr, $e:expr) => {{
+        let (a, b) = $a.pair($b);
+        Complex::from(match (a.parts(), b.parts()) {
+            #[cfg(feature = "rug")]
+            (Parts::Rug($x), Parts::Rug($y)) => Parts::Rug($rug),
+            #[cfg(feature = "fastnum")]
+            (Parts::Fastnum($x), Parts::Fastnum($y)) => Parts::Fastnum($e),
+            (Parts::F64($x), Parts::F64($y)) => Parts::F64($e),
+            (Parts::F32($x), Parts::F32($y)) => Parts::F32($e),
+            _ => unreachable!(),
+        })
+    }};
+}
+macro_rules! impl_ops {
+    ($(($trait:ident, $method:ident, $op:tt, $cplx:path)),*) => {
+        $(
+            impl<T: ToComplex> $trait<T> for Complex {
+                type Output = Complex;
+                fn $method(self, rhs: T) -> Complex {
+                    let rhs = rhs.operand(self.re.prec());
+                    each2!(self, rhs, a, b => a $op b, $cplx(a, b))
+                }
+            }
+            impl<T: ToComplex> $trait<T> for &Complex {
+                type Output = Complex;
+                fn $method(self, rhs: T) -> Complex {
+                    self.clone() $op rhs
+                }
+            }
+        )*
+    };
+}
+impl_ops!(
+    (Add, add, +, cplx::add),
+    (Sub, sub, -, cplx::sub),
+    (Mul, mul, *, cplx::mul),
+    (Div, div, /, cplx::div)
+);
+macro_rules! impl_assign {
+    ($(($trait:ident, $method:ident, $op:tt)),*) => {
+        $(
+            impl<T: ToComplex> std::ops::$trait<T> for Complex {
+                fn $method(&mut self, rhs: T) {
+                    *self = std::mem::take(self) $op rhs
+                }
+            }
+        )*
+    };
+}
+impl_assign!(
+    (AddAssign, add_assign, +),
+    (SubAssign, sub_assign, -),
+    (MulAssign, mul_assign, *),
+    (DivAssign, div_assign, /)
+);
+macro_rules! impl_prim {
+    ($($t:ty),*) => {
+        $(
+            impl Add<Complex> for $t {
+                type Output = Complex;
+                fn add(self, rhs: Complex) -> Complex {
+                    self.operand(rhs.re.prec()) + rhs
+                }
+            }
+            impl Sub<Complex> for $t {
+                type Output = Complex;
+                fn sub(self, rhs: Complex) -> Complex {
+                    self.operand(rhs.re.prec()) - rhs
+                }
+            }
+            impl Mul<Complex> for $t {
+                type Output = Complex;
+                fn mul(self, rhs: Complex) -> Complex {
+                    self.operand(rhs.re.prec()) * rhs
+                }
+            }
+            impl Div<Complex> for $t {
+                type Output = Complex;
+                fn div(self, rhs: Complex) -> Complex {
+                    self.operand(rhs.re.prec()) / rhs
+                }
+            }
+            impl Add<&Complex> for $t {
+                type Output = Complex;
+                fn add(self, rhs: &Complex) -> Complex {
+                    self + rhs.clone()
+                }
+            }
+            impl Sub<&Complex> for $t {
+                type Output = Complex;
+                fn sub(self, rhs: &Complex) -> Complex {
+                    self - rhs.clone()
+                }
+            }
+            impl Mul<&Complex> for $t {
+                type Output = Complex;
+                fn mul(self, rhs: &Complex) -> Complex {
+                    self * rhs.clone()
+                }
+            }
+            impl Div<&Complex> for $t {
+                type Output = Complex;
+                fn div(self, rhs: &Complex) -> Complex {
+                    self / rhs.clone()
+                }
+            }
+        )*
+    };
+}
+impl_prim!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, Float, &Float,
+    Integer, &Integer, Rational, &Rational
+);
+macro_rules! impl_eq {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq<$t> for Complex {
+                fn eq(&self, other: &$t) -> bool {
+                    self.im.is_zero() && self.re == *other
+                }
+            }
+            impl PartialEq<Complex> for $t {
+                fn eq(&self, other: &Complex) -> bool {
+                    other == self
+                }
+            }
+        )*
+    };
+}
+impl_eq!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, Float
+);
+macro_rules! impl_shift {
+    ($($t:ty),*) => {
+        $(
+            impl Shl<$t> for Complex {
+                type Output = Complex;
+                fn shl(self, rhs: $t) -> Complex {
+                    Complex {
+                        re: self.re << rhs,
+                        im: self.im << rhs,
+                    }
+                }
+            }
+            impl Shr<$t> for Complex {
+                type Output = Complex;
+                fn shr(self, rhs: $t) -> Complex {
+                    Complex {
+                        re: self.re >> rhs,
+                        im: self.im >> rhs,
+                    }
+                }
+            }
+        )*
+    };
+}
+impl_shift!(u32, i32);
+impl Neg for Complex {
+    type Output = Complex;
+    fn neg(self) -> Complex {
+        Complex {
+            re: -self.re,
+            im: -self.im,
+        }
     }
 }
-impl Add<Complex> for Float {
+impl Neg for &Complex {
     type Output = Complex;
-    fn add(self, rhs: Complex) -> Self::Output {
-        Complex::from(self) + rhs
+    fn neg(self) -> Complex {
+        -self.clone()
     }
 }
-impl Mul<Float> for Complex {
-    type Output = Self;
-    fn mul(self, rhs: Float) -> Self::Output {
-        self * Complex::from(rhs)
+impl<T: ToComplex> Pow<T> for Complex {
+    type Output = Complex;
+    fn pow(self, rhs: T) -> Complex {
+        let rhs = rhs.operand(self.re.prec());
+        each2!(self, rhs, a, b => rug::ops::Pow::pow(a, &b), cplx::pow(a, b))
     }
 }
-impl Mul<Complex> for Float {
+impl<T: ToComplex> Pow<T> for &Complex {
     type Output = Complex;
-    fn mul(self, rhs: Complex) -> Self::Output {
-        Complex::from(self) * rhs
+    fn pow(self, rhs: T) -> Complex {
+        self.clone().pow(rhs)
     }
 }
-
-impl Display for Complex {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Rug(a) => a.fmt(f),
-            Self::Fastnum(a) => a.fmt(f),
-            Self::F32(a) => a.fmt(f),
-            Self::F64(a) => a.fmt(f),
+impl CompleteRound for Complex {
+    type Completed = Complex;
+    fn complete<P: Prec>(self, prec: P) -> Complex {
+        self.to_complex(prec.precision())
+    }
+}
+///a checked complex number string, "(re im)" or a real one
+#[derive(Clone, Debug)]
+pub struct ParseComplex {
+    re: ParseIncomplete,
+    im: Option<ParseIncomplete>,
+}
+impl CompleteRound for ParseComplex {
+    type Completed = Complex;
+    fn complete<P: Prec>(self, prec: P) -> Complex {
+        let prec = prec.precision();
+        match self.im {
+            Some(im) => (self.re, im).to_complex(prec),
+            None => self.re.to_complex(prec),
         }
     }
 }
+impl Display for Complex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({} {})", self.re, self.im)
+    }
+}
+impl Default for Complex {
+    fn default() -> Complex {
+        Complex::from(Float::F64(0.0))
+    }
+}
 impl Complex {
-    pub fn real(&self) -> Float {
-        match self {
-            Self::Rug(a) => Float::Rug(a.real().clone()),
-            Self::Fastnum(a) => Float::Fastnum(a.0),
-            Self::F64(a) => Float::F64(a.0),
-            Self::F32(a) => Float::F32(a.0),
+    fn parts(self) -> Parts {
+        match (self.re, self.im) {
+            #[cfg(feature = "rug")]
+            (Float::Rug(re), Float::Rug(im)) => Parts::Rug(rug::Complex::from((re, im))),
+            #[cfg(feature = "fastnum")]
+            (Float::Fastnum(re), Float::Fastnum(im)) => Parts::Fastnum((re, im)),
+            (Float::F64(re), Float::F64(im)) => Parts::F64((re, im)),
+            (Float::F32(re), Float::F32(im)) => Parts::F32((re, im)),
+            (re, im) => Complex::from((re, im)).parts(),
         }
     }
-    pub fn imag(&self) -> Float {
-        match self {
-            Self::Rug(a) => Float::Rug(a.imag().clone()),
-            Self::Fastnum(a) => Float::Fastnum(a.1),
-            Self::F64(a) => Float::F64(a.1),
-            Self::F32(a) => Float::F32(a.1),
+    ///both operands in the less precise of their backends
+    fn pair(self, other: Complex) -> (Complex, Complex) {
+        let (a, b) = (self.re.ftype(), other.re.ftype());
+        if a == b {
+            return (self, other);
         }
+        let p = if lower(a, b) == a {
+            self.re.prec()
+        } else {
+            other.re.prec()
+        };
+        (self.to_complex(p), other.to_complex(p))
+    }
+    pub fn new<P: Prec>(prec: P) -> Complex {
+        Complex::from(Float::new(prec.precision()))
     }
-    pub fn real_imag(self) -> (Float, Float) {
-        match self {
-            Self::Rug(a) => {
-                let (a, b) = a.into_real_imag();
-                (Float::Rug(a), Float::Rug(b))
+    pub fn with_val<P: Prec, T: ToComplex>(prec: P, val: T) -> Complex {
+        val.to_complex(prec.precision())
+    }
+    pub fn parse_radix<S: AsRef<[u8]>>(
+        src: S,
+        radix: i32,
+    ) -> Result<ParseComplex, ParseFloatError> {
+        let s = std::str::from_utf8(src.as_ref())
+            .map_err(|_| ParseFloatError)?
+            .trim();
+        match s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+            Some(s) => {
+                let (re, im) = s.split_once([' ', ',']).ok_or(ParseFloatError)?;
+                Ok(ParseComplex {
+                    re: Float::parse_radix(re.trim(), radix)?,
+                    im: Some(Float::parse_radix(im.trim(), radix)?),
+                })
             }
-            Self::Fastnum(a) => (Float::Fastnum(a.0), Float::Fastnum(a.1)),
-            Self::F64(a) => (Float::F64(a.0), Float::F64(a.1)),
-            Self::F32(a) => (Float::F32(a.0), Float::F32(a.1)),
+            None => Ok(ParseComplex {
+                re: Float::parse_radix(s, radix)?,
+                im: None,
+            }),
         }
     }
-    pub fn is_zero(&self) -> bool {
-        match self {
-            Self::Rug(a) => a.is_zero(),
-            Self::Fastnum(a) => a.0.is_zero() && a.1.is_zero(),
-            Self::F64(a) => a.0 == 0.0 && a.1 == 0.0,
-            Self::F32(a) => a.0 == 0.0 && a.1 == 0.0,
-        }
+    ///the backend and the bits of both parts
+    pub fn prec(&self) -> (Precision, Precision) {
+        (self.re.prec(), self.im.prec())
     }
     pub fn ftype(&self) -> Type {
-        match self {
-            Self::Rug(_) => Type::Rug,
-            Self::Fastnum(_) => Type::Fastnum,
-            Self::F64(_) => Type::F64,
-            Self::F32(_) => Type::F32,
-        }
+        self.re.ftype()
     }
-    pub fn sin_cos(self) -> (Self, Self) {
-        match self {
-            Self::Rug(a) => {
-                let p = a.prec();
-                let (s, c) = a.sin_cos(rug::Complex::new(p));
-                (Self::Rug(s), Self::Rug(c))
-            }
-            Self::Fastnum(a) => {
-                let (s, c) = a.sin_cos();
-                (Self::Fastnum(s), Self::Fastnum(c))
-            }
-            Self::F64(a) => {
-                let (s, c) = a.sin_cos();
-                (Self::F64(s), Self::F64(c))
-            }
-            Self::F32(a) => {
-                let (s, c) = a.sin_cos();
-                (Self::F32(s), Self::F32(c))
-            }
-        }
+    ///rounds both parts to the given bits within the same backend
+    pub fn set_prec(&mut self, bits: u32) {
+        self.re.set_prec(bits);
+        self.im.set_prec(bits);
     }
-}
-
-impl PartialEq<f64> for Complex {
-    fn eq(&self, other: &f64) -> bool {
-        match self {
-            Self::Rug(a) => a == other,
-            Self::Fastnum(a) => a.0 == *other,
-            Self::F64(a) => a.0 == *other,
-            Self::F32(a) => a.0 == *other as f32,
-        }
+    pub fn real(&self) -> &Float {
+        &self.re
     }
-}
-impl PartialEq<i32> for Complex {
-    fn eq(&self, other: &i32) -> bool {
-        match self {
-            Self::Rug(a) => a == other,
-            Self::Fastnum(a) => a.0 == *other,
-            Self::F64(a) => a.0 == *other as f64,
-            Self::F32(a) => a.0 == *other as f32,
-        }
+    pub fn imag(&self) -> &Float {
+        &self.im
     }
-}
-impl Mul<Integer> for Complex {
-    type Output = Self;
-    fn mul(self, rhs: Integer) -> Self::Output {
-        match (self, rhs) {
-            (Complex::Rug(a), Integer::Rug(b)) => Complex::Rug(a * b),
-            (Complex::Fastnum(a), Integer::Fastnum(b)) => Complex::Fastnum(a * b),
-            (Complex::F64(a), Integer::F64(b)) => Complex::F64(a * b as f64),
-            (Complex::F32(a), Integer::F32(b)) => Complex::F32(a * b as f32),
-            _ => unreachable!(),
-        }
+    pub fn mut_real(&mut self) -> &mut Float {
+        &mut self.re
     }
-}
-impl Div<Integer> for Complex {
-    type Output = Self;
-    fn div(self, rhs: Integer) -> Self::Output {
-        match (self, rhs) {
-            (Complex::Rug(a), Integer::Rug(b)) => Complex::Rug(a / b),
-            (Complex::Fastnum(a), Integer::Fastnum(b)) => Complex::Fastnum(a / b),
-            (Complex::F64(a), Integer::F64(b)) => Complex::F64(a / b as f64),
-            (Complex::F32(a), Integer::F32(b)) => Complex::F32(a / b as f32),
-            _ => unreachable!(),
-        }
+    pub fn mut_imag(&mut self) -> &mut Float {
+        &mut self.im
     }
-}
-impl Mul<Complex> for Integer {
-    type Output = Complex;
-    fn mul(self, rhs: Complex) -> Self::Output {
-        match (rhs, self) {
-            (Complex::Rug(a), Integer::Rug(b)) => Complex::Rug(a * b),
-            (Complex::Fastnum(a), Integer::Fastnum(b)) => Complex::Fastnum(a * b),
-            (Complex::F64(a), Integer::F64(b)) => Complex::F64(a * b as f64),
-            (Complex::F32(a), Integer::F32(b)) => Complex::F32(a * b as f32),
-            _ => unreachable!(),
+    pub fn into_real_imag(self) -> (Float, Float) {
+        (self.re, self.im)
+    }
+    pub fn is_zero(&self) -> bool {
+        self.re.is_zero() && self.im.is_zero()
+    }
+    pub fn total_cmp(&self, other: &Complex) -> Ordering {
+        self.re
+            .total_cmp(&other.re)
+            .then_with(|| self.im.total_cmp(&other.im))
+    }
+    pub fn abs_ref(&self) -> Float {
+        self.re.clone().hypot(&self.im)
+    }
+    pub fn abs(self) -> Complex {
+        Complex::from(self.abs_ref())
+    }
+    pub fn arg(self) -> Complex {
+        Complex::from(self.im.atan2(&self.re))
+    }
+    pub fn norm(self) -> Complex {
+        Complex::from(self.re.square() + self.im.square())
+    }
+    pub fn conj(self) -> Complex {
+        Complex {
+            re: self.re,
+            im: -self.im,
         }
     }
-}
-impl Div<Complex> for Integer {
-    type Output = Complex;
-    fn div(self, rhs: Complex) -> Self::Output {
-        match (rhs, self) {
-            (Complex::Rug(a), Integer::Rug(b)) => Complex::Rug(a / b),
-            (Complex::Fastnum(a), Integer::Fastnum(b)) => Complex::Fastnum(a / b),
-            (Complex::F64(a), Integer::F64(b)) => Complex::F64(a / b as f64),
-            (Complex::F32(a), Integer::F32(b)) => Complex::F32(a / b as f32),
-            _ => unreachable!(),
+    pub fn mul_i(self, negative: bool) -> Complex {
+        if negative {
+            Complex {
+                re: self.im,
+                im: -self.re,
+            }
+        } else {
+            Complex {
+                re: -self.im,
+                im: self.re,
+            }
         }
     }
+    pub fn sin_cos(self, _: Complex) -> (Complex, Complex) {
+        (self.clone().sin(), self.cos())
+    }
 }
-
-impl WithVal<(Float, Float)> for Complex {
-    fn with_val(_: Type, prec: u32, val: (Float, Float)) -> Self {
-        match val {
-            (Float::Rug(a), Float::Rug(b)) => Complex::Rug(rug::Complex::with_val(prec, (a, b))),
-            (Float::Fastnum(a), Float::Fastnum(b)) => Complex::Fastnum(CDecimal(a, b)),
-            (Float::F64(a), Float::F64(b)) => Complex::F64(CF64(a, b)),
-            (Float::F32(a), Float::F32(b)) => Complex::F32(CF32(a, b)),
-            _ => unreachable!(),
+macro_rules! unary {
+    ($($f:ident),*) => {
+        impl Complex {
+            $(
+                pub fn $f(self) -> Complex {
+                    each!(self, a => a.$f(), cplx::$f(a))
+                }
+            )*
         }
-    }
+    };
 }
-impl_new_val!(
-    Complex,
-    (Rug, rug::Complex::with_val),
-    (Fastnum, CDecimal::with_val),
-    (F64, |_, x| CF64(x, 0.0)),
-    (F32, |_, x| CF32(x as f32, 0.0))
-);
-
-impl_from_complex_tuple!(f64, i32);
-impl_from_complex_tuple!(i32, f64);
-impl_from_complex_tuple!(i32, Special);
-impl_from_complex_tuple!(Special, Special);
-impl_from_complex_tuple!(Special, i32);
-impl_complex!(Complex, Rug, Fastnum, F64, F32);
-impl_neg!(Complex, Rug, Fastnum, F64, F32);
-impl_self_ops!(
-    Complex,
-    (Rug, |x| x),
-    (Fastnum, |x| x),
-    (F64, |x| x),
-    (F32, |x| x)
+unary!(
+    recip, square, sqrt, exp, ln, log10, sin, cos, tan, sinh, cosh, tanh, asin, acos, atan, asinh,
+    acosh, atanh
 );
@@ -1,177 +1,628 @@
-use super::{NewDeciVal, Parse, Prec, SinhCosh, SpecialValuesDeci};
-use crate::macros::impls::{
-    dec_impl, impl_neg, impl_new_val_deci, impl_partial_ord, impl_rem, impl_self_ops,
-};
+use super::{float::Constant, real::Real};
+use fastnum::decimal::{Context, Sign};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
-use std::fmt::{Display, Formatter};
-use std::ops::{Div, Mul};
-#[derive(Copy, Clone, PartialEq)]
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+type D<const N: usize> = fastnum::decimal::Decimal<N>;
+///fastnum signals overflow, division by zero and invalid operations by panicking,
+///values carry this context so they give inf and NaN instead, the functions below
+///still check their domain since not every fastnum function keeps the context
+const CTX: Context = Context::default().without_traps();
+///past this exp over- or underflows the exponent range of both sizes
+const EXP_LIMIT: f64 = 70000.0;
+const EULER: &str = "0.57721566490153286060651209008240243104215933593992359880576723488486772677766467093694706329174674951463144724980708248096050401448654283622417399764492353625350033374293733773767394279259525824709491600873520394816567085323315177661152862119950150798479374508570574002992135478614669402960432542151905877553526733139925401296742051375413954911";
+const CATALAN: &str = "0.91596559417721901505460351493238411077414937428167213426649811962176301977625476947935651292611510624857442261919619957903589880332585905943159473748115840699533202877331946051903872747816408786590902478384939287185003015395591985574546186138418564768432221829098858216716839037880419680929155658458843097282839788025232155452003349637158004658";
+#[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Decimal {
     D512(fastnum::D512),
     D256(fastnum::D256),
 }
-
-impl Prec for Decimal {
-    fn prec(&self) -> u32 {
-        match self {
-            Self::D512(_) => 512,
-            Self::D256(_) => 256,
+fn nan<const N: usize>() -> D<N> {
+    D::<N>::NAN.with_ctx(CTX)
+}
+fn inf<const N: usize>(negative: bool) -> D<N> {
+    if negative {
+        D::<N>::NEG_INFINITY.with_ctx(CTX)
+    } else {
+        D::<N>::INFINITY.with_ctx(CTX)
+    }
+}
+fn one<const N: usize>() -> D<N> {
+    D::<N>::ONE.with_ctx(CTX)
+}
+fn zero<const N: usize>() -> D<N> {
+    D::<N>::ZERO.with_ctx(CTX)
+}
+fn exp_limit<const N: usize>() -> D<N> {
+    D::<N>::from_f64(EXP_LIMIT).with_ctx(CTX)
+}
+mod checked {
+    use super::*;
+    pub fn add<const N: usize>(a: D<N>, b: D<N>) -> D<N> {
+        if a.is_nan() || b.is_nan() || (a.is_infinite() && b.is_infinite() && a.sign() != b.sign())
+        {
+            return nan();
         }
+        (a + b).with_ctx(CTX)
     }
-    fn set_prec(&mut self, _new_prec: u32) {
-        match self {
-            Self::D512(_) => todo!(),
-            Self::D256(_) => todo!(),
+    pub fn sub<const N: usize>(a: D<N>, b: D<N>) -> D<N> {
+        add(a, -b)
+    }
+    pub fn mul<const N: usize>(a: D<N>, b: D<N>) -> D<N> {
+        if a.is_nan()
+            || b.is_nan()
+            || (a.is_infinite() && b.is_zero())
+            || (a.is_zero() && b.is_infinite())
+        {
+            return nan();
+        }
+        (a * b).with_ctx(CTX)
+    }
+    pub fn div<const N: usize>(a: D<N>, b: D<N>) -> D<N> {
+        if a.is_nan() || b.is_nan() || (a.is_zero() && b.is_zero()) {
+            return nan();
+        }
+        if b.is_zero() {
+            return inf(a.is_sign_negative() != b.is_sign_negative());
+        }
+        if a.is_infinite() && b.is_infinite() {
+            return nan();
+        }
+        (a / b).with_ctx(CTX)
+    }
+    pub fn rem<const N: usize>(a: D<N>, b: D<N>) -> D<N> {
+        //once b is below the spacing of a there is no digit of the remainder left
+        if a.is_nan()
+            || b.is_nan()
+            || b.is_zero()
+            || a.is_infinite()
+            || mul(a.abs(), D::<N>::EPSILON.with_ctx(CTX)) >= b.abs()
+        {
+            return nan();
+        }
+        if b.is_infinite() {
+            return a;
+        }
+        (a % b).with_ctx(CTX)
+    }
+    pub fn sqrt<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() || (a.is_sign_negative() && !a.is_zero()) {
+            nan()
+        } else if a.is_zero() || a.is_infinite() {
+            a
+        } else {
+            root(a, 2, D::sqrt)
+        }
+    }
+    pub fn cbrt<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() || a.is_zero() || a.is_infinite() {
+            a
+        } else if a.is_sign_negative() {
+            -root(-a, 3, D::cbrt)
+        } else {
+            root(a, 3, D::cbrt)
+        }
+    }
+    ///a positive a as m·10^k with 1≤m<10
+    fn split10<const N: usize>(a: D<N>) -> (D<N>, i32) {
+        let n = a.digits_count() as i32;
+        let m = D::<N>::from_parts(a.digits(), 1 - n, Sign::Plus, CTX);
+        (m, n - 1 - a.fractional_digits_count() as i32)
+    }
+    ///the nth root by f of the mantissa alone, fastnum overflows on large exponents
+    fn root<const N: usize>(a: D<N>, n: i32, f: fn(D<N>) -> D<N>) -> D<N> {
+        let (m, k) = split10(a);
+        let r = k.rem_euclid(n);
+        let m = mul(m, D::<N>::from_i32(10i32.pow(r as u32)).with_ctx(CTX));
+        let m = f(m).with_ctx(CTX);
+        let exp = (k - r) / n - m.fractional_digits_count() as i32;
+        D::<N>::from_parts(m.digits(), exp, Sign::Plus, CTX)
+    }
+    pub fn exp<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() {
+            a
+        } else if a > exp_limit() {
+            inf(false)
+        } else if a < -exp_limit::<N>() {
+            zero()
+        } else {
+            a.exp().with_ctx(CTX)
+        }
+    }
+    pub fn ln<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() || (a.is_sign_negative() && !a.is_zero()) {
+            nan()
+        } else if a.is_zero() {
+            inf(true)
+        } else if a.is_infinite() {
+            a
+        } else {
+            //fastnum's series only converges near 1 and its halving runs out of stack for
+            //large exponents, so take the power of ten out first
+            let (m, k) = split10(a);
+            let k = mul(
+                D::<N>::from_i32(k).with_ctx(CTX),
+                D::<N>::LN_10.with_ctx(CTX),
+            );
+            add(m.ln().with_ctx(CTX), k)
+        }
+    }
+    ///fastnum only reduces by its own 2π, which leaves large arguments outside [0,2π),
+    ///so reduce here and give NaN once not a digit of the reduced argument is left
+    pub fn trig<const N: usize>(a: D<N>, f: fn(D<N>) -> D<N>) -> D<N> {
+        let tau = D::<N>::TAU.with_ctx(CTX);
+        if !a.is_finite() || mul(a.abs(), D::<N>::EPSILON.with_ctx(CTX)) >= tau {
+            return nan();
+        }
+        if a.abs() < tau {
+            return f(a).with_ctx(CTX);
+        }
+        let mut r = sub(a, mul(div(a, tau).floor(), tau));
+        if r.is_sign_negative() {
+            r = add(r, tau)
+        }
+        if r >= tau {
+            r = sub(r, tau)
+        }
+        f(r).with_ctx(CTX)
+    }
+    pub fn sinh<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() || a.is_infinite() {
+            a
+        } else if a.abs() > exp_limit() {
+            inf(a.is_sign_negative())
+        } else {
+            a.sinh().with_ctx(CTX)
+        }
+    }
+    pub fn cosh<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() {
+            a
+        } else if a.is_infinite() || a.abs() > exp_limit() {
+            inf(false)
+        } else {
+            a.cosh().with_ctx(CTX)
+        }
+    }
+    pub fn tanh<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() {
+            a
+        } else if a.is_infinite() || a.abs() > exp_limit() {
+            if a.is_sign_negative() {
+                -one::<N>()
+            } else {
+                one()
+            }
+        } else {
+            a.tanh().with_ctx(CTX)
+        }
+    }
+    pub fn asin<const N: usize>(a: D<N>, f: fn(D<N>) -> D<N>) -> D<N> {
+        if a.is_nan() || a.abs() > one() {
+            nan()
+        } else {
+            f(a).with_ctx(CTX)
+        }
+    }
+    pub fn atan<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() {
+            a
+        } else if a.is_infinite() {
+            let half_pi = D::<N>::FRAC_PI_2.with_ctx(CTX);
+            if a.is_sign_negative() {
+                -half_pi
+            } else {
+                half_pi
+            }
+        } else if a.abs() > one() {
+            //fastnum loses large arguments, atan(a)=±π/2-atan(1/a)
+            let half_pi = D::<N>::FRAC_PI_2.with_ctx(CTX);
+            let half_pi = if a.is_sign_negative() {
+                -half_pi
+            } else {
+                half_pi
+            };
+            sub(half_pi, div(one(), a).atan().with_ctx(CTX))
+        } else {
+            a.atan().with_ctx(CTX)
+        }
+    }
+    pub fn asinh<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() || a.is_infinite() {
+            a
+        } else if a.abs() > one() {
+            let r = acosh(sqrt(add(mul(a, a), one())));
+            if a.is_sign_negative() { -r } else { r }
+        } else {
+            a.asinh().with_ctx(CTX)
+        }
+    }
+    ///ln(a+sqrt(a²-1)) through the ln above, fastnum's own does not reduce large arguments
+    pub fn acosh<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() || a < one() {
+            nan()
+        } else if a.is_infinite() {
+            a
+        } else if mul(mul(a, a), D::<N>::EPSILON.with_ctx(CTX)) > one() {
+            add(ln(a), D::<N>::LN_2.with_ctx(CTX))
+        } else {
+            ln(add(a, sqrt(sub(mul(a, a), one()))))
+        }
+    }
+    pub fn atanh<const N: usize>(a: D<N>) -> D<N> {
+        if a.is_nan() || a.abs() > one() {
+            nan()
+        } else if a.abs() == one() {
+            inf(a.is_sign_negative())
+        } else {
+            a.atanh().with_ctx(CTX)
+        }
+    }
+    pub fn atan2<const N: usize>(y: D<N>, x: D<N>) -> D<N> {
+        if y.is_nan() || x.is_nan() {
+            nan()
+        } else if y.is_zero() && x.is_zero() {
+            if x.is_sign_negative() {
+                let pi = D::<N>::PI.with_ctx(CTX);
+                if y.is_sign_negative() { -pi } else { pi }
+            } else {
+                y
+            }
+        } else if x.is_infinite() || y.is_infinite() {
+            D::<N>::from_f64(y.to_f64().atan2(x.to_f64())).with_ctx(CTX)
+        } else {
+            y.atan2(x).with_ctx(CTX)
+        }
+    }
+    pub fn hypot<const N: usize>(a: D<N>, b: D<N>) -> D<N> {
+        if a.is_infinite() || b.is_infinite() {
+            inf(false)
+        } else if a.is_nan() || b.is_nan() {
+            nan()
+        } else if a.is_zero() {
+            b.abs()
+        } else if b.is_zero() {
+            a.abs()
+        } else {
+            let (a, b) = if a.abs() < b.abs() {
+                (b.abs(), a.abs())
+            } else {
+                (a.abs(), b.abs())
+            };
+            let r = div(b, a);
+            mul(a, sqrt(add(one(), mul(r, r))))
+        }
+    }
+    pub fn powf<const N: usize>(a: D<N>, n: D<N>) -> D<N> {
+        if n.is_zero() {
+            return one();
+        }
+        if a.is_nan() || n.is_nan() {
+            return nan();
+        }
+        if a.is_zero() {
+            return if n.is_sign_negative() {
+                inf(false)
+            } else {
+                zero()
+            };
+        }
+        let neg = a.is_sign_negative();
+        if neg && !n.is_integral() {
+            return nan();
+        }
+        let odd = neg && n.is_odd();
+        let t = mul(ln(a.abs()), n);
+        let r = exp(t);
+        if odd { -r } else { r }
+    }
+    pub fn powi<const N: usize>(a: D<N>, n: i32) -> D<N> {
+        powf(a, D::<N>::from_i32(n).with_ctx(CTX))
+    }
+    pub fn round<const N: usize>(a: D<N>, f: fn(D<N>) -> D<N>) -> D<N> {
+        if !a.is_finite() {
+            a
+        } else {
+            f(a).with_ctx(CTX)
         }
     }
 }
-
+macro_rules! unary {
+    ($a:expr, $x:ident => $e:expr) => {
+        match $a {
+            Decimal::D512($x) => Decimal::D512($e),
+            Decimal::D256($x) => Decimal::D256($e),
+        }
+    };
+}
+macro_rules! binary {
+    ($a:expr, $b:expr, $x:ident, $y:ident => $e:expr) => {
+        match $a.pair($b) {
+            (Decimal::D512($x), Decimal::D512($y)) => Decimal::D512($e),
+            (Decimal::D256($x), Decimal::D256($y)) => Decimal::D256($e),
+            _ => unreachable!(),
+        }
+    };
+}
 impl Decimal {
-    pub fn is_zero(&self) -> bool {
-        match self {
-            Self::D512(a) => a.is_zero(),
-            Self::D256(a) => a.is_zero(),
+    ///the size holding the given bits
+    pub fn new(bits: u32, x: f64) -> Decimal {
+        if bits > 256 {
+            Decimal::D512(of(x))
+        } else {
+            Decimal::D256(of(x))
+        }
+    }
+    ///both in the smaller size
+    fn pair(self, other: Decimal) -> (Decimal, Decimal) {
+        match (self, other) {
+            (Decimal::D512(a), Decimal::D256(b)) => {
+                (Decimal::D256(a.transmute().with_ctx(CTX)), Decimal::D256(b))
+            }
+            (Decimal::D256(a), Decimal::D512(b)) => {
+                (Decimal::D256(a), Decimal::D256(b.transmute().with_ctx(CTX)))
+            }
+            p => p,
+        }
+    }
+    pub fn with_bits(self, bits: u32) -> Decimal {
+        match (self, bits > 256) {
+            (Decimal::D256(a), true) => Decimal::D512(a.transmute().with_ctx(CTX)),
+            (Decimal::D512(a), false) => Decimal::D256(a.transmute().with_ctx(CTX)),
+            (a, _) => a,
+        }
+    }
+    pub fn parse(bits: u32, s: &str) -> Option<Decimal> {
+        let s = s.replace('@', "e");
+        let t = s.trim_start_matches(['-', '+']).to_ascii_lowercase();
+        if t == "inf" || t == "infinity" || t == "nan" {
+            let x = if t == "nan" {
+                f64::NAN
+            } else if s.starts_with('-') {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            };
+            return Some(Decimal::new(bits, x));
+        }
+        Some(if bits > 256 {
+            Decimal::D512(fastnum::D512::from_str(&s, CTX).ok()?.with_ctx(CTX))
+        } else {
+            Decimal::D256(fastnum::D256::from_str(&s, CTX).ok()?.with_ctx(CTX))
+        })
+    }
+    pub fn from_i128(bits: u32, n: i128) -> Decimal {
+        if bits > 256 {
+            Decimal::D512(fastnum::D512::from_i128(n).with_ctx(CTX))
+        } else {
+            Decimal::D256(fastnum::D256::from_i128(n).with_ctx(CTX))
         }
     }
-    pub fn to_integer(self) -> fastnum::I512 {
+    pub fn mul_add(self, mul: Decimal, add: Decimal) -> Decimal {
+        self * mul + add
+    }
+}
+fn of<const N: usize>(x: f64) -> D<N> {
+    if x.is_nan() {
+        nan()
+    } else if x.is_infinite() {
+        inf(x < 0.0)
+    } else {
+        D::<N>::from_f64(x).with_ctx(CTX)
+    }
+}
+fn decimal_digits<const N: usize>(a: D<N>) -> (String, i32) {
+    let s = a.abs().reduce().to_scientific_notation();
+    let (mantissa, exp) = s.split_once(['e', 'E']).unwrap_or((&s, "0"));
+    (
+        mantissa.replace('.', ""),
+        exp.parse::<i32>().unwrap_or(0) + 1,
+    )
+}
+impl Real for Decimal {
+    fn bits(self) -> u32 {
         match self {
-            Self::D512(_) => todo!(),
-            Self::D256(_) => todo!(),
+            Decimal::D512(_) => 512,
+            Decimal::D256(_) => 256,
         }
     }
-    pub fn to_f64(&self) -> f64 {
+    fn of(self, x: f64) -> Decimal {
+        unary!(self, _a => of(x))
+    }
+    fn constant(self, c: Constant) -> Decimal {
+        match c {
+            Constant::Pi => unary!(self, _a => D::PI.with_ctx(CTX)),
+            Constant::Log2 => unary!(self, _a => D::LN_2.with_ctx(CTX)),
+            Constant::Euler => Decimal::parse(self.bits(), EULER).unwrap(),
+            Constant::Catalan => Decimal::parse(self.bits(), CATALAN).unwrap(),
+        }
+    }
+    fn to_f64(self) -> f64 {
         match self {
-            Self::D512(_) => todo!(),
-            Self::D256(_) => todo!(),
+            Decimal::D512(a) => a.to_f64(),
+            Decimal::D256(a) => a.to_f64(),
+        }
+    }
+    fn decimal_digits(self) -> Option<(String, i32)> {
+        Some(match self {
+            Decimal::D512(a) => decimal_digits(a),
+            Decimal::D256(a) => decimal_digits(a),
+        })
+    }
+    fn next(self, up: bool) -> Decimal {
+        if !self.is_finite() {
+            return self;
         }
+        let ulp = unary!(self, a => if a.is_zero() {
+            D::MIN_POSITIVE.with_ctx(CTX)
+        } else {
+            checked::mul(a.abs(), D::EPSILON.with_ctx(CTX))
+        });
+        if up { self + ulp } else { self - ulp }
     }
-    pub fn to_string_radix(self, _base: i32, _num_digits: Option<usize>) -> String {
+    fn is_nan(self) -> bool {
         match self {
-            Self::D512(a) => a.to_string(),
-            Self::D256(a) => a.to_string(),
+            Decimal::D512(a) => a.is_nan(),
+            Decimal::D256(a) => a.is_nan(),
         }
     }
-}
-
-impl Mul<fastnum::I512> for Decimal {
-    type Output = Self;
-    fn mul(self, rhs: fastnum::I512) -> Self::Output {
+    fn is_infinite(self) -> bool {
         match self {
-            Decimal::D512(a) => Decimal::D512(
-                a * fastnum::D512::from_str(&rhs.to_string(), fastnum::decimal::Context::default())
-                    .unwrap_or_default(),
-            ),
-            Decimal::D256(a) => Decimal::D256(
-                a * fastnum::D256::from_str(&rhs.to_string(), fastnum::decimal::Context::default())
-                    .unwrap_or_default(),
-            ),
+            Decimal::D512(a) => a.is_infinite(),
+            Decimal::D256(a) => a.is_infinite(),
         }
     }
-}
-
-impl Div<fastnum::I512> for Decimal {
-    type Output = Self;
-    fn div(self, rhs: fastnum::I512) -> Self::Output {
+    fn is_sign_negative(self) -> bool {
         match self {
-            Decimal::D512(a) => Decimal::D512(
-                a / fastnum::D512::from_str(&rhs.to_string(), fastnum::decimal::Context::default())
-                    .unwrap_or_default(),
-            ),
-            Decimal::D256(a) => Decimal::D256(
-                a / fastnum::D256::from_str(&rhs.to_string(), fastnum::decimal::Context::default())
-                    .unwrap_or_default(),
-            ),
-        }
-    }
-}
-
-impl Parse<&str> for Decimal {
-    fn parse(prec: u32, s: &str) -> Option<Self> {
-        match prec.next_power_of_two() {
-            512 => fastnum::D512::from_str(s, fastnum::decimal::Context::default())
-                .ok()
-                .map(Self::D512),
-            256 => fastnum::D256::from_str(s, fastnum::decimal::Context::default())
-                .ok()
-                .map(Self::D256),
-            _ => unreachable!(),
+            Decimal::D512(a) => a.is_sign_negative(),
+            Decimal::D256(a) => a.is_sign_negative(),
         }
     }
-    fn parse_radix(prec: u32, s: &str, _base: i32) -> Option<Self> {
-        match prec.next_power_of_two() {
-            512 => fastnum::D512::from_str(s, fastnum::decimal::Context::default())
-                .ok()
-                .map(Self::D512),
-            256 => fastnum::D256::from_str(s, fastnum::decimal::Context::default())
-                .ok()
-                .map(Self::D256),
-            _ => unreachable!(),
+    fn is_zero(self) -> bool {
+        match self {
+            Decimal::D512(a) => a.is_zero(),
+            Decimal::D256(a) => a.is_zero(),
         }
     }
-}
-
-impl SpecialValuesDeci for Decimal {
-    fn pi(prec: u32) -> Self {
-        match prec.next_power_of_two() {
-            512 => Self::D512(fastnum::D512::PI),
-            256 => Self::D256(fastnum::D256::PI),
-            _ => unreachable!(),
+    fn abs(self) -> Decimal {
+        unary!(self, a => a.abs())
+    }
+    fn floor(self) -> Decimal {
+        unary!(self, a => checked::round(a, D::floor))
+    }
+    fn ceil(self) -> Decimal {
+        unary!(self, a => checked::round(a, D::ceil))
+    }
+    fn round(self) -> Decimal {
+        //fastnum rounds ties to even, rug and f64 away from zero
+        let half = self.of(0.5);
+        if self.is_sign_negative() {
+            -(-self + half).floor()
+        } else {
+            (self + half).floor()
         }
     }
-    fn nan(prec: u32) -> Self {
-        match prec.next_power_of_two() {
-            512 => Self::D512(fastnum::D512::NAN),
-            256 => Self::D256(fastnum::D256::NAN),
-            _ => unreachable!(),
+    fn trunc(self) -> Decimal {
+        if self.is_sign_negative() {
+            self.ceil()
+        } else {
+            self.floor()
         }
     }
-    fn inf(prec: u32) -> Self {
-        match prec.next_power_of_two() {
-            512 => Self::D512(fastnum::D512::INFINITY),
-            256 => Self::D256(fastnum::D256::INFINITY),
+    fn sqrt(self) -> Decimal {
+        unary!(self, a => checked::sqrt(a))
+    }
+    fn cbrt(self) -> Decimal {
+        unary!(self, a => checked::cbrt(a))
+    }
+    fn exp(self) -> Decimal {
+        unary!(self, a => checked::exp(a))
+    }
+    fn ln(self) -> Decimal {
+        unary!(self, a => checked::ln(a))
+    }
+    fn sin(self) -> Decimal {
+        unary!(self, a => checked::trig(a, D::sin))
+    }
+    fn cos(self) -> Decimal {
+        unary!(self, a => checked::trig(a, D::cos))
+    }
+    fn tan(self) -> Decimal {
+        unary!(self, a => checked::trig(a, D::tan))
+    }
+    fn sinh(self) -> Decimal {
+        unary!(self, a => checked::sinh(a))
+    }
+    fn cosh(self) -> Decimal {
+        unary!(self, a => checked::cosh(a))
+    }
+    fn tanh(self) -> Decimal {
+        unary!(self, a => checked::tanh(a))
+    }
+    fn asin(self) -> Decimal {
+        unary!(self, a => checked::asin(a, D::asin))
+    }
+    fn acos(self) -> Decimal {
+        unary!(self, a => checked::asin(a, D::acos))
+    }
+    fn atan(self) -> Decimal {
+        unary!(self, a => checked::atan(a))
+    }
+    fn asinh(self) -> Decimal {
+        unary!(self, a => checked::asinh(a))
+    }
+    fn acosh(self) -> Decimal {
+        unary!(self, a => checked::acosh(a))
+    }
+    fn atanh(self) -> Decimal {
+        unary!(self, a => checked::atanh(a))
+    }
+    fn atan2(self, x: Decimal) -> Decimal {
+        binary!(self, x, a, b => checked::atan2(a, b))
+    }
+    fn hypot(self, other: Decimal) -> Decimal {
+        binary!(self, other, a, b => checked::hypot(a, b))
+    }
+    fn powf(self, n: Decimal) -> Decimal {
+        binary!(self, n, a, b => checked::powf(a, b))
+    }
+    fn powi(self, n: i32) -> Decimal {
+        unary!(self, a => checked::powi(a, n))
+    }
+    fn log10(self) -> Decimal {
+        self.ln() / unary!(self, _a => D::LN_10.with_ctx(CTX))
+    }
+}
+macro_rules! impl_op {
+    ($(($trait:ident, $method:ident)),*) => {
+        $(
+            impl $trait for Decimal {
+                type Output = Decimal;
+                fn $method(self, rhs: Decimal) -> Decimal {
+                    binary!(self, rhs, a, b => checked::$method(a, b))
+                }
+            }
+        )*
+    };
+}
+impl_op!((Add, add), (Sub, sub), (Mul, mul), (Div, div), (Rem, rem));
+impl Neg for Decimal {
+    type Output = Decimal;
+    fn neg(self) -> Decimal {
+        unary!(self, a => -a)
+    }
+}
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        match self.pair(*other) {
+            (Decimal::D512(a), Decimal::D512(b)) => !a.is_nan() && a == b,
+            (Decimal::D256(a), Decimal::D256(b)) => !a.is_nan() && a == b,
             _ => unreachable!(),
         }
     }
 }
-
-impl Display for Decimal {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::D512(a) => a.fmt(f),
-            Self::D256(a) => a.fmt(f),
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        match self.pair(*other) {
+            (Decimal::D512(a), Decimal::D512(b)) => a.partial_cmp(&b),
+            (Decimal::D256(a), Decimal::D256(b)) => a.partial_cmp(&b),
+            _ => unreachable!(),
         }
     }
 }
-impl PartialEq<f64> for Decimal {
-    fn eq(&self, other: &f64) -> bool {
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Decimal::D512(a) => &a.to_f64() == other,
-            Decimal::D256(b) => &b.to_f64() == other,
+            Decimal::D512(a) => Display::fmt(a, f),
+            Decimal::D256(a) => Display::fmt(a, f),
         }
     }
 }
-impl PartialEq<i32> for Decimal {
-    fn eq(&self, other: &i32) -> bool {
-        match self {
-            Decimal::D512(a) => a.to_f64() == *other as f64,
-            Decimal::D256(b) => b.to_f64() == *other as f64,
-        }
-    }
-}
-impl_rem!(Decimal, (D512, |x| x), (D256, |x| x));
-impl_new_val_deci!(Decimal);
-impl_partial_ord!(
-    Decimal,
-    (D512, |x: &fastnum::D512| x.to_f64()),
-    (D256, |x: &fastnum::D256| x.to_f64())
-);
-dec_impl!(Decimal, D512, D256);
-impl_neg!(Decimal, D512, D256);
-impl_self_ops!(
-    Decimal,
-    (D512, |x: &fastnum::D512| *x),
-    (D256, |x: &fastnum::D256| *x)
-);
@@ -1,337 +1,996 @@
+#[cfg(feature = "fastnum")]
+use super::Decimal;
 use super::{
-    Decimal, Integer, NewVal, Parse, ParseU, Prec, SinhCosh, Special, SpecialValues,
-    SpecialValuesDeci, Type, WithVal, WithValDeci,
+    Integer, Precision, Rational, Type, lower,
+    ops::{CompleteRound, Pow},
+    real::{self, Real},
 };
-use crate::macros::impls::{
-    float_impl, impl_neg, impl_new_val, impl_partial_ord, impl_rem, impl_self_ops,
-};
-use rug::ops::CompleteRound;
+use crate::error::Error;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::ops::{Div, Mul};
 use std::{
-    cmp::{Ordering, PartialOrd},
-    fmt::{Display, Formatter},
+    cmp::Ordering,
+    fmt::{self, Display, Formatter, LowerExp},
+    ops::{Add, Div, Mul, Neg, Rem, Shl, Shr, Sub},
 };
-#[derive(Clone, PartialEq)]
+///a real number in one of the backends, operations between two backends are carried out
+///in the less precise one
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Float {
+    #[cfg(feature = "rug")]
     Rug(rug::Float),
+    #[cfg(feature = "fastnum")]
     Fastnum(Decimal),
     F64(f64),
     F32(f32),
 }
-
-impl Prec for Float {
-    fn prec(&self) -> u32 {
-        match self {
-            Self::Rug(a) => a.prec(),
-            Self::Fastnum(a) => a.prec(),
-            Self::F64(_) => 64,
-            Self::F32(_) => 32,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Constant {
+    Log2,
+    Pi,
+    Euler,
+    Catalan,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Special {
+    Zero,
+    NegZero,
+    Infinity,
+    NegInfinity,
+    Nan,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseFloatError;
+impl Display for ParseFloatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid float")
+    }
+}
+impl std::error::Error for ParseFloatError {}
+///a checked number string, completing it parses it in the given backend
+#[derive(Clone, Debug)]
+pub struct ParseIncomplete {
+    src: String,
+    radix: i32,
+}
+impl CompleteRound for ParseIncomplete {
+    type Completed = Float;
+    fn complete<P: super::Prec>(self, prec: P) -> Float {
+        self.to_float(prec.precision())
+    }
+}
+///values a Float can be created from in a given precision
+pub trait ToFloat {
+    fn to_float(self, prec: Precision) -> Float;
+}
+macro_rules! to_float {
+    ($f:ident, $($t:ty),*) => {
+        $(
+            impl ToFloat for $t {
+                fn to_float(self, prec: Precision) -> Float {
+                    Float::$f(prec, self as _)
+                }
+            }
+            impl ToFloat for &$t {
+                fn to_float(self, prec: Precision) -> Float {
+                    Float::$f(prec, *self as _)
+                }
+            }
+        )*
+    };
+}
+to_float!(
+    from_i128, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize
+);
+to_float!(from_f64, f32, f64);
+impl ToFloat for u128 {
+    fn to_float(self, prec: Precision) -> Float {
+        match i128::try_from(self) {
+            Ok(n) => Float::from_i128(prec, n),
+            Err(_) => Float::from_f64(prec, self as f64),
         }
     }
-    fn set_prec(&mut self, new_prec: u32) {
-        match self {
-            Self::Rug(a) => a.set_prec(new_prec),
-            Self::Fastnum(a) => a.set_prec(new_prec),
-            Self::F64(_) => {}
-            Self::F32(_) => {}
+}
+impl ToFloat for &u128 {
+    fn to_float(self, prec: Precision) -> Float {
+        (*self).to_float(prec)
+    }
+}
+impl ToFloat for bool {
+    fn to_float(self, prec: Precision) -> Float {
+        Float::from_i128(prec, self as i128)
+    }
+}
+impl ToFloat for Float {
+    fn to_float(self, prec: Precision) -> Float {
+        self.convert(prec)
+    }
+}
+impl ToFloat for &Float {
+    fn to_float(self, prec: Precision) -> Float {
+        self.clone().convert(prec)
+    }
+}
+impl ToFloat for Integer {
+    fn to_float(self, prec: Precision) -> Float {
+        (&self).to_float(prec)
+    }
+}
+impl ToFloat for &Integer {
+    fn to_float(self, prec: Precision) -> Float {
+        #[cfg(feature = "rug")]
+        if prec.ty == Type::Rug {
+            return Float::Rug(rug::Float::with_val(prec.bits, self.as_rug()));
+        }
+        match self.to_i128() {
+            Some(n) => Float::from_i128(prec, n),
+            None => ParseIncomplete {
+                src: self.to_string(),
+                radix: 10,
+            }
+            .to_float(prec),
         }
     }
 }
-impl Float {
-    pub fn cmp0(&self) -> Option<Ordering> {
-        self.partial_cmp(&Float::new(self.ftype(), self.prec()))
+impl ToFloat for Rational {
+    fn to_float(self, prec: Precision) -> Float {
+        (&self).to_float(prec)
     }
-    pub fn is_zero(&self) -> bool {
-        match self {
-            Self::Rug(a) => a.is_zero(),
-            Self::Fastnum(a) => a.is_zero(),
-            Self::F64(a) => a == &0.0,
-            Self::F32(a) => a == &0.0,
+}
+impl ToFloat for &Rational {
+    fn to_float(self, prec: Precision) -> Float {
+        #[cfg(feature = "rug")]
+        if prec.ty == Type::Rug {
+            return Float::Rug(rug::Float::with_val(prec.bits, self.as_rug()));
         }
+        if !super::fits(self) {
+            return Float::from_f64(prec, f64::NAN);
+        }
+        self.numer().to_float(prec) / self.denom().to_float(prec)
     }
-    pub fn ftype(&self) -> Type {
-        match self {
-            Self::Rug(_) => Type::Rug,
-            Self::Fastnum(_) => Type::Fastnum,
-            Self::F64(_) => Type::F64,
-            Self::F32(_) => Type::F32,
+}
+impl ToFloat for Constant {
+    fn to_float(self, prec: Precision) -> Float {
+        match prec.ty {
+            #[cfg(feature = "rug")]
+            Type::Rug => Float::Rug(rug::Float::with_val(
+                prec.bits,
+                match self {
+                    Constant::Log2 => rug::float::Constant::Log2,
+                    Constant::Pi => rug::float::Constant::Pi,
+                    Constant::Euler => rug::float::Constant::Euler,
+                    Constant::Catalan => rug::float::Constant::Catalan,
+                },
+            )),
+            #[cfg(feature = "fastnum")]
+            Type::Fastnum => Float::Fastnum(Decimal::new(prec.bits, 0.0).constant(self)),
+            Type::F64 => Float::F64(0f64.constant(self)),
+            Type::F32 => Float::F32(0f32.constant(self)),
         }
     }
-    pub fn to_f64(&self) -> f64 {
-        match self {
-            Float::Rug(a) => a.to_f64(),
-            Float::Fastnum(a) => a.to_f64(),
-            Float::F64(a) => *a,
-            Float::F32(a) => *a as f64,
+}
+impl ToFloat for Special {
+    fn to_float(self, prec: Precision) -> Float {
+        Float::from_f64(
+            prec,
+            match self {
+                Special::Zero => 0.0,
+                Special::NegZero => -0.0,
+                Special::Infinity => f64::INFINITY,
+                Special::NegInfinity => f64::NEG_INFINITY,
+                Special::Nan => f64::NAN,
+            },
+        )
+    }
+}
+impl ToFloat for ParseIncomplete {
+    fn to_float(self, prec: Precision) -> Float {
+        let (src, radix) = (self.src.as_str(), self.radix);
+        let n = match prec.ty {
+            #[cfg(feature = "rug")]
+            Type::Rug => None,
+            #[cfg(feature = "fastnum")]
+            Type::Fastnum => if radix == 10 {
+                Decimal::parse(prec.bits, src)
+            } else {
+                None
+            }
+            .or_else(|| real::parse(Decimal::new(prec.bits, 0.0), src, radix))
+            .map(Float::Fastnum),
+            Type::F64 => if radix == 10 {
+                src.replace('@', "e").parse().ok()
+            } else {
+                None
+            }
+            .or_else(|| real::parse(0f64, src, radix))
+            .map(Float::F64),
+            Type::F32 => if radix == 10 {
+                src.replace('@', "e").parse().ok()
+            } else {
+                None
+            }
+            .or_else(|| real::parse(0f32, src, radix))
+            .map(Float::F32),
+        };
+        match n {
+            Some(n) => n,
+            #[cfg(feature = "rug")]
+            None => Float::Rug(rug::Float::with_val(
+                prec.bits.max(64),
+                rug::Float::parse_radix(src, radix).unwrap(),
+            ))
+            .convert(prec),
+            #[cfg(not(feature = "rug"))]
+            None => Float::from_f64(prec, f64::NAN),
         }
     }
-    pub fn to_string_radix(self, base: i32, num_digits: Option<usize>) -> String {
-        match self {
-            Self::Rug(a) => a.to_string_radix(base, num_digits),
-            Self::Fastnum(a) => a.to_string_radix(base, num_digits),
-            Self::F64(a) => a.to_string(),
-            Self::F32(a) => a.to_string(),
-        }
-    }
-}
-impl ParseU<&str> for Float {
-    fn parse(t: Type, prec: u32, s: &str) -> Option<Self> {
-        match t {
-            Type::Rug => rug::Float::parse(s)
-                .ok()
-                .map(|a| Float::Rug(a.complete(prec))),
-            Type::Fastnum => Decimal::parse(prec, s).map(Float::Fastnum),
-            Type::F64 => s.parse().ok().map(Float::F64),
-            Type::F32 => s.parse().ok().map(Float::F32),
-        }
-    }
-    fn parse_radix(t: Type, prec: u32, s: &str, base: i32) -> Option<Self> {
-        match t {
-            Type::Rug => rug::Float::parse_radix(s, base)
-                .ok()
-                .map(|a| Float::Rug(a.complete(prec))),
-            Type::Fastnum => Decimal::parse_radix(prec, s, base).map(Float::Fastnum),
-            Type::F64 => s.parse().ok().map(Float::F64),
-            Type::F32 => s.parse().ok().map(Float::F32),
-        }
-    }
-}
-impl Mul<Integer> for Float {
-    type Output = Self;
-    fn mul(self, rhs: Integer) -> Self::Output {
-        match (self, rhs) {
-            (Float::Rug(a), Integer::Rug(b)) => Float::Rug(a * b),
-            (Float::Fastnum(a), Integer::Fastnum(b)) => Float::Fastnum(a * b),
-            (Float::F64(a), Integer::F64(b)) => Float::F64(a * b as f64),
-            (Float::F32(a), Integer::F32(b)) => Float::F32(a * b as f32),
+}
+///applies a Real function or the rug method of the same name
+macro_rules! each {
+    ($a:expr, $x:ident => $rug:expr, $e:expr) => {
+        match $a {
+            #[cfg(feature = "rug")]
+            Float::Rug($x) => Float::Rug($rug),
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum($x) => Float::Fastnum($e),
+            Float::F64($x) => Float::F64($e),
+            Float::F32($x) => Float::F32($e),
+        }
+    };
+}
+///the same for two operands brought to one backend
+macro_rules! each2 {
+    ($a:expr, $b:expr, $x:ident, $y:ident => $rug:expr, $e:expr) => {
+        match $a.pair($b) {
+            #[cfg(feature = "rug")]
+            (Float::Rug($x), Float::Rug($y)) => Float::Rug($rug),
+            #[cfg(feature = "fastnum")]
+            (Float::Fastnum($x), Float::Fastnum($y)) => Float::Fastnum($e),
+            (Float::F64($x), Float::F64($y)) => Float::F64($e),
+            (Float::F32($x), Float::F32($y)) => Float::F32($e),
             _ => unreachable!(),
         }
+    };
+}
+///a predicate or conversion on any backend
+macro_rules! get {
+    ($a:expr, $x:ident => $rug:expr, $e:expr) => {
+        match $a {
+            #[cfg(feature = "rug")]
+            Float::Rug($x) => $rug,
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum($x) => $e,
+            Float::F64($x) => $e,
+            Float::F32($x) => $e,
+        }
+    };
+}
+macro_rules! impl_ops {
+    ($(($trait:ident, $method:ident, $op:tt)),*) => {
+        $(
+            impl $trait<&Float> for Float {
+                type Output = Float;
+                fn $method(self, rhs: &Float) -> Float {
+                    match (self, rhs) {
+                        #[cfg(feature = "rug")]
+                        (Float::Rug(a), Float::Rug(b)) => Float::Rug(a $op b),
+                        #[cfg(feature = "fastnum")]
+                        (Float::Fastnum(a), Float::Fastnum(b)) => Float::Fastnum(a $op *b),
+                        (Float::F64(a), Float::F64(b)) => Float::F64(a $op b),
+                        (Float::F32(a), Float::F32(b)) => Float::F32(a $op b),
+                        (a, b) => {
+                            let (a, b) = a.pair(b.clone());
+                            a $op &b
+                        }
+                    }
+                }
+            }
+            impl $trait<Float> for Float {
+                type Output = Float;
+                fn $method(self, rhs: Float) -> Float {
+                    self $op &rhs
+                }
+            }
+            impl $trait<&Float> for &Float {
+                type Output = Float;
+                fn $method(self, rhs: &Float) -> Float {
+                    self.clone() $op rhs
+                }
+            }
+            impl $trait<Float> for &Float {
+                type Output = Float;
+                fn $method(self, rhs: Float) -> Float {
+                    self.clone() $op &rhs
+                }
+            }
+            impl $trait<Integer> for Float {
+                type Output = Float;
+                fn $method(self, rhs: Integer) -> Float {
+                    let rhs = rhs.to_float(self.prec());
+                    self $op rhs
+                }
+            }
+            impl $trait<&Integer> for Float {
+                type Output = Float;
+                fn $method(self, rhs: &Integer) -> Float {
+                    let rhs = rhs.to_float(self.prec());
+                    self $op rhs
+                }
+            }
+            impl $trait<Float> for Integer {
+                type Output = Float;
+                fn $method(self, rhs: Float) -> Float {
+                    self.to_float(rhs.prec()) $op rhs
+                }
+            }
+            impl $trait<Float> for &Integer {
+                type Output = Float;
+                fn $method(self, rhs: Float) -> Float {
+                    self.to_float(rhs.prec()) $op rhs
+                }
+            }
+            impl $trait<&Float> for Integer {
+                type Output = Float;
+                fn $method(self, rhs: &Float) -> Float {
+                    self.to_float(rhs.prec()) $op rhs
+                }
+            }
+            impl $trait<&Float> for &Integer {
+                type Output = Float;
+                fn $method(self, rhs: &Float) -> Float {
+                    self.to_float(rhs.prec()) $op rhs
+                }
+            }
+        )*
+    };
+}
+impl_ops!((Add, add, +), (Sub, sub, -), (Mul, mul, *), (Div, div, /), (Rem, rem, %));
+macro_rules! impl_prim_ops {
+    ($($t:ty),*) => {
+        $(
+            impl_prim_ops!(@ops $t, (Add, add, +), (Sub, sub, -), (Mul, mul, *), (Div, div, /), (Rem, rem, %));
+            impl PartialEq<$t> for Float {
+                fn eq(&self, other: &$t) -> bool {
+                    match self {
+                        #[cfg(feature = "rug")]
+                        Float::Rug(a) => a == other,
+                        a => *a == other.to_float(a.prec()),
+                    }
+                }
+            }
+            impl PartialEq<Float> for $t {
+                fn eq(&self, other: &Float) -> bool {
+                    other == self
+                }
+            }
+            impl PartialOrd<$t> for Float {
+                fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                    match self {
+                        #[cfg(feature = "rug")]
+                        Float::Rug(a) => a.partial_cmp(other),
+                        a => a.partial_cmp(&other.to_float(a.prec())),
+                    }
+                }
+            }
+            impl PartialOrd<Float> for $t {
+                fn partial_cmp(&self, other: &Float) -> Option<Ordering> {
+                    other.partial_cmp(self).map(Ordering::reverse)
+                }
+            }
+            impl Pow<$t> for Float {
+                type Output = Float;
+                fn pow(self, rhs: $t) -> Float {
+                    match self {
+                        #[cfg(feature = "rug")]
+                        Float::Rug(a) => Float::Rug(rug::ops::Pow::pow(a, rhs)),
+                        a => {
+                            let rhs = rhs.to_float(a.prec());
+                            a.pow(rhs)
+                        }
+                    }
+                }
+            }
+            impl Pow<$t> for &Float {
+                type Output = Float;
+                fn pow(self, rhs: $t) -> Float {
+                    self.clone().pow(rhs)
+                }
+            }
+        )*
+    };
+    (@ops $t:ty, $(($trait:ident, $method:ident, $op:tt)),*) => {
+        $(
+            impl $trait<$t> for Float {
+                type Output = Float;
+                fn $method(self, rhs: $t) -> Float {
+                    match self {
+                        #[cfg(feature = "rug")]
+                        Float::Rug(a) => Float::Rug(a $op rhs),
+                        a => {
+                            let rhs = rhs.to_float(a.prec());
+                            a $op rhs
+                        }
+                    }
+                }
+            }
+            impl $trait<$t> for &Float {
+                type Output = Float;
+                fn $method(self, rhs: $t) -> Float {
+                    self.clone() $op rhs
+                }
+            }
+            impl $trait<Float> for $t {
+                type Output = Float;
+                fn $method(self, rhs: Float) -> Float {
+                    match rhs {
+                        #[cfg(feature = "rug")]
+                        Float::Rug(b) => Float::Rug(self $op b),
+                        b => self.to_float(b.prec()) $op b,
+                    }
+                }
+            }
+            impl $trait<&Float> for $t {
+                type Output = Float;
+                fn $method(self, rhs: &Float) -> Float {
+                    self $op rhs.clone()
+                }
+            }
+        )*
+    };
+}
+impl_prim_ops!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+macro_rules! impl_assign {
+    ($(($trait:ident, $method:ident, $op:tt, $base:ident)),*) => {
+        $(
+            impl<T> std::ops::$trait<T> for Float
+            where
+                Float: $base<T, Output = Float>,
+            {
+                fn $method(&mut self, rhs: T) {
+                    *self = std::mem::replace(self, Float::F64(0.0)) $op rhs
+                }
+            }
+        )*
+    };
+}
+impl_assign!(
+    (AddAssign, add_assign, +, Add),
+    (SubAssign, sub_assign, -, Sub),
+    (MulAssign, mul_assign, *, Mul),
+    (DivAssign, div_assign, /, Div),
+    (RemAssign, rem_assign, %, Rem)
+);
+impl PartialEq for Float {
+    fn eq(&self, other: &Float) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
     }
 }
-impl Div<Integer> for Float {
-    type Output = Self;
-    fn div(self, rhs: Integer) -> Self::Output {
-        match (self, rhs) {
-            (Float::Rug(a), Integer::Rug(b)) => Float::Rug(a / b),
-            (Float::Fastnum(a), Integer::Fastnum(b)) => Float::Fastnum(a / b),
-            (Float::F64(a), Integer::F64(b)) => Float::F64(a / b as f64),
-            (Float::F32(a), Integer::F32(b)) => Float::F32(a / b as f32),
-            _ => unreachable!(),
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Float) -> Option<Ordering> {
+        match (self, other) {
+            #[cfg(feature = "rug")]
+            (Float::Rug(a), Float::Rug(b)) => a.partial_cmp(b),
+            #[cfg(feature = "fastnum")]
+            (Float::Fastnum(a), Float::Fastnum(b)) => a.partial_cmp(b),
+            (Float::F64(a), Float::F64(b)) => a.partial_cmp(b),
+            (Float::F32(a), Float::F32(b)) => a.partial_cmp(b),
+            (a, b) => {
+                let (a, b) = a.clone().pair(b.clone());
+                a.partial_cmp(&b)
+            }
         }
     }
 }
-impl Mul<Float> for Integer {
+impl PartialEq<Integer> for Float {
+    fn eq(&self, other: &Integer) -> bool {
+        *self == other.to_float(self.prec())
+    }
+}
+impl PartialOrd<Integer> for Float {
+    fn partial_cmp(&self, other: &Integer) -> Option<Ordering> {
+        self.partial_cmp(&other.to_float(self.prec()))
+    }
+}
+impl PartialEq<Float> for Integer {
+    fn eq(&self, other: &Float) -> bool {
+        other == self
+    }
+}
+impl PartialOrd<Float> for Integer {
+    fn partial_cmp(&self, other: &Float) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+impl Pow<Float> for Float {
     type Output = Float;
-    fn mul(self, rhs: Float) -> Self::Output {
-        match (rhs, self) {
-            (Float::Rug(a), Integer::Rug(b)) => Float::Rug(a * b),
-            (Float::Fastnum(a), Integer::Fastnum(b)) => Float::Fastnum(a * b),
-            (Float::F64(a), Integer::F64(b)) => Float::F64(a * b as f64),
-            (Float::F32(a), Integer::F32(b)) => Float::F32(a * b as f32),
-            _ => unreachable!(),
-        }
+    fn pow(self, rhs: Float) -> Float {
+        each2!(self, rhs, a, b => rug::ops::Pow::pow(a, &b), a.powf(b))
     }
 }
-impl Div<Float> for Integer {
+impl Pow<&Float> for Float {
     type Output = Float;
-    fn div(self, rhs: Float) -> Self::Output {
-        match (rhs, self) {
-            (Float::Rug(a), Integer::Rug(b)) => Float::Rug(a / b),
-            (Float::Fastnum(a), Integer::Fastnum(b)) => Float::Fastnum(a / b),
-            (Float::F64(a), Integer::F64(b)) => Float::F64(a / b as f64),
-            (Float::F32(a), Integer::F32(b)) => Float::F32(a / b as f32),
-            _ => unreachable!(),
-        }
+    fn pow(self, rhs: &Float) -> Float {
+        self.pow(rhs.clone())
     }
 }
-impl SpecialValues for Float {
-    fn pi(t: Type, prec: u32) -> Self {
-        match t {
-            Type::Rug => Self::Rug(rug::Float::with_val(prec, rug::float::Constant::Pi)),
-            Type::Fastnum => Self::Fastnum(Decimal::pi(prec)),
-            Type::F64 => Self::F64(f64::pi(prec)),
-            Type::F32 => Self::F32(f32::pi(prec)),
-        }
+impl Pow<Float> for &Float {
+    type Output = Float;
+    fn pow(self, rhs: Float) -> Float {
+        self.clone().pow(rhs)
     }
-    fn nan(t: Type, prec: u32) -> Self {
-        match t {
-            Type::Rug => Self::Rug(rug::Float::with_val(prec, rug::float::Special::Nan)),
-            Type::Fastnum => Self::Fastnum(Decimal::nan(prec)),
-            Type::F64 => Self::F64(f64::nan(prec)),
-            Type::F32 => Self::F32(f32::nan(prec)),
-        }
+}
+impl Pow<&Float> for &Float {
+    type Output = Float;
+    fn pow(self, rhs: &Float) -> Float {
+        self.clone().pow(rhs.clone())
     }
-    fn inf(t: Type, prec: u32) -> Self {
-        match t {
-            Type::Rug => Self::Rug(rug::Float::with_val(prec, rug::float::Special::Infinity)),
-            Type::Fastnum => Self::Fastnum(Decimal::inf(prec)),
-            Type::F64 => Self::F64(f64::inf(prec)),
-            Type::F32 => Self::F32(f32::inf(prec)),
-        }
+}
+impl Pow<Integer> for Float {
+    type Output = Float;
+    fn pow(self, rhs: Integer) -> Float {
+        let rhs = rhs.to_float(self.prec());
+        self.pow(rhs)
     }
 }
-
-impl WithVal<Special> for Float {
-    fn with_val(obj: Type, prec: u32, val: Special) -> Self {
-        match val {
-            Special::Pi => Float::pi(obj, prec),
-            Special::Nan => Float::nan(obj, prec),
-            Special::Infinity => Float::inf(obj, prec),
-        }
+impl Pow<&Integer> for Float {
+    type Output = Float;
+    fn pow(self, rhs: &Integer) -> Float {
+        let rhs = rhs.to_float(self.prec());
+        self.pow(rhs)
+    }
+}
+macro_rules! impl_shift {
+    ($($t:ty),*) => {
+        $(
+            impl Shl<$t> for Float {
+                type Output = Float;
+                fn shl(self, rhs: $t) -> Float {
+                    each!(self, a => a << rhs, a * a.of(2.0).powi(rhs as i32))
+                }
+            }
+            impl Shr<$t> for Float {
+                type Output = Float;
+                fn shr(self, rhs: $t) -> Float {
+                    each!(self, a => a >> rhs, a / a.of(2.0).powi(rhs as i32))
+                }
+            }
+        )*
+    };
+}
+impl_shift!(u32, i32);
+impl Neg for Float {
+    type Output = Float;
+    fn neg(self) -> Float {
+        each!(self, a => -a, -a)
+    }
+}
+impl Neg for &Float {
+    type Output = Float;
+    fn neg(self) -> Float {
+        -self.clone()
+    }
+}
+impl CompleteRound for Float {
+    type Completed = Float;
+    fn complete<P: super::Prec>(self, prec: P) -> Float {
+        self.convert(prec.precision())
     }
 }
-
 impl Display for Float {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => Display::fmt(a, f),
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum(a) => Display::fmt(a, f),
+            Float::F64(a) => Display::fmt(a, f),
+            Float::F32(a) => Display::fmt(a, f),
+        }
+    }
+}
+impl LowerExp for Float {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Rug(a) => a.fmt(f),
-            Self::Fastnum(a) => a.fmt(f),
-            Self::F32(a) => a.fmt(f),
-            Self::F64(a) => a.fmt(f),
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => LowerExp::fmt(a, f),
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum(a) => LowerExp::fmt(&a.to_f64(), f),
+            Float::F64(a) => LowerExp::fmt(a, f),
+            Float::F32(a) => LowerExp::fmt(a, f),
         }
     }
 }
-
 impl Float {
-    pub fn sin_cos(self) -> (Self, Self) {
+    pub fn new<P: super::Prec>(prec: P) -> Float {
+        Float::from_f64(prec.precision(), 0.0)
+    }
+    pub fn with_val<P: super::Prec, T: ToFloat>(prec: P, val: T) -> Float {
+        val.to_float(prec.precision())
+    }
+    pub(crate) fn from_f64(prec: Precision, x: f64) -> Float {
+        match prec.ty {
+            #[cfg(feature = "rug")]
+            Type::Rug => Float::Rug(rug::Float::with_val(prec.bits, x)),
+            #[cfg(feature = "fastnum")]
+            Type::Fastnum => Float::Fastnum(Decimal::new(prec.bits, x)),
+            Type::F64 => Float::F64(x),
+            Type::F32 => Float::F32(x as f32),
+        }
+    }
+    pub(crate) fn from_i128(prec: Precision, n: i128) -> Float {
+        match prec.ty {
+            #[cfg(feature = "rug")]
+            Type::Rug => Float::Rug(rug::Float::with_val(prec.bits, n)),
+            #[cfg(feature = "fastnum")]
+            Type::Fastnum => Float::Fastnum(Decimal::from_i128(prec.bits, n)),
+            Type::F64 => Float::F64(n as f64),
+            Type::F32 => Float::F32(n as f32),
+        }
+    }
+    pub fn parse<S: AsRef<[u8]>>(src: S) -> Result<ParseIncomplete, ParseFloatError> {
+        Float::parse_radix(src, 10)
+    }
+    pub fn parse_radix<S: AsRef<[u8]>>(
+        src: S,
+        radix: i32,
+    ) -> Result<ParseIncomplete, ParseFloatError> {
+        let src = std::str::from_utf8(src.as_ref()).map_err(|_| ParseFloatError)?;
+        #[cfg(feature = "rug")]
+        let valid = rug::Float::parse_radix(src, radix).is_ok();
+        #[cfg(not(feature = "rug"))]
+        let valid = real::parse(0f64, src, radix).is_some();
+        if valid {
+            Ok(ParseIncomplete {
+                src: src.trim().to_string(),
+                radix,
+            })
+        } else {
+            Err(ParseFloatError)
+        }
+    }
+    pub fn ftype(&self) -> Type {
         match self {
-            Self::Rug(a) => {
-                let p = a.prec();
-                let (s, c) = a.sin_cos(rug::Float::new(p));
-                (Self::Rug(s), Self::Rug(c))
-            }
-            Self::Fastnum(a) => {
-                let (s, c) = a.sin_cos();
-                (Self::Fastnum(s), Self::Fastnum(c))
-            }
-            Self::F64(a) => {
-                let (s, c) = a.sin_cos();
-                (Self::F64(s), Self::F64(c))
-            }
-            Self::F32(a) => {
-                let (s, c) = a.sin_cos();
-                (Self::F32(s), Self::F32(c))
-            }
+            #[cfg(feature = "rug")]
+            Float::Rug(_) => Type::Rug,
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum(_) => Type::Fastnum,
+            Float::F64(_) => Type::F64,
+            Float::F32(_) => Type::F32,
         }
     }
-    pub fn sinh_cosh(self) -> (Self, Self) {
+    ///the backend and the bits of this number
+    pub fn prec(&self) -> Precision {
+        Precision::new(self.ftype(), get!(self, a => a.prec(), a.bits()))
+    }
+    ///rounds to the given bits within the same backend
+    #[cfg_attr(
+        not(any(feature = "rug", feature = "fastnum")),
+        allow(unused_variables)
+    )]
+    pub fn set_prec(&mut self, bits: u32) {
         match self {
-            Self::Rug(a) => {
-                let p = a.prec();
-                let (s, c) = a.sinh_cosh(rug::Float::new(p));
-                (Self::Rug(s), Self::Rug(c))
-            }
-            Self::Fastnum(a) => {
-                let (s, c) = a.sinh_cosh();
-                (Self::Fastnum(s), Self::Fastnum(c))
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => a.set_prec(bits),
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum(a) => *a = a.with_bits(bits),
+            Float::F64(_) | Float::F32(_) => {}
+        }
+    }
+    ///converts into the backend and the bits of prec
+    pub(crate) fn convert(self, prec: Precision) -> Float {
+        match (self, prec.ty) {
+            #[cfg(feature = "rug")]
+            (Float::Rug(a), Type::Rug) => {
+                if a.prec() == prec.bits {
+                    Float::Rug(a)
+                } else {
+                    Float::Rug(rug::Float::with_val(prec.bits, a))
+                }
             }
-            Self::F64(a) => {
-                let (s, c) = a.sinh_cosh();
-                (Self::F64(s), Self::F64(c))
+            #[cfg(feature = "fastnum")]
+            (Float::Fastnum(a), Type::Fastnum) => Float::Fastnum(a.with_bits(prec.bits)),
+            #[cfg(all(feature = "rug", feature = "fastnum"))]
+            (Float::Rug(a), Type::Fastnum) => {
+                let digits = prec.bits as usize * 3 / 10 + 2;
+                ParseIncomplete {
+                    src: a.to_string_radix(10, Some(digits)),
+                    radix: 10,
+                }
+                .to_float(prec)
             }
-            Self::F32(a) => {
-                let (s, c) = a.sinh_cosh();
-                (Self::F32(s), Self::F32(c))
+            #[cfg(all(feature = "rug", feature = "fastnum"))]
+            (Float::Fastnum(a), Type::Rug) => Float::Rug(
+                rug::Float::parse(a.to_string())
+                    .map(|a| rug::Float::with_val(prec.bits, a))
+                    .unwrap_or_else(|_| rug::Float::with_val(prec.bits, a.to_f64())),
+            ),
+            (Float::F64(a), _) => Float::from_f64(prec, a),
+            (Float::F32(a), _) => Float::from_f64(prec, a as f64),
+            #[cfg(any(feature = "rug", feature = "fastnum"))]
+            (a, Type::F64) => Float::F64(a.to_f64()),
+            #[cfg(any(feature = "rug", feature = "fastnum"))]
+            (a, Type::F32) => Float::F32(a.to_f32()),
+        }
+    }
+    ///both operands in the less precise of their backends
+    pub(crate) fn pair(self, other: Float) -> (Float, Float) {
+        let (a, b) = (self.ftype(), other.ftype());
+        if a == b {
+            return (self, other);
+        }
+        let ty = lower(a, b);
+        let p = if ty == a { self.prec() } else { other.prec() };
+        (self.convert(p), other.convert(p))
+    }
+    pub fn to_f64(&self) -> f64 {
+        get!(self, a => a.to_f64(), Real::to_f64(*a))
+    }
+    pub fn to_f32(&self) -> f32 {
+        match self {
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => a.to_f32(),
+            Float::F32(a) => *a,
+            a => a.to_f64() as f32,
+        }
+    }
+    pub fn to_integer(&self) -> Option<Integer> {
+        match self {
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => a.to_integer().map(Integer::from),
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum(a) if a.is_finite() => Float::Fastnum(Real::round(*a))
+                .to_rational()
+                .map(|q| q.numer().clone()),
+            a if a.is_finite() => Integer::from_f64(a.to_f64().round_ties_even()),
+            _ => None,
+        }
+    }
+    pub fn to_rational(&self) -> Option<Rational> {
+        match self {
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => a.to_rational().map(Rational::from),
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum(a) if a.is_finite() => {
+                let (neg, s, exp) = self.to_sign_string_exp(10, None);
+                digits_rational(neg, &s, exp.unwrap_or(0) - s.len() as i32)
             }
+            a => Rational::from_f64(a.to_f64()),
         }
     }
-    pub fn atan2(self, other: Self) -> Self {
-        match (self, other) {
-            (Self::Rug(a), Self::Rug(b)) => Self::Rug(a.atan2(&b)),
-            (Self::Fastnum(a), Self::Fastnum(b)) => Self::Fastnum(a.atan2(b)),
-            (Self::F64(a), Self::F64(b)) => Self::F64(a.atan2(b)),
-            (Self::F32(a), Self::F32(b)) => Self::F32(a.atan2(b)),
-            _ => unreachable!(),
+    pub fn get_exp(&self) -> Option<i32> {
+        match self {
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => a.get_exp(),
+            a if a.is_zero() || !a.is_finite() => None,
+            a => Some(a.clone().abs().log2().floor().to_f64() as i32 + 1),
         }
     }
-    pub fn hypot(self, other: Self) -> Self {
+    pub fn is_zero(&self) -> bool {
+        get!(self, a => a.is_zero(), a.is_zero())
+    }
+    pub fn is_nan(&self) -> bool {
+        get!(self, a => a.is_nan(), Real::is_nan(*a))
+    }
+    pub fn is_infinite(&self) -> bool {
+        get!(self, a => a.is_infinite(), Real::is_infinite(*a))
+    }
+    pub fn is_finite(&self) -> bool {
+        get!(self, a => a.is_finite(), Real::is_finite(*a))
+    }
+    pub fn is_normal(&self) -> bool {
+        get!(self, a => a.is_normal(), Real::is_finite(*a) && !a.is_zero())
+    }
+    pub fn is_integer(&self) -> bool {
+        get!(self, a => a.is_integer(), Real::is_integer(*a))
+    }
+    pub fn is_sign_positive(&self) -> bool {
+        !self.is_sign_negative()
+    }
+    ///the sign bit of a NaN is whatever the hardware left there, so NaN counts as positive
+    pub fn is_sign_negative(&self) -> bool {
+        get!(self, a => a.is_sign_negative(), Real::is_sign_negative(*a) && !Real::is_nan(*a))
+    }
+    pub fn cmp0(&self) -> Option<Ordering> {
+        get!(self, a => a.cmp0(), a.partial_cmp(&a.of(0.0)))
+    }
+    ///orders NaN above everything like f64::total_cmp
+    pub fn total_cmp(&self, other: &Float) -> Ordering {
         match (self, other) {
-            (Self::Rug(a), Self::Rug(b)) => Self::Rug(a.hypot(&b)),
-            (Self::Fastnum(a), Self::Fastnum(b)) => Self::Fastnum(a.hypot(b)),
-            (Self::F64(a), Self::F64(b)) => Self::F64(a.hypot(b)),
-            (Self::F32(a), Self::F32(b)) => Self::F32(a.hypot(b)),
-            _ => unreachable!(),
+            #[cfg(feature = "rug")]
+            (Float::Rug(a), Float::Rug(b)) => a.total_cmp(b),
+            (Float::F64(a), Float::F64(b)) => a.total_cmp(b),
+            (Float::F32(a), Float::F32(b)) => a.total_cmp(b),
+            (a, b) => match (a.is_nan(), b.is_nan()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                _ => a.partial_cmp(b).unwrap(),
+            },
         }
     }
-    pub fn to_integer(self) -> Integer {
+    pub fn next_up(&mut self) {
         match self {
-            Float::Rug(a) => Integer::Rug(a.to_integer().unwrap()),
-            Float::Fastnum(a) => Integer::Fastnum(a.to_integer()),
-            Float::F64(a) => Integer::F64(a as i128),
-            Float::F32(a) => Integer::F32(a as i128),
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => a.next_up(),
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum(a) => *a = a.next(true),
+            Float::F64(a) => *a = a.next(true),
+            Float::F32(a) => *a = a.next(true),
         }
     }
-    pub fn gamma(self) -> Self {
+    pub fn next_down(&mut self) {
         match self {
-            Float::Rug(a) => Self::Rug(a.gamma()),
-            Float::Fastnum(a) => Self::Fastnum(a),
-            Float::F64(a) => Self::F64(a),
-            Float::F32(a) => Self::F32(a),
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => a.next_down(),
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum(a) => *a = a.next(false),
+            Float::F64(a) => *a = a.next(false),
+            Float::F32(a) => *a = a.next(false),
         }
     }
-}
-
-impl PartialEq<f64> for Float {
-    fn eq(&self, other: &f64) -> bool {
+    pub fn next_toward(&mut self, to: &Float) {
+        match PartialOrd::partial_cmp(&*self, to) {
+            Some(Ordering::Less) => self.next_up(),
+            Some(Ordering::Greater) => self.next_down(),
+            _ => {}
+        }
+    }
+    pub fn max(self, other: &Float) -> Float {
+        each2!(self, other.clone(), a, b => a.max(&b), Real::max(a, b))
+    }
+    pub fn min(self, other: &Float) -> Float {
+        each2!(self, other.clone(), a, b => a.min(&b), Real::min(a, b))
+    }
+    pub fn clamp(self, min: &Float, max: &Float) -> Float {
+        self.max(min).min(max)
+    }
+    pub fn abs_ref(&self) -> Float {
+        self.clone().abs()
+    }
+    pub fn square_ref(&self) -> Float {
+        self.clone().square()
+    }
+    pub fn to_u32_saturating(&self) -> Option<u32> {
         match self {
-            Float::Rug(a) => a == other,
-            Float::Fastnum(a) => a == other,
-            Float::F64(a) => a == other,
-            Float::F32(a) => *a == *other as f32,
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => a.to_u32_saturating(),
+            a if a.is_nan() => None,
+            a => Some(a.to_f64().round() as u32),
         }
     }
-}
-impl PartialEq<i32> for Float {
-    fn eq(&self, other: &i32) -> bool {
+    pub fn to_i32_saturating(&self) -> Option<i32> {
         match self {
-            Float::Rug(a) => a == other,
-            Float::Fastnum(a) => a == other,
-            Float::F64(a) => *a == *other as f64,
-            Float::F32(a) => *a == *other as f32,
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => a.to_i32_saturating(),
+            a if a.is_nan() => None,
+            a => Some(a.to_f64().round() as i32),
         }
     }
-}
-impl PartialOrd<i32> for Float {
-    fn partial_cmp(&self, other: &i32) -> Option<Ordering> {
+    pub fn atan2(self, x: &Float) -> Float {
+        each2!(self, x.clone(), a, b => a.atan2(&b), a.atan2(b))
+    }
+    pub fn hypot(self, other: &Float) -> Float {
+        each2!(self, other.clone(), a, b => a.hypot(&b), a.hypot(b))
+    }
+    pub fn sin_cos(self, _: Float) -> (Float, Float) {
         match self {
-            Float::Rug(a) => a.partial_cmp(other),
-            Float::Fastnum(a) => a.to_integer().partial_cmp(&fastnum::I512::from(*other)),
-            Float::F64(a) => a.partial_cmp(&(*other as f64)),
-            Float::F32(a) => a.partial_cmp(&(*other as f32)),
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => {
+                let p = a.prec();
+                let (s, c) = a.sin_cos(rug::Float::new(p));
+                (Float::Rug(s), Float::Rug(c))
+            }
+            a => (a.clone().sin(), a.cos()),
+        }
+    }
+    pub fn mul_add(self, mul: &Float, add: &Float) -> Float {
+        match (self, mul, add) {
+            #[cfg(feature = "rug")]
+            (Float::Rug(a), Float::Rug(b), Float::Rug(c)) => Float::Rug(a.mul_add(b, c)),
+            (Float::F64(a), Float::F64(b), Float::F64(c)) => Float::F64(a.mul_add(*b, *c)),
+            (a, b, c) => a * b + c,
+        }
+    }
+    pub fn square(self) -> Float {
+        each!(self, a => a.square(), a * a)
+    }
+    pub fn ln(self) -> Float {
+        each!(self, a => a.ln(), Real::ln(a))
+    }
+    pub fn fract(self) -> Float {
+        each!(self, a => a.fract(), Real::fract(a))
+    }
+    ///the real gamma function, the decimal backend has none and leaves it to the caller
+    pub fn gamma(self) -> Result<Float, Error> {
+        match self {
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => Ok(Float::Rug(a.gamma())),
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum(_) => Err(Error::UnsupportedBackend("requires rug", None)),
+            Float::F64(a) => Ok(Float::F64(Real::gamma(a))),
+            Float::F32(a) => Ok(Float::F32(Real::gamma(a))),
+        }
+    }
+    pub fn to_sign_string_exp(
+        &self,
+        radix: i32,
+        digits: Option<usize>,
+    ) -> (bool, String, Option<i32>) {
+        match self {
+            #[cfg(feature = "rug")]
+            Float::Rug(a) => a.to_sign_string_exp(radix, digits),
+            #[cfg(feature = "fastnum")]
+            Float::Fastnum(a) => real::to_sign_string_exp(*a, radix, digits),
+            Float::F64(a) => real::to_sign_string_exp(*a, radix, digits),
+            Float::F32(a) => real::to_sign_string_exp(*a, radix, digits),
+        }
+    }
+    pub fn to_string_radix(&self, radix: i32, digits: Option<usize>) -> String {
+        #[cfg(feature = "rug")]
+        if let Float::Rug(a) = self {
+            return a.to_string_radix(radix, digits);
+        }
+        let (neg, s, exp) = self.to_sign_string_exp(radix, digits);
+        let sign = if neg { "-" } else { "" };
+        match exp {
+            None => format!("{sign}{s}"),
+            Some(exp) => {
+                let (a, b) = s.split_at(1);
+                let marker = if radix <= 10 { 'e' } else { '@' };
+                if exp == 1 {
+                    format!("{sign}{a}.{b}")
+                } else {
+                    format!("{sign}{a}.{b}{marker}{}", exp - 1)
+                }
+            }
         }
     }
 }
-
-impl_new_val!(
-    Float,
-    (Rug, rug::Float::with_val),
-    (Fastnum, Decimal::with_val),
-    (F64, |_, x| x),
-    (F32, |_, x| x as f32)
-);
-impl_rem!(
-    Float,
-    (Rug, |x| x),
-    (Fastnum, |x| x),
-    (F64, |x| x as f64),
-    (F32, |x| x as f32)
-);
-float_impl!(Float, Rug, Fastnum, F64, F32);
-impl_partial_ord!(
-    Float,
-    (Rug, |x: &rug::Float| x.to_f64()),
-    (Fastnum, |x: &Decimal| x.to_f64()),
-    (F64, |x: &f64| *x),
-    (F32, |x: &f32| *x as f64)
-);
-impl_neg!(Float, Rug, Fastnum, F64, F32);
-impl_self_ops!(
-    Float,
-    (Rug, |x| x),
-    (Fastnum, |x| x),
-    (F64, |x| x),
-    (F32, |x| x)
+///the rational sign·digits·10^exp
+#[cfg(feature = "fastnum")]
+fn digits_rational(neg: bool, digits: &str, exp: i32) -> Option<Rational> {
+    let n = Integer::from_str_radix(digits, 10).ok()?;
+    let scale = Integer::from(10).pow(exp.unsigned_abs());
+    let q = if exp < 0 {
+        Rational::from((n, scale))
+    } else {
+        Rational::from(n * scale)
+    };
+    let q = if neg { -q } else { q };
+    super::fits(&q).then_some(q)
+}
+macro_rules! unary {
+    ($($f:ident),*) => {
+        impl Float {
+            $(
+                pub fn $f(self) -> Float {
+                    each!(self, a => a.$f(), Real::$f(a))
+                }
+            )*
+        }
+    };
+}
+unary!(
+    abs, floor, ceil, round, trunc, recip, sqrt, cbrt, log2, log10, exp, exp2, exp10, sin, cos,
+    tan, sinh, cosh, tanh, asin, acos, atan, asinh, acosh, atanh, signum
 );
+///functions only mpfr has, the other backends return an error
+macro_rules! rug_only {
+    ($($f:ident),*) => {
+        impl Float {
+            $(
+                pub fn $f(self) -> Result<Float, Error> {
+                    match self {
+                        #[cfg(feature = "rug")]
+                        Float::Rug(a) => Ok(Float::Rug(a.$f())),
+                        _ => Err(Error::UnsupportedBackend("requires rug", None)),
+                    }
+                }
+            )*
+        }
+    };
+}
+rug_only!(erf, erfc, ai, zeta, digamma);
@@ -1,129 +1,588 @@
-use super::Type;
-use crate::macros::impls::{impl_int_ops, impl_pow, impl_self_ops};
-use crate::types::Pow;
-use rug::ops::Pow as RugPow;
+use super::ops::Pow;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter};
-use std::ops::{Add, AddAssign};
-#[derive(Clone, PartialEq, PartialOrd)]
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    iter::Sum,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+///an exact integer, gmp backed with rug, without it an i128 where results that do not
+///fit become OVERFLOW which every later operation keeps and which converts to NaN,
+///so nothing wraps around silently
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Integer {
+    #[cfg(feature = "rug")]
     Rug(rug::Integer),
-    Fastnum(fastnum::I512),
-    F64(i128),
-    F32(i128),
+    #[cfg(not(feature = "rug"))]
+    I128(i128),
+}
+#[cfg(not(feature = "rug"))]
+pub(crate) const OVERFLOW: i128 = i128::MIN;
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IsPrime {
+    No,
+    Probably,
+    Yes,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseIntegerError;
+impl Display for ParseIntegerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid integer")
+    }
+}
+impl std::error::Error for ParseIntegerError {}
+impl Default for Integer {
+    fn default() -> Integer {
+        Integer::new()
+    }
+}
+pub trait ToInteger {
+    fn to_integer(self) -> Integer;
+}
+macro_rules! to_integer {
+    ($($t:ty),*) => {
+        $(
+            impl ToInteger for $t {
+                fn to_integer(self) -> Integer {
+                    #[cfg(feature = "rug")]
+                    return Integer::Rug(rug::Integer::from(self));
+                    #[cfg(not(feature = "rug"))]
+                    Integer::checked(i128::try_from(self).ok())
+                }
+            }
+            impl ToInteger for &$t {
+                fn to_integer(self) -> Integer {
+                    (*self).to_integer()
+                }
+            }
+            impl From<$t> for Integer {
+                fn from(value: $t) -> Integer {
+                    value.to_integer()
+                }
+            }
+        )*
+    };
+}
+to_integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+impl ToInteger for Integer {
+    fn to_integer(self) -> Integer {
+        self
+    }
+}
+impl ToInteger for &Integer {
+    fn to_integer(self) -> Integer {
+        self.clone()
+    }
+}
+impl From<&Integer> for Integer {
+    fn from(value: &Integer) -> Integer {
+        value.clone()
+    }
+}
+#[cfg(feature = "rug")]
+impl From<rug::Integer> for Integer {
+    fn from(value: rug::Integer) -> Integer {
+        Integer::Rug(value)
+    }
+}
+///applies the rug expression or the checked i128 one
+macro_rules! map {
+    ($a:expr, $x:ident => $rug:expr, $i:expr) => {
+        match $a {
+            #[cfg(feature = "rug")]
+            Integer::Rug($x) => Integer::Rug($rug),
+            #[cfg(not(feature = "rug"))]
+            Integer::I128($x) => Integer::checked(($x != OVERFLOW).then_some($x).and_then(|$x| $i)),
+        }
+    };
+}
+macro_rules! map2 {
+    ($a:expr, $b:expr, $x:ident, $y:ident => $rug:expr, $i:expr) => {
+        match ($a, $b) {
+            #[cfg(feature = "rug")]
+            (Integer::Rug($x), Integer::Rug($y)) => Integer::Rug($rug),
+            #[cfg(not(feature = "rug"))]
+            (Integer::I128($x), Integer::I128($y)) => Integer::checked(
+                ($x != OVERFLOW && $y != OVERFLOW)
+                    .then_some(($x, $y))
+                    .and_then(|($x, $y)| $i),
+            ),
+        }
+    };
+}
+macro_rules! impl_ops {
+    ($(($trait:ident, $method:ident, $checked:ident, $op:tt)),*) => {
+        $(
+            impl<T: ToInteger> $trait<T> for Integer {
+                type Output = Integer;
+                fn $method(self, rhs: T) -> Integer {
+                    map2!(self, rhs.to_integer(), a, b => a $op b, a.$checked(b))
+                }
+            }
+            impl<T: ToInteger> $trait<T> for &Integer {
+                type Output = Integer;
+                fn $method(self, rhs: T) -> Integer {
+                    self.clone() $op rhs
+                }
+            }
+        )*
+    };
+}
+impl_ops!(
+    (Add, add, checked_add, +),
+    (Sub, sub, checked_sub, -),
+    (Mul, mul, checked_mul, *),
+    (Div, div, checked_div, /),
+    (Rem, rem, checked_rem, %)
+);
+macro_rules! impl_assign {
+    ($(($trait:ident, $method:ident, $op:tt)),*) => {
+        $(
+            impl<T: ToInteger> std::ops::$trait<T> for Integer {
+                fn $method(&mut self, rhs: T) {
+                    *self = std::mem::take(self) $op rhs
+                }
+            }
+        )*
+    };
+}
+impl_assign!(
+    (AddAssign, add_assign, +),
+    (SubAssign, sub_assign, -),
+    (MulAssign, mul_assign, *),
+    (DivAssign, div_assign, /),
+    (RemAssign, rem_assign, %)
+);
+macro_rules! impl_prim {
+    ($($t:ty),*) => {
+        $(
+            impl Add<Integer> for $t {
+                type Output = Integer;
+                fn add(self, rhs: Integer) -> Integer {
+                    self.to_integer() + rhs
+                }
+            }
+            impl Sub<Integer> for $t {
+                type Output = Integer;
+                fn sub(self, rhs: Integer) -> Integer {
+                    self.to_integer() - rhs
+                }
+            }
+            impl Mul<Integer> for $t {
+                type Output = Integer;
+                fn mul(self, rhs: Integer) -> Integer {
+                    self.to_integer() * rhs
+                }
+            }
+            impl Div<Integer> for $t {
+                type Output = Integer;
+                fn div(self, rhs: Integer) -> Integer {
+                    self.to_integer() / rhs
+                }
+            }
+            impl PartialEq<$t> for Integer {
+                fn eq(&self, other: &$t) -> bool {
+                    *self == other.to_integer()
+                }
+            }
+            impl PartialEq<Integer> for $t {
+                fn eq(&self, other: &Integer) -> bool {
+                    self.to_integer() == *other
+                }
+            }
+            impl PartialOrd<$t> for Integer {
+                fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                    self.partial_cmp(&other.to_integer())
+                }
+            }
+            impl PartialOrd<Integer> for $t {
+                fn partial_cmp(&self, other: &Integer) -> Option<Ordering> {
+                    self.to_integer().partial_cmp(other)
+                }
+            }
+        )*
+    };
+}
+impl_prim!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+impl Neg for Integer {
+    type Output = Integer;
+    fn neg(self) -> Integer {
+        map!(self, a => -a, a.checked_neg())
+    }
+}
+impl Neg for &Integer {
+    type Output = Integer;
+    fn neg(self) -> Integer {
+        -self.clone()
+    }
+}
+impl Pow<u32> for Integer {
+    type Output = Integer;
+    fn pow(self, rhs: u32) -> Integer {
+        map!(self, a => rug::ops::Pow::pow(a, rhs), a.checked_pow(rhs))
+    }
+}
+impl Pow<u32> for &Integer {
+    type Output = Integer;
+    fn pow(self, rhs: u32) -> Integer {
+        self.clone().pow(rhs)
+    }
+}
+impl PartialEq<f64> for Integer {
+    fn eq(&self, other: &f64) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+impl PartialOrd<f64> for Integer {
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+        match self {
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => a.partial_cmp(other),
+            #[cfg(not(feature = "rug"))]
+            a => a.to_f64().partial_cmp(other),
+        }
+    }
+}
+impl Sum for Integer {
+    fn sum<I: Iterator<Item = Integer>>(iter: I) -> Integer {
+        iter.fold(Integer::new(), |a, b| a + b)
+    }
+}
+impl<'a> Sum<&'a Integer> for Integer {
+    fn sum<I: Iterator<Item = &'a Integer>>(iter: I) -> Integer {
+        iter.fold(Integer::new(), |a, b| a + b)
+    }
 }
-
-#[derive(Clone, PartialEq, PartialOrd)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Int<T: Add + AddAssign>(T);
-
 impl Display for Integer {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Rug(a) => a.fmt(f),
-            Self::Fastnum(a) => a.fmt(f),
-            Self::F64(a) => a.fmt(f),
-            Self::F32(a) => a.fmt(f),
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => Display::fmt(a, f),
+            #[cfg(not(feature = "rug"))]
+            a => match a.get() {
+                Some(n) => Display::fmt(&n, f),
+                None => f.write_str("NaN"),
+            },
         }
     }
 }
-
+#[cfg(not(feature = "rug"))]
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+#[cfg(not(feature = "rug"))]
+fn isqrt(n: i128) -> i128 {
+    if n < 2 {
+        return n.max(0);
+    }
+    let mut x = (n as f64).sqrt() as i128;
+    while x.checked_mul(x).is_none_or(|s| s > n) {
+        x -= 1;
+    }
+    while (x + 1).checked_mul(x + 1).is_some_and(|s| s <= n) {
+        x += 1;
+    }
+    x
+}
+///a primitive conversion on either backend
+macro_rules! to_prim {
+    ($($f:ident, $t:ty),*) => {
+        impl Integer {
+            $(
+                pub fn $f(&self) -> Option<$t> {
+                    match self {
+                        #[cfg(feature = "rug")]
+                        Integer::Rug(a) => a.$f(),
+                        #[cfg(not(feature = "rug"))]
+                        a => a.get()?.try_into().ok(),
+                    }
+                }
+            )*
+        }
+    };
+}
+to_prim!(
+    to_i32, i32, to_i64, i64, to_u32, u32, to_u64, u64, to_isize, isize, to_usize, usize, to_i128,
+    i128, to_u128, u128
+);
 impl Integer {
-    pub fn is_probably_prime(&self, reps: u32) -> bool {
+    pub fn new() -> Integer {
+        #[cfg(feature = "rug")]
+        return Integer::Rug(rug::Integer::new());
+        #[cfg(not(feature = "rug"))]
+        Integer::I128(0)
+    }
+    #[cfg(not(feature = "rug"))]
+    pub(crate) fn checked(n: Option<i128>) -> Integer {
+        Integer::I128(n.unwrap_or(OVERFLOW))
+    }
+    #[cfg(not(feature = "rug"))]
+    pub(crate) fn get(&self) -> Option<i128> {
+        let Integer::I128(n) = self;
+        (*n != OVERFLOW).then_some(*n)
+    }
+    #[cfg(feature = "rug")]
+    pub(crate) fn as_rug(&self) -> &rug::Integer {
+        let Integer::Rug(n) = self;
+        n
+    }
+    ///the integer value of a finite integral f64
+    pub(crate) fn from_f64(value: f64) -> Option<Integer> {
+        #[cfg(feature = "rug")]
+        return rug::Integer::from_f64(value).map(Integer::Rug);
+        #[cfg(not(feature = "rug"))]
+        (value.is_finite() && value.abs() < i128::MAX as f64)
+            .then_some(Integer::I128(value as i128))
+    }
+    pub fn from_str_radix(src: &str, radix: i32) -> Result<Integer, ParseIntegerError> {
+        #[cfg(feature = "rug")]
+        return rug::Integer::from_str_radix(src, radix)
+            .map(Integer::Rug)
+            .map_err(|_| ParseIntegerError);
+        #[cfg(not(feature = "rug"))]
+        i128::from_str_radix(src, radix as u32)
+            .ok()
+            .filter(|n| *n != OVERFLOW)
+            .map(Integer::I128)
+            .ok_or(ParseIntegerError)
+    }
+    pub fn to_string_radix(&self, radix: i32) -> String {
         match self {
-            Self::Rug(a) => a.is_probably_prime(reps) != rug::integer::IsPrime::No,
-            Self::Fastnum(_) => false,
-            Self::F64(_) => false,
-            Self::F32(_) => false,
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => a.to_string_radix(radix),
+            #[cfg(not(feature = "rug"))]
+            a => {
+                let Some(n) = a.get() else {
+                    return "NaN".to_string();
+                };
+                let mut u = n.unsigned_abs();
+                if u == 0 {
+                    return "0".to_string();
+                }
+                let mut s = Vec::new();
+                while u != 0 {
+                    s.push(
+                        std::char::from_digit((u % radix as u128) as u32, radix as u32).unwrap(),
+                    );
+                    u /= radix as u128;
+                }
+                if n < 0 {
+                    s.push('-');
+                }
+                s.into_iter().rev().collect()
+            }
         }
     }
-    pub fn next_prime(self) -> Self {
+    pub fn to_f64(&self) -> f64 {
         match self {
-            Self::Rug(a) => Self::Rug(a.next_prime()),
-            Self::Fastnum(a) => Self::Fastnum(a),
-            Self::F64(a) => Self::F64(a),
-            Self::F32(a) => Self::F32(a),
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => a.to_f64(),
+            #[cfg(not(feature = "rug"))]
+            a => a.get().map_or(f64::NAN, |n| n as f64),
         }
     }
-    pub fn from(obj: Type, val: u32) -> Self {
-        match obj {
-            Type::Rug => Self::Rug(rug::Integer::from(val)),
-            Type::Fastnum => Self::Fastnum(fastnum::I512::from(val)),
-            Type::F64 => Self::F64(val as i128),
-            Type::F32 => Self::F32(val as i128),
+    ///false once an i128 overflowed
+    pub fn is_finite(&self) -> bool {
+        match self {
+            #[cfg(feature = "rug")]
+            Integer::Rug(_) => true,
+            #[cfg(not(feature = "rug"))]
+            Integer::I128(n) => *n != OVERFLOW,
         }
     }
-    pub fn new(obj: Type) -> Self {
-        match obj {
-            Type::Rug => Self::Rug(rug::Integer::new()),
-            Type::Fastnum => Self::Fastnum(fastnum::I512::from(0)),
-            Type::F64 => Self::F64(0),
-            Type::F32 => Self::F32(0),
+    pub fn is_zero(&self) -> bool {
+        self.cmp0() == Ordering::Equal
+    }
+    pub fn is_even(&self) -> bool {
+        match self {
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => a.is_even(),
+            #[cfg(not(feature = "rug"))]
+            Integer::I128(n) => n % 2 == 0,
         }
     }
-    pub fn to_i128(self) -> i128 {
+    pub fn is_odd(&self) -> bool {
+        !self.is_even()
+    }
+    pub fn cmp0(&self) -> Ordering {
         match self {
-            Self::Rug(a) => a.to_i128().unwrap_or_default(),
-            Self::Fastnum(a) => a.to_string().parse().unwrap_or_default(),
-            Self::F64(a) => a,
-            Self::F32(a) => a,
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => a.cmp0(),
+            #[cfg(not(feature = "rug"))]
+            Integer::I128(n) => n.cmp(&0),
         }
     }
-    pub fn to_string_radix(self, base: i32) -> String {
+    pub fn get_bit(&self, index: u32) -> bool {
         match self {
-            Self::Rug(a) => a.to_string_radix(base),
-            Self::Fastnum(a) => a.to_str_radix(base as u32),
-            Self::F64(a) => a.to_string(),
-            Self::F32(a) => a.to_string(),
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => a.get_bit(index),
+            #[cfg(not(feature = "rug"))]
+            Integer::I128(n) => index < 128 && (n >> index) & 1 == 1,
         }
     }
-    pub fn binomial(self, k: u32) -> Self {
+    pub fn mod_u(&self, modulo: u32) -> u32 {
         match self {
-            Self::Rug(a) => Self::Rug(a.binomial(k)),
-            Self::Fastnum(a) => Self::Fastnum(a),
-            Self::F64(a) => Self::F64(a),
-            Self::F32(a) => Self::F32(a),
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => a.mod_u(modulo),
+            #[cfg(not(feature = "rug"))]
+            Integer::I128(n) => n.rem_euclid(modulo as i128) as u32,
         }
     }
-    pub fn div_rem(self, d: Self) -> (Self, Self) {
-        match (self, d) {
-            (Self::Rug(a), Self::Rug(d)) => {
-                let (a, b) = a.div_rem(d);
-                (Self::Rug(a), Self::Rug(b))
+    pub fn significant_bits(&self) -> u32 {
+        match self {
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => a.significant_bits(),
+            #[cfg(not(feature = "rug"))]
+            Integer::I128(n) => 128 - n.unsigned_abs().leading_zeros(),
+        }
+    }
+    pub fn abs(self) -> Integer {
+        map!(self, a => a.abs(), Some(a.abs()))
+    }
+    pub fn signum(self) -> Integer {
+        map!(self, a => a.signum(), Some(a.signum()))
+    }
+    pub fn sqrt(self) -> Integer {
+        map!(self, a => a.sqrt(), Some(isqrt(a)))
+    }
+    pub fn is_perfect_square(&self) -> bool {
+        match self {
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => a.is_perfect_square(),
+            #[cfg(not(feature = "rug"))]
+            Integer::I128(n) => *n >= 0 && isqrt(*n).pow(2) == *n,
+        }
+    }
+    pub fn is_divisible(&self, divisor: &Integer) -> bool {
+        match (self, divisor) {
+            #[cfg(feature = "rug")]
+            (Integer::Rug(a), Integer::Rug(b)) => a.is_divisible(b),
+            #[cfg(not(feature = "rug"))]
+            (Integer::I128(a), Integer::I128(b)) => {
+                if *b == 0 {
+                    *a == 0
+                } else {
+                    a % b == 0
+                }
+            }
+        }
+    }
+    pub fn modulo(self, divisor: &Integer) -> Integer {
+        map2!(self, divisor.clone(), a, b => a.modulo(&b), a.checked_rem_euclid(b))
+    }
+    pub fn div_rem(self, divisor: Integer) -> (Integer, Integer) {
+        (&self / &divisor, self % divisor)
+    }
+    pub fn gcd(self, other: &Integer) -> Integer {
+        map2!(self, other.clone(), a, b => a.gcd(&b), Some(gcd(a, b)))
+    }
+    pub fn lcm(self, other: &Integer) -> Integer {
+        map2!(self, other.clone(), a, b => a.lcm(&b), {
+            let g = gcd(a, b);
+            if g == 0 {
+                Some(0)
+            } else {
+                (a / g).checked_mul(b).map(i128::abs)
             }
-            (Self::Fastnum(a), Self::Fastnum(d)) => {
-                let (a, b) = (a / d, a.rem(d));
-                (Self::Fastnum(a), Self::Fastnum(b))
+        })
+    }
+    pub fn invert(self, modulo: &Integer) -> Result<Integer, Integer> {
+        match (self, modulo) {
+            #[cfg(feature = "rug")]
+            (Integer::Rug(a), Integer::Rug(b)) => {
+                a.invert(b).map(Integer::Rug).map_err(Integer::Rug)
             }
-            (Self::F64(a), Self::F64(d)) => {
-                let (a, b) = (a / d, a % d);
-                (Self::F64(a), Self::F64(b))
+            #[cfg(not(feature = "rug"))]
+            (a, b) => {
+                let (Some(n), Some(m)) = (a.get(), b.get()) else {
+                    return Err(a);
+                };
+                let m = m.abs();
+                let (mut r0, mut r1) = (n.rem_euclid(m), m);
+                let (mut s0, mut s1) = (1i128, 0i128);
+                while r1 != 0 {
+                    let q = r0 / r1;
+                    (r0, r1) = (r1, r0 - q * r1);
+                    (s0, s1) = (s1, s0 - q * s1);
+                }
+                if r0 == 1 {
+                    Ok(Integer::I128(s0.rem_euclid(m)))
+                } else {
+                    Err(a)
+                }
             }
-            (Self::F32(a), Self::F32(d)) => {
-                let (a, b) = (a / d, a % d);
-                (Self::F32(a), Self::F32(b))
+        }
+    }
+    pub fn is_probably_prime(&self, reps: u32) -> IsPrime {
+        match self {
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => match a.is_probably_prime(reps) {
+                rug::integer::IsPrime::No => IsPrime::No,
+                rug::integer::IsPrime::Probably => IsPrime::Probably,
+                rug::integer::IsPrime::Yes => IsPrime::Yes,
+            },
+            #[cfg(not(feature = "rug"))]
+            Integer::I128(n) => {
+                let _ = reps;
+                let n = *n;
+                if n < 2 {
+                    return IsPrime::No;
+                }
+                if n < 4 {
+                    return IsPrime::Yes;
+                }
+                if n % 2 == 0 {
+                    return IsPrime::No;
+                }
+                let mut i = 3;
+                while i <= n / i {
+                    if n % i == 0 {
+                        return IsPrime::No;
+                    }
+                    i += 2;
+                }
+                IsPrime::Yes
             }
-            _ => unreachable!(),
         }
     }
+    pub fn next_prime(self) -> Integer {
+        match self {
+            #[cfg(feature = "rug")]
+            Integer::Rug(a) => Integer::Rug(a.next_prime()),
+            #[cfg(not(feature = "rug"))]
+            a => {
+                let mut n = a.max(Integer::I128(1)) + 1;
+                while n.is_probably_prime(0) == IsPrime::No {
+                    n += 1;
+                }
+                n
+            }
+        }
+    }
+    pub fn binomial(self, k: u32) -> Integer {
+        map!(self, a => a.binomial(k), {
+            let k = k as i128;
+            if k > a && a >= 0 {
+                Some(0)
+            } else {
+                (0..k).try_fold(1i128, |r, i| Some(r.checked_mul(a.checked_sub(i)?)? / (i + 1)))
+            }
+        })
+    }
+    pub fn factorial(n: u32) -> Integer {
+        #[cfg(feature = "rug")]
+        return Integer::Rug(rug::Integer::from(rug::Integer::factorial(n)));
+        #[cfg(not(feature = "rug"))]
+        Integer::checked((2..=n as i128).try_fold(1i128, |r, i| r.checked_mul(i)))
+    }
 }
-
-impl_pow!(
-    Integer,
-    u32,
-    (Rug, |x| x),
-    (Fastnum, |x| x),
-    (F64, |x| x),
-    (F32, |x| x)
-);
-impl_self_ops!(
-    Integer,
-    (Rug, |x| x),
-    (Fastnum, |x| x),
-    (F64, |x| x),
-    (F32, |x| x)
-);
-impl_int_ops!(Integer, Integer, i32);
-impl_int_ops!(Integer, Integer, u32);
@@ -0,0 +1,56 @@
+pub trait Pow<Rhs> {
+    type Output;
+    fn pow(self, rhs: Rhs) -> Self::Output;
+}
+///values completed in the backend and precision given
+pub trait CompleteRound {
+    type Completed;
+    fn complete<P: super::Prec>(self, prec: P) -> Self::Completed;
+}
+pub trait DivRounding<Rhs = Self> {
+    type Output;
+    fn div_trunc(self, rhs: Rhs) -> Self::Output;
+    fn div_ceil(self, rhs: Rhs) -> Self::Output;
+    fn div_floor(self, rhs: Rhs) -> Self::Output;
+    fn div_euc(self, rhs: Rhs) -> Self::Output;
+}
+macro_rules! impl_prim {
+    ($($t:ty),*) => {
+        $(
+            impl Pow<$t> for $t {
+                type Output = $t;
+                fn pow(self, rhs: $t) -> $t {
+                    self.powf(rhs)
+                }
+            }
+            impl Pow<i32> for $t {
+                type Output = $t;
+                fn pow(self, rhs: i32) -> $t {
+                    self.powi(rhs)
+                }
+            }
+            impl Pow<u32> for $t {
+                type Output = $t;
+                fn pow(self, rhs: u32) -> $t {
+                    self.powi(rhs as i32)
+                }
+            }
+            impl DivRounding for $t {
+                type Output = $t;
+                fn div_trunc(self, rhs: $t) -> $t {
+                    (self / rhs).trunc()
+                }
+                fn div_ceil(self, rhs: $t) -> $t {
+                    (self / rhs).ceil()
+                }
+                fn div_floor(self, rhs: $t) -> $t {
+                    (self / rhs).floor()
+                }
+                fn div_euc(self, rhs: $t) -> $t {
+                    self.div_euclid(rhs)
+                }
+            }
+        )*
+    };
+}
+impl_prim!(f32, f64);
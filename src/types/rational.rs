@@ -0,0 +1,445 @@
+#[cfg(not(feature = "rug"))]
+use super::integer::OVERFLOW;
+use super::{
+    integer::{Integer, ToInteger},
+    ops::Pow,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+///an exact fraction, gmp backed with rug, without it a reduced i128 fraction where a zero
+///denominator marks a result that did not fit, it stays that way through later operations
+///and converts to NaN
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Rational {
+    #[cfg(feature = "rug")]
+    Rug(rug::Rational),
+    #[cfg(not(feature = "rug"))]
+    I128(Integer, Integer),
+}
+impl Default for Rational {
+    fn default() -> Rational {
+        Rational::new()
+    }
+}
+pub trait ToRational {
+    fn to_rational(self) -> Rational;
+}
+impl<T: ToInteger> ToRational for T {
+    fn to_rational(self) -> Rational {
+        (self.to_integer(), Integer::from(1)).to_rational()
+    }
+}
+impl ToRational for Rational {
+    fn to_rational(self) -> Rational {
+        self
+    }
+}
+impl ToRational for &Rational {
+    fn to_rational(self) -> Rational {
+        self.clone()
+    }
+}
+macro_rules! from {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Rational {
+                fn from(value: $t) -> Rational {
+                    value.to_rational()
+                }
+            }
+        )*
+    };
+}
+from!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, Integer, &Integer, &Rational
+);
+#[cfg(feature = "rug")]
+impl From<rug::Rational> for Rational {
+    fn from(value: rug::Rational) -> Rational {
+        Rational::Rug(value)
+    }
+}
+impl<A: ToInteger, B: ToInteger> From<(A, B)> for Rational {
+    fn from(value: (A, B)) -> Rational {
+        value.to_rational()
+    }
+}
+impl<A: ToInteger, B: ToInteger> ToRational for (A, B) {
+    fn to_rational(self) -> Rational {
+        match (self.0.to_integer(), self.1.to_integer()) {
+            #[cfg(feature = "rug")]
+            (Integer::Rug(a), Integer::Rug(b)) => Rational::Rug(rug::Rational::from((a, b))),
+            #[cfg(not(feature = "rug"))]
+            (a, b) => Rational::checked(a.get(), b.get()),
+        }
+    }
+}
+///applies the rug expression or the one on the numerators and denominators
+macro_rules! map2 {
+    ($a:expr, $b:expr, $x:ident, $y:ident => $rug:expr, $frac:expr) => {
+        match ($a, $b) {
+            #[cfg(feature = "rug")]
+            (Rational::Rug($x), Rational::Rug($y)) => Rational::Rug($rug),
+            #[cfg(not(feature = "rug"))]
+            ($x, $y) => {
+                let (Rational::I128(an, ad), Rational::I128(bn, bd)) = ($x, $y);
+                let (num, den): (Integer, Integer) = $frac(an, ad, bn, bd);
+                Rational::checked(num.get(), den.get())
+            }
+        }
+    };
+}
+macro_rules! impl_ops {
+    ($(($trait:ident, $method:ident, $op:tt, $frac:expr)),*) => {
+        $(
+            impl<T: ToRational> $trait<T> for Rational {
+                type Output = Rational;
+                fn $method(self, rhs: T) -> Rational {
+                    map2!(self, rhs.to_rational(), a, b => a $op b, $frac)
+                }
+            }
+            impl<T: ToRational> $trait<T> for &Rational {
+                type Output = Rational;
+                fn $method(self, rhs: T) -> Rational {
+                    self.clone() $op rhs
+                }
+            }
+        )*
+    };
+}
+impl_ops!(
+    (Add, add, +, |an: Integer, ad: Integer, bn: Integer, bd: Integer| (
+        &an * &bd + &bn * &ad,
+        ad * bd
+    )),
+    (Sub, sub, -, |an: Integer, ad: Integer, bn: Integer, bd: Integer| (
+        &an * &bd - &bn * &ad,
+        ad * bd
+    )),
+    (Mul, mul, *, |an: Integer, ad: Integer, bn: Integer, bd: Integer| (
+        an * bn,
+        ad * bd
+    )),
+    (Div, div, /, |an: Integer, ad: Integer, bn: Integer, bd: Integer| (
+        an * bd,
+        ad * bn
+    ))
+);
+macro_rules! impl_assign {
+    ($(($trait:ident, $method:ident, $op:tt)),*) => {
+        $(
+            impl<T: ToRational> std::ops::$trait<T> for Rational {
+                fn $method(&mut self, rhs: T) {
+                    *self = std::mem::take(self) $op rhs
+                }
+            }
+        )*
+    };
+}
+impl_assign!(
+    (AddAssign, add_assign, +),
+    (SubAssign, sub_assign, -),
+    (MulAssign, mul_assign, *),
+    (DivAssign, div_assign, /)
+);
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        match (self, other) {
+            #[cfg(feature = "rug")]
+            (Rational::Rug(a), Rational::Rug(b)) => a.cmp(b),
+            #[cfg(not(feature = "rug"))]
+            (Rational::I128(an, ad), Rational::I128(bn, bd)) => {
+                match ((an * bd).get(), (bn * ad).get()) {
+                    (Some(a), Some(b)) if self.is_finite() && other.is_finite() => a.cmp(&b),
+                    _ => self.to_f64().total_cmp(&other.to_f64()),
+                }
+            }
+        }
+    }
+}
+macro_rules! impl_prim {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq<$t> for Rational {
+                fn eq(&self, other: &$t) -> bool {
+                    *self == other.to_rational()
+                }
+            }
+            impl PartialEq<Rational> for $t {
+                fn eq(&self, other: &Rational) -> bool {
+                    self.to_rational() == *other
+                }
+            }
+            impl PartialOrd<$t> for Rational {
+                fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                    self.partial_cmp(&other.to_rational())
+                }
+            }
+            impl PartialOrd<Rational> for $t {
+                fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+                    self.to_rational().partial_cmp(other)
+                }
+            }
+            impl Add<Rational> for $t {
+                type Output = Rational;
+                fn add(self, rhs: Rational) -> Rational {
+                    self.to_rational() + rhs
+                }
+            }
+            impl Sub<Rational> for $t {
+                type Output = Rational;
+                fn sub(self, rhs: Rational) -> Rational {
+                    self.to_rational() - rhs
+                }
+            }
+            impl Mul<Rational> for $t {
+                type Output = Rational;
+                fn mul(self, rhs: Rational) -> Rational {
+                    self.to_rational() * rhs
+                }
+            }
+            impl Div<Rational> for $t {
+                type Output = Rational;
+                fn div(self, rhs: Rational) -> Rational {
+                    self.to_rational() / rhs
+                }
+            }
+        )*
+    };
+}
+impl_prim!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, Integer
+);
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(a) => Rational::Rug(-a),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, d) => Rational::checked((-n).get(), d.get()),
+        }
+    }
+}
+impl Neg for &Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        -self.clone()
+    }
+}
+impl Pow<i32> for Rational {
+    type Output = Rational;
+    fn pow(self, rhs: i32) -> Rational {
+        let r = self.pow(rhs.unsigned_abs());
+        if rhs < 0 { r.recip() } else { r }
+    }
+}
+impl Pow<u32> for Rational {
+    type Output = Rational;
+    fn pow(self, rhs: u32) -> Rational {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(a) => Rational::Rug(rug::ops::Pow::pow(a, rhs)),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, d) => Rational::checked(n.pow(rhs).get(), d.pow(rhs).get()),
+        }
+    }
+}
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(a) => Display::fmt(a, f),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, d) => {
+                if !self.is_finite() {
+                    f.write_str("NaN")
+                } else if *d == 1 {
+                    write!(f, "{n}")
+                } else {
+                    write!(f, "{n}/{d}")
+                }
+            }
+        }
+    }
+}
+impl Rational {
+    pub fn new() -> Rational {
+        #[cfg(feature = "rug")]
+        return Rational::Rug(rug::Rational::new());
+        #[cfg(not(feature = "rug"))]
+        Rational::I128(Integer::from(0), Integer::from(1))
+    }
+    #[cfg(not(feature = "rug"))]
+    fn checked(num: Option<i128>, den: Option<i128>) -> Rational {
+        match (num, den) {
+            (Some(num), Some(den)) if den != 0 && num != OVERFLOW && den != OVERFLOW => {
+                let g = Integer::from(num)
+                    .gcd(&Integer::from(den))
+                    .get()
+                    .unwrap_or(1);
+                let (num, den) = (num / g, den / g);
+                let s = if den < 0 { -1 } else { 1 };
+                Rational::I128(Integer::from(num * s), Integer::from(den * s))
+            }
+            _ => Rational::I128(Integer::from(0), Integer::from(0)),
+        }
+    }
+    #[cfg(feature = "rug")]
+    pub(crate) fn as_rug(&self) -> &rug::Rational {
+        let Rational::Rug(q) = self;
+        q
+    }
+    ///the exact value of a finite f64
+    pub fn from_f64(value: f64) -> Option<Rational> {
+        #[cfg(feature = "rug")]
+        return rug::Rational::from_f64(value).map(Rational::Rug);
+        #[cfg(not(feature = "rug"))]
+        {
+            if !value.is_finite() {
+                return None;
+            }
+            let mut den = 1i128;
+            let mut num = value;
+            while num.fract() != 0.0 {
+                num *= 2.0;
+                den = den.checked_mul(2)?;
+            }
+            if num.abs() >= i128::MAX as f64 {
+                return None;
+            }
+            Some(Rational::checked(Some(num as i128), Some(den))).filter(Rational::is_finite)
+        }
+    }
+    pub fn numer(&self) -> Integer {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(q) => Integer::Rug(q.numer().clone()),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, _) => n.clone(),
+        }
+    }
+    pub fn denom(&self) -> Integer {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(q) => Integer::Rug(q.denom().clone()),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(_, d) => d.clone(),
+        }
+    }
+    pub fn into_numer_denom(self) -> (Integer, Integer) {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(q) => {
+                let (n, d) = q.into_numer_denom();
+                (Integer::Rug(n), Integer::Rug(d))
+            }
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, d) => (n, d),
+        }
+    }
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(q) => q.to_f64(),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, d) => {
+                if self.is_finite() {
+                    n.to_f64() / d.to_f64()
+                } else {
+                    f64::NAN
+                }
+            }
+        }
+    }
+    pub fn is_finite(&self) -> bool {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(_) => true,
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(_, d) => !d.is_zero(),
+        }
+    }
+    pub fn is_zero(&self) -> bool {
+        self.cmp0() == Ordering::Equal && self.is_finite()
+    }
+    pub fn is_integer(&self) -> bool {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(q) => q.is_integer(),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(_, d) => *d == 1,
+        }
+    }
+    pub fn cmp0(&self) -> Ordering {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(q) => q.cmp0(),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, _) => n.cmp0(),
+        }
+    }
+    pub fn recip(self) -> Rational {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(q) => Rational::Rug(q.recip()),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, d) => Rational::checked(d.get(), n.get()),
+        }
+    }
+    pub fn abs(self) -> Rational {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(q) => Rational::Rug(q.abs()),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, d) => Rational::I128(n.abs(), d),
+        }
+    }
+    pub fn signum(self) -> Rational {
+        Rational::from(self.numer().signum())
+    }
+    pub fn floor(self) -> Rational {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(q) => Rational::Rug(q.floor()),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, d) => {
+                if d.is_zero() {
+                    return Rational::I128(n, d);
+                }
+                Rational::checked(n.get().map(|n| n.div_euclid(d.get().unwrap())), Some(1))
+            }
+        }
+    }
+    pub fn ceil(self) -> Rational {
+        -(-self).floor()
+    }
+    pub fn trunc(self) -> Rational {
+        match self {
+            #[cfg(feature = "rug")]
+            Rational::Rug(q) => Rational::Rug(q.trunc()),
+            #[cfg(not(feature = "rug"))]
+            Rational::I128(n, d) => {
+                if d.is_zero() {
+                    return Rational::I128(n, d);
+                }
+                Rational::checked((n / d).get(), Some(1))
+            }
+        }
+    }
+    pub fn fract(self) -> Rational {
+        let t = self.clone().trunc();
+        self - t
+    }
+}
@@ -0,0 +1,575 @@
+use super::float::Constant;
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+///the scalar operations of the f64, f32 and fastnum backends, the complex functions,
+///parsing and digit generation below are written once over it
+pub(crate) trait Real:
+    Copy
+    + PartialOrd
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    fn bits(self) -> u32;
+    ///x in the same backend as self
+    fn of(self, x: f64) -> Self;
+    fn constant(self, c: Constant) -> Self;
+    fn to_f64(self) -> f64;
+    ///the shortest decimal digits and the exponent of 0.digits, if the backend has them
+    fn decimal_digits(self) -> Option<(String, i32)>;
+    ///the neighbouring representable number
+    fn next(self, up: bool) -> Self;
+    fn is_nan(self) -> bool;
+    fn is_infinite(self) -> bool;
+    fn is_sign_negative(self) -> bool;
+    fn abs(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn trunc(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn tanh(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn asinh(self) -> Self;
+    fn acosh(self) -> Self;
+    fn atanh(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn is_zero(self) -> bool {
+        self == self.of(0.0)
+    }
+    fn is_finite(self) -> bool {
+        !self.is_nan() && !self.is_infinite()
+    }
+    fn is_integer(self) -> bool {
+        self.is_finite() && self.trunc() == self
+    }
+    fn fract(self) -> Self {
+        self - self.trunc()
+    }
+    fn recip(self) -> Self {
+        self.of(1.0) / self
+    }
+    fn signum(self) -> Self {
+        if self.is_nan() {
+            self
+        } else {
+            self.of(if self.is_sign_negative() { -1.0 } else { 1.0 })
+        }
+    }
+    fn copysign(self, sign: Self) -> Self {
+        if self.is_sign_negative() == sign.is_sign_negative() {
+            self
+        } else {
+            -self
+        }
+    }
+    fn log2(self) -> Self {
+        self.ln() / self.constant(Constant::Log2)
+    }
+    fn log10(self) -> Self {
+        self.ln() / self.of(10.0).ln()
+    }
+    fn exp2(self) -> Self {
+        self.of(2.0).powf(self)
+    }
+    fn exp10(self) -> Self {
+        self.of(10.0).powf(self)
+    }
+    fn max(self, other: Self) -> Self {
+        if self.is_nan() || other > self {
+            other
+        } else {
+            self
+        }
+    }
+    fn min(self, other: Self) -> Self {
+        if self.is_nan() || other < self {
+            other
+        } else {
+            self
+        }
+    }
+    fn gamma(self) -> Self {
+        gamma(self)
+    }
+}
+macro_rules! impl_real {
+    ($($t:ident, $bits:expr),*) => {
+        $(
+            impl Real for $t {
+                fn bits(self) -> u32 {
+                    $bits
+                }
+                fn of(self, x: f64) -> $t {
+                    x as $t
+                }
+                fn constant(self, c: Constant) -> $t {
+                    (match c {
+                        Constant::Log2 => std::f64::consts::LN_2,
+                        Constant::Pi => std::f64::consts::PI,
+                        Constant::Euler => 0.577_215_664_901_532_9,
+                        Constant::Catalan => 0.915_965_594_177_219,
+                    }) as $t
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn decimal_digits(self) -> Option<(String, i32)> {
+                    let s = format!("{:e}", self.abs());
+                    let (mantissa, exp) = s.split_once('e')?;
+                    Some((mantissa.replace('.', ""), exp.parse::<i32>().ok()? + 1))
+                }
+                fn next(self, up: bool) -> $t {
+                    if self.is_nan() || self == if up { $t::INFINITY } else { $t::NEG_INFINITY } {
+                        return self;
+                    }
+                    if self == 0.0 {
+                        let tiny = $t::from_bits(1);
+                        return if up { tiny } else { -tiny };
+                    }
+                    let bits = self.to_bits();
+                    $t::from_bits(if (self > 0.0) == up { bits + 1 } else { bits - 1 })
+                }
+                fn is_nan(self) -> bool {
+                    $t::is_nan(self)
+                }
+                fn is_infinite(self) -> bool {
+                    $t::is_infinite(self)
+                }
+                fn is_sign_negative(self) -> bool {
+                    $t::is_sign_negative(self)
+                }
+                fn abs(self) -> $t {
+                    $t::abs(self)
+                }
+                fn floor(self) -> $t {
+                    $t::floor(self)
+                }
+                fn ceil(self) -> $t {
+                    $t::ceil(self)
+                }
+                fn round(self) -> $t {
+                    $t::round(self)
+                }
+                fn trunc(self) -> $t {
+                    $t::trunc(self)
+                }
+                fn sqrt(self) -> $t {
+                    $t::sqrt(self)
+                }
+                fn cbrt(self) -> $t {
+                    $t::cbrt(self)
+                }
+                fn exp(self) -> $t {
+                    $t::exp(self)
+                }
+                fn ln(self) -> $t {
+                    $t::ln(self)
+                }
+                fn sin(self) -> $t {
+                    $t::sin(self)
+                }
+                fn cos(self) -> $t {
+                    $t::cos(self)
+                }
+                fn tan(self) -> $t {
+                    $t::tan(self)
+                }
+                fn sinh(self) -> $t {
+                    $t::sinh(self)
+                }
+                fn cosh(self) -> $t {
+                    $t::cosh(self)
+                }
+                fn tanh(self) -> $t {
+                    $t::tanh(self)
+                }
+                fn asin(self) -> $t {
+                    $t::asin(self)
+                }
+                fn acos(self) -> $t {
+                    $t::acos(self)
+                }
+                fn atan(self) -> $t {
+                    $t::atan(self)
+                }
+                fn asinh(self) -> $t {
+                    $t::asinh(self)
+                }
+                fn acosh(self) -> $t {
+                    $t::acosh(self)
+                }
+                fn atanh(self) -> $t {
+                    $t::atanh(self)
+                }
+                fn atan2(self, x: $t) -> $t {
+                    $t::atan2(self, x)
+                }
+                fn hypot(self, other: $t) -> $t {
+                    $t::hypot(self, other)
+                }
+                fn powf(self, n: $t) -> $t {
+                    $t::powf(self, n)
+                }
+                fn powi(self, n: i32) -> $t {
+                    $t::powi(self, n)
+                }
+                fn log2(self) -> $t {
+                    $t::log2(self)
+                }
+                fn log10(self) -> $t {
+                    $t::log10(self)
+                }
+                fn exp2(self) -> $t {
+                    $t::exp2(self)
+                }
+            }
+        )*
+    };
+}
+impl_real!(f64, 53, f32, 24);
+fn gamma<T: Real>(x: T) -> T {
+    const G: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    let pi = x.constant(Constant::Pi);
+    if x.is_nan() {
+        x
+    } else if x.is_integer() && x >= x.of(1.0) && x <= x.of(171.0) {
+        (2..x.to_f64() as u32).fold(x.of(1.0), |a, b| a * x.of(b as f64))
+    } else if x < x.of(0.5) {
+        pi / ((pi * x).sin() * gamma(x.of(1.0) - x))
+    } else {
+        let x = x - x.of(1.0);
+        let t = x + x.of(7.5);
+        let a = G
+            .iter()
+            .enumerate()
+            .skip(1)
+            .fold(x.of(G[0]), |a, (i, g)| a + x.of(*g) / (x + x.of(i as f64)));
+        let g = (x.of(2.0) * pi).sqrt() * t.powf(x + x.of(0.5)) * (-t).exp() * a;
+        //t^(x+1/2) overflows together with e^-t underflowing
+        if g.is_nan() { x.of(f64::INFINITY) } else { g }
+    }
+}
+fn digit(c: u8, radix: u32) -> Option<u32> {
+    (c as char).to_digit(radix)
+}
+///parses a number in the given radix into the backend of like
+pub(crate) fn parse<T: Real>(like: T, s: &str, radix: i32) -> Option<T> {
+    let s = s.trim();
+    let (neg, s) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let lower = s.to_ascii_lowercase();
+    let n = match lower.trim_matches('@') {
+        "inf" | "infinity" => like.of(f64::INFINITY),
+        "nan" => like.of(f64::NAN),
+        _ => {
+            let (mantissa, exp) =
+                match lower
+                    .find('@')
+                    .or(if radix <= 10 { lower.find('e') } else { None })
+                {
+                    Some(i) => (&lower[..i], lower[i + 1..].parse::<i32>().ok()?),
+                    None => (lower.as_str(), 0),
+                };
+            let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+            if int.is_empty() && frac.is_empty() {
+                return None;
+            }
+            let r = like.of(radix as f64);
+            let mut n = like.of(0.0);
+            for c in int.bytes().chain(frac.bytes()) {
+                n = n * r + like.of(digit(c, radix as u32)? as f64);
+            }
+            let exp = exp.checked_sub(i32::try_from(frac.len()).ok()?)?;
+            if exp < 0 {
+                n / r.powi(-exp)
+            } else {
+                n * r.powi(exp)
+            }
+        }
+    };
+    Some(if neg { -n } else { n })
+}
+///the sign, the digits and the exponent of 0.digits, the same shape rug returns
+pub(crate) fn to_sign_string_exp<T: Real>(
+    x: T,
+    radix: i32,
+    digits: Option<usize>,
+) -> (bool, String, Option<i32>) {
+    let neg = x.is_sign_negative() && !x.is_nan();
+    let x = x.abs();
+    if x.is_zero() || !x.is_finite() {
+        let s = if x.is_zero() {
+            "0"
+        } else if x.is_nan() {
+            "NaN"
+        } else {
+            "inf"
+        };
+        return (neg, s.to_string(), None);
+    }
+    if radix == 10 && digits.is_none() {
+        if let Some((s, exp)) = x.decimal_digits() {
+            return (neg, s, Some(exp));
+        }
+    }
+    let r = x.of(radix as f64);
+    let count = digits
+        .unwrap_or((x.bits() as f64 / (radix as f64).log2()).ceil() as usize + 1)
+        .max(1);
+    let mut exp = (x.ln() / r.ln()).floor().to_f64() as i32 + 1;
+    let mut m = if exp < 0 {
+        x * r.powi(-exp)
+    } else {
+        x / r.powi(exp)
+    };
+    if m >= x.of(1.0) {
+        m = m / r;
+        exp += 1;
+    } else if m < x.of(1.0) / r {
+        m = m * r;
+        exp -= 1;
+    }
+    let mut out = Vec::with_capacity(count);
+    for _ in 0..count {
+        m = m * r;
+        let d = (m.floor().to_f64() as u32).min(radix as u32 - 1);
+        out.push(d);
+        m = m - x.of(d as f64);
+    }
+    if m * x.of(2.0) >= x.of(1.0) {
+        let mut i = out.len();
+        loop {
+            if i == 0 {
+                out.insert(0, 1);
+                out.pop();
+                exp += 1;
+                break;
+            }
+            i -= 1;
+            if out[i] + 1 == radix as u32 {
+                out[i] = 0;
+            } else {
+                out[i] += 1;
+                break;
+            }
+        }
+    }
+    let s = out
+        .into_iter()
+        .map(|d| std::char::from_digit(d, radix as u32).unwrap())
+        .collect();
+    (neg, s, Some(exp))
+}
+///complex functions on (re, im) pairs with real fast paths
+pub(crate) mod cplx {
+    use super::Real;
+    type C<T> = (T, T);
+    fn zero<T: Real>(a: T) -> C<T> {
+        (a, a.of(0.0))
+    }
+    pub fn mul<T: Real>((a, b): C<T>, (c, d): C<T>) -> C<T> {
+        if b.is_zero() && d.is_zero() {
+            return (a * c, b + d);
+        }
+        (a * c - b * d, a * d + b * c)
+    }
+    pub fn div<T: Real>((a, b): C<T>, (c, d): C<T>) -> C<T> {
+        if d.is_zero() {
+            if c.is_zero() && a.is_zero() && b.is_zero() {
+                let nan = a.of(f64::NAN);
+                return (nan, nan);
+            }
+            return (a / c, if b.is_zero() { b } else { b / c });
+        }
+        let n = c * c + d * d;
+        ((a * c + b * d) / n, (b * c - a * d) / n)
+    }
+    pub fn recip<T: Real>(a: C<T>) -> C<T> {
+        div(zero(a.0.of(1.0)), a)
+    }
+    pub fn square<T: Real>(a: C<T>) -> C<T> {
+        mul(a, a)
+    }
+    pub fn sqrt<T: Real>((a, b): C<T>) -> C<T> {
+        if b.is_zero() {
+            return if !a.is_sign_negative() || a.is_zero() {
+                zero(a.sqrt())
+            } else {
+                (a.of(0.0), (-a).sqrt())
+            };
+        }
+        let r = a.hypot(b);
+        let two = a.of(2.0);
+        (((r + a) / two).sqrt(), ((r - a) / two).sqrt().copysign(b))
+    }
+    pub fn exp<T: Real>((a, b): C<T>) -> C<T> {
+        let e = a.exp();
+        if b.is_zero() {
+            return zero(e);
+        }
+        (e * b.cos(), e * b.sin())
+    }
+    pub fn ln<T: Real>((a, b): C<T>) -> C<T> {
+        if b.is_zero() && (!a.is_sign_negative() || a.is_zero()) {
+            return zero(a.ln());
+        }
+        (a.hypot(b).ln(), b.atan2(a))
+    }
+    pub fn log10<T: Real>(a: C<T>) -> C<T> {
+        let (re, im) = ln(a);
+        let l = a.0.of(10.0).ln();
+        (re / l, im / l)
+    }
+    pub fn mul_i<T: Real>((a, b): C<T>, negative: bool) -> C<T> {
+        if negative { (b, -a) } else { (-b, a) }
+    }
+    pub fn sin<T: Real>((a, b): C<T>) -> C<T> {
+        if b.is_zero() {
+            return zero(a.sin());
+        }
+        (a.sin() * b.cosh(), a.cos() * b.sinh())
+    }
+    pub fn cos<T: Real>((a, b): C<T>) -> C<T> {
+        if b.is_zero() {
+            return (a.cos(), -b);
+        }
+        (a.cos() * b.cosh(), -(a.sin() * b.sinh()))
+    }
+    pub fn tan<T: Real>(a: C<T>) -> C<T> {
+        if a.1.is_zero() {
+            return zero(a.0.tan());
+        }
+        div(sin(a), cos(a))
+    }
+    pub fn sinh<T: Real>(a: C<T>) -> C<T> {
+        if a.1.is_zero() {
+            return zero(a.0.sinh());
+        }
+        mul_i(sin(mul_i(a, false)), true)
+    }
+    pub fn cosh<T: Real>(a: C<T>) -> C<T> {
+        if a.1.is_zero() {
+            return zero(a.0.cosh());
+        }
+        cos(mul_i(a, false))
+    }
+    pub fn tanh<T: Real>(a: C<T>) -> C<T> {
+        if a.1.is_zero() {
+            return zero(a.0.tanh());
+        }
+        mul_i(tan(mul_i(a, false)), true)
+    }
+    pub fn asin<T: Real>(a: C<T>) -> C<T> {
+        let one = a.0.of(1.0);
+        if a.1.is_zero() {
+            return if a.0.abs() <= one {
+                zero(a.0.asin())
+            } else {
+                //the upper side of the cut, as mpc takes it for a +0 imaginary part
+                let half_pi = a.0.constant(super::Constant::Pi) / a.0.of(2.0);
+                (half_pi.copysign(a.0), a.0.abs().acosh())
+            };
+        }
+        let s = sqrt(sub(zero(one), square(a)));
+        mul_i(ln(add(mul_i(a, false), s)), true)
+    }
+    pub fn acos<T: Real>(a: C<T>) -> C<T> {
+        let one = a.0.of(1.0);
+        if a.1.is_zero() && a.0.abs() <= one {
+            return zero(a.0.acos());
+        }
+        let half_pi = a.0.constant(super::Constant::Pi) / a.0.of(2.0);
+        sub(zero(half_pi), asin(a))
+    }
+    pub fn atan<T: Real>(a: C<T>) -> C<T> {
+        if a.1.is_zero() {
+            return zero(a.0.atan());
+        }
+        let (i, one) = (mul_i(a, false), zero(a.0.of(1.0)));
+        let (re, im) = mul_i(sub(ln(sub(one, i)), ln(add(one, i))), false);
+        let two = a.0.of(2.0);
+        (re / two, im / two)
+    }
+    pub fn asinh<T: Real>(a: C<T>) -> C<T> {
+        if a.1.is_zero() {
+            return zero(a.0.asinh());
+        }
+        mul_i(asin(mul_i(a, false)), true)
+    }
+    pub fn acosh<T: Real>(a: C<T>) -> C<T> {
+        let one = a.0.of(1.0);
+        if a.1.is_zero() && a.0 >= one {
+            return zero(a.0.acosh());
+        }
+        let s = mul(sqrt(add(a, zero(one))), sqrt(sub(a, zero(one))));
+        ln(add(s, a))
+    }
+    pub fn atanh<T: Real>(a: C<T>) -> C<T> {
+        if a.1.is_zero() {
+            return if a.0.abs() <= a.0.of(1.0) {
+                zero(a.0.atanh())
+            } else {
+                let half_pi = a.0.constant(super::Constant::Pi) / a.0.of(2.0);
+                (a.0.recip().atanh(), half_pi)
+            };
+        }
+        mul_i(atan(mul_i(a, false)), true)
+    }
+    pub fn add<T: Real>(a: C<T>, b: C<T>) -> C<T> {
+        (a.0 + b.0, a.1 + b.1)
+    }
+    pub fn sub<T: Real>(a: C<T>, b: C<T>) -> C<T> {
+        (a.0 - b.0, a.1 - b.1)
+    }
+    pub fn pow<T: Real>(a: C<T>, b: C<T>) -> C<T> {
+        if b.1.is_zero() && a.1.is_zero() && (!a.0.is_sign_negative() || b.0.is_integer()) {
+            return zero(a.0.powf(b.0));
+        }
+        if a.0.is_zero() && a.1.is_zero() {
+            return if b.0.is_zero() && b.1.is_zero() {
+                zero(a.0.of(1.0))
+            } else if b.0 > a.0.of(0.0) {
+                a
+            } else if b.0 < a.0.of(0.0) {
+                zero(a.0.of(f64::INFINITY))
+            } else {
+                let nan = a.0.of(f64::NAN);
+                (nan, nan)
+            };
+        }
+        exp(mul(ln(a), b))
+    }
+}
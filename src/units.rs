@@ -1,15 +1,15 @@
-use crate::{
-    complex::NumStr,
-    types::Type,
-    units::{AngleType::Radians, Notation::Normal},
-};
 #[cfg(feature = "bin-deps")]
-use rug::ops::CompleteRound;
-use rug::{
-    Complex, Float,
+use crate::types::ops::CompleteRound;
+use crate::types::{
+    Complex, Float, Precision, Rational,
     float::Constant::Pi,
     ops::{DivRounding, Pow},
 };
+use crate::{
+    complex::NumStr,
+    error::Error,
+    units::{AngleType::Radians, Notation::Normal},
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -44,11 +44,17 @@ pub struct Units {
     pub usd: f64,
     pub unit: f64,
 }
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Number {
     pub number: Complex,
     pub units: Option<Units>,
+    pub exact: Option<Rational>,
+}
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.number == other.number && self.units == other.units
+    }
 }
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -176,6 +182,37 @@ pub enum GraphType {
     Depth,
     None,
 }
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Type {
+    #[cfg(feature = "rug")]
+    #[default]
+    Rug,
+    #[cfg(feature = "fastnum")]
+    Fastnum,
+    #[cfg_attr(not(feature = "rug"), default)]
+    F64,
+    F32,
+}
+
+impl std::str::FromStr for Type {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            #[cfg(feature = "rug")]
+            "rug" | "mpfr" => Ok(Type::Rug),
+            #[cfg(not(feature = "rug"))]
+            "rug" | "mpfr" => Err(Error::UnsupportedBackend("requires rug", None)),
+            #[cfg(feature = "fastnum")]
+            "fastnum" | "decimal" => Ok(Type::Fastnum),
+            #[cfg(not(feature = "fastnum"))]
+            "fastnum" | "decimal" => Err(Error::UnsupportedBackend("requires fastnum", None)),
+            "f64" | "double" => Ok(Type::F64),
+            "f32" | "single" => Ok(Type::F32),
+            _ => Err(Error::Setting("bad float type", None)),
+        }
+    }
+}
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Options {
@@ -223,11 +260,12 @@ pub struct Options {
     pub progress: bool,
     pub keep_data_file: bool,
     pub graphing: bool,
+    pub exact: bool,
 }
 impl Default for Options {
     fn default() -> Self {
         Self {
-            float_type: Type::Rug,
+            float_type: Type::default(),
             notation: Normal,
             angle: Radians,
             graphtype: GraphType::Normal,
@@ -246,8 +284,14 @@ impl Default for Options {
             color: Auto::Auto,
             prompt: true,
             comma: false,
+            #[cfg(feature = "rug")]
             prec: 512,
+            #[cfg(not(feature = "rug"))]
+            prec: 53,
+            #[cfg(feature = "rug")]
             graph_prec: 128,
+            #[cfg(not(feature = "rug"))]
+            graph_prec: 53,
             xr: (-8.0, 8.0),
             yr: (-8.0, 8.0),
             zr: (-8.0, 8.0),
@@ -275,9 +319,16 @@ impl Default for Options {
             progress: false,
             keep_data_file: false,
             graphing: false,
+            exact: false,
         }
     }
 }
+impl Options {
+    ///the backend and bits numbers are created with
+    pub fn precision(&self) -> Precision {
+        Precision::new(self.float_type, self.prec)
+    }
+}
 impl Units {
     pub fn is_none(&self) -> bool {
         self.second == 0.0
@@ -841,7 +892,7 @@ pub fn is_unit(unit: &mut String) -> bool {
             }
         })
 }
-pub fn prefixes(mut unit: String, prec: u32) -> (String, Float) {
+pub fn prefixes(mut unit: String, prec: Precision) -> (String, Float) {
     if is_unit(&mut unit) {
         return (unit, Float::with_val(prec, 1));
     }
@@ -1226,8 +1277,12 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
         }
         "steradian" | "sr" => {
             match options.angle {
-                AngleType::Gradians => num *= 40000 / Float::with_val(options.prec, Pi).pow(2),
-                AngleType::Degrees => num *= 32400 / Float::with_val(options.prec, Pi).pow(2),
+                AngleType::Gradians => {
+                    num *= 40000 / Float::with_val(options.precision(), Pi).pow(2)
+                }
+                AngleType::Degrees => {
+                    num *= 32400 / Float::with_val(options.precision(), Pi).pow(2)
+                }
                 AngleType::Radians => {}
             };
             units.angle = 2.0
@@ -1262,8 +1317,12 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
         }
         "lumen" | "lm" => {
             match options.angle {
-                AngleType::Gradians => num *= 40000 / Float::with_val(options.prec, Pi).pow(2),
-                AngleType::Degrees => num *= 32400 / Float::with_val(options.prec, Pi).pow(2),
+                AngleType::Gradians => {
+                    num *= 40000 / Float::with_val(options.precision(), Pi).pow(2)
+                }
+                AngleType::Degrees => {
+                    num *= 32400 / Float::with_val(options.precision(), Pi).pow(2)
+                }
                 AngleType::Radians => {}
             };
             units.angle = 2.0;
@@ -1271,8 +1330,12 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
         }
         "lux" | "lx" => {
             match options.angle {
-                AngleType::Gradians => num *= 40000 / Float::with_val(options.prec, Pi).pow(2),
-                AngleType::Degrees => num *= 32400 / Float::with_val(options.prec, Pi).pow(2),
+                AngleType::Gradians => {
+                    num *= 40000 / Float::with_val(options.precision(), Pi).pow(2)
+                }
+                AngleType::Degrees => {
+                    num *= 32400 / Float::with_val(options.precision(), Pi).pow(2)
+                }
                 AngleType::Radians => {}
             };
             units.angle = 2.0;
@@ -1307,7 +1370,7 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
         }
         "parsec" | "pc" => {
             units.meter = 1.0;
-            num *= 648000 / Float::with_val(options.prec, Pi);
+            num *= 648000 / Float::with_val(options.precision(), Pi);
             num *= 149597870700u64;
         }
         "au" => {
@@ -1357,7 +1420,7 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
                 ..Units::default()
             };
             add = Some(Number::from(
-                Complex::with_val(options.prec, 5463) / 20,
+                Complex::with_val(options.precision(), 5463) / 20,
                 Some(unit),
             ));
         }
@@ -1370,7 +1433,7 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
                 ..Units::default()
             };
             add = Some(Number::from(
-                Complex::with_val(options.prec, 45967) / 180,
+                Complex::with_val(options.precision(), 45967) / 180,
                 Some(unit),
             ));
         }
@@ -1527,7 +1590,7 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
                     num /= 60;
                 }
                 AngleType::Radians => {
-                    num *= Float::with_val(options.prec, Pi) / 180;
+                    num *= Float::with_val(options.precision(), Pi) / 180;
                     num /= 60;
                 }
             };
@@ -1544,7 +1607,7 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
                     num /= 3600;
                 }
                 AngleType::Radians => {
-                    num *= Float::with_val(options.prec, Pi) / 180;
+                    num *= Float::with_val(options.precision(), Pi) / 180;
                     num /= 3600;
                 }
             };
@@ -1557,14 +1620,14 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
                     num *= 200;
                     num /= 180
                 }
-                AngleType::Radians => num *= Float::with_val(options.prec, Pi) / 180,
+                AngleType::Radians => num *= Float::with_val(options.precision(), Pi) / 180,
             };
             units.angle = 1.0;
         }
         "rad" | "radian" => {
             match options.angle {
-                AngleType::Degrees => num *= 180 / Float::with_val(options.prec, Pi),
-                AngleType::Gradians => num *= 200 / Float::with_val(options.prec, Pi),
+                AngleType::Degrees => num *= 180 / Float::with_val(options.precision(), Pi),
+                AngleType::Gradians => num *= 200 / Float::with_val(options.precision(), Pi),
                 AngleType::Radians => {}
             };
             units.angle = 1.0
@@ -1576,7 +1639,7 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
                     num /= 200
                 }
                 AngleType::Gradians => {}
-                AngleType::Radians => num *= Float::with_val(options.prec, Pi) / 200,
+                AngleType::Radians => num *= Float::with_val(options.precision(), Pi) / 200,
             };
             units.angle = 1.0;
         }
@@ -1613,7 +1676,7 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
             units.second = -1.0;
             num *= 132521403;
             num /= 40000000;
-            num /= Float::with_val(options.prec, Pi);
+            num /= Float::with_val(options.precision(), Pi);
             num /= 10000000000000000000000000000000000u128;
         }
         "eV" => {
@@ -1693,7 +1756,7 @@ pub fn to_unit(unit: String, mut num: Float, options: Options) -> (Number, Optio
                         units.usd = 1.0;
                         num *= Float::parse(l.split(' ').next_back().unwrap())
                             .unwrap()
-                            .complete(options.prec);
+                            .complete(options.precision());
                     }
                 }
             }